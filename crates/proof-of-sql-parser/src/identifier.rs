@@ -184,6 +184,7 @@ mod tests {
             "all",
             "asc",
             "desc",
+            "distinct",
             "as",
             "and",
             "from",
@@ -204,6 +205,20 @@ mod tests {
             "false",
             "timestamp",
             "to_timestamp",
+            "join",
+            "inner",
+            "left",
+            "right",
+            "full",
+            "on",
+            "is",
+            "in",
+            "between",
+            "like",
+            "having",
+            "case",
+            "when",
+            "else",
         ];
 
         for keyword in &keywords {
@@ -212,6 +227,26 @@ mod tests {
                 "Should not parse keyword as identifier: {keyword}"
             );
         }
+
+        // Function and type names and keywords that can not be confused with identifiers are not reserved
+        for name in [
+            "outer",
+            "null",
+            "then",
+            "end",
+            "cast",
+            "interval",
+            "length",
+            "starts_with",
+            "extract",
+            "date_trunc",
+            "bigint",
+        ] {
+            assert!(
+                Identifier::from_str(name).is_ok(),
+                "Should parse non-reserved word as identifier: {name}"
+            );
+        }
     }
 
     #[test]
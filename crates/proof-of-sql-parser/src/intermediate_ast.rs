@@ -4,7 +4,10 @@
 * https://docs.rs/vervolg/latest/vervolg/ast/enum.Statement.html
 ***/
 
-use crate::{posql_time::PoSQLTimestamp, Identifier};
+use crate::{
+    posql_time::{PoSQLInterval, PoSQLTimeField, PoSQLTimeUnit, PoSQLTimestamp},
+    Identifier, SelectStatement,
+};
 use alloc::{boxed::Box, string::String, vec::Vec};
 use bigdecimal::BigDecimal;
 use core::{
@@ -15,7 +18,7 @@ use core::{
 use serde::{Deserialize, Serialize};
 
 /// Representation of a `SetExpression`, a collection of rows, each having one or more columns.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum SetExpression {
     /// Query result as `SetExpression`
     Query {
@@ -27,11 +30,19 @@ pub enum SetExpression {
         /// If None, no filter is applied
         where_expr: Option<Box<Expression>>,
         /// Group by expressions e.g. `a` in `SELECT a, COUNT(*) FROM table GROUP BY a`
-        group_by: Vec<Identifier>,
+        group_by: Vec<Box<Expression>>,
+        /// Filter expression on the groups e.g. `SUM(b) > 5` in
+        /// `SELECT a, SUM(b) FROM table GROUP BY a HAVING SUM(b) > 5`
+        /// If None, no filter is applied
+        #[serde(default)]
+        having: Option<Box<Expression>>,
+        /// Whether duplicate rows are removed e.g. `SELECT DISTINCT a FROM table`
+        #[serde(default)]
+        distinct: bool,
     },
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// What to select in a query
 pub enum SelectResultExpr {
     /// All columns in a table e.g. `SELECT * FROM table`
@@ -40,7 +51,7 @@ pub enum SelectResultExpr {
     AliasedResultExpr(AliasedResultExpr),
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// An expression with an alias e.g. `a + 1 AS b`
 pub struct AliasedResultExpr {
     /// The expression e.g. `a + 1`, `COUNT(*)`, etc.
@@ -71,7 +82,7 @@ impl AliasedResultExpr {
 }
 
 /// Representations of base queries
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum TableExpression {
     /// The row set of a given table; possibly providing an alias
     Named {
@@ -79,9 +90,43 @@ pub enum TableExpression {
         table: Identifier,
         /// Namespace / schema for the table
         schema: Option<Identifier>,
+        /// The alias of the table e.g. `t` in `FROM table AS t`
+        #[serde(default)]
+        alias: Option<Identifier>,
+    },
+    /// The join of two table expressions e.g. `a JOIN b ON a.x = b.y`
+    Join {
+        /// The left table expression
+        left: Box<TableExpression>,
+        /// The right table expression
+        right: Box<TableExpression>,
+        /// The kind of join
+        join_type: JoinType,
+        /// The join condition e.g. `a.x = b.y`
+        on: Box<Expression>,
+    },
+    /// The result of a subquery, a derived table e.g. `(SELECT a FROM tab) AS t`
+    Subquery {
+        /// The subquery
+        query: Box<SelectStatement>,
+        /// The mandatory alias of the derived table e.g. `t` in `FROM (SELECT a FROM tab) AS t`
+        alias: Identifier,
     },
 }
 
+/// The kinds of joins
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum JoinType {
+    /// `INNER JOIN` or just `JOIN`
+    Inner,
+    /// `LEFT JOIN` or `LEFT OUTER JOIN`
+    Left,
+    /// `RIGHT JOIN` or `RIGHT OUTER JOIN`
+    Right,
+    /// `FULL JOIN` or `FULL OUTER JOIN`
+    Full,
+}
+
 /// Binary operators for simple expressions
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum BinaryOperator {
@@ -97,6 +142,9 @@ pub enum BinaryOperator {
     /// Numeric division
     Division,
 
+    /// Numeric modulo
+    Modulo,
+
     /// Logical And
     And,
 
@@ -132,6 +180,8 @@ pub enum AggregationOperator {
     Sum,
     /// Count
     Count,
+    /// Count of distinct values
+    CountDistinct,
     /// Return the first value
     First,
 }
@@ -143,6 +193,7 @@ impl Display for AggregationOperator {
             AggregationOperator::Min => write!(f, "min"),
             AggregationOperator::Sum => write!(f, "sum"),
             AggregationOperator::Count => write!(f, "count"),
+            AggregationOperator::CountDistinct => write!(f, "count_distinct"),
             AggregationOperator::First => write!(f, "first"),
         }
     }
@@ -157,6 +208,14 @@ pub enum Expression {
     /// Column
     Column(Identifier),
 
+    /// Column qualified with a table e.g. `t.a`
+    QualifiedColumn {
+        /// The table name or alias
+        table: Identifier,
+        /// The column
+        column: Identifier,
+    },
+
     /// Unary operation
     Unary {
         /// The unary operator
@@ -178,6 +237,12 @@ pub enum Expression {
     /// * expression
     Wildcard,
 
+    /// `IS NULL` expression
+    IsNull(Box<Expression>),
+
+    /// `IS NOT NULL` expression
+    IsNotNull(Box<Expression>),
+
     /// Aggregation operation
     Aggregation {
         /// The aggregation operator
@@ -185,6 +250,100 @@ pub enum Expression {
         /// The expression to aggregate
         expr: Box<Expression>,
     },
+
+    /// `[NOT] IN (subquery)` expression e.g. `a IN (SELECT b FROM tab)`
+    InSubquery {
+        /// The expression to look up in the result of the subquery
+        expr: Box<Expression>,
+        /// The subquery, which returns a single column
+        subquery: Box<SelectStatement>,
+        /// Whether this is `NOT IN`
+        negated: bool,
+    },
+
+    /// `[NOT] IN (list)` expression e.g. `a IN (1, 2, 3)`
+    InList {
+        /// The expression to look up in the list
+        expr: Box<Expression>,
+        /// The values of the list
+        list: Vec<Expression>,
+        /// Whether this is `NOT IN`
+        negated: bool,
+    },
+
+    /// `[NOT] BETWEEN` expression e.g. `a BETWEEN 1 AND 10`
+    Between {
+        /// The expression to compare to the bounds
+        expr: Box<Expression>,
+        /// The inclusive lower bound
+        low: Box<Expression>,
+        /// The inclusive upper bound
+        high: Box<Expression>,
+        /// Whether this is `NOT BETWEEN`
+        negated: bool,
+    },
+
+    /// `[NOT] LIKE` expression e.g. `a LIKE 'abc%'`
+    Like {
+        /// The string to match
+        expr: Box<Expression>,
+        /// The pattern, where `%` matches any sequence of characters and `_` any single character
+        pattern: String,
+        /// Whether this is `NOT LIKE`
+        negated: bool,
+    },
+
+    /// `LENGTH` expression e.g. `LENGTH(a)`, which is the number of characters of a string
+    Length(Box<Expression>),
+
+    /// `STARTS_WITH` expression e.g. `STARTS_WITH(a, 'abc')`
+    StartsWith {
+        /// The string to check
+        expr: Box<Expression>,
+        /// The prefix
+        prefix: String,
+    },
+
+    /// Timestamp plus or minus an interval e.g. `ts + INTERVAL '1 day'`,
+    /// where subtraction adds the negated interval
+    AddInterval {
+        /// The timestamp
+        expr: Box<Expression>,
+        /// The interval to add
+        interval: PoSQLInterval,
+    },
+
+    /// `EXTRACT` expression e.g. `EXTRACT(HOUR FROM ts)`
+    Extract {
+        /// The field to extract
+        field: PoSQLTimeField,
+        /// The timestamp
+        expr: Box<Expression>,
+    },
+
+    /// `DATE_TRUNC` expression e.g. `DATE_TRUNC('day', ts)`
+    DateTrunc {
+        /// The field to truncate to
+        field: PoSQLTimeField,
+        /// The timestamp
+        expr: Box<Expression>,
+    },
+
+    /// `CAST` expression e.g. `CAST(a AS DECIMAL(10, 2))`
+    Cast {
+        /// The expression to cast
+        expr: Box<Expression>,
+        /// The data type to cast to
+        data_type: DataType,
+    },
+
+    /// `CASE` expression e.g. `CASE WHEN a > 1 THEN 'large' ELSE 'small' END`
+    Case {
+        /// The conditions and results of the `WHEN ... THEN ...` branches, in order
+        when_then: Vec<(Expression, Expression)>,
+        /// The result of the `ELSE` branch, if any
+        else_expr: Option<Box<Expression>>,
+    },
 }
 
 impl Expression {
@@ -224,6 +383,15 @@ impl Expression {
         })
     }
 
+    /// Create a new `COUNT(DISTINCT)`
+    #[must_use]
+    pub fn count_distinct(self) -> Box<Self> {
+        Box::new(Expression::Aggregation {
+            op: AggregationOperator::CountDistinct,
+            expr: Box::new(self),
+        })
+    }
+
     /// Create a new `FIRST()`
     #[must_use]
     pub fn first(self) -> Box<Self> {
@@ -290,8 +458,32 @@ impl core::ops::Sub<Box<Expression>> for Box<Expression> {
     }
 }
 
+/// Data types that an expression can be cast to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DataType {
+    /// `BOOLEAN`
+    Boolean,
+    /// `TINYINT`
+    TinyInt,
+    /// `SMALLINT`
+    SmallInt,
+    /// `INT`
+    Int,
+    /// `BIGINT`
+    BigInt,
+    /// `DECIMAL(precision, scale)`
+    Decimal {
+        /// The total number of digits
+        precision: u8,
+        /// The number of digits after the decimal point
+        scale: u8,
+    },
+    /// `TIMESTAMP(precision)`
+    Timestamp(PoSQLTimeUnit),
+}
+
 /// `OrderBy`
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct OrderBy {
     /// which column to order by
     pub expr: Identifier,
@@ -300,7 +492,7 @@ pub struct OrderBy {
 }
 
 /// `OrderByDirection` values
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum OrderByDirection {
     /// Ascending
     Asc,
@@ -319,7 +511,7 @@ impl Display for OrderByDirection {
 }
 
 /// Limits for a limit clause
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Slice {
     /// number of rows to return
     ///
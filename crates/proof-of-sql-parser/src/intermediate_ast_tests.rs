@@ -1,5 +1,9 @@
 use crate::{
-    intermediate_ast::OrderByDirection::{Asc, Desc},
+    intermediate_ast::{
+        DataType, JoinType,
+        OrderByDirection::{Asc, Desc},
+    },
+    posql_time::{PoSQLInterval, PoSQLTimeField, PoSQLTimeUnit},
     sql::*,
     utility::*,
     SelectStatement,
@@ -386,6 +390,24 @@ fn we_can_parse_a_query_with_one_logical_not_filter_expression() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_query_with_is_null_and_is_not_null_filter_expressions() {
+    let ast = "select a from sxt_tab where not b = 3 is null and c is not null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(not(is_null(equal(col("b"), lit(3)))), is_not_null(col("c"))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_query_with_one_logical_and_filter_expression() {
     let ast = "select a from sxt_tab where (b = 3) and c"
@@ -490,6 +512,27 @@ fn we_can_parse_a_query_with_three_logical_not_and_or_filter_expressions() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_modulo_with_the_precedence_of_multiplication_and_division() {
+    let ast = "select a from sxt_tab where b + c % 3 * d = a / 2 % 4"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(
+                col("b") + modulo(col("c"), lit(3)) * col("d"),
+                modulo(col("a") / lit(2), lit(4)),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_query_with_the_minimum_i128_value_as_the_equal_filter_literal() {
     let ast = ("select a from sxt_tab where b = ".to_owned() + &i128::MIN.to_string())
@@ -842,15 +885,29 @@ fn we_cannot_parse_a_query_with_select_tablename_followed_by_star() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_schemas_followed_by_column_and_table_names() {
-    assert!("select tab.a from tab".parse::<SelectStatement>().is_err());
-    assert!("select tab.a from eth.tab;"
+fn we_can_parse_a_query_with_columns_qualified_by_table_names() {
+    let ast = "select tab.a from eth.tab where tab.b = 3;"
         .parse::<SelectStatement>()
-        .is_err());
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(qualified_col("tab", "a"), "a")],
+            tab(Some("eth"), "tab"),
+            equal(qualified_col("tab", "b"), lit(3)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_schemas_followed_by_column_and_table_names() {
     assert!("select eth.tab.a from eth.tab"
         .parse::<SelectStatement>()
         .is_err());
-    assert!("select a from eth.tab where tab.b = 3;"
+    assert!("select a from eth.tab where eth.tab.b = 3;"
         .parse::<SelectStatement>()
         .is_err());
 }
@@ -1033,22 +1090,542 @@ fn we_can_parse_a_query_with_filter_gt() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_inner_join_keyword() {
-    assert!(
-        "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4;"
+fn we_can_parse_a_query_with_a_join() {
+    let ast = "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4;"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(qualified_col("tab1", "a"), "a")],
+            join(
+                tab(None, "tab1"),
+                tab(None, "tab2"),
+                JoinType::Inner,
+                equal(qualified_col("tab1", "c"), qualified_col("tab2", "c")),
+            ),
+            gt(qualified_col("tab2", "b"), lit(4)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+
+    let inner_join_ast =
+        "select tab1.a from tab1 inner join tab2 on tab1.c = tab2.c where tab2.b > 4;"
             .parse::<SelectStatement>()
-            .is_err()
+            .unwrap();
+    assert_eq!(inner_join_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_left_join_and_table_aliases() {
+    let ast =
+        "select t.a, u.b as ub from eth.tab1 as t left join tab2 u on t.c = u.c and t.d = u.d"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(qualified_col("t", "a"), "a"),
+                col_res(qualified_col("u", "b"), "ub"),
+            ],
+            join(
+                aliased_tab(Some("eth"), "tab1", "t"),
+                aliased_tab(None, "tab2", "u"),
+                JoinType::Left,
+                and(
+                    equal(qualified_col("t", "c"), qualified_col("u", "c")),
+                    equal(qualified_col("t", "d"), qualified_col("u", "d")),
+                ),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
     );
+    assert_eq!(ast, expected_ast);
+
+    let left_outer_join_ast =
+        "select t.a, u.b as ub from eth.tab1 t left outer join tab2 as u on t.c = u.c and t.d = u.d"
+            .parse::<SelectStatement>()
+            .unwrap();
+    assert_eq!(left_outer_join_ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_multiple_joins() {
+    let ast = "select a from tab1 join tab2 on c = d left join tab3 on e = f"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["a"]),
+            join(
+                join(
+                    tab(None, "tab1"),
+                    tab(None, "tab2"),
+                    JoinType::Inner,
+                    equal(col("c"), col("d")),
+                ),
+                tab(None, "tab3"),
+                JoinType::Left,
+                equal(col("e"), col("f")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_a_join_without_a_join_condition() {
+    assert!("select a from tab1 join tab2"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab1 left join tab2 where a = 1"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_right_or_full_join() {
+    for (query, join_type) in [
+        (
+            "select a from tab1 right join tab2 on a = b",
+            JoinType::Right,
+        ),
+        (
+            "select a from tab1 RIGHT OUTER JOIN tab2 on a = b",
+            JoinType::Right,
+        ),
+        ("select a from tab1 full join tab2 on a = b", JoinType::Full),
+        (
+            "select a from tab1 full outer join tab2 on a = b",
+            JoinType::Full,
+        ),
+    ] {
+        let expected_ast = select(
+            query_all(
+                cols_res(&["a"]),
+                join(
+                    tab(None, "tab1"),
+                    tab(None, "tab2"),
+                    join_type,
+                    equal(col("a"), col("b")),
+                ),
+                vec![],
+            ),
+            vec![],
+            None,
+        );
+        assert_eq!(query.parse::<SelectStatement>().unwrap(), expected_ast);
+    }
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_subquery_in_from() {
+    let ast = "select t.a, b from (select a, b from tab where c = 1) as t where b > 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(qualified_col("t", "a"), "a"),
+                col_res(col("b"), "b"),
+            ],
+            subquery_tab(
+                select(
+                    query(
+                        cols_res(&["a", "b"]),
+                        tab(None, "tab"),
+                        equal(col("c"), lit(1)),
+                        vec![],
+                    ),
+                    vec![],
+                    None,
+                ),
+                "t",
+            ),
+            gt(col("b"), lit(2)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast_without_as = "select t.a, b from (select a, b from tab where c = 1) t where b > 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast_without_as, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_in_and_not_in_subqueries() {
+    let ast = "select a from tab where b in (select b from eth.tab2 order by b limit 2) and not c not in (select d from tab3)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                in_subquery(
+                    col("b"),
+                    select(
+                        query_all(cols_res(&["b"]), tab(Some("eth"), "tab2"), vec![]),
+                        order("b", Asc),
+                        slice(2, 0),
+                    ),
+                ),
+                not(not_in_subquery(
+                    col("c"),
+                    select(
+                        query_all(cols_res(&["d"]), tab(None, "tab3"), vec![]),
+                        vec![],
+                        None,
+                    ),
+                )),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_a_subquery_in_from_without_an_alias() {
+    assert!("select a from (select a from tab)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab where a in (select a from tab;)"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_distinct() {
+    let ast = "SELECT DISTINCT a, b FROM tab WHERE c = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        distinct(query(
+            cols_res(&["a", "b"]),
+            tab(None, "tab"),
+            equal(col("c"), lit(1)),
+            vec![],
+        )),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_count_distinct() {
+    let ast = "select a, count(distinct b) as n, count(b) from tab group by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(count_distinct(col("b")), "n"),
+                col_res(count(col("b")), "__count__"),
+            ],
+            tab(None, "tab"),
+            group_by(&["a"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_distinct_in_the_wrong_place() {
+    assert!("select a distinct from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select count(distinct *) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(distinct a) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
 }
 
 // Case when
 #[test]
-fn we_cannot_parse_a_query_with_case_when_keyword() {
-    assert!(
-        "select case when a == 2 then 3 else 5 from tab where b <= 4;"
-            .parse::<SelectStatement>()
-            .is_err()
+fn we_can_parse_a_query_with_case_when() {
+    let ast = "select case when a > 1000 then 'large' when a > 100 then 'medium' else 'small' end as size, case b when 1 then 2 end as c from tab where case when c then d else false end"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(
+                    case_when(
+                        vec![
+                            (gt(col("a"), lit(1000)), lit("large")),
+                            (gt(col("a"), lit(100)), lit("medium")),
+                        ],
+                        Some(lit("small")),
+                    ),
+                    "size",
+                ),
+                col_res(
+                    case_when(vec![(equal(col("b"), lit(1)), lit(2))], None),
+                    "c",
+                ),
+            ],
+            tab(None, "tab"),
+            case_when(vec![(col("c"), col("d"))], Some(lit(false))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_an_invalid_case_when() {
+    for query in [
+        "select case when a == 2 then 3 else 5 from tab where b <= 4;",
+        "select case when a = 2 then 3 else 5 from tab",
+        "select case else 5 end from tab",
+        "select case when a = 2 end from tab",
+        "select case when a = 2 then 3 else 4 else 5 end from tab",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err());
+    }
+}
+
+// In list and between
+#[test]
+fn we_can_parse_a_query_with_in_lists_and_between() {
+    let ast = "select a from tab where b in (1, 2, 3) and c not in ('x') and d between 1 and e + 1 and not f not between -1 and 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                and(
+                    and(
+                        in_list(col("b"), vec![lit(1), lit(2), lit(3)]),
+                        not_in_list(col("c"), vec![lit("x")]),
+                    ),
+                    between(col("d"), lit(1), add(col("e"), lit(1))),
+                ),
+                not(not_between(col("f"), lit(-1), lit(1))),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_an_invalid_in_list_or_between() {
+    for query in [
+        "select a from tab where b in ()",
+        "select a from tab where b in (1, 2,)",
+        "select a from tab where b between 1",
+        "select a from tab where b between and 2",
+        "select a from tab where b not between 1 or 2",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err());
+    }
+}
+
+#[test]
+fn we_can_parse_a_query_with_string_operations() {
+    let ast = "select length(a) as l, CHAR_LENGTH(b) as m from tab where a like 'ab%' and b not like 'a_c' and starts_with(c, 'x') and d >= 'y'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(length(col("a")), "l"),
+                col_res(length(col("b")), "m"),
+            ],
+            tab(None, "tab"),
+            and(
+                and(
+                    and(like(col("a"), "ab%"), not_like(col("b"), "a_c")),
+                    starts_with(col("c"), "x"),
+                ),
+                ge(col("d"), lit("y")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
     );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_function_names_as_identifiers() {
+    let ast = "select length from tab where starts_with = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["length"]),
+            tab(None, "tab"),
+            equal(col("starts_with"), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_invalid_string_operations() {
+    for query in [
+        "select a from tab where b like c",
+        "select a from tab where b like",
+        "select lower(a) from tab",
+        "select length(a, 'b') from tab",
+        "select a from tab where starts_with(b)",
+        "select a from tab where starts_with(b, c)",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err());
+    }
+}
+
+#[test]
+fn we_can_parse_a_query_with_timestamp_operations() {
+    let ast = "select date_trunc('day', ts + interval '1 hour') as d, EXTRACT(dow FROM ts) as w from tab where ts - INTERVAL '2 days 30 minutes' > a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(
+                    date_trunc(
+                        PoSQLTimeField::Day,
+                        add_interval(col("ts"), PoSQLInterval::new(3_600_000_000_000)),
+                    ),
+                    "d",
+                ),
+                col_res(extract(PoSQLTimeField::DayOfWeek, col("ts")), "w"),
+            ],
+            tab(None, "tab"),
+            gt(
+                add_interval(col("ts"), PoSQLInterval::new(-174_600_000_000_000)),
+                col("a"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_function_arguments_of_any_precedence() {
+    let ast = "select cast(a + 1 as bigint) as a, length(b) * 2 as l, case c - 1 when 0 then d or e end as c from tab where starts_with(b, 'x') and f"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(cast(add(col("a"), lit(1)), DataType::BigInt), "a"),
+                col_res(mul(length(col("b")), lit(2)), "l"),
+                col_res(
+                    case_when(
+                        vec![(equal(sub(col("c"), lit(1)), lit(0)), or(col("d"), col("e")))],
+                        None,
+                    ),
+                    "c",
+                ),
+            ],
+            tab(None, "tab"),
+            and(starts_with(col("b"), "x"), col("f")),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_cast_and_interval_as_identifiers() {
+    let ast = "select cast(cast as bigint) as interval from tab where interval + interval '1 hour' > cast"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(cast(col("cast"), DataType::BigInt), "interval")],
+            tab(None, "tab"),
+            gt(
+                add_interval(col("interval"), PoSQLInterval::new(3_600_000_000_000)),
+                col("cast"),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_keywords_that_can_not_be_confused_with_identifiers_as_identifiers() {
+    let ast = "select case when outer is null then then else end end as end from tab left outer join null on outer = id where then is not null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(
+                case_when(vec![(is_null(col("outer")), col("then"))], Some(col("end"))),
+                "end",
+            )],
+            join(
+                tab(None, "tab"),
+                tab(None, "null"),
+                JoinType::Left,
+                equal(col("outer"), col("id")),
+            ),
+            is_not_null(col("then")),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_invalid_timestamp_operations() {
+    for query in [
+        "select ts + interval '1 month' as a from tab",
+        "select ts + interval 1 as a from tab",
+        "select interval '1 day' as a from tab",
+        "select extract(year from ts) as a from tab",
+        "select extract('hour' from ts) as a from tab",
+        "select date_trunc('month', ts) as a from tab",
+        "select date_trunc(ts, 'day') as a from tab",
+        "select date_trunc(day, ts) as a from tab",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err(), "{query}");
+    }
 }
 
 //////////////////////
@@ -1136,6 +1713,74 @@ fn we_can_parse_a_simple_group_by_clause_with_multiple_columns() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_group_by_clause_with_expressions() {
+    let ast = "select a + b as s, count(*) as c from tab group by a + b, date_trunc('day', t), d;"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(add(col("a"), col("b")), "s"), count_all_res("c")],
+            tab(None, "tab"),
+            vec![
+                add(col("a"), col("b")),
+                date_trunc(PoSQLTimeField::Day, col("t")),
+                col("d"),
+            ],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_group_by_clause_with_having() {
+    let ast = "select a, sum(b) as s from tab where c = 1 group by a having sum(b) > 100 and count(*) >= 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query(
+                vec![col_res(col("a"), "a"), col_res(sum(col("b")), "s")],
+                tab(None, "tab"),
+                equal(col("c"), lit(1)),
+                group_by(&["a"]),
+            ),
+            and(gt(sum(col("b")), lit(100)), ge(count_all(), lit(2))),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_having_without_group_by() {
+    let ast = "SELECT SUM(a) AS s FROM tab HAVING SUM(a) > 1 ORDER BY s LIMIT 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query_all(vec![col_res(sum(col("a")), "s")], tab(None, "tab"), vec![]),
+            gt(sum(col("a")), lit(1)),
+        ),
+        order("s", Asc),
+        slice(1, 0),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_having_before_group_by() {
+    assert!("select a from tab having sum(b) > 1 group by a"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab group by a having"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_can_parse_a_simple_group_by_clause_using_the_wildcard() {
     let ast = "select * from tab group by a"
@@ -1439,3 +2084,84 @@ fn we_can_use_aggregation_inside_another_aggregation() {
     );
     assert_eq!(ast, expected_ast);
 }
+
+#[test]
+fn we_can_parse_a_query_with_casts() {
+    let ast = "select cast(a as bigint) as a, CAST(b AS Decimal(10, 2)) as b, cast(c as numeric(5)) as c, cast(d as bool) as d, cast(e as timestamp) as e, cast(f as TIMESTAMP(3)) as f from tab where cast(g as int) = cast(h as smallint)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(cast(col("a"), DataType::BigInt), "a"),
+                col_res(
+                    cast(
+                        col("b"),
+                        DataType::Decimal {
+                            precision: 10,
+                            scale: 2,
+                        },
+                    ),
+                    "b",
+                ),
+                col_res(
+                    cast(
+                        col("c"),
+                        DataType::Decimal {
+                            precision: 5,
+                            scale: 0,
+                        },
+                    ),
+                    "c",
+                ),
+                col_res(cast(col("d"), DataType::Boolean), "d"),
+                col_res(
+                    cast(col("e"), DataType::Timestamp(PoSQLTimeUnit::Second)),
+                    "e",
+                ),
+                col_res(
+                    cast(col("f"), DataType::Timestamp(PoSQLTimeUnit::Millisecond)),
+                    "f",
+                ),
+            ],
+            tab(None, "tab"),
+            equal(
+                cast(col("g"), DataType::Int),
+                cast(col("h"), DataType::SmallInt),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_type_names_as_identifiers() {
+    let ast = "select int, decimal from bigint"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(cols_res(&["int", "decimal"]), tab(None, "bigint"), vec![]),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_query_with_an_invalid_cast() {
+    for query in [
+        "select cast(a as varchar) from tab",
+        "select cast(a as int(3)) from tab",
+        "select cast(a as decimal) from tab",
+        "select cast(a as decimal(1000, 2)) from tab",
+        "select cast(a as decimal(10, 2, 1)) from tab",
+        "select cast(a as timestamp(2)) from tab",
+        "select cast(a) from tab",
+        "select cast(a bigint) from tab",
+    ] {
+        assert!(query.parse::<SelectStatement>().is_err());
+    }
+}
@@ -52,6 +52,21 @@ pub enum PoSQLTimestampError {
         /// The underlying error
         error: String,
     },
+
+    /// Represents a failure to parse an interval, which must consist of integer quantities
+    /// of units of a fixed length
+    #[snafu(display("Invalid interval: {error}"))]
+    InvalidInterval {
+        /// The underlying error
+        error: String,
+    },
+
+    /// Represents a failure to parse a field of a timestamp, which must have a fixed length
+    #[snafu(display("Unsupported time field: {field}"))]
+    UnsupportedTimeField {
+        /// The unsupported field
+        field: String,
+    },
 }
 
 // This exists because TryFrom<DataType> for ColumnType error is String
@@ -0,0 +1,104 @@
+use super::PoSQLTimestampError;
+use alloc::string::ToString;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// A part of a timestamp that can be extracted with `EXTRACT(field FROM ts)`
+/// or truncated to with `DATE_TRUNC('field', ts)`
+///
+/// Only fields of a fixed length are supported, so months and years are not.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PoSQLTimeField {
+    /// Microseconds
+    Microsecond,
+    /// Milliseconds
+    Millisecond,
+    /// Seconds
+    Second,
+    /// Minutes
+    Minute,
+    /// Hours
+    Hour,
+    /// Days
+    Day,
+    /// Weeks, which start on Mondays
+    Week,
+    /// The day of the week, from Sunday (0) to Saturday (6)
+    DayOfWeek,
+    /// Seconds since 1970-01-01 00:00:00 UTC
+    Epoch,
+}
+
+impl TryFrom<&str> for PoSQLTimeField {
+    type Error = PoSQLTimestampError;
+
+    fn try_from(value: &str) -> Result<Self, PoSQLTimestampError> {
+        match value.to_lowercase().as_str() {
+            "microsecond" | "microseconds" => Ok(Self::Microsecond),
+            "millisecond" | "milliseconds" => Ok(Self::Millisecond),
+            "second" | "seconds" => Ok(Self::Second),
+            "minute" | "minutes" => Ok(Self::Minute),
+            "hour" | "hours" => Ok(Self::Hour),
+            "day" | "days" => Ok(Self::Day),
+            "week" | "weeks" => Ok(Self::Week),
+            "dow" => Ok(Self::DayOfWeek),
+            "epoch" => Ok(Self::Epoch),
+            _ => Err(PoSQLTimestampError::UnsupportedTimeField {
+                field: value.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for PoSQLTimeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Microsecond => write!(f, "microsecond"),
+            Self::Millisecond => write!(f, "millisecond"),
+            Self::Second => write!(f, "second"),
+            Self::Minute => write!(f, "minute"),
+            Self::Hour => write!(f, "hour"),
+            Self::Day => write!(f, "day"),
+            Self::Week => write!(f, "week"),
+            Self::DayOfWeek => write!(f, "dow"),
+            Self::Epoch => write!(f, "epoch"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_parse_and_display_time_fields() {
+        for field in [
+            PoSQLTimeField::Microsecond,
+            PoSQLTimeField::Millisecond,
+            PoSQLTimeField::Second,
+            PoSQLTimeField::Minute,
+            PoSQLTimeField::Hour,
+            PoSQLTimeField::Day,
+            PoSQLTimeField::Week,
+            PoSQLTimeField::DayOfWeek,
+            PoSQLTimeField::Epoch,
+        ] {
+            assert_eq!(
+                PoSQLTimeField::try_from(field.to_string().as_str()),
+                Ok(field)
+            );
+        }
+        assert_eq!(PoSQLTimeField::try_from("HOURS"), Ok(PoSQLTimeField::Hour));
+    }
+
+    #[test]
+    fn we_cannot_parse_time_fields_without_a_fixed_length() {
+        for value in ["month", "quarter", "year", "doy", ""] {
+            assert!(matches!(
+                PoSQLTimeField::try_from(value),
+                Err(PoSQLTimestampError::UnsupportedTimeField { .. })
+            ));
+        }
+    }
+}
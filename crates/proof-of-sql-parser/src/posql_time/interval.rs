@@ -0,0 +1,168 @@
+use super::{PoSQLTimeUnit, PoSQLTimestampError};
+use alloc::{string::ToString, vec::Vec};
+use core::{fmt, ops::Neg};
+use serde::{Deserialize, Serialize};
+
+/// A fixed length of time parsed from an interval literal, e.g. `INTERVAL '1 day 12 hours'`
+///
+/// Months and years have no fixed length and are not supported.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Clone, Copy, Hash, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PoSQLInterval {
+    nanoseconds: i64,
+}
+
+impl PoSQLInterval {
+    /// Create an interval from a count of nanoseconds
+    #[must_use]
+    pub const fn new(nanoseconds: i64) -> Self {
+        Self { nanoseconds }
+    }
+
+    /// Get the length of the interval in nanoseconds
+    #[must_use]
+    pub const fn nanoseconds(self) -> i64 {
+        self.nanoseconds
+    }
+
+    /// Get the length of the interval as a count of `timeunit`,
+    /// or `None` if the interval is not a whole number of `timeunit`.
+    #[must_use]
+    pub fn count_of(self, timeunit: PoSQLTimeUnit) -> Option<i64> {
+        let nanoseconds_per_unit = 10_i64.pow(9 - u32::try_from(u64::from(timeunit)).ok()?);
+        (self.nanoseconds % nanoseconds_per_unit == 0)
+            .then_some(self.nanoseconds / nanoseconds_per_unit)
+    }
+}
+
+impl Neg for PoSQLInterval {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.nanoseconds)
+    }
+}
+
+/// The length of one of the units an interval can be written in, in nanoseconds
+fn unit_nanoseconds(unit: &str) -> Option<i64> {
+    let unit = unit.to_lowercase();
+    match unit.strip_suffix('s').unwrap_or(&unit) {
+        "nanosecond" => Some(1),
+        "microsecond" => Some(1_000),
+        "millisecond" => Some(1_000_000),
+        "second" => Some(1_000_000_000),
+        "minute" => Some(60_000_000_000),
+        "hour" => Some(3_600_000_000_000),
+        "day" => Some(86_400_000_000_000),
+        "week" => Some(604_800_000_000_000),
+        _ => None,
+    }
+}
+
+impl TryFrom<&str> for PoSQLInterval {
+    type Error = PoSQLTimestampError;
+
+    /// Parses a sequence of integer quantities followed by their units, e.g. `1 day -2 hours`.
+    ///
+    /// The units are nanoseconds, microseconds, milliseconds, seconds, minutes, hours, days
+    /// and weeks, in the singular or the plural.
+    fn try_from(value: &str) -> Result<Self, PoSQLTimestampError> {
+        let invalid = || PoSQLTimestampError::InvalidInterval {
+            error: value.to_string(),
+        };
+        let parts = value.split_whitespace().collect::<Vec<_>>();
+        if parts.is_empty() || parts.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        parts
+            .chunks_exact(2)
+            .try_fold(0_i64, |nanoseconds, part| {
+                let quantity = part[0].parse::<i64>().ok()?;
+                quantity
+                    .checked_mul(unit_nanoseconds(part[1])?)?
+                    .checked_add(nanoseconds)
+            })
+            .map(Self::new)
+            .ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for PoSQLInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} nanoseconds", self.nanoseconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn we_can_parse_intervals() {
+        assert_eq!(
+            PoSQLInterval::try_from("1 day"),
+            Ok(PoSQLInterval::new(86_400_000_000_000))
+        );
+        assert_eq!(
+            PoSQLInterval::try_from(" 2 Hours  -30 minutes "),
+            Ok(PoSQLInterval::new(5_400_000_000_000))
+        );
+        assert_eq!(
+            PoSQLInterval::try_from("1 week 1 second 1 millisecond 1 microsecond 1 nanosecond"),
+            Ok(PoSQLInterval::new(604_801_001_001_001))
+        );
+        assert_eq!(
+            -PoSQLInterval::try_from("3 seconds").unwrap(),
+            PoSQLInterval::new(-3_000_000_000)
+        );
+    }
+
+    #[test]
+    fn we_cannot_parse_invalid_intervals() {
+        for value in [
+            "",
+            "1",
+            "day",
+            "1 day 2",
+            "1.5 days",
+            "1 month",
+            "1 year",
+            "1000000 weeks",
+        ] {
+            assert!(
+                matches!(
+                    PoSQLInterval::try_from(value),
+                    Err(PoSQLTimestampError::InvalidInterval { .. })
+                ),
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_count_of_a_time_unit_in_an_interval() {
+        let interval = PoSQLInterval::try_from("1500 milliseconds").unwrap();
+        assert_eq!(interval.count_of(PoSQLTimeUnit::Second), None);
+        assert_eq!(interval.count_of(PoSQLTimeUnit::Millisecond), Some(1_500));
+        assert_eq!(
+            interval.count_of(PoSQLTimeUnit::Microsecond),
+            Some(1_500_000)
+        );
+        assert_eq!(
+            interval.count_of(PoSQLTimeUnit::Nanosecond),
+            Some(1_500_000_000)
+        );
+        assert_eq!(
+            (-interval).count_of(PoSQLTimeUnit::Millisecond),
+            Some(-1_500)
+        );
+    }
+
+    #[test]
+    fn we_can_display_an_interval() {
+        assert_eq!(
+            PoSQLInterval::try_from("1 second").unwrap().to_string(),
+            "1000000000 nanoseconds"
+        );
+    }
+}
@@ -1,6 +1,12 @@
 mod error;
 /// Errors related to time operations, including timezone and timestamp conversions.
 pub use error::PoSQLTimestampError;
+mod field;
+/// Defines the fields of a timestamp that can be extracted or truncated to
+pub use field::PoSQLTimeField;
+mod interval;
+/// Defines a fixed length of time
+pub use interval::PoSQLInterval;
 mod timestamp;
 /// Defines an RFC3339-formatted timestamp
 pub use timestamp::PoSQLTimestamp;
@@ -1,11 +1,11 @@
-use super::intermediate_ast::{OrderBy, SetExpression, Slice, TableExpression};
+use super::intermediate_ast::{Expression, OrderBy, SetExpression, Slice, TableExpression};
 use crate::{sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId};
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use core::{fmt, str::FromStr};
 use serde::{Deserialize, Serialize};
 
 /// Representation of a select statement, that is, the only type of queries allowed.
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Hash)]
 pub struct SelectStatement {
     /// the query expression
     pub expr: Box<SetExpression>,
@@ -44,16 +44,9 @@ impl SelectStatement {
     /// - The vector with all tables referenced by the intermediate ast, encoded as resource ids.
     #[must_use]
     pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
-        let set_expression: &SetExpression = &(self.expr);
-
-        match set_expression {
-            SetExpression::Query {
-                result_exprs: _,
-                from,
-                where_expr: _,
-                group_by: _,
-            } => convert_table_expr_to_resource_id_vector(&from[..], default_schema),
-        }
+        let mut tables = Vec::new();
+        push_select_statement_resource_ids(self, default_schema, &mut tables);
+        tables
     }
 }
 
@@ -69,33 +62,124 @@ impl FromStr for SelectStatement {
     }
 }
 
+/// Pushes the resource ids of all tables referenced by `select_statement`, including those
+/// referenced by its subqueries, to `tables`
+///
 /// # Panics
 ///
-/// This function will panic in the following cases:
-/// - If `ResourceId::try_new` fails to create a valid `ResourceId`,
-///   the `.unwrap()` call will cause a panic.
-fn convert_table_expr_to_resource_id_vector(
-    table_expressions: &[Box<TableExpression>],
+/// This function will panic if `ResourceId::try_new` fails to create a valid `ResourceId`.
+fn push_select_statement_resource_ids(
+    select_statement: &SelectStatement,
     default_schema: Identifier,
-) -> Vec<ResourceId> {
-    let mut tables = Vec::new();
-
-    for table_expression in table_expressions {
-        let table_ref: &TableExpression = table_expression;
-
-        match table_ref {
-            TableExpression::Named { table, schema } => {
-                let schema = schema.as_ref().map_or_else(
-                    || default_schema.name(),
-                    super::identifier::Identifier::as_str,
-                );
+    tables: &mut Vec<ResourceId>,
+) {
+    match select_statement.expr.as_ref() {
+        SetExpression::Query {
+            from,
+            where_expr,
+            having,
+            ..
+        } => {
+            for table_expression in from {
+                push_table_expr_resource_ids(table_expression, default_schema, tables);
+            }
+            for expr in where_expr.iter().chain(having) {
+                push_expr_resource_ids(expr, default_schema, tables);
+            }
+        }
+    }
+}
 
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+/// Pushes the resource ids of all tables referenced by the subqueries in `expr` to `tables`
+fn push_expr_resource_ids(
+    expr: &Expression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match expr {
+        Expression::InSubquery { expr, subquery, .. } => {
+            push_expr_resource_ids(expr, default_schema, tables);
+            push_select_statement_resource_ids(subquery, default_schema, tables);
+        }
+        Expression::Binary { left, right, .. } => {
+            push_expr_resource_ids(left, default_schema, tables);
+            push_expr_resource_ids(right, default_schema, tables);
+        }
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::Aggregation { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Length(expr)
+        | Expression::StartsWith { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. } => {
+            push_expr_resource_ids(expr, default_schema, tables);
+        }
+        Expression::InList { expr, list, .. } => {
+            push_expr_resource_ids(expr, default_schema, tables);
+            for expr in list {
+                push_expr_resource_ids(expr, default_schema, tables);
+            }
+        }
+        Expression::Between {
+            expr, low, high, ..
+        } => {
+            push_expr_resource_ids(expr, default_schema, tables);
+            push_expr_resource_ids(low, default_schema, tables);
+            push_expr_resource_ids(high, default_schema, tables);
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            for (condition, result) in when_then {
+                push_expr_resource_ids(condition, default_schema, tables);
+                push_expr_resource_ids(result, default_schema, tables);
+            }
+            if let Some(else_expr) = else_expr {
+                push_expr_resource_ids(else_expr, default_schema, tables);
             }
         }
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Wildcard => {}
     }
+}
 
-    tables
+/// Pushes the resource ids of all tables referenced by `table_expression` to `tables`
+///
+/// # Panics
+///
+/// This function will panic if `ResourceId::try_new` fails to create a valid `ResourceId`.
+fn push_table_expr_resource_ids(
+    table_expression: &TableExpression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match table_expression {
+        TableExpression::Named { table, schema, .. } => {
+            let schema = schema.as_ref().map_or_else(
+                || default_schema.name(),
+                super::identifier::Identifier::as_str,
+            );
+
+            tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+        }
+        TableExpression::Join {
+            left, right, on, ..
+        } => {
+            push_table_expr_resource_ids(left, default_schema, tables);
+            push_table_expr_resource_ids(right, default_schema, tables);
+            push_expr_resource_ids(on, default_schema, tables);
+        }
+        TableExpression::Subquery { query, .. } => {
+            push_select_statement_resource_ids(query, default_schema, tables);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -138,4 +222,39 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_join() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT a.X FROM TAB AS a JOIN SCHEMA.TAB2 b ON a.X = b.X")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "tab2").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_subqueries() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT t.X FROM (SELECT X, Y FROM TAB) AS t WHERE t.Y IN (SELECT Y FROM SCHEMA.TAB2) AND t.X NOT IN (SELECT X FROM TAB3)")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "tab2").unwrap(),
+                ResourceId::try_new("eth", "tab3").unwrap()
+            ]
+        );
+    }
 }
@@ -1,20 +1,39 @@
 //! This module exists to adapt the current parser to `sqlparser`.
 use crate::{
     intermediate_ast::{
-        AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator, Expression, Literal,
-        OrderBy as PoSqlOrderBy, OrderByDirection, SelectResultExpr, SetExpression,
-        TableExpression, UnaryOperator as PoSqlUnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator,
+        DataType as PoSqlDataType, Expression, JoinType, Literal, OrderBy as PoSqlOrderBy,
+        OrderByDirection, SelectResultExpr, SetExpression, TableExpression,
+        UnaryOperator as PoSqlUnaryOperator,
     },
     Identifier, ResourceId, SelectStatement,
 };
-use alloc::{boxed::Box, string::ToString, vec};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 use core::fmt::Display;
 use sqlparser::ast::{
-    BinaryOperator, DataType, Expr, Function, FunctionArg, FunctionArgExpr, GroupByExpr, Ident,
-    ObjectName, Offset, OffsetRows, OrderByExpr, Query, Select, SelectItem, SetExpr, TableFactor,
+    BinaryOperator, DataType, Distinct, ExactNumberInfo, Expr, Function, FunctionArg,
+    FunctionArgExpr, GroupByExpr, Ident, Interval, Join, JoinConstraint, JoinOperator, ObjectName,
+    Offset, OffsetRows, OrderByExpr, Query, Select, SelectItem, SetExpr, TableAlias, TableFactor,
     TableWithJoins, TimezoneInfo, UnaryOperator, Value, WildcardAdditionalOptions,
 };
 
+/// Convert a call of the function `name` into a [`Expr`].
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(Function {
+        name: ObjectName(vec![Ident::new(name)]),
+        args: args
+            .into_iter()
+            .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+            .collect(),
+        filter: None,
+        null_treatment: None,
+        over: None,
+        distinct: false,
+        special: false,
+        order_by: vec![],
+    })
+}
+
 /// Convert a number into a [`Expr`].
 fn number<T>(val: T) -> Expr
 where
@@ -43,7 +62,11 @@ impl From<ResourceId> for ObjectName {
 impl From<TableExpression> for TableFactor {
     fn from(table: TableExpression) -> Self {
         match table {
-            TableExpression::Named { table, schema } => {
+            TableExpression::Named {
+                table,
+                schema,
+                alias,
+            } => {
                 let object_name = if let Some(schema) = schema {
                     ObjectName(vec![schema.into(), table.into()])
                 } else {
@@ -51,13 +74,60 @@ impl From<TableExpression> for TableFactor {
                 };
                 TableFactor::Table {
                     name: object_name,
-                    alias: None,
+                    alias: alias.map(|alias| TableAlias {
+                        name: alias.into(),
+                        columns: vec![],
+                    }),
                     args: None,
                     with_hints: vec![],
                     version: None,
                     partitions: vec![],
                 }
             }
+            join @ TableExpression::Join { .. } => TableFactor::NestedJoin {
+                table_with_joins: Box::new(join.into()),
+                alias: None,
+            },
+            TableExpression::Subquery { query, alias } => TableFactor::Derived {
+                lateral: false,
+                subquery: Box::new((*query).into()),
+                alias: Some(TableAlias {
+                    name: alias.into(),
+                    columns: vec![],
+                }),
+            },
+        }
+    }
+}
+
+impl From<TableExpression> for TableWithJoins {
+    fn from(table: TableExpression) -> Self {
+        match table {
+            TableExpression::Join {
+                left,
+                right,
+                join_type,
+                on,
+            } => {
+                let mut table_with_joins = TableWithJoins::from(*left);
+                let constraint = JoinConstraint::On((*on).into());
+                table_with_joins.joins.push(Join {
+                    relation: (*right).into(),
+                    join_operator: match join_type {
+                        JoinType::Inner => JoinOperator::Inner(constraint),
+                        JoinType::Left => JoinOperator::LeftOuter(constraint),
+                        JoinType::Right => JoinOperator::RightOuter(constraint),
+                        JoinType::Full => JoinOperator::FullOuter(constraint),
+                    },
+                });
+                table_with_joins
+            }
+            table @ (TableExpression::Named { .. } | TableExpression::Subquery { .. }) => {
+                TableWithJoins {
+                    relation: table.into(),
+                    joins: vec![],
+                }
+            }
         }
     }
 }
@@ -96,6 +166,7 @@ impl From<PoSqlBinaryOperator> for BinaryOperator {
             PoSqlBinaryOperator::Subtract => BinaryOperator::Minus,
             PoSqlBinaryOperator::Multiply => BinaryOperator::Multiply,
             PoSqlBinaryOperator::Division => BinaryOperator::Divide,
+            PoSqlBinaryOperator::Modulo => BinaryOperator::Modulo,
         }
     }
 }
@@ -108,6 +179,24 @@ impl From<PoSqlUnaryOperator> for UnaryOperator {
     }
 }
 
+impl From<PoSqlDataType> for DataType {
+    fn from(data_type: PoSqlDataType) -> Self {
+        match data_type {
+            PoSqlDataType::Boolean => DataType::Boolean,
+            PoSqlDataType::TinyInt => DataType::TinyInt(None),
+            PoSqlDataType::SmallInt => DataType::SmallInt(None),
+            PoSqlDataType::Int => DataType::Int(None),
+            PoSqlDataType::BigInt => DataType::BigInt(None),
+            PoSqlDataType::Decimal { precision, scale } => DataType::Decimal(
+                ExactNumberInfo::PrecisionAndScale(precision.into(), scale.into()),
+            ),
+            PoSqlDataType::Timestamp(timeunit) => {
+                DataType::Timestamp(Some(timeunit.into()), TimezoneInfo::None)
+            }
+        }
+    }
+}
+
 impl From<PoSqlOrderBy> for OrderByExpr {
     fn from(order_by: PoSqlOrderBy) -> Self {
         let asc = match order_by.direction {
@@ -127,6 +216,9 @@ impl From<Expression> for Expr {
         match expr {
             Expression::Literal(literal) => literal.into(),
             Expression::Column(identifier) => id(identifier),
+            Expression::QualifiedColumn { table, column } => {
+                Expr::CompoundIdentifier(vec![table.into(), column.into()])
+            }
             Expression::Unary { op, expr } => Expr::UnaryOp {
                 op: op.into(),
                 expr: Box::new((*expr).into()),
@@ -137,16 +229,113 @@ impl From<Expression> for Expr {
                 right: Box::new((*right).into()),
             },
             Expression::Wildcard => Expr::Wildcard,
+            Expression::IsNull(expr) => Expr::IsNull(Box::new((*expr).into())),
+            Expression::IsNotNull(expr) => Expr::IsNotNull(Box::new((*expr).into())),
             Expression::Aggregation { op, expr } => Expr::Function(Function {
-                name: ObjectName(vec![Ident::new(op.to_string())]),
+                name: ObjectName(vec![Ident::new(match op {
+                    AggregationOperator::CountDistinct => AggregationOperator::Count.to_string(),
+                    _ => op.to_string(),
+                })]),
                 args: vec![FunctionArg::Unnamed((*expr).into())],
                 filter: None,
                 null_treatment: None,
                 over: None,
-                distinct: false,
+                distinct: op == AggregationOperator::CountDistinct,
                 special: false,
                 order_by: vec![],
             }),
+            Expression::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => Expr::InSubquery {
+                expr: Box::new((*expr).into()),
+                subquery: Box::new((*subquery).into()),
+                negated,
+            },
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => Expr::InList {
+                expr: Box::new((*expr).into()),
+                list: list.into_iter().map(Into::into).collect(),
+                negated,
+            },
+            Expression::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => Expr::Between {
+                expr: Box::new((*expr).into()),
+                negated,
+                low: Box::new((*low).into()),
+                high: Box::new((*high).into()),
+            },
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => Expr::Like {
+                negated,
+                expr: Box::new((*expr).into()),
+                pattern: Box::new(Expr::Value(Value::SingleQuotedString(pattern))),
+                escape_char: None,
+            },
+            Expression::Length(expr) => function("length", vec![(*expr).into()]),
+            Expression::StartsWith { expr, prefix } => function(
+                "starts_with",
+                vec![
+                    (*expr).into(),
+                    Expr::Value(Value::SingleQuotedString(prefix)),
+                ],
+            ),
+            Expression::AddInterval { expr, interval } => Expr::BinaryOp {
+                left: Box::new((*expr).into()),
+                op: BinaryOperator::Plus,
+                right: Box::new(Expr::Interval(Interval {
+                    value: Box::new(Expr::Value(Value::SingleQuotedString(interval.to_string()))),
+                    leading_field: None,
+                    leading_precision: None,
+                    last_field: None,
+                    fractional_seconds_precision: None,
+                })),
+            },
+            Expression::Extract { field, expr } => function(
+                "date_part",
+                vec![
+                    Expr::Value(Value::SingleQuotedString(field.to_string())),
+                    (*expr).into(),
+                ],
+            ),
+            Expression::DateTrunc { field, expr } => function(
+                "date_trunc",
+                vec![
+                    Expr::Value(Value::SingleQuotedString(field.to_string())),
+                    (*expr).into(),
+                ],
+            ),
+            Expression::Cast { expr, data_type } => Expr::Cast {
+                expr: Box::new((*expr).into()),
+                data_type: data_type.into(),
+                format: None,
+            },
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => {
+                let (conditions, results) = when_then
+                    .into_iter()
+                    .map(|(condition, result)| (condition.into(), result.into()))
+                    .unzip();
+                Expr::Case {
+                    operand: None,
+                    conditions,
+                    results,
+                    else_result: else_expr.map(|expr| Box::new((*expr).into())),
+                }
+            }
         }
     }
 }
@@ -188,25 +377,26 @@ impl From<SetExpression> for Select {
                 from,
                 where_expr,
                 group_by,
+                having,
+                distinct,
             } => Select {
-                distinct: None,
+                distinct: distinct.then_some(Distinct::Distinct),
                 top: None,
                 projection: result_exprs.into_iter().map(SelectItem::from).collect(),
                 into: None,
                 from: from
                     .into_iter()
-                    .map(|table_expression| TableWithJoins {
-                        relation: (*table_expression).into(),
-                        joins: vec![],
-                    })
+                    .map(|table_expression| (*table_expression).into())
                     .collect(),
                 lateral_views: vec![],
                 selection: where_expr.map(|expr| (*expr).into()),
-                group_by: GroupByExpr::Expressions(group_by.into_iter().map(id).collect()),
+                group_by: GroupByExpr::Expressions(
+                    group_by.into_iter().map(|expr| (*expr).into()).collect(),
+                ),
                 cluster_by: vec![],
                 distribute_by: vec![],
                 sort_by: vec![],
-                having: None,
+                having: having.map(|expr| (*expr).into()),
                 named_window: vec![],
                 qualify: None,
                 value_table_mode: None,
@@ -268,6 +458,10 @@ mod test {
             "select timestamp '2024-11-07T04:55:12.345+03:00' as time from t;",
             "select timestamp(3) '2024-11-07 01:55:12.345 UTC' as time from t;",
         );
+        check_posql_intermediate_ast_to_sqlparser_equivalence(
+            "select a - interval '1 day' as b, extract(hour from a) as c, date_trunc('Day', a) as d from t;",
+            "select a + interval '-86400000000000 nanoseconds' as b, date_part('hour', a) as c, date_trunc('day', a) as d from t;",
+        );
     }
 
     // Check that PoSQL intermediate AST can be converted to SQL parser AST and that the two are equal.
@@ -299,5 +493,44 @@ mod test {
         check_posql_intermediate_ast_to_sqlparser_equality(
             "select cat as cat, sum(a) as s, count(*) as rows from tab where d = 'Space and Time' group by cat;",
         );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab where b IS NULL and c IS NOT NULL;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select t.a as a, u.b as b from namespace.tab as t join tab2 as u on t.c = u.c;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select t.a as a from tab as t left join tab2 as u on t.c = u.c and t.d = u.d where u.e = 1;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select distinct a as a, b as b from tab where c = 4;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, count(distinct b) as b from tab group by a;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a + b as s, count(*) as c from tab group by a + b, c;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a, sum(b) as s from tab where c = 4 group by a having sum(b) > 100;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select t.a as a from (select a as a, b as b from tab where c = 4) as t where t.b > 2;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab where b in (select b as b from tab2) and c not in (select c as c from tab3);",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select cast(a as bigint) as a, cast(b as decimal(10,2)) as b, cast(c as timestamp(3)) as c from tab where cast(d as int) = 4;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select case when a > 1 then 'large' when a > 0 then 'small' else 'none' end as size, case when b then 1 end as c from tab where case when c = 1 then d else false end;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select a as a from tab where b in (1, 2, 3) and c not in ('x', 'y') and d between 1 and 10 and e not between 2 and 3;",
+        );
+        check_posql_intermediate_ast_to_sqlparser_equality(
+            "select length(a) as l from tab where a like 'ab%' and b not like 'c' and starts_with(c, 'x') and d < 'y';",
+        );
     }
 }
@@ -1,11 +1,13 @@
 use crate::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        OrderByDirection, SelectResultExpr, SetExpression, Slice, TableExpression, UnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator, DataType, Expression, JoinType,
+        Literal, OrderBy, OrderByDirection, SelectResultExpr, SetExpression, Slice,
+        TableExpression, UnaryOperator,
     },
+    posql_time::{PoSQLInterval, PoSQLTimeField},
     Identifier, SelectStatement,
 };
-use alloc::{boxed::Box, vec, vec::Vec};
+use alloc::{boxed::Box, string::ToString, vec, vec::Vec};
 
 ///
 /// # Panics
@@ -76,6 +78,141 @@ pub fn not(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` P IS NULL
+#[must_use]
+pub fn is_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::IsNull(expr))
+}
+
+/// Construct a new boxed `Expression` P IS NOT NULL
+#[must_use]
+pub fn is_not_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::IsNotNull(expr))
+}
+
+/// Construct a new boxed `Expression` P IN (SUBQUERY)
+#[must_use]
+pub fn in_subquery(expr: Box<Expression>, subquery: SelectStatement) -> Box<Expression> {
+    Box::new(Expression::InSubquery {
+        expr,
+        subquery: Box::new(subquery),
+        negated: false,
+    })
+}
+
+/// Construct a new boxed `Expression` P NOT IN (SUBQUERY)
+#[must_use]
+pub fn not_in_subquery(expr: Box<Expression>, subquery: SelectStatement) -> Box<Expression> {
+    Box::new(Expression::InSubquery {
+        expr,
+        subquery: Box::new(subquery),
+        negated: true,
+    })
+}
+
+/// Construct a new boxed `Expression` P IN (LIST)
+#[must_use]
+pub fn in_list(expr: Box<Expression>, list: Vec<Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::InList {
+        expr,
+        list: list.into_iter().map(|expr| *expr).collect(),
+        negated: false,
+    })
+}
+
+/// Construct a new boxed `Expression` P NOT IN (LIST)
+#[must_use]
+pub fn not_in_list(expr: Box<Expression>, list: Vec<Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::InList {
+        expr,
+        list: list.into_iter().map(|expr| *expr).collect(),
+        negated: true,
+    })
+}
+
+/// Construct a new boxed `Expression` P BETWEEN LOW AND HIGH
+#[must_use]
+pub fn between(
+    expr: Box<Expression>,
+    low: Box<Expression>,
+    high: Box<Expression>,
+) -> Box<Expression> {
+    Box::new(Expression::Between {
+        expr,
+        low,
+        high,
+        negated: false,
+    })
+}
+
+/// Construct a new boxed `Expression` P NOT BETWEEN LOW AND HIGH
+#[must_use]
+pub fn not_between(
+    expr: Box<Expression>,
+    low: Box<Expression>,
+    high: Box<Expression>,
+) -> Box<Expression> {
+    Box::new(Expression::Between {
+        expr,
+        low,
+        high,
+        negated: true,
+    })
+}
+
+/// Construct a new boxed `Expression` P LIKE PATTERN
+#[must_use]
+pub fn like(expr: Box<Expression>, pattern: &str) -> Box<Expression> {
+    Box::new(Expression::Like {
+        expr,
+        pattern: pattern.to_string(),
+        negated: false,
+    })
+}
+
+/// Construct a new boxed `Expression` P NOT LIKE PATTERN
+#[must_use]
+pub fn not_like(expr: Box<Expression>, pattern: &str) -> Box<Expression> {
+    Box::new(Expression::Like {
+        expr,
+        pattern: pattern.to_string(),
+        negated: true,
+    })
+}
+
+/// Construct a new boxed `Expression` LENGTH(P)
+#[must_use]
+pub fn length(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Length(expr))
+}
+
+/// Construct a new boxed `Expression` `STARTS_WITH(P, PREFIX)`
+#[must_use]
+pub fn starts_with(expr: Box<Expression>, prefix: &str) -> Box<Expression> {
+    Box::new(Expression::StartsWith {
+        expr,
+        prefix: prefix.to_string(),
+    })
+}
+
+/// Construct a new boxed `Expression` P + INTERVAL
+#[must_use]
+pub fn add_interval(expr: Box<Expression>, interval: PoSQLInterval) -> Box<Expression> {
+    Box::new(Expression::AddInterval { expr, interval })
+}
+
+/// Construct a new boxed `Expression` EXTRACT(FIELD FROM P)
+#[must_use]
+pub fn extract(field: PoSQLTimeField, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Extract { field, expr })
+}
+
+/// Construct a new boxed `Expression` `DATE_TRUNC('FIELD', P)`
+#[must_use]
+pub fn date_trunc(field: PoSQLTimeField, expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::DateTrunc { field, expr })
+}
+
 /// Construct a new boxed `Expression` P AND Q
 #[must_use]
 pub fn and(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
@@ -136,6 +273,37 @@ pub fn div(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A % B
+#[must_use]
+pub fn modulo(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::Modulo,
+        left,
+        right,
+    })
+}
+
+/// Construct a new boxed `Expression` CAST(P AS `data_type`)
+#[must_use]
+pub fn cast(expr: Box<Expression>, data_type: DataType) -> Box<Expression> {
+    Box::new(Expression::Cast { expr, data_type })
+}
+
+/// Construct a new boxed `Expression` CASE WHEN P THEN Q ... ELSE R END
+#[must_use]
+pub fn case_when(
+    when_then: Vec<(Box<Expression>, Box<Expression>)>,
+    else_expr: Option<Box<Expression>>,
+) -> Box<Expression> {
+    Box::new(Expression::Case {
+        when_then: when_then
+            .into_iter()
+            .map(|(condition, result)| (*condition, *result))
+            .collect(),
+        else_expr,
+    })
+}
+
 /// Get table from schema and name.
 ///
 /// If the schema is `None`, the table is assumed to be in the default schema.
@@ -147,6 +315,50 @@ pub fn tab(schema: Option<&str>, name: &str) -> Box<TableExpression> {
     Box::new(TableExpression::Named {
         table: name.parse().unwrap(),
         schema: schema.map(|schema| schema.parse().unwrap()),
+        alias: None,
+    })
+}
+
+/// Get table from schema and name and give it an alias
+///
+/// # Panics
+///
+/// This function will panic if the `name`, the `schema` (if provided) or the `alias` cannot be parsed as valid [Identifier]s.
+#[must_use]
+pub fn aliased_tab(schema: Option<&str>, name: &str, alias: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Named {
+        table: name.parse().unwrap(),
+        schema: schema.map(|schema| schema.parse().unwrap()),
+        alias: Some(alias.parse().unwrap()),
+    })
+}
+
+/// Join two table expressions i.e. `LEFT_TAB [INNER | LEFT] JOIN RIGHT_TAB ON EXPR`
+#[must_use]
+pub fn join(
+    left: Box<TableExpression>,
+    right: Box<TableExpression>,
+    join_type: JoinType,
+    on: Box<Expression>,
+) -> Box<TableExpression> {
+    Box::new(TableExpression::Join {
+        left,
+        right,
+        join_type,
+        on,
+    })
+}
+
+/// Get a derived table from a subquery and its alias i.e. `(SUBQUERY) AS ALIAS`
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn subquery_tab(query: SelectStatement, alias: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Subquery {
+        query: Box::new(query),
+        alias: alias.parse().unwrap(),
     })
 }
 
@@ -160,6 +372,19 @@ pub fn col(name: &str) -> Box<Expression> {
     Box::new(Expression::Column(name.parse().unwrap()))
 }
 
+/// Get column qualified with a table from table and column name
+///
+/// # Panics
+///
+/// This function will panic if the `table` or the `name` cannot be parsed as valid [Identifier]s.
+#[must_use]
+pub fn qualified_col(table: &str, name: &str) -> Box<Expression> {
+    Box::new(Expression::QualifiedColumn {
+        table: table.parse().unwrap(),
+        column: name.parse().unwrap(),
+    })
+}
+
 /// Get literal from value
 pub fn lit<L: Into<Literal>>(literal: L) -> Box<Expression> {
     Box::new(Expression::Literal(literal.into()))
@@ -201,6 +426,15 @@ pub fn count(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Count the distinct non-null entries of expression
+#[must_use]
+pub fn count_distinct(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::CountDistinct,
+        expr,
+    })
+}
+
 /// Count the rows
 #[must_use]
 pub fn count_all() -> Box<Expression> {
@@ -319,13 +553,15 @@ pub fn query(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
     where_expr: Box<Expression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Box<Expression>>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: Some(where_expr),
         group_by,
+        having: None,
+        distinct: false,
     })
 }
 
@@ -336,16 +572,37 @@ pub fn query(
 pub fn query_all(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Box<Expression>>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
         from: vec![tab],
         where_expr: None,
         group_by,
+        having: None,
+        distinct: false,
     })
 }
 
+/// Remove the duplicate rows of a `SetExpression` i.e. turn SELECT ... into SELECT DISTINCT ...
+#[must_use]
+pub fn distinct(mut expr: Box<SetExpression>) -> Box<SetExpression> {
+    let SetExpression::Query { distinct, .. } = &mut *expr;
+    *distinct = true;
+    expr
+}
+
+/// Filter the groups of a `SetExpression` i.e. turn SELECT ... GROUP BY ... into SELECT ... GROUP BY ... HAVING ...
+#[must_use]
+pub fn having(mut expr: Box<SetExpression>, having: Box<Expression>) -> Box<SetExpression> {
+    let SetExpression::Query {
+        having: having_expr,
+        ..
+    } = &mut *expr;
+    *having_expr = Some(having);
+    expr
+}
+
 /// Generate a query of the kind SELECT ... ORDER BY ... [LIMIT ... OFFSET ...]
 ///
 /// Note that `expr` is a boxed `SetExpression`
@@ -408,6 +665,6 @@ pub fn slice(number_rows: u64, offset_value: i64) -> Option<Slice> {
 /// This function will panic if any of the `ids` cannot be parsed
 /// into an identifier.
 #[must_use]
-pub fn group_by(ids: &[&str]) -> Vec<Identifier> {
-    ids.iter().map(|id| id.parse().unwrap()).collect()
+pub fn group_by(ids: &[&str]) -> Vec<Box<Expression>> {
+    ids.iter().map(|id| col(id)).collect()
 }
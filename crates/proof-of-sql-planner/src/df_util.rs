@@ -1,9 +1,12 @@
+use crate::context::PoSqlTableSource;
+use alloc::sync::Arc;
 use arrow::datatypes::{DataType, Field, Schema};
 use datafusion::{
     catalog::TableReference,
     common::{Column, DFSchema},
-    logical_expr::Expr,
+    logical_expr::{Expr, TableSource},
 };
+use proof_of_sql::base::database::ColumnField;
 
 /// Create a `Expr::Column` from full table name and column
 pub(crate) fn df_column(table_name: &str, column: &str) -> Expr {
@@ -25,3 +28,8 @@ pub(crate) fn df_schema(table_name: &str, pairs: Vec<(&str, DataType)>) -> DFSch
     );
     DFSchema::try_from_qualified_schema(table_name, &arrow_schema).unwrap()
 }
+
+/// Create a `TableSource` from the column fields of a table
+pub(crate) fn posql_table_source(column_fields: Vec<ColumnField>) -> Arc<dyn TableSource> {
+    Arc::new(PoSqlTableSource::new(column_fields))
+}
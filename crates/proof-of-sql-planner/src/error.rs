@@ -1,9 +1,10 @@
 use arrow::datatypes::DataType;
 use datafusion::{
     common::DataFusionError,
-    logical_expr::{Expr, Operator},
+    logical_expr::{Expr, LogicalPlan, Operator},
 };
 use proof_of_sql::sql::parse::ConversionError;
+use proof_of_sql_parser::ParseError;
 use snafu::Snafu;
 use sqlparser::parser::ParserError;
 
@@ -28,6 +29,12 @@ pub enum PlannerError {
         /// Underlying datafusion error
         source: DataFusionError,
     },
+    /// Returned when an identifier can not be parsed
+    #[snafu(transparent)]
+    ParseError {
+        /// Underlying parse error
+        source: ParseError,
+    },
     /// Returned when a datatype is not supported
     #[snafu(display("Unsupported datatype: {}", data_type))]
     UnsupportedDataType {
@@ -46,6 +53,12 @@ pub enum PlannerError {
         /// Unsupported logical expression
         expr: Expr,
     },
+    /// Returned when a `LogicalPlan` is not supported
+    #[snafu(display("LogicalPlan {:?} is not supported", plan))]
+    UnsupportedLogicalPlan {
+        /// Unsupported `LogicalPlan`
+        plan: Box<LogicalPlan>,
+    },
     /// Returned when the `LogicalPlan` is not resolved
     #[snafu(display("LogicalPlan is not resolved"))]
     UnresolvedLogicalPlan,
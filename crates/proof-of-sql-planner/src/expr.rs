@@ -1,9 +1,9 @@
 use super::{column_to_column_ref, scalar_value_to_literal_value, PlannerError, PlannerResult};
 use datafusion::{
-    common::DFSchema,
-    logical_expr::{BinaryExpr, Expr, Operator},
+    common::{DFSchema, ScalarValue},
+    logical_expr::{Between, BinaryExpr, Case, Cast, Expr, InList, Like, Operator},
 };
-use proof_of_sql::sql::proof_exprs::DynProofExpr;
+use proof_of_sql::{base::database::ColumnType, sql::proof_exprs::DynProofExpr};
 
 /// Convert an [`datafusion::expr::Expr`] to [`DynProofExpr`]
 ///
@@ -48,6 +48,14 @@ pub fn expr_to_proof_expr(expr: &Expr, schema: &DFSchema) -> PlannerResult<DynPr
                     left_proof_expr,
                     right_proof_expr,
                 )?),
+                Operator::Divide => Ok(DynProofExpr::try_new_divide(
+                    left_proof_expr,
+                    right_proof_expr,
+                )?),
+                Operator::Modulo => Ok(DynProofExpr::try_new_modulo(
+                    left_proof_expr,
+                    right_proof_expr,
+                )?),
                 Operator::And => Ok(DynProofExpr::try_new_and(
                     left_proof_expr,
                     right_proof_expr,
@@ -63,6 +71,95 @@ pub fn expr_to_proof_expr(expr: &Expr, schema: &DFSchema) -> PlannerResult<DynPr
             let proof_expr = expr_to_proof_expr(expr, schema)?;
             Ok(DynProofExpr::try_new_not(proof_expr)?)
         }
+        Expr::Cast(Cast { expr, data_type }) => {
+            let proof_expr = expr_to_proof_expr(expr, schema)?;
+            let to_type = ColumnType::try_from(data_type.clone()).map_err(|_e| {
+                PlannerError::UnsupportedDataType {
+                    data_type: data_type.clone(),
+                }
+            })?;
+            Ok(DynProofExpr::try_new_cast(proof_expr, to_type)?)
+        }
+        Expr::InList(InList {
+            expr: in_list_expr,
+            list,
+            negated,
+        }) => {
+            let proof_expr = expr_to_proof_expr(in_list_expr, schema)?;
+            let values = list
+                .iter()
+                .map(|value| match value {
+                    Expr::Literal(val) => scalar_value_to_literal_value(val.clone()),
+                    _ => Err(PlannerError::UnsupportedLogicalExpression { expr: expr.clone() }),
+                })
+                .collect::<PlannerResult<_>>()?;
+            let in_list = DynProofExpr::try_new_in_list(proof_expr, values)?;
+            Ok(if *negated {
+                DynProofExpr::try_new_not(in_list)?
+            } else {
+                in_list
+            })
+        }
+        Expr::Between(Between {
+            expr: between_expr,
+            negated,
+            low,
+            high,
+        }) => {
+            let between = DynProofExpr::try_new_between(
+                expr_to_proof_expr(between_expr, schema)?,
+                expr_to_proof_expr(low, schema)?,
+                expr_to_proof_expr(high, schema)?,
+            )?;
+            Ok(if *negated {
+                DynProofExpr::try_new_not(between)?
+            } else {
+                between
+            })
+        }
+        Expr::Like(Like {
+            negated,
+            expr: like_expr,
+            pattern,
+            escape_char: None,
+            case_insensitive: false,
+        }) => match pattern.as_ref() {
+            Expr::Literal(ScalarValue::Utf8(Some(pattern))) => {
+                let like =
+                    DynProofExpr::try_new_like(expr_to_proof_expr(like_expr, schema)?, pattern)?;
+                Ok(if *negated {
+                    DynProofExpr::try_new_not(like)?
+                } else {
+                    like
+                })
+            }
+            _ => Err(PlannerError::UnsupportedLogicalExpression { expr: expr.clone() }),
+        },
+        Expr::Case(Case {
+            expr: operand,
+            when_then_expr,
+            else_expr: Some(else_expr),
+        }) => {
+            let operand = operand
+                .as_ref()
+                .map(|operand| expr_to_proof_expr(operand, schema))
+                .transpose()?;
+            let when_then = when_then_expr
+                .iter()
+                .map(|(when, then)| {
+                    let when = expr_to_proof_expr(when, schema)?;
+                    let when = match &operand {
+                        Some(operand) => DynProofExpr::try_new_equals(operand.clone(), when)?,
+                        None => when,
+                    };
+                    Ok((when, expr_to_proof_expr(then, schema)?))
+                })
+                .collect::<PlannerResult<_>>()?;
+            Ok(DynProofExpr::try_new_case(
+                when_then,
+                expr_to_proof_expr(else_expr, schema)?,
+            )?)
+        }
         _ => Err(PlannerError::UnsupportedLogicalExpression { expr: expr.clone() }),
     }
 }
@@ -72,7 +169,7 @@ mod tests {
     use super::*;
     use crate::df_util::*;
     use arrow::datatypes::DataType;
-    use datafusion::{common::ScalarValue, logical_expr::expr::Placeholder};
+    use datafusion::logical_expr::expr::Placeholder;
     use proof_of_sql::base::database::{ColumnRef, ColumnType, LiteralValue, TableRef};
 
     #[expect(non_snake_case)]
@@ -84,6 +181,15 @@ mod tests {
         ))
     }
 
+    #[expect(non_snake_case)]
+    fn COLUMN_VARCHAR() -> DynProofExpr {
+        DynProofExpr::new_column(ColumnRef::new(
+            TableRef::from_names(Some("namespace"), "table_name"),
+            "column".into(),
+            ColumnType::VarChar,
+        ))
+    }
+
     #[expect(non_snake_case)]
     fn COLUMN1_SMALLINT() -> DynProofExpr {
         DynProofExpr::new_column(ColumnRef::new(
@@ -225,6 +331,28 @@ mod tests {
             expr_to_proof_expr(&expr, &schema).unwrap(),
             DynProofExpr::try_new_multiply(COLUMN1_SMALLINT(), COLUMN2_BIGINT(),).unwrap()
         );
+
+        // Divide
+        let expr = Expr::BinaryExpr(BinaryExpr {
+            left: Box::new(df_column("namespace.table_name", "column1")),
+            right: Box::new(df_column("namespace.table_name", "column2")),
+            op: Operator::Divide,
+        });
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_divide(COLUMN1_SMALLINT(), COLUMN2_BIGINT(),).unwrap()
+        );
+
+        // Modulo
+        let expr = Expr::BinaryExpr(BinaryExpr {
+            left: Box::new(df_column("namespace.table_name", "column1")),
+            right: Box::new(df_column("namespace.table_name", "column2")),
+            op: Operator::Modulo,
+        });
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_modulo(COLUMN1_SMALLINT(), COLUMN2_BIGINT(),).unwrap()
+        );
     }
 
     #[test]
@@ -302,6 +430,213 @@ mod tests {
         );
     }
 
+    // Cast
+    #[test]
+    fn we_can_convert_cast_expr_to_proof_expr() {
+        let schema = df_schema("namespace.table_name", vec![("column", DataType::Int32)]);
+        let expr = Expr::Cast(Cast::new(
+            Box::new(df_column("namespace.table_name", "column")),
+            DataType::Int64,
+        ));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_cast(COLUMN_INT(), ColumnType::BigInt).unwrap()
+        );
+
+        // Unsupported target type
+        let expr = Expr::Cast(Cast::new(
+            Box::new(df_column("namespace.table_name", "column")),
+            DataType::Float32,
+        ));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::UnsupportedDataType { .. })
+        ));
+    }
+
+    // InList
+    #[test]
+    fn we_can_convert_in_list_expr_to_proof_expr() {
+        let schema = df_schema("namespace.table_name", vec![("column", DataType::Int32)]);
+        let expr = df_column("namespace.table_name", "column").in_list(
+            vec![
+                Expr::Literal(ScalarValue::Int32(Some(1))),
+                Expr::Literal(ScalarValue::Int32(Some(2))),
+            ],
+            false,
+        );
+        let in_list = DynProofExpr::try_new_in_list(
+            COLUMN_INT(),
+            vec![LiteralValue::Int(1), LiteralValue::Int(2)],
+        )
+        .unwrap();
+        assert_eq!(expr_to_proof_expr(&expr, &schema).unwrap(), in_list);
+
+        // NOT IN
+        let expr = df_column("namespace.table_name", "column").in_list(
+            vec![
+                Expr::Literal(ScalarValue::Int32(Some(1))),
+                Expr::Literal(ScalarValue::Int32(Some(2))),
+            ],
+            true,
+        );
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_not(in_list).unwrap()
+        );
+
+        // Values that are not literals
+        let expr = df_column("namespace.table_name", "column")
+            .in_list(vec![df_column("namespace.table_name", "column")], false);
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
+    // Between
+    #[test]
+    fn we_can_convert_between_expr_to_proof_expr() {
+        let schema = df_schema("namespace.table_name", vec![("column", DataType::Int32)]);
+        let expr = df_column("namespace.table_name", "column").between(
+            Expr::Literal(ScalarValue::Int32(Some(1))),
+            Expr::Literal(ScalarValue::Int32(Some(5))),
+        );
+        let between = DynProofExpr::try_new_between(
+            COLUMN_INT(),
+            DynProofExpr::new_literal(LiteralValue::Int(1)),
+            DynProofExpr::new_literal(LiteralValue::Int(5)),
+        )
+        .unwrap();
+        assert_eq!(expr_to_proof_expr(&expr, &schema).unwrap(), between);
+
+        // NOT BETWEEN
+        let expr = df_column("namespace.table_name", "column").not_between(
+            Expr::Literal(ScalarValue::Int32(Some(1))),
+            Expr::Literal(ScalarValue::Int32(Some(5))),
+        );
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_not(between).unwrap()
+        );
+    }
+
+    // Like
+    #[test]
+    fn we_can_convert_like_expr_to_proof_expr() {
+        let schema = df_schema("namespace.table_name", vec![("column", DataType::Utf8)]);
+        let expr = df_column("namespace.table_name", "column")
+            .like(Expr::Literal(ScalarValue::Utf8(Some("ab%".to_string()))));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_starts_with(COLUMN_VARCHAR(), "ab").unwrap()
+        );
+
+        // NOT LIKE without wildcards
+        let expr = df_column("namespace.table_name", "column")
+            .not_like(Expr::Literal(ScalarValue::Utf8(Some("ab".to_string()))));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_not(
+                DynProofExpr::try_new_equals(
+                    COLUMN_VARCHAR(),
+                    DynProofExpr::new_literal(LiteralValue::VarChar("ab".to_string()))
+                )
+                .unwrap()
+            )
+            .unwrap()
+        );
+
+        // Unsupported patterns
+        let expr = df_column("namespace.table_name", "column")
+            .like(Expr::Literal(ScalarValue::Utf8(Some("%ab".to_string()))));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::ConversionError { .. })
+        ));
+        let expr = df_column("namespace.table_name", "column")
+            .ilike(Expr::Literal(ScalarValue::Utf8(Some("ab%".to_string()))));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
+    // Case
+    #[test]
+    fn we_can_convert_case_expr_to_proof_expr() {
+        let schema = df_schema(
+            "namespace.table_name",
+            vec![("column1", DataType::Int16), ("column2", DataType::Int64)],
+        );
+        let expr = Expr::Case(Case::new(
+            None,
+            vec![(
+                Box::new(
+                    df_column("namespace.table_name", "column1")
+                        .gt(Expr::Literal(ScalarValue::Int16(Some(0)))),
+                ),
+                Box::new(df_column("namespace.table_name", "column1")),
+            )],
+            Some(Box::new(df_column("namespace.table_name", "column2"))),
+        ));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_case(
+                vec![(
+                    DynProofExpr::try_new_inequality(
+                        COLUMN1_SMALLINT(),
+                        DynProofExpr::new_literal(LiteralValue::SmallInt(0)),
+                        false
+                    )
+                    .unwrap(),
+                    COLUMN1_SMALLINT()
+                )],
+                COLUMN2_BIGINT()
+            )
+            .unwrap()
+        );
+
+        // Simple case with an operand
+        let expr = Expr::Case(Case::new(
+            Some(Box::new(df_column("namespace.table_name", "column1"))),
+            vec![(
+                Box::new(Expr::Literal(ScalarValue::Int16(Some(1)))),
+                Box::new(df_column("namespace.table_name", "column2")),
+            )],
+            Some(Box::new(Expr::Literal(ScalarValue::Int64(Some(0))))),
+        ));
+        assert_eq!(
+            expr_to_proof_expr(&expr, &schema).unwrap(),
+            DynProofExpr::try_new_case(
+                vec![(
+                    DynProofExpr::try_new_equals(
+                        COLUMN1_SMALLINT(),
+                        DynProofExpr::new_literal(LiteralValue::SmallInt(1))
+                    )
+                    .unwrap(),
+                    COLUMN2_BIGINT()
+                )],
+                DynProofExpr::new_literal(LiteralValue::BigInt(0))
+            )
+            .unwrap()
+        );
+
+        // Case without an else branch
+        let expr = Expr::Case(Case::new(
+            None,
+            vec![(
+                Box::new(Expr::Literal(ScalarValue::Boolean(Some(true)))),
+                Box::new(df_column("namespace.table_name", "column2")),
+            )],
+            None,
+        ));
+        assert!(matches!(
+            expr_to_proof_expr(&expr, &schema),
+            Err(PlannerError::UnsupportedLogicalExpression { .. })
+        ));
+    }
+
     #[test]
     fn we_cannot_convert_unsupported_logical_expr_to_proof_expr() {
         // Unsupported logical expression
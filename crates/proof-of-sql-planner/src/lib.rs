@@ -10,8 +10,10 @@ mod expr;
 pub use expr::expr_to_proof_expr;
 mod error;
 pub use error::{PlannerError, PlannerResult};
+mod plan;
+pub use plan::logical_plan_to_proof_plan;
 mod util;
 pub(crate) use util::{
-    column_fields_to_schema, column_to_column_ref, scalar_value_to_literal_value,
-    table_reference_to_table_ref,
+    column_fields_to_schema, column_to_column_ref, df_schema_to_column_fields,
+    scalar_value_to_literal_value, table_reference_to_table_ref,
 };
@@ -0,0 +1,1803 @@
+use super::{
+    column_to_column_ref, df_schema_to_column_fields, expr_to_proof_expr,
+    table_reference_to_table_ref, PlannerError, PlannerResult,
+};
+use datafusion::{
+    arrow::datatypes::DataType,
+    common::{Column, DFSchema, JoinType},
+    logical_expr::{
+        expr::{AggregateFunction, AggregateFunctionDefinition, Alias, Sort as SortExpr},
+        Aggregate, AggregateFunction as BuiltinAggregateFunction, Cast, Distinct, EmptyRelation,
+        Expr, ExprSchemable, Filter, Join, Limit, LogicalPlan, Projection, Sort, SubqueryAlias,
+        TableScan, TryCast, Union,
+    },
+};
+use proof_of_sql::{
+    base::database::{try_average_sum_column_type, ColumnField, ColumnRef, LiteralValue, TableRef},
+    sql::{
+        parse::{ConversionError, QueryExpr},
+        postprocessing::{
+            DistinctPostprocessing, OrderByPostprocessing, OwnedTablePostprocessing,
+            SelectPostprocessing, SlicePostprocessing,
+        },
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_plans::{DistinctExec, DynProofPlan, JoinType as ProofJoinType, SortExec},
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{AliasedResultExpr, BinaryOperator, Expression},
+    Identifier,
+};
+use sqlparser::ast::Ident;
+
+/// Convert a [`LogicalPlan`] to a [`QueryExpr`], i.e. a [`DynProofPlan`]
+/// and the [`OwnedTablePostprocessing`] steps to apply on its result
+///
+/// The `LogicalPlan` is expected to be resolved, analyzed and optimized.
+/// Sorts whose input needs postprocessing or that can not be proven are applied in postprocessing
+/// and so is every node above such a sort.
+pub fn logical_plan_to_proof_plan(plan: &LogicalPlan) -> PlannerResult<QueryExpr> {
+    let (proof_plan, postprocessing) = logical_plan_to_proof_plan_with_postprocessing(plan)?;
+    Ok(QueryExpr::new(proof_plan, postprocessing))
+}
+
+/// Convert a [`LogicalPlan`] to a [`DynProofPlan`] and postprocessing steps
+fn logical_plan_to_proof_plan_with_postprocessing(
+    plan: &LogicalPlan,
+) -> PlannerResult<(DynProofPlan, Vec<OwnedTablePostprocessing>)> {
+    match plan {
+        LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: true,
+            ..
+        }) => Ok((DynProofPlan::new_empty(), vec![])),
+        LogicalPlan::TableScan(table_scan) => {
+            Ok((table_scan_to_proof_plan(table_scan, None)?, vec![]))
+        }
+        LogicalPlan::Filter(Filter {
+            predicate, input, ..
+        }) => match input.as_ref() {
+            LogicalPlan::TableScan(table_scan) => Ok((
+                table_scan_to_proof_plan(table_scan, Some(predicate))?,
+                vec![],
+            )),
+            _ => {
+                let (input_plan, postprocessing) =
+                    logical_plan_to_proof_plan_with_postprocessing(input)?;
+                if !postprocessing.is_empty() {
+                    return Err(unsupported_plan(plan));
+                }
+                let schema = input.schema();
+                // The result of the input plan can differ from the result of `input`,
+                // e.g. a join returns only one of each pair of join columns
+                let input_table_ref = input_plan.get_table_references().first().cloned();
+                let aliased_results = input_plan
+                    .get_column_result_fields()
+                    .into_iter()
+                    .map(|field| {
+                        let table_ref = match schema
+                            .columns()
+                            .into_iter()
+                            .find(|column| column.name == field.name().value)
+                        {
+                            Some(column) => column_to_column_ref(&column, schema)?.table_ref(),
+                            None => input_table_ref
+                                .clone()
+                                .ok_or_else(|| unsupported_plan(plan))?,
+                        };
+                        Ok(AliasedDynProofExpr {
+                            expr: DynProofExpr::new_column(ColumnRef::new(
+                                table_ref,
+                                field.name(),
+                                field.data_type(),
+                            )),
+                            alias: field.name(),
+                        })
+                    })
+                    .collect::<PlannerResult<Vec<_>>>()?;
+                Ok((
+                    DynProofPlan::new_filter_with_input(
+                        aliased_results,
+                        input_plan,
+                        expr_to_proof_expr(predicate, schema)?,
+                    ),
+                    vec![],
+                ))
+            }
+        },
+        LogicalPlan::Projection(projection) => projection_to_proof_plan(projection),
+        LogicalPlan::Aggregate(aggregate) => {
+            let output = aggregate
+                .schema
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (index, field.name().clone()))
+                .collect::<Vec<_>>();
+            aggregate_to_proof_plan(aggregate, &output)
+        }
+        LogicalPlan::Limit(Limit {
+            skip, fetch, input, ..
+        }) => {
+            let (input_plan, mut postprocessing) =
+                logical_plan_to_proof_plan_with_postprocessing(input)?;
+            if postprocessing.is_empty() {
+                Ok((DynProofPlan::new_slice(input_plan, *skip, *fetch), vec![]))
+            } else {
+                postprocessing.push(OwnedTablePostprocessing::new_slice(
+                    SlicePostprocessing::new(
+                        fetch.map(|fetch| u64::try_from(fetch).unwrap_or(u64::MAX)),
+                        Some(i64::try_from(*skip).unwrap_or(i64::MAX)),
+                    ),
+                ));
+                Ok((input_plan, postprocessing))
+            }
+        }
+        LogicalPlan::Sort(sort) => sort_to_proof_plan(sort),
+        LogicalPlan::Distinct(Distinct::All(input)) => {
+            let (input_plan, mut postprocessing) =
+                logical_plan_to_proof_plan_with_postprocessing(input)?;
+            let column_types = input_plan
+                .get_column_result_fields()
+                .iter()
+                .map(ColumnField::data_type)
+                .collect::<Vec<_>>();
+            if postprocessing.is_empty() && DistinctExec::can_deduplicate(&column_types) {
+                Ok((DynProofPlan::new_distinct(input_plan), vec![]))
+            } else {
+                postprocessing.push(OwnedTablePostprocessing::new_distinct(
+                    DistinctPostprocessing::new(),
+                ));
+                Ok((input_plan, postprocessing))
+            }
+        }
+        LogicalPlan::Union(Union { inputs, schema, .. }) => {
+            let input_plans = inputs
+                .iter()
+                .map(|input| {
+                    let (input_plan, postprocessing) =
+                        logical_plan_to_proof_plan_with_postprocessing(input)?;
+                    if postprocessing.is_empty() {
+                        Ok(input_plan)
+                    } else {
+                        Err(unsupported_plan(plan))
+                    }
+                })
+                .collect::<PlannerResult<Vec<_>>>()?;
+            Ok((
+                DynProofPlan::new_union(input_plans, df_schema_to_column_fields(schema)?),
+                vec![],
+            ))
+        }
+        LogicalPlan::Join(join) => join_to_proof_plan(join),
+        // An alias only renames the qualifier of the columns of its input
+        LogicalPlan::SubqueryAlias(SubqueryAlias { input, .. }) => {
+            logical_plan_to_proof_plan_with_postprocessing(input)
+        }
+        _ => Err(unsupported_plan(plan)),
+    }
+}
+
+/// Create an [`PlannerError::UnsupportedLogicalPlan`] error from a [`LogicalPlan`]
+fn unsupported_plan(plan: &LogicalPlan) -> PlannerError {
+    PlannerError::UnsupportedLogicalPlan {
+        plan: Box::new(plan.clone()),
+    }
+}
+
+/// Convert a [`Projection`] to a [`DynProofPlan`] and postprocessing steps
+///
+/// A `Projection` directly over an `Aggregate` is merged into the resulting `GroupByExec`.
+fn projection_to_proof_plan(
+    projection: &Projection,
+) -> PlannerResult<(DynProofPlan, Vec<OwnedTablePostprocessing>)> {
+    let unsupported = || PlannerError::UnsupportedLogicalPlan {
+        plan: Box::new(LogicalPlan::Projection(projection.clone())),
+    };
+    let Projection {
+        expr,
+        input,
+        schema,
+        ..
+    } = projection;
+    if let LogicalPlan::Aggregate(aggregate) = input.as_ref() {
+        let output = expr
+            .iter()
+            .enumerate()
+            .map(|(index, e)| {
+                let column = match e {
+                    Expr::Column(column) => column,
+                    Expr::Alias(Alias { expr, .. }) => match expr.as_ref() {
+                        Expr::Column(column) => column,
+                        _ => return Err(unsupported()),
+                    },
+                    _ => return Err(unsupported()),
+                };
+                Ok((
+                    aggregate.schema.index_of_column(column)?,
+                    schema.field(index).name().clone(),
+                ))
+            })
+            .collect::<PlannerResult<Vec<_>>>()?;
+        return aggregate_to_proof_plan(aggregate, &output);
+    }
+    let (input_plan, postprocessing) = logical_plan_to_proof_plan_with_postprocessing(input)?;
+    if !postprocessing.is_empty() {
+        return Err(unsupported());
+    }
+    let aliased_results = expr
+        .iter()
+        .enumerate()
+        .map(|(index, e)| {
+            let inner_expr = match e {
+                Expr::Alias(Alias { expr, .. }) => expr.as_ref(),
+                _ => e,
+            };
+            Ok(AliasedDynProofExpr {
+                expr: expr_to_proof_expr(inner_expr, input.schema())?,
+                alias: schema.field(index).name().as_str().into(),
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    Ok((
+        DynProofPlan::new_projection(aliased_results, input_plan),
+        vec![],
+    ))
+}
+
+/// Convert a [`Sort`] to a [`DynProofPlan`] and postprocessing steps
+///
+/// The sort is proven if its input needs no postprocessing and can be provably sorted
+/// by the sort columns, see [`SortExec::can_sort_by`]. Otherwise it is done in postprocessing.
+fn sort_to_proof_plan(sort: &Sort) -> PlannerResult<(DynProofPlan, Vec<OwnedTablePostprocessing>)> {
+    let unsupported = || PlannerError::UnsupportedLogicalPlan {
+        plan: Box::new(LogicalPlan::Sort(sort.clone())),
+    };
+    let Sort {
+        expr, input, fetch, ..
+    } = sort;
+    let (input_plan, mut postprocessing) = logical_plan_to_proof_plan_with_postprocessing(input)?;
+    // Postprocessing returns the columns of `input`, otherwise the sort columns are found
+    // in the result of the input plan
+    let index_direction_pairs = expr
+        .iter()
+        .map(|e| match e {
+            Expr::Sort(SortExpr { expr, asc, .. }) => match expr.as_ref() {
+                Expr::Column(column) if postprocessing.is_empty() => Ok((
+                    proof_plan_column_index(input, &input_plan, column)?.ok_or_else(unsupported)?,
+                    *asc,
+                )),
+                Expr::Column(column) => Ok((input.schema().index_of_column(column)?, *asc)),
+                _ => Err(unsupported()),
+            },
+            _ => Err(unsupported()),
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let fields = input_plan.get_column_result_fields();
+    let sort_column_types = index_direction_pairs
+        .iter()
+        .map(|&(index, _)| fields.get(index).map(ColumnField::data_type))
+        .collect::<Option<Vec<_>>>();
+    if postprocessing.is_empty()
+        && sort_column_types.is_some_and(|types| SortExec::can_sort_by(&types))
+    {
+        let sort_plan = DynProofPlan::new_sort(input_plan, index_direction_pairs);
+        let plan = match fetch {
+            Some(fetch) => DynProofPlan::new_slice(sort_plan, 0, Some(*fetch)),
+            None => sort_plan,
+        };
+        return Ok((plan, vec![]));
+    }
+    postprocessing.push(OwnedTablePostprocessing::new_order_by(
+        OrderByPostprocessing::new(index_direction_pairs),
+    ));
+    if let Some(fetch) = fetch {
+        postprocessing.push(OwnedTablePostprocessing::new_slice(
+            SlicePostprocessing::new(Some(u64::try_from(*fetch).unwrap_or(u64::MAX)), None),
+        ));
+    }
+    Ok((input_plan, postprocessing))
+}
+
+/// Find the index of a column of the result of `input` in the result of `input_plan`,
+/// the proof plan of `input`
+///
+/// The result of a proof plan can differ from the result of its `LogicalPlan`,
+/// e.g. a join returns only one of each pair of join columns, so columns are found by name.
+/// Returns `None` if the column is not part of the result of `input_plan`.
+fn proof_plan_column_index(
+    input: &LogicalPlan,
+    input_plan: &DynProofPlan,
+    column: &Column,
+) -> PlannerResult<Option<usize>> {
+    let schema = input.schema();
+    let name = schema.field(schema.index_of_column(column)?).name();
+    Ok(input_plan
+        .get_column_result_fields()
+        .iter()
+        .position(|field| field.name().value == *name))
+}
+
+/// Convert an inner, outer, left semi or left anti equi-[`Join`] without additional filter
+/// to a [`DynProofPlan`]
+///
+/// The result of an outer or inner join must have unique column names, so that its columns
+/// can be found by name, see [`proof_plan_column_index`]. A right join column that has
+/// a different name than its left join column must not share a name with another column.
+fn join_to_proof_plan(join: &Join) -> PlannerResult<(DynProofPlan, Vec<OwnedTablePostprocessing>)> {
+    let unsupported = || PlannerError::UnsupportedLogicalPlan {
+        plan: Box::new(LogicalPlan::Join(join.clone())),
+    };
+    let Join {
+        left,
+        right,
+        on,
+        filter,
+        join_type,
+        ..
+    } = join;
+    if filter.is_some() {
+        return Err(unsupported());
+    }
+    let proof_join_type = match join_type {
+        JoinType::Inner => Some(ProofJoinType::Inner),
+        JoinType::Left => Some(ProofJoinType::Left),
+        JoinType::Right => Some(ProofJoinType::Right),
+        JoinType::Full => Some(ProofJoinType::Full),
+        JoinType::LeftSemi | JoinType::LeftAnti => None,
+        _ => return Err(unsupported()),
+    };
+    let (left_plan, left_postprocessing) = logical_plan_to_proof_plan_with_postprocessing(left)?;
+    let (right_plan, right_postprocessing) = logical_plan_to_proof_plan_with_postprocessing(right)?;
+    if !left_postprocessing.is_empty() || !right_postprocessing.is_empty() {
+        return Err(unsupported());
+    }
+    let (left_join_column_indexes, right_join_column_indexes): (Vec<_>, Vec<_>) = on
+        .iter()
+        .map(|(left_expr, right_expr)| match (left_expr, right_expr) {
+            (Expr::Column(left_column), Expr::Column(right_column)) => Ok((
+                proof_plan_column_index(left, &left_plan, left_column)?.ok_or_else(unsupported)?,
+                proof_plan_column_index(right, &right_plan, right_column)?
+                    .ok_or_else(unsupported)?,
+            )),
+            _ => Err(unsupported()),
+        })
+        .collect::<PlannerResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    // Semi and anti joins only return the columns of the left input
+    let Some(proof_join_type) = proof_join_type else {
+        return Ok((
+            DynProofPlan::new_semi_join(
+                left_plan,
+                right_plan,
+                left_join_column_indexes,
+                right_join_column_indexes,
+                *join_type == JoinType::LeftAnti,
+            ),
+            vec![],
+        ));
+    };
+    // The join columns come first, followed by the other columns of the left and right inputs
+    let left_fields = left_plan.get_column_result_fields();
+    let right_fields = right_plan.get_column_result_fields();
+    let result_idents = left_join_column_indexes
+        .iter()
+        .map(|i| &left_fields[*i])
+        .chain(
+            left_fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !left_join_column_indexes.contains(i))
+                .map(|(_, field)| field),
+        )
+        .chain(
+            right_fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !right_join_column_indexes.contains(i))
+                .map(|(_, field)| field),
+        )
+        .map(ColumnField::name)
+        .collect::<Vec<Ident>>();
+    let has_duplicate_idents = result_idents
+        .iter()
+        .enumerate()
+        .any(|(i, ident)| result_idents[..i].contains(ident));
+    // A right join column is found by name, so its name must be its left join column's or unused
+    let has_ambiguous_right_join_column = left_join_column_indexes
+        .iter()
+        .zip(&right_join_column_indexes)
+        .any(|(&left_index, &right_index)| {
+            let right_ident = right_fields[right_index].name();
+            right_ident != left_fields[left_index].name() && result_idents.contains(&right_ident)
+        });
+    if has_duplicate_idents || has_ambiguous_right_join_column {
+        return Err(unsupported());
+    }
+    Ok((
+        DynProofPlan::new_sort_merge_join_with_join_type(
+            left_plan,
+            right_plan,
+            left_join_column_indexes,
+            right_join_column_indexes,
+            result_idents,
+            proof_join_type,
+        ),
+        vec![],
+    ))
+}
+
+/// Get the [`TableRef`] and the [`DFSchema`] of all the columns of the table of a [`TableScan`]
+///
+/// Note that the projected schema of a `TableScan` need not contain the columns used by its filters.
+fn table_scan_table_ref_and_schema(table_scan: &TableScan) -> PlannerResult<(TableRef, DFSchema)> {
+    let table_ref = table_reference_to_table_ref(&table_scan.table_name)?;
+    let schema = DFSchema::try_from_qualified_schema(
+        table_scan.table_name.clone(),
+        &table_scan.source.schema(),
+    )?;
+    Ok((table_ref, schema))
+}
+
+/// Convert the filters of a [`TableScan`] and an optional additional predicate to a single
+/// [`DynProofExpr`]
+///
+/// Returns `None` if there are no filters at all.
+fn table_scan_where_clause(
+    table_scan: &TableScan,
+    predicate: Option<&Expr>,
+    schema: &DFSchema,
+) -> PlannerResult<Option<DynProofExpr>> {
+    table_scan
+        .filters
+        .iter()
+        .chain(predicate)
+        .map(|expr| expr_to_proof_expr(expr, schema))
+        .reduce(|acc, expr| Ok(DynProofExpr::try_new_and(acc?, expr?)?))
+        .transpose()
+}
+
+/// Convert a [`TableScan`] with an optional additional predicate to a [`DynProofPlan`]
+///
+/// A `TableScan` without filters becomes a `TableExec`, otherwise it becomes a `FilterExec`.
+fn table_scan_to_proof_plan(
+    table_scan: &TableScan,
+    predicate: Option<&Expr>,
+) -> PlannerResult<DynProofPlan> {
+    let (table_ref, schema) = table_scan_table_ref_and_schema(table_scan)?;
+    let column_fields = df_schema_to_column_fields(&table_scan.projected_schema)?;
+    let plan = match table_scan_where_clause(table_scan, predicate, &schema)? {
+        None => DynProofPlan::new_table(table_ref, column_fields),
+        Some(where_clause) => {
+            let aliased_results = column_fields
+                .into_iter()
+                .map(|field| AliasedDynProofExpr {
+                    expr: DynProofExpr::new_column(ColumnRef::new(
+                        table_ref.clone(),
+                        field.name(),
+                        field.data_type(),
+                    )),
+                    alias: field.name(),
+                })
+                .collect();
+            DynProofPlan::new_filter(aliased_results, TableExpr { table_ref }, where_clause)
+        }
+    };
+    Ok(match table_scan.fetch {
+        Some(fetch) => DynProofPlan::new_slice(plan, 0, Some(fetch)),
+        None => plan,
+    })
+}
+
+/// The input of a `GroupByExec`
+enum AggregateInput {
+    /// A table, of which only the columns used by the aggregation are loaded
+    Table(TableRef),
+    /// The result of another plan
+    Plan(DynProofPlan),
+}
+
+/// The kind of column an aggregate output maps to in a `GroupByExec`
+enum AggregateOutput {
+    /// The `i`-th group by expression
+    GroupBy(usize),
+    /// The `i`-th sum column
+    Sum(usize),
+    /// The `i`-th max column
+    Max(usize),
+    /// The `i`-th min column
+    Min(usize),
+    /// The count column
+    Count,
+    /// The quotient of the `i`-th sum column by the count column
+    Avg(usize),
+}
+
+/// Convert the argument of `AVG` to a [`DynProofExpr`]
+///
+/// `DataFusion` casts integer arguments of `AVG` to `Float64`, so such casts are removed.
+fn average_arg_to_proof_expr(expr: &Expr, schema: &DFSchema) -> PlannerResult<DynProofExpr> {
+    match expr {
+        Expr::Cast(Cast {
+            expr: inner_expr,
+            data_type: DataType::Float64,
+        })
+        | Expr::TryCast(TryCast {
+            expr: inner_expr,
+            data_type: DataType::Float64,
+        }) if inner_expr
+            .get_type(schema)
+            .is_ok_and(|data_type| data_type.is_integer()) =>
+        {
+            expr_to_proof_expr(inner_expr, schema)
+        }
+        _ => expr_to_proof_expr(expr, schema),
+    }
+}
+
+/// Convert an [`Aggregate`] to a [`DynProofPlan`] and postprocessing steps
+///
+/// `output` contains the index of an output column of the `Aggregate` and its final name
+/// for every column of the query result.
+/// Only `SUM`, `MAX`, `MIN`, `AVG` and `COUNT` grouped by expressions are supported.
+/// The input, possibly below a `Filter`, is either a `TableScan` or any plan that can be proven
+/// without postprocessing, e.g. a join or a union.
+/// `AVG(expr)` is proven as the sum of `expr` as a decimal, see [`try_average_sum_column_type`],
+/// which is divided by the count in postprocessing.
+fn aggregate_to_proof_plan(
+    aggregate: &Aggregate,
+    output: &[(usize, String)],
+) -> PlannerResult<(DynProofPlan, Vec<OwnedTablePostprocessing>)> {
+    let unsupported = || PlannerError::UnsupportedLogicalPlan {
+        plan: Box::new(LogicalPlan::Aggregate(aggregate.clone())),
+    };
+    let (predicate, input) = match aggregate.input.as_ref() {
+        LogicalPlan::Filter(Filter {
+            predicate, input, ..
+        }) => (Some(predicate), input.as_ref()),
+        input => (None, input),
+    };
+    let (input, where_clause, schema) = match input {
+        LogicalPlan::TableScan(table_scan) if table_scan.fetch.is_none() => {
+            let (table_ref, schema) = table_scan_table_ref_and_schema(table_scan)?;
+            let where_clause = table_scan_where_clause(table_scan, predicate, &schema)?;
+            (AggregateInput::Table(table_ref), where_clause, schema)
+        }
+        _ => {
+            let (input_plan, postprocessing) =
+                logical_plan_to_proof_plan_with_postprocessing(input)?;
+            if !postprocessing.is_empty() {
+                return Err(unsupported());
+            }
+            let schema = input.schema().as_ref().clone();
+            let where_clause = predicate
+                .map(|predicate| expr_to_proof_expr(predicate, &schema))
+                .transpose()?;
+            (AggregateInput::Plan(input_plan), where_clause, schema)
+        }
+    };
+    let where_clause =
+        where_clause.unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
+    // The final name of an output column of the `Aggregate` if it is selected
+    let final_name = |index: usize| {
+        output
+            .iter()
+            .find(|(output_index, _)| *output_index == index)
+            .map(|(_, name)| name.clone())
+    };
+    // Group by columns keep their names, other expressions are named after their output
+    let group_by_exprs = aggregate
+        .group_expr
+        .iter()
+        .enumerate()
+        .map(|(index, expr)| {
+            let alias = match expr {
+                Expr::Column(column) => column.name.as_str().into(),
+                _ => final_name(index)
+                    .unwrap_or_else(|| format!("__group_by_{index}__"))
+                    .as_str()
+                    .into(),
+            };
+            Ok(AliasedDynProofExpr {
+                expr: expr_to_proof_expr(expr, &schema)?,
+                alias,
+            })
+        })
+        .collect::<PlannerResult<Vec<_>>>()?;
+    let num_group_by = group_by_exprs.len();
+    let mut sum_expr = Vec::new();
+    let mut max_expr = Vec::new();
+    let mut min_expr = Vec::new();
+    let mut count_alias: Option<Ident> = None;
+    let mut kinds = (0..num_group_by)
+        .map(AggregateOutput::GroupBy)
+        .collect::<Vec<_>>();
+    for (i, expr) in aggregate.aggr_expr.iter().enumerate() {
+        let index = num_group_by + i;
+        let name =
+            final_name(index).unwrap_or_else(|| aggregate.schema.field(index).name().clone());
+        let inner_expr = match expr {
+            Expr::Alias(Alias { expr, .. }) => expr.as_ref(),
+            _ => expr,
+        };
+        match inner_expr {
+            Expr::AggregateFunction(AggregateFunction {
+                func_def: AggregateFunctionDefinition::BuiltIn(BuiltinAggregateFunction::Sum),
+                args,
+                distinct: false,
+                filter: None,
+                ..
+            }) if args.len() == 1 => {
+                sum_expr.push(AliasedDynProofExpr {
+                    expr: expr_to_proof_expr(&args[0], &schema)?,
+                    alias: name.as_str().into(),
+                });
+                kinds.push(AggregateOutput::Sum(sum_expr.len() - 1));
+            }
+            // `MAX` and `MIN` are only provable for types that can be compared numerically
+            Expr::AggregateFunction(AggregateFunction {
+                func_def:
+                    AggregateFunctionDefinition::BuiltIn(
+                        func @ (BuiltinAggregateFunction::Max | BuiltinAggregateFunction::Min),
+                    ),
+                args,
+                filter: None,
+                ..
+            }) if args.len() == 1
+                && !matches!(
+                    aggregate.schema.field(index).data_type(),
+                    DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary
+                ) =>
+            {
+                let aliased_expr = AliasedDynProofExpr {
+                    expr: expr_to_proof_expr(&args[0], &schema)?,
+                    alias: name.as_str().into(),
+                };
+                if *func == BuiltinAggregateFunction::Max {
+                    max_expr.push(aliased_expr);
+                    kinds.push(AggregateOutput::Max(max_expr.len() - 1));
+                } else {
+                    min_expr.push(aliased_expr);
+                    kinds.push(AggregateOutput::Min(min_expr.len() - 1));
+                }
+            }
+            Expr::AggregateFunction(AggregateFunction {
+                func_def: AggregateFunctionDefinition::BuiltIn(BuiltinAggregateFunction::Avg),
+                args,
+                distinct: false,
+                filter: None,
+                ..
+            }) if args.len() == 1 => {
+                let proof_expr = average_arg_to_proof_expr(&args[0], &schema)?;
+                let sum_type = try_average_sum_column_type(proof_expr.data_type())
+                    .map_err(ConversionError::from)?;
+                sum_expr.push(AliasedDynProofExpr {
+                    expr: DynProofExpr::try_new_cast(proof_expr, sum_type)?,
+                    alias: format!("__avg_sum_{index}__").as_str().into(),
+                });
+                kinds.push(AggregateOutput::Avg(sum_expr.len() - 1));
+            }
+            // `COUNT(expr)` only counts the rows where `expr` is not NULL,
+            // so it is the same as `COUNT(*)` if `expr` can not be NULL
+            Expr::AggregateFunction(AggregateFunction {
+                func_def: AggregateFunctionDefinition::BuiltIn(BuiltinAggregateFunction::Count),
+                args,
+                distinct: false,
+                filter: None,
+                ..
+            }) if args.iter().all(|arg| {
+                matches!(arg, Expr::Wildcard { .. })
+                    || arg.nullable(&schema).is_ok_and(|nullable| !nullable)
+            }) =>
+            {
+                count_alias.get_or_insert_with(|| name.as_str().into());
+                kinds.push(AggregateOutput::Count);
+            }
+            _ => return Err(unsupported()),
+        }
+    }
+    let count_alias = count_alias.unwrap_or_else(|| Ident::new("__count__"));
+    // Names of the columns of the `GroupByExec` result
+    let group_by_result_names = group_by_exprs
+        .iter()
+        .chain(&sum_expr)
+        .chain(&max_expr)
+        .chain(&min_expr)
+        .map(|aliased_expr| aliased_expr.alias.clone())
+        .chain(core::iter::once(count_alias.clone()))
+        .collect::<Vec<_>>();
+    let count_index = num_group_by + sum_expr.len() + max_expr.len() + min_expr.len();
+    // Indexes in the `GroupByExec` result of the query result columns,
+    // or of the sum columns for averages
+    let result_indexes = output
+        .iter()
+        .map(|(index, _)| match kinds[*index] {
+            AggregateOutput::GroupBy(i) => i,
+            AggregateOutput::Sum(i) | AggregateOutput::Avg(i) => num_group_by + i,
+            AggregateOutput::Max(i) => num_group_by + sum_expr.len() + i,
+            AggregateOutput::Min(i) => num_group_by + sum_expr.len() + max_expr.len() + i,
+            AggregateOutput::Count => count_index,
+        })
+        .collect::<Vec<_>>();
+    let is_average = |index: usize| matches!(kinds[index], AggregateOutput::Avg(_));
+    let is_identity = result_indexes.len() == group_by_result_names.len()
+        && result_indexes.iter().zip(output).enumerate().all(
+            |(i, (result_index, (index, name)))| {
+                *result_index == i && !is_average(*index) && group_by_result_names[i].value == *name
+            },
+        );
+    let postprocessing = if is_identity {
+        vec![]
+    } else {
+        let result_column = |result_index: usize| {
+            Identifier::try_new(group_by_result_names[result_index].value.as_str())
+                .map(|identifier| Box::new(Expression::Column(identifier)))
+        };
+        let aliased_result_exprs = result_indexes
+            .iter()
+            .zip(output)
+            .map(|(result_index, (index, name))| {
+                let expr = if is_average(*index) {
+                    Expression::Binary {
+                        op: BinaryOperator::Division,
+                        left: result_column(*result_index)?,
+                        right: result_column(count_index)?,
+                    }
+                } else {
+                    *result_column(*result_index)?
+                };
+                Ok(AliasedResultExpr::new(expr, Identifier::try_new(name)?))
+            })
+            .collect::<PlannerResult<Vec<_>>>()?;
+        vec![OwnedTablePostprocessing::new_select(
+            SelectPostprocessing::new(aliased_result_exprs),
+        )]
+    };
+    let plan = match input {
+        AggregateInput::Table(table_ref) => DynProofPlan::new_group_by(
+            group_by_exprs,
+            sum_expr,
+            max_expr,
+            min_expr,
+            count_alias,
+            TableExpr { table_ref },
+            where_clause,
+        ),
+        AggregateInput::Plan(input_plan) => DynProofPlan::new_group_by_with_input(
+            group_by_exprs,
+            sum_expr,
+            max_expr,
+            min_expr,
+            count_alias,
+            input_plan,
+            where_clause,
+        ),
+    };
+    Ok((plan, postprocessing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::df_util::*;
+    use alloc::sync::Arc;
+    use datafusion::{
+        common::{Column, ScalarValue},
+        logical_expr::{avg, cast, count, lit, max, min, sum, LogicalPlanBuilder, TableSource},
+    };
+    use proof_of_sql::base::{database::ColumnType, math::decimal::Precision};
+
+    fn table_source() -> Arc<dyn TableSource> {
+        posql_table_source(vec![
+            ColumnField::new("a".into(), ColumnType::BigInt),
+            ColumnField::new("b".into(), ColumnType::Int),
+            ColumnField::new("c".into(), ColumnType::VarChar),
+        ])
+    }
+
+    fn scan(table_name: &str, projection: Option<Vec<usize>>) -> LogicalPlanBuilder {
+        LogicalPlanBuilder::scan(table_name, table_source(), projection).unwrap()
+    }
+
+    fn scan_with_filters(table_name: &str, filters: Vec<Expr>) -> LogicalPlanBuilder {
+        LogicalPlanBuilder::scan_with_filters(table_name, table_source(), None, filters).unwrap()
+    }
+
+    fn table_ref() -> TableRef {
+        TableRef::from_names(Some("namespace"), "table")
+    }
+
+    fn column(name: &str, column_type: ColumnType) -> DynProofExpr {
+        DynProofExpr::new_column(ColumnRef::new(table_ref(), name.into(), column_type))
+    }
+
+    fn aliased(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+        AliasedDynProofExpr {
+            expr,
+            alias: alias.into(),
+        }
+    }
+
+    fn all_column_fields() -> Vec<ColumnField> {
+        vec![
+            ColumnField::new("a".into(), ColumnType::BigInt),
+            ColumnField::new("b".into(), ColumnType::Int),
+            ColumnField::new("c".into(), ColumnType::VarChar),
+        ]
+    }
+
+    // TableScan
+    #[test]
+    fn we_can_convert_table_scan_to_table_exec() {
+        let plan = scan("namespace.table", None).build().unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_table(table_ref(), all_column_fields())
+        );
+        assert!(query_expr.postprocessing().is_empty());
+
+        // With projection
+        let plan = scan("namespace.table", Some(vec![2, 0])).build().unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_table(
+                table_ref(),
+                vec![
+                    ColumnField::new("c".into(), ColumnType::VarChar),
+                    ColumnField::new("a".into(), ColumnType::BigInt),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn we_can_convert_table_scan_with_filters_to_filter_exec() {
+        let plan = scan_with_filters(
+            "namespace.table",
+            vec![
+                df_column("namespace.table", "a").gt(lit(5_i64)),
+                df_column("namespace.table", "b").eq(lit(3_i32)),
+            ],
+        )
+        .build()
+        .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_filter(
+                vec![
+                    aliased(column("a", ColumnType::BigInt), "a"),
+                    aliased(column("b", ColumnType::Int), "b"),
+                    aliased(column("c", ColumnType::VarChar), "c"),
+                ],
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::try_new_and(
+                    DynProofExpr::try_new_inequality(
+                        column("a", ColumnType::BigInt),
+                        DynProofExpr::new_literal(LiteralValue::BigInt(5)),
+                        false
+                    )
+                    .unwrap(),
+                    DynProofExpr::try_new_equals(
+                        column("b", ColumnType::Int),
+                        DynProofExpr::new_literal(LiteralValue::Int(3))
+                    )
+                    .unwrap()
+                )
+                .unwrap()
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    // Filter
+    #[test]
+    fn we_can_convert_filter_over_table_scan_to_filter_exec() {
+        let plan = scan("namespace.table", None)
+            .filter(df_column("namespace.table", "b").eq(lit(3_i32)))
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_filter(
+                vec![
+                    aliased(column("a", ColumnType::BigInt), "a"),
+                    aliased(column("b", ColumnType::Int), "b"),
+                    aliased(column("c", ColumnType::VarChar), "c"),
+                ],
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::try_new_equals(
+                    column("b", ColumnType::Int),
+                    DynProofExpr::new_literal(LiteralValue::Int(3))
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    // Projection
+    #[test]
+    fn we_can_convert_projection_to_projection_exec() {
+        let plan = scan("namespace.table", None)
+            .project(vec![
+                df_column("namespace.table", "a").alias("x"),
+                df_column("namespace.table", "b"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_projection(
+                vec![
+                    aliased(column("a", ColumnType::BigInt), "x"),
+                    aliased(column("b", ColumnType::Int), "b"),
+                ],
+                DynProofPlan::new_table(table_ref(), all_column_fields())
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    // Limit
+    #[test]
+    fn we_can_convert_limit_to_slice_exec() {
+        let plan = scan("namespace.table", None)
+            .limit(1, Some(2))
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_slice(
+                DynProofPlan::new_table(table_ref(), all_column_fields()),
+                1,
+                Some(2)
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    // Sort
+    #[test]
+    fn we_can_convert_sort_and_limit_to_sort_exec_and_slice_exec() {
+        let plan = scan("namespace.table", None)
+            .sort(vec![
+                df_column("namespace.table", "b").sort(false, false),
+                df_column("namespace.table", "a").sort(true, false),
+            ])
+            .unwrap()
+            .limit(0, Some(3))
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_slice(
+                DynProofPlan::new_sort(
+                    DynProofPlan::new_table(table_ref(), all_column_fields()),
+                    vec![(1, false), (0, true)]
+                ),
+                0,
+                Some(3)
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    #[test]
+    fn we_can_convert_sort_by_a_varchar_column_and_limit_to_postprocessing() {
+        let plan = scan("namespace.table", None)
+            .sort(vec![df_column("namespace.table", "c").sort(true, false)])
+            .unwrap()
+            .limit(0, Some(3))
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_table(table_ref(), all_column_fields())
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[
+                OwnedTablePostprocessing::new_order_by(OrderByPostprocessing::new(vec![(2, true)])),
+                OwnedTablePostprocessing::new_slice(SlicePostprocessing::new(Some(3), Some(0))),
+            ]
+        );
+    }
+
+    // Distinct
+    #[test]
+    fn we_can_convert_distinct_to_distinct_exec() {
+        let plan = scan("namespace.table", Some(vec![0, 1]))
+            .distinct()
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_distinct(DynProofPlan::new_table(
+                table_ref(),
+                vec![
+                    ColumnField::new("a".into(), ColumnType::BigInt),
+                    ColumnField::new("b".into(), ColumnType::Int),
+                ]
+            ))
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    #[test]
+    fn we_can_convert_distinct_over_a_varchar_column_to_postprocessing() {
+        let plan = scan("namespace.table", None)
+            .distinct()
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_table(table_ref(), all_column_fields())
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[OwnedTablePostprocessing::new_distinct(
+                DistinctPostprocessing::new()
+            )]
+        );
+    }
+
+    // Union
+    #[test]
+    fn we_can_convert_union_to_union_exec() {
+        let plan = scan("namespace.table", None)
+            .union(scan("namespace.other", None).build().unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_union(
+                vec![
+                    DynProofPlan::new_table(table_ref(), all_column_fields()),
+                    DynProofPlan::new_table(
+                        TableRef::from_names(Some("namespace"), "other"),
+                        all_column_fields()
+                    ),
+                ],
+                all_column_fields()
+            )
+        );
+    }
+
+    // Join
+    #[test]
+    fn we_can_convert_inner_join_to_sort_merge_join_exec() {
+        let plan = scan("namespace.table", Some(vec![0, 1]))
+            .join(
+                scan("namespace.other", Some(vec![1, 2])).build().unwrap(),
+                JoinType::Inner,
+                (
+                    vec![Column::new(Some("namespace.table"), "b")],
+                    vec![Column::new(Some("namespace.other"), "b")],
+                ),
+                None,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_sort_merge_join(
+                DynProofPlan::new_table(
+                    table_ref(),
+                    vec![
+                        ColumnField::new("a".into(), ColumnType::BigInt),
+                        ColumnField::new("b".into(), ColumnType::Int),
+                    ]
+                ),
+                DynProofPlan::new_table(
+                    TableRef::from_names(Some("namespace"), "other"),
+                    vec![
+                        ColumnField::new("b".into(), ColumnType::Int),
+                        ColumnField::new("c".into(), ColumnType::VarChar),
+                    ]
+                ),
+                vec![1],
+                vec![0],
+                vec!["b".into(), "a".into(), "c".into()]
+            )
+        );
+    }
+
+    #[test]
+    fn we_can_convert_left_join_to_sort_merge_join_exec() {
+        let plan = scan("namespace.table", Some(vec![0, 1]))
+            .join(
+                scan("namespace.other", Some(vec![1, 2])).build().unwrap(),
+                JoinType::Left,
+                (
+                    vec![Column::new(Some("namespace.table"), "b")],
+                    vec![Column::new(Some("namespace.other"), "b")],
+                ),
+                None,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_sort_merge_join_with_join_type(
+                DynProofPlan::new_table(
+                    table_ref(),
+                    vec![
+                        ColumnField::new("a".into(), ColumnType::BigInt),
+                        ColumnField::new("b".into(), ColumnType::Int),
+                    ]
+                ),
+                DynProofPlan::new_table(
+                    TableRef::from_names(Some("namespace"), "other"),
+                    vec![
+                        ColumnField::new("b".into(), ColumnType::Int),
+                        ColumnField::new("c".into(), ColumnType::VarChar),
+                    ]
+                ),
+                vec![1],
+                vec![0],
+                vec!["b".into(), "a".into(), "c".into()],
+                ProofJoinType::Left
+            )
+        );
+    }
+
+    #[test]
+    fn we_can_convert_semi_and_anti_joins_to_semi_join_exec() {
+        for (join_type, is_anti_join) in [(JoinType::LeftSemi, false), (JoinType::LeftAnti, true)] {
+            let plan = scan("namespace.table", Some(vec![0, 1]))
+                .join(
+                    scan("namespace.other", Some(vec![1, 2])).build().unwrap(),
+                    join_type,
+                    (
+                        vec![Column::new(Some("namespace.table"), "b")],
+                        vec![Column::new(Some("namespace.other"), "b")],
+                    ),
+                    None,
+                )
+                .unwrap()
+                .build()
+                .unwrap();
+            let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+            assert_eq!(
+                query_expr.proof_expr(),
+                &DynProofPlan::new_semi_join(
+                    DynProofPlan::new_table(
+                        table_ref(),
+                        vec![
+                            ColumnField::new("a".into(), ColumnType::BigInt),
+                            ColumnField::new("b".into(), ColumnType::Int),
+                        ]
+                    ),
+                    DynProofPlan::new_table(
+                        TableRef::from_names(Some("namespace"), "other"),
+                        vec![
+                            ColumnField::new("b".into(), ColumnType::Int),
+                            ColumnField::new("c".into(), ColumnType::VarChar),
+                        ]
+                    ),
+                    vec![1],
+                    vec![0],
+                    is_anti_join
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn we_cannot_convert_right_semi_join() {
+        let plan = scan("namespace.table", Some(vec![0, 1]))
+            .join(
+                scan("namespace.other", Some(vec![1, 2])).build().unwrap(),
+                JoinType::RightSemi,
+                (
+                    vec![Column::new(Some("namespace.table"), "b")],
+                    vec![Column::new(Some("namespace.other"), "b")],
+                ),
+                None,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            logical_plan_to_proof_plan(&plan),
+            Err(PlannerError::UnsupportedLogicalPlan { .. })
+        ));
+    }
+
+    // SubqueryAlias
+    #[test]
+    fn we_can_convert_subquery_alias_to_its_input() {
+        let plan = scan("namespace.table", Some(vec![0, 1]))
+            .alias("t")
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_table(
+                table_ref(),
+                vec![
+                    ColumnField::new("a".into(), ColumnType::BigInt),
+                    ColumnField::new("b".into(), ColumnType::Int),
+                ]
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    // Aggregate
+    #[test]
+    fn we_can_convert_aggregate_to_group_by_exec() {
+        let plan = scan_with_filters(
+            "namespace.table",
+            vec![df_column("namespace.table", "b").eq(lit(3_i32))],
+        )
+        .aggregate(
+            vec![df_column("namespace.table", "c")],
+            vec![sum(df_column("namespace.table", "a")), count(lit(1_i64))],
+        )
+        .unwrap()
+        .project(vec![
+            df_column("namespace.table", "c"),
+            Expr::Column(Column::from_name("SUM(namespace.table.a)")).alias("sum_a"),
+            Expr::Column(Column::from_name("COUNT(Int64(1))")).alias("cnt"),
+        ])
+        .unwrap()
+        .build()
+        .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by(
+                vec![aliased(column("c", ColumnType::VarChar), "c")],
+                vec![aliased(column("a", ColumnType::BigInt), "sum_a")],
+                vec![],
+                vec![],
+                "cnt".into(),
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::try_new_equals(
+                    column("b", ColumnType::Int),
+                    DynProofExpr::new_literal(LiteralValue::Int(3))
+                )
+                .unwrap()
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    #[test]
+    fn we_can_convert_aggregate_grouped_by_an_expression_to_group_by_exec() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                vec![df_column("namespace.table", "a") + lit(1_i64)],
+                vec![count(lit(1_i64))],
+            )
+            .unwrap()
+            .project(vec![
+                Expr::Column(Column::from_name("namespace.table.a + Int64(1)")).alias("a1"),
+                Expr::Column(Column::from_name("COUNT(Int64(1))")).alias("cnt"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by(
+                vec![aliased(
+                    DynProofExpr::try_new_add(
+                        column("a", ColumnType::BigInt),
+                        DynProofExpr::new_literal(LiteralValue::BigInt(1))
+                    )
+                    .unwrap(),
+                    "a1"
+                )],
+                vec![],
+                vec![],
+                vec![],
+                "cnt".into(),
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::new_literal(LiteralValue::Boolean(true))
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    #[test]
+    fn we_can_convert_aggregate_without_count_to_group_by_exec_and_select() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                vec![df_column("namespace.table", "c")],
+                vec![sum(df_column("namespace.table", "a"))],
+            )
+            .unwrap()
+            .project(vec![
+                Expr::Column(Column::from_name("SUM(namespace.table.a)")).alias("sum_a"),
+                df_column("namespace.table", "c"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by(
+                vec![aliased(column("c", ColumnType::VarChar), "c")],
+                vec![aliased(column("a", ColumnType::BigInt), "sum_a")],
+                vec![],
+                vec![],
+                "__count__".into(),
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::new_literal(LiteralValue::Boolean(true))
+            )
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[OwnedTablePostprocessing::new_select(
+                SelectPostprocessing::new(vec![
+                    AliasedResultExpr::new(
+                        Expression::Column("sum_a".parse().unwrap()),
+                        "sum_a".parse().unwrap()
+                    ),
+                    AliasedResultExpr::new(
+                        Expression::Column("c".parse().unwrap()),
+                        "c".parse().unwrap()
+                    ),
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn we_can_convert_aggregate_with_max_and_min_to_group_by_exec() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                vec![df_column("namespace.table", "c")],
+                vec![
+                    min(df_column("namespace.table", "b")),
+                    count(lit(1_i64)),
+                    max(df_column("namespace.table", "a")),
+                ],
+            )
+            .unwrap()
+            .project(vec![
+                df_column("namespace.table", "c"),
+                Expr::Column(Column::from_name("MIN(namespace.table.b)")).alias("min_b"),
+                Expr::Column(Column::from_name("COUNT(Int64(1))")).alias("cnt"),
+                Expr::Column(Column::from_name("MAX(namespace.table.a)")).alias("max_a"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by(
+                vec![aliased(column("c", ColumnType::VarChar), "c")],
+                vec![],
+                vec![aliased(column("a", ColumnType::BigInt), "max_a")],
+                vec![aliased(column("b", ColumnType::Int), "min_b")],
+                "cnt".into(),
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::new_literal(LiteralValue::Boolean(true))
+            )
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[OwnedTablePostprocessing::new_select(
+                SelectPostprocessing::new(
+                    ["c", "min_b", "cnt", "max_a"]
+                        .into_iter()
+                        .map(|name| AliasedResultExpr::new(
+                            Expression::Column(name.parse().unwrap()),
+                            name.parse().unwrap()
+                        ))
+                        .collect()
+                )
+            )]
+        );
+    }
+
+    #[test]
+    fn we_can_convert_aggregate_with_avg_to_group_by_exec_and_select() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                vec![df_column("namespace.table", "c")],
+                vec![
+                    avg(df_column("namespace.table", "a")),
+                    count(df_column("namespace.table", "b")),
+                ],
+            )
+            .unwrap()
+            .project(vec![
+                df_column("namespace.table", "c"),
+                Expr::Column(Column::from_name("AVG(namespace.table.a)")).alias("avg_a"),
+                Expr::Column(Column::from_name("COUNT(namespace.table.b)")).alias("cnt"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by(
+                vec![aliased(column("c", ColumnType::VarChar), "c")],
+                vec![aliased(
+                    DynProofExpr::try_new_cast(
+                        column("a", ColumnType::BigInt),
+                        ColumnType::Decimal75(Precision::new(19).unwrap(), 0)
+                    )
+                    .unwrap(),
+                    "__avg_sum_1__"
+                )],
+                vec![],
+                vec![],
+                "cnt".into(),
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::new_literal(LiteralValue::Boolean(true))
+            )
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[OwnedTablePostprocessing::new_select(
+                SelectPostprocessing::new(vec![
+                    AliasedResultExpr::new(
+                        Expression::Column("c".parse().unwrap()),
+                        "c".parse().unwrap()
+                    ),
+                    AliasedResultExpr::new(
+                        Expression::Binary {
+                            op: BinaryOperator::Division,
+                            left: Box::new(Expression::Column("__avg_sum_1__".parse().unwrap())),
+                            right: Box::new(Expression::Column("cnt".parse().unwrap())),
+                        },
+                        "avg_a".parse().unwrap()
+                    ),
+                    AliasedResultExpr::new(
+                        Expression::Column("cnt".parse().unwrap()),
+                        "cnt".parse().unwrap()
+                    ),
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn we_can_convert_aggregate_with_avg_of_an_integer_cast_to_float() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                Vec::<Expr>::new(),
+                vec![
+                    avg(cast(df_column("namespace.table", "b"), DataType::Float64)).alias("avg_b"),
+                ],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by(
+                vec![],
+                vec![aliased(
+                    DynProofExpr::try_new_cast(
+                        column("b", ColumnType::Int),
+                        ColumnType::Decimal75(Precision::new(10).unwrap(), 0)
+                    )
+                    .unwrap(),
+                    "__avg_sum_0__"
+                )],
+                vec![],
+                vec![],
+                "__count__".into(),
+                TableExpr {
+                    table_ref: table_ref()
+                },
+                DynProofExpr::new_literal(LiteralValue::Boolean(true))
+            )
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[OwnedTablePostprocessing::new_select(
+                SelectPostprocessing::new(vec![AliasedResultExpr::new(
+                    Expression::Binary {
+                        op: BinaryOperator::Division,
+                        left: Box::new(Expression::Column("__avg_sum_0__".parse().unwrap())),
+                        right: Box::new(Expression::Column("__count__".parse().unwrap())),
+                    },
+                    "avg_b".parse().unwrap()
+                )])
+            )]
+        );
+    }
+
+    fn inner_join_of_table_and_other() -> LogicalPlanBuilder {
+        scan("namespace.table", Some(vec![0, 1]))
+            .join(
+                scan("namespace.other", Some(vec![1, 2])).build().unwrap(),
+                JoinType::Inner,
+                (
+                    vec![Column::new(Some("namespace.table"), "b")],
+                    vec![Column::new(Some("namespace.other"), "b")],
+                ),
+                None,
+            )
+            .unwrap()
+    }
+
+    fn sort_merge_join_of_table_and_other() -> DynProofPlan {
+        DynProofPlan::new_sort_merge_join(
+            DynProofPlan::new_table(
+                table_ref(),
+                vec![
+                    ColumnField::new("a".into(), ColumnType::BigInt),
+                    ColumnField::new("b".into(), ColumnType::Int),
+                ],
+            ),
+            DynProofPlan::new_table(
+                TableRef::from_names(Some("namespace"), "other"),
+                vec![
+                    ColumnField::new("b".into(), ColumnType::Int),
+                    ColumnField::new("c".into(), ColumnType::VarChar),
+                ],
+            ),
+            vec![1],
+            vec![0],
+            vec!["b".into(), "a".into(), "c".into()],
+        )
+    }
+
+    #[test]
+    fn we_can_convert_sort_over_join_to_sort_exec_by_the_columns_of_the_join() {
+        // The join returns b, a and c, while its schema has a, b, b and c
+        let plan = inner_join_of_table_and_other()
+            .sort(vec![
+                df_column("namespace.table", "a").sort(true, false),
+                df_column("namespace.other", "b").sort(false, false),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_sort(
+                sort_merge_join_of_table_and_other(),
+                vec![(1, true), (0, false)]
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+
+        // Columns that can not be proven to be sorted are sorted in postprocessing
+        let plan = inner_join_of_table_and_other()
+            .sort(vec![df_column("namespace.other", "c").sort(true, false)])
+            .unwrap()
+            .build()
+            .unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &sort_merge_join_of_table_and_other()
+        );
+        assert_eq!(
+            query_expr.postprocessing(),
+            &[OwnedTablePostprocessing::new_order_by(
+                OrderByPostprocessing::new(vec![(2, true)])
+            )]
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_join_whose_inputs_have_columns_of_the_same_name() {
+        // Both inputs have a column `a` that is not a join column
+        let plan = scan("namespace.table", Some(vec![0, 1]))
+            .join(
+                scan("namespace.other", Some(vec![0, 1])).build().unwrap(),
+                JoinType::Inner,
+                (
+                    vec![Column::new(Some("namespace.table"), "b")],
+                    vec![Column::new(Some("namespace.other"), "b")],
+                ),
+                None,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            logical_plan_to_proof_plan(&plan),
+            Err(PlannerError::UnsupportedLogicalPlan { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_convert_filter_over_join_to_filter_exec_with_input() {
+        let plan = inner_join_of_table_and_other()
+            .filter(df_column("namespace.table", "a").eq(lit(5_i64)))
+            .unwrap()
+            .build()
+            .unwrap();
+        let other_table_ref = TableRef::from_names(Some("namespace"), "other");
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_filter_with_input(
+                vec![
+                    aliased(column("b", ColumnType::Int), "b"),
+                    aliased(column("a", ColumnType::BigInt), "a"),
+                    aliased(
+                        DynProofExpr::new_column(ColumnRef::new(
+                            other_table_ref,
+                            "c".into(),
+                            ColumnType::VarChar
+                        )),
+                        "c"
+                    ),
+                ],
+                sort_merge_join_of_table_and_other(),
+                DynProofExpr::try_new_equals(
+                    column("a", ColumnType::BigInt),
+                    DynProofExpr::new_literal(LiteralValue::BigInt(5))
+                )
+                .unwrap()
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    #[test]
+    fn we_can_convert_aggregate_over_join_to_group_by_exec_with_input() {
+        let plan = inner_join_of_table_and_other()
+            .filter(df_column("namespace.table", "a").eq(lit(5_i64)))
+            .unwrap()
+            .aggregate(
+                vec![df_column("namespace.other", "c")],
+                vec![sum(df_column("namespace.table", "a")), count(lit(1_i64))],
+            )
+            .unwrap()
+            .project(vec![
+                df_column("namespace.other", "c"),
+                Expr::Column(Column::from_name("SUM(namespace.table.a)")).alias("sum_a"),
+                Expr::Column(Column::from_name("COUNT(Int64(1))")).alias("cnt"),
+            ])
+            .unwrap()
+            .build()
+            .unwrap();
+        let other_table_ref = TableRef::from_names(Some("namespace"), "other");
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(
+            query_expr.proof_expr(),
+            &DynProofPlan::new_group_by_with_input(
+                vec![aliased(
+                    DynProofExpr::new_column(ColumnRef::new(
+                        other_table_ref.clone(),
+                        "c".into(),
+                        ColumnType::VarChar
+                    )),
+                    "c"
+                )],
+                vec![aliased(column("a", ColumnType::BigInt), "sum_a")],
+                vec![],
+                vec![],
+                "cnt".into(),
+                DynProofPlan::new_sort_merge_join(
+                    DynProofPlan::new_table(
+                        table_ref(),
+                        vec![
+                            ColumnField::new("a".into(), ColumnType::BigInt),
+                            ColumnField::new("b".into(), ColumnType::Int),
+                        ]
+                    ),
+                    DynProofPlan::new_table(
+                        other_table_ref.clone(),
+                        vec![
+                            ColumnField::new("b".into(), ColumnType::Int),
+                            ColumnField::new("c".into(), ColumnType::VarChar),
+                        ]
+                    ),
+                    vec![1],
+                    vec![0],
+                    vec!["b".into(), "a".into(), "c".into()]
+                ),
+                DynProofExpr::try_new_equals(
+                    column("a", ColumnType::BigInt),
+                    DynProofExpr::new_literal(LiteralValue::BigInt(5))
+                )
+                .unwrap()
+            )
+        );
+        assert!(query_expr.postprocessing().is_empty());
+    }
+
+    #[test]
+    fn we_cannot_convert_count_of_nullable_expressions() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                Vec::<Expr>::new(),
+                vec![count(lit(ScalarValue::Int64(None)))],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            logical_plan_to_proof_plan(&plan),
+            Err(PlannerError::UnsupportedLogicalPlan { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_convert_unsupported_aggregate() {
+        let plan = scan("namespace.table", None)
+            .aggregate(
+                vec![df_column("namespace.table", "a")],
+                vec![max(df_column("namespace.table", "c"))],
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            logical_plan_to_proof_plan(&plan),
+            Err(PlannerError::UnsupportedLogicalPlan { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_convert_projection_over_sort() {
+        let plan = scan("namespace.table", None)
+            .sort(vec![df_column("namespace.table", "b").sort(true, false)])
+            .unwrap()
+            .project(vec![df_column("namespace.table", "a")])
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            logical_plan_to_proof_plan(&plan),
+            Err(PlannerError::UnsupportedLogicalPlan { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_convert_empty_relation_to_empty_exec() {
+        let plan = LogicalPlanBuilder::empty(true).build().unwrap();
+        let query_expr = logical_plan_to_proof_plan(&plan).unwrap();
+        assert_eq!(query_expr.proof_expr(), &DynProofPlan::new_empty());
+        assert!(query_expr.postprocessing().is_empty());
+    }
+}
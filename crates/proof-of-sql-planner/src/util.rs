@@ -79,6 +79,24 @@ pub(crate) fn column_to_column_ref(column: &Column, schema: &DFSchema) -> Planne
     ))
 }
 
+/// Convert the fields of a [`DFSchema`] to a Vec<ColumnField>
+///
+/// Errors out if any of the fields has a datatype not supported by `PoSQL`
+pub(crate) fn df_schema_to_column_fields(schema: &DFSchema) -> PlannerResult<Vec<ColumnField>> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let column_type = ColumnType::try_from(field.data_type().clone()).map_err(|_e| {
+                PlannerError::UnsupportedDataType {
+                    data_type: field.data_type().clone(),
+                }
+            })?;
+            Ok(ColumnField::new(field.name().as_str().into(), column_type))
+        })
+        .collect()
+}
+
 /// Convert a Vec<ColumnField> to a Schema
 pub(crate) fn column_fields_to_schema(column_fields: Vec<ColumnField>) -> Schema {
     Schema::new(
@@ -282,6 +300,35 @@ mod tests {
         ));
     }
 
+    // DFSchema to ColumnFields
+    #[test]
+    fn we_can_convert_df_schema_to_column_fields() {
+        let arrow_schema = Schema::new(vec![
+            Field::new("a", DataType::Int16, false),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        let df_schema =
+            DFSchema::try_from_qualified_schema("namespace.table", &arrow_schema).unwrap();
+        assert_eq!(
+            df_schema_to_column_fields(&df_schema).unwrap(),
+            vec![
+                ColumnField::new("a".into(), ColumnType::SmallInt),
+                ColumnField::new("b".into(), ColumnType::VarChar),
+            ]
+        );
+    }
+
+    #[test]
+    fn we_cannot_convert_df_schema_with_unsupported_data_type_to_column_fields() {
+        let arrow_schema = Schema::new(vec![Field::new("a", DataType::Float32, false)]);
+        let df_schema =
+            DFSchema::try_from_qualified_schema("namespace.table", &arrow_schema).unwrap();
+        assert!(matches!(
+            df_schema_to_column_fields(&df_schema),
+            Err(PlannerError::UnsupportedDataType { .. })
+        ));
+    }
+
     // ColumnFields to Schema
     #[test]
     fn we_can_convert_column_fields_to_schema() {
@@ -14,18 +14,22 @@ use clap::{arg, Parser, Subcommand, ValueEnum};
 use commit_accessor::CommitAccessor;
 use csv_accessor::{read_record_batch_from_csv, CsvDataAccessor};
 use itertools::Itertools;
+#[cfg(feature = "hyperkzg")]
+use proof_of_sql::proof_primitive::hyperkzg::{
+    HyperKZGCommitmentEvaluationProof, HyperKZGPublicSetup,
+};
 use proof_of_sql::{
     base::{
-        commitment::TableCommitment,
+        commitment::{CommitmentEvaluationProof, TableCommitment},
         database::{SchemaAccessor, TableRef},
     },
     proof_primitive::dory::{
-        DynamicDoryCommitment, DynamicDoryEvaluationProof, ProverSetup, PublicParameters,
-        VerifierSetup,
+        DynamicDoryEvaluationProof, ProverSetup, PublicParameters, VerifierSetup,
     },
     sql::{parse::QueryExpr, proof::VerifiableQueryResult},
 };
 use proof_of_sql_parser::SelectStatement;
+use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 use std::{
     fs,
@@ -42,11 +46,35 @@ struct CliArgs {
     /// Path to the directory where the csv files are stored.
     #[arg(short, long, default_value = ".")]
     path: String,
+    /// The commitment scheme to commit to tables and prove queries with.
+    ///
+    /// Commitments and proofs are only compatible with the backend that created them.
+    #[arg(long, default_value = "dynamic-dory")]
+    backend: Backend,
+    /// Path to a BN254 powers of tau file to load the `HyperKZG` setup from.
+    ///
+    /// Without it, a fixed test setup is used, which is not secure.
+    #[arg(long)]
+    ptau: Option<PathBuf>,
     #[command(subcommand)]
     /// TODO: add docs
     command: Commands,
 }
 
+/// The commitment scheme that `posql_db` proves with.
+#[derive(Clone, ValueEnum, Debug)]
+enum Backend {
+    /// Dynamic Dory, which is transparent.
+    DynamicDory,
+    /// `HyperKZG` over BN254, which is the scheme the EVM verifier supports.
+    /// This requires the `hyperkzg` feature.
+    HyperKzg,
+}
+
+/// The number of rows the `HyperKZG` setup supports, which matches the Dory setup of `posql_db`.
+#[cfg(feature = "hyperkzg")]
+const HYPERKZG_SETUP_LEN: usize = 1 << 9;
+
 #[derive(Clone, ValueEnum, Debug)]
 #[value(rename_all = "UPPER")]
 enum CsvDataType {
@@ -148,7 +176,7 @@ fn end_timer(instant: Instant) {
 /// - **Proof Verification Failure**: Panics if the proof verification process fails.
 /// - **Serialization/Deserialization Failure**: Panics if the proof cannot be serialized or deserialized.
 /// - **Record Batch Conversion Failure**: Panics if the query result cannot be converted into a `RecordBatch`.
-#[allow(clippy::too_many_lines)]
+/// - **Setup Load Failure**: Panics if the powers of tau file cannot be loaded, or if `HyperKZG` is selected without the `hyperkzg` feature.
 fn main() {
     let args = CliArgs::parse();
 
@@ -160,9 +188,42 @@ fn main() {
     }
 
     let mut rng = <ark_std::rand::rngs::StdRng as ark_std::rand::SeedableRng>::from_seed([0u8; 32]);
-    let public_parameters = PublicParameters::rand(5, &mut rng);
-    let prover_setup = ProverSetup::from(&public_parameters);
-    let verifier_setup = VerifierSetup::from(&public_parameters);
+    match args.backend {
+        Backend::DynamicDory => {
+            let public_parameters = PublicParameters::rand(5, &mut rng);
+            let prover_setup = ProverSetup::from(&public_parameters);
+            let verifier_setup = VerifierSetup::from(&public_parameters);
+            run::<DynamicDoryEvaluationProof>(args, &prover_setup, &verifier_setup);
+        }
+        #[cfg(feature = "hyperkzg")]
+        Backend::HyperKzg => {
+            let public_setup = match &args.ptau {
+                Some(ptau) => HyperKZGPublicSetup::load_from_ptau_file(ptau, HYPERKZG_SETUP_LEN)
+                    .expect("Failed to load powers of tau file"),
+                None => HyperKZGPublicSetup::test_rand(HYPERKZG_SETUP_LEN, &mut rng),
+            };
+            let prover_setup = public_setup.prover_setup();
+            let verifier_setup = public_setup.verifier_setup();
+            run::<HyperKZGCommitmentEvaluationProof>(args, &prover_setup, &verifier_setup);
+        }
+        #[cfg(not(feature = "hyperkzg"))]
+        Backend::HyperKzg => panic!("The HyperKZG backend requires the `hyperkzg` feature."),
+    }
+}
+
+/// Runs a command with the given commitment scheme.
+///
+/// # Panics
+///
+/// See [`main`].
+#[allow(clippy::too_many_lines)]
+fn run<CP>(
+    args: CliArgs,
+    prover_setup: CP::ProverPublicSetup<'_>,
+    verifier_setup: CP::VerifierPublicSetup<'_>,
+) where
+    CP: CommitmentEvaluationProof + Serialize + for<'a> Deserialize<'a>,
+{
     match args.command {
         Commands::Create {
             table,
@@ -170,7 +231,7 @@ fn main() {
             data_types,
         } => {
             let commit_accessor =
-                CommitAccessor::<DynamicDoryCommitment>::new(PathBuf::from(args.path.clone()));
+                CommitAccessor::<CP::Commitment>::new(PathBuf::from(args.path.clone()));
             let csv_accessor = CsvDataAccessor::new(PathBuf::from(args.path));
             let schema = Schema::new(
                 columns
@@ -182,7 +243,7 @@ fn main() {
                     .collect::<Vec<_>>(),
             );
             let batch = RecordBatch::new_empty(Arc::new(schema));
-            let table_commitment = TableCommitment::try_from_record_batch(&batch, &&prover_setup)
+            let table_commitment = TableCommitment::try_from_record_batch(&batch, &prover_setup)
                 .expect("Failed to create table commitment.");
             commit_accessor
                 .write_commit(&table, &table_commitment)
@@ -196,7 +257,7 @@ fn main() {
             file: file_path,
         } => {
             let mut commit_accessor =
-                CommitAccessor::<DynamicDoryCommitment>::new(PathBuf::from(args.path.clone()));
+                CommitAccessor::<CP::Commitment>::new(PathBuf::from(args.path.clone()));
             let csv_accessor = CsvDataAccessor::new(PathBuf::from(args.path));
             commit_accessor
                 .load_commit(&table_name)
@@ -216,7 +277,7 @@ fn main() {
                 .expect("Failed to write batch");
             let timer = start_timer("Updating Commitment");
             table_commitment
-                .try_append_record_batch(&append_batch, &&prover_setup)
+                .try_append_record_batch(&append_batch, &prover_setup)
                 .expect("Failed to append batch");
             end_timer(timer);
             commit_accessor
@@ -225,7 +286,7 @@ fn main() {
         }
         Commands::Prove { query, file } => {
             let mut commit_accessor =
-                CommitAccessor::<DynamicDoryCommitment>::new(PathBuf::from(args.path.clone()));
+                CommitAccessor::<CP::Commitment>::new(PathBuf::from(args.path.clone()));
             let mut csv_accessor = CsvDataAccessor::new(PathBuf::from(args.path.clone()));
             let tables = query.get_table_references("example".parse().unwrap());
             for table in tables.into_iter().map(Into::into) {
@@ -245,11 +306,8 @@ fn main() {
             }
             let query = QueryExpr::try_new(query, "example".into(), &commit_accessor).unwrap();
             let timer = start_timer("Generating Proof");
-            let proof = VerifiableQueryResult::<DynamicDoryEvaluationProof>::new(
-                query.proof_expr(),
-                &csv_accessor,
-                &&prover_setup,
-            );
+            let proof =
+                VerifiableQueryResult::<CP>::new(query.proof_expr(), &csv_accessor, &prover_setup);
             end_timer(timer);
             fs::write(
                 file,
@@ -259,7 +317,7 @@ fn main() {
         }
         Commands::Verify { query, file } => {
             let mut commit_accessor =
-                CommitAccessor::<DynamicDoryCommitment>::new(PathBuf::from(args.path.clone()));
+                CommitAccessor::<CP::Commitment>::new(PathBuf::from(args.path.clone()));
             let table_refs = query.get_table_references("example".parse().unwrap());
             for table_ref in table_refs {
                 let table_name: TableRef = table_ref.into();
@@ -268,13 +326,13 @@ fn main() {
                     .expect("Failed to load commit");
             }
             let query = QueryExpr::try_new(query, "example".into(), &commit_accessor).unwrap();
-            let result: VerifiableQueryResult<DynamicDoryEvaluationProof> =
+            let result: VerifiableQueryResult<CP> =
                 postcard::from_bytes(&fs::read(file).expect("Failed to read proof"))
                     .expect("Failed to deserialize proof");
 
             let timer = start_timer("Verifying Proof");
             let query_result = result
-                .verify(query.proof_expr(), &commit_accessor, &&verifier_setup)
+                .verify(query.proof_expr(), &commit_accessor, &verifier_setup)
                 .expect("Failed to verify proof");
             end_timer(timer);
             println!(
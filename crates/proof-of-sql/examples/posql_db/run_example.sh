@@ -1,4 +1,5 @@
 cd crates/proof-of-sql/examples/posql_db
+# To prove with HyperKZG, pass `--features hyperkzg` to this script and add `--backend hyper-kzg` before each command.
 cargo run  --features="arrow,utils" "$@" --example posql_db create -t sxt.table -c a,b -d BIGINT,VARCHAR
 cargo run  --features="arrow,utils" "$@" --example posql_db append -t sxt.table -f hello_world.csv
 cargo run  --features="arrow,utils" "$@" --example posql_db prove -q "SELECT b FROM sxt.table WHERE a = 2" -f hello.proof
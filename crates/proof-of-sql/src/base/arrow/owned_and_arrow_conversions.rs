@@ -12,9 +12,17 @@
 //! This is because there is no `Int128` type in Arrow.
 //! This does not check that the values are less than 39 digits.
 //! However, the actual arrow backing `i128` is the correct value.
+//!
+//! Arrow null buffers are mapped to presence bitmaps (see [`NullableOwnedColumn`]).
+//! Converting a `RecordBatch` stores a presence bitmap with every column that contains nulls.
+//! Converting an `OwnedTable` folds the presence bitmaps, as well as the presence columns of
+//! query results, back into the null buffers of their columns.
 use super::scalar_and_i256_conversions::{convert_i256_to_scalar, convert_scalar_to_i256};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable, OwnedTableError},
+    database::{
+        is_presence_column_ident, presence_column_ident, NullableOwnedColumn, OwnedColumn,
+        OwnedTable, OwnedTableError,
+    },
     map::IndexMap,
     math::decimal::Precision,
     scalar::Scalar,
@@ -22,10 +30,11 @@ use crate::base::{
 use alloc::sync::Arc;
 use arrow::{
     array::{
-        ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Decimal256Array, Int16Array,
-        Int32Array, Int64Array, Int8Array, StringArray, TimestampMicrosecondArray,
+        make_array, Array, ArrayRef, BinaryArray, BooleanArray, Decimal128Array, Decimal256Array,
+        Int16Array, Int32Array, Int64Array, Int8Array, StringArray, TimestampMicrosecondArray,
         TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt8Array,
     },
+    buffer::NullBuffer,
     datatypes::{i256, DataType, Schema, SchemaRef, TimeUnit as ArrowTimeUnit},
     error::ArrowError,
     record_batch::RecordBatch,
@@ -118,20 +127,47 @@ impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
     }
 }
 
+/// # Panics
+///
+/// Will panic under the same conditions as the conversion from [`OwnedColumn`].
+impl<S: Scalar> From<NullableOwnedColumn<S>> for ArrayRef {
+    fn from(value: NullableOwnedColumn<S>) -> Self {
+        let (values, presence) = value.into_parts();
+        let array = ArrayRef::from(values);
+        match presence {
+            Some(presence) => make_array(
+                array
+                    .into_data()
+                    .into_builder()
+                    .nulls(Some(NullBuffer::from(presence)))
+                    .build()
+                    .expect("presence has the same length as the values"),
+            ),
+            None => array,
+        }
+    }
+}
+
 impl<S: Scalar> TryFrom<OwnedTable<S>> for RecordBatch {
     type Error = ArrowError;
     fn try_from(value: OwnedTable<S>) -> Result<Self, Self::Error> {
         if value.is_empty() {
             Ok(RecordBatch::new_empty(SchemaRef::new(Schema::empty())))
         } else {
-            RecordBatch::try_from_iter(
-                value
-                    .into_inner()
-                    .into_iter()
-                    .map(|(identifier, owned_column)| {
-                        (identifier.value, ArrayRef::from(owned_column))
-                    }),
-            )
+            let mut presence_bitmaps = value.presence().clone();
+            let (presence_columns, columns): (IndexMap<_, _>, IndexMap<_, _>) = value
+                .into_inner()
+                .into_iter()
+                .partition(|(identifier, _)| is_presence_column_ident(identifier));
+            RecordBatch::try_from_iter(columns.into_iter().map(|(identifier, owned_column)| {
+                let presence = match presence_columns.get(&presence_column_ident(&identifier)) {
+                    Some(OwnedColumn::Boolean(presence)) => Some(presence.clone()),
+                    _ => presence_bitmaps.swap_remove(&identifier),
+                };
+                let nullable_column = NullableOwnedColumn::try_new(owned_column, presence)
+                    .expect("columns of an OwnedTable have the same length");
+                (identifier.value, ArrayRef::from(nullable_column))
+            }))
         }
     }
 }
@@ -313,22 +349,56 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
     }
 }
 
+impl<S: Scalar> TryFrom<ArrayRef> for NullableOwnedColumn<S> {
+    type Error = OwnedArrowConversionError;
+    fn try_from(value: ArrayRef) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+impl<S: Scalar> TryFrom<&ArrayRef> for NullableOwnedColumn<S> {
+    type Error = OwnedArrowConversionError;
+
+    /// # Panics
+    ///
+    /// Will panic under the same conditions as the conversion to [`OwnedColumn`].
+    fn try_from(value: &ArrayRef) -> Result<Self, Self::Error> {
+        match value.nulls().filter(|nulls| nulls.null_count() > 0) {
+            Some(nulls) => {
+                let presence = nulls.iter().collect();
+                let values_without_nulls = make_array(
+                    value
+                        .to_data()
+                        .into_builder()
+                        .nulls(None)
+                        .build()
+                        .expect("removing the null buffer of valid array data keeps it valid"),
+                );
+                let values = OwnedColumn::try_from(&values_without_nulls)?;
+                Ok(Self::try_new(values, Some(presence))
+                    .expect("the null buffer has the same length as the values"))
+            }
+            None => Ok(Self::try_new(OwnedColumn::try_from(value)?, None)
+                .expect("a column without presence is always valid")),
+        }
+    }
+}
+
 impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
     type Error = OwnedArrowConversionError;
     fn try_from(value: RecordBatch) -> Result<Self, Self::Error> {
         let num_columns = value.num_columns();
-        let table: Result<IndexMap<_, _>, Self::Error> = value
-            .schema()
-            .fields()
-            .iter()
-            .zip(value.columns())
-            .map(|(field, array_ref)| {
-                let owned_column = OwnedColumn::try_from(array_ref)?;
-                let identifier = Ident::new(field.name());
-                Ok((identifier, owned_column))
-            })
-            .collect();
-        let owned_table = Self::try_new(table?)?;
+        let mut table: IndexMap<Ident, OwnedColumn<S>> = IndexMap::default();
+        let mut presence: IndexMap<Ident, Vec<bool>> = IndexMap::default();
+        for (field, array_ref) in value.schema().fields().iter().zip(value.columns()) {
+            let (owned_column, column_presence) =
+                NullableOwnedColumn::try_from(array_ref)?.into_parts();
+            let identifier = Ident::new(field.name());
+            if let Some(column_presence) = column_presence {
+                presence.insert(identifier.clone(), column_presence);
+            }
+            table.insert(identifier, owned_column);
+        }
+        let owned_table = Self::try_new_with_presence(table, presence)?;
         if num_columns == owned_table.num_columns() {
             Ok(owned_table)
         } else {
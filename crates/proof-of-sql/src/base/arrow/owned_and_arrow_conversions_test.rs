@@ -1,6 +1,6 @@
 use super::owned_and_arrow_conversions::OwnedArrowConversionError;
 use crate::base::{
-    database::{owned_table_utility::*, OwnedColumn, OwnedTable},
+    database::{owned_table_utility::*, NullableOwnedColumn, OwnedColumn, OwnedTable},
     map::IndexMap,
     scalar::test_scalar::TestScalar,
 };
@@ -192,6 +192,79 @@ fn we_can_convert_between_owned_table_and_record_batch() {
     );
 }
 
+#[test]
+fn we_can_convert_between_owned_table_with_presence_and_record_batch_with_nulls() {
+    let batch = RecordBatch::try_from_iter([
+        (
+            "int64",
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+        ),
+        (
+            "string",
+            Arc::new(StringArray::from(vec![None, Some("b"), Some("c")])) as ArrayRef,
+        ),
+        (
+            "boolean",
+            Arc::new(BooleanArray::from(vec![true, false, true])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    we_can_convert_between_owned_table_and_record_batch_impl(
+        &nullable_owned_table(
+            [
+                bigint("int64", [1, 0, 3]),
+                varchar("string", ["", "b", "c"]),
+                boolean("boolean", [true, false, true]),
+            ],
+            [
+                ("int64".into(), vec![true, false, true]),
+                ("string".into(), vec![false, true, true]),
+            ],
+        ),
+        &batch,
+    );
+}
+
+#[test]
+fn we_can_convert_an_owned_table_with_presence_columns_to_a_record_batch_with_nulls() {
+    let batch = RecordBatch::try_from_iter([
+        (
+            "int64",
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+        ),
+        (
+            "boolean",
+            Arc::new(BooleanArray::from(vec![true, false, true])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let owned_table = owned_table::<TestScalar>([
+        bigint("int64", [1, 0, 3]),
+        presence("int64", [true, false, true]),
+        boolean("boolean", [true, false, true]),
+    ]);
+    assert_eq!(RecordBatch::try_from(owned_table).unwrap(), batch);
+}
+
+#[test]
+fn we_can_convert_an_array_ref_with_nulls_to_a_nullable_owned_column() {
+    let array: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)]));
+    let nullable_column = NullableOwnedColumn::<TestScalar>::try_from(&array).unwrap();
+    assert_eq!(
+        nullable_column.values(),
+        &OwnedColumn::Boolean(vec![true, false, false])
+    );
+    assert_eq!(nullable_column.presence(), Some(&[true, false, true][..]));
+    assert_eq!(ArrayRef::from(nullable_column).as_ref(), array.as_ref());
+
+    let array: ArrayRef = Arc::new(Int64Array::from(vec![1, 2]));
+    let nullable_column = NullableOwnedColumn::<TestScalar>::try_from(array).unwrap();
+    assert_eq!(
+        nullable_column.into_parts(),
+        (OwnedColumn::BigInt(vec![1, 2]), None)
+    );
+}
+
 #[test]
 #[should_panic(expected = "not implemented: Cannot convert Scalar type to arrow type")]
 fn we_panic_when_converting_an_owned_table_with_a_scalar_column() {
@@ -7,13 +7,19 @@ use crate::base::{
         AppendColumnCommitmentsError, AppendTableCommitmentError, Commitment, TableCommitment,
         TableCommitmentFromColumnsError,
     },
-    database::Column,
+    database::{Column, NullableOwnedColumn},
+    map::IndexMap,
     scalar::Scalar,
 };
-use arrow::record_batch::RecordBatch;
+use arrow::{array::Array, record_batch::RecordBatch};
 use bumpalo::Bump;
 use sqlparser::ast::Ident;
 
+/// Converts a [`RecordBatch`] into a list of named [`Column`]s.
+///
+/// Every array that contains nulls is converted into its values, with nulls replaced by
+/// default values. The nulls themselves are dropped.
+///
 /// This function will return an error if:
 /// - The field name cannot be parsed into an [`Identifier`].
 /// - The conversion of an Arrow array to a [`Column`] fails.
@@ -21,24 +27,48 @@ pub fn batch_to_columns<'a, S: Scalar + 'a>(
     batch: &'a RecordBatch,
     alloc: &'a Bump,
 ) -> Result<Vec<(Ident, Column<'a, S>)>, RecordBatchToColumnsError> {
-    batch
-        .schema()
-        .fields()
-        .into_iter()
-        .zip(batch.columns())
-        .map(|(field, array)| {
-            let identifier: Ident = field.name().as_str().into();
+    batch_to_columns_and_presence(batch, alloc).map(|(columns, _)| columns)
+}
+
+/// Converts a [`RecordBatch`] into a list of named [`Column`]s, and the presence bitmaps of the
+/// arrays that contain nulls.
+fn batch_to_columns_and_presence<'a, S: Scalar + 'a>(
+    batch: &'a RecordBatch,
+    alloc: &'a Bump,
+) -> Result<ColumnsAndPresence<'a, S>, RecordBatchToColumnsError> {
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    let mut presence = IndexMap::default();
+    for (field, array) in batch.schema().fields().into_iter().zip(batch.columns()) {
+        let identifier: Ident = field.name().as_str().into();
+        if array.null_count() == 0 {
             let column: Column<S> = array.to_column(alloc, &(0..array.len()), None)?;
-            Ok((identifier, column))
-        })
-        .collect()
+            columns.push((identifier, column));
+        } else {
+            let (values, column_presence) = NullableOwnedColumn::<S>::try_from(array)?.into_parts();
+            let column_presence =
+                column_presence.expect("arrays with nulls have a presence bitmap");
+            presence.insert(
+                identifier.clone(),
+                &*alloc.alloc_slice_copy(&column_presence),
+            );
+            columns.push((
+                identifier,
+                Column::from_owned_column(alloc.alloc(values), alloc),
+            ));
+        }
+    }
+    Ok((columns, presence))
 }
 
+type ColumnsAndPresence<'a, S> = (Vec<(Ident, Column<'a, S>)>, IndexMap<Ident, &'a [bool]>);
+
 impl<C: Commitment> TableCommitment<C> {
     /// Append an arrow [`RecordBatch`] to the existing [`TableCommitment`].
     ///
     /// The row offset is assumed to be the end of the [`TableCommitment`]'s current range.
     ///
+    /// The nulls of the batch are committed to as the presence of their columns.
+    ///
     /// Will error on a variety of mismatches, or if the provided columns have mixed length.
     #[allow(clippy::missing_panics_doc)]
     pub fn try_append_record_batch(
@@ -46,10 +76,11 @@ impl<C: Commitment> TableCommitment<C> {
         batch: &RecordBatch,
         setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendRecordBatchTableCommitmentError> {
-        match self.try_append_rows(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
-                .iter()
-                .map(|(a, b)| (a, b)),
+        let alloc = Bump::new();
+        let (columns, presence) = batch_to_columns_and_presence::<C::Scalar>(batch, &alloc)?;
+        match self.try_append_rows_with_presence(
+            columns.iter().map(|(a, b)| (a, b)),
+            &presence,
             setup,
         ) {
             Ok(()) => Ok(()),
@@ -75,16 +106,19 @@ impl<C: Commitment> TableCommitment<C> {
     }
 
     /// Returns a [`TableCommitment`] to the provided arrow [`RecordBatch`] with the given row offset.
+    ///
+    /// The nulls of the batch are committed to as the presence of their columns.
     #[allow(clippy::missing_panics_doc)]
     pub fn try_from_record_batch_with_offset(
         batch: &RecordBatch,
         offset: usize,
         setup: &C::PublicSetup<'_>,
     ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
-        match Self::try_from_columns_with_offset(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
-                .iter()
-                .map(|(a, b)| (a, b)),
+        let alloc = Bump::new();
+        let (columns, presence) = batch_to_columns_and_presence::<C::Scalar>(batch, &alloc)?;
+        match Self::try_from_columns_and_presence_with_offset(
+            columns.iter().map(|(a, b)| (a, b)),
+            &presence,
             offset,
             setup,
         ) {
@@ -102,9 +136,9 @@ impl<C: Commitment> TableCommitment<C> {
 #[cfg(all(test, feature = "blitzar"))]
 mod tests {
     use super::*;
-    use crate::base::scalar::Curve25519Scalar;
+    use crate::base::{commitment::VecCommitmentExt, scalar::Curve25519Scalar};
     use arrow::{
-        array::{Int64Array, StringArray},
+        array::{ArrayRef, Int64Array, StringArray},
         datatypes::{DataType, Field, Schema},
         record_batch::RecordBatch,
     };
@@ -175,4 +209,61 @@ mod tests {
 
         assert_eq!(commitment, expected_commitment);
     }
+
+    #[test]
+    fn we_can_create_table_commitments_with_record_batches_containing_nulls() {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "a",
+                Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            ),
+            (
+                "b",
+                Arc::new(StringArray::from(vec!["1", "2", "3"])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let b_scals = ["1".into(), "2".into(), "3".into()];
+
+        let columns = [
+            (&"a".into(), &Column::<Curve25519Scalar>::BigInt(&[1, 0, 3])),
+            (
+                &"b".into(),
+                &Column::<Curve25519Scalar>::VarChar((&["1", "2", "3"], &b_scals)),
+            ),
+        ];
+        let presence = IndexMap::from_iter([("a".into(), [true, false, true].as_slice())]);
+
+        let expected_commitment =
+            TableCommitment::<RistrettoPoint>::try_from_columns_and_presence_with_offset(
+                columns,
+                &presence,
+                0,
+                &(),
+            )
+            .unwrap();
+        let commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&batch, &()).unwrap();
+
+        assert_eq!(commitment, expected_commitment);
+        assert_eq!(
+            commitment
+                .column_commitments()
+                .get_presence_commitment(&"a".into()),
+            Some(
+                Vec::<RistrettoPoint>::from_columns_with_offset(
+                    [[true, false, true].as_slice()],
+                    0,
+                    &()
+                )[0]
+            )
+        );
+        assert_eq!(
+            commitment
+                .column_commitments()
+                .get_presence_commitment(&"b".into()),
+            None
+        );
+    }
 }
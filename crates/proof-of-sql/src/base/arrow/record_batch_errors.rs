@@ -1,4 +1,7 @@
-use super::arrow_array_to_column_conversion::ArrowArrayToColumnConversionError;
+use super::{
+    arrow_array_to_column_conversion::ArrowArrayToColumnConversionError,
+    owned_and_arrow_conversions::OwnedArrowConversionError,
+};
 use crate::base::commitment::ColumnCommitmentsMismatch;
 use proof_of_sql_parser::ParseError;
 use snafu::Snafu;
@@ -12,6 +15,12 @@ pub enum RecordBatchToColumnsError {
         /// The underlying source error
         source: ArrowArrayToColumnConversionError,
     },
+    /// Error converting from an arrow array that contains nulls
+    #[snafu(transparent)]
+    NullableArrayConversionError {
+        /// The underlying source error
+        source: OwnedArrowConversionError,
+    },
     #[snafu(transparent)]
     /// This error occurs when converting from a record batch name to an identifier fails. (Which may be impossible.)
     FieldParseFail {
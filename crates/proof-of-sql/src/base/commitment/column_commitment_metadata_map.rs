@@ -32,6 +32,11 @@ pub enum ColumnCommitmentsMismatch {
         /// The second column ident
         id_b: String,
     },
+    /// Commitments with different nullable columns cannot operate with each other.
+    #[snafu(display(
+        "commitments with different nullable columns cannot operate with each other"
+    ))]
+    NullableColumns,
 }
 
 /// Extension trait intended for [`ColumnCommitmentMetadataMap`].
@@ -3,15 +3,18 @@ use super::{
     ColumnCommitmentMetadataMapExt, ColumnCommitmentsMismatch, Commitment, VecCommitmentExt,
 };
 use crate::base::{
-    database::{ColumnField, ColumnRef, CommitmentAccessor, TableRef},
-    map::IndexSet,
+    database::{
+        is_presence_column_ident, presence_column_owner, ColumnField, ColumnRef, ColumnType,
+        CommitmentAccessor, TableRef,
+    },
+    map::{IndexMap, IndexSet},
 };
 use alloc::{
     string::{String, ToString},
     vec,
     vec::Vec,
 };
-use core::{iter, slice};
+use core::{iter, ops::Range, slice};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
 use sqlparser::ast::Ident;
@@ -43,30 +46,56 @@ pub enum AppendColumnCommitmentsError {
 /// Commitments for a collection of columns with some metadata.
 ///
 /// These columns do not need to belong to the same table, and can have differing lengths.
+///
+/// The presence of a nullable column is committed to alongside the column itself.
+/// A column without a presence commitment has no null values.
 #[derive(Clone, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ColumnCommitments<C> {
     commitments: Vec<C>,
     column_metadata: ColumnCommitmentMetadataMap,
+    #[serde(default)]
+    presence_commitments: IndexMap<Ident, C>,
 }
 
 impl<C: Commitment> ColumnCommitments<C> {
     /// Create a new [`ColumnCommitments`] for a table from a commitment accessor.
+    ///
+    /// Fields that refer to the presence of a column are committed to alongside that column.
     pub fn from_accessor_with_max_bounds(
         table: &TableRef,
         columns: &[ColumnField],
         accessor: &impl CommitmentAccessor<C>,
     ) -> Self {
+        let (presence_columns, columns): (Vec<_>, Vec<_>) = columns
+            .iter()
+            .cloned()
+            .partition(|c| is_presence_column_ident(&c.name()));
         let column_metadata =
-            ColumnCommitmentMetadataMap::from_column_fields_with_max_bounds(columns);
+            ColumnCommitmentMetadataMap::from_column_fields_with_max_bounds(&columns);
         let commitments = columns
             .iter()
             .map(|c| {
                 accessor.get_commitment(ColumnRef::new(table.clone(), c.name(), c.data_type()))
             })
             .collect();
+        let presence_commitments = presence_columns
+            .iter()
+            .filter_map(|c| {
+                let owner = presence_column_owner(&c.name())?;
+                column_metadata.contains_key(&owner).then(|| {
+                    let commitment = accessor.get_commitment(ColumnRef::new(
+                        table.clone(),
+                        c.name(),
+                        ColumnType::Boolean,
+                    ));
+                    (owner, commitment)
+                })
+            })
+            .collect();
         ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
         }
     }
 
@@ -107,6 +136,20 @@ impl<C: Commitment> ColumnCommitments<C> {
             .map(|index| self.commitments[index].clone())
     }
 
+    /// Returns the commitment to the presence of the column with the given ident,
+    /// or `None` if the column has no null values.
+    #[must_use]
+    pub fn get_presence_commitment(&self, identifier: &Ident) -> Option<C> {
+        self.presence_commitments.get(identifier).cloned()
+    }
+
+    /// Returns a reference to the stored commitments to the presence of nullable columns,
+    /// keyed by the ident of their column.
+    #[must_use]
+    pub fn presence_commitments(&self) -> &IndexMap<Ident, C> {
+        &self.presence_commitments
+    }
+
     /// Returns the metadata for the commitment with the given ident.
     #[must_use]
     pub fn get_metadata(&self, identifier: &Ident) -> Option<&ColumnCommitmentMetadata> {
@@ -160,6 +203,7 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments: IndexMap::default(),
         })
     }
 
@@ -168,15 +212,42 @@ impl<C: Commitment> ColumnCommitments<C> {
     /// The given generator offset will be used for committing to the new rows.
     /// You most likely want this to be equal to the 0-indexed row number of the first new row.
     ///
+    /// Nullable columns have no null values among the new rows.
+    ///
     /// Will error on a variety of mismatches.
     /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
-    #[allow(clippy::missing_panics_doc)]
     pub fn try_append_rows_with_offset<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
         offset: usize,
         setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendColumnCommitmentsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        self.try_append_rows_with_presence_and_offset(
+            columns,
+            &IndexMap::default(),
+            0..0,
+            offset,
+            setup,
+        )
+    }
+
+    /// Append rows of data from the provided columns, and the presence of the new rows of their
+    /// nullable columns, to the existing commitments.
+    ///
+    /// Nullable columns missing from `presence` have no null values among the new rows.
+    /// Columns that become nullable have no null values among their `existing_rows`.
+    #[allow(clippy::missing_panics_doc)]
+    pub(super) fn try_append_rows_with_presence_and_offset<'a, COL>(
+        &mut self,
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        presence: &IndexMap<Ident, &[bool]>,
+        existing_rows: Range<usize>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), AppendColumnCommitmentsError>
     where
         COL: Into<CommittableColumn<'a>>,
     {
@@ -210,13 +281,64 @@ impl<C: Commitment> ColumnCommitments<C> {
 
         self.column_metadata = self.column_metadata.clone().try_union(column_metadata)?;
 
+        let num_rows = committable_columns
+            .first()
+            .map_or(0, CommittableColumn::len);
         self.commitments
             .try_append_rows_with_offset(committable_columns, offset, setup)
             .expect("we've already checked that self and other have equal column counts");
 
+        let existing_presence = vec![true; existing_rows.len()];
+        let new_nullable_columns = presence
+            .keys()
+            .filter(|identifier| !self.presence_commitments.contains_key(*identifier))
+            .map(|identifier| (identifier, existing_presence.as_slice()))
+            .collect::<Vec<_>>();
+        self.add_presence_with_offset(new_nullable_columns, existing_rows.start, setup);
+
+        let new_presence = vec![true; num_rows];
+        let nullable_columns = self
+            .presence_commitments
+            .keys()
+            .map(|identifier| {
+                let presence = presence
+                    .get(identifier)
+                    .copied()
+                    .unwrap_or(new_presence.as_slice());
+                (identifier.clone(), presence)
+            })
+            .collect::<Vec<_>>();
+        self.add_presence_with_offset(
+            nullable_columns
+                .iter()
+                .map(|(identifier, presence)| (identifier, *presence)),
+            offset,
+            setup,
+        );
+
         Ok(())
     }
 
+    /// Add the commitments to the provided presence, using the given generator offset, to the
+    /// presence commitments of their columns.
+    ///
+    /// Columns without a presence commitment become nullable.
+    pub(super) fn add_presence_with_offset<'a>(
+        &mut self,
+        presence: impl IntoIterator<Item = (&'a Ident, &'a [bool])>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) {
+        let (identifiers, presence): (Vec<&Ident>, Vec<&[bool]>) = presence.into_iter().unzip();
+        let commitments = Vec::<C>::from_columns_with_offset(presence, offset, setup);
+        for (identifier, commitment) in identifiers.into_iter().zip(commitments) {
+            *self
+                .presence_commitments
+                .entry(identifier.clone())
+                .or_default() += commitment;
+        }
+    }
+
     /// Add new columns to this [`ColumnCommitments`] using the given generator offset.
     pub fn try_extend_columns_with_offset<'a, COL>(
         &mut self,
@@ -268,6 +390,13 @@ impl<C: Commitment> ColumnCommitments<C> {
         Self: Sized,
     {
         let column_metadata = self.column_metadata.try_union(other.column_metadata)?;
+        let presence_commitments =
+            try_zip_presence_commitments(self.presence_commitments, other.presence_commitments)?
+                .map(|(identifier, mut commitment, other_commitment)| {
+                    commitment += other_commitment;
+                    (identifier, commitment)
+                })
+                .collect();
         let commitments = self
             .commitments
             .try_add(other.commitments)
@@ -276,6 +405,7 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
         })
     }
 
@@ -289,6 +419,13 @@ impl<C: Commitment> ColumnCommitments<C> {
         Self: Sized,
     {
         let column_metadata = self.column_metadata.try_difference(other.column_metadata)?;
+        let presence_commitments =
+            try_zip_presence_commitments(self.presence_commitments, other.presence_commitments)?
+                .map(|(identifier, mut commitment, other_commitment)| {
+                    commitment -= other_commitment;
+                    (identifier, commitment)
+                })
+                .collect();
         let commitments = self
             .commitments
             .try_sub(other.commitments)
@@ -297,10 +434,32 @@ impl<C: Commitment> ColumnCommitments<C> {
         Ok(ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments,
         })
     }
 }
 
+/// Pairs up the presence commitments of two [`ColumnCommitments`],
+/// erroring if they do not have the same nullable columns.
+fn try_zip_presence_commitments<C>(
+    presence_commitments: IndexMap<Ident, C>,
+    mut other_presence_commitments: IndexMap<Ident, C>,
+) -> Result<impl Iterator<Item = (Ident, C, C)>, ColumnCommitmentsMismatch> {
+    if presence_commitments.len() != other_presence_commitments.len() {
+        return Err(ColumnCommitmentsMismatch::NullableColumns);
+    }
+    presence_commitments
+        .into_iter()
+        .map(|(identifier, commitment)| {
+            let other_commitment = other_presence_commitments
+                .swap_remove(&identifier)
+                .ok_or(ColumnCommitmentsMismatch::NullableColumns)?;
+            Ok((identifier, commitment, other_commitment))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(IntoIterator::into_iter)
+}
+
 /// Owning iterator for [`ColumnCommitments`].
 pub type IntoIter<C> = iter::Map<
     iter::Zip<<ColumnCommitmentMetadataMap as IntoIterator>::IntoIter, vec::IntoIter<C>>,
@@ -347,6 +506,7 @@ impl<C> FromIterator<(Ident, ColumnCommitmentMetadata, C)> for ColumnCommitments
         ColumnCommitments {
             commitments,
             column_metadata,
+            presence_commitments: IndexMap::default(),
         }
     }
 }
@@ -1,8 +1,8 @@
 use super::{Commitment, TableCommitment};
 use crate::base::{
     database::{
-        ColumnField, ColumnRef, ColumnType, CommitmentAccessor, MetadataAccessor, SchemaAccessor,
-        TableRef,
+        presence_column_ident, presence_column_owner, ColumnField, ColumnRef, ColumnType,
+        CommitmentAccessor, MetadataAccessor, SchemaAccessor, TableRef,
     },
     map::IndexMap,
 };
@@ -23,6 +23,8 @@ where
     C: Commitment,
 {
     /// Create a new `QueryCommitments` from a collection of columns and an accessor.
+    ///
+    /// Columns that refer to the presence of a column are committed to alongside that column.
     fn from_accessor_with_max_bounds(
         columns: impl IntoIterator<Item = ColumnRef>,
         accessor: &(impl CommitmentAccessor<C> + SchemaAccessor),
@@ -51,11 +53,23 @@ impl<C: Commitment> QueryCommitmentsExt<C> for QueryCommitments<C> {
                 let selected_column_fields = accessor
                     .lookup_schema(table_ref.clone())
                     .into_iter()
-                    .filter_map(|(ident, _)| {
-                        column_fields
+                    .flat_map(|(ident, column_type)| {
+                        let presence_field = column_fields
+                            .iter()
+                            .find(|column_field| {
+                                column_field.name() == presence_column_ident(&ident)
+                            })
+                            .cloned();
+                        let column_field = column_fields
                             .iter()
                             .find(|column_field| column_field.name() == ident)
                             .cloned()
+                            .or_else(|| {
+                                presence_field
+                                    .is_some()
+                                    .then(|| ColumnField::new(ident, column_type))
+                            });
+                        column_field.into_iter().chain(presence_field)
                     })
                     .collect::<Vec<_>>();
                 let table_commitment = TableCommitment::from_accessor_with_max_bounds(
@@ -87,10 +101,14 @@ impl<C: Commitment> MetadataAccessor for QueryCommitments<C> {
 impl<C: Commitment> CommitmentAccessor<C> for QueryCommitments<C> {
     fn get_commitment(&self, column: ColumnRef) -> C {
         let table_commitment = self.get(&column.table_ref()).unwrap();
+        let column_commitments = table_commitment.column_commitments();
 
-        table_commitment
-            .column_commitments()
+        column_commitments
             .get_commitment(&column.column_id())
+            .or_else(|| {
+                column_commitments
+                    .get_presence_commitment(&presence_column_owner(&column.column_id())?)
+            })
             .unwrap()
     }
 }
@@ -102,11 +120,16 @@ impl<C: Commitment> SchemaAccessor for QueryCommitments<C> {
         column_id: Ident,
     ) -> Option<ColumnType> {
         let table_commitment = self.get(&table_ref)?;
+        let column_commitments = table_commitment.column_commitments();
 
-        table_commitment
-            .column_commitments()
+        column_commitments
             .get_metadata(&column_id)
             .map(|column_metadata| *column_metadata.column_type())
+            .or_else(|| {
+                column_commitments
+                    .get_presence_commitment(&presence_column_owner(&column_id)?)
+                    .map(|_| ColumnType::Boolean)
+            })
     }
 
     /// # Panics
@@ -136,8 +159,8 @@ mod tests {
         base::{
             commitment::{naive_commitment::NaiveCommitment, Bounds, ColumnBounds},
             database::{
-                owned_table_utility::*, OwnedColumn, OwnedTable, OwnedTableTestAccessor,
-                TestAccessor,
+                owned_table_utility::*, presence_column_ref, OwnedColumn, OwnedTable,
+                OwnedTableTestAccessor, TestAccessor,
             },
             scalar::test_scalar::TestScalar,
         },
@@ -398,4 +421,59 @@ mod tests {
         );
         assert_eq!(query_commitments, expected_query_commitments);
     }
+
+    #[test]
+    fn we_can_get_query_commitments_to_the_presence_of_nullable_columns_from_accessor() {
+        let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+        let prover_setup = ProverSetup::from(&public_parameters);
+        let setup = DoryProverPublicSetup::new(&prover_setup, 3);
+
+        let column_a_id: Ident = "column_a".into();
+        let table = nullable_owned_table(
+            [varchar(
+                column_a_id.value.as_str(),
+                ["Lorem", "", "dolor", "sit"],
+            )],
+            [(column_a_id.clone(), vec![true, false, true, true])],
+        );
+        let table_commitment = TableCommitment::from_owned_table_with_offset(&table, 0, &setup);
+        let table_id = TableRef::new("table", "a");
+
+        let mut accessor =
+            OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(setup);
+        accessor.add_table(table_id.clone(), table, 0);
+
+        let column_a = ColumnRef::new(table_id.clone(), column_a_id.clone(), ColumnType::VarChar);
+        let presence_a = presence_column_ref(&column_a);
+
+        // the presence alone selects its column
+        let query_commitments = QueryCommitments::<DoryCommitment>::from_accessor_with_max_bounds(
+            [presence_a.clone()],
+            &accessor,
+        );
+        assert_eq!(
+            query_commitments,
+            QueryCommitments::from_iter([(table_id.clone(), table_commitment.clone())])
+        );
+
+        assert_eq!(
+            query_commitments.get_commitment(presence_a.clone()),
+            table_commitment
+                .column_commitments()
+                .get_presence_commitment(&column_a_id)
+                .unwrap()
+        );
+        assert_eq!(
+            query_commitments.lookup_column(table_id.clone(), presence_a.column_id()),
+            Some(ColumnType::Boolean)
+        );
+        assert_eq!(
+            query_commitments.lookup_column(table_id, column_a_id),
+            Some(ColumnType::VarChar)
+        );
+        assert_eq!(
+            query_commitments.get_commitment(column_a),
+            table_commitment.column_commitments().commitments()[0]
+        );
+    }
 }
@@ -4,6 +4,7 @@ use super::{
 };
 use crate::base::{
     database::{ColumnField, CommitmentAccessor, OwnedTable, TableRef},
+    map::IndexMap,
     scalar::Scalar,
 };
 use alloc::vec::Vec;
@@ -164,6 +165,28 @@ impl<C: Commitment> TableCommitment<C> {
         offset: usize,
         setup: &C::PublicSetup<'_>,
     ) -> Result<TableCommitment<C>, TableCommitmentFromColumnsError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        Self::try_from_columns_and_presence_with_offset(
+            columns,
+            &IndexMap::default(),
+            offset,
+            setup,
+        )
+    }
+
+    /// Returns a [`TableCommitment`] to the provided columns and the presence of their nullable
+    /// columns with the given row offset.
+    ///
+    /// Provided columns must have the same length and no duplicate idents.
+    /// The presence must belong to provided columns and have the same length.
+    pub(crate) fn try_from_columns_and_presence_with_offset<'a, COL>(
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        presence: &IndexMap<Ident, &[bool]>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<TableCommitment<C>, TableCommitmentFromColumnsError>
     where
         COL: Into<CommittableColumn<'a>>,
     {
@@ -174,11 +197,18 @@ impl<C: Commitment> TableCommitment<C> {
 
         let num_rows = num_rows_of_columns(&committable_columns)?;
 
-        let column_commitments = ColumnCommitments::try_from_columns_with_offset(
+        let mut column_commitments = ColumnCommitments::try_from_columns_with_offset(
             identifiers.into_iter().zip(committable_columns.into_iter()),
             offset,
             setup,
         )?;
+        column_commitments.add_presence_with_offset(
+            presence
+                .iter()
+                .map(|(identifier, presence)| (identifier, *presence)),
+            offset,
+            setup,
+        );
 
         Ok(TableCommitment {
             column_commitments,
@@ -187,6 +217,8 @@ impl<C: Commitment> TableCommitment<C> {
     }
 
     /// Returns a [`TableCommitment`] to the provided table with the given row offset.
+    ///
+    /// The presence of the nullable columns of the table is committed to alongside their columns.
     #[allow(
         clippy::missing_panics_doc,
         reason = "since OwnedTables cannot have columns of mixed length or duplicate idents"
@@ -199,20 +231,48 @@ impl<C: Commitment> TableCommitment<C> {
     where
         S: Scalar,
     {
-        Self::try_from_columns_with_offset(owned_table.inner_table(), offset, setup)
-            .expect("OwnedTables cannot have columns of mixed length or duplicate idents")
+        Self::try_from_columns_and_presence_with_offset(
+            owned_table.inner_table(),
+            &presence_of_owned_table(owned_table),
+            offset,
+            setup,
+        )
+        .expect("OwnedTables cannot have columns of mixed length or duplicate idents")
     }
 
     /// Append rows of data from the provided columns to the existing [`TableCommitment`].
     ///
     /// The row offset is assumed to be the end of the [`TableCommitment`]'s current range.
     ///
+    /// Nullable columns have no null values among the new rows.
+    ///
     /// Will error on a variety of mismatches, or if the provided columns have mixed length.
     pub fn try_append_rows<'a, COL>(
         &mut self,
         columns: impl IntoIterator<Item = (&'a Ident, COL)>,
         setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendTableCommitmentError>
+    where
+        COL: Into<CommittableColumn<'a>>,
+    {
+        self.try_append_rows_with_presence(columns, &IndexMap::default(), setup)
+    }
+
+    /// Append rows of data from the provided columns, and the presence of the new rows of their
+    /// nullable columns, to the existing [`TableCommitment`].
+    ///
+    /// The row offset is assumed to be the end of the [`TableCommitment`]'s current range.
+    ///
+    /// Nullable columns missing from `presence` have no null values among the new rows.
+    /// The presence must belong to provided columns and have the same length.
+    ///
+    /// Will error on a variety of mismatches, or if the provided columns have mixed length.
+    pub(crate) fn try_append_rows_with_presence<'a, COL>(
+        &mut self,
+        columns: impl IntoIterator<Item = (&'a Ident, COL)>,
+        presence: &IndexMap<Ident, &[bool]>,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), AppendTableCommitmentError>
     where
         COL: Into<CommittableColumn<'a>>,
     {
@@ -223,11 +283,14 @@ impl<C: Commitment> TableCommitment<C> {
 
         let num_rows = num_rows_of_columns(&committable_columns)?;
 
-        self.column_commitments.try_append_rows_with_offset(
-            identifiers.into_iter().zip(committable_columns.into_iter()),
-            self.range.end,
-            setup,
-        )?;
+        self.column_commitments
+            .try_append_rows_with_presence_and_offset(
+                identifiers.into_iter().zip(committable_columns.into_iter()),
+                presence,
+                self.range.clone(),
+                self.range.end,
+                setup,
+            )?;
         self.range.end += num_rows;
 
         Ok(())
@@ -235,6 +298,8 @@ impl<C: Commitment> TableCommitment<C> {
 
     /// Append data of the provided table to the exiting [`TableCommitment`].
     ///
+    /// The presence of the nullable columns of the table is committed to alongside their columns.
+    ///
     /// Will error on a variety of mismatches.
     /// See [`ColumnCommitmentsMismatch`] for an enumeration of these errors.
     /// # Panics
@@ -248,18 +313,22 @@ impl<C: Commitment> TableCommitment<C> {
     where
         S: Scalar,
     {
-        self.try_append_rows(owned_table.inner_table(), setup)
-            .map_err(|e| match e {
-                AppendTableCommitmentError::AppendColumnCommitments { source: e } => match e {
-                    AppendColumnCommitmentsError::Mismatch { source: e } => e,
-                    AppendColumnCommitmentsError::DuplicateIdents { .. } => {
-                        panic!("OwnedTables cannot have duplicate idents");
-                    }
-                },
-                AppendTableCommitmentError::MixedLengthColumns { .. } => {
-                    panic!("OwnedTables cannot have columns of mixed length");
+        self.try_append_rows_with_presence(
+            owned_table.inner_table(),
+            &presence_of_owned_table(owned_table),
+            setup,
+        )
+        .map_err(|e| match e {
+            AppendTableCommitmentError::AppendColumnCommitments { source: e } => match e {
+                AppendColumnCommitmentsError::Mismatch { source: e } => e,
+                AppendColumnCommitmentsError::DuplicateIdents { .. } => {
+                    panic!("OwnedTables cannot have duplicate idents");
                 }
-            })
+            },
+            AppendTableCommitmentError::MixedLengthColumns { .. } => {
+                panic!("OwnedTables cannot have columns of mixed length");
+            }
+        })
     }
 
     /// Add new columns to this [`TableCommitment`].
@@ -356,6 +425,15 @@ impl<C: Commitment> TableCommitment<C> {
     }
 }
 
+/// Returns the presence of the nullable columns of the provided table.
+fn presence_of_owned_table<S: Scalar>(owned_table: &OwnedTable<S>) -> IndexMap<Ident, &[bool]> {
+    owned_table
+        .presence()
+        .iter()
+        .map(|(identifier, presence)| (identifier.clone(), presence.as_slice()))
+        .collect()
+}
+
 /// Return the number of rows for the provided columns, erroring if they have mixed length.
 fn num_rows_of_columns<'a>(
     committable_columns: impl IntoIterator<Item = &'a CommittableColumn<'a>>,
@@ -378,7 +456,7 @@ fn num_rows_of_columns<'a>(
 mod tests {
     use super::*;
     use crate::base::{
-        commitment::naive_commitment::NaiveCommitment,
+        commitment::{naive_commitment::NaiveCommitment, VecCommitmentExt},
         database::{owned_table_utility::*, Column, OwnedColumn},
         map::IndexMap,
         scalar::test_scalar::TestScalar,
@@ -632,6 +710,112 @@ mod tests {
         assert_eq!(table_commitment, table_commitment_clone);
     }
 
+    #[test]
+    fn we_can_commit_to_the_presence_of_nullable_columns() {
+        let nullable_table: OwnedTable<TestScalar> = nullable_owned_table(
+            [
+                bigint("column_a", [1, 0, 3, 4]),
+                varchar("column_b", ["Lorem", "ipsum", "dolor", "sit"]),
+            ],
+            [("column_a".into(), vec![true, false, true, true])],
+        );
+        let table_commitment = TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(
+            &nullable_table,
+            2,
+            &(),
+        );
+
+        // the values are committed to as usual
+        let table: OwnedTable<TestScalar> = owned_table([
+            bigint("column_a", [1, 0, 3, 4]),
+            varchar("column_b", ["Lorem", "ipsum", "dolor", "sit"]),
+        ]);
+        let expected_column_commitments =
+            TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(&table, 2, &())
+                .column_commitments()
+                .commitments()
+                .clone();
+        assert_eq!(
+            table_commitment.column_commitments().commitments(),
+            &expected_column_commitments
+        );
+        assert_eq!(table_commitment.range(), &(2..6));
+
+        // the presence is committed to alongside its column
+        let expected_presence_commitment = Vec::<NaiveCommitment>::from_columns_with_offset(
+            [[true, false, true, true].as_slice()],
+            2,
+            &(),
+        )
+        .remove(0);
+        assert_eq!(
+            table_commitment
+                .column_commitments()
+                .get_presence_commitment(&"column_a".into()),
+            Some(expected_presence_commitment)
+        );
+        assert_eq!(
+            table_commitment
+                .column_commitments()
+                .get_presence_commitment(&"column_b".into()),
+            None
+        );
+
+        // different presence yields a different commitment
+        let other_nullable_table: OwnedTable<TestScalar> = nullable_owned_table(
+            [
+                bigint("column_a", [1, 0, 3, 4]),
+                varchar("column_b", ["Lorem", "ipsum", "dolor", "sit"]),
+            ],
+            [("column_a".into(), vec![true, true, true, true])],
+        );
+        assert_ne!(
+            TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(
+                &other_nullable_table,
+                2,
+                &()
+            ),
+            table_commitment
+        );
+    }
+
+    #[test]
+    fn we_can_append_rows_with_presence_to_table_commitment() {
+        let initial_table: OwnedTable<TestScalar> =
+            owned_table([bigint("column_a", [1, 2]), bigint("column_b", [3, 4])]);
+        let mut table_commitment = TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(
+            &initial_table,
+            0,
+            &(),
+        );
+
+        // column_a becomes nullable, so its existing rows are present
+        let nullable_table: OwnedTable<TestScalar> = nullable_owned_table(
+            [bigint("column_a", [0, 5]), bigint("column_b", [5, 6])],
+            [("column_a".into(), vec![false, true])],
+        );
+        table_commitment
+            .append_owned_table(&nullable_table, &())
+            .unwrap();
+
+        // nullable columns without new presence have no nulls among the new rows
+        let table: OwnedTable<TestScalar> =
+            owned_table([bigint("column_a", [7]), bigint("column_b", [7])]);
+        table_commitment.append_owned_table(&table, &()).unwrap();
+
+        let expected_table: OwnedTable<TestScalar> = nullable_owned_table(
+            [
+                bigint("column_a", [1, 2, 0, 5, 7]),
+                bigint("column_b", [3, 4, 5, 6, 7]),
+            ],
+            [("column_a".into(), vec![true, true, false, true, true])],
+        );
+        assert_eq!(
+            table_commitment,
+            TableCommitment::from_owned_table_with_offset(&expected_table, 0, &())
+        );
+    }
+
     #[test]
     fn we_cannot_append_mismatched_columns_to_table_commitment() {
         let base_table: OwnedTable<TestScalar> = owned_table([
@@ -866,6 +1050,37 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn we_cannot_add_table_commitments_with_different_nullable_columns() {
+        let table_commitment = TableCommitment::<NaiveCommitment>::from_owned_table_with_offset(
+            &owned_table::<TestScalar>([bigint("column_a", [1, 2])]),
+            0,
+            &(),
+        );
+        let nullable_table_commitment = TableCommitment::from_owned_table_with_offset(
+            &nullable_owned_table(
+                [bigint("column_a", [0, 4])],
+                [("column_a".into(), vec![false, true])],
+            ),
+            2,
+            &(),
+        );
+        assert!(matches!(
+            table_commitment
+                .clone()
+                .try_add(nullable_table_commitment.clone()),
+            Err(TableCommitmentArithmeticError::ColumnMismatch {
+                source: ColumnCommitmentsMismatch::NullableColumns
+            })
+        ));
+        assert!(matches!(
+            nullable_table_commitment.try_add(table_commitment),
+            Err(TableCommitmentArithmeticError::ColumnMismatch {
+                source: ColumnCommitmentsMismatch::NullableColumns
+            })
+        ));
+    }
+
     #[test]
     fn we_cannot_add_noncontiguous_table_commitments() {
         let base_table: OwnedTable<TestScalar> = owned_table([
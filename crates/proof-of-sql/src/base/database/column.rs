@@ -151,6 +151,38 @@ impl<'a, S: Scalar> Column<'a, S> {
         }
     }
 
+    /// Generate a column of the given type in which every value is the default value of the type
+    ///
+    /// Every default value, including the empty string and the empty binary, has the zero scalar.
+    pub fn default_with_length(column_type: ColumnType, length: usize, alloc: &'a Bump) -> Self {
+        match column_type {
+            ColumnType::Boolean => Column::Boolean(alloc.alloc_slice_fill_copy(length, false)),
+            ColumnType::Uint8 => Column::Uint8(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::TinyInt => Column::TinyInt(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::SmallInt => Column::SmallInt(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::Int => Column::Int(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::BigInt => Column::BigInt(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::Int128 => Column::Int128(alloc.alloc_slice_fill_copy(length, 0)),
+            ColumnType::Scalar => Column::Scalar(alloc.alloc_slice_fill_copy(length, S::ZERO)),
+            ColumnType::Decimal75(precision, scale) => Column::Decimal75(
+                precision,
+                scale,
+                alloc.alloc_slice_fill_copy(length, S::ZERO),
+            ),
+            ColumnType::TimestampTZ(tu, tz) => {
+                Column::TimestampTZ(tu, tz, alloc.alloc_slice_fill_copy(length, 0))
+            }
+            ColumnType::VarChar => Column::VarChar((
+                alloc.alloc_slice_fill_copy(length, ""),
+                alloc.alloc_slice_fill_copy(length, S::ZERO),
+            )),
+            ColumnType::VarBinary => Column::VarBinary((
+                alloc.alloc_slice_fill_copy(length, &[] as &[u8]),
+                alloc.alloc_slice_fill_copy(length, S::ZERO),
+            )),
+        }
+    }
+
     /// Generate a `Int128` `rho` column [0, 1, 2, ..., length - 1]
     pub fn rho(length: usize, alloc: &'a Bump) -> Self {
         let raw_rho = (0..length as i128).collect::<Vec<_>>();
@@ -198,6 +230,42 @@ impl<'a, S: Scalar> Column<'a, S> {
         }
     }
 
+    /// Convert a column of scalars to a column of `column_type`
+    ///
+    /// Returns `None` if a value does not fit into `column_type`,
+    /// or if values of `column_type` are not determined by their scalars.
+    pub(crate) fn try_from_scalars(
+        alloc: &'a Bump,
+        scalars: &'a [S],
+        column_type: ColumnType,
+    ) -> Option<Self> {
+        fn try_convert<'a, S: Scalar + TryInto<T>, T: Copy>(
+            alloc: &'a Bump,
+            scalars: &[S],
+        ) -> Option<&'a [T]> {
+            let values = scalars
+                .iter()
+                .map(|scalar| (*scalar).try_into().ok())
+                .collect::<Option<Vec<T>>>()?;
+            Some(alloc.alloc_slice_copy(&values))
+        }
+        Some(match column_type {
+            ColumnType::Boolean => Column::Boolean(try_convert(alloc, scalars)?),
+            ColumnType::Uint8 => Column::Uint8(try_convert(alloc, scalars)?),
+            ColumnType::TinyInt => Column::TinyInt(try_convert(alloc, scalars)?),
+            ColumnType::SmallInt => Column::SmallInt(try_convert(alloc, scalars)?),
+            ColumnType::Int => Column::Int(try_convert(alloc, scalars)?),
+            ColumnType::BigInt => Column::BigInt(try_convert(alloc, scalars)?),
+            ColumnType::Int128 => Column::Int128(try_convert(alloc, scalars)?),
+            ColumnType::Decimal75(precision, scale) => Column::Decimal75(precision, scale, scalars),
+            ColumnType::TimestampTZ(tu, tz) => {
+                Column::TimestampTZ(tu, tz, try_convert(alloc, scalars)?)
+            }
+            ColumnType::Scalar => Column::Scalar(scalars),
+            ColumnType::VarChar | ColumnType::VarBinary => return None,
+        })
+    }
+
     /// Returns the column as a slice of booleans if it is a boolean column. Otherwise, returns None.
     pub(crate) fn as_boolean(&self) -> Option<&'a [bool]> {
         match self {
@@ -980,6 +1048,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn we_can_create_columns_of_default_values() {
+        let alloc = Bump::new();
+        let column = Column::<TestScalar>::default_with_length(ColumnType::BigInt, 3, &alloc);
+        assert_eq!(column, Column::BigInt(&[0, 0, 0]));
+
+        let column = Column::<TestScalar>::default_with_length(ColumnType::VarChar, 2, &alloc);
+        assert_eq!(column, Column::VarChar((&["", ""], &[TestScalar::ZERO; 2])));
+        assert_eq!(column.scalar_at(0), Some(TestScalar::from("")));
+
+        let column = Column::<TestScalar>::default_with_length(
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            0,
+            &alloc,
+        );
+        assert_eq!(
+            column,
+            Column::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), &[])
+        );
+    }
+
     #[test]
     fn we_can_get_the_len_of_a_column() {
         let precision = 10;
@@ -1255,4 +1344,40 @@ mod tests {
         );
         assert_eq!(ColumnType::VarChar.min_scalar::<TestScalar>(), None);
     }
+
+    #[test]
+    fn we_can_convert_scalars_to_columns_of_a_type() {
+        let alloc = Bump::new();
+        let scalars = [-1, 0, 300].map(TestScalar::from);
+        assert_eq!(
+            Column::try_from_scalars(&alloc, &scalars, ColumnType::BigInt),
+            Some(Column::BigInt(&[-1, 0, 300]))
+        );
+        let timestamp_type = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc());
+        assert_eq!(
+            Column::try_from_scalars(&alloc, &scalars, timestamp_type),
+            Some(Column::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                &[-1, 0, 300]
+            ))
+        );
+        let decimal_type = ColumnType::Decimal75(Precision::new(3).unwrap(), 1);
+        assert_eq!(
+            Column::try_from_scalars(&alloc, &scalars, decimal_type),
+            Some(Column::Decimal75(
+                Precision::new(3).unwrap(),
+                1,
+                scalars.as_slice()
+            ))
+        );
+        assert_eq!(
+            Column::try_from_scalars(&alloc, &scalars, ColumnType::TinyInt),
+            None
+        );
+        assert_eq!(
+            Column::try_from_scalars(&alloc, &scalars, ColumnType::VarChar),
+            None
+        );
+    }
 }
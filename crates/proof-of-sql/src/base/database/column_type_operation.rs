@@ -4,6 +4,7 @@ use crate::base::{
     math::decimal::{DecimalError, Precision},
 };
 use alloc::{format, string::ToString};
+use proof_of_sql_parser::posql_time::{PoSQLInterval, PoSQLTimeField};
 // For decimal type manipulation please refer to
 // https://learn.microsoft.com/en-us/sql/t-sql/data-types/precision-scale-and-length-transact-sql?view=sql-server-ver16
 
@@ -169,9 +170,259 @@ pub fn try_divide_column_types(
     Ok(ColumnType::Decimal75(precision, scale))
 }
 
+/// Determine the output type of a modulo operation if it is possible
+/// to take the modulo of the two input types. If the types are not compatible, return
+/// an error.
+///
+/// The remainder has the larger of the two scales and can have no more integer digits
+/// than either of the inputs.
+///
+/// # Panics
+///
+/// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
+/// - Panics if `lhs` or `rhs` is an integer, and `lhs.max_integer_type(&rhs)` returns `None`.
+pub fn try_modulo_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if !lhs.is_numeric()
+        || !rhs.is_numeric()
+        || lhs == ColumnType::Scalar
+        || rhs == ColumnType::Scalar
+    {
+        return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: "%".to_string(),
+            left_type: lhs,
+            right_type: rhs,
+        });
+    }
+    if lhs.is_integer() && rhs.is_integer() {
+        // We can unwrap here because we know that both types are integers
+        return Ok(lhs.max_integer_type(&rhs).unwrap());
+    }
+    let left_precision_value =
+        i16::from(lhs.precision_value().expect("Numeric types have precision"));
+    let right_precision_value =
+        i16::from(rhs.precision_value().expect("Numeric types have precision"));
+    let left_scale = lhs.scale().expect("Numeric types have scale");
+    let right_scale = rhs.scale().expect("Numeric types have scale");
+    let scale = left_scale.max(right_scale);
+    let precision_value: i16 = i16::from(scale)
+        + (left_precision_value - i16::from(left_scale))
+            .min(right_precision_value - i16::from(right_scale));
+    let precision = u8::try_from(precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
+/// Determine the output type of a `CASE` expression with branches of the two input types
+/// if the types are compatible. If the types are not compatible, return an error.
+///
+/// Branches of the same type keep their type. Numeric types other than scalars are combined
+/// into the smallest type that can hold the values of both.
+///
+/// # Panics
+///
+/// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
+pub fn try_case_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if lhs == rhs {
+        return Ok(lhs);
+    }
+    if !lhs.is_numeric()
+        || !rhs.is_numeric()
+        || lhs == ColumnType::Scalar
+        || rhs == ColumnType::Scalar
+    {
+        return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: "CASE".to_string(),
+            left_type: lhs,
+            right_type: rhs,
+        });
+    }
+    if lhs.is_integer() && rhs.is_integer() {
+        // A signed integer type needs 16 bits to hold every `Uint8`
+        let widen_uint8 = |column_type| match column_type {
+            ColumnType::Uint8 => ColumnType::SmallInt,
+            _ => column_type,
+        };
+        // We can unwrap here because we know that both types are integers
+        return Ok(widen_uint8(lhs)
+            .max_integer_type(&widen_uint8(rhs))
+            .unwrap());
+    }
+    let left_precision_value =
+        i16::from(lhs.precision_value().expect("Numeric types have precision"));
+    let right_precision_value =
+        i16::from(rhs.precision_value().expect("Numeric types have precision"));
+    let left_scale = lhs.scale().expect("Numeric types have scale");
+    let right_scale = rhs.scale().expect("Numeric types have scale");
+    let scale = left_scale.max(right_scale);
+    let precision_value: i16 = i16::from(scale)
+        + (left_precision_value - i16::from(left_scale))
+            .max(right_precision_value - i16::from(right_scale));
+    let precision = u8::try_from(precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
+/// Determine the output type of a cast if it is possible to cast `from` to `to`.
+/// If the cast is not supported, return an error.
+///
+/// Booleans and numeric types other than scalars can be cast to numeric types other than scalars.
+/// Timestamps can be cast to timestamps of any time unit and to and from `BigInt`.
+/// Every type can be cast to itself.
+pub fn try_cast_column_types(
+    from: ColumnType,
+    to: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    let is_castable_numeric =
+        |column_type: ColumnType| column_type.is_numeric() && column_type != ColumnType::Scalar;
+    let is_supported = from == to
+        || (matches!(from, ColumnType::Boolean) || is_castable_numeric(from))
+            && is_castable_numeric(to)
+        || matches!(
+            (from, to),
+            (ColumnType::TimestampTZ(_, _), ColumnType::TimestampTZ(_, _))
+                | (ColumnType::TimestampTZ(_, _), ColumnType::BigInt)
+                | (ColumnType::BigInt, ColumnType::TimestampTZ(_, _))
+        );
+    if is_supported {
+        Ok(to)
+    } else {
+        Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: format!("CAST AS {to}"),
+            operand_type: from,
+        })
+    }
+}
+
+/// Determine the output type of adding `interval` to `from` if it is possible.
+/// If the operation is not supported, return an error.
+///
+/// Intervals can be added to timestamps whose time unit divides them.
+pub fn try_add_interval_column_type(
+    from: ColumnType,
+    interval: PoSQLInterval,
+) -> ColumnOperationResult<ColumnType> {
+    match from {
+        ColumnType::TimestampTZ(timeunit, _) if interval.count_of(timeunit).is_some() => Ok(from),
+        _ => Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: format!("+ INTERVAL '{interval}'"),
+            operand_type: from,
+        }),
+    }
+}
+
+/// Determine the output type of extracting `field` from `from` if it is possible.
+/// If the operation is not supported, return an error.
+///
+/// Seconds, minutes, hours, the day of the week and the epoch can be extracted from timestamps.
+pub fn try_extract_column_type(
+    field: PoSQLTimeField,
+    from: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    match (field, from) {
+        (
+            PoSQLTimeField::Second
+            | PoSQLTimeField::Minute
+            | PoSQLTimeField::Hour
+            | PoSQLTimeField::DayOfWeek
+            | PoSQLTimeField::Epoch,
+            ColumnType::TimestampTZ(_, _),
+        ) => Ok(ColumnType::BigInt),
+        _ => Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: format!("EXTRACT({field})"),
+            operand_type: from,
+        }),
+    }
+}
+
+/// Determine the output type of truncating `from` to `field` if it is possible.
+/// If the operation is not supported, return an error.
+///
+/// Timestamps can be truncated to fields from microseconds to weeks.
+pub fn try_date_trunc_column_type(
+    field: PoSQLTimeField,
+    from: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    match (field, from) {
+        (
+            PoSQLTimeField::Microsecond
+            | PoSQLTimeField::Millisecond
+            | PoSQLTimeField::Second
+            | PoSQLTimeField::Minute
+            | PoSQLTimeField::Hour
+            | PoSQLTimeField::Day
+            | PoSQLTimeField::Week,
+            ColumnType::TimestampTZ(_, _),
+        ) => Ok(from),
+        _ => Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: format!("DATE_TRUNC('{field}')"),
+            operand_type: from,
+        }),
+    }
+}
+
+/// Determine the type that values of `from` are summed as when they are averaged
+/// if it is possible. If the operation is not supported, return an error.
+///
+/// Integers are summed as decimals with the same precision and a scale of zero.
+/// The average is the sum divided by the `BIGINT` count, whose type is given by
+/// [`try_divide_column_types`], e.g. `DECIMAL(39, 20)` for `BIGINT` values.
+///
+/// # Panics
+///
+/// - Panics if `from` does not have a precision when it is expected to be a numeric type.
+pub fn try_average_sum_column_type(from: ColumnType) -> ColumnOperationResult<ColumnType> {
+    if !from.is_numeric() || from == ColumnType::Scalar {
+        return Err(ColumnOperationError::UnaryOperationInvalidColumnType {
+            operator: "AVG".to_string(),
+            operand_type: from,
+        });
+    }
+    let sum_type = if from.is_integer() {
+        let precision_value = from
+            .precision_value()
+            .expect("Numeric types have precision");
+        let precision = Precision::new(precision_value)
+            .map_err(|source| ColumnOperationError::DecimalConversionError { source })?;
+        ColumnType::Decimal75(precision, 0)
+    } else {
+        from
+    };
+    try_divide_column_types(sum_type, ColumnType::BigInt)?;
+    Ok(sum_type)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
     #[test]
     fn we_can_add_numeric_types() {
@@ -750,4 +1001,266 @@ mod test {
             })
         ));
     }
+
+    #[test]
+    fn we_can_modulo_numeric_types() {
+        // lhs and rhs are integers
+        let lhs = ColumnType::TinyInt;
+        let rhs = ColumnType::BigInt;
+        let actual = try_modulo_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::BigInt;
+        assert_eq!(expected, actual);
+
+        // lhs is an integer and rhs is a decimal
+        let lhs = ColumnType::Int;
+        let rhs = ColumnType::Decimal75(Precision::new(5).unwrap(), 2);
+        let actual = try_modulo_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(5).unwrap(), 2);
+        assert_eq!(expected, actual);
+
+        // lhs and rhs are decimals with different scales
+        let lhs = ColumnType::Decimal75(Precision::new(20).unwrap(), 3);
+        let rhs = ColumnType::Decimal75(Precision::new(10).unwrap(), 5);
+        let actual = try_modulo_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(10).unwrap(), 5);
+        assert_eq!(expected, actual);
+
+        // lhs and rhs are decimals with negative scales
+        let lhs = ColumnType::Decimal75(Precision::new(10).unwrap(), -2);
+        let rhs = ColumnType::Decimal75(Precision::new(15).unwrap(), -1);
+        let actual = try_modulo_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(11).unwrap(), -1);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_cannot_modulo_non_numeric_or_scalar_types() {
+        let lhs = ColumnType::TinyInt;
+        let rhs = ColumnType::VarChar;
+        assert!(matches!(
+            try_modulo_column_types(lhs, rhs),
+            Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+        ));
+
+        let lhs = ColumnType::Scalar;
+        let rhs = ColumnType::BigInt;
+        assert!(matches!(
+            try_modulo_column_types(lhs, rhs),
+            Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_cast_numeric_boolean_and_timestamp_types() {
+        let decimal = ColumnType::Decimal75(Precision::new(10).unwrap(), 2);
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc());
+        let millis = ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc());
+        for (from, to) in [
+            (ColumnType::Int, ColumnType::BigInt),
+            (ColumnType::BigInt, ColumnType::TinyInt),
+            (ColumnType::SmallInt, ColumnType::Uint8),
+            (ColumnType::Int128, decimal),
+            (decimal, ColumnType::Int),
+            (ColumnType::Boolean, ColumnType::Int),
+            (ColumnType::Boolean, decimal),
+            (timestamp, millis),
+            (millis, ColumnType::BigInt),
+            (ColumnType::BigInt, timestamp),
+            (ColumnType::VarChar, ColumnType::VarChar),
+            (ColumnType::Scalar, ColumnType::Scalar),
+        ] {
+            assert_eq!(try_cast_column_types(from, to).unwrap(), to);
+        }
+    }
+
+    #[test]
+    fn we_cannot_cast_unsupported_types() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc());
+        for (from, to) in [
+            (ColumnType::Int, ColumnType::Boolean),
+            (ColumnType::VarChar, ColumnType::BigInt),
+            (ColumnType::BigInt, ColumnType::VarChar),
+            (ColumnType::Scalar, ColumnType::BigInt),
+            (ColumnType::BigInt, ColumnType::Scalar),
+            (timestamp, ColumnType::Int),
+            (ColumnType::Int, timestamp),
+            (ColumnType::VarBinary, ColumnType::VarChar),
+        ] {
+            assert!(matches!(
+                try_cast_column_types(from, to),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_combine_the_types_of_case_branches() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc());
+        for (lhs, rhs, expected) in [
+            (
+                ColumnType::VarChar,
+                ColumnType::VarChar,
+                ColumnType::VarChar,
+            ),
+            (
+                ColumnType::Boolean,
+                ColumnType::Boolean,
+                ColumnType::Boolean,
+            ),
+            (timestamp, timestamp, timestamp),
+            (ColumnType::Int, ColumnType::BigInt, ColumnType::BigInt),
+            (ColumnType::Uint8, ColumnType::TinyInt, ColumnType::SmallInt),
+            (ColumnType::Uint8, ColumnType::Int, ColumnType::Int),
+            (
+                ColumnType::Int,
+                ColumnType::Decimal75(Precision::new(5).unwrap(), 2),
+                ColumnType::Decimal75(Precision::new(12).unwrap(), 2),
+            ),
+            (
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 4),
+                ColumnType::Decimal75(Precision::new(8).unwrap(), 1),
+                ColumnType::Decimal75(Precision::new(11).unwrap(), 4),
+            ),
+        ] {
+            assert_eq!(try_case_column_types(lhs, rhs).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn we_cannot_combine_incompatible_types_of_case_branches() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc());
+        for (lhs, rhs) in [
+            (ColumnType::VarChar, ColumnType::BigInt),
+            (ColumnType::Boolean, ColumnType::Int),
+            (ColumnType::Scalar, ColumnType::BigInt),
+            (timestamp, ColumnType::BigInt),
+        ] {
+            assert!(matches!(
+                try_case_column_types(lhs, rhs),
+                Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_case_column_types(
+                ColumnType::Decimal75(Precision::new(75).unwrap(), -10),
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 10),
+            ),
+            Err(ColumnOperationError::DecimalConversionError { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_add_intervals_to_timestamps() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc());
+        let interval = PoSQLInterval::try_from("1 day 2 milliseconds").unwrap();
+        assert_eq!(
+            try_add_interval_column_type(timestamp, interval).unwrap(),
+            timestamp
+        );
+        assert_eq!(
+            try_add_interval_column_type(timestamp, -interval).unwrap(),
+            timestamp
+        );
+    }
+
+    #[test]
+    fn we_cannot_add_intervals_to_other_types_or_finer_than_the_time_unit() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc());
+        let interval = PoSQLInterval::try_from("1 millisecond").unwrap();
+        for from in [timestamp, ColumnType::BigInt, ColumnType::VarChar] {
+            assert!(matches!(
+                try_add_interval_column_type(from, interval),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_extract_fields_from_timestamps() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::new(3_600));
+        for field in [
+            PoSQLTimeField::Second,
+            PoSQLTimeField::Minute,
+            PoSQLTimeField::Hour,
+            PoSQLTimeField::DayOfWeek,
+            PoSQLTimeField::Epoch,
+        ] {
+            assert_eq!(
+                try_extract_column_type(field, timestamp).unwrap(),
+                ColumnType::BigInt
+            );
+        }
+        for (field, from) in [
+            (PoSQLTimeField::Day, timestamp),
+            (PoSQLTimeField::Millisecond, timestamp),
+            (PoSQLTimeField::Hour, ColumnType::BigInt),
+        ] {
+            assert!(matches!(
+                try_extract_column_type(field, from),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_truncate_timestamps_to_fields() {
+        let timestamp = ColumnType::TimestampTZ(PoSQLTimeUnit::Nanosecond, PoSQLTimeZone::utc());
+        for field in [
+            PoSQLTimeField::Microsecond,
+            PoSQLTimeField::Millisecond,
+            PoSQLTimeField::Second,
+            PoSQLTimeField::Minute,
+            PoSQLTimeField::Hour,
+            PoSQLTimeField::Day,
+            PoSQLTimeField::Week,
+        ] {
+            assert_eq!(
+                try_date_trunc_column_type(field, timestamp).unwrap(),
+                timestamp
+            );
+        }
+        for (field, from) in [
+            (PoSQLTimeField::DayOfWeek, timestamp),
+            (PoSQLTimeField::Epoch, timestamp),
+            (PoSQLTimeField::Day, ColumnType::Int),
+        ] {
+            assert!(matches!(
+                try_date_trunc_column_type(field, from),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn we_can_get_the_sum_type_of_averages() {
+        assert_eq!(
+            try_average_sum_column_type(ColumnType::BigInt).unwrap(),
+            ColumnType::Decimal75(Precision::new(19).unwrap(), 0)
+        );
+        assert_eq!(
+            try_divide_column_types(
+                try_average_sum_column_type(ColumnType::BigInt).unwrap(),
+                ColumnType::BigInt
+            )
+            .unwrap(),
+            ColumnType::Decimal75(Precision::new(39).unwrap(), 20)
+        );
+        let decimal = ColumnType::Decimal75(Precision::new(10).unwrap(), 2);
+        assert_eq!(try_average_sum_column_type(decimal).unwrap(), decimal);
+        for from in [
+            ColumnType::VarChar,
+            ColumnType::Boolean,
+            ColumnType::Scalar,
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        ] {
+            assert!(matches!(
+                try_average_sum_column_type(from),
+                Err(ColumnOperationError::UnaryOperationInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_average_sum_column_type(ColumnType::Decimal75(Precision::new(60).unwrap(), 0)),
+            Err(ColumnOperationError::DecimalConversionError { .. })
+        ));
+    }
 }
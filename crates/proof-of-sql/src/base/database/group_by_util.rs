@@ -7,7 +7,7 @@ use crate::base::{
     if_rayon,
     scalar::Scalar,
 };
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
@@ -68,26 +68,7 @@ pub fn aggregate_columns<'a, S: Scalar>(
         return Err(AggregateColumnsError::ColumnLengthMismatch);
     }
 
-    // `filtered_indexes` is a vector of indexes of the rows that are selected. We sort this vector
-    // so that all the rows in the same group are next to each other.
-    let mut filtered_indexes: Vec<_> = selection_column_in
-        .iter()
-        .enumerate()
-        .filter(|&(_, &b)| b)
-        .map(|(i, _)| i)
-        .collect();
-    if_rayon!(
-        filtered_indexes.par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(
-            group_by_columns_in,
-            a,
-            b
-        )),
-        filtered_indexes.sort_unstable_by(|&a, &b| compare_indexes_by_columns(
-            group_by_columns_in,
-            a,
-            b
-        ))
-    );
+    let filtered_indexes = sorted_filtered_indexes(group_by_columns_in, selection_column_in);
 
     // `group_by_result_indexes` gives a single index for each group in `filtered_indexes`. It does
     // not matter which index is chosen for each group, so we choose the first one. This is only used
@@ -142,6 +123,57 @@ pub fn aggregate_columns<'a, S: Scalar>(
     })
 }
 
+/// Returns a vector of indexes of the rows that are selected, sorted so that
+/// all the rows in the same group are next to each other.
+fn sorted_filtered_indexes<S: Scalar>(
+    group_by_columns_in: &[Column<S>],
+    selection_column_in: &[bool],
+) -> Vec<usize> {
+    let mut filtered_indexes: Vec<_> = selection_column_in
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| b)
+        .map(|(i, _)| i)
+        .collect();
+    if_rayon!(
+        filtered_indexes.par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(
+            group_by_columns_in,
+            a,
+            b
+        )),
+        filtered_indexes.sort_unstable_by(|&a, &b| compare_indexes_by_columns(
+            group_by_columns_in,
+            a,
+            b
+        ))
+    );
+    filtered_indexes
+}
+
+/// Returns, for each row, the index of the group that the row belongs to
+/// in the output of [`aggregate_columns`], or `None` if the row is not selected.
+pub(crate) fn group_indexes_by_columns<S: Scalar>(
+    group_by_columns_in: &[Column<S>],
+    selection_column_in: &[bool],
+) -> Vec<Option<usize>> {
+    let filtered_indexes = sorted_filtered_indexes(group_by_columns_in, selection_column_in);
+    let mut group_indexes = vec![None; selection_column_in.len()];
+    let mut group_index = 0;
+    for (position, &index) in filtered_indexes.iter().enumerate() {
+        if position > 0
+            && compare_indexes_by_columns(
+                group_by_columns_in,
+                filtered_indexes[position - 1],
+                index,
+            ) != Ordering::Equal
+        {
+            group_index += 1;
+        }
+        group_indexes[index] = Some(group_index);
+    }
+    group_indexes
+}
+
 /// Returns a slice with the lifetime of `alloc` that contains the grouped sums of `column`.
 /// The `counts` slice contains the number of elements in each group and the `indexes` slice
 /// contains the indexes of the elements in `column`.
@@ -689,3 +689,39 @@ fn we_can_aggregate_columns_with_varbinary_in_group_by() {
     assert!(aggregate_result.max_columns.is_empty());
     assert!(aggregate_result.min_columns.is_empty());
 }
+
+#[test]
+fn we_can_get_group_indexes_by_columns() {
+    let slice_a = &[3, 3, 3, 2, 2, 1, 1, 2, 2, 3, 3, 3];
+    let slice_b = &[1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2];
+    let selection = &[
+        true, false, true, false, true, false, true, false, true, false, true, false,
+    ];
+    let column_a = Column::BigInt::<TestScalar>(slice_a);
+    let column_b = Column::Int(slice_b);
+    let group_indexes = group_indexes_by_columns(&[column_a, column_b], selection);
+    assert_eq!(
+        group_indexes,
+        vec![
+            Some(2),
+            None,
+            Some(2),
+            None,
+            Some(1),
+            None,
+            Some(0),
+            None,
+            Some(1),
+            None,
+            Some(2),
+            None
+        ]
+    );
+}
+
+#[test]
+fn we_can_get_group_indexes_with_empty_group_by() {
+    let selection = &[false, true, true];
+    let group_indexes = group_indexes_by_columns::<TestScalar>(&[], selection);
+    assert_eq!(group_indexes, vec![None, Some(0), Some(0)]);
+}
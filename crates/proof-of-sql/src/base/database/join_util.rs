@@ -6,7 +6,7 @@ use super::{
     TableOperationError, TableOperationResult, TableOptions,
 };
 use crate::base::scalar::Scalar;
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
@@ -216,11 +216,62 @@ pub(crate) fn get_sort_merge_join_indexes<'a, S: Scalar>(
     .collect::<Vec<(usize, usize)>>()
 }
 
+/// Get the indexes of the rows of a table with `num_rows` rows that do not appear in `matched_row_indexes`.
+///
+/// These are the rows of one side of a join that have no match on the other side.
+/// The result is sorted in increasing order.
+pub(crate) fn get_unmatched_row_indexes(
+    num_rows: usize,
+    matched_row_indexes: &[usize],
+) -> Vec<usize> {
+    let mut is_matched = vec![false; num_rows];
+    for &index in matched_row_indexes {
+        is_matched[index] = true;
+    }
+    (0..num_rows).filter(|&i| !is_matched[i]).collect()
+}
+
+/// Get the rows of `table` whose indexes do not appear in `matched_row_indexes`, in their original order.
+///
+/// This is used to get the unmatched rows of the preserved side of an outer join.
+pub(crate) fn get_unmatched_rows_of_table<'a, S: Scalar>(
+    table: &Table<'a, S>,
+    matched_row_indexes: &[usize],
+    alloc: &'a Bump,
+) -> ColumnOperationResult<Vec<Column<'a, S>>> {
+    let unmatched_row_indexes = get_unmatched_row_indexes(table.num_rows(), matched_row_indexes);
+    table
+        .columns()
+        .map(|column| apply_column_to_indexes(column, alloc, &unmatched_row_indexes))
+        .collect()
+}
+
+/// Get the rows of `table` whose indexes appear in `matched_row_indexes`, once each and in their original order.
+///
+/// This is used to get the result of a semi join.
+pub(crate) fn get_matched_rows_of_table<'a, S: Scalar>(
+    table: &Table<'a, S>,
+    matched_row_indexes: &[usize],
+    alloc: &'a Bump,
+) -> ColumnOperationResult<Vec<Column<'a, S>>> {
+    let matched_row_indexes = matched_row_indexes
+        .iter()
+        .copied()
+        .sorted_unstable()
+        .dedup()
+        .collect::<Vec<_>>();
+    table
+        .columns()
+        .map(|column| apply_column_to_indexes(column, alloc, &matched_row_indexes))
+        .collect()
+}
+
 /// Apply sort merge join indexes
 ///
 /// Currently we only support INNER JOINs and only support joins on equalities.
+/// There may be any number of join columns, in which case rows match if they agree on all of them.
 /// In terms of ordering of columns we retain
-/// 1. Join columns
+/// 1. Join columns, in the order of `left_join_column_indexes`
 /// 2. Other columns from the left table
 /// 3. Other columns from the right table
 /// # Panics
@@ -724,6 +775,20 @@ mod tests {
         assert_eq!(row_indexes, vec![(1, 3), (1, 4), (3, 5), (4, 3), (4, 4)]);
     }
 
+    #[test]
+    fn we_can_get_sort_merge_join_indexes_two_tables_on_multiple_columns() {
+        let left_on = vec![
+            Column::<TestScalar>::Int(&[1_i32, 1, 2, 2, 3]),
+            Column::<TestScalar>::BigInt(&[10_i64, 20, 10, 20, 10]),
+        ];
+        let right_on = vec![
+            Column::<TestScalar>::Int(&[2_i32, 1, 1, 3, 2]),
+            Column::<TestScalar>::BigInt(&[10_i64, 20, 20, 30, 20]),
+        ];
+        let row_indexes = get_sort_merge_join_indexes(&left_on, &right_on, 5, 5);
+        assert_eq!(row_indexes, vec![(1, 1), (1, 2), (2, 0), (3, 4)]);
+    }
+
     #[test]
     fn we_can_get_sort_merge_join_indexes_two_tables_with_empty_results() {
         let left_on = vec![Column::<TestScalar>::Int(&[3_i32, 15, 9, 14, 15, 7])];
@@ -753,6 +818,58 @@ mod tests {
         assert!(row_indexes.is_empty());
     }
 
+    #[test]
+    fn we_can_get_unmatched_row_indexes() {
+        assert_eq!(
+            get_unmatched_row_indexes(6, &[3, 1, 1, 4, 4]),
+            vec![0, 2, 5]
+        );
+        assert_eq!(get_unmatched_row_indexes(3, &[]), vec![0, 1, 2]);
+        assert_eq!(get_unmatched_row_indexes(2, &[1, 0]), Vec::<usize>::new());
+        assert_eq!(get_unmatched_row_indexes(0, &[]), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn we_can_get_unmatched_rows_of_table() {
+        let bump = Bump::new();
+        let table = Table::<'_, TestScalar>::try_from_iter_with_options(
+            vec![
+                ("a".into(), Column::SmallInt(&[8_i16, 2, 5, 1])),
+                ("b".into(), Column::Int(&[3_i32, 5, 9, 4])),
+            ],
+            TableOptions::default(),
+        )
+        .expect("Table creation should not fail");
+        let result = get_unmatched_rows_of_table(&table, &[2, 0, 2], &bump).unwrap();
+        assert_eq!(
+            result,
+            vec![Column::SmallInt(&[2_i16, 1]), Column::Int(&[5_i32, 4])]
+        );
+    }
+
+    #[test]
+    fn we_can_get_matched_rows_of_table() {
+        let bump = Bump::new();
+        let table = Table::<'_, TestScalar>::try_from_iter_with_options(
+            vec![
+                ("a".into(), Column::SmallInt(&[8_i16, 2, 5, 1])),
+                ("b".into(), Column::Int(&[3_i32, 5, 9, 4])),
+            ],
+            TableOptions::default(),
+        )
+        .expect("Table creation should not fail");
+        let result = get_matched_rows_of_table(&table, &[2, 0, 2], &bump).unwrap();
+        assert_eq!(
+            result,
+            vec![Column::SmallInt(&[8_i16, 5]), Column::Int(&[3_i32, 9])]
+        );
+        let result = get_matched_rows_of_table(&table, &[], &bump).unwrap();
+        assert_eq!(
+            result,
+            vec![Column::SmallInt(&[0_i16; 0]), Column::Int(&[0_i32; 0])]
+        );
+    }
+
     #[test]
     fn we_can_apply_sort_merge_join_indexes_two_tables() {
         let bump = Bump::new();
@@ -796,6 +913,54 @@ mod tests {
         assert_eq!(result[2], Column::BigInt(&[7_i64, 8, 9, 8, 9]));
     }
 
+    #[test]
+    fn we_can_apply_sort_merge_join_indexes_two_tables_on_multiple_columns() {
+        let bump = Bump::new();
+        let tenant: Ident = "tenant".into();
+        let order: Ident = "order".into();
+        let x: Ident = "x".into();
+        let y: Ident = "y".into();
+
+        let left = Table::<'_, TestScalar>::try_from_iter_with_options(
+            vec![
+                (tenant.clone(), Column::Int(&[1_i32, 1, 2, 2, 3])),
+                (order.clone(), Column::BigInt(&[10_i64, 20, 10, 20, 10])),
+                (x.clone(), Column::SmallInt(&[5_i16, 6, 7, 8, 9])),
+            ],
+            TableOptions::default(),
+        )
+        .expect("Table creation should not fail");
+        let right = Table::<'_, TestScalar>::try_from_iter_with_options(
+            vec![
+                (order.clone(), Column::BigInt(&[10_i64, 20, 20, 30, 20])),
+                (tenant.clone(), Column::Int(&[2_i32, 1, 1, 3, 2])),
+                (y.clone(), Column::BigInt(&[100_i64, 101, 102, 103, 104])),
+            ],
+            TableOptions::default(),
+        )
+        .expect("Table creation should not fail");
+
+        let left_row_indexes = vec![1, 1, 2, 3];
+        let right_row_indexes = vec![1, 2, 0, 4];
+
+        let result = apply_sort_merge_join_indexes(
+            &left,
+            &right,
+            &[0, 1],
+            &[1, 0],
+            &left_row_indexes,
+            &right_row_indexes,
+            &bump,
+        )
+        .unwrap();
+
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], Column::Int(&[1_i32, 1, 2, 2]));
+        assert_eq!(result[1], Column::BigInt(&[20_i64, 20, 10, 20]));
+        assert_eq!(result[2], Column::SmallInt(&[6_i16, 6, 7, 8]));
+        assert_eq!(result[3], Column::BigInt(&[101_i64, 102, 100, 104]));
+    }
+
     #[test]
     fn we_can_apply_sort_merge_join_indexes_two_tables_with_empty_results() {
         let bump = Bump::new();
@@ -14,7 +14,10 @@ mod slice_decimal_operation;
 
 mod column_type_operation;
 pub use column_type_operation::{
-    try_add_subtract_column_types, try_divide_column_types, try_multiply_column_types,
+    try_add_interval_column_type, try_add_subtract_column_types, try_average_sum_column_type,
+    try_case_column_types, try_cast_column_types, try_date_trunc_column_type,
+    try_divide_column_types, try_extract_column_type, try_modulo_column_types,
+    try_multiply_column_types,
 };
 
 mod column_arithmetic_operation;
@@ -24,7 +27,7 @@ mod column_comparison_operation;
 pub(super) use column_comparison_operation::{ComparisonOp, EqualOp, GreaterThanOp, LessThanOp};
 
 mod column_index_operation;
-pub(super) use column_index_operation::apply_column_to_indexes;
+pub(crate) use column_index_operation::apply_column_to_indexes;
 
 mod column_repetition_operation;
 pub(super) use column_repetition_operation::{ColumnRepeatOp, ElementwiseRepeatOp, RepetitionOp};
@@ -65,6 +68,20 @@ pub use owned_column_error::{OwnedColumnError, OwnedColumnResult};
 /// TODO: add docs
 pub(crate) mod owned_column_operation;
 
+mod nullable_column;
+pub use nullable_column::{
+    is_presence_column_ident, presence_column_ident, presence_column_owner, presence_column_ref,
+    NullableOwnedColumn, PRESENCE_COLUMN_SUFFIX,
+};
+
+mod varchar_encoding;
+pub use varchar_encoding::{
+    is_varchar_encoding_column_ident, varchar_key, varchar_key_column_ident,
+    varchar_key_column_ref, varchar_key_limbs, varchar_length, varchar_length_column_ident,
+    varchar_length_column_ref, varchar_prefix_key_limb_bounds, VARCHAR_KEY_COLUMN_SUFFIX,
+    VARCHAR_KEY_PREFIX_BYTES, VARCHAR_LENGTH_COLUMN_SUFFIX,
+};
+
 mod owned_table;
 pub use owned_table::OwnedTable;
 pub(crate) use owned_table::{OwnedTableError, TableCoercionError};
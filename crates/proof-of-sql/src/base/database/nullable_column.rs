@@ -0,0 +1,204 @@
+//! Nullable columns are represented as a column of values together with a presence bitmap.
+//!
+//! The values of a nullable column are stored as a regular column in which every null slot holds
+//! the default value of the column type. The presence bitmap is `true` exactly where the value is
+//! not null. It is stored with its column, in [`Table`](super::Table) and
+//! [`OwnedTable`](super::OwnedTable), and it is committed to together with its column in
+//! [`ColumnCommitments`](crate::base::commitment::ColumnCommitments).
+//!
+//! Proofs refer to the presence of a column through the reference returned by
+//! [`presence_column_ref`], which accessors resolve to the presence bitmap of the column.
+use super::{ColumnRef, ColumnType, OwnedColumn, OwnedColumnError, OwnedColumnResult};
+use crate::base::scalar::Scalar;
+use alloc::{format, string::String, vec::Vec};
+use sqlparser::ast::Ident;
+
+/// The suffix appended to the identifier of a column to get the identifier of its presence.
+///
+/// `$` can not appear in an identifier parsed from SQL, so the presence of a column can not be
+/// referenced directly in a query.
+pub const PRESENCE_COLUMN_SUFFIX: &str = "$presence";
+
+/// Returns the identifier that refers to the presence of the column `ident`.
+#[must_use]
+pub fn presence_column_ident(ident: &Ident) -> Ident {
+    Ident::new(format!("{}{PRESENCE_COLUMN_SUFFIX}", ident.value))
+}
+
+/// Returns whether `ident` is the identifier of a presence column.
+#[must_use]
+pub fn is_presence_column_ident(ident: &Ident) -> bool {
+    ident.value.ends_with(PRESENCE_COLUMN_SUFFIX)
+}
+
+/// Returns the identifier of the column whose presence `ident` refers to,
+/// or `None` if `ident` is not the identifier of a presence column.
+#[must_use]
+pub fn presence_column_owner(ident: &Ident) -> Option<Ident> {
+    ident
+        .value
+        .strip_suffix(PRESENCE_COLUMN_SUFFIX)
+        .map(Ident::new)
+}
+
+/// Returns the reference to the presence of the column referenced by `column_ref`.
+#[must_use]
+pub fn presence_column_ref(column_ref: &ColumnRef) -> ColumnRef {
+    ColumnRef::new(
+        column_ref.table_ref(),
+        presence_column_ident(&column_ref.column_id()),
+        ColumnType::Boolean,
+    )
+}
+
+/// An [`OwnedColumn`] together with an optional presence bitmap.
+///
+/// A missing presence bitmap means that no value is null.
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct NullableOwnedColumn<S: Scalar> {
+    values: OwnedColumn<S>,
+    presence: Option<Vec<bool>>,
+}
+
+impl<S: Scalar> NullableOwnedColumn<S> {
+    /// Creates a nullable column from its values and presence.
+    ///
+    /// Values at null positions are replaced with the default value of the column type
+    /// so that the commitment to the values does not depend on them.
+    pub fn try_new(values: OwnedColumn<S>, presence: Option<Vec<bool>>) -> OwnedColumnResult<Self> {
+        match presence {
+            Some(presence) if presence.len() != values.len() => {
+                Err(OwnedColumnError::PresenceLengthMismatch {
+                    values_len: values.len(),
+                    presence_len: presence.len(),
+                })
+            }
+            Some(presence) => Ok(Self {
+                values: default_null_values(values, &presence),
+                presence: Some(presence),
+            }),
+            None => Ok(Self {
+                values,
+                presence: None,
+            }),
+        }
+    }
+
+    /// Returns the values of the column. Null slots hold the default value of the column type.
+    #[must_use]
+    pub fn values(&self) -> &OwnedColumn<S> {
+        &self.values
+    }
+
+    /// Returns the presence bitmap, if any value may be null.
+    #[must_use]
+    pub fn presence(&self) -> Option<&[bool]> {
+        self.presence.as_deref()
+    }
+
+    /// Returns the number of rows in the column.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether the column has no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the values and the presence of the column.
+    #[must_use]
+    pub fn into_parts(self) -> (OwnedColumn<S>, Option<Vec<bool>>) {
+        (self.values, self.presence)
+    }
+}
+
+fn default_nulls<T: Default>(values: Vec<T>, presence: &[bool]) -> Vec<T> {
+    values
+        .into_iter()
+        .zip(presence)
+        .map(|(value, &present)| if present { value } else { T::default() })
+        .collect()
+}
+
+fn default_null_values<S: Scalar>(values: OwnedColumn<S>, presence: &[bool]) -> OwnedColumn<S> {
+    match values {
+        OwnedColumn::Boolean(col) => OwnedColumn::Boolean(default_nulls(col, presence)),
+        OwnedColumn::Uint8(col) => OwnedColumn::Uint8(default_nulls(col, presence)),
+        OwnedColumn::TinyInt(col) => OwnedColumn::TinyInt(default_nulls(col, presence)),
+        OwnedColumn::SmallInt(col) => OwnedColumn::SmallInt(default_nulls(col, presence)),
+        OwnedColumn::Int(col) => OwnedColumn::Int(default_nulls(col, presence)),
+        OwnedColumn::BigInt(col) => OwnedColumn::BigInt(default_nulls(col, presence)),
+        OwnedColumn::VarChar(col) => OwnedColumn::VarChar(default_nulls::<String>(col, presence)),
+        OwnedColumn::VarBinary(col) => OwnedColumn::VarBinary(default_nulls(col, presence)),
+        OwnedColumn::Int128(col) => OwnedColumn::Int128(default_nulls(col, presence)),
+        OwnedColumn::Decimal75(precision, scale, col) => {
+            OwnedColumn::Decimal75(precision, scale, default_nulls(col, presence))
+        }
+        OwnedColumn::Scalar(col) => OwnedColumn::Scalar(default_nulls(col, presence)),
+        OwnedColumn::TimestampTZ(time_unit, timezone, col) => {
+            OwnedColumn::TimestampTZ(time_unit, timezone, default_nulls(col, presence))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{database::TableRef, scalar::test_scalar::TestScalar};
+    use alloc::{string::ToString, vec};
+
+    #[test]
+    fn we_can_get_presence_column_identifiers() {
+        let ident = Ident::new("a");
+        let presence = presence_column_ident(&ident);
+        assert_eq!(presence, Ident::new("a$presence"));
+        assert!(is_presence_column_ident(&presence));
+        assert!(!is_presence_column_ident(&ident));
+        assert_eq!(presence_column_owner(&presence), Some(ident.clone()));
+        assert_eq!(presence_column_owner(&ident), None);
+
+        let column_ref = ColumnRef::new(TableRef::new("sxt", "table"), ident, ColumnType::BigInt);
+        assert_eq!(
+            presence_column_ref(&column_ref),
+            ColumnRef::new(TableRef::new("sxt", "table"), presence, ColumnType::Boolean)
+        );
+    }
+
+    #[test]
+    fn we_can_create_a_nullable_column_with_null_values_defaulted() {
+        let column = NullableOwnedColumn::<TestScalar>::try_new(
+            OwnedColumn::VarChar(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+            Some(vec![true, false, true]),
+        )
+        .unwrap();
+        assert_eq!(
+            column.values(),
+            &OwnedColumn::VarChar(vec!["a".to_string(), String::new(), "c".to_string()])
+        );
+        assert_eq!(column.presence(), Some(&[true, false, true][..]));
+        assert_eq!(column.len(), 3);
+        assert!(!column.is_empty());
+
+        let column =
+            NullableOwnedColumn::<TestScalar>::try_new(OwnedColumn::BigInt(vec![1, 2]), None)
+                .unwrap();
+        assert_eq!(column.into_parts(), (OwnedColumn::BigInt(vec![1, 2]), None));
+    }
+
+    #[test]
+    fn we_cannot_create_a_nullable_column_with_mismatched_presence() {
+        assert_eq!(
+            NullableOwnedColumn::<TestScalar>::try_new(
+                OwnedColumn::BigInt(vec![1, 2]),
+                Some(vec![true])
+            ),
+            Err(OwnedColumnError::PresenceLengthMismatch {
+                values_len: 2,
+                presence_len: 1
+            })
+        );
+    }
+}
@@ -1,9 +1,12 @@
 //! Contains the utility functions for ordering.
 use crate::base::{
-    database::{Column, OwnedColumn, TableOperationError, TableOperationResult},
+    database::{
+        apply_column_to_indexes, Column, OwnedColumn, TableOperationError, TableOperationResult,
+    },
     scalar::{Scalar, ScalarExt},
 };
 use alloc::vec::Vec;
+use bumpalo::Bump;
 use core::cmp::Ordering;
 
 /// A list of pairs of column indexes to order by and their respective order by directions.
@@ -37,6 +40,54 @@ pub(crate) fn compare_indexes_by_columns<S: Scalar>(
         .unwrap_or(Ordering::Equal)
 }
 
+/// Compares the tuples `(order_by_pairs[0].0[i], order_by_pairs[1].0[i], ...)` and
+/// `(order_by_pairs[0].0[j], order_by_pairs[1].0[j], ...)` in lexicographic order.
+/// Note that direction flips the ordering.
+pub(crate) fn compare_indexes_by_columns_with_direction<S: Scalar>(
+    order_by_pairs: &[(Column<S>, bool)],
+    i: usize,
+    j: usize,
+) -> Ordering {
+    order_by_pairs
+        .iter()
+        .map(|(col, is_asc)| {
+            let ordering = compare_indexes_by_columns(core::slice::from_ref(col), i, j);
+            match is_asc {
+                true => ordering,
+                false => ordering.reverse(),
+            }
+        })
+        .find(|&ord| ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Sorts the rows of `columns` by the columns with the given indexes in the given directions.
+///
+/// The sort is stable, i.e. rows with equal sort keys keep their relative order.
+///
+/// # Panics
+/// Panics if any of the indexes in `order_by` is out of bounds.
+pub(crate) fn sort_columns<'a, S: Scalar>(
+    alloc: &'a Bump,
+    columns: &[Column<'a, S>],
+    order_by: &[(usize, bool)],
+) -> Vec<Column<'a, S>> {
+    let order_by_pairs = order_by
+        .iter()
+        .map(|&(index, is_asc)| (columns[index], is_asc))
+        .collect::<Vec<_>>();
+    let num_rows = columns.first().map_or(0, Column::len);
+    let mut indexes = (0..num_rows).collect::<Vec<_>>();
+    indexes.sort_by(|&i, &j| compare_indexes_by_columns_with_direction(&order_by_pairs, i, j));
+    columns
+        .iter()
+        .map(|column| {
+            apply_column_to_indexes(column, alloc, &indexes)
+                .expect("Indexes can not be out of bounds")
+        })
+        .collect()
+}
+
 /// Compares the tuples `(left[0][i], left[1][i], ...)` and
 /// `(right[0][j], right[1][j], ...)` in lexicographic order.
 ///
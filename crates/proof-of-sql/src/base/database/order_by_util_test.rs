@@ -6,6 +6,7 @@ use crate::{
     },
     proof_primitive::dory::DoryScalar,
 };
+use bumpalo::Bump;
 use core::cmp::Ordering;
 
 #[test]
@@ -310,3 +311,38 @@ fn we_can_compare_indexes_by_columns_for_varbinary_columns() {
     assert_eq!(compare_indexes_by_columns(columns, 3, 4), Ordering::Greater); // "baz" vs "bar"
     assert_eq!(compare_indexes_by_columns(columns, 1, 4), Ordering::Equal); // "bar" vs "bar"
 }
+
+#[test]
+fn we_can_sort_columns_by_multiple_columns_with_directions() {
+    let alloc = Bump::new();
+    let columns = [
+        Column::BigInt::<TestScalar>(&[3, 1, 2, 1, 3]),
+        Column::Boolean(&[true, false, true, true, false]),
+        Column::Int(&[0, 1, 2, 3, 4]),
+    ];
+    let sorted_columns = sort_columns(&alloc, &columns, &[(0, false), (1, true)]);
+    assert_eq!(
+        sorted_columns,
+        vec![
+            Column::BigInt(&[3, 3, 2, 1, 1]),
+            Column::Boolean(&[false, true, true, false, true]),
+            Column::Int(&[4, 0, 2, 1, 3]),
+        ]
+    );
+}
+
+#[test]
+fn we_can_sort_columns_stably() {
+    let alloc = Bump::new();
+    let columns = [
+        Column::SmallInt::<TestScalar>(&[2, 1, 2, 1]),
+        Column::Int(&[0, 1, 2, 3]),
+    ];
+    let sorted_columns = sort_columns(&alloc, &columns, &[(0, true)]);
+    assert_eq!(
+        sorted_columns,
+        vec![Column::SmallInt(&[1, 1, 2, 2]), Column::Int(&[1, 3, 0, 2])]
+    );
+    let sorted_columns = sort_columns(&alloc, &columns[..0], &[]);
+    assert!(sorted_columns.is_empty());
+}
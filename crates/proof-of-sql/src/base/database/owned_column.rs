@@ -384,7 +384,7 @@ impl<'a, S: Scalar> From<&Column<'a, S>> for OwnedColumn<S> {
 }
 
 impl<S: Scalar> OwnedColumn<S> {
-    /// Attempts to coerce a column of scalars to a numeric column of the specified type.
+    /// Attempts to coerce a column of scalars to a numeric or timestamp column of the specified type.
     /// If the specified type is the same as the current column type, the function will return the column as is.
     ///
     /// # Arguments
@@ -402,7 +402,7 @@ impl<S: Scalar> OwnedColumn<S> {
     ///
     /// Otherwise, this function will return an error if:
     /// * The column type is not `Scalar`.
-    /// * The target type is not a numeric type.
+    /// * The target type is not a numeric or timestamp type.
     /// * There is an overflow during the coercion.
     pub(crate) fn try_coerce_scalar_to_numeric(
         self,
@@ -451,6 +451,12 @@ impl<S: Scalar> OwnedColumn<S> {
                 ColumnType::Decimal75(precision, scale) => {
                     Ok(OwnedColumn::Decimal75(precision, scale, vec))
                 }
+                ColumnType::TimestampTZ(tu, tz) => vec
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .try_collect()
+                    .map_err(|_| ColumnCoercionError::Overflow)
+                    .map(|col| OwnedColumn::TimestampTZ(tu, tz, col)),
                 _ => Err(ColumnCoercionError::InvalidTypeCoercion),
             }
         } else {
@@ -777,7 +783,20 @@ mod test {
             .unwrap();
         assert_eq!(
             coerced_col,
-            OwnedColumn::Decimal75(Precision::new(75).unwrap(), 0, scalars)
+            OwnedColumn::Decimal75(Precision::new(75).unwrap(), 0, scalars.clone())
+        );
+
+        // Coerce to TimestampTZ
+        let coerced_col = col
+            .clone()
+            .try_coerce_scalar_to_numeric(ColumnType::TimestampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+            ))
+            .unwrap();
+        assert_eq!(
+            coerced_col,
+            OwnedColumn::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), vec![1, 2, 3])
         );
     }
 
@@ -25,6 +25,16 @@ pub enum OwnedColumnError {
         /// The underlying error
         error: String,
     },
+    /// The presence of a nullable column does not have the same length as its values.
+    #[snafu(display(
+        "presence has length {presence_len} but the values have length {values_len}"
+    ))]
+    PresenceLengthMismatch {
+        /// The length of the values
+        values_len: usize,
+        /// The length of the presence
+        presence_len: usize,
+    },
 }
 
 /// Errors that can occur when coercing a column.
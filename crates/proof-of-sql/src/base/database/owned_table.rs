@@ -1,4 +1,4 @@
-use super::{ColumnField, OwnedColumn, Table};
+use super::{ColumnField, NullableOwnedColumn, OwnedColumn, Table};
 use crate::base::{
     database::ColumnCoercionError, map::IndexMap, polynomial::compute_evaluation_vector,
     scalar::Scalar,
@@ -15,6 +15,14 @@ pub enum OwnedTableError {
     /// The columns have different lengths.
     #[snafu(display("Columns have different lengths"))]
     ColumnLengthMismatch,
+
+    /// A presence bitmap is provided for a column that is not in the table.
+    #[snafu(display("Presence is provided for a column that is not in the table"))]
+    PresenceWithoutColumn,
+
+    /// A presence bitmap has length different from its column.
+    #[snafu(display("Presence has length different from its column"))]
+    PresenceLengthMismatch,
 }
 
 /// Errors that can occur when coercing a table.
@@ -31,26 +39,48 @@ pub(crate) enum TableCoercionError {
 }
 
 /// A table of data, with schema included. This is simply a map from `Ident` to `OwnedColumn`,
-/// where columns order matters.
+/// where columns order matters, together with the presence bitmaps of its nullable columns.
 /// This is primarily used as an internal result that is used before
 /// converting to the final result in either Arrow format or JSON.
 /// This is the analog of an arrow [`RecordBatch`](arrow::record_batch::RecordBatch).
 #[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 pub struct OwnedTable<S: Scalar> {
     table: IndexMap<Ident, OwnedColumn<S>>,
+    #[serde(default)]
+    presence: IndexMap<Ident, Vec<bool>>,
 }
 impl<S: Scalar> OwnedTable<S> {
     /// Creates a new [`OwnedTable`].
     pub fn try_new(table: IndexMap<Ident, OwnedColumn<S>>) -> Result<Self, OwnedTableError> {
-        if table.is_empty() {
-            return Ok(Self { table });
+        Self::try_new_with_presence(table, IndexMap::default())
+    }
+    /// Creates a new [`OwnedTable`] with the presence bitmaps of its nullable columns.
+    ///
+    /// A presence bitmap is `true` exactly where the value of its column is not null.
+    /// Values at null positions are replaced with the default value of the column type.
+    pub fn try_new_with_presence(
+        table: IndexMap<Ident, OwnedColumn<S>>,
+        presence: IndexMap<Ident, Vec<bool>>,
+    ) -> Result<Self, OwnedTableError> {
+        if !table.is_empty() {
+            let num_rows = table[0].len();
+            if table.values().any(|column| column.len() != num_rows) {
+                return Err(OwnedTableError::ColumnLengthMismatch);
+            }
         }
-        let num_rows = table[0].len();
-        if table.values().any(|column| column.len() != num_rows) {
-            Err(OwnedTableError::ColumnLengthMismatch)
-        } else {
-            Ok(Self { table })
+        if presence.keys().any(|ident| !table.contains_key(ident)) {
+            return Err(OwnedTableError::PresenceWithoutColumn);
         }
+        let table = table
+            .into_iter()
+            .map(|(ident, column)| match presence.get(&ident) {
+                Some(bits) => NullableOwnedColumn::try_new(column, Some(bits.clone()))
+                    .map(|nullable_column| (ident, nullable_column.into_parts().0)),
+                None => Ok((ident, column)),
+            })
+            .collect::<Result<IndexMap<_, _>, _>>()
+            .map_err(|_| OwnedTableError::PresenceLengthMismatch)?;
+        Ok(Self { table, presence })
     }
     /// Creates a new [`OwnedTable`].
     pub fn try_from_iter<T: IntoIterator<Item = (Ident, OwnedColumn<S>)>>(
@@ -117,6 +147,8 @@ impl<S: Scalar> OwnedTable<S> {
         self.table.is_empty()
     }
     /// Returns the columns of this table as an `IndexMap`
+    ///
+    /// The presence bitmaps of nullable columns are dropped.
     #[must_use]
     pub fn into_inner(self) -> IndexMap<Ident, OwnedColumn<S>> {
         self.table
@@ -135,6 +167,16 @@ impl<S: Scalar> OwnedTable<S> {
     pub fn column_by_index(&self, index: usize) -> Option<&OwnedColumn<S>> {
         self.table.get_index(index).map(|(_, v)| v)
     }
+    /// Returns the presence bitmaps of the nullable columns of this table as an `IndexMap`
+    #[must_use]
+    pub fn presence(&self) -> &IndexMap<Ident, Vec<bool>> {
+        &self.presence
+    }
+    /// Returns the presence bitmap of the column `ident`, or `None` if the column is not nullable.
+    #[must_use]
+    pub fn presence_for_column(&self, ident: &Ident) -> Option<&[bool]> {
+        self.presence.get(ident).map(Vec::as_slice)
+    }
 
     pub(crate) fn mle_evaluations(&self, evaluation_point: &[S]) -> Vec<S> {
         let mut evaluation_vector = vec![S::ZERO; self.num_rows()];
@@ -151,6 +193,7 @@ impl<S: Scalar> OwnedTable<S> {
 impl<S: Scalar> PartialEq for OwnedTable<S> {
     fn eq(&self, other: &Self) -> bool {
         self.table == other.table
+            && self.presence == other.presence
             && self
                 .table
                 .keys()
@@ -169,25 +212,25 @@ impl<S: Scalar> core::ops::Index<&str> for OwnedTable<S> {
 
 impl<'a, S: Scalar> From<&Table<'a, S>> for OwnedTable<S> {
     fn from(value: &Table<'a, S>) -> Self {
-        OwnedTable::try_from_iter(
+        OwnedTable::try_new_with_presence(
             value
                 .inner_table()
                 .iter()
-                .map(|(name, column)| (name.clone(), OwnedColumn::from(column))),
+                .map(|(name, column)| (name.clone(), OwnedColumn::from(column)))
+                .collect(),
+            value
+                .presence()
+                .iter()
+                .map(|(name, presence)| (name.clone(), presence.to_vec()))
+                .collect(),
         )
-        .expect("Tables should not have columns with differing lengths")
+        .expect("Tables should not have columns or presence with differing lengths")
     }
 }
 
 impl<'a, S: Scalar> From<Table<'a, S>> for OwnedTable<S> {
     fn from(value: Table<'a, S>) -> Self {
-        OwnedTable::try_from_iter(
-            value
-                .into_inner()
-                .into_iter()
-                .map(|(name, column)| (name, OwnedColumn::from(&column))),
-        )
-        .expect("Tables should not have columns with differing lengths")
+        OwnedTable::from(&value)
     }
 }
 
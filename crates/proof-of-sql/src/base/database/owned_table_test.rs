@@ -167,3 +167,42 @@ fn we_cannot_create_an_owned_table_with_differing_column_lengths() {
         Err(OwnedTableError::ColumnLengthMismatch)
     ));
 }
+#[test]
+fn we_can_create_an_owned_table_with_presence() {
+    let owned_table = nullable_owned_table::<TestScalar>(
+        [bigint("a", [1, 2, 3]), varchar("b", ["x", "y", "z"])],
+        [("b".into(), vec![true, false, true])],
+    );
+    assert_eq!(owned_table.num_rows(), 3);
+    assert_eq!(owned_table.presence_for_column(&"a".into()), None);
+    assert_eq!(
+        owned_table.presence_for_column(&"b".into()),
+        Some(&[true, false, true][..])
+    );
+    // Null values are replaced with the default value
+    assert_eq!(
+        owned_table.inner_table()[&Ident::new("b")],
+        OwnedColumn::VarChar(vec!["x".to_string(), String::new(), "z".to_string()])
+    );
+    assert_ne!(
+        owned_table,
+        owned_table([bigint("a", [1, 2, 3]), varchar("b", ["x", "", "z"])])
+    );
+}
+#[test]
+fn we_cannot_create_an_owned_table_with_invalid_presence() {
+    assert!(matches!(
+        OwnedTable::<TestScalar>::try_new_with_presence(
+            IndexMap::from_iter([("a".into(), OwnedColumn::BigInt(vec![0]))]),
+            IndexMap::from_iter([("b".into(), vec![true])]),
+        ),
+        Err(OwnedTableError::PresenceWithoutColumn)
+    ));
+    assert!(matches!(
+        OwnedTable::<TestScalar>::try_new_with_presence(
+            IndexMap::from_iter([("a".into(), OwnedColumn::BigInt(vec![0]))]),
+            IndexMap::from_iter([("a".into(), vec![true, false])]),
+        ),
+        Err(OwnedTableError::PresenceLengthMismatch)
+    ));
+}
@@ -1,11 +1,11 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
-    OwnedTable, SchemaAccessor, TableRef, TestAccessor,
+    presence_column_owner, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedColumn, OwnedTable, SchemaAccessor, TableRef, TestAccessor,
 };
 use crate::base::{
     commitment::{CommitmentEvaluationProof, VecCommitmentExt},
     map::IndexMap,
-    scalar::ScalarExt,
+    scalar::{Scalar, ScalarExt},
 };
 use alloc::{string::String, vec::Vec};
 use bumpalo::Bump;
@@ -82,15 +82,11 @@ impl<CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment>
 /// indicating that an invalid column reference was provided.
 impl<CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for OwnedTableTestAccessor<'_, CP> {
     fn get_column(&self, column: ColumnRef) -> Column<CP::Scalar> {
-        match self
-            .tables
-            .get(&column.table_ref())
-            .unwrap()
-            .0
-            .inner_table()
-            .get(&column.column_id())
-            .unwrap()
-        {
+        let table = &self.tables.get(&column.table_ref()).unwrap().0;
+        if let Some(presence) = presence_of(table, &column.column_id()) {
+            return Column::Boolean(presence);
+        }
+        match table.inner_table().get(&column.column_id()).unwrap() {
             OwnedColumn::Boolean(col) => Column::Boolean(col),
             OwnedColumn::TinyInt(col) => Column::TinyInt(col),
             OwnedColumn::Uint8(col) => Column::Uint8(col),
@@ -140,6 +136,14 @@ impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
 {
     fn get_commitment(&self, column: ColumnRef) -> CP::Commitment {
         let (table, offset) = self.tables.get(&column.table_ref()).unwrap();
+        if let Some(presence) = presence_of(table, &column.column_id()) {
+            return Vec::<CP::Commitment>::from_columns_with_offset(
+                [presence],
+                *offset,
+                self.setup.as_ref().unwrap(),
+            )[0]
+            .clone();
+        }
         let owned_column = table.inner_table().get(&column.column_id()).unwrap();
         Vec::<CP::Commitment>::from_columns_with_offset(
             [owned_column],
@@ -167,14 +171,11 @@ impl<CP: CommitmentEvaluationProof> MetadataAccessor for OwnedTableTestAccessor<
 }
 impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<'_, CP> {
     fn lookup_column(&self, table_ref: TableRef, column_id: Ident) -> Option<ColumnType> {
-        Some(
-            self.tables
-                .get(&table_ref)?
-                .0
-                .inner_table()
-                .get(&column_id)?
-                .column_type(),
-        )
+        let table = &self.tables.get(&table_ref)?.0;
+        if presence_of(table, &column_id).is_some() {
+            return Some(ColumnType::Boolean);
+        }
+        Some(table.inner_table().get(&column_id)?.column_type())
     }
     ///
     /// # Panics
@@ -192,6 +193,12 @@ impl<CP: CommitmentEvaluationProof> SchemaAccessor for OwnedTableTestAccessor<'_
     }
 }
 
+/// Returns the presence bitmap that `column_id` refers to, if it is the identifier of the presence
+/// of a nullable column of `table`.
+fn presence_of<'a, S: Scalar>(table: &'a OwnedTable<S>, column_id: &Ident) -> Option<&'a [bool]> {
+    table.presence_for_column(&presence_column_owner(column_id)?)
+}
+
 impl<'a, CP: CommitmentEvaluationProof> OwnedTableTestAccessor<'a, CP> {
     /// Create a new empty test accessor with the given setup.
     pub fn new_empty_with_setup(setup: CP::ProverPublicSetup<'a>) -> Self {
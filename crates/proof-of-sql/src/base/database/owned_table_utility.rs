@@ -13,8 +13,11 @@
 //!     decimal75("f", 12, 1, [1, 2, 3]),
 //! ]);
 //! ```
-use super::{OwnedColumn, OwnedTable};
-use crate::base::scalar::Scalar;
+use super::{
+    presence_column_ident, varchar_key, varchar_key_column_ident, varchar_length,
+    varchar_length_column_ident, OwnedColumn, OwnedTable,
+};
+use crate::base::{map::IndexMap, scalar::Scalar};
 use alloc::{string::String, vec::Vec};
 use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 use sqlparser::ast::Ident;
@@ -45,6 +48,30 @@ pub fn owned_table<S: Scalar>(
     OwnedTable::try_from_iter(iter).unwrap()
 }
 
+/// Creates an [`OwnedTable`] from a list of `(Ident, OwnedColumn)` pairs and the presence
+/// bitmaps of its nullable columns.
+/// A `false` entry in the presence of a column marks the corresponding value as null.
+/// This is a convenience wrapper around [`OwnedTable::try_new_with_presence`] primarily for use in tests.
+///
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = nullable_owned_table::<Curve25519Scalar>(
+///     [bigint("a", [1, 0, 3]), boolean("b", [true, false, true])],
+///     [("a".into(), vec![true, false, true])],
+/// );
+/// ```
+///
+/// # Panics
+/// - Panics if converting the columns and presence into an `OwnedTable<S>` fails.
+pub fn nullable_owned_table<S: Scalar>(
+    iter: impl IntoIterator<Item = (Ident, OwnedColumn<S>)>,
+    presence: impl IntoIterator<Item = (Ident, Vec<bool>)>,
+) -> OwnedTable<S> {
+    OwnedTable::try_new_with_presence(IndexMap::from_iter(iter), IndexMap::from_iter(presence))
+        .unwrap()
+}
+
 /// Creates a (Ident, `OwnedColumn`) pair for a uint8 column.
 /// This is primarily intended for use in conjunction with [`owned_table`].
 /// # Example
@@ -160,6 +187,26 @@ pub fn boolean<S: Scalar>(
     )
 }
 
+/// Creates a `(Ident, OwnedColumn)` pair for the presence column of the nullable column `name`
+/// in a query result, where nullable result columns are followed by their presence.
+/// A `false` entry marks the corresponding value of `name` as null.
+/// This is primarily intended for use in conjunction with [`owned_table`].
+/// Nullable columns of tables that are queried are created with [`nullable_owned_table`] instead.
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     bigint("a", [1, 0, 3]),
+///     presence("a", [true, false, true]),
+/// ]);
+/// ```
+pub fn presence<S: Scalar>(
+    name: impl Into<Ident>,
+    data: impl IntoIterator<Item = impl Into<bool>>,
+) -> (Ident, OwnedColumn<S>) {
+    boolean(presence_column_ident(&name.into()), data)
+}
+
 /// Creates a `(Ident, OwnedColumn)` pair for a int128 column.
 /// This is primarily intended for use in conjunction with [`owned_table`].
 /// # Example
@@ -217,6 +264,55 @@ pub fn varchar<S: Scalar>(
     )
 }
 
+/// Creates a `(Ident, OwnedColumn)` pair for the key column of the varchar column `name`.
+/// See [`crate::base::database::varchar_key`] for the encoding of the strings.
+/// This is primarily intended for use in conjunction with [`owned_table`].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     varchar("a", ["a", "b", "c"]),
+///     varchar_keys("a", ["a", "b", "c"]),
+/// ]);
+/// ```
+pub fn varchar_keys<S: Scalar>(
+    name: impl Into<Ident>,
+    data: impl IntoIterator<Item = impl AsRef<str>>,
+) -> (Ident, OwnedColumn<S>) {
+    (
+        varchar_key_column_ident(&name.into()),
+        OwnedColumn::Scalar(
+            data.into_iter()
+                .map(|value| varchar_key(value.as_ref()))
+                .collect(),
+        ),
+    )
+}
+
+/// Creates a `(Ident, OwnedColumn)` pair for the length column of the varchar column `name`.
+/// This is primarily intended for use in conjunction with [`owned_table`].
+/// # Example
+/// ```
+/// use proof_of_sql::base::{database::owned_table_utility::*, scalar::Curve25519Scalar};
+/// let result = owned_table::<Curve25519Scalar>([
+///     varchar("a", ["a", "b", "c"]),
+///     varchar_lengths("a", ["a", "b", "c"]),
+/// ]);
+/// ```
+pub fn varchar_lengths<S: Scalar>(
+    name: impl Into<Ident>,
+    data: impl IntoIterator<Item = impl AsRef<str>>,
+) -> (Ident, OwnedColumn<S>) {
+    (
+        varchar_length_column_ident(&name.into()),
+        OwnedColumn::BigInt(
+            data.into_iter()
+                .map(|value| varchar_length(value.as_ref()))
+                .collect(),
+        ),
+    )
+}
+
 /// Creates a `(Ident, OwnedColumn)` pair for a varbinary column.
 /// This is primarily intended for use in conjunction with [`owned_table`].
 /// # Example
@@ -35,15 +35,24 @@ pub enum TableError {
     /// The table is empty and there is no specified row count.
     #[snafu(display("Table is empty and no row count is specified"))]
     EmptyTableWithoutSpecifiedRowCount,
+
+    /// A presence bitmap is provided for a column that is not in the table.
+    #[snafu(display("Presence is provided for a column that is not in the table"))]
+    PresenceWithoutColumn,
+
+    /// A presence bitmap has length different from the row count.
+    #[snafu(display("Presence has length different from the row count"))]
+    PresenceLengthMismatch,
 }
 /// A table of data, with schema included. This is simply a map from `Ident` to `Column`,
-/// where columns order matters.
+/// where columns order matters, together with the presence bitmaps of its nullable columns.
 /// This is primarily used as an internal result that is used before
 /// converting to the final result in either Arrow format or JSON.
 /// This is the analog of an arrow [`RecordBatch`](arrow::record_batch::RecordBatch).
 #[derive(Debug, Clone, Eq)]
 pub struct Table<'a, S: Scalar> {
     table: IndexMap<Ident, Column<'a, S>>,
+    presence: IndexMap<Ident, &'a [bool]>,
     row_count: usize,
 }
 impl<'a, S: Scalar> Table<'a, S> {
@@ -57,24 +66,47 @@ impl<'a, S: Scalar> Table<'a, S> {
         table: IndexMap<Ident, Column<'a, S>>,
         options: TableOptions,
     ) -> Result<Self, TableError> {
-        match (table.is_empty(), options.row_count) {
+        Self::try_new_with_presence(table, IndexMap::default(), options)
+    }
+
+    /// Creates a new [`Table`] with the given columns, the presence bitmaps of its nullable columns
+    /// and [`TableOptions`].
+    ///
+    /// A presence bitmap is `true` exactly where the value of its column is not null.
+    pub fn try_new_with_presence(
+        table: IndexMap<Ident, Column<'a, S>>,
+        presence: IndexMap<Ident, &'a [bool]>,
+        options: TableOptions,
+    ) -> Result<Self, TableError> {
+        let row_count = match (table.is_empty(), options.row_count) {
             (true, None) => Err(TableError::EmptyTableWithoutSpecifiedRowCount),
-            (true, Some(row_count)) => Ok(Self { table, row_count }),
+            (true, Some(row_count)) => Ok(row_count),
             (false, None) => {
                 let row_count = table[0].len();
                 if table.values().any(|column| column.len() != row_count) {
                     Err(TableError::ColumnLengthMismatch)
                 } else {
-                    Ok(Self { table, row_count })
+                    Ok(row_count)
                 }
             }
             (false, Some(row_count)) => {
                 if table.values().any(|column| column.len() != row_count) {
                     Err(TableError::ColumnLengthMismatchWithSpecifiedRowCount)
                 } else {
-                    Ok(Self { table, row_count })
+                    Ok(row_count)
                 }
             }
+        }?;
+        if presence.keys().any(|ident| !table.contains_key(ident)) {
+            Err(TableError::PresenceWithoutColumn)
+        } else if presence.values().any(|bits| bits.len() != row_count) {
+            Err(TableError::PresenceLengthMismatch)
+        } else {
+            Ok(Self {
+                table,
+                presence,
+                row_count,
+            })
         }
     }
 
@@ -109,6 +141,8 @@ impl<'a, S: Scalar> Table<'a, S> {
         self.table.is_empty()
     }
     /// Returns the columns of this table as an `IndexMap`
+    ///
+    /// The presence bitmaps of nullable columns are dropped.
     #[must_use]
     pub fn into_inner(self) -> IndexMap<Ident, Column<'a, S>> {
         self.table
@@ -139,6 +173,16 @@ impl<'a, S: Scalar> Table<'a, S> {
     pub fn column(&self, index: usize) -> Option<&Column<'a, S>> {
         self.table.values().nth(index)
     }
+    /// Returns the presence bitmaps of the nullable columns of this table as an `IndexMap`
+    #[must_use]
+    pub fn presence(&self) -> &IndexMap<Ident, &'a [bool]> {
+        &self.presence
+    }
+    /// Returns the presence bitmap of the column `ident`, or `None` if the column is not nullable.
+    #[must_use]
+    pub fn presence_for_column(&self, ident: &Ident) -> Option<&'a [bool]> {
+        self.presence.get(ident).copied()
+    }
     /// Add the `rho` column as the last column to the table.
     #[must_use]
     pub fn add_rho_column(mut self, alloc: &'a Bump) -> Self {
@@ -153,6 +197,7 @@ impl<'a, S: Scalar> Table<'a, S> {
 impl<S: Scalar> PartialEq for Table<'_, S> {
     fn eq(&self, other: &Self) -> bool {
         self.table == other.table
+            && self.presence == other.presence
             && self
                 .table
                 .keys()
@@ -103,6 +103,47 @@ fn we_cannot_create_a_table_with_no_columns_without_specified_row_count() {
     ));
 }
 
+#[test]
+fn we_can_create_a_table_with_presence() {
+    let table = Table::<TestScalar>::try_new_with_presence(
+        indexmap! {
+            "a".into() => Column::BigInt(&[1, 0]),
+            "b".into() => Column::Int128(&[0, 1]),
+        },
+        indexmap! {"a".into() => &[true, false][..]},
+        TableOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(table.num_columns(), 2);
+    assert_eq!(table.num_rows(), 2);
+    assert_eq!(
+        table.presence_for_column(&"a".into()),
+        Some(&[true, false][..])
+    );
+    assert_eq!(table.presence_for_column(&"b".into()), None);
+    assert_eq!(table.presence().len(), 1);
+}
+
+#[test]
+fn we_cannot_create_a_table_with_invalid_presence() {
+    assert!(matches!(
+        Table::<TestScalar>::try_new_with_presence(
+            indexmap! {"a".into() => Column::BigInt(&[1, 0])},
+            indexmap! {"b".into() => &[true, false][..]},
+            TableOptions::default(),
+        ),
+        Err(TableError::PresenceWithoutColumn)
+    ));
+    assert!(matches!(
+        Table::<TestScalar>::try_new_with_presence(
+            indexmap! {"a".into() => Column::BigInt(&[1, 0])},
+            indexmap! {"a".into() => &[true][..]},
+            TableOptions::default(),
+        ),
+        Err(TableError::PresenceLengthMismatch)
+    ));
+}
+
 #[test]
 fn we_can_create_an_empty_table_with_some_columns() {
     let alloc = Bump::new();
@@ -1,10 +1,11 @@
 use super::{
-    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
-    SchemaAccessor, Table, TableRef, TestAccessor,
+    presence_column_owner, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, SchemaAccessor, Table, TableRef, TestAccessor,
 };
 use crate::base::{
     commitment::{CommitmentEvaluationProof, VecCommitmentExt},
     map::IndexMap,
+    scalar::Scalar,
 };
 use alloc::vec::Vec;
 use sqlparser::ast::Ident;
@@ -76,14 +77,11 @@ impl<'a, CP: CommitmentEvaluationProof> TestAccessor<CP::Commitment> for TableTe
 /// indicating that an invalid column reference was provided.
 impl<'a, CP: CommitmentEvaluationProof> DataAccessor<CP::Scalar> for TableTestAccessor<'a, CP> {
     fn get_column(&self, column: ColumnRef) -> Column<'a, CP::Scalar> {
-        *self
-            .tables
-            .get(&column.table_ref())
-            .unwrap()
-            .0
-            .inner_table()
-            .get(&column.column_id())
-            .unwrap()
+        let table = &self.tables.get(&column.table_ref()).unwrap().0;
+        if let Some(presence) = presence_of(table, &column.column_id()) {
+            return Column::Boolean(presence);
+        }
+        *table.inner_table().get(&column.column_id()).unwrap()
     }
 }
 
@@ -96,6 +94,14 @@ impl<CP: CommitmentEvaluationProof> CommitmentAccessor<CP::Commitment>
 {
     fn get_commitment(&self, column: ColumnRef) -> CP::Commitment {
         let (table, offset) = self.tables.get(&column.table_ref()).unwrap();
+        if let Some(presence) = presence_of(table, &column.column_id()) {
+            return Vec::<CP::Commitment>::from_columns_with_offset(
+                [presence],
+                *offset,
+                self.setup.as_ref().unwrap(),
+            )[0]
+            .clone();
+        }
         let borrowed_column = table.inner_table().get(&column.column_id()).unwrap();
         Vec::<CP::Commitment>::from_columns_with_offset(
             [borrowed_column],
@@ -123,14 +129,11 @@ impl<CP: CommitmentEvaluationProof> MetadataAccessor for TableTestAccessor<'_, C
 }
 impl<CP: CommitmentEvaluationProof> SchemaAccessor for TableTestAccessor<'_, CP> {
     fn lookup_column(&self, table_ref: TableRef, column_id: Ident) -> Option<ColumnType> {
-        Some(
-            self.tables
-                .get(&table_ref)?
-                .0
-                .inner_table()
-                .get(&column_id)?
-                .column_type(),
-        )
+        let table = &self.tables.get(&table_ref)?.0;
+        if presence_of(table, &column_id).is_some() {
+            return Some(ColumnType::Boolean);
+        }
+        Some(table.inner_table().get(&column_id)?.column_type())
     }
     ///
     /// # Panics
@@ -148,6 +151,12 @@ impl<CP: CommitmentEvaluationProof> SchemaAccessor for TableTestAccessor<'_, CP>
     }
 }
 
+/// Returns the presence bitmap that `column_id` refers to, if it is the identifier of the presence
+/// of a nullable column of `table`.
+fn presence_of<'a, S: Scalar>(table: &Table<'a, S>, column_id: &Ident) -> Option<&'a [bool]> {
+    table.presence_for_column(&presence_column_owner(column_id)?)
+}
+
 impl<'a, CP: CommitmentEvaluationProof> TableTestAccessor<'a, CP> {
     /// Create a new empty test accessor with the given setup.
     pub fn new_empty_with_setup(setup: CP::ProverPublicSetup<'a>) -> Self {
@@ -0,0 +1,265 @@
+//! String operations beyond equality are proven over auxiliary encoding columns.
+//!
+//! A `VarChar` column is committed as a column of hashes, which only supports equality.
+//! To prove comparisons, prefix matches and lengths, a table may store two encoding columns
+//! next to a `VarChar` column, under the identifiers returned by [`varchar_key_column_ident`] and
+//! [`varchar_length_column_ident`]. They are committed to, accessed and proven like any other
+//! column:
+//! - the *key* column is a scalar column holding the first [`VARCHAR_KEY_PREFIX_BYTES`] bytes of
+//!   each string, zero padded and read as a big endian number, followed by its length in bytes as
+//!   a 32 bit number. Comparing keys compares strings lexicographically, except that strings which
+//!   are both longer than [`VARCHAR_KEY_PREFIX_BYTES`] bytes and share their leading bytes are
+//!   ordered by their length only.
+//! - the *length* column is a bigint column holding the number of characters of each string.
+use super::{ColumnRef, ColumnType, OwnedColumn, OwnedTable};
+use crate::base::scalar::Scalar;
+use alloc::{format, vec::Vec};
+use sqlparser::ast::Ident;
+
+/// The suffix appended to the identifier of a `VarChar` column to get the identifier of its key column.
+///
+/// `$` can not appear in an identifier parsed from SQL, so encoding columns can not be referenced
+/// directly in a query.
+pub const VARCHAR_KEY_COLUMN_SUFFIX: &str = "$key";
+
+/// The suffix appended to the identifier of a `VarChar` column to get the identifier of its length column.
+pub const VARCHAR_LENGTH_COLUMN_SUFFIX: &str = "$length";
+
+/// The number of leading bytes of a string that are stored in its key.
+///
+/// Together with the 32 bit length, a key has at most 248 bits, so that the difference of two keys
+/// always has a unique sign.
+pub const VARCHAR_KEY_PREFIX_BYTES: usize = 27;
+
+/// Returns the identifier of the key column of the `VarChar` column `ident`.
+#[must_use]
+pub fn varchar_key_column_ident(ident: &Ident) -> Ident {
+    Ident::new(format!("{}{VARCHAR_KEY_COLUMN_SUFFIX}", ident.value))
+}
+
+/// Returns the identifier of the length column of the `VarChar` column `ident`.
+#[must_use]
+pub fn varchar_length_column_ident(ident: &Ident) -> Ident {
+    Ident::new(format!("{}{VARCHAR_LENGTH_COLUMN_SUFFIX}", ident.value))
+}
+
+/// Returns whether `ident` is the identifier of an encoding column.
+#[must_use]
+pub fn is_varchar_encoding_column_ident(ident: &Ident) -> bool {
+    ident.value.ends_with(VARCHAR_KEY_COLUMN_SUFFIX)
+        || ident.value.ends_with(VARCHAR_LENGTH_COLUMN_SUFFIX)
+}
+
+/// Returns the reference to the key column of the `VarChar` column referenced by `column_ref`.
+#[must_use]
+pub fn varchar_key_column_ref(column_ref: &ColumnRef) -> ColumnRef {
+    ColumnRef::new(
+        column_ref.table_ref(),
+        varchar_key_column_ident(&column_ref.column_id()),
+        ColumnType::Scalar,
+    )
+}
+
+/// Returns the reference to the length column of the `VarChar` column referenced by `column_ref`.
+#[must_use]
+pub fn varchar_length_column_ref(column_ref: &ColumnRef) -> ColumnRef {
+    ColumnRef::new(
+        column_ref.table_ref(),
+        varchar_length_column_ident(&column_ref.column_id()),
+        ColumnType::BigInt,
+    )
+}
+
+/// Converts the 32 big endian bytes of a key into the limbs of a scalar.
+fn key_limbs(bytes: [u8; 32]) -> [u64; 4] {
+    let mut limbs = [0; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("chunks have 8 bytes"));
+    }
+    limbs
+}
+
+/// Returns the 32 big endian bytes of a key with the given leading bytes, padding and length.
+fn key_bytes(leading_bytes: &[u8], padding: u8, length: u32) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    let (prefix, length_bytes) =
+        bytes[32 - VARCHAR_KEY_PREFIX_BYTES - 4..].split_at_mut(VARCHAR_KEY_PREFIX_BYTES);
+    prefix.fill(padding);
+    let leading_bytes = &leading_bytes[..leading_bytes.len().min(VARCHAR_KEY_PREFIX_BYTES)];
+    prefix[..leading_bytes.len()].copy_from_slice(leading_bytes);
+    length_bytes.copy_from_slice(&length.to_be_bytes());
+    bytes
+}
+
+/// Returns the limbs of the key of `value`.
+#[must_use]
+pub fn varchar_key_limbs(value: &str) -> [u64; 4] {
+    let length = u32::try_from(value.len()).unwrap_or(u32::MAX);
+    key_limbs(key_bytes(value.as_bytes(), 0, length))
+}
+
+/// Returns the key of `value`.
+#[must_use]
+pub fn varchar_key<S: Scalar>(value: &str) -> S {
+    varchar_key_limbs(value).into()
+}
+
+/// Returns the length of `value`, which is its number of characters.
+#[must_use]
+pub fn varchar_length(value: &str) -> i64 {
+    i64::try_from(value.chars().count()).unwrap_or(i64::MAX)
+}
+
+/// Returns the limbs of the smallest and the largest key of a string starting with `prefix`,
+/// or `None` if `prefix` is longer than [`VARCHAR_KEY_PREFIX_BYTES`] bytes.
+///
+/// The smallest key belongs to `prefix` itself. Requiring the length to be at least the length of
+/// the prefix is what distinguishes strings with trailing zero bytes.
+#[must_use]
+pub fn varchar_prefix_key_limb_bounds(prefix: &str) -> Option<([u64; 4], [u64; 4])> {
+    (prefix.len() <= VARCHAR_KEY_PREFIX_BYTES).then(|| {
+        let length = u32::try_from(prefix.len()).expect("the prefix is short");
+        (
+            key_limbs(key_bytes(prefix.as_bytes(), 0, length)),
+            key_limbs(key_bytes(prefix.as_bytes(), u8::MAX, u32::MAX)),
+        )
+    })
+}
+
+impl<S: Scalar> OwnedTable<S> {
+    /// Adds the key and length columns of every `VarChar` column that does not have them yet,
+    /// so that string operations beyond equality can be proven over the table.
+    #[must_use]
+    pub fn with_varchar_encodings(self) -> Self {
+        let mut table = self.into_inner();
+        let encodings = table
+            .iter()
+            .filter_map(|(ident, column)| match column {
+                OwnedColumn::VarChar(values)
+                    if !table.contains_key(&varchar_key_column_ident(ident)) =>
+                {
+                    Some((ident.clone(), values.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (ident, values) in encodings {
+            table.insert(
+                varchar_key_column_ident(&ident),
+                OwnedColumn::Scalar(values.iter().map(|value| varchar_key(value)).collect()),
+            );
+            table.insert(
+                varchar_length_column_ident(&ident),
+                OwnedColumn::BigInt(values.iter().map(|value| varchar_length(value)).collect()),
+            );
+        }
+        Self::try_new(table).expect("Encoding columns have the length of their columns")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::{
+        database::{owned_table_utility::*, TableRef},
+        scalar::test_scalar::TestScalar,
+    };
+    use alloc::format;
+
+    #[test]
+    fn we_can_get_encoding_column_identifiers() {
+        let ident = Ident::new("a");
+        let key = varchar_key_column_ident(&ident);
+        let length = varchar_length_column_ident(&ident);
+        assert_eq!(key, Ident::new("a$key"));
+        assert_eq!(length, Ident::new("a$length"));
+        assert!(is_varchar_encoding_column_ident(&key));
+        assert!(is_varchar_encoding_column_ident(&length));
+        assert!(!is_varchar_encoding_column_ident(&ident));
+
+        let column_ref = ColumnRef::new(TableRef::new("sxt", "table"), ident, ColumnType::VarChar);
+        assert_eq!(
+            varchar_key_column_ref(&column_ref),
+            ColumnRef::new(TableRef::new("sxt", "table"), key, ColumnType::Scalar)
+        );
+        assert_eq!(
+            varchar_length_column_ref(&column_ref),
+            ColumnRef::new(TableRef::new("sxt", "table"), length, ColumnType::BigInt)
+        );
+    }
+
+    #[test]
+    fn we_can_encode_strings_as_keys_that_preserve_their_order() {
+        assert_eq!(varchar_key_limbs(""), [0; 4]);
+        assert_eq!(varchar_key_limbs("a"), [1, 0, 0, 0x61 << 48]);
+        assert_eq!(varchar_key_limbs("ab"), [2, 0, 0, 0x6162 << 40]);
+        let mut strings = [
+            "", "\0", "a", "a\0", "a\0b", "ab", "abc", "b", "ba", "é", "ü", "z",
+        ];
+        strings.reverse();
+        strings.sort_by_key(|value| varchar_key::<TestScalar>(value));
+        let mut sorted = strings;
+        sorted.sort_unstable();
+        assert_eq!(strings, sorted);
+    }
+
+    #[test]
+    fn strings_sharing_their_leading_bytes_are_ordered_by_length() {
+        let long = "a".repeat(VARCHAR_KEY_PREFIX_BYTES);
+        let longer = format!("{long}b");
+        let longest = format!("{long}ab");
+        assert!(varchar_key::<TestScalar>(&long) < varchar_key(&longer));
+        assert!(varchar_key::<TestScalar>(&longer) < varchar_key(&longest));
+        assert_eq!(
+            varchar_key::<TestScalar>(&format!("{long}c")),
+            varchar_key(&longer)
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_key_bounds_of_strings_with_a_prefix() {
+        let (low, high) = varchar_prefix_key_limb_bounds("ab").unwrap();
+        let low = TestScalar::from(low);
+        let high = TestScalar::from(high);
+        for value in ["ab", "ab\0", "abc", "ab\u{10ffff}", &"ab".repeat(20)] {
+            let key = varchar_key::<TestScalar>(value);
+            assert!(low <= key && key <= high, "{value:?}");
+        }
+        for value in ["", "a", "a\u{10ffff}", "ac", "b"] {
+            let key = varchar_key::<TestScalar>(value);
+            assert!(key < low || high < key, "{value:?}");
+        }
+        let (low, _) = varchar_prefix_key_limb_bounds("ab\0").unwrap();
+        assert!(varchar_key::<TestScalar>("ab") < TestScalar::from(low));
+
+        let (low, high) = varchar_prefix_key_limb_bounds("").unwrap();
+        assert_eq!(low, [0; 4]);
+        assert_eq!(high, [u64::MAX, u64::MAX, u64::MAX, u64::MAX >> 8]);
+        assert!(varchar_prefix_key_limb_bounds(&"a".repeat(VARCHAR_KEY_PREFIX_BYTES)).is_some());
+        assert!(
+            varchar_prefix_key_limb_bounds(&"a".repeat(VARCHAR_KEY_PREFIX_BYTES + 1)).is_none()
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_length_of_strings_in_characters() {
+        assert_eq!(varchar_length(""), 0);
+        assert_eq!(varchar_length("abc"), 3);
+        assert_eq!(varchar_length("héllo"), 5);
+    }
+
+    #[test]
+    fn we_can_add_the_encoding_columns_to_a_table() {
+        let table =
+            owned_table::<TestScalar>([bigint("a", [1_i64, 2]), varchar("b", ["x", "héllo"])]);
+        let expected = owned_table([
+            bigint("a", [1_i64, 2]),
+            varchar("b", ["x", "héllo"]),
+            varchar_keys("b", ["x", "héllo"]),
+            varchar_lengths("b", ["x", "héllo"]),
+        ]);
+        let table = table.with_varchar_encodings();
+        assert_eq!(table, expected);
+        assert_eq!(table.clone().with_varchar_encodings(), table);
+    }
+}
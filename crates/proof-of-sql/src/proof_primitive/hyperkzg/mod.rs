@@ -10,7 +10,7 @@ use ark_bn254::G1Affine;
 #[cfg(feature = "blitzar")]
 use blitzar;
 use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
-use ff::Field;
+use ff::{Field, PrimeField};
 #[cfg(not(feature = "blitzar"))]
 use itertools::Itertools;
 use nova_snark::{
@@ -25,7 +25,7 @@ use nova_snark::{
 };
 #[cfg(not(feature = "blitzar"))]
 use nova_snark::{provider::hyperkzg::CommitmentEngine, traits::commitment::CommitmentEngineTrait};
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use tracing::{span, Level};
 
 /// The scalar used in the `HyperKZG` PCS. This is the BN254 scalar.
@@ -36,9 +36,21 @@ pub type BNScalar = MontScalar<ark_bn254::FrConfig>;
 pub struct HyperKZGEngine;
 
 type NovaCommitment = nova_snark::provider::hyperkzg::Commitment<HyperKZGEngine>;
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+type NovaAffine = nova_snark::provider::bn256_grumpkin::bn256::Affine;
+type NovaBase = <HyperKZGEngine as Engine>::Base;
+
+mod ptau;
+pub use ptau::PtauError;
+
+mod public_setup;
+pub use public_setup::HyperKZGPublicSetup;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 /// A newtype wrapper of nova's hyperkzg commitment.
 /// This is the commitment type used in the hyperkzg proof system.
+///
+/// The commitment serializes as the big-endian `x` and `y` coordinates, which is how the EVM represents a point.
+/// The identity is `(0, 0)`.
 pub struct HyperKZGCommitment {
     /// The underlying commitment.
     pub commitment: NovaCommitment,
@@ -47,6 +59,66 @@ pub struct HyperKZGCommitment {
 /// The evaluation proof for the `HyperKZG` PCS.
 pub type HyperKZGCommitmentEvaluationProof = EvaluationArgument<HyperKZGEngine>;
 
+/// Converts a field element to a big-endian word.
+fn to_be_word<F: PrimeField>(value: &F) -> [u8; 32] {
+    let mut word = [0; 32];
+    word.copy_from_slice(value.to_repr().as_ref());
+    word.reverse();
+    word
+}
+
+/// Converts a big-endian word to a field element. Returns `None` if the word is not canonical.
+fn from_be_word<F: PrimeField>(word: &[u8; 32]) -> Option<F> {
+    let mut repr = F::Repr::default();
+    repr.as_mut().copy_from_slice(word);
+    repr.as_mut().reverse();
+    F::from_repr_vartime(repr)
+}
+
+fn affine_to_be_words(point: &NovaAffine) -> [[u8; 32]; 2] {
+    [to_be_word(&point.x), to_be_word(&point.y)]
+}
+
+/// Returns `None` if the words are not canonical or are not a point on the curve.
+fn affine_from_be_words(words: &[[u8; 32]; 2]) -> Option<NovaAffine> {
+    let x: NovaBase = from_be_word(&words[0])?;
+    let y: NovaBase = from_be_word(&words[1])?;
+    let is_identity = x.is_zero_vartime() && y.is_zero_vartime();
+    let is_on_curve = y.square() == x.square() * x + NovaBase::from(3);
+    (is_identity || is_on_curve).then_some(NovaAffine { x, y })
+}
+
+impl HyperKZGCommitment {
+    fn to_be_words(&self) -> [[u8; 32]; 2] {
+        // The transcript representation starts with the little-endian `x` and `y` coordinates.
+        let bytes = self.commitment.to_transcript_bytes();
+        let mut words = [[0; 32]; 2];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(32)) {
+            word.copy_from_slice(chunk);
+            word.reverse();
+        }
+        words
+    }
+}
+
+impl Serialize for HyperKZGCommitment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [x, y] = self.to_be_words();
+        (x, y).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HyperKZGCommitment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y) = <([u8; 32], [u8; 32])>::deserialize(deserializer)?;
+        let point =
+            affine_from_be_words(&[x, y]).ok_or_else(|| D::Error::custom("invalid point"))?;
+        Ok(Self {
+            commitment: NovaCommitment::new(point.into()),
+        })
+    }
+}
+
 impl AddAssign for HyperKZGCommitment {
     fn add_assign(&mut self, rhs: Self) {
         self.commitment = self.commitment + rhs.commitment;
@@ -210,18 +282,32 @@ impl TranscriptEngineTrait<HyperKZGEngine> for Keccak256Transcript {
     }
 
     fn squeeze(&mut self, _label: &'static [u8]) -> Result<NovaScalar, NovaError> {
-        Ok(Transcript::scalar_challenge_as_be::<BNScalar>(self).into())
+        let challenge = Transcript::scalar_challenge_as_be::<BNScalar>(self).into();
+        // Discard the next challenge, so that the state is the hash of the challenge, as in `draw_challenge`
+        Transcript::challenge_as_le(self);
+        Ok(challenge)
     }
 
     fn absorb<T: TranscriptReprTrait<<HyperKZGEngine as Engine>::GE>>(
         &mut self,
-        _label: &'static [u8],
+        label: &'static [u8],
         o: &T,
     ) {
+        let bytes = o.to_transcript_bytes();
+        let chunks: Vec<_> = bytes.chunks(32).collect();
+        // The evaluations are absorbed as `[ypos, yneg, y]`, while the EVM verifier reads them round by round.
+        let chunks = if label == b"v" && chunks.len() % 3 == 0 {
+            let ell = chunks.len() / 3;
+            (0..chunks.len())
+                .map(|k| chunks[(k % 3) * ell + k / 3])
+                .collect()
+        } else {
+            chunks
+        };
         Transcript::extend_as_le_from_refs(
             self,
-            o.to_transcript_bytes()
-                .chunks(32)
+            chunks
+                .into_iter()
                 // Reverse the bytes in each 32 byte chunk, making them effectivelly big-endian
                 .flat_map(|chunk| chunk.iter().rev()),
         );
@@ -286,34 +372,155 @@ impl CommitmentEvaluationProof for HyperKZGCommitmentEvaluationProof {
         if generators_offset != 0 {
             Err(NovaError::InvalidPCS)?;
         }
-        let nova_commit = commit_batch
-            .iter()
-            .zip(batching_factors)
-            .map(|(c, m)| c.commitment * NovaScalar::from(m))
-            .fold(NovaCommitment::default(), Add::add);
-        let nova_eval = evaluations
-            .iter()
-            .zip(batching_factors)
-            .map(|(&e, &f)| e * f)
-            .sum::<Self::Scalar>();
-        let mut nova_point = slice_ops::slice_cast(b_point);
-        nova_point.reverse();
-        if nova_point.is_empty() {
-            nova_point.push(NovaScalar::ZERO);
-        }
         transcript.wrap_transcript(|keccak_transcript| {
-            EvaluationEngine::verify(
-                setup,
-                keccak_transcript,
-                &nova_commit,
-                &nova_point,
-                &nova_eval.into(),
+            verify_batched_proof_impl(
                 self,
+                keccak_transcript,
+                commit_batch,
+                batching_factors,
+                evaluations,
+                b_point,
+                setup,
             )
         })
     }
 }
 
+fn verify_batched_proof_impl(
+    proof: &HyperKZGCommitmentEvaluationProof,
+    transcript: &mut Keccak256Transcript,
+    commit_batch: &[HyperKZGCommitment],
+    batching_factors: &[BNScalar],
+    evaluations: &[BNScalar],
+    b_point: &[BNScalar],
+    setup: &VerifierKey<HyperKZGEngine>,
+) -> Result<(), NovaError> {
+    let nova_commit = commit_batch
+        .iter()
+        .zip(batching_factors)
+        .map(|(c, m)| c.commitment * NovaScalar::from(m))
+        .fold(NovaCommitment::default(), Add::add);
+    let nova_eval = evaluations
+        .iter()
+        .zip(batching_factors)
+        .map(|(&e, &f)| e * f)
+        .sum::<BNScalar>();
+    let mut nova_point = slice_ops::slice_cast(b_point);
+    nova_point.reverse();
+    if nova_point.is_empty() {
+        nova_point.push(NovaScalar::ZERO);
+    }
+    EvaluationEngine::verify(
+        setup,
+        transcript,
+        &nova_commit,
+        &nova_point,
+        &nova_eval.into(),
+        proof,
+    )
+}
+
+/// Verifies a batched evaluation proof the way [`CommitmentEvaluationProof::verify_batched_proof`] does,
+/// except that the outer transcript is a single word, as the EVM verifier keeps it.
+///
+/// `transcript_state` is the challenge that `wrap_transcript` would seed the inner transcript with.
+/// On success, this returns the final challenge of the inner transcript, which the caller appends to its transcript.
+pub(crate) fn verify_batched_proof_with_transcript_state(
+    proof: &HyperKZGCommitmentEvaluationProof,
+    transcript_state: [u8; 32],
+    commit_batch: &[HyperKZGCommitment],
+    batching_factors: &[BNScalar],
+    evaluations: &[BNScalar],
+    b_point: &[BNScalar],
+    setup: &VerifierKey<HyperKZGEngine>,
+) -> Result<[u8; 32], NovaError> {
+    let mut transcript: Keccak256Transcript = Transcript::new();
+    transcript.extend_as_le([transcript_state]);
+    verify_batched_proof_impl(
+        proof,
+        &mut transcript,
+        commit_batch,
+        batching_factors,
+        evaluations,
+        b_point,
+        setup,
+    )?;
+    Ok(transcript.challenge_as_le())
+}
+
+/// The fields of nova's `EvaluationArgument`, which are only reachable through serde.
+#[derive(Serialize, Deserialize)]
+struct EvaluationArgumentFields {
+    com: Vec<NovaAffine>,
+    w: Vec<NovaAffine>,
+    v: Vec<Vec<NovaScalar>>,
+}
+
+/// A [`HyperKZGCommitmentEvaluationProof`] in the layout that the EVM verifier reads.
+///
+/// Points are `(x, y)` and scalars are single words, all big-endian.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EVMHyperKZGProof {
+    /// The commitments to the intermediate polynomials.
+    pub com: Vec<[[u8; 32]; 2]>,
+    /// For each round `i`, the evaluations `v_{i,0}`, `v_{i,1}` and `v_{i,2}` at `r`, `-r` and `r^2`.
+    pub v: Vec<[[u8; 32]; 3]>,
+    /// The witnesses for the openings at `r`, `-r` and `r^2`.
+    pub w: [[[u8; 32]; 2]; 3],
+}
+
+impl EVMHyperKZGProof {
+    /// Returns `None` if the proof does not have the shape of a `HyperKZG` proof.
+    pub(crate) fn try_from_proof(proof: &HyperKZGCommitmentEvaluationProof) -> Option<Self> {
+        let config = bincode::config::standard();
+        let bytes = bincode::serde::encode_to_vec(proof, config).ok()?;
+        let (fields, _): (EvaluationArgumentFields, _) =
+            bincode::serde::decode_from_slice(&bytes, config).ok()?;
+        let [ypos, yneg, y] = <[Vec<NovaScalar>; 3]>::try_from(fields.v).ok()?;
+        if ypos.len() != y.len() || yneg.len() != y.len() {
+            return None;
+        }
+        let w = <[NovaAffine; 3]>::try_from(fields.w).ok()?;
+        Some(Self {
+            com: fields.com.iter().map(affine_to_be_words).collect(),
+            v: itertools::izip!(&ypos, &yneg, &y)
+                .map(|(v0, v1, v2)| [to_be_word(v0), to_be_word(v1), to_be_word(v2)])
+                .collect(),
+            w: w.each_ref().map(affine_to_be_words),
+        })
+    }
+
+    /// Returns `None` if any word is not a canonical scalar or point.
+    pub(crate) fn try_into_proof(&self) -> Option<HyperKZGCommitmentEvaluationProof> {
+        let v = (0..3)
+            .map(|j| {
+                self.v
+                    .iter()
+                    .map(|vi| from_be_word::<NovaScalar>(&vi[j]))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let fields = EvaluationArgumentFields {
+            com: self
+                .com
+                .iter()
+                .map(affine_from_be_words)
+                .collect::<Option<_>>()?,
+            w: self
+                .w
+                .iter()
+                .map(affine_from_be_words)
+                .collect::<Option<_>>()?,
+            v,
+        };
+        let config = bincode::config::standard();
+        let bytes = bincode::serde::encode_to_vec(&fields, config).ok()?;
+        bincode::serde::decode_from_slice(&bytes, config)
+            .ok()
+            .map(|(proof, _)| proof)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -641,4 +848,77 @@ mod tests {
         let expected: HyperKZGCommitment = HyperKZGCommitment::from(&G1Affine::generator());
         assert_eq!(commitment.commitment, expected.commitment);
     }
+
+    fn evm_config() -> bincode::config::Configuration<
+        bincode::config::BigEndian,
+        bincode::config::Fixint,
+        bincode::config::NoLimit,
+    > {
+        bincode::config::legacy()
+            .with_fixed_int_encoding()
+            .with_big_endian()
+    }
+
+    #[test]
+    fn we_can_serialize_commitments_as_evm_points() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 8);
+        let commitments = HyperKZGCommitment::compute_commitments(
+            &[
+                CommittableColumn::BigInt(&[1, 2, 3]),
+                CommittableColumn::BigInt(&[0; 0]),
+            ],
+            0,
+            &&ck,
+        );
+        for commitment in commitments {
+            let bytes = bincode::serde::encode_to_vec(commitment, evm_config()).unwrap();
+            assert_eq!(bytes.len(), 64);
+            let (deserialized, _): (HyperKZGCommitment, _) =
+                bincode::serde::decode_from_slice(&bytes, evm_config()).unwrap();
+            assert_eq!(deserialized, commitment);
+        }
+        let identity =
+            bincode::serde::encode_to_vec(HyperKZGCommitment::default(), evm_config()).unwrap();
+        assert_eq!(identity, [0; 64]);
+    }
+
+    #[test]
+    fn we_cannot_deserialize_commitments_that_are_not_points() {
+        // (1, 1) is not on the curve.
+        let mut bytes = [0; 64];
+        bytes[31] = 1;
+        bytes[63] = 1;
+        assert!(
+            bincode::serde::decode_from_slice::<HyperKZGCommitment, _>(&bytes, evm_config())
+                .is_err()
+        );
+        // The coordinates must be less than the modulus.
+        let bytes = [0xFF; 64];
+        assert!(
+            bincode::serde::decode_from_slice::<HyperKZGCommitment, _>(&bytes, evm_config())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn we_can_convert_evaluation_proofs_to_and_from_the_evm_layout() {
+        let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 32);
+        let mut rng = ark_std::test_rng();
+        let a: Vec<BNScalar> = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+            .take(20)
+            .collect();
+        let b_point: Vec<BNScalar> = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+            .take(5)
+            .collect();
+        let mut transcript: Keccak256Transcript = Transcript::new();
+        let proof = HyperKZGCommitmentEvaluationProof::new(&mut transcript, &a, &b_point, 0, &&ck);
+        let evm_proof = EVMHyperKZGProof::try_from_proof(&proof).unwrap();
+        assert_eq!(evm_proof.com.len(), b_point.len() - 1);
+        assert_eq!(evm_proof.v.len(), b_point.len());
+        let round_tripped_proof = evm_proof.try_into_proof().unwrap();
+        assert_eq!(
+            EVMHyperKZGProof::try_from_proof(&round_tripped_proof).unwrap(),
+            evm_proof
+        );
+    }
 }
@@ -0,0 +1,231 @@
+//! A loader for the powers of tau files of `snarkjs`, which is also the format of the perpetual powers of tau ceremony.
+//!
+//! The file starts with the magic `ptau`, a `u32` version and a `u32` section count.
+//! Each section is a `u32` type and a `u64` size, followed by its data. All integers are little-endian.
+//! Only the header (1), the `τ^i·G` section (2) and the `τ^i·H` section (3) are read.
+//! The number of powers is taken from the size of the `τ^i·G` section, so truncated files can be read as well.
+//! Field elements are little-endian and in Montgomery form, which is also how `ark-ff` represents them.
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ec::{
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr,
+};
+use ark_ff::{BigInt, PrimeField};
+use snafu::Snafu;
+use std::io::{self, Read, Seek, SeekFrom};
+
+const MAGIC: [u8; 4] = *b"ptau";
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+const FIELD_SIZE: usize = 32;
+
+/// Errors that can occur when loading a powers of tau file.
+#[derive(Snafu, Debug)]
+pub enum PtauError {
+    /// The file cannot be read.
+    #[snafu(
+        context(false),
+        display("failed to read the powers of tau file: {source}")
+    )]
+    Io {
+        /// The underlying error
+        source: io::Error,
+    },
+    /// The file is not a powers of tau file.
+    #[snafu(display("not a powers of tau file"))]
+    InvalidMagic,
+    /// The file is not over the BN254 base field.
+    #[snafu(display("the powers of tau file is not over BN254"))]
+    UnsupportedCurve,
+    /// A section that is needed is missing, or comes before the header.
+    #[snafu(display("missing section {section} of the powers of tau file"))]
+    MissingSection {
+        /// The type of the section
+        section: u32,
+    },
+    /// The file does not have enough powers of tau.
+    #[snafu(display(
+        "the powers of tau file has {available} powers, but {requested} were requested"
+    ))]
+    InsufficientPowers {
+        /// The number of powers in the file
+        available: usize,
+        /// The number of powers that were requested
+        requested: usize,
+    },
+    /// A section is larger than any file can be.
+    #[snafu(display("invalid size of section {section} of the powers of tau file"))]
+    InvalidSectionSize {
+        /// The type of the section
+        section: u32,
+    },
+    /// A point is not canonical, is not on the curve or does not start the powers with the generator.
+    #[snafu(display("invalid point in the powers of tau file"))]
+    InvalidPoint,
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_limbs(reader: &mut impl Read) -> io::Result<BigInt<4>> {
+    let mut limbs = [0; 4];
+    for limb in &mut limbs {
+        *limb = read_u64(reader)?;
+    }
+    Ok(BigInt::new(limbs))
+}
+
+/// Reads a field element in Montgomery form.
+fn read_fq(reader: &mut impl Read) -> Result<Fq, PtauError> {
+    let montgomery = read_limbs(reader)?;
+    if montgomery >= Fq::MODULUS {
+        return Err(PtauError::InvalidPoint);
+    }
+    Ok(Fq::new_unchecked(montgomery))
+}
+
+fn read_fq2(reader: &mut impl Read) -> Result<Fq2, PtauError> {
+    Ok(Fq2::new(read_fq(reader)?, read_fq(reader)?))
+}
+
+fn check_point<P: SWCurveConfig>(point: Affine<P>) -> Result<Affine<P>, PtauError> {
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+        .then_some(point)
+        .ok_or(PtauError::InvalidPoint)
+}
+
+fn read_g1(reader: &mut impl Read) -> Result<G1Affine, PtauError> {
+    check_point(G1Affine::new_unchecked(read_fq(reader)?, read_fq(reader)?))
+}
+
+fn read_g2(reader: &mut impl Read) -> Result<G2Affine, PtauError> {
+    check_point(G2Affine::new_unchecked(
+        read_fq2(reader)?,
+        read_fq2(reader)?,
+    ))
+}
+
+/// Reads the header section, checking that the file is over BN254.
+fn read_header(reader: &mut impl Read) -> Result<(), PtauError> {
+    let field_size = read_u32(reader)?;
+    let modulus = read_limbs(reader)?;
+    if usize::try_from(field_size) != Ok(FIELD_SIZE) || modulus != Fq::MODULUS {
+        return Err(PtauError::UnsupportedCurve);
+    }
+    Ok(())
+}
+
+/// Reads `τ^i·G` for `i < len`, along with `τ·H`, from a powers of tau file.
+pub(super) fn read_ptau(
+    mut reader: impl Read + Seek,
+    len: usize,
+) -> Result<(Vec<G1Affine>, G2Affine), PtauError> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(PtauError::InvalidMagic);
+    }
+    let _version = read_u32(&mut reader)?;
+    let num_sections = read_u32(&mut reader)?;
+
+    let mut has_header = false;
+    let mut tau_powers = None;
+    let mut tau_h = None;
+    for _ in 0..num_sections {
+        let section = read_u32(&mut reader)?;
+        let size = read_u64(&mut reader)?;
+        let end = reader
+            .stream_position()?
+            .checked_add(size)
+            .ok_or(PtauError::InvalidSectionSize { section })?;
+        match (section, has_header) {
+            (HEADER_SECTION, _) => {
+                read_header(&mut reader)?;
+                has_header = true;
+            }
+            (TAU_G1_SECTION | TAU_G2_SECTION, false) => {
+                return Err(PtauError::MissingSection {
+                    section: HEADER_SECTION,
+                })
+            }
+            (TAU_G1_SECTION, true) => {
+                let available = usize::try_from(size).unwrap_or(usize::MAX) / (2 * FIELD_SIZE);
+                if len > available {
+                    return Err(PtauError::InsufficientPowers {
+                        available,
+                        requested: len,
+                    });
+                }
+                let powers = (0..len)
+                    .map(|_| read_g1(&mut reader))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if powers.first().is_some_and(|g| *g != G1Affine::generator()) {
+                    return Err(PtauError::InvalidPoint);
+                }
+                tau_powers = Some(powers);
+            }
+            (TAU_G2_SECTION, true) => {
+                if read_g2(&mut reader)? != G2Affine::generator() {
+                    return Err(PtauError::InvalidPoint);
+                }
+                tau_h = Some(read_g2(&mut reader)?);
+            }
+            _ => {}
+        }
+        reader.seek(SeekFrom::Start(end))?;
+    }
+    let tau_powers = tau_powers.ok_or(PtauError::MissingSection {
+        section: TAU_G1_SECTION,
+    })?;
+    let tau_h = tau_h.ok_or(PtauError::MissingSection {
+        section: TAU_G2_SECTION,
+    })?;
+    Ok((tau_powers, tau_h))
+}
+
+/// Writes `τ^i·G`, along with `H` and `τ·H`, as a truncated powers of tau file.
+#[cfg(test)]
+pub(super) fn write_ptau(tau_powers: &[G1Affine], tau_h: &G2Affine) -> Vec<u8> {
+    use ark_ff::BigInteger;
+    let power = tau_powers.len().next_power_of_two().trailing_zeros();
+    let write_fq = |bytes: &mut Vec<u8>, value: &Fq| bytes.extend(value.0.to_bytes_le());
+    let mut header = Vec::new();
+    header.extend(u32::try_from(FIELD_SIZE).unwrap().to_le_bytes());
+    header.extend(Fq::MODULUS.to_bytes_le());
+    header.extend(power.to_le_bytes());
+    header.extend(power.to_le_bytes());
+    let mut tau_g1 = Vec::new();
+    for point in tau_powers {
+        write_fq(&mut tau_g1, &point.x);
+        write_fq(&mut tau_g1, &point.y);
+    }
+    let mut tau_g2 = Vec::new();
+    for point in [G2Affine::generator(), *tau_h] {
+        for value in [point.x.c0, point.x.c1, point.y.c0, point.y.c1] {
+            write_fq(&mut tau_g2, &value);
+        }
+    }
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(3u32.to_le_bytes());
+    for (section, data) in [
+        (HEADER_SECTION, header),
+        (TAU_G1_SECTION, tau_g1),
+        (TAU_G2_SECTION, tau_g2),
+    ] {
+        bytes.extend(section.to_le_bytes());
+        bytes.extend((data.len() as u64).to_le_bytes());
+        bytes.extend(data);
+    }
+    bytes
+}
@@ -0,0 +1,306 @@
+use super::{
+    affine_from_be_words,
+    ptau::{read_ptau, PtauError},
+    HyperKZGEngine, NovaAffine,
+};
+use alloc::vec::Vec;
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField, UniformRand};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::rand::{CryptoRng, Rng};
+use core::iter;
+use group::UncompressedEncoding;
+use nova_snark::{
+    provider::{
+        hyperkzg::{CommitmentKey, EvaluationEngine, VerifierKey},
+        traits::{DlogGroup, PairingGroup},
+    },
+    traits::{evaluation::EvaluationEngineTrait, Engine},
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Error, ErrorKind, Read, Seek, Write},
+    path::Path,
+};
+
+type NovaPoint = <HyperKZGEngine as Engine>::GE;
+type NovaG2Affine = <<NovaPoint as PairingGroup>::G2 as DlogGroup>::AffineGroupElement;
+
+/// The label that the blinding generator of the commitment key is derived from.
+const BLINDING_GENERATOR_LABEL: &[u8] = b"HyperKZG blinding generator";
+
+fn fq_to_be_word(value: Fq) -> [u8; 32] {
+    value
+        .into_bigint()
+        .to_bytes_be()
+        .try_into()
+        .expect("a base field element is 32 bytes")
+}
+
+fn g1_to_nova(point: &G1Affine) -> NovaAffine {
+    affine_from_be_words(&[fq_to_be_word(point.x), fq_to_be_word(point.y)])
+        .expect("an ark point is a nova point")
+}
+
+fn g2_to_nova(point: &G2Affine) -> NovaG2Affine {
+    // The uncompressed encoding is `x.c0`, `x.c1`, `y.c0` and `y.c1`, each little-endian.
+    let mut encoding = <NovaG2Affine as UncompressedEncoding>::Uncompressed::default();
+    for (chunk, value) in encoding
+        .as_mut()
+        .chunks_mut(32)
+        .zip([point.x.c0, point.x.c1, point.y.c0, point.y.c1])
+    {
+        chunk.copy_from_slice(&value.into_bigint().to_bytes_le());
+    }
+    Option::from(NovaG2Affine::from_uncompressed(&encoding)).expect("an ark point is a nova point")
+}
+
+/// The public setup for the `HyperKZG` PCS, which is the powers of a secret `τ` in G1, along with `τ` in G2.
+///
+/// This can be generated at random for testing, or loaded from the powers of tau file of a trusted setup ceremony.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HyperKZGPublicSetup {
+    /// `τ^i·G` for each `i` less than the length of the setup, where `G` is the generator of G1.
+    pub(super) tau_powers: Vec<G1Affine>,
+    /// `τ·H`, where `H` is the generator of G2.
+    pub(super) tau_h: G2Affine,
+}
+
+impl HyperKZGPublicSetup {
+    /// Generate a cryptographically secure random public setup that supports columns of up to `len` rows.
+    ///
+    /// Note: whoever generates this setup learns `τ`, which is enough to forge proofs.
+    pub fn rand<R: CryptoRng + Rng + ?Sized>(len: usize, rng: &mut R) -> Self {
+        Self::rand_impl(len, rng)
+    }
+    /// Generate a random public setup for testing that supports columns of up to `len` rows.
+    pub fn test_rand<R: Rng + ?Sized>(len: usize, rng: &mut R) -> Self {
+        Self::rand_impl(len, rng)
+    }
+    fn rand_impl<R: Rng + ?Sized>(len: usize, rng: &mut R) -> Self {
+        Self::from_tau(Fr::rand(rng), len)
+    }
+    fn from_tau(tau: Fr, len: usize) -> Self {
+        let generator = G1Projective::from(G1Affine::generator());
+        let tau_powers: Vec<_> = iter::successors(Some(Fr::ONE), |power| Some(*power * tau))
+            .map(|power| generator * power)
+            .take(len)
+            .collect();
+        Self {
+            tau_powers: G1Projective::normalize_batch(&tau_powers),
+            tau_h: (G2Projective::from(G2Affine::generator()) * tau).into_affine(),
+        }
+    }
+    /// Load the first `len` powers of tau from a `snarkjs` powers of tau file, such as those of the perpetual powers of tau ceremony.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, is not a BN254 powers of tau file, or has fewer than `len` powers.
+    pub fn load_from_ptau(reader: impl Read + Seek, len: usize) -> Result<Self, PtauError> {
+        let (tau_powers, tau_h) = read_ptau(reader, len)?;
+        Ok(Self { tau_powers, tau_h })
+    }
+    /// Load the first `len` powers of tau from a `snarkjs` powers of tau file on disk.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, is not a BN254 powers of tau file, or has fewer than `len` powers.
+    pub fn load_from_ptau_file(path: &Path, len: usize) -> Result<Self, PtauError> {
+        Self::load_from_ptau(BufReader::new(File::open(path)?), len)
+    }
+    /// The maximum number of rows of a column that this setup supports.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tau_powers.len()
+    }
+    /// Whether this setup supports no columns at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tau_powers.is_empty()
+    }
+    /// `τ·H`, where `H` is the generator of G2. This is the only part of the setup that the verifier needs.
+    #[must_use]
+    pub fn tau_h(&self) -> &G2Affine {
+        &self.tau_h
+    }
+    /// The setup that the prover uses, which is nova's commitment key.
+    #[must_use]
+    pub fn prover_setup(&self) -> CommitmentKey<HyperKZGEngine> {
+        let blinding_generator = NovaPoint::from_label(BLINDING_GENERATOR_LABEL, 1)
+            .pop()
+            .expect("from_label returns the requested number of points");
+        CommitmentKey::new(
+            self.tau_powers.iter().map(g1_to_nova).collect(),
+            blinding_generator,
+            g2_to_nova(&self.tau_h),
+        )
+    }
+    /// The setup that the verifier uses, which is nova's verifier key.
+    #[must_use]
+    pub fn verifier_setup(&self) -> VerifierKey<HyperKZGEngine> {
+        EvaluationEngine::setup(&self.prover_setup()).1
+    }
+    /// Function to save `HyperKZGPublicSetup` to a file in binary form
+    pub fn save_to_file(&self, path: &Path) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let mut serialized_data = Vec::new();
+        self.serialize_with_mode(&mut serialized_data, Compress::No)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{e}")))?;
+        writer.write_all(&serialized_data)?;
+        writer.flush()
+    }
+    /// Function to load `HyperKZGPublicSetup` from a file in binary form
+    pub fn load_from_file(path: &Path) -> std::io::Result<Self> {
+        let mut serialized_data = Vec::new();
+        BufReader::new(File::open(path)?).read_to_end(&mut serialized_data)?;
+        Self::deserialize_with_mode(&mut &serialized_data[..], Compress::No, Validate::Yes)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("{e}")))
+    }
+}
+
+impl CanonicalSerialize for HyperKZGPublicSetup {
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.tau_powers.serialize_with_mode(&mut writer, compress)?;
+        self.tau_h.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.tau_powers.serialized_size(compress) + self.tau_h.serialized_size(compress)
+    }
+}
+
+impl CanonicalDeserialize for HyperKZGPublicSetup {
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            tau_powers: Vec::deserialize_with_mode(&mut reader, compress, validate)?,
+            tau_h: G2Affine::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl Valid for HyperKZGPublicSetup {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.tau_powers.check()?;
+        self.tau_h.check()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::ptau::write_ptau, *};
+    use crate::{
+        base::commitment::commitment_evaluation_proof_test::test_simple_commitment_evaluation_proof,
+        proof_primitive::hyperkzg::HyperKZGCommitmentEvaluationProof,
+    };
+    use ark_std::test_rng;
+    use std::io::Cursor;
+
+    #[test]
+    fn we_can_serialize_and_deserialize_a_public_setup() {
+        let setup = HyperKZGPublicSetup::test_rand(8, &mut test_rng());
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            setup.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), setup.serialized_size(compress));
+            let deserialized =
+                HyperKZGPublicSetup::deserialize_with_mode(&bytes[..], compress, Validate::Yes)
+                    .unwrap();
+            assert_eq!(deserialized, setup);
+        }
+    }
+
+    #[test]
+    fn we_can_save_and_load_a_public_setup() {
+        let setup = HyperKZGPublicSetup::test_rand(8, &mut test_rng());
+        let path = std::env::temp_dir().join("hyperkzg_public_setup_round_trip.bin");
+        setup.save_to_file(&path).unwrap();
+        assert_eq!(HyperKZGPublicSetup::load_from_file(&path).unwrap(), setup);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn we_can_load_a_public_setup_from_a_ptau_file() {
+        let tau = Fr::from(123_u64);
+        let setup = HyperKZGPublicSetup::from_tau(tau, 8);
+        let ptau = write_ptau(&setup.tau_powers, &setup.tau_h);
+        assert_eq!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&ptau), 8).unwrap(),
+            setup
+        );
+        assert_eq!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&ptau), 5).unwrap(),
+            HyperKZGPublicSetup::from_tau(tau, 5)
+        );
+        assert!(matches!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&ptau), 9),
+            Err(PtauError::InsufficientPowers {
+                available: 8,
+                requested: 9
+            })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_load_a_public_setup_from_an_invalid_ptau_file() {
+        let setup = HyperKZGPublicSetup::from_tau(Fr::from(123_u64), 4);
+        let ptau = write_ptau(&setup.tau_powers, &setup.tau_h);
+
+        let mut invalid_magic = ptau.clone();
+        invalid_magic[0] = b'x';
+        assert!(matches!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&invalid_magic), 4),
+            Err(PtauError::InvalidMagic)
+        ));
+
+        // The modulus starts after the magic, the version, the section count, the section header and the field size.
+        let mut invalid_modulus = ptau.clone();
+        invalid_modulus[4 + 4 + 4 + 12 + 4] ^= 1;
+        assert!(matches!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&invalid_modulus), 4),
+            Err(PtauError::UnsupportedCurve)
+        ));
+
+        // The last byte is the most significant byte of `τ·H`'s `y.c1`.
+        let mut invalid_point = ptau.clone();
+        *invalid_point.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&invalid_point), 4),
+            Err(PtauError::InvalidPoint)
+        ));
+
+        assert!(matches!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&ptau[..ptau.len() - 1]), 4),
+            Err(PtauError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_load_a_public_setup_from_a_ptau_file_with_a_huge_section() {
+        let mut ptau = b"ptau".to_vec();
+        ptau.extend(1_u32.to_le_bytes());
+        ptau.extend(1_u32.to_le_bytes());
+        ptau.extend(99_u32.to_le_bytes());
+        ptau.extend(u64::MAX.to_le_bytes());
+        assert!(matches!(
+            HyperKZGPublicSetup::load_from_ptau(Cursor::new(&ptau), 4),
+            Err(PtauError::InvalidSectionSize { section: 99 })
+        ));
+    }
+
+    #[test]
+    fn we_can_create_hyperkzg_evaluation_proofs_with_a_public_setup() {
+        let setup = HyperKZGPublicSetup::test_rand(32, &mut test_rng());
+        let ck = setup.prover_setup();
+        let vk = setup.verifier_setup();
+        test_simple_commitment_evaluation_proof::<HyperKZGCommitmentEvaluationProof>(&&ck, &&vk);
+    }
+}
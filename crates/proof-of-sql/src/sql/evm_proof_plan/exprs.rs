@@ -1,91 +1,411 @@
 use super::error::Error;
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue},
+        database::{ColumnRef, ColumnType, LiteralValue},
         map::IndexSet,
+        math::{decimal::Precision, i256::I256},
     },
     sql::proof_exprs::{self, DynProofExpr},
 };
-use alloc::boxed::Box;
-use serde::Serialize;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::AggregationOperator,
+    posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone},
+};
+use serde::{Deserialize, Serialize};
 
 /// Represents an expression that can be serialized for EVM.
-#[derive(Serialize)]
+///
+/// New variants are only ever appended so that the encoding of existing plans does not change.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(super) enum Expr {
     Column(ColumnExpr),
-    Equals(EqualsExpr),
+    Equals(BinaryExpr),
     Literal(LiteralExpr),
+    And(BinaryExpr),
+    Or(BinaryExpr),
+    Not(Box<Expr>),
+    Inequality(InequalityExpr),
+    InList(InListExpr),
+    Between(BetweenExpr),
+    AddSubtract(AddSubtractExpr),
+    Multiply(BinaryExpr),
+    Divide(BinaryExpr),
+    Modulo(BinaryExpr),
+    Cast(CastExpr),
+    Case(CaseExpr),
+    DateTrunc(TimeFieldExpr),
+    Extract(TimeFieldExpr),
+    Aggregate(AggregateExpr),
 }
 impl Expr {
     /// Try to create an `Expr` from a `DynProofExpr`.
+    ///
+    /// Columns that are not yet part of `column_refs` are appended to it.
     pub(super) fn try_from_proof_expr(
         expr: &DynProofExpr,
-        column_refs: &IndexSet<ColumnRef>,
+        column_refs: &mut IndexSet<ColumnRef>,
     ) -> Result<Self, Error> {
-        match expr {
+        Ok(match expr {
             DynProofExpr::Column(column_expr) => {
-                ColumnExpr::try_from_proof_expr(column_expr, column_refs).map(Self::Column)
+                Self::Column(ColumnExpr::from_proof_expr(column_expr, column_refs))
             }
             DynProofExpr::Literal(literal_expr) => {
-                LiteralExpr::try_from_proof_expr(literal_expr).map(Self::Literal)
+                Self::Literal(LiteralExpr::from(&literal_expr.value))
             }
-            DynProofExpr::Equals(equals_expr) => {
-                EqualsExpr::try_from_proof_expr(equals_expr, column_refs).map(Self::Equals)
+            DynProofExpr::Equals(expr) => Self::Equals(BinaryExpr::try_from_proof_exprs(
+                &expr.lhs,
+                &expr.rhs,
+                column_refs,
+            )?),
+            DynProofExpr::And(expr) => Self::And(BinaryExpr::try_from_proof_exprs(
+                &expr.lhs,
+                &expr.rhs,
+                column_refs,
+            )?),
+            DynProofExpr::Or(expr) => Self::Or(BinaryExpr::try_from_proof_exprs(
+                &expr.lhs,
+                &expr.rhs,
+                column_refs,
+            )?),
+            DynProofExpr::Not(expr) => Self::Not(Box::new(Self::try_from_proof_expr(
+                &expr.expr,
+                column_refs,
+            )?)),
+            DynProofExpr::Inequality(expr) => Self::Inequality(InequalityExpr {
+                lhs: Box::new(Self::try_from_proof_expr(&expr.lhs, column_refs)?),
+                rhs: Box::new(Self::try_from_proof_expr(&expr.rhs, column_refs)?),
+                is_lt: expr.is_lt,
+            }),
+            DynProofExpr::InList(expr) => Self::InList(InListExpr {
+                expr: Box::new(Self::try_from_proof_expr(&expr.expr, column_refs)?),
+                list: expr.list.iter().map(LiteralExpr::from).collect(),
+            }),
+            DynProofExpr::Between(expr) => Self::Between(BetweenExpr {
+                expr: Box::new(Self::try_from_proof_expr(&expr.expr, column_refs)?),
+                low: Box::new(Self::try_from_proof_expr(&expr.low, column_refs)?),
+                high: Box::new(Self::try_from_proof_expr(&expr.high, column_refs)?),
+            }),
+            DynProofExpr::AddSubtract(expr) => Self::AddSubtract(AddSubtractExpr {
+                lhs: Box::new(Self::try_from_proof_expr(&expr.lhs, column_refs)?),
+                rhs: Box::new(Self::try_from_proof_expr(&expr.rhs, column_refs)?),
+                is_subtract: expr.is_subtract,
+            }),
+            DynProofExpr::Multiply(expr) => Self::Multiply(BinaryExpr::try_from_proof_exprs(
+                &expr.lhs,
+                &expr.rhs,
+                column_refs,
+            )?),
+            DynProofExpr::Divide(expr) => Self::Divide(BinaryExpr::try_from_proof_exprs(
+                &expr.lhs,
+                &expr.rhs,
+                column_refs,
+            )?),
+            DynProofExpr::Modulo(expr) => Self::Modulo(BinaryExpr::try_from_proof_exprs(
+                &expr.lhs,
+                &expr.rhs,
+                column_refs,
+            )?),
+            DynProofExpr::Cast(expr) => Self::Cast(CastExpr {
+                from_expr: Box::new(Self::try_from_proof_expr(&expr.from_expr, column_refs)?),
+                to_type: expr.to_type,
+            }),
+            DynProofExpr::Case(expr) => Self::Case(CaseExpr {
+                when_then: expr
+                    .when_then
+                    .iter()
+                    .map(|(when, then)| {
+                        Ok((
+                            Self::try_from_proof_expr(when, column_refs)?,
+                            Self::try_from_proof_expr(then, column_refs)?,
+                        ))
+                    })
+                    .collect::<Result<_, Error>>()?,
+                else_expr: Box::new(Self::try_from_proof_expr(&expr.else_expr, column_refs)?),
+            }),
+            DynProofExpr::DateTrunc(expr) => Self::DateTrunc(TimeFieldExpr {
+                expr: Box::new(Self::try_from_proof_expr(&expr.expr, column_refs)?),
+                field: expr.field,
+            }),
+            DynProofExpr::Extract(expr) => Self::Extract(TimeFieldExpr {
+                expr: Box::new(Self::try_from_proof_expr(&expr.expr, column_refs)?),
+                field: expr.field,
+            }),
+            DynProofExpr::Aggregate(expr) => Self::Aggregate(AggregateExpr {
+                op: expr.op,
+                expr: Box::new(Self::try_from_proof_expr(&expr.expr, column_refs)?),
+            }),
+        })
+    }
+
+    /// Try to recreate the `DynProofExpr` that this `Expr` was created from.
+    pub(super) fn try_into_proof_expr(
+        self,
+        column_refs: &IndexSet<ColumnRef>,
+    ) -> Result<DynProofExpr, Error> {
+        let into_boxed_proof_expr =
+            |expr: Box<Self>| expr.try_into_proof_expr(column_refs).map(Box::new);
+        Ok(match self {
+            Self::Column(column_expr) => {
+                DynProofExpr::Column(column_expr.try_into_proof_expr(column_refs)?)
             }
-            _ => Err(Error::NotSupported),
-        }
+            Self::Literal(literal_expr) => {
+                DynProofExpr::Literal(proof_exprs::LiteralExpr::new(literal_expr.into()))
+            }
+            Self::Equals(expr) => {
+                let (lhs, rhs) = expr.try_into_proof_exprs(column_refs)?;
+                DynProofExpr::Equals(proof_exprs::EqualsExpr::new(lhs, rhs))
+            }
+            Self::And(expr) => {
+                let (lhs, rhs) = expr.try_into_proof_exprs(column_refs)?;
+                DynProofExpr::And(proof_exprs::AndExpr::new(lhs, rhs))
+            }
+            Self::Or(expr) => {
+                let (lhs, rhs) = expr.try_into_proof_exprs(column_refs)?;
+                DynProofExpr::Or(proof_exprs::OrExpr::new(lhs, rhs))
+            }
+            Self::Not(expr) => {
+                DynProofExpr::Not(proof_exprs::NotExpr::new(into_boxed_proof_expr(expr)?))
+            }
+            Self::Inequality(expr) => DynProofExpr::Inequality(proof_exprs::InequalityExpr::new(
+                into_boxed_proof_expr(expr.lhs)?,
+                into_boxed_proof_expr(expr.rhs)?,
+                expr.is_lt,
+            )),
+            Self::InList(expr) => DynProofExpr::InList(proof_exprs::InListExpr::new(
+                into_boxed_proof_expr(expr.expr)?,
+                expr.list.into_iter().map(Into::into).collect(),
+            )),
+            Self::Between(expr) => DynProofExpr::Between(proof_exprs::BetweenExpr::new(
+                into_boxed_proof_expr(expr.expr)?,
+                into_boxed_proof_expr(expr.low)?,
+                into_boxed_proof_expr(expr.high)?,
+            )),
+            Self::AddSubtract(expr) => {
+                DynProofExpr::AddSubtract(proof_exprs::AddSubtractExpr::new(
+                    into_boxed_proof_expr(expr.lhs)?,
+                    into_boxed_proof_expr(expr.rhs)?,
+                    expr.is_subtract,
+                ))
+            }
+            Self::Multiply(expr) => {
+                let (lhs, rhs) = expr.try_into_proof_exprs(column_refs)?;
+                DynProofExpr::Multiply(proof_exprs::MultiplyExpr::new(lhs, rhs))
+            }
+            Self::Divide(expr) => {
+                let (lhs, rhs) = expr.try_into_proof_exprs(column_refs)?;
+                DynProofExpr::Divide(proof_exprs::DivideExpr::new(lhs, rhs))
+            }
+            Self::Modulo(expr) => {
+                let (lhs, rhs) = expr.try_into_proof_exprs(column_refs)?;
+                DynProofExpr::Modulo(proof_exprs::ModuloExpr::new(lhs, rhs))
+            }
+            Self::Cast(expr) => DynProofExpr::Cast(proof_exprs::CastExpr::new(
+                into_boxed_proof_expr(expr.from_expr)?,
+                expr.to_type,
+            )),
+            Self::Case(expr) => DynProofExpr::Case(proof_exprs::CaseExpr::new(
+                expr.when_then
+                    .into_iter()
+                    .map(|(when, then)| {
+                        Ok((
+                            when.try_into_proof_expr(column_refs)?,
+                            then.try_into_proof_expr(column_refs)?,
+                        ))
+                    })
+                    .collect::<Result<_, Error>>()?,
+                into_boxed_proof_expr(expr.else_expr)?,
+            )),
+            Self::DateTrunc(expr) => DynProofExpr::DateTrunc(proof_exprs::DateTruncExpr::new(
+                into_boxed_proof_expr(expr.expr)?,
+                expr.field,
+            )),
+            Self::Extract(expr) => DynProofExpr::Extract(proof_exprs::ExtractExpr::new(
+                into_boxed_proof_expr(expr.expr)?,
+                expr.field,
+            )),
+            Self::Aggregate(expr) => DynProofExpr::Aggregate(proof_exprs::AggregateExpr::new(
+                expr.op,
+                into_boxed_proof_expr(expr.expr)?,
+            )),
+        })
     }
 }
 
 /// Represents a column expression.
-#[derive(Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(super) struct ColumnExpr {
     column_number: usize,
 }
 impl ColumnExpr {
-    /// Try to create a `ColumnExpr` from a `proof_exprs::ColumnExpr`.
-    fn try_from_proof_expr(
+    /// Create a `ColumnExpr` from a `proof_exprs::ColumnExpr`.
+    fn from_proof_expr(
         expr: &proof_exprs::ColumnExpr,
+        column_refs: &mut IndexSet<ColumnRef>,
+    ) -> Self {
+        Self {
+            column_number: column_refs.insert_full(expr.column_ref.clone()).0,
+        }
+    }
+    /// Try to recreate the `proof_exprs::ColumnExpr` that this `ColumnExpr` was created from.
+    fn try_into_proof_expr(
+        self,
         column_refs: &IndexSet<ColumnRef>,
-    ) -> Result<Self, Error> {
-        Ok(Self {
-            column_number: column_refs
-                .get_index_of(&expr.column_ref)
-                .ok_or(Error::ColumnNotFound)?,
-        })
+    ) -> Result<proof_exprs::ColumnExpr, Error> {
+        Ok(proof_exprs::ColumnExpr::new(
+            column_refs
+                .get_index(self.column_number)
+                .ok_or(Error::ColumnNotFound)?
+                .clone(),
+        ))
     }
 }
 
 /// Represents a literal expression.
-#[derive(Serialize)]
+///
+/// `BigInt` comes first so that it keeps the encoding it had before the other types were supported.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(super) enum LiteralExpr {
     BigInt(i64),
+    Boolean(bool),
+    Uint8(u8),
+    TinyInt(i8),
+    SmallInt(i16),
+    Int(i32),
+    VarChar(String),
+    Int128(i128),
+    Decimal75(Precision, i8, I256),
+    Scalar([u64; 4]),
+    TimeStampTZ(PoSQLTimeUnit, PoSQLTimeZone, i64),
 }
-impl LiteralExpr {
-    /// Try to create a `LiteralExpr` from a `proof_exprs::LiteralExpr`.
-    fn try_from_proof_expr(expr: &proof_exprs::LiteralExpr) -> Result<Self, Error> {
-        match expr.value {
-            LiteralValue::BigInt(value) => Ok(LiteralExpr::BigInt(value)),
-            _ => Err(Error::NotSupported),
+impl From<&LiteralValue> for LiteralExpr {
+    fn from(value: &LiteralValue) -> Self {
+        match value {
+            LiteralValue::BigInt(value) => Self::BigInt(*value),
+            LiteralValue::Boolean(value) => Self::Boolean(*value),
+            LiteralValue::Uint8(value) => Self::Uint8(*value),
+            LiteralValue::TinyInt(value) => Self::TinyInt(*value),
+            LiteralValue::SmallInt(value) => Self::SmallInt(*value),
+            LiteralValue::Int(value) => Self::Int(*value),
+            LiteralValue::VarChar(value) => Self::VarChar(value.clone()),
+            LiteralValue::Int128(value) => Self::Int128(*value),
+            LiteralValue::Decimal75(precision, scale, value) => {
+                Self::Decimal75(*precision, *scale, *value)
+            }
+            LiteralValue::Scalar(limbs) => Self::Scalar(*limbs),
+            LiteralValue::TimeStampTZ(time_unit, timezone, value) => {
+                Self::TimeStampTZ(*time_unit, *timezone, *value)
+            }
+        }
+    }
+}
+impl From<LiteralExpr> for LiteralValue {
+    fn from(value: LiteralExpr) -> Self {
+        match value {
+            LiteralExpr::BigInt(value) => Self::BigInt(value),
+            LiteralExpr::Boolean(value) => Self::Boolean(value),
+            LiteralExpr::Uint8(value) => Self::Uint8(value),
+            LiteralExpr::TinyInt(value) => Self::TinyInt(value),
+            LiteralExpr::SmallInt(value) => Self::SmallInt(value),
+            LiteralExpr::Int(value) => Self::Int(value),
+            LiteralExpr::VarChar(value) => Self::VarChar(value),
+            LiteralExpr::Int128(value) => Self::Int128(value),
+            LiteralExpr::Decimal75(precision, scale, value) => {
+                Self::Decimal75(precision, scale, value)
+            }
+            LiteralExpr::Scalar(limbs) => Self::Scalar(limbs),
+            LiteralExpr::TimeStampTZ(time_unit, timezone, value) => {
+                Self::TimeStampTZ(time_unit, timezone, value)
+            }
         }
     }
 }
 
-/// Represents an equals expression.
-#[derive(Serialize)]
-pub(super) struct EqualsExpr {
+/// Represents an expression with two operands and no other parameters, e.g. an equals expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct BinaryExpr {
     lhs: Box<Expr>,
     rhs: Box<Expr>,
 }
-impl EqualsExpr {
-    /// Try to create an `EqualsExpr` from a `proof_exprs::EqualsExpr`.
-    fn try_from_proof_expr(
-        expr: &proof_exprs::EqualsExpr,
-        column_refs: &IndexSet<ColumnRef>,
+impl BinaryExpr {
+    /// Try to create a `BinaryExpr` from the operands of a binary `DynProofExpr`.
+    fn try_from_proof_exprs(
+        lhs: &DynProofExpr,
+        rhs: &DynProofExpr,
+        column_refs: &mut IndexSet<ColumnRef>,
     ) -> Result<Self, Error> {
-        Ok(EqualsExpr {
-            lhs: Box::new(Expr::try_from_proof_expr(&expr.lhs, column_refs)?),
-            rhs: Box::new(Expr::try_from_proof_expr(&expr.rhs, column_refs)?),
+        Ok(Self {
+            lhs: Box::new(Expr::try_from_proof_expr(lhs, column_refs)?),
+            rhs: Box::new(Expr::try_from_proof_expr(rhs, column_refs)?),
         })
     }
+    /// Try to recreate the operands that this `BinaryExpr` was created from.
+    fn try_into_proof_exprs(
+        self,
+        column_refs: &IndexSet<ColumnRef>,
+    ) -> Result<(Box<DynProofExpr>, Box<DynProofExpr>), Error> {
+        Ok((
+            Box::new(self.lhs.try_into_proof_expr(column_refs)?),
+            Box::new(self.rhs.try_into_proof_expr(column_refs)?),
+        ))
+    }
+}
+
+/// Represents an inequality expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct InequalityExpr {
+    lhs: Box<Expr>,
+    rhs: Box<Expr>,
+    is_lt: bool,
+}
+
+/// Represents an `IN` expression over a list of literals.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct InListExpr {
+    expr: Box<Expr>,
+    list: Vec<LiteralExpr>,
+}
+
+/// Represents a `BETWEEN` expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct BetweenExpr {
+    expr: Box<Expr>,
+    low: Box<Expr>,
+    high: Box<Expr>,
+}
+
+/// Represents a numeric `+` / `-` expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct AddSubtractExpr {
+    lhs: Box<Expr>,
+    rhs: Box<Expr>,
+    is_subtract: bool,
+}
+
+/// Represents a `CAST` expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct CastExpr {
+    from_expr: Box<Expr>,
+    to_type: ColumnType,
+}
+
+/// Represents a `CASE` expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct CaseExpr {
+    when_then: Vec<(Expr, Expr)>,
+    else_expr: Box<Expr>,
+}
+
+/// Represents a `DATE_TRUNC` or `EXTRACT` expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct TimeFieldExpr {
+    expr: Box<Expr>,
+    field: PoSQLTimeField,
+}
+
+/// Represents an aggregate expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct AggregateExpr {
+    op: AggregationOperator,
+    expr: Box<Expr>,
 }
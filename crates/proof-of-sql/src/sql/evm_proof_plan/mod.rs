@@ -2,7 +2,11 @@ mod error;
 mod exprs;
 mod plans;
 mod proof_plan;
+#[cfg(feature = "hyperkzg")]
+mod solidity_verifier;
 #[cfg(test)]
 mod tests;
 
 pub use proof_plan::EVMProofPlan;
+#[cfg(feature = "hyperkzg")]
+pub use solidity_verifier::{generate_solidity_verifier, SolidityVerifierError};
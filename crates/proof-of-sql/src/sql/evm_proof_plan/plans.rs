@@ -1,62 +1,463 @@
 use super::{error::Error, exprs::Expr};
 use crate::{
     base::{
-        database::{ColumnRef, TableRef},
+        database::{ColumnField, ColumnRef, ColumnType, TableRef},
         map::IndexSet,
     },
-    sql::proof_plans::{self, DynProofPlan},
+    sql::{
+        proof::ProofPlan,
+        proof_exprs::AliasedDynProofExpr,
+        proof_plans::{self, DynProofPlan, JoinType},
+    },
 };
-use alloc::vec::Vec;
-use serde::Serialize;
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
 
 /// Represents a plan that can be serialized for EVM.
-#[derive(Serialize)]
+///
+/// New variants are only ever appended so that the encoding of existing plans does not change.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(super) enum Plan {
     Filter(FilterExec),
+    Empty,
+    Table(TableExec),
+    Projection(ProjectionExec),
+    GroupBy(GroupByExec),
+    Slice(SliceExec),
+    Sort(SortExec),
+    Distinct(Box<Plan>),
+    Union(UnionExec),
+    SortMergeJoin(SortMergeJoinExec),
+    SemiJoin(SemiJoinExec),
 }
 
 impl Plan {
     /// Try to create a `Plan` from a `DynProofPlan`.
+    ///
+    /// Columns referenced by expressions that are not yet part of `column_refs` are appended to it.
     pub(super) fn try_from_proof_plan(
         plan: &DynProofPlan,
         table_refs: &IndexSet<TableRef>,
-        column_refs: &IndexSet<ColumnRef>,
+        column_refs: &mut IndexSet<ColumnRef>,
     ) -> Result<Self, Error> {
-        match plan {
-            DynProofPlan::Filter(filter_exec) => {
-                FilterExec::try_from_proof_plan(filter_exec, table_refs, column_refs)
-                    .map(Self::Filter)
+        let try_from_input =
+            |input: &DynProofPlan, column_refs: &mut IndexSet<ColumnRef>| -> Result<_, Error> {
+                Ok(Box::new(Self::try_from_proof_plan(
+                    input,
+                    table_refs,
+                    column_refs,
+                )?))
+            };
+        Ok(match plan {
+            DynProofPlan::Filter(filter_exec) => Self::Filter(FilterExec {
+                input: try_from_input(&filter_exec.input, column_refs)?,
+                where_clause: Expr::try_from_proof_expr(&filter_exec.where_clause, column_refs)?,
+                results: try_from_aliased_exprs(&filter_exec.aliased_results, column_refs)?,
+            }),
+            DynProofPlan::Empty(_) => Self::Empty,
+            DynProofPlan::Table(table_exec) => Self::Table(TableExec::try_from_proof_plan(
+                table_exec,
+                table_refs,
+                column_refs,
+            )?),
+            DynProofPlan::Projection(projection_exec) => Self::Projection(ProjectionExec {
+                input: try_from_input(&projection_exec.input, column_refs)?,
+                results: try_from_aliased_exprs(&projection_exec.aliased_results, column_refs)?,
+            }),
+            DynProofPlan::GroupBy(group_by_exec) => Self::GroupBy(GroupByExec {
+                input: try_from_input(&group_by_exec.input, column_refs)?,
+                where_clause: Expr::try_from_proof_expr(&group_by_exec.where_clause, column_refs)?,
+                group_by_exprs: try_from_aliased_exprs(&group_by_exec.group_by_exprs, column_refs)?,
+                sum_exprs: try_from_aliased_exprs(&group_by_exec.sum_expr, column_refs)?,
+                max_exprs: try_from_aliased_exprs(&group_by_exec.max_expr, column_refs)?,
+                min_exprs: try_from_aliased_exprs(&group_by_exec.min_expr, column_refs)?,
+                count_alias: group_by_exec.count_alias.value.clone(),
+            }),
+            DynProofPlan::Slice(slice_exec) => Self::Slice(SliceExec {
+                input: try_from_input(&slice_exec.input, column_refs)?,
+                skip: slice_exec.skip,
+                fetch: slice_exec.fetch,
+            }),
+            DynProofPlan::Sort(sort_exec) => Self::Sort(SortExec {
+                input: try_from_input(&sort_exec.input, column_refs)?,
+                order_by: sort_exec.order_by.clone(),
+            }),
+            DynProofPlan::Distinct(distinct_exec) => {
+                Self::Distinct(try_from_input(&distinct_exec.input, column_refs)?)
+            }
+            DynProofPlan::Union(union_exec) => Self::Union(UnionExec {
+                inputs: union_exec
+                    .inputs
+                    .iter()
+                    .map(|input| Self::try_from_proof_plan(input, table_refs, column_refs))
+                    .collect::<Result<_, _>>()?,
+                schema: union_exec.schema.iter().map(Field::from).collect(),
+            }),
+            DynProofPlan::SortMergeJoin(join_exec) => Self::SortMergeJoin(SortMergeJoinExec {
+                left: try_from_input(&join_exec.left, column_refs)?,
+                right: try_from_input(&join_exec.right, column_refs)?,
+                left_join_column_indexes: join_exec.left_join_column_indexes.clone(),
+                right_join_column_indexes: join_exec.right_join_column_indexes.clone(),
+                result_idents: join_exec
+                    .result_idents
+                    .iter()
+                    .map(|ident| ident.value.clone())
+                    .collect(),
+                join_type: join_exec.join_type,
+            }),
+            DynProofPlan::SemiJoin(join_exec) => Self::SemiJoin(SemiJoinExec {
+                left: try_from_input(&join_exec.left, column_refs)?,
+                right: try_from_input(&join_exec.right, column_refs)?,
+                left_join_column_indexes: join_exec.left_join_column_indexes.clone(),
+                right_join_column_indexes: join_exec.right_join_column_indexes.clone(),
+                is_anti_join: join_exec.is_anti_join,
+            }),
+        })
+    }
+
+    /// Try to recreate the `DynProofPlan` that this `Plan` was created from.
+    ///
+    /// The preconditions of the plan constructors are checked, so that malformed plans are
+    /// rejected rather than causing a panic.
+    pub(super) fn try_into_proof_plan(
+        self,
+        table_refs: &IndexSet<TableRef>,
+        column_refs: &IndexSet<ColumnRef>,
+    ) -> Result<DynProofPlan, Error> {
+        let try_into_input = |input: Box<Self>| -> Result<_, Error> {
+            Ok(Box::new(
+                input.try_into_proof_plan(table_refs, column_refs)?,
+            ))
+        };
+        Ok(match self {
+            Self::Filter(filter_exec) => {
+                DynProofPlan::Filter(proof_plans::FilterExec::new_with_input(
+                    try_into_aliased_exprs(filter_exec.results, column_refs)?,
+                    try_into_input(filter_exec.input)?,
+                    filter_exec.where_clause.try_into_proof_expr(column_refs)?,
+                ))
+            }
+            Self::Empty => DynProofPlan::Empty(proof_plans::EmptyExec::new()),
+            Self::Table(table_exec) => {
+                DynProofPlan::Table(table_exec.try_into_proof_plan(table_refs, column_refs)?)
+            }
+            Self::Projection(projection_exec) => {
+                DynProofPlan::Projection(proof_plans::ProjectionExec::new(
+                    try_into_aliased_exprs(projection_exec.results, column_refs)?,
+                    try_into_input(projection_exec.input)?,
+                ))
+            }
+            Self::GroupBy(group_by_exec) => {
+                DynProofPlan::GroupBy(proof_plans::GroupByExec::new_with_input(
+                    try_into_aliased_exprs(group_by_exec.group_by_exprs, column_refs)?,
+                    try_into_aliased_exprs(group_by_exec.sum_exprs, column_refs)?,
+                    try_into_aliased_exprs(group_by_exec.max_exprs, column_refs)?,
+                    try_into_aliased_exprs(group_by_exec.min_exprs, column_refs)?,
+                    Ident::new(group_by_exec.count_alias),
+                    try_into_input(group_by_exec.input)?,
+                    group_by_exec
+                        .where_clause
+                        .try_into_proof_expr(column_refs)?,
+                ))
+            }
+            Self::Slice(slice_exec) => DynProofPlan::Slice(proof_plans::SliceExec::new(
+                try_into_input(slice_exec.input)?,
+                slice_exec.skip,
+                slice_exec.fetch,
+            )),
+            Self::Sort(sort_exec) => {
+                let input = try_into_input(sort_exec.input)?;
+                let num_columns = input.get_column_result_fields().len();
+                if sort_exec.order_by.is_empty()
+                    || sort_exec
+                        .order_by
+                        .iter()
+                        .any(|&(index, _)| index >= num_columns)
+                {
+                    return Err(Error::InvalidPlan);
+                }
+                DynProofPlan::Sort(proof_plans::SortExec::new(input, sort_exec.order_by))
+            }
+            Self::Distinct(input) => {
+                let input = try_into_input(input)?;
+                if input.get_column_result_fields().is_empty() {
+                    return Err(Error::InvalidPlan);
+                }
+                DynProofPlan::Distinct(proof_plans::DistinctExec::new(input))
+            }
+            Self::Union(union_exec) => DynProofPlan::Union(proof_plans::UnionExec::new(
+                union_exec
+                    .inputs
+                    .into_iter()
+                    .map(|input| input.try_into_proof_plan(table_refs, column_refs))
+                    .collect::<Result<_, _>>()?,
+                union_exec.schema.into_iter().map(Into::into).collect(),
+            )),
+            Self::SortMergeJoin(join_exec) => {
+                let left = try_into_input(join_exec.left)?;
+                let right = try_into_input(join_exec.right)?;
+                let num_columns_left = left.get_column_result_fields().len();
+                let num_columns_right = right.get_column_result_fields().len();
+                let num_join_columns = join_exec.left_join_column_indexes.len();
+                if num_columns_left == 0
+                    || num_columns_right == 0
+                    || !are_valid_join_columns(
+                        &join_exec.left_join_column_indexes,
+                        &join_exec.right_join_column_indexes,
+                        num_columns_left,
+                        num_columns_right,
+                    )
+                    || join_exec.result_idents.len() + num_join_columns
+                        != num_columns_left + num_columns_right
+                {
+                    return Err(Error::InvalidPlan);
+                }
+                DynProofPlan::SortMergeJoin(proof_plans::SortMergeJoinExec::new_with_join_type(
+                    left,
+                    right,
+                    join_exec.left_join_column_indexes,
+                    join_exec.right_join_column_indexes,
+                    join_exec
+                        .result_idents
+                        .into_iter()
+                        .map(Ident::new)
+                        .collect(),
+                    join_exec.join_type,
+                ))
             }
-            _ => Err(Error::NotSupported),
+            Self::SemiJoin(join_exec) => {
+                let left = try_into_input(join_exec.left)?;
+                let right = try_into_input(join_exec.right)?;
+                if join_exec.left_join_column_indexes.is_empty()
+                    || !are_valid_join_columns(
+                        &join_exec.left_join_column_indexes,
+                        &join_exec.right_join_column_indexes,
+                        left.get_column_result_fields().len(),
+                        right.get_column_result_fields().len(),
+                    )
+                {
+                    return Err(Error::InvalidPlan);
+                }
+                DynProofPlan::SemiJoin(proof_plans::SemiJoinExec::new(
+                    left,
+                    right,
+                    join_exec.left_join_column_indexes,
+                    join_exec.right_join_column_indexes,
+                    join_exec.is_anti_join,
+                ))
+            }
+        })
+    }
+}
+
+/// Whether the join columns are in bounds and there are as many on the left as on the right
+fn are_valid_join_columns(
+    left_join_column_indexes: &[usize],
+    right_join_column_indexes: &[usize],
+    num_columns_left: usize,
+    num_columns_right: usize,
+) -> bool {
+    left_join_column_indexes.len() == right_join_column_indexes.len()
+        && left_join_column_indexes
+            .iter()
+            .all(|&index| index < num_columns_left)
+        && right_join_column_indexes
+            .iter()
+            .all(|&index| index < num_columns_right)
+}
+
+/// Represents an aliased expression.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct AliasedExpr {
+    expr: Expr,
+    alias: String,
+}
+
+/// Try to create `AliasedExpr`s from `AliasedDynProofExpr`s.
+fn try_from_aliased_exprs(
+    aliased_exprs: &[AliasedDynProofExpr],
+    column_refs: &mut IndexSet<ColumnRef>,
+) -> Result<Vec<AliasedExpr>, Error> {
+    aliased_exprs
+        .iter()
+        .map(|aliased_expr| {
+            Ok(AliasedExpr {
+                expr: Expr::try_from_proof_expr(&aliased_expr.expr, column_refs)?,
+                alias: aliased_expr.alias.value.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Try to recreate the `AliasedDynProofExpr`s that the `AliasedExpr`s were created from.
+fn try_into_aliased_exprs(
+    aliased_exprs: Vec<AliasedExpr>,
+    column_refs: &IndexSet<ColumnRef>,
+) -> Result<Vec<AliasedDynProofExpr>, Error> {
+    aliased_exprs
+        .into_iter()
+        .map(|aliased_expr| {
+            Ok(AliasedDynProofExpr {
+                expr: aliased_expr.expr.try_into_proof_expr(column_refs)?,
+                alias: Ident::new(aliased_expr.alias),
+            })
+        })
+        .collect()
+}
+
+/// Represents a column of a schema.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct Field {
+    name: String,
+    column_type: ColumnType,
+}
+impl From<&ColumnField> for Field {
+    fn from(field: &ColumnField) -> Self {
+        Self {
+            name: field.name().value,
+            column_type: field.data_type(),
         }
     }
 }
+impl From<Field> for ColumnField {
+    fn from(field: Field) -> Self {
+        ColumnField::new(Ident::new(field.name), field.column_type)
+    }
+}
 
 /// Represents a filter execution plan.
-#[derive(Serialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(super) struct FilterExec {
-    table_number: usize,
+    input: Box<Plan>,
     where_clause: Expr,
-    results: Vec<Expr>,
+    results: Vec<AliasedExpr>,
+}
+
+/// Represents a table execution plan.
+///
+/// The columns of the table are referenced by their number, since they are always committed to.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct TableExec {
+    table_number: usize,
+    column_numbers: Vec<usize>,
 }
 
-impl FilterExec {
-    /// Try to create a `FilterExec` from a `proof_plans::FilterExec`.
+impl TableExec {
+    /// Try to create a `TableExec` from a `proof_plans::TableExec`.
     fn try_from_proof_plan(
-        plan: &proof_plans::FilterExec,
+        plan: &proof_plans::TableExec,
         table_refs: &IndexSet<TableRef>,
-        column_refs: &IndexSet<ColumnRef>,
+        column_refs: &mut IndexSet<ColumnRef>,
     ) -> Result<Self, Error> {
         Ok(Self {
             table_number: table_refs
-                .get_index_of(&plan.table.table_ref)
+                .get_index_of(&plan.table_ref)
                 .ok_or(Error::TableNotFound)?,
-            results: plan
-                .aliased_results
+            column_numbers: plan
+                .schema
                 .iter()
-                .map(|result| Expr::try_from_proof_expr(&result.expr, column_refs))
-                .collect::<Result<_, _>>()?,
-            where_clause: Expr::try_from_proof_expr(&plan.where_clause, column_refs)?,
+                .map(|field| {
+                    column_refs
+                        .insert_full(ColumnRef::new(
+                            plan.table_ref.clone(),
+                            field.name(),
+                            field.data_type(),
+                        ))
+                        .0
+                })
+                .collect(),
         })
     }
+
+    /// Try to recreate the `proof_plans::TableExec` that this `TableExec` was created from.
+    fn try_into_proof_plan(
+        self,
+        table_refs: &IndexSet<TableRef>,
+        column_refs: &IndexSet<ColumnRef>,
+    ) -> Result<proof_plans::TableExec, Error> {
+        let table_ref = table_refs
+            .get_index(self.table_number)
+            .ok_or(Error::TableNotFound)?
+            .clone();
+        let schema = self
+            .column_numbers
+            .into_iter()
+            .map(|column_number| {
+                let column_ref = column_refs
+                    .get_index(column_number)
+                    .ok_or(Error::ColumnNotFound)?;
+                if column_ref.table_ref() != table_ref {
+                    return Err(Error::ColumnNotFound);
+                }
+                Ok(ColumnField::new(
+                    column_ref.column_id(),
+                    *column_ref.column_type(),
+                ))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(proof_plans::TableExec::new(table_ref, schema))
+    }
+}
+
+/// Represents a projection execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct ProjectionExec {
+    input: Box<Plan>,
+    results: Vec<AliasedExpr>,
+}
+
+/// Represents a group by execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct GroupByExec {
+    input: Box<Plan>,
+    where_clause: Expr,
+    group_by_exprs: Vec<AliasedExpr>,
+    sum_exprs: Vec<AliasedExpr>,
+    max_exprs: Vec<AliasedExpr>,
+    min_exprs: Vec<AliasedExpr>,
+    count_alias: String,
+}
+
+/// Represents a slice execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct SliceExec {
+    input: Box<Plan>,
+    skip: usize,
+    fetch: Option<usize>,
+}
+
+/// Represents a sort execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct SortExec {
+    input: Box<Plan>,
+    order_by: Vec<(usize, bool)>,
+}
+
+/// Represents a union execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct UnionExec {
+    inputs: Vec<Plan>,
+    schema: Vec<Field>,
+}
+
+/// Represents a sort merge join execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct SortMergeJoinExec {
+    left: Box<Plan>,
+    right: Box<Plan>,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+    result_idents: Vec<String>,
+    join_type: JoinType,
+}
+
+/// Represents a semi join execution plan.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(super) struct SemiJoinExec {
+    left: Box<Plan>,
+    right: Box<Plan>,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+    is_anti_join: bool,
 }
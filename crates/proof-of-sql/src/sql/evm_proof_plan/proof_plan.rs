@@ -1,7 +1,9 @@
 use super::{error::Error, plans::Plan};
 use crate::{
     base::{
-        database::{ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableRef},
+        database::{
+            ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation, TableRef,
+        },
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
@@ -19,11 +21,12 @@ use alloc::{
 };
 use bumpalo::Bump;
 use itertools::Itertools;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlparser::ast::Ident;
 
 #[derive(Debug)]
 /// An implementation of `ProofPlan` that allows for EVM compatible serialization.
-/// Serialization should be done using bincode with fixint, big-endian encoding in order to be compatible with EVM.
+/// Serialization and deserialization should be done using bincode with fixint, big-endian encoding in order to be compatible with EVM.
 ///
 /// This is simply a wrapper around a `DynProofPlan`.
 pub struct EVMProofPlan {
@@ -48,30 +51,38 @@ impl EVMProofPlan {
     }
 }
 
+/// The compact form of an [`EVMProofPlan`].
+///
+/// Tables and columns are referenced by their index into `tables` and `columns`.
+/// The tables and columns of [`ProofPlan::get_table_references`] and
+/// [`ProofPlan::get_column_references`] come first, in that order,
+/// followed by the other tables and columns that are referenced by expressions,
+/// e.g. the columns of a join.
+#[derive(Serialize, Deserialize)]
+struct CompactPlan {
+    tables: Vec<String>,
+    columns: Vec<(usize, String, ColumnType)>,
+    plan: Plan,
+}
+
 impl Serialize for EVMProofPlan {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        #[derive(Serialize)]
-        struct CompactPlan {
-            tables: Vec<String>,
-            columns: Vec<(usize, String)>,
-            plan: Plan,
-        }
-
-        let table_refs = self.get_table_references();
-        let column_refs = self.get_column_references();
+        let mut table_refs = self.get_table_references();
+        let mut column_refs = self.get_column_references();
 
-        let plan = Plan::try_from_proof_plan(self.inner(), &table_refs, &column_refs)
+        let plan = Plan::try_from_proof_plan(self.inner(), &table_refs, &mut column_refs)
             .map_err(serde::ser::Error::custom)?;
         let columns = column_refs
             .into_iter()
             .map(|column_ref| {
-                let table_index = table_refs
-                    .get_index_of(&column_ref.table_ref())
-                    .ok_or(Error::TableNotFound)?;
-                Ok((table_index, column_ref.column_id().to_string()))
+                let table_index = table_refs.insert_full(column_ref.table_ref()).0;
+                (
+                    table_index,
+                    column_ref.column_id().value,
+                    *column_ref.column_type(),
+                )
             })
-            .try_collect()
-            .map_err(serde::ser::Error::custom::<Error>)?;
+            .collect();
         let tables = table_refs.iter().map(ToString::to_string).collect();
 
         CompactPlan {
@@ -83,6 +94,40 @@ impl Serialize for EVMProofPlan {
     }
 }
 
+impl<'de> Deserialize<'de> for EVMProofPlan {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let CompactPlan {
+            tables,
+            columns,
+            plan,
+        } = CompactPlan::deserialize(deserializer)?;
+
+        let table_refs: IndexSet<TableRef> = tables
+            .iter()
+            .map(|table| TableRef::try_from(table.as_str()).map_err(|_| Error::InvalidTableName))
+            .try_collect()
+            .map_err(serde::de::Error::custom)?;
+        let column_refs: IndexSet<ColumnRef> = columns
+            .into_iter()
+            .map(|(table_index, column_name, column_type)| {
+                let table_ref = table_refs
+                    .get_index(table_index)
+                    .ok_or(Error::TableNotFound)?;
+                Ok(ColumnRef::new(
+                    table_ref.clone(),
+                    Ident::new(column_name),
+                    column_type,
+                ))
+            })
+            .try_collect()
+            .map_err(serde::de::Error::custom::<Error>)?;
+
+        plan.try_into_proof_plan(&table_refs, &column_refs)
+            .map(Self::new)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl ProofPlan for EVMProofPlan {
     fn verifier_evaluate<S: Scalar>(
         &self,
@@ -0,0 +1,1157 @@
+// SPDX-License-Identifier: UNLICENSED
+// This is licensed under the Cryptographic Open Software License 1.0
+// This file was generated by `generate-solidity-verifier`. Do not edit it by hand.
+pragma solidity ^0.8.28;
+
+{{CONSTANTS}}
+
+{{ERRORS}}
+
+/// @dev The keccak256 hash of the EVM encoding of the plan that this verifier is specialized to.
+uint256 constant PLAN_HASH = {{PLAN_HASH}};
+/// @dev The x-coordinate imaginary component of τ·H from the HyperKZG verifier key.
+uint256 constant VK_TAU_H_X_IMAG = {{VK_TAU_H_X_IMAG}};
+/// @dev The x-coordinate real component of τ·H from the HyperKZG verifier key.
+uint256 constant VK_TAU_H_X_REAL = {{VK_TAU_H_X_REAL}};
+/// @dev The y-coordinate imaginary component of τ·H from the HyperKZG verifier key.
+uint256 constant VK_TAU_H_Y_IMAG = {{VK_TAU_H_Y_IMAG}};
+/// @dev The y-coordinate real component of τ·H from the HyperKZG verifier key.
+uint256 constant VK_TAU_H_Y_REAL = {{VK_TAU_H_Y_REAL}};
+
+/// @dev The number of tables that the plan references, in the order of `get_table_references`.
+uint256 constant NUM_TABLES = {{NUM_TABLES}};
+/// @dev The number of columns that the plan references, in the order of `get_column_references`.
+uint256 constant NUM_COLUMNS = {{NUM_COLUMNS}};
+/// @dev The number of columns of the result.
+uint256 constant NUM_RESULT_COLUMNS = {{NUM_RESULT_COLUMNS}};
+/// @dev The number of words that `verify_plan` keeps its intermediate evaluations in.
+uint256 constant NUM_PLAN_VARIABLES = {{NUM_PLAN_VARIABLES}};
+/// @dev Whether the leading result columns must be strictly increasing, which is the case for a top level group by.
+uint256 constant CHECK_RESULT_ORDER = {{CHECK_RESULT_ORDER}};
+/// @dev The number of leading result columns that must be strictly increasing.
+uint256 constant NUM_ORDERED_RESULT_COLUMNS = {{NUM_ORDERED_RESULT_COLUMNS}};
+
+/// @dev Offset of the queue of bit distributions, as `[vary_mask, leading_bit_mask]` pairs, in the query builder.
+uint256 constant BUILDER_BIT_DISTRIBUTIONS_OFFSET = 0x20 * 11;
+/// @dev Offset of the chi evaluation of a single row in the query builder.
+uint256 constant BUILDER_SINGLETON_CHI_EVALUATION_OFFSET = 0x20 * 12;
+/// @dev Size of the query builder, which extends the verification builder.
+uint256 constant QUERY_BUILDER_SIZE = 0x20 * 13;
+
+/// @dev Offset of the transcript state in the verifier state.
+uint256 constant STATE_TRANSCRIPT_OFFSET = 0x20 * 0;
+/// @dev Offset of the calldata pointer to the encoding of the result in the verifier state.
+uint256 constant STATE_RESULT_PTR_OFFSET = 0x20 * 1;
+/// @dev Offset of the calldata pointer to the end of the encoding of the result in the verifier state.
+uint256 constant STATE_RESULT_END_OFFSET = 0x20 * 2;
+/// @dev Offset of the range length in the verifier state.
+uint256 constant STATE_RANGE_LENGTH_OFFSET = 0x20 * 3;
+/// @dev Offset of the number of sumcheck variables in the verifier state.
+uint256 constant STATE_NUM_VARS_OFFSET = 0x20 * 4;
+/// @dev Offset of the chi evaluation lengths, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_CHI_LENGTHS_OFFSET = 0x20 * 5;
+/// @dev Offset of the rho evaluation lengths, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_RHO_LENGTHS_OFFSET = 0x20 * 7;
+/// @dev Offset of the first round commitments, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_FIRST_ROUND_COMMITMENTS_OFFSET = 0x20 * 9;
+/// @dev Offset of the final round commitments, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_FINAL_ROUND_COMMITMENTS_OFFSET = 0x20 * 11;
+/// @dev Offset of the bit distributions, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_BIT_DISTRIBUTIONS_OFFSET = 0x20 * 13;
+/// @dev Offset of the memory pointer to the post result challenges in the verifier state.
+uint256 constant STATE_POST_RESULT_CHALLENGES_OFFSET = 0x20 * 15;
+/// @dev Offset of the memory pointer to the entrywise point in the verifier state.
+uint256 constant STATE_ENTRYWISE_POINT_OFFSET = 0x20 * 16;
+/// @dev Offset of the memory pointer to the constraint multipliers in the verifier state.
+uint256 constant STATE_CONSTRAINT_MULTIPLIERS_OFFSET = 0x20 * 17;
+/// @dev Offset of the memory pointer to the sumcheck evaluation point in the verifier state.
+uint256 constant STATE_EVALUATION_POINT_OFFSET = 0x20 * 18;
+/// @dev Offset of the expected evaluation of the sumcheck proof in the verifier state.
+uint256 constant STATE_EXPECTED_EVALUATION_OFFSET = 0x20 * 19;
+/// @dev Offset of the degree of the sumcheck proof in the verifier state.
+uint256 constant STATE_DEGREE_OFFSET = 0x20 * 20;
+/// @dev Offset of the first round evaluations, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_FIRST_ROUND_EVALUATIONS_OFFSET = 0x20 * 21;
+/// @dev Offset of the column evaluations, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_COLUMN_EVALUATIONS_OFFSET = 0x20 * 23;
+/// @dev Offset of the final round evaluations, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_FINAL_ROUND_EVALUATIONS_OFFSET = 0x20 * 25;
+/// @dev Offset of the memory pointer to the evaluation challenges in the verifier state.
+uint256 constant STATE_EVALUATION_CHALLENGES_OFFSET = 0x20 * 27;
+/// @dev Offset of the `com` points of the HyperKZG proof, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_COM_OFFSET = 0x20 * 28;
+/// @dev Offset of the `v` evaluations of the HyperKZG proof, as a count and a calldata pointer, in the verifier state.
+uint256 constant STATE_V_OFFSET = 0x20 * 30;
+/// @dev Offset of the calldata pointer to the `w` points of the HyperKZG proof in the verifier state.
+uint256 constant STATE_W_OFFSET = 0x20 * 32;
+/// @dev Offset of the HyperKZG transcript state in the verifier state.
+uint256 constant STATE_HYPERKZG_TRANSCRIPT_OFFSET = 0x20 * 33;
+/// @dev Offset of the HyperKZG challenges `r`, `q` and `d`, and of the bivariate evaluation `b`, in the verifier state.
+uint256 constant STATE_HYPERKZG_CHALLENGES_OFFSET = 0x20 * 34;
+/// @dev Size of the verifier state.
+uint256 constant STATE_SIZE = 0x20 * 38;
+
+/// @dev Offset of the values of a result column, which follow the number of rows and whether it is a scalar column.
+uint256 constant RESULT_COLUMN_VALUES_OFFSET = 0x20 * 2;
+/// @dev Result value kind of a boolean, which is a single byte that is 0 or 1.
+uint256 constant RESULT_KIND_BOOLEAN = 0x00;
+/// @dev Result value kind of an unsigned byte.
+uint256 constant RESULT_KIND_UINT8 = 0x01;
+/// @dev Result value kind of a byte array, which is a `u64` length followed by the bytes.
+uint256 constant RESULT_KIND_VARBINARY = 0x02;
+/// @dev Result value kind of a canonical field element.
+uint256 constant RESULT_KIND_WORD = 0x03;
+/// @dev Result value kind of a canonical field element that must fit into an unsigned byte.
+uint256 constant RESULT_KIND_WORD_UINT8 = 0x04;
+/// @dev Result value kind of a big-endian signed integer, with the width in bytes in the low bits.
+uint256 constant RESULT_KIND_INT = 0x20;
+/// @dev Result value kind of a canonical field element that must fit into a signed integer,
+/// with the width in bytes in the low bits.
+uint256 constant RESULT_KIND_WORD_INT = 0x40;
+
+/// @dev Error code for when the plan is not the one that the verifier is specialized to.
+uint32 constant ERR_UNEXPECTED_PLAN = 0x4aab20ab;
+/// @dev Error code for when the table lengths or the commitments do not match the plan.
+uint32 constant ERR_INVALID_VERIFIER_INPUT = 0x360b3068;
+/// @dev Error code for when the proof is not a valid encoding.
+uint32 constant ERR_INVALID_CALLDATA = 0x8129bbcd;
+/// @dev Error code for when a bit distribution is invalid or outside of the acceptable range.
+uint32 constant ERR_INVALID_BIT_DISTRIBUTION = 0x41272869;
+/// @dev Error code for when the size of the sumcheck proof does not match the number of variables.
+uint32 constant ERR_INVALID_SUMCHECK_PROOF_SIZE = 0x3f889a17;
+/// @dev Error code for when the bits of a column do not decompose it.
+uint32 constant ERR_INVALID_BIT_DECOMPOSITION = 0x10a1442b;
+/// @dev Error code for when a quotient or a remainder is not the one of an integer division.
+uint32 constant ERR_DIVISION_CHECK_FAILED = 0xd6feb2b3;
+/// @dev Error code for when a value is out of the range of its type.
+uint32 constant ERR_OUT_OF_RANGE = 0x7db3aba7;
+/// @dev Error code for when a column is not monotonic.
+uint32 constant ERR_MONOTONICITY_CHECK_FAILED = 0xeca8c4c9;
+/// @dev Error code for when an extremum does not bound its group.
+uint32 constant ERR_EXTREMUM_CHECK_FAILED = 0x4eaa2f8b;
+/// @dev Error code for when the rows of a distinct are not distinct.
+uint32 constant ERR_DISTINCT_CHECK_FAILED = 0x9789a6a5;
+/// @dev Error code for when the result is not a valid encoding of a table with the fields of the plan.
+uint32 constant ERR_INVALID_RESULT = 0x7c599f80;
+/// @dev Error code for when the rows of the result are not ordered as the plan requires.
+uint32 constant ERR_RESULT_NOT_ORDERED = 0x661d67b2;
+/// @dev Error code for when the evaluations of the result do not match the evaluations of the plan.
+uint32 constant ERR_RESULT_EVALUATION_MISMATCH = 0x9d1a8a5f;
+/// @dev Error code for when the proof contains more than the plan consumes.
+uint32 constant ERR_INCOMPLETE_VERIFICATION = 0xd37d00d0;
+/// @dev Error code for when the sumcheck evaluation does not match the evaluation of the constraints.
+uint32 constant ERR_SUMCHECK_EVALUATION_MISMATCH = 0x7ef92c61;
+/// @dev Error code for when the numbers of commitments and evaluations of a round differ.
+uint32 constant ERR_PCS_BATCH_SIZE_MISMATCH = 0x4cf624ca;
+/// @dev Error code for when the HyperKZG proof does not match the number of variables.
+uint32 constant ERR_HYPER_KZG_PROOF_SIZE_MISMATCH = 0xbe285ccd;
+/// @dev Error code for when the HyperKZG opening is degenerate.
+uint32 constant ERR_HYPER_KZG_DEGENERATE = 0x06900765;
+/// @dev Error code for when the HyperKZG pairing check fails.
+uint32 constant ERR_HYPER_KZG_PAIRING_CHECK_FAILED = 0xa41148a3;
+
+/// @title QueryVerifier
+/// @dev Library verifying proofs of a single plan with a single HyperKZG setup.
+/// It is generated from the Rust implementation of the plan, so it should be regenerated rather than edited.
+library QueryVerifier {
+    /// @notice Error thrown when the plan is not the one that the verifier is specialized to.
+    error UnexpectedPlan();
+    /// @notice Error thrown when the table lengths or the commitments do not match the plan.
+    error InvalidVerifierInput();
+    /// @notice Error thrown when the proof is not a valid encoding.
+    error InvalidCalldata();
+    /// @notice Error thrown when a bit distribution is invalid or outside of the acceptable range.
+    error InvalidBitDistribution();
+    /// @notice Error thrown when the size of the sumcheck proof does not match the number of variables.
+    error InvalidSumcheckProofSize();
+    /// @notice Error thrown when the bits of a column do not decompose it.
+    error InvalidBitDecomposition();
+    /// @notice Error thrown when a quotient or a remainder is not the one of an integer division.
+    error DivisionCheckFailed();
+    /// @notice Error thrown when a value is out of the range of its type.
+    error OutOfRange();
+    /// @notice Error thrown when a column is not monotonic.
+    error MonotonicityCheckFailed();
+    /// @notice Error thrown when an extremum does not bound its group.
+    error ExtremumCheckFailed();
+    /// @notice Error thrown when the rows of a distinct are not distinct.
+    error DistinctCheckFailed();
+    /// @notice Error thrown when the result is not a valid encoding of a table with the fields of the plan.
+    error InvalidResult();
+    /// @notice Error thrown when the rows of the result are not ordered as the plan requires.
+    error ResultNotOrdered();
+    /// @notice Error thrown when the evaluations of the result do not match the evaluations of the plan.
+    error ResultEvaluationMismatch();
+    /// @notice Error thrown when the proof contains more than the plan consumes.
+    error IncompleteVerification();
+    /// @notice Error thrown when the sumcheck evaluation does not match the evaluation of the constraints.
+    error SumcheckEvaluationMismatch();
+    /// @notice Error thrown when the numbers of commitments and evaluations of a round differ.
+    error PCSBatchSizeMismatch();
+    /// @notice Error thrown when the HyperKZG proof does not match the number of variables.
+    error HyperKZGProofSizeMismatch();
+    /// @notice Error thrown when the HyperKZG opening is degenerate.
+    error HyperKZGDegenerate();
+    /// @notice Error thrown when the HyperKZG pairing check fails.
+    error HyperKZGPairingCheckFailed();
+
+    /// @notice Verifies a proof of the plan that this verifier is specialized to
+    /// @custom:as-yul-wrapper
+    /// #### Wrapped Yul Function
+    /// ##### Signature
+    /// ```yul
+    /// verify_query(proof_ptr, proof_end, table_lengths_ptr, commitments_ptr) -> verification_hash
+    /// ```
+    /// ##### Parameters
+    /// * `proof_ptr` - calldata pointer to the proof, as encoded by `encode_evm_calldata`
+    /// * `proof_end` - calldata pointer to the end of the proof
+    /// * `table_lengths_ptr` - memory pointer to the array of the lengths of the tables of the plan
+    /// * `commitments_ptr` - memory pointer to the array of the `(x, y)` commitments of the columns of the plan
+    /// ##### Return Values
+    /// * `verification_hash` - the final transcript state, which `verify_evm_calldata` returns as the verification hash
+    /// @dev This checks the proof the same way `verify_evm_calldata` does.
+    /// The proof starts with the plan and the result, so the verification hash commits to both.
+    /// The tables and the columns are in the order of `get_table_references` and `get_column_references`,
+    /// and each commitment takes two words.
+    /// @param __proof The proof, as encoded by `encode_evm_calldata`
+    /// @param __tableLengths The lengths of the tables of the plan
+    /// @param __commitments The commitments of the columns of the plan
+    /// @return __verificationHash The verification hash of the proof
+    function verify(bytes calldata __proof, uint256[] memory __tableLengths, uint256[] memory __commitments)
+        internal
+        view
+        returns (uint256 __verificationHash)
+    {
+        assembly {
+{{IMPORTS}}
+            function read_uint64(ptr, end) -> value, next_ptr {
+                next_ptr := add(ptr, UINT64_SIZE)
+                if gt(next_ptr, end) { err(ERR_INVALID_CALLDATA) }
+                value := shr(UINT64_PADDING_BITS, calldataload(ptr))
+            }
+            function skip_calldata(ptr, size, end) -> next_ptr {
+                next_ptr := add(ptr, size)
+                if gt(next_ptr, end) { err(ERR_INVALID_CALLDATA) }
+            }
+            // Reads a `u64` count followed by that many elements, and stores the count and a pointer to the elements.
+            function read_array(slot_ptr, ptr, element_size, end) -> next_ptr {
+                let count
+                count, ptr := read_uint64(ptr, end)
+                mstore(slot_ptr, count)
+                mstore(add(slot_ptr, WORD_SIZE), ptr)
+                next_ptr := skip_calldata(ptr, mul(count, element_size), end)
+            }
+            // Reads a `[u64; 4]` with the least significant limb first.
+            function read_limbs(ptr) -> value {
+                let word := calldataload(ptr)
+                value :=
+                    or(
+                        or(shr(192, word), shl(192, word)),
+                        or(
+                            and(shr(64, word), shl(64, 0xffffffffffffffff)),
+                            and(shl(64, word), shl(128, 0xffffffffffffffff))
+                        )
+                    )
+            }
+            function reverse_bytes(value) -> result {
+                result :=
+                    or(
+                        shr(8, and(value, 0xff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00)),
+                        shl(8, and(value, 0x00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff00ff))
+                    )
+                result :=
+                    or(
+                        shr(16, and(result, 0xffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000)),
+                        shl(16, and(result, 0x0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff0000ffff))
+                    )
+                result :=
+                    or(
+                        shr(32, and(result, 0xffffffff00000000ffffffff00000000ffffffff00000000ffffffff00000000)),
+                        shl(32, and(result, 0x00000000ffffffff00000000ffffffff00000000ffffffff00000000ffffffff))
+                    )
+                result :=
+                    or(
+                        shr(64, and(result, 0xffffffffffffffff0000000000000000ffffffffffffffff0000000000000000)),
+                        shl(64, and(result, 0x0000000000000000ffffffffffffffff0000000000000000ffffffffffffffff))
+                    )
+                result := or(shr(128, result), shl(128, result))
+            }
+            function draw_prefixed_challenges(transcript_ptr, count) -> result_ptr {
+                result_ptr := mload(FREE_PTR)
+                mstore(result_ptr, count)
+                mstore(FREE_PTR, add(result_ptr, WORD_SIZE))
+                pop(draw_challenges(transcript_ptr, count))
+            }
+            function read_plan_and_result(state_ptr, ptr, end) -> next_ptr {
+                let plan_length
+                plan_length, ptr := read_uint64(ptr, end)
+                let plan_ptr := ptr
+                ptr := skip_calldata(ptr, plan_length, end)
+                let result_length
+                result_length, ptr := read_uint64(ptr, end)
+                next_ptr := skip_calldata(ptr, result_length, end)
+                mstore(add(state_ptr, STATE_RESULT_PTR_OFFSET), ptr)
+                mstore(add(state_ptr, STATE_RESULT_END_OFFSET), next_ptr)
+
+                let free_ptr := mload(FREE_PTR)
+                calldatacopy(free_ptr, plan_ptr, plan_length)
+                if sub(keccak256(free_ptr, plan_length), PLAN_HASH) { err(ERR_UNEXPECTED_PLAN) }
+                // The transcript starts with the plan, the result and the `u64` offset of the tables, which is 0.
+                calldatacopy(add(free_ptr, plan_length), ptr, result_length)
+                mstore(add(free_ptr, add(plan_length, result_length)), 0)
+                mstore(
+                    add(state_ptr, STATE_TRANSCRIPT_OFFSET),
+                    keccak256(free_ptr, add(add(plan_length, result_length), UINT64_SIZE))
+                )
+            }
+            function read_first_round_message(state_ptr, ptr, end) -> next_ptr {
+                let start := ptr
+                let range_length
+                range_length, ptr := read_uint64(ptr, end)
+                let post_result_challenge_count
+                post_result_challenge_count, ptr := read_uint64(ptr, end)
+                ptr := read_array(add(state_ptr, STATE_CHI_LENGTHS_OFFSET), ptr, UINT64_SIZE, end)
+                ptr := read_array(add(state_ptr, STATE_RHO_LENGTHS_OFFSET), ptr, UINT64_SIZE, end)
+                next_ptr := read_array(add(state_ptr, STATE_FIRST_ROUND_COMMITMENTS_OFFSET), ptr, WORDX2_SIZE, end)
+
+                let transcript_ptr := add(state_ptr, STATE_TRANSCRIPT_OFFSET)
+                append_calldata(transcript_ptr, start, sub(next_ptr, start))
+                mstore(add(state_ptr, STATE_RANGE_LENGTH_OFFSET), range_length)
+                mstore(add(state_ptr, STATE_NUM_VARS_OFFSET), log2_up(range_length))
+                mstore(
+                    add(state_ptr, STATE_POST_RESULT_CHALLENGES_OFFSET),
+                    draw_prefixed_challenges(transcript_ptr, post_result_challenge_count)
+                )
+            }
+            function read_final_round_message(state_ptr, ptr, end) -> next_ptr {
+                let start := ptr
+                let constraint_count
+                constraint_count, ptr := read_uint64(ptr, end)
+                ptr := read_array(add(state_ptr, STATE_FINAL_ROUND_COMMITMENTS_OFFSET), ptr, WORDX2_SIZE, end)
+                next_ptr := read_array(add(state_ptr, STATE_BIT_DISTRIBUTIONS_OFFSET), ptr, WORDX2_SIZE, end)
+
+                let transcript_ptr := add(state_ptr, STATE_TRANSCRIPT_OFFSET)
+                append_calldata(transcript_ptr, start, sub(next_ptr, start))
+                mstore(
+                    add(state_ptr, STATE_ENTRYWISE_POINT_OFFSET),
+                    draw_challenges(transcript_ptr, mload(add(state_ptr, STATE_NUM_VARS_OFFSET)))
+                )
+                mstore(
+                    add(state_ptr, STATE_CONSTRAINT_MULTIPLIERS_OFFSET),
+                    draw_prefixed_challenges(transcript_ptr, constraint_count)
+                )
+            }
+            function read_sumcheck_proof(state_ptr, ptr, end) -> next_ptr {
+                let count
+                count, ptr := read_uint64(ptr, end)
+                next_ptr := skip_calldata(ptr, mul(count, WORD_SIZE), end)
+                let num_vars := mload(add(state_ptr, STATE_NUM_VARS_OFFSET))
+                if or(iszero(count), mod(count, num_vars)) { err(ERR_INVALID_SUMCHECK_PROOF_SIZE) }
+                let degree := sub(div(count, num_vars), 1)
+
+                // The evaluation point is allocated right after this word, so that it is prefixed with its length.
+                let point_ptr := mload(FREE_PTR)
+                mstore(point_ptr, num_vars)
+                mstore(FREE_PTR, add(point_ptr, WORD_SIZE))
+                let evaluation_point_ptr, expected_evaluation :=
+                    verify_sumcheck_proof(add(state_ptr, STATE_TRANSCRIPT_OFFSET), ptr, num_vars, degree)
+                mstore(add(state_ptr, STATE_EVALUATION_POINT_OFFSET), point_ptr)
+                mstore(add(state_ptr, STATE_EXPECTED_EVALUATION_OFFSET), expected_evaluation)
+                mstore(add(state_ptr, STATE_DEGREE_OFFSET), degree)
+            }
+            function read_pcs_evaluations(state_ptr, ptr, end) -> next_ptr {
+                let start := ptr
+                ptr := read_array(add(state_ptr, STATE_FIRST_ROUND_EVALUATIONS_OFFSET), ptr, WORD_SIZE, end)
+                ptr := read_array(add(state_ptr, STATE_COLUMN_EVALUATIONS_OFFSET), ptr, WORD_SIZE, end)
+                next_ptr := read_array(add(state_ptr, STATE_FINAL_ROUND_EVALUATIONS_OFFSET), ptr, WORD_SIZE, end)
+
+                let transcript_ptr := add(state_ptr, STATE_TRANSCRIPT_OFFSET)
+                append_calldata(transcript_ptr, start, sub(next_ptr, start))
+                let count :=
+                    add(
+                        add(
+                            mload(add(state_ptr, STATE_FIRST_ROUND_EVALUATIONS_OFFSET)),
+                            mload(add(state_ptr, STATE_COLUMN_EVALUATIONS_OFFSET))
+                        ),
+                        mload(add(state_ptr, STATE_FINAL_ROUND_EVALUATIONS_OFFSET))
+                    )
+                mstore(add(state_ptr, STATE_EVALUATION_CHALLENGES_OFFSET), draw_challenges(transcript_ptr, count))
+            }
+            function read_hyperkzg_proof(state_ptr, ptr, end) -> next_ptr {
+                ptr := read_array(add(state_ptr, STATE_COM_OFFSET), ptr, WORDX2_SIZE, end)
+                ptr := read_array(add(state_ptr, STATE_V_OFFSET), ptr, WORDX3_SIZE, end)
+                mstore(add(state_ptr, STATE_W_OFFSET), ptr)
+                next_ptr := skip_calldata(ptr, WORDX6_SIZE, end)
+            }
+            function read_proof(state_ptr, ptr, end) {
+                ptr := read_plan_and_result(state_ptr, ptr, end)
+                ptr := read_first_round_message(state_ptr, ptr, end)
+                ptr := read_final_round_message(state_ptr, ptr, end)
+                ptr := read_sumcheck_proof(state_ptr, ptr, end)
+                ptr := read_pcs_evaluations(state_ptr, ptr, end)
+                ptr := read_hyperkzg_proof(state_ptr, ptr, end)
+                if sub(ptr, end) { err(ERR_INVALID_CALLDATA) }
+            }
+
+            // Copies an array of words from calldata into a prefixed memory array, checking that each is canonical.
+            function copy_evaluations(slot_ptr) -> array_ptr {
+                let count := mload(slot_ptr)
+                let ptr := mload(add(slot_ptr, WORD_SIZE))
+                array_ptr := mload(FREE_PTR)
+                mstore(array_ptr, count)
+                let target := add(array_ptr, WORD_SIZE)
+                mstore(FREE_PTR, add(target, mul(count, WORD_SIZE)))
+                for {} count { count := sub(count, 1) } {
+                    let value := calldataload(ptr)
+                    if iszero(lt(value, MODULUS)) { err(ERR_INVALID_CALLDATA) }
+                    mstore(target, value)
+                    target := add(target, WORD_SIZE)
+                    ptr := add(ptr, WORD_SIZE)
+                }
+            }
+            function compute_rho_evaluation(length, point_ptr, num_vars) -> result {
+                if gt(length, shl(num_vars, 1)) { err(ERR_INVALID_CALLDATA) }
+                // `sum` and `result` are the sums of `chi_i` and of `i * chi_i` over the rows of the low bits of `length`.
+                // `full` is the sum of `i * chi_i` over all rows.
+                let sum := 0
+                let full := 0
+                for { let k := 0 } lt(k, num_vars) { k := add(k, 1) } {
+                    let x := mload(add(point_ptr, mul(k, WORD_SIZE)))
+                    let one_minus_x := addmod(1, sub(MODULUS, x), MODULUS)
+                    switch and(shr(k, length), 1)
+                    case 0 {
+                        sum := mulmod(sum, one_minus_x, MODULUS)
+                        result := mulmod(result, one_minus_x, MODULUS)
+                    }
+                    default {
+                        result :=
+                            addmod(
+                                mulmod(one_minus_x, full, MODULUS),
+                                mulmod(x, addmod(mulmod(shl(k, 1), sum, MODULUS), result, MODULUS), MODULUS),
+                                MODULUS
+                            )
+                        sum := addmod(one_minus_x, mulmod(x, sum, MODULUS), MODULUS)
+                    }
+                    full := addmod(full, mulmod(shl(k, 1), x, MODULUS), MODULUS)
+                }
+                if eq(length, shl(num_vars, 1)) { result := full }
+            }
+            // Computes the chi or the rho evaluations of the `u64` lengths of a calldata array.
+            function compute_length_evaluations(slot_ptr, point_ptr, num_vars, is_rho) -> array_ptr {
+                let count := mload(slot_ptr)
+                let ptr := mload(add(slot_ptr, WORD_SIZE))
+                array_ptr := mload(FREE_PTR)
+                mstore(array_ptr, count)
+                let target := add(array_ptr, WORD_SIZE)
+                mstore(FREE_PTR, add(target, mul(count, WORD_SIZE)))
+                for {} count { count := sub(count, 1) } {
+                    let length := shr(UINT64_PADDING_BITS, calldataload(ptr))
+                    switch is_rho
+                    case 0 { mstore(target, compute_truncated_lagrange_basis_sum(length, point_ptr, num_vars)) }
+                    default { mstore(target, compute_rho_evaluation(length, point_ptr, num_vars)) }
+                    target := add(target, WORD_SIZE)
+                    ptr := add(ptr, UINT64_SIZE)
+                }
+            }
+            function compute_table_chi_evaluations(table_lengths_ptr, point_ptr, num_vars) -> array_ptr {
+                array_ptr := mload(FREE_PTR)
+                mstore(array_ptr, NUM_TABLES)
+                mstore(FREE_PTR, add(array_ptr, mul(add(NUM_TABLES, 1), WORD_SIZE)))
+                for { let i := 0 } lt(i, NUM_TABLES) { i := add(i, 1) } {
+                    let offset := mul(add(i, 1), WORD_SIZE)
+                    mstore(
+                        add(array_ptr, offset),
+                        compute_truncated_lagrange_basis_sum(mload(add(table_lengths_ptr, offset)), point_ptr, num_vars)
+                    )
+                }
+            }
+            // Reads the bit distributions into a queue of `[vary_mask, leading_bit_mask]` pairs, checking each of them.
+            function read_bit_distributions(slot_ptr) -> queue_ptr {
+                let count := mload(slot_ptr)
+                let ptr := mload(add(slot_ptr, WORD_SIZE))
+                queue_ptr := mload(FREE_PTR)
+                mstore(queue_ptr, mul(count, 2))
+                let target := add(queue_ptr, WORD_SIZE)
+                mstore(FREE_PTR, add(target, mul(count, WORDX2_SIZE)))
+                for {} count { count := sub(count, 1) } {
+                    let vary_mask := read_limbs(ptr)
+                    let leading_bit_mask := read_limbs(add(ptr, WORD_SIZE))
+                    let lead_mask := or(leading_bit_mask, shl(255, 1))
+                    if and(and(vary_mask, lead_mask), shr(1, not(0))) { err(ERR_INVALID_BIT_DISTRIBUTION) }
+                    let inverse_mask := and(xor(not(vary_mask), lead_mask), shr(1, not(0)))
+                    if sub(shr(128, inverse_mask), shr(129, not(0))) { err(ERR_INVALID_BIT_DISTRIBUTION) }
+                    mstore(target, vary_mask)
+                    mstore(add(target, WORD_SIZE), leading_bit_mask)
+                    target := add(target, WORDX2_SIZE)
+                    ptr := add(ptr, WORDX2_SIZE)
+                }
+            }
+            function setup_builder(state_ptr, table_lengths_ptr) -> builder_ptr {
+                builder_ptr := mload(FREE_PTR)
+                mstore(FREE_PTR, add(builder_ptr, QUERY_BUILDER_SIZE))
+                let num_vars := mload(add(state_ptr, STATE_NUM_VARS_OFFSET))
+                let point_ptr := add(mload(add(state_ptr, STATE_EVALUATION_POINT_OFFSET)), WORD_SIZE)
+
+                if sub(mload(add(state_ptr, STATE_COLUMN_EVALUATIONS_OFFSET)), NUM_COLUMNS) {
+                    err(ERR_INVALID_CALLDATA)
+                }
+                mstore(
+                    add(builder_ptr, BUILDER_CHALLENGES_OFFSET),
+                    mload(add(state_ptr, STATE_POST_RESULT_CHALLENGES_OFFSET))
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_FIRST_ROUND_MLES_OFFSET),
+                    copy_evaluations(add(state_ptr, STATE_FIRST_ROUND_EVALUATIONS_OFFSET))
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_FINAL_ROUND_MLES_OFFSET),
+                    copy_evaluations(add(state_ptr, STATE_FINAL_ROUND_EVALUATIONS_OFFSET))
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_COLUMN_EVALUATIONS_OFFSET),
+                    copy_evaluations(add(state_ptr, STATE_COLUMN_EVALUATIONS_OFFSET))
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_CHI_EVALUATIONS_OFFSET),
+                    compute_length_evaluations(add(state_ptr, STATE_CHI_LENGTHS_OFFSET), point_ptr, num_vars, 0)
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_RHO_EVALUATIONS_OFFSET),
+                    compute_length_evaluations(add(state_ptr, STATE_RHO_LENGTHS_OFFSET), point_ptr, num_vars, 1)
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_CONSTRAINT_MULTIPLIERS_OFFSET),
+                    mload(add(state_ptr, STATE_CONSTRAINT_MULTIPLIERS_OFFSET))
+                )
+                mstore(add(builder_ptr, BUILDER_MAX_DEGREE_OFFSET), mload(add(state_ptr, STATE_DEGREE_OFFSET)))
+                mstore(add(builder_ptr, BUILDER_AGGREGATE_EVALUATION_OFFSET), 0)
+                mstore(
+                    add(builder_ptr, BUILDER_ROW_MULTIPLIERS_EVALUATION_OFFSET),
+                    compute_truncated_lagrange_basis_inner_product(
+                        mload(add(state_ptr, STATE_RANGE_LENGTH_OFFSET)),
+                        point_ptr,
+                        mload(add(state_ptr, STATE_ENTRYWISE_POINT_OFFSET)),
+                        num_vars
+                    )
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_TABLE_CHI_EVALUATIONS_OFFSET),
+                    compute_table_chi_evaluations(table_lengths_ptr, point_ptr, num_vars)
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_BIT_DISTRIBUTIONS_OFFSET),
+                    read_bit_distributions(add(state_ptr, STATE_BIT_DISTRIBUTIONS_OFFSET))
+                )
+                mstore(
+                    add(builder_ptr, BUILDER_SINGLETON_CHI_EVALUATION_OFFSET),
+                    compute_truncated_lagrange_basis_sum(1, point_ptr, num_vars)
+                )
+            }
+            function check_builder_completed(builder_ptr) {
+                if or(
+                    or(
+                        mload(mload(add(builder_ptr, BUILDER_CHALLENGES_OFFSET))),
+                        mload(mload(add(builder_ptr, BUILDER_CONSTRAINT_MULTIPLIERS_OFFSET)))
+                    ),
+                    or(
+                        or(
+                            mload(mload(add(builder_ptr, BUILDER_FIRST_ROUND_MLES_OFFSET))),
+                            mload(mload(add(builder_ptr, BUILDER_FINAL_ROUND_MLES_OFFSET)))
+                        ),
+                        mload(mload(add(builder_ptr, BUILDER_BIT_DISTRIBUTIONS_OFFSET)))
+                    )
+                ) { err(ERR_INCOMPLETE_VERIFICATION) }
+            }
+
+            // `fold` is the folded column multiplied by the challenge `alpha`.
+            function star_identity(fold, star, chi_eval) -> eval {
+                eval := addmod(mulmod(addmod(1, fold, MODULUS), star, MODULUS), sub(MODULUS, chi_eval), MODULUS)
+            }
+            function verify_product(builder_ptr, lhs_eval, rhs_eval) -> product_eval {
+                product_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    addmod(product_eval, sub(MODULUS, mulmod(lhs_eval, rhs_eval, MODULUS)), MODULUS),
+                    2
+                )
+            }
+            function verify_equals_zero(builder_ptr, eval, chi_eval) -> selection_eval {
+                let pseudo_inverse_eval := builder_consume_final_round_mle(builder_ptr)
+                selection_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(builder_ptr, mulmod(selection_eval, eval, MODULUS), 2)
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    addmod(
+                        addmod(chi_eval, sub(MODULUS, selection_eval), MODULUS),
+                        sub(MODULUS, mulmod(eval, pseudo_inverse_eval, MODULUS)),
+                        MODULUS
+                    ),
+                    2
+                )
+            }
+            function consume_sign_bits(builder_ptr, vary_mask) -> rhs, last_bit_eval {
+                for { let bit := 0 } vary_mask { bit := add(bit, 1) } {
+                    if and(vary_mask, 1) {
+                        last_bit_eval := builder_consume_final_round_mle(builder_ptr)
+                        builder_produce_identity_constraint(
+                            builder_ptr,
+                            addmod(last_bit_eval, sub(MODULUS, mulmod(last_bit_eval, last_bit_eval, MODULUS)), MODULUS),
+                            2
+                        )
+                        if lt(bit, 255) { rhs := addmod(rhs, mulmod(shl(bit, 1), last_bit_eval, MODULUS), MODULUS) }
+                    }
+                    vary_mask := shr(1, vary_mask)
+                }
+            }
+            function compute_constant_bits_evaluation(vary_mask, leading_bit_mask, lead_eval, chi_eval) -> eval {
+                let lead_mask := or(leading_bit_mask, shl(255, 1))
+                let inverse_mask := and(xor(not(vary_mask), lead_mask), shr(1, not(0)))
+                // The bits from 252 to 254 must match the inverse of the lead bit, so that the value fits into 253 bits.
+                if sub(and(inverse_mask, shl(252, 7)), shl(252, 7)) { err(ERR_INVALID_BIT_DECOMPOSITION) }
+                eval :=
+                    addmod(
+                        addmod(
+                            mulmod(lead_eval, mod(lead_mask, MODULUS), MODULUS),
+                            mulmod(
+                                addmod(chi_eval, sub(MODULUS, lead_eval), MODULUS), mod(inverse_mask, MODULUS), MODULUS
+                            ),
+                            MODULUS
+                        ),
+                        mulmod(sub(MODULUS, chi_eval), mod(shl(255, 1), MODULUS), MODULUS),
+                        MODULUS
+                    )
+            }
+            // Returns the evaluation of the column that is 1 where `eval` is negative and 0 elsewhere.
+            function verify_sign(builder_ptr, eval, chi_eval) -> sign_eval {
+                let vary_mask := dequeue(add(builder_ptr, BUILDER_BIT_DISTRIBUTIONS_OFFSET))
+                let leading_bit_mask := dequeue(add(builder_ptr, BUILDER_BIT_DISTRIBUTIONS_OFFSET))
+                let rhs, lead_eval := consume_sign_bits(builder_ptr, vary_mask)
+                if iszero(shr(255, vary_mask)) { lead_eval := mul(chi_eval, shr(255, leading_bit_mask)) }
+                rhs := addmod(rhs, compute_constant_bits_evaluation(vary_mask, leading_bit_mask, lead_eval, chi_eval), MODULUS)
+                if sub(rhs, eval) { err(ERR_INVALID_BIT_DECOMPOSITION) }
+                sign_eval := addmod(chi_eval, sub(MODULUS, lead_eval), MODULUS)
+            }
+            function verify_divide_and_modulo(builder_ptr, lhs_eval, rhs_eval, chi_eval) ->
+                quotient_eval,
+                remainder_eval
+            {
+                quotient_eval := builder_consume_final_round_mle(builder_ptr)
+                remainder_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    addmod(
+                        addmod(mulmod(quotient_eval, rhs_eval, MODULUS), remainder_eval, MODULUS),
+                        sub(MODULUS, lhs_eval),
+                        MODULUS
+                    ),
+                    2
+                )
+                let rhs_is_zero_eval := verify_equals_zero(builder_ptr, rhs_eval, chi_eval)
+                builder_produce_identity_constraint(builder_ptr, mulmod(rhs_is_zero_eval, quotient_eval, MODULUS), 2)
+                let abs_remainder_eval := verify_abs(builder_ptr, lhs_eval, remainder_eval, chi_eval)
+                let abs_rhs_eval := verify_abs(builder_ptr, rhs_eval, rhs_eval, chi_eval)
+                verify_remainder_bound(builder_ptr, abs_remainder_eval, abs_rhs_eval, rhs_is_zero_eval, chi_eval)
+                let abs_quotient_eval := verify_abs(builder_ptr, quotient_eval, quotient_eval, chi_eval)
+                verify_operand_bound(builder_ptr, abs_quotient_eval, chi_eval)
+                verify_operand_bound(builder_ptr, abs_rhs_eval, chi_eval)
+            }
+            // Returns the evaluation of `|eval|`, where the sign is that of `sign_source_eval`.
+            function verify_abs(builder_ptr, sign_source_eval, eval, chi_eval) -> abs_eval {
+                let sign_eval := verify_sign(builder_ptr, sign_source_eval, chi_eval)
+                abs_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    addmod(
+                        addmod(abs_eval, sub(MODULUS, eval), MODULUS),
+                        mulmod(2, mulmod(sign_eval, eval, MODULUS), MODULUS),
+                        MODULUS
+                    ),
+                    2
+                )
+            }
+            function verify_remainder_bound(builder_ptr, abs_remainder_eval, abs_rhs_eval, rhs_is_zero_eval, chi_eval) {
+                let zero_remainder_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    addmod(
+                        zero_remainder_eval, sub(MODULUS, mulmod(rhs_is_zero_eval, abs_remainder_eval, MODULUS)), MODULUS
+                    ),
+                    2
+                )
+                if verify_sign(builder_ptr, abs_remainder_eval, chi_eval) { err(ERR_DIVISION_CHECK_FAILED) }
+                let slack_eval :=
+                    addmod(
+                        addmod(abs_rhs_eval, sub(MODULUS, abs_remainder_eval), MODULUS),
+                        addmod(sub(MODULUS, chi_eval), addmod(zero_remainder_eval, rhs_is_zero_eval, MODULUS), MODULUS),
+                        MODULUS
+                    )
+                if verify_sign(builder_ptr, slack_eval, chi_eval) { err(ERR_DIVISION_CHECK_FAILED) }
+            }
+            // Checks that `abs_eval` is at most `2^125 - 1`, so that the division check can not wrap around the modulus.
+            function verify_operand_bound(builder_ptr, abs_eval, chi_eval) {
+                let slack_eval :=
+                    addmod(mulmod(0x1fffffffffffffffffffffffffffffff, chi_eval, MODULUS), sub(MODULUS, abs_eval), MODULUS)
+                if verify_sign(builder_ptr, slack_eval, chi_eval) { err(ERR_DIVISION_CHECK_FAILED) }
+            }
+            function verify_filter(builder_ptr, c_fold, d_fold, chi_n_eval, chi_m_eval, selection_eval) {
+                let c_star_eval := builder_consume_final_round_mle(builder_ptr)
+                let d_star_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_zerosum_constraint(
+                    builder_ptr,
+                    addmod(mulmod(c_star_eval, selection_eval, MODULUS), sub(MODULUS, d_star_eval), MODULUS),
+                    2
+                )
+                builder_produce_identity_constraint(builder_ptr, star_identity(c_fold, c_star_eval, chi_n_eval), 2)
+                builder_produce_identity_constraint(builder_ptr, star_identity(d_fold, d_star_eval, chi_m_eval), 2)
+            }
+            function verify_membership_check(builder_ptr, c_fold, d_fold, chi_n_eval, chi_m_eval) -> multiplicity_eval {
+                multiplicity_eval := builder_consume_first_round_mle(builder_ptr)
+                verify_filter(builder_ptr, c_fold, d_fold, chi_n_eval, chi_m_eval, multiplicity_eval)
+            }
+            function verify_permutation_check(builder_ptr, c_fold, d_fold, chi_eval) {
+                let c_star_eval := builder_consume_final_round_mle(builder_ptr)
+                let d_star_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_zerosum_constraint(
+                    builder_ptr, addmod(c_star_eval, sub(MODULUS, d_star_eval), MODULUS), 1
+                )
+                builder_produce_identity_constraint(builder_ptr, star_identity(c_fold, c_star_eval, chi_eval), 2)
+                builder_produce_identity_constraint(builder_ptr, star_identity(d_fold, d_star_eval, chi_eval), 2)
+            }
+            function verify_shift(builder_ptr, alpha, beta, column_eval, chi_eval) ->
+                shifted_column_eval,
+                shifted_chi_eval
+            {
+                shifted_column_eval := builder_consume_final_round_mle(builder_ptr)
+                shifted_chi_eval := builder_consume_chi_evaluation(builder_ptr)
+                let rho_eval := builder_consume_rho_evaluation(builder_ptr)
+                let shifted_rho_eval := builder_consume_rho_evaluation(builder_ptr)
+                let c_fold :=
+                    mulmod(
+                        alpha,
+                        addmod(mulmod(addmod(rho_eval, chi_eval, MODULUS), beta, MODULUS), column_eval, MODULUS),
+                        MODULUS
+                    )
+                let d_fold :=
+                    mulmod(alpha, addmod(mulmod(shifted_rho_eval, beta, MODULUS), shifted_column_eval, MODULUS), MODULUS)
+                verify_permutation_check(builder_ptr, c_fold, d_fold, shifted_chi_eval)
+            }
+            function is_monotonic_sign_allowed(sign_eval, chi_eval, shifted_chi_eval, singleton_chi_eval, strict) ->
+                allowed
+            {
+                switch strict
+                case 0 {
+                    let chi_diff_eval := addmod(shifted_chi_eval, sub(MODULUS, chi_eval), MODULUS)
+                    allowed :=
+                        or(
+                            or(eq(sign_eval, singleton_chi_eval), eq(sign_eval, chi_diff_eval)),
+                            or(eq(sign_eval, addmod(singleton_chi_eval, chi_diff_eval, MODULUS)), iszero(sign_eval))
+                        )
+                }
+                default {
+                    allowed :=
+                        or(
+                            eq(sign_eval, chi_eval),
+                            or(
+                                eq(sign_eval, addmod(shifted_chi_eval, sub(MODULUS, singleton_chi_eval), MODULUS)),
+                                eq(sign_eval, addmod(chi_eval, sub(MODULUS, singleton_chi_eval), MODULUS))
+                            )
+                        )
+                }
+            }
+            // Checks that the column is increasing, strictly if `strict` is set.
+            function verify_monotonic(builder_ptr, alpha, beta, column_eval, chi_eval, strict) {
+                let shifted_column_eval, shifted_chi_eval := verify_shift(builder_ptr, alpha, beta, column_eval, chi_eval)
+                let ind_eval := addmod(column_eval, sub(MODULUS, shifted_column_eval), MODULUS)
+                if strict { ind_eval := addmod(shifted_column_eval, sub(MODULUS, column_eval), MODULUS) }
+                let sign_eval := verify_sign(builder_ptr, ind_eval, shifted_chi_eval)
+                if iszero(
+                    is_monotonic_sign_allowed(
+                        sign_eval,
+                        chi_eval,
+                        shifted_chi_eval,
+                        mload(add(builder_ptr, BUILDER_SINGLETON_CHI_EVALUATION_OFFSET)),
+                        strict
+                    )
+                ) { err(ERR_MONOTONICITY_CHECK_FAILED) }
+            }
+            function verify_union_input(builder_ptr, c_fold, chi_n_eval) -> c_star_eval {
+                c_star_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(builder_ptr, star_identity(c_fold, c_star_eval, chi_n_eval), 2)
+            }
+            function verify_union_output(builder_ptr, d_fold, chi_m_eval, c_star_sum_eval) {
+                let d_star_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(builder_ptr, star_identity(d_fold, d_star_eval, chi_m_eval), 2)
+                builder_produce_zerosum_constraint(
+                    builder_ptr, addmod(c_star_sum_eval, sub(MODULUS, d_star_eval), MODULUS), 1
+                )
+            }
+            // `weighted_sum_in_fold` is the folded input sums multiplied by the selection.
+            function verify_group_by(
+                builder_ptr, g_in_fold, g_out_fold, weighted_sum_in_fold, sum_out_fold, chi_n_eval, chi_m_eval
+            ) {
+                let g_in_star_eval := builder_consume_final_round_mle(builder_ptr)
+                let g_out_star_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_zerosum_constraint(
+                    builder_ptr,
+                    addmod(
+                        mulmod(g_in_star_eval, weighted_sum_in_fold, MODULUS),
+                        sub(MODULUS, mulmod(g_out_star_eval, sum_out_fold, MODULUS)),
+                        MODULUS
+                    ),
+                    3
+                )
+                builder_produce_identity_constraint(builder_ptr, star_identity(g_in_fold, g_in_star_eval, chi_n_eval), 2)
+                builder_produce_identity_constraint(builder_ptr, star_identity(g_out_fold, g_out_star_eval, chi_m_eval), 2)
+            }
+            // `sign` is 1 for a maximum and -1 for a minimum.
+            function verify_extremum(builder_ptr, value_eval, expanded_eval, is_extremum_eval, selection_eval, sign, chi_eval) {
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    mulmod(is_extremum_eval, addmod(value_eval, sub(MODULUS, expanded_eval), MODULUS), MODULUS),
+                    2
+                )
+                let diff_eval := builder_consume_final_round_mle(builder_ptr)
+                builder_produce_identity_constraint(
+                    builder_ptr,
+                    addmod(
+                        diff_eval,
+                        mulmod(
+                            sign,
+                            addmod(mulmod(selection_eval, value_eval, MODULUS), sub(MODULUS, expanded_eval), MODULUS),
+                            MODULUS
+                        ),
+                        MODULUS
+                    ),
+                    2
+                )
+                if verify_sign(builder_ptr, diff_eval, chi_eval) { err(ERR_EXTREMUM_CHECK_FAILED) }
+            }
+
+            // Returns the kind of the values of a result column, and whether the column is a scalar column.
+            function read_result_column_header(ptr, end, exact_hash, scalar_hash, spec) -> kind, is_scalar, next_ptr {
+                let free_ptr := mload(FREE_PTR)
+                let exact_length := and(spec, 0xffff)
+                if iszero(gt(add(ptr, exact_length), end)) {
+                    calldatacopy(free_ptr, ptr, exact_length)
+                    if eq(keccak256(free_ptr, exact_length), exact_hash) {
+                        kind := and(shr(16, spec), 0xff)
+                        is_scalar := eq(exact_hash, scalar_hash)
+                        next_ptr := add(ptr, exact_length)
+                    }
+                }
+                if iszero(next_ptr) {
+                    let scalar_length := and(shr(32, spec), 0xffff)
+                    if gt(add(ptr, scalar_length), end) { err(ERR_INVALID_RESULT) }
+                    calldatacopy(free_ptr, ptr, scalar_length)
+                    if sub(keccak256(free_ptr, scalar_length), scalar_hash) { err(ERR_INVALID_RESULT) }
+                    kind := and(shr(48, spec), 0xff)
+                    is_scalar := 1
+                    next_ptr := add(ptr, scalar_length)
+                }
+            }
+            function read_result_word(ptr, end, kind) -> value, next_ptr {
+                next_ptr := skip_calldata(ptr, WORD_SIZE, end)
+                value := calldataload(ptr)
+                if iszero(lt(value, MODULUS)) { err(ERR_INVALID_RESULT) }
+                switch kind
+                case 0x03 {}
+                case 0x04 { if gt(value, 0xff) { err(ERR_INVALID_RESULT) } }
+                default {
+                    let bound := shl(sub(shl(3, and(kind, 0x1f)), 1), 1)
+                    if and(iszero(lt(value, bound)), lt(value, sub(MODULUS, bound))) { err(ERR_INVALID_RESULT) }
+                }
+            }
+            // Reads a value of a result column as a field element.
+            function read_result_value(ptr, end, kind) -> value, next_ptr {
+                switch kind
+                case 0x00 {
+                    next_ptr := skip_calldata(ptr, 1, end)
+                    value := shr(248, calldataload(ptr))
+                    if gt(value, 1) { err(ERR_INVALID_RESULT) }
+                }
+                case 0x01 {
+                    next_ptr := skip_calldata(ptr, 1, end)
+                    value := shr(248, calldataload(ptr))
+                }
+                case 0x02 {
+                    let length
+                    length, ptr := read_uint64(ptr, end)
+                    next_ptr := skip_calldata(ptr, length, end)
+                    if length {
+                        let free_ptr := mload(FREE_PTR)
+                        calldatacopy(free_ptr, ptr, length)
+                        value := and(reverse_bytes(keccak256(free_ptr, length)), MODULUS_MASK)
+                    }
+                }
+                default {
+                    switch and(kind, 0xe0)
+                    case 0x20 {
+                        let width := and(kind, 0x1f)
+                        next_ptr := skip_calldata(ptr, width, end)
+                        value := signextend(sub(width, 1), shr(sub(256, shl(3, width)), calldataload(ptr)))
+                        if slt(value, 0) { value := add(value, MODULUS) }
+                    }
+                    default { value, next_ptr := read_result_word(ptr, end, kind) }
+                }
+            }
+            // Reads a result column into `[num_rows, is_scalar, values...]`.
+            function read_result_column(ptr, end, exact_hash, scalar_hash, spec) -> column_ptr, next_ptr {
+                let kind, is_scalar
+                kind, is_scalar, ptr := read_result_column_header(ptr, end, exact_hash, scalar_hash, spec)
+                let num_rows
+                num_rows, ptr := read_uint64(ptr, end)
+                // Every value takes at least a byte, which bounds the memory that is allocated.
+                if gt(num_rows, sub(end, ptr)) { err(ERR_INVALID_RESULT) }
+                column_ptr := mload(FREE_PTR)
+                mstore(column_ptr, num_rows)
+                mstore(add(column_ptr, WORD_SIZE), is_scalar)
+                let value_ptr := add(column_ptr, RESULT_COLUMN_VALUES_OFFSET)
+                mstore(FREE_PTR, add(value_ptr, mul(num_rows, WORD_SIZE)))
+                for {} num_rows { num_rows := sub(num_rows, 1) } {
+                    let value
+                    value, ptr := read_result_value(ptr, end, kind)
+                    mstore(value_ptr, value)
+                    value_ptr := add(value_ptr, WORD_SIZE)
+                }
+                next_ptr := ptr
+            }
+            function read_result(ptr, end) -> columns_ptr {
+                let num_columns
+                num_columns, ptr := read_uint64(ptr, end)
+                if sub(num_columns, NUM_RESULT_COLUMNS) { err(ERR_INVALID_RESULT) }
+                columns_ptr := mload(FREE_PTR)
+                mstore(columns_ptr, NUM_RESULT_COLUMNS)
+                mstore(FREE_PTR, add(columns_ptr, mul(add(NUM_RESULT_COLUMNS, 1), WORD_SIZE)))
+                let column_ptr
+{{READ_RESULT}}
+                if sub(ptr, end) { err(ERR_INVALID_RESULT) }
+            }
+            function compute_evaluation_vector(length, point_ptr, num_vars) -> evaluations_ptr {
+                evaluations_ptr := mload(FREE_PTR)
+                mstore(FREE_PTR, add(evaluations_ptr, mul(length, WORD_SIZE)))
+                if length { mstore(evaluations_ptr, 1) }
+                for { let j := 0 } lt(j, num_vars) { j := add(j, 1) } {
+                    let x := mload(add(point_ptr, mul(j, WORD_SIZE)))
+                    let one_minus_x := addmod(1, sub(MODULUS, x), MODULUS)
+                    let mid := shl(j, 1)
+                    for { let i := 0 } and(lt(i, mid), lt(i, length)) { i := add(i, 1) } {
+                        let left_ptr := add(evaluations_ptr, mul(i, WORD_SIZE))
+                        let value := mload(left_ptr)
+                        if lt(add(i, mid), length) {
+                            mstore(add(left_ptr, mul(mid, WORD_SIZE)), mulmod(value, x, MODULUS))
+                        }
+                        mstore(left_ptr, mulmod(value, one_minus_x, MODULUS))
+                    }
+                }
+            }
+            function compute_column_evaluation(column_ptr, evaluations_ptr) -> eval {
+                let value_ptr := add(column_ptr, RESULT_COLUMN_VALUES_OFFSET)
+                for { let i := mload(column_ptr) } i { i := sub(i, 1) } {
+                    eval := addmod(eval, mulmod(mload(value_ptr), mload(evaluations_ptr), MODULUS), MODULUS)
+                    value_ptr := add(value_ptr, WORD_SIZE)
+                    evaluations_ptr := add(evaluations_ptr, WORD_SIZE)
+                }
+            }
+            // Scalar columns are compared as unsigned integers, and all other columns as signed integers.
+            function compare_result_values(column_ptr, i, j) -> ordering {
+                let lhs := mload(add(add(column_ptr, RESULT_COLUMN_VALUES_OFFSET), mul(i, WORD_SIZE)))
+                let rhs := mload(add(add(column_ptr, RESULT_COLUMN_VALUES_OFFSET), mul(j, WORD_SIZE)))
+                if iszero(mload(add(column_ptr, WORD_SIZE))) {
+                    if gt(lhs, shr(1, MODULUS_MINUS_ONE)) { lhs := sub(lhs, MODULUS) }
+                    if gt(rhs, shr(1, MODULUS_MINUS_ONE)) { rhs := sub(rhs, MODULUS) }
+                    ordering := sub(sgt(lhs, rhs), slt(lhs, rhs))
+                    leave
+                }
+                ordering := sub(gt(lhs, rhs), lt(lhs, rhs))
+            }
+            function is_row_increasing(columns_ptr, row) -> increasing {
+                for { let k := 0 } lt(k, NUM_ORDERED_RESULT_COLUMNS) { k := add(k, 1) } {
+                    let ordering := compare_result_values(mload(add(columns_ptr, mul(add(k, 1), WORD_SIZE))), sub(row, 1), row)
+                    if ordering {
+                        increasing := eq(ordering, sub(0, 1))
+                        leave
+                    }
+                }
+            }
+            function check_result_order(columns_ptr, num_rows) {
+                if CHECK_RESULT_ORDER {
+                    if iszero(num_rows) { err(ERR_INVALID_RESULT) }
+                    for { let row := 1 } lt(row, num_rows) { row := add(row, 1) } {
+                        if iszero(is_row_increasing(columns_ptr, row)) { err(ERR_RESULT_NOT_ORDERED) }
+                    }
+                }
+            }
+            function verify_result(state_ptr, evaluations_ptr) {
+                let columns_ptr :=
+                    read_result(
+                        mload(add(state_ptr, STATE_RESULT_PTR_OFFSET)), mload(add(state_ptr, STATE_RESULT_END_OFFSET))
+                    )
+                let num_vars := mload(add(state_ptr, STATE_NUM_VARS_OFFSET))
+                let num_rows := 0
+                if NUM_RESULT_COLUMNS { num_rows := mload(mload(add(columns_ptr, WORD_SIZE))) }
+                if gt(num_rows, shl(num_vars, 1)) { err(ERR_INVALID_RESULT) }
+                let vector_ptr :=
+                    compute_evaluation_vector(
+                        num_rows, add(mload(add(state_ptr, STATE_EVALUATION_POINT_OFFSET)), WORD_SIZE), num_vars
+                    )
+                for { let i := 0 } lt(i, NUM_RESULT_COLUMNS) { i := add(i, 1) } {
+                    let offset := mul(add(i, 1), WORD_SIZE)
+                    let column_ptr := mload(add(columns_ptr, offset))
+                    if sub(mload(column_ptr), num_rows) { err(ERR_INVALID_RESULT) }
+                    if sub(compute_column_evaluation(column_ptr, vector_ptr), mload(add(evaluations_ptr, offset))) {
+                        err(ERR_RESULT_EVALUATION_MISMATCH)
+                    }
+                }
+                check_result_order(columns_ptr, num_rows)
+            }
+
+            function gather_calldata(target, slot_ptr, element_size) -> next_target {
+                let size := mul(mload(slot_ptr), element_size)
+                calldatacopy(target, mload(add(slot_ptr, WORD_SIZE)), size)
+                next_target := add(target, size)
+            }
+            // Batches the commitments and the evaluations of the proof, in the order the prover opens them.
+            function batch_pcs(state_ptr, commitments_ptr) -> batch_ptr, batch_eval {
+                let first_round_count := mload(add(state_ptr, STATE_FIRST_ROUND_COMMITMENTS_OFFSET))
+                let final_round_count := mload(add(state_ptr, STATE_FINAL_ROUND_COMMITMENTS_OFFSET))
+                if or(
+                    sub(first_round_count, mload(add(state_ptr, STATE_FIRST_ROUND_EVALUATIONS_OFFSET))),
+                    sub(final_round_count, mload(add(state_ptr, STATE_FINAL_ROUND_EVALUATIONS_OFFSET)))
+                ) { err(ERR_PCS_BATCH_SIZE_MISMATCH) }
+
+                let points_ptr := mload(FREE_PTR)
+                let target := gather_calldata(points_ptr, add(state_ptr, STATE_FIRST_ROUND_COMMITMENTS_OFFSET), WORDX2_SIZE)
+                mcopy(target, commitments_ptr, mul(NUM_COLUMNS, WORDX2_SIZE))
+                target :=
+                    gather_calldata(
+                        add(target, mul(NUM_COLUMNS, WORDX2_SIZE)),
+                        add(state_ptr, STATE_FINAL_ROUND_COMMITMENTS_OFFSET),
+                        WORDX2_SIZE
+                    )
+                let factors_ptr := mload(add(state_ptr, STATE_EVALUATION_CHALLENGES_OFFSET))
+                let count := add(add(first_round_count, NUM_COLUMNS), final_round_count)
+                // The first round, column and final round evaluations are next to each other, up to their counts.
+                let evaluations_ptr := target
+                target := gather_calldata(target, add(state_ptr, STATE_FIRST_ROUND_EVALUATIONS_OFFSET), WORD_SIZE)
+                target := gather_calldata(target, add(state_ptr, STATE_COLUMN_EVALUATIONS_OFFSET), WORD_SIZE)
+                target := gather_calldata(target, add(state_ptr, STATE_FINAL_ROUND_EVALUATIONS_OFFSET), WORD_SIZE)
+
+                batch_ptr := target
+                mstore(FREE_PTR, add(batch_ptr, mul(5, WORD_SIZE)))
+                mstore(batch_ptr, 0)
+                mstore(add(batch_ptr, WORD_SIZE), 0)
+                for {} count { count := sub(count, 1) } {
+                    let factor := mload(factors_ptr)
+                    mcopy(add(batch_ptr, WORDX2_SIZE), points_ptr, WORDX2_SIZE)
+                    ec_mul_assign(add(batch_ptr, WORDX2_SIZE), factor)
+                    ec_add(batch_ptr)
+                    batch_eval := addmod(batch_eval, mulmod(factor, mload(evaluations_ptr), MODULUS), MODULUS)
+                    points_ptr := add(points_ptr, WORDX2_SIZE)
+                    evaluations_ptr := add(evaluations_ptr, WORD_SIZE)
+                    factors_ptr := add(factors_ptr, WORD_SIZE)
+                }
+            }
+            function check_hyperkzg_proof(state_ptr) {
+                let num_vars := mload(add(state_ptr, STATE_NUM_VARS_OFFSET))
+                if or(
+                    sub(mload(add(state_ptr, STATE_COM_OFFSET)), sub(num_vars, 1)),
+                    sub(mload(add(state_ptr, STATE_V_OFFSET)), num_vars)
+                ) { err(ERR_HYPER_KZG_PROOF_SIZE_MISMATCH) }
+                let v_ptr := mload(add(state_ptr, add(STATE_V_OFFSET, WORD_SIZE)))
+                for { let i := mul(3, num_vars) } i { i := sub(i, 1) } {
+                    if iszero(lt(calldataload(v_ptr), MODULUS)) { err(ERR_INVALID_CALLDATA) }
+                    v_ptr := add(v_ptr, WORD_SIZE)
+                }
+            }
+            function compute_hyperkzg_l(state_ptr, batch_ptr, scratch_ptr) {
+                let challenges_ptr := add(state_ptr, STATE_HYPERKZG_CHALLENGES_OFFSET)
+                compute_gl_msm(
+                    mload(add(state_ptr, add(STATE_COM_OFFSET, WORD_SIZE))),
+                    sub(mload(add(state_ptr, STATE_NUM_VARS_OFFSET)), 1),
+                    mload(add(state_ptr, STATE_W_OFFSET)),
+                    batch_ptr,
+                    mload(challenges_ptr),
+                    mload(add(challenges_ptr, WORD_SIZE)),
+                    mload(add(challenges_ptr, WORDX2_SIZE)),
+                    mload(add(challenges_ptr, WORDX3_SIZE)),
+                    scratch_ptr
+                )
+            }
+            function verify_hyperkzg(state_ptr, batch_ptr, batch_eval) {
+                check_hyperkzg_proof(state_ptr)
+                let num_vars := mload(add(state_ptr, STATE_NUM_VARS_OFFSET))
+                let v_ptr := mload(add(state_ptr, add(STATE_V_OFFSET, WORD_SIZE)))
+                let transcript_ptr := add(state_ptr, STATE_HYPERKZG_TRANSCRIPT_OFFSET)
+                mstore(transcript_ptr, mload(add(state_ptr, STATE_TRANSCRIPT_OFFSET)))
+                let r, q, d :=
+                    run_transcript(
+                        mload(add(state_ptr, add(STATE_COM_OFFSET, WORD_SIZE))),
+                        v_ptr,
+                        mload(add(state_ptr, STATE_W_OFFSET)),
+                        transcript_ptr,
+                        num_vars
+                    )
+                if or(iszero(r), iszero(or(mload(batch_ptr), mload(add(batch_ptr, WORD_SIZE))))) {
+                    err(ERR_HYPER_KZG_DEGENERATE)
+                }
+                check_v_consistency(v_ptr, r, mload(add(state_ptr, STATE_EVALUATION_POINT_OFFSET)), batch_eval)
+                let challenges_ptr := add(state_ptr, STATE_HYPERKZG_CHALLENGES_OFFSET)
+                mstore(challenges_ptr, r)
+                mstore(add(challenges_ptr, WORD_SIZE), q)
+                mstore(add(challenges_ptr, WORDX2_SIZE), d)
+                mstore(add(challenges_ptr, WORDX3_SIZE), bivariate_evaluation(v_ptr, q, d, num_vars))
+
+                // e(L, -H) + e(R, τ·H) == 0
+                let pairing_ptr := mload(FREE_PTR)
+                mstore(FREE_PTR, add(pairing_ptr, WORDX12_SIZE))
+                compute_hyperkzg_l(state_ptr, batch_ptr, pairing_ptr)
+                univariate_group_evaluation(mload(add(state_ptr, STATE_W_OFFSET)), d, 3, add(pairing_ptr, WORDX6_SIZE))
+                mstore(add(pairing_ptr, WORDX2_SIZE), G2_NEG_GEN_X_IMAG)
+                mstore(add(pairing_ptr, WORDX3_SIZE), G2_NEG_GEN_X_REAL)
+                mstore(add(pairing_ptr, WORDX4_SIZE), G2_NEG_GEN_Y_IMAG)
+                mstore(add(pairing_ptr, mul(5, WORD_SIZE)), G2_NEG_GEN_Y_REAL)
+                mstore(add(pairing_ptr, mul(8, WORD_SIZE)), VK_TAU_H_X_IMAG)
+                mstore(add(pairing_ptr, mul(9, WORD_SIZE)), VK_TAU_H_X_REAL)
+                mstore(add(pairing_ptr, mul(10, WORD_SIZE)), VK_TAU_H_Y_IMAG)
+                mstore(add(pairing_ptr, mul(11, WORD_SIZE)), VK_TAU_H_Y_REAL)
+                if iszero(ec_pairing_x2(pairing_ptr)) { err(ERR_HYPER_KZG_PAIRING_CHECK_FAILED) }
+
+                // The final challenge of the HyperKZG transcript is appended to the transcript.
+                mstore(add(transcript_ptr, WORD_SIZE), keccak256(transcript_ptr, WORD_SIZE))
+                let outer_transcript_ptr := add(state_ptr, STATE_TRANSCRIPT_OFFSET)
+                mstore(transcript_ptr, mload(outer_transcript_ptr))
+                mstore(outer_transcript_ptr, keccak256(transcript_ptr, WORDX2_SIZE))
+            }
+
+            function verify_plan(builder_ptr) -> evaluations_ptr {
+                let vars_ptr := mload(FREE_PTR)
+                mstore(FREE_PTR, add(vars_ptr, mul(NUM_PLAN_VARIABLES, WORD_SIZE)))
+{{VERIFY_PLAN}}
+            }
+
+            function verify_query(proof_ptr, proof_end, table_lengths_ptr, commitments_ptr) -> verification_hash {
+                if or(sub(mload(table_lengths_ptr), NUM_TABLES), sub(mload(commitments_ptr), mul(NUM_COLUMNS, 2))) {
+                    err(ERR_INVALID_VERIFIER_INPUT)
+                }
+                let state_ptr := mload(FREE_PTR)
+                mstore(FREE_PTR, add(state_ptr, STATE_SIZE))
+                read_proof(state_ptr, proof_ptr, proof_end)
+
+                let builder_ptr := setup_builder(state_ptr, table_lengths_ptr)
+                let evaluations_ptr := verify_plan(builder_ptr)
+                check_builder_completed(builder_ptr)
+                verify_result(state_ptr, evaluations_ptr)
+                if sub(
+                    mload(add(builder_ptr, BUILDER_AGGREGATE_EVALUATION_OFFSET)),
+                    mload(add(state_ptr, STATE_EXPECTED_EVALUATION_OFFSET))
+                ) { err(ERR_SUMCHECK_EVALUATION_MISMATCH) }
+
+                let batch_ptr, batch_eval := batch_pcs(state_ptr, add(commitments_ptr, WORD_SIZE))
+                verify_hyperkzg(state_ptr, batch_ptr, batch_eval)
+                verification_hash := mload(add(state_ptr, STATE_TRANSCRIPT_OFFSET))
+            }
+            __verificationHash :=
+                verify_query(__proof.offset, add(__proof.offset, __proof.length), __tableLengths, __commitments)
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use alloc::string::String;
+use snafu::Snafu;
+
+/// Errors that can occur when generating a Solidity verifier.
+#[derive(Snafu, Debug, PartialEq)]
+pub enum SolidityVerifierError {
+    /// The plan is rejected by its own verifier, regardless of the proof.
+    #[snafu(display("the plan can not be verified: {error}"))]
+    UnverifiablePlan {
+        /// The reason that the verifier of the plan gives
+        error: &'static str,
+    },
+    /// A column is referenced that is not available where it is referenced.
+    #[snafu(display("column not found"))]
+    ColumnNotFound,
+    /// A table is referenced that is not a table of the plan.
+    #[snafu(display("table not found"))]
+    TableNotFound,
+    /// The plan cannot be encoded for the EVM.
+    #[snafu(display("the plan cannot be encoded for the EVM"))]
+    PlanEncoding,
+    /// A Solidity source that the verifier is assembled from is missing.
+    #[snafu(display("missing Solidity source {path}"))]
+    MissingSource {
+        /// The path of the source, relative to `solidity/src`
+        path: String,
+    },
+    /// A Solidity source does not contain the definition that the verifier takes from it.
+    #[snafu(display("{name} not found in {path}"))]
+    MissingDefinition {
+        /// The name of the Yul function or Solidity struct
+        name: String,
+        /// The path of the source, relative to `solidity/src`
+        path: String,
+    },
+}
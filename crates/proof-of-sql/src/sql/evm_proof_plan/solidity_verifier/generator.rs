@@ -0,0 +1,112 @@
+use super::{
+    plan_codegen::generate_verify_plan,
+    result_codegen::generate_read_result,
+    sources::{import_yul_functions, read_source, strip_preamble, IMPORTS},
+    SolidityVerifierError,
+};
+use crate::sql::{evm_proof_plan::EVMProofPlan, proof::ProofPlan};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use ark_bn254::{Fq, G2Affine};
+use ark_ff::PrimeField;
+use tiny_keccak::{Hasher, Keccak};
+
+const TEMPLATE: &str = include_str!("QueryVerifier.template.sol");
+
+pub(super) fn word_literal(word: &[u8; 32]) -> String {
+    word.iter().fold("0x".to_string(), |literal, byte| {
+        format!("{literal}{byte:02x}")
+    })
+}
+
+fn field_literal(element: Fq) -> String {
+    let mut word = [0; 32];
+    for (chunk, limb) in word.chunks_mut(8).zip(element.into_bigint().0.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    word_literal(&word)
+}
+
+fn indent(lines: &[String], width: usize) -> String {
+    lines
+        .iter()
+        .map(|line| format!("{:width$}{line}", ""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generates a Solidity verifier that is specialized to `plan` and to the `HyperKZG` setup with the given `tau_h`.
+///
+/// `tau_h` is `τ·H` from the verifier key, where `H` is the generator of G2.
+/// `read_file` returns the content of a file of the `solidity/src` tree, given its path relative to that directory.
+/// The generated verifier is a single file that does not depend on that tree.
+///
+/// # Errors
+/// Returns an error if the verifier of the plan rejects it regardless of the proof,
+/// or if a source the verifier is assembled from is missing or does not contain what is taken from it.
+pub fn generate_solidity_verifier(
+    plan: &EVMProofPlan,
+    tau_h: &G2Affine,
+    read_file: impl Fn(&str) -> Option<String>,
+) -> Result<String, SolidityVerifierError> {
+    let plan_bytes = bincode::serde::encode_to_vec(
+        plan,
+        bincode::config::legacy()
+            .with_fixed_int_encoding()
+            .with_big_endian(),
+    )
+    .map_err(|_| SolidityVerifierError::PlanEncoding)?;
+    let mut plan_hash = [0; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(&plan_bytes);
+    hasher.finalize(&mut plan_hash);
+
+    let verify_plan = generate_verify_plan(plan.inner())?;
+    let read_result = generate_read_result(&plan.inner().get_column_result_fields())?;
+
+    let constants = read_source(&read_file, "base/Constants.sol")?;
+    let errors = read_source(&read_file, "base/Errors.sol")?;
+    let imports = import_yul_functions(&read_file, &IMPORTS)?;
+
+    Ok(TEMPLATE
+        .replace("{{CONSTANTS}}", strip_preamble(&constants))
+        .replace("{{ERRORS}}", strip_preamble(&errors))
+        .replace("{{PLAN_HASH}}", &word_literal(&plan_hash))
+        .replace("{{VK_TAU_H_X_IMAG}}", &field_literal(tau_h.x.c1))
+        .replace("{{VK_TAU_H_X_REAL}}", &field_literal(tau_h.x.c0))
+        .replace("{{VK_TAU_H_Y_IMAG}}", &field_literal(tau_h.y.c1))
+        .replace("{{VK_TAU_H_Y_REAL}}", &field_literal(tau_h.y.c0))
+        .replace(
+            "{{NUM_TABLES}}",
+            &plan.inner().get_table_references().len().to_string(),
+        )
+        .replace(
+            "{{NUM_COLUMNS}}",
+            &plan.inner().get_column_references().len().to_string(),
+        )
+        .replace(
+            "{{NUM_RESULT_COLUMNS}}",
+            &plan.inner().get_column_result_fields().len().to_string(),
+        )
+        .replace(
+            "{{NUM_PLAN_VARIABLES}}",
+            &verify_plan.num_variables.to_string(),
+        )
+        .replace(
+            "{{CHECK_RESULT_ORDER}}",
+            &u8::from(verify_plan.num_ordered_result_columns.is_some()).to_string(),
+        )
+        .replace(
+            "{{NUM_ORDERED_RESULT_COLUMNS}}",
+            &verify_plan
+                .num_ordered_result_columns
+                .unwrap_or(0)
+                .to_string(),
+        )
+        .replace("{{IMPORTS}}\n", &imports)
+        .replace("{{READ_RESULT}}", &indent(&read_result, 16))
+        .replace("{{VERIFY_PLAN}}", &indent(&verify_plan.lines, 16)))
+}
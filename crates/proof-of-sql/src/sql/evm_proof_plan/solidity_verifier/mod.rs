@@ -0,0 +1,14 @@
+//! Generation of Solidity verifiers that are specialized to a single [`EVMProofPlan`](super::EVMProofPlan).
+//!
+//! The generated verifier is assembled from the building blocks in `solidity/src`, along with `verify_plan`,
+//! which is generated from the plan so that it consumes the verification builder exactly the way
+//! `ProofPlan::verifier_evaluate` does.
+mod error;
+pub use error::SolidityVerifierError;
+mod generator;
+pub use generator::generate_solidity_verifier;
+mod plan_codegen;
+mod result_codegen;
+mod sources;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,1468 @@
+//! Generation of `verify_plan`, the Yul equivalent of [`ProofPlan::verifier_evaluate`] for a single plan.
+//!
+//! Every plan and expression is unrolled into straight-line code that reads from and writes to the
+//! verification builder in exactly the order that its `verifier_evaluate` does.
+//! Intermediate evaluations are kept in memory rather than in Yul variables,
+//! so that the generated code never runs out of stack, however large the plan is.
+use super::SolidityVerifierError;
+use crate::{
+    base::{
+        database::{slice_operation::apply_slice_to_indexes, ColumnRef, TableRef},
+        map::IndexMap,
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    proof_primitive::hyperkzg::BNScalar,
+    sql::{
+        proof::ProofPlan,
+        proof_exprs::{
+            timestamp_field_division, AliasedDynProofExpr, DynProofExpr, FloorDivision, ProofExpr,
+        },
+        proof_plans::{
+            verify_join_column_types, DistinctExec, DynProofPlan, GroupByExec, SemiJoinExec,
+            SortExec, SortMergeJoinExec,
+        },
+    },
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::iter;
+
+/// `2^64`, which [`PlanCodegen::sort_key`] folds 64 bit columns with.
+const TWO_POW_64: &str = "0x10000000000000000";
+
+/// The Yul counterpart of a `TableEvaluation`, i.e. the expressions loading its evaluations.
+struct YulTableEvaluation {
+    column_evals: Vec<String>,
+    chi_eval: String,
+}
+
+fn add(lhs: &str, rhs: &str) -> String {
+    format!("addmod({lhs}, {rhs}, MODULUS)")
+}
+
+fn sub(lhs: &str, rhs: &str) -> String {
+    format!("addmod({lhs}, sub(MODULUS, mod({rhs}, MODULUS)), MODULUS)")
+}
+
+fn mul(lhs: &str, rhs: &str) -> String {
+    format!("mulmod({lhs}, {rhs}, MODULUS)")
+}
+
+/// Formats a scalar as a Yul literal.
+fn scalar_literal(scalar: BNScalar) -> String {
+    let limbs: [u64; 4] = scalar.into();
+    match limbs.iter().rposition(|&limb| limb != 0) {
+        None => "0".to_string(),
+        Some(top) => limbs[..top]
+            .iter()
+            .rev()
+            .fold(format!("0x{:x}", limbs[top]), |literal, limb| {
+                format!("{literal}{limb:016x}")
+            }),
+    }
+}
+
+/// Multiplies an evaluation by `10^exponent`.
+fn scale_by(eval: &str, exponent: u8) -> String {
+    match exponent {
+        0 => eval.to_string(),
+        _ => mul(eval, &scalar_literal(BNScalar::pow10(exponent))),
+    }
+}
+
+/// Mirrors `scale_and_subtract_eval` and `scale_and_add_subtract_eval`.
+fn scale_and_add_subtract(
+    (lhs_eval, lhs_scale): (&str, i8),
+    (rhs_eval, rhs_scale): (&str, i8),
+    is_subtract: bool,
+) -> String {
+    let max_scale = lhs_scale.max(rhs_scale);
+    let left_scaled_eval = scale_by(lhs_eval, max_scale.abs_diff(lhs_scale));
+    let right_scaled_eval = scale_by(rhs_eval, max_scale.abs_diff(rhs_scale));
+    if is_subtract {
+        sub(&left_scaled_eval, &right_scaled_eval)
+    } else {
+        add(&left_scaled_eval, &right_scaled_eval)
+    }
+}
+
+fn scale(expr: &DynProofExpr) -> i8 {
+    expr.data_type().scale().unwrap_or(0)
+}
+
+/// Converts an error that the verifier of a plan returns regardless of the proof.
+fn unverifiable_plan(error: ProofError) -> SolidityVerifierError {
+    match error {
+        ProofError::VerificationError { error } | ProofError::UnsupportedQueryPlan { error } => {
+            SolidityVerifierError::UnverifiablePlan { error }
+        }
+        _ => SolidityVerifierError::UnverifiablePlan {
+            error: "the plan is rejected by its verifier",
+        },
+    }
+}
+
+/// The column evaluations of `input`, keyed the way that the expressions over it reference them.
+///
+/// This mirrors `input_accessor` of the projection and filter plans.
+fn input_accessor(
+    input: &DynProofPlan,
+    input_eval: &YulTableEvaluation,
+) -> Result<IndexMap<ColumnRef, String>, SolidityVerifierError> {
+    let input_schema = input.get_column_result_fields();
+    if input_schema
+        .iter()
+        .enumerate()
+        .any(|(i, field)| input_schema[..i].iter().any(|f| f.name() == field.name()))
+    {
+        return Err(SolidityVerifierError::UnverifiablePlan {
+            error: "input columns do not have unique names",
+        });
+    }
+    let mut input_table_refs = input.get_table_references();
+    if input_table_refs.is_empty() {
+        input_table_refs.insert(TableRef::from_names(None, "empty"));
+    }
+    Ok(input_table_refs
+        .iter()
+        .flat_map(|table_ref| {
+            input_schema
+                .iter()
+                .zip(&input_eval.column_evals)
+                .map(move |(field, eval)| {
+                    (
+                        ColumnRef::new(table_ref.clone(), field.name(), field.data_type()),
+                        eval.clone(),
+                    )
+                })
+        })
+        .collect())
+}
+
+/// The indexes of `\hat{L}` or `\hat{R}` in the order of the result, i.e. the join columns first.
+fn hat_column_indexes(join_column_indexes: &[usize], num_columns: usize) -> Vec<usize> {
+    join_column_indexes
+        .iter()
+        .copied()
+        .chain((0..=num_columns).filter(|i| !join_column_indexes.contains(i)))
+        .collect()
+}
+
+/// The indexes of the columns of an input that are not join columns.
+fn other_column_indexes(join_column_indexes: &[usize], num_columns: usize) -> Vec<usize> {
+    (0..num_columns)
+        .filter(|i| !join_column_indexes.contains(i))
+        .collect()
+}
+
+fn select(evals: &[String], indexes: &[usize]) -> Vec<String> {
+    apply_slice_to_indexes(evals, indexes).expect("Indexes can not be out of bounds")
+}
+
+/// Writes the body of `verify_plan`, one line at a time.
+#[derive(Default)]
+struct PlanCodegen {
+    lines: Vec<String>,
+    variable_count: usize,
+}
+
+impl PlanCodegen {
+    /// Stores `value` in a new memory word, returning the expression that loads it.
+    fn bind(&mut self, name: &str, value: &str) -> String {
+        let offset = 32 * self.variable_count;
+        self.variable_count += 1;
+        self.lines.push(format!(
+            "mstore(add(vars_ptr, {offset:#x}), {value}) // {name}"
+        ));
+        format!("mload(add(vars_ptr, {offset:#x}))")
+    }
+
+    /// Stores both values that `call` returns in new memory words, returning the expressions that load them.
+    fn bind_pair(
+        &mut self,
+        (first_name, second_name): (&str, &str),
+        call: &str,
+    ) -> (String, String) {
+        let first_offset = 32 * self.variable_count;
+        let second_offset = first_offset + 32;
+        self.variable_count += 2;
+        self.lines.push(format!(
+            "{{ let first, second := {call} mstore(add(vars_ptr, {first_offset:#x}), first) mstore(add(vars_ptr, {second_offset:#x}), second) }} // {first_name}, {second_name}"
+        ));
+        (
+            format!("mload(add(vars_ptr, {first_offset:#x}))"),
+            format!("mload(add(vars_ptr, {second_offset:#x}))"),
+        )
+    }
+
+    fn comment(&mut self, comment: &str) {
+        self.lines.push(format!("// {comment}"));
+    }
+
+    fn produce_identity_constraint(&mut self, evaluation: &str, degree: usize) {
+        self.lines.push(format!(
+            "builder_produce_identity_constraint(builder_ptr, {evaluation}, {degree})"
+        ));
+    }
+
+    fn produce_zerosum_constraint(&mut self, evaluation: &str, degree: usize) {
+        self.lines.push(format!(
+            "builder_produce_zerosum_constraint(builder_ptr, {evaluation}, {degree})"
+        ));
+    }
+
+    /// Reverts with `error` unless `eval` is nonnegative.
+    fn require_nonnegative(&mut self, eval: &str, chi_eval: &str, error: &str) {
+        self.lines.push(format!(
+            "if verify_sign(builder_ptr, {eval}, {chi_eval}) {{ err({error}) }}"
+        ));
+    }
+
+    fn consume_final_round_mle(&mut self, name: &str) -> String {
+        self.bind(name, "builder_consume_final_round_mle(builder_ptr)")
+    }
+
+    fn consume_final_round_mles(&mut self, name: &str, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|_| self.consume_final_round_mle(name))
+            .collect()
+    }
+
+    fn consume_first_round_mles(&mut self, name: &str, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|_| self.bind(name, "builder_consume_first_round_mle(builder_ptr)"))
+            .collect()
+    }
+
+    fn consume_challenge(&mut self, name: &str) -> String {
+        self.bind(name, "builder_consume_challenge(builder_ptr)")
+    }
+
+    fn consume_chi_evaluation(&mut self, name: &str) -> String {
+        self.bind(name, "builder_consume_chi_evaluation(builder_ptr)")
+    }
+
+    fn consume_rho_evaluation(&mut self, name: &str) -> String {
+        self.bind(name, "builder_consume_rho_evaluation(builder_ptr)")
+    }
+
+    /// Mirrors `factor * fold_vals(beta, vals)`.
+    fn fold(&mut self, factor: &str, beta: &str, vals: &[String]) -> String {
+        let Some((first, rest)) = vals.split_first() else {
+            return "0".to_string();
+        };
+        let fold = rest.iter().fold(first.clone(), |acc, val| {
+            self.bind("fold", &add(&mul(&acc, beta), val))
+        });
+        self.bind("fold", &mul(factor, &fold))
+    }
+
+    /// Mirrors `sort_key_eval`, which folds 64 bit columns.
+    fn sort_key(&mut self, order_by: &[(usize, bool)], column_evals: &[String]) -> String {
+        order_by
+            .iter()
+            .fold(None, |acc: Option<String>, &(index, is_asc)| {
+                let eval = &column_evals[index];
+                let shifted = acc.map(|acc| mul(&acc, TWO_POW_64));
+                Some(match (shifted, is_asc) {
+                    (None, true) => eval.clone(),
+                    (None, false) => self.bind("sort_key", &sub("0", eval)),
+                    (Some(shifted), true) => self.bind("sort_key", &add(&shifted, eval)),
+                    (Some(shifted), false) => self.bind("sort_key", &sub(&shifted, eval)),
+                })
+            })
+            .unwrap_or_else(|| "0".to_string())
+    }
+
+    /// The sort key of all of `column_evals` in ascending order.
+    fn ascending_sort_key(&mut self, column_evals: &[String]) -> String {
+        let order_by: Vec<_> = (0..column_evals.len()).map(|i| (i, true)).collect();
+        self.sort_key(&order_by, column_evals)
+    }
+
+    fn verify_plan(
+        &mut self,
+        plan: &DynProofPlan,
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval_map: &IndexMap<TableRef, String>,
+        is_root: bool,
+    ) -> Result<YulTableEvaluation, SolidityVerifierError> {
+        match plan {
+            DynProofPlan::Empty(_) => Ok(YulTableEvaluation {
+                column_evals: Vec::new(),
+                chi_eval: "mload(add(builder_ptr, BUILDER_SINGLETON_CHI_EVALUATION_OFFSET))"
+                    .to_string(),
+            }),
+            DynProofPlan::Table(table_exec) => {
+                let column_evals = table_exec
+                    .schema
+                    .iter()
+                    .map(|field| {
+                        let column_ref = ColumnRef::new(
+                            table_exec.table_ref.clone(),
+                            field.name(),
+                            field.data_type(),
+                        );
+                        accessor
+                            .get(&column_ref)
+                            .cloned()
+                            .ok_or(SolidityVerifierError::ColumnNotFound)
+                    })
+                    .collect::<Result<_, _>>()?;
+                let chi_eval = chi_eval_map
+                    .get(&table_exec.table_ref)
+                    .cloned()
+                    .ok_or(SolidityVerifierError::TableNotFound)?;
+                Ok(YulTableEvaluation {
+                    column_evals,
+                    chi_eval,
+                })
+            }
+            DynProofPlan::Projection(projection_exec) => {
+                let input_eval =
+                    self.verify_plan(&projection_exec.input, accessor, chi_eval_map, false)?;
+                self.comment("projection");
+                let current_accessor = input_accessor(&projection_exec.input, &input_eval)?;
+                let column_evals = self.verify_aliased_exprs(
+                    &projection_exec.aliased_results,
+                    &current_accessor,
+                    &input_eval.chi_eval,
+                )?;
+                Ok(YulTableEvaluation {
+                    column_evals,
+                    chi_eval: input_eval.chi_eval,
+                })
+            }
+            DynProofPlan::Filter(filter_exec) => {
+                let input_eval =
+                    self.verify_plan(&filter_exec.input, accessor, chi_eval_map, false)?;
+                self.comment("filter");
+                let input_chi_eval = &input_eval.chi_eval;
+                let current_accessor = input_accessor(&filter_exec.input, &input_eval)?;
+                let selection_eval =
+                    self.verify_expr(&filter_exec.where_clause, &current_accessor, input_chi_eval)?;
+                let columns_evals = self.verify_aliased_exprs(
+                    &filter_exec.aliased_results,
+                    &current_accessor,
+                    input_chi_eval,
+                )?;
+                let filtered_columns_evals = self.consume_final_round_mles(
+                    "filtered_column_eval",
+                    filter_exec.aliased_results.len(),
+                );
+                let alpha = self.consume_challenge("alpha");
+                let beta = self.consume_challenge("beta");
+                let output_chi_eval = self.consume_chi_evaluation("output_chi_eval");
+                self.verify_filter(
+                    (&alpha, &beta),
+                    (input_chi_eval, &output_chi_eval),
+                    &columns_evals,
+                    &selection_eval,
+                    &filtered_columns_evals,
+                );
+                Ok(YulTableEvaluation {
+                    column_evals: filtered_columns_evals,
+                    chi_eval: output_chi_eval,
+                })
+            }
+            DynProofPlan::Slice(slice_exec) => {
+                let input_eval =
+                    self.verify_plan(&slice_exec.input, accessor, chi_eval_map, false)?;
+                self.comment("slice");
+                let output_chi_eval = self.consume_chi_evaluation("output_chi_eval");
+                let offset_chi_eval = self.consume_chi_evaluation("offset_chi_eval");
+                let max_chi_eval = self.consume_chi_evaluation("max_chi_eval");
+                let selection_eval =
+                    self.bind("selection_eval", &sub(&max_chi_eval, &offset_chi_eval));
+                let filtered_columns_evals = self.consume_final_round_mles(
+                    "filtered_column_eval",
+                    input_eval.column_evals.len(),
+                );
+                let alpha = self.consume_challenge("alpha");
+                let beta = self.consume_challenge("beta");
+                self.verify_filter(
+                    (&alpha, &beta),
+                    (&input_eval.chi_eval, &output_chi_eval),
+                    &input_eval.column_evals,
+                    &selection_eval,
+                    &filtered_columns_evals,
+                );
+                Ok(YulTableEvaluation {
+                    column_evals: filtered_columns_evals,
+                    chi_eval: output_chi_eval,
+                })
+            }
+            DynProofPlan::Sort(sort_exec) => {
+                let fields = sort_exec.input.get_column_result_fields();
+                let sort_column_types: Vec<_> = sort_exec
+                    .order_by
+                    .iter()
+                    .map(|&(index, _)| fields[index].data_type())
+                    .collect();
+                if !SortExec::can_sort_by(&sort_column_types) {
+                    return Err(SolidityVerifierError::UnverifiablePlan {
+                        error: "Sort columns can not be folded into a sort key",
+                    });
+                }
+                let input_eval =
+                    self.verify_plan(&sort_exec.input, accessor, chi_eval_map, false)?;
+                self.comment("sort");
+                let chi_eval = &input_eval.chi_eval;
+                let sorted_column_evals = self
+                    .consume_final_round_mles("sorted_column_eval", input_eval.column_evals.len());
+                let alpha = self.consume_challenge("alpha");
+                let beta = self.consume_challenge("beta");
+                let c_fold = self.fold(&alpha, &beta, &input_eval.column_evals);
+                let d_fold = self.fold(&alpha, &beta, &sorted_column_evals);
+                self.lines.push(format!(
+                    "verify_permutation_check(builder_ptr, {c_fold}, {d_fold}, {chi_eval})"
+                ));
+                let sort_key_eval = self.sort_key(&sort_exec.order_by, &sorted_column_evals);
+                self.verify_monotonic((&alpha, &beta), &sort_key_eval, chi_eval, false);
+                Ok(YulTableEvaluation {
+                    column_evals: sorted_column_evals,
+                    chi_eval: input_eval.chi_eval,
+                })
+            }
+            DynProofPlan::Distinct(distinct_exec) => {
+                self.verify_distinct(distinct_exec, accessor, chi_eval_map)
+            }
+            DynProofPlan::Union(union_exec) => {
+                let input_evals = union_exec
+                    .inputs
+                    .iter()
+                    .map(|input| self.verify_plan(input, accessor, chi_eval_map, false))
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.comment("union");
+                let output_column_evals =
+                    self.consume_final_round_mles("output_column_eval", union_exec.schema.len());
+                let output_chi_eval = self.consume_chi_evaluation("output_chi_eval");
+                let gamma = self.consume_challenge("gamma");
+                let beta = self.consume_challenge("beta");
+                let parts: Vec<_> = input_evals
+                    .iter()
+                    .map(|input_eval| (input_eval.column_evals.as_slice(), &*input_eval.chi_eval))
+                    .collect();
+                self.verify_union(
+                    (&gamma, &beta),
+                    &parts,
+                    &output_column_evals,
+                    &output_chi_eval,
+                );
+                Ok(YulTableEvaluation {
+                    column_evals: output_column_evals,
+                    chi_eval: output_chi_eval,
+                })
+            }
+            DynProofPlan::GroupBy(group_by_exec) => {
+                self.verify_group_by(group_by_exec, accessor, chi_eval_map, is_root)
+            }
+            DynProofPlan::SortMergeJoin(join_exec) => {
+                self.verify_sort_merge_join(join_exec, accessor, chi_eval_map)
+            }
+            DynProofPlan::SemiJoin(semi_join_exec) => {
+                self.verify_semi_join(semi_join_exec, accessor, chi_eval_map)
+            }
+        }
+    }
+
+    fn verify_aliased_exprs(
+        &mut self,
+        aliased_exprs: &[AliasedDynProofExpr],
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval: &str,
+    ) -> Result<Vec<String>, SolidityVerifierError> {
+        aliased_exprs
+            .iter()
+            .map(|aliased_expr| self.verify_expr(&aliased_expr.expr, accessor, chi_eval))
+            .collect()
+    }
+
+    /// Mirrors `DistinctExec::verifier_evaluate`.
+    fn verify_distinct(
+        &mut self,
+        distinct_exec: &DistinctExec,
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval_map: &IndexMap<TableRef, String>,
+    ) -> Result<YulTableEvaluation, SolidityVerifierError> {
+        let column_types: Vec<_> = distinct_exec
+            .input
+            .get_column_result_fields()
+            .iter()
+            .map(|field| field.data_type())
+            .collect();
+        if !DistinctExec::can_deduplicate(&column_types) {
+            return Err(SolidityVerifierError::UnverifiablePlan {
+                error: "Distinct columns can not be folded into a sort key",
+            });
+        }
+        let input_eval = self.verify_plan(&distinct_exec.input, accessor, chi_eval_map, false)?;
+        self.comment("distinct");
+        let input_chi_eval = &input_eval.chi_eval;
+        let distinct_column_evals =
+            self.consume_final_round_mles("distinct_column_eval", input_eval.column_evals.len());
+        let count_eval = self.consume_final_round_mle("count_eval");
+        let alpha = self.consume_challenge("alpha");
+        let beta = self.consume_challenge("beta");
+        let output_chi_eval = self.consume_chi_evaluation("output_chi_eval");
+        let g_in_fold = self.fold(&alpha, &beta, &input_eval.column_evals);
+        let g_out_fold = self.fold(&alpha, &beta, &distinct_column_evals);
+        self.lines.push(format!(
+            "verify_group_by(builder_ptr, {g_in_fold}, {g_out_fold}, {}, {count_eval}, {input_chi_eval}, {output_chi_eval})",
+            mul(input_chi_eval, input_chi_eval)
+        ));
+        let key_eval = self.ascending_sort_key(&distinct_column_evals);
+        self.verify_monotonic((&alpha, &beta), &key_eval, &output_chi_eval, true);
+        self.require_nonnegative(
+            &sub(&count_eval, &output_chi_eval),
+            &output_chi_eval,
+            "ERR_DISTINCT_CHECK_FAILED",
+        );
+        Ok(YulTableEvaluation {
+            column_evals: distinct_column_evals,
+            chi_eval: output_chi_eval,
+        })
+    }
+
+    /// Mirrors `GroupByExec::verifier_evaluate`.
+    fn verify_group_by(
+        &mut self,
+        group_by_exec: &GroupByExec,
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval_map: &IndexMap<TableRef, String>,
+        is_root: bool,
+    ) -> Result<YulTableEvaluation, SolidityVerifierError> {
+        let proves_unique_groups = group_by_exec.proves_unique_groups();
+        if !is_root && !proves_unique_groups {
+            return Err(SolidityVerifierError::UnverifiablePlan {
+                error: "GroupByExec with group by columns that can not be folded into a sort key is only supported at top level of query plan.",
+            });
+        }
+        let input_eval = self.verify_plan(&group_by_exec.input, accessor, chi_eval_map, false)?;
+        self.comment("group by");
+        let input_chi_eval = &input_eval.chi_eval;
+        let current_accessor = input_accessor(&group_by_exec.input, &input_eval)?;
+        let where_eval = self.verify_expr(
+            &group_by_exec.where_clause,
+            &current_accessor,
+            input_chi_eval,
+        )?;
+        let group_by_evals = self.verify_aliased_exprs(
+            &group_by_exec.group_by_exprs,
+            &current_accessor,
+            input_chi_eval,
+        )?;
+        let aggregate_evals =
+            self.verify_aliased_exprs(&group_by_exec.sum_expr, &current_accessor, input_chi_eval)?;
+        let extremum_evals = self.verify_aliased_exprs(
+            &[
+                group_by_exec.max_expr.as_slice(),
+                group_by_exec.min_expr.as_slice(),
+            ]
+            .concat(),
+            &current_accessor,
+            input_chi_eval,
+        )?;
+        let group_by_result_columns_evals = self.consume_final_round_mles(
+            "group_by_result_column_eval",
+            group_by_exec.group_by_exprs.len(),
+        );
+        let sum_result_columns_evals =
+            self.consume_final_round_mles("sum_result_column_eval", group_by_exec.sum_expr.len());
+        let extremum_result_columns_evals =
+            self.consume_final_round_mles("extremum_result_column_eval", extremum_evals.len());
+        let count_column_eval = self.consume_final_round_mle("count_column_eval");
+        let alpha = self.consume_challenge("alpha");
+        let beta = self.consume_challenge("beta");
+        let output_chi_eval = self.consume_chi_evaluation("output_chi_eval");
+        let (expanded_extremum_evals, is_extremum_evals): (Vec<_>, Vec<_>) = extremum_evals
+            .iter()
+            .map(|_| {
+                (
+                    self.consume_final_round_mle("expanded_extremum_eval"),
+                    self.consume_final_round_mle("is_extremum_eval"),
+                )
+            })
+            .unzip();
+
+        // Each extremum is an additional group by key, and each group has exactly one row attaining it.
+        let g_in_fold = self.fold(
+            &alpha,
+            &beta,
+            &[group_by_evals, expanded_extremum_evals.clone()].concat(),
+        );
+        let g_out_fold = self.fold(
+            &alpha,
+            &beta,
+            &[
+                group_by_result_columns_evals.clone(),
+                extremum_result_columns_evals.clone(),
+            ]
+            .concat(),
+        );
+        let sum_in_fold = self.fold(
+            &beta,
+            &beta,
+            &[aggregate_evals, is_extremum_evals.clone()].concat(),
+        );
+        let weighted_sum_in_fold = self.bind(
+            "weighted_sum_in_fold",
+            &mul(&where_eval, &add(input_chi_eval, &sum_in_fold)),
+        );
+        let sum_out_evals: Vec<_> = sum_result_columns_evals
+            .iter()
+            .cloned()
+            .chain(iter::repeat(output_chi_eval.clone()).take(extremum_evals.len()))
+            .collect();
+        let sum_out_fold = self.fold(&beta, &beta, &sum_out_evals);
+        self.lines.push(format!(
+            "verify_group_by(builder_ptr, {g_in_fold}, {g_out_fold}, {weighted_sum_in_fold}, {}, {input_chi_eval}, {output_chi_eval})",
+            add(&count_column_eval, &sum_out_fold)
+        ));
+        for (i, value_eval) in extremum_evals.iter().enumerate() {
+            let sign = if i < group_by_exec.max_expr.len() {
+                BNScalar::ONE
+            } else {
+                -BNScalar::ONE
+            };
+            self.lines.push(format!(
+                "verify_extremum(builder_ptr, {value_eval}, {}, {}, {where_eval}, {}, {input_chi_eval})",
+                expanded_extremum_evals[i],
+                is_extremum_evals[i],
+                scalar_literal(sign)
+            ));
+        }
+        if proves_unique_groups {
+            let key_eval = self.ascending_sort_key(&group_by_result_columns_evals);
+            self.verify_monotonic((&alpha, &beta), &key_eval, &output_chi_eval, true);
+        }
+        Ok(YulTableEvaluation {
+            column_evals: group_by_result_columns_evals
+                .into_iter()
+                .chain(sum_result_columns_evals)
+                .chain(extremum_result_columns_evals)
+                .chain(iter::once(count_column_eval))
+                .collect(),
+            chi_eval: output_chi_eval,
+        })
+    }
+
+    /// Mirrors `SortMergeJoinExec::verifier_evaluate`.
+    #[allow(clippy::too_many_lines)]
+    fn verify_sort_merge_join(
+        &mut self,
+        join_exec: &SortMergeJoinExec,
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval_map: &IndexMap<TableRef, String>,
+    ) -> Result<YulTableEvaluation, SolidityVerifierError> {
+        let left_join_column_indexes = &join_exec.left_join_column_indexes;
+        let right_join_column_indexes = &join_exec.right_join_column_indexes;
+        verify_join_column_types(
+            &join_exec.left.get_column_result_fields(),
+            &join_exec.right.get_column_result_fields(),
+            left_join_column_indexes,
+            right_join_column_indexes,
+        )
+        .map_err(unverifiable_plan)?;
+        let left_eval = self.verify_plan(&join_exec.left, accessor, chi_eval_map, false)?;
+        let right_eval = self.verify_plan(&join_exec.right, accessor, chi_eval_map, false)?;
+        self.comment("sort merge join");
+        let left_chi_eval = &left_eval.chi_eval;
+        let right_chi_eval = &right_eval.chi_eval;
+        let res_chi_eval = self.consume_chi_evaluation("res_chi_eval");
+        let u_chi_eval = self.consume_chi_evaluation("u_chi_eval");
+        let left_rho_eval = self.consume_rho_evaluation("left_rho_eval");
+        let right_rho_eval = self.consume_rho_evaluation("right_rho_eval");
+        let alpha = self.consume_challenge("alpha");
+        let beta = self.consume_challenge("beta");
+        let challenges = (alpha.as_str(), beta.as_str());
+        let num_columns_left = left_eval.column_evals.len();
+        let num_columns_right = right_eval.column_evals.len();
+        let num_columns_u = left_join_column_indexes.len();
+        let left_hat_column_evals = [left_eval.column_evals.as_slice(), &[left_rho_eval]].concat();
+        let right_hat_column_evals =
+            [right_eval.column_evals.as_slice(), &[right_rho_eval]].concat();
+        let num_columns_res_hat = num_columns_left + num_columns_right - num_columns_u + 2;
+        let res_hat_column_evals =
+            self.consume_final_round_mles("res_hat_column_eval", num_columns_res_hat);
+        // The rho columns of the result, folded into a single strictly increasing column
+        let i_eval = self.bind(
+            "i_eval",
+            &add(
+                &mul(TWO_POW_64, &res_hat_column_evals[num_columns_left]),
+                &res_hat_column_evals[num_columns_res_hat - 1],
+            ),
+        );
+        let u_column_evals = self.consume_first_round_mles("u_column_eval", num_columns_u);
+        let u_fold_eval = self.ascending_sort_key(&u_column_evals);
+        let hat_left_column_evals = select(
+            &left_hat_column_evals,
+            &hat_column_indexes(left_join_column_indexes, num_columns_left),
+        );
+        let hat_right_column_evals = select(
+            &right_hat_column_evals,
+            &hat_column_indexes(right_join_column_indexes, num_columns_right),
+        );
+        let res_left_column_evals = res_hat_column_evals[..=num_columns_left].to_vec();
+        let res_right_column_evals: Vec<_> = (0..num_columns_u)
+            .chain(num_columns_left + 1..num_columns_res_hat)
+            .map(|i| res_hat_column_evals[i].clone())
+            .collect();
+        self.verify_membership_check(
+            challenges,
+            (left_chi_eval, &res_chi_eval),
+            &hat_left_column_evals,
+            &res_left_column_evals,
+        );
+        self.verify_membership_check(
+            challenges,
+            (right_chi_eval, &res_chi_eval),
+            &hat_right_column_evals,
+            &res_right_column_evals,
+        );
+        let w_l_eval = self.verify_membership_check(
+            challenges,
+            (&u_chi_eval, left_chi_eval),
+            &u_column_evals,
+            &select(&left_hat_column_evals, left_join_column_indexes),
+        );
+        let w_r_eval = self.verify_membership_check(
+            challenges,
+            (&u_chi_eval, right_chi_eval),
+            &u_column_evals,
+            &select(&right_hat_column_evals, right_join_column_indexes),
+        );
+        self.verify_monotonic(challenges, &i_eval, &res_chi_eval, true);
+        self.verify_monotonic(challenges, &u_fold_eval, &u_chi_eval, true);
+        self.produce_zerosum_constraint(&sub(&mul(&w_l_eval, &w_r_eval), &res_chi_eval), 2);
+        // Drop the rho columns of the result
+        let res_column_evals: Vec<_> = (0..num_columns_left)
+            .chain(num_columns_left + 1..num_columns_left + 1 + num_columns_right - num_columns_u)
+            .map(|i| res_hat_column_evals[i].clone())
+            .collect();
+        let join_type = join_exec.join_type;
+        if !join_type.preserves_left() && !join_type.preserves_right() {
+            return Ok(YulTableEvaluation {
+                column_evals: res_column_evals,
+                chi_eval: res_chi_eval,
+            });
+        }
+
+        // The matched rows and the unmatched rows of the preserved inputs, padded to the output schema
+        let num_left_other_columns = num_columns_left - num_columns_u;
+        let num_right_other_columns = num_columns_right - num_columns_u;
+        let part_evals = |join_evals: &[String],
+                          left_other_evals: Option<&[String]>,
+                          right_other_evals: Option<&[String]>,
+                          chi_eval: &str| {
+            let padded = |evals: Option<&[String]>, num_columns: usize| {
+                evals.map_or_else(|| vec!["0".to_string(); num_columns], <[String]>::to_vec)
+            };
+            let presence = |evals: Option<&[String]>, num_columns: usize| {
+                vec![if evals.is_some() { chi_eval } else { "0" }.to_string(); num_columns]
+            };
+            let mut evals = join_evals.to_vec();
+            evals.extend(padded(left_other_evals, num_left_other_columns));
+            evals.extend(padded(right_other_evals, num_right_other_columns));
+            if join_type.preserves_right() {
+                evals.extend(presence(left_other_evals, num_left_other_columns));
+            }
+            if join_type.preserves_left() {
+                evals.extend(presence(right_other_evals, num_right_other_columns));
+            }
+            evals
+        };
+        let mut parts = vec![(
+            part_evals(
+                &res_column_evals[..num_columns_u],
+                Some(&res_column_evals[num_columns_u..num_columns_left]),
+                Some(&res_column_evals[num_columns_left..]),
+                &res_chi_eval,
+            ),
+            res_chi_eval.clone(),
+        )];
+        if join_type.preserves_left() {
+            let (unmatched_evals, unmatched_chi_eval) = self.verify_rows_by_match(
+                challenges,
+                (left_chi_eval, &u_chi_eval),
+                (&left_hat_column_evals, left_join_column_indexes),
+                &u_column_evals,
+                (&w_l_eval, &w_r_eval),
+                false,
+            );
+            let join_evals = select(&unmatched_evals, left_join_column_indexes);
+            let other_evals = select(
+                &unmatched_evals,
+                &other_column_indexes(left_join_column_indexes, num_columns_left),
+            );
+            parts.push((
+                part_evals(&join_evals, Some(&other_evals), None, &unmatched_chi_eval),
+                unmatched_chi_eval,
+            ));
+        }
+        if join_type.preserves_right() {
+            let (unmatched_evals, unmatched_chi_eval) = self.verify_rows_by_match(
+                challenges,
+                (right_chi_eval, &u_chi_eval),
+                (&right_hat_column_evals, right_join_column_indexes),
+                &u_column_evals,
+                (&w_r_eval, &w_l_eval),
+                false,
+            );
+            let join_evals = select(&unmatched_evals, right_join_column_indexes);
+            let other_evals = select(
+                &unmatched_evals,
+                &other_column_indexes(right_join_column_indexes, num_columns_right),
+            );
+            parts.push((
+                part_evals(&join_evals, None, Some(&other_evals), &unmatched_chi_eval),
+                unmatched_chi_eval,
+            ));
+        }
+        let output_column_evals =
+            self.consume_final_round_mles("output_column_eval", parts[0].0.len());
+        let output_chi_eval = self.consume_chi_evaluation("output_chi_eval");
+        let parts: Vec<_> = parts
+            .iter()
+            .map(|(evals, chi_eval)| (evals.as_slice(), chi_eval.as_str()))
+            .collect();
+        self.verify_union(challenges, &parts, &output_column_evals, &output_chi_eval);
+        Ok(YulTableEvaluation {
+            column_evals: output_column_evals,
+            chi_eval: output_chi_eval,
+        })
+    }
+
+    /// Mirrors `SemiJoinExec::verifier_evaluate`.
+    fn verify_semi_join(
+        &mut self,
+        semi_join_exec: &SemiJoinExec,
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval_map: &IndexMap<TableRef, String>,
+    ) -> Result<YulTableEvaluation, SolidityVerifierError> {
+        let left_join_column_indexes = &semi_join_exec.left_join_column_indexes;
+        verify_join_column_types(
+            &semi_join_exec.left.get_column_result_fields(),
+            &semi_join_exec.right.get_column_result_fields(),
+            left_join_column_indexes,
+            &semi_join_exec.right_join_column_indexes,
+        )
+        .map_err(unverifiable_plan)?;
+        let left_eval = self.verify_plan(&semi_join_exec.left, accessor, chi_eval_map, false)?;
+        let right_eval = self.verify_plan(&semi_join_exec.right, accessor, chi_eval_map, false)?;
+        self.comment(if semi_join_exec.is_anti_join {
+            "anti join"
+        } else {
+            "semi join"
+        });
+        let u_chi_eval = self.consume_chi_evaluation("u_chi_eval");
+        let left_rho_eval = self.consume_rho_evaluation("left_rho_eval");
+        let alpha = self.consume_challenge("alpha");
+        let beta = self.consume_challenge("beta");
+        let challenges = (alpha.as_str(), beta.as_str());
+        let u_column_evals =
+            self.consume_first_round_mles("u_column_eval", left_join_column_indexes.len());
+        let u_fold_eval = self.ascending_sort_key(&u_column_evals);
+        let w_l_eval = self.verify_membership_check(
+            challenges,
+            (&u_chi_eval, &left_eval.chi_eval),
+            &u_column_evals,
+            &select(&left_eval.column_evals, left_join_column_indexes),
+        );
+        let w_r_eval = self.verify_membership_check(
+            challenges,
+            (&u_chi_eval, &right_eval.chi_eval),
+            &u_column_evals,
+            &select(
+                &right_eval.column_evals,
+                &semi_join_exec.right_join_column_indexes,
+            ),
+        );
+        self.verify_monotonic(challenges, &u_fold_eval, &u_chi_eval, true);
+        let num_columns_left = left_eval.column_evals.len();
+        let left_hat_column_evals = [left_eval.column_evals.as_slice(), &[left_rho_eval]].concat();
+        let (res_hat_column_evals, res_chi_eval) = self.verify_rows_by_match(
+            challenges,
+            (&left_eval.chi_eval, &u_chi_eval),
+            (&left_hat_column_evals, left_join_column_indexes),
+            &u_column_evals,
+            (&w_l_eval, &w_r_eval),
+            !semi_join_exec.is_anti_join,
+        );
+        // Drop the rho column of the result
+        Ok(YulTableEvaluation {
+            column_evals: res_hat_column_evals[..num_columns_left].to_vec(),
+            chi_eval: res_chi_eval,
+        })
+    }
+
+    /// Mirrors `verify_filter`.
+    fn verify_filter(
+        &mut self,
+        (alpha, beta): (&str, &str),
+        (chi_n_eval, chi_m_eval): (&str, &str),
+        c_evals: &[String],
+        s_eval: &str,
+        d_evals: &[String],
+    ) {
+        let c_fold = self.fold(alpha, beta, c_evals);
+        let d_fold = self.fold(alpha, beta, d_evals);
+        self.lines.push(format!(
+            "verify_filter(builder_ptr, {c_fold}, {d_fold}, {chi_n_eval}, {chi_m_eval}, {s_eval})"
+        ));
+    }
+
+    /// Mirrors `verify_membership_check`, returning the multiplicity evaluation.
+    fn verify_membership_check(
+        &mut self,
+        (alpha, beta): (&str, &str),
+        (chi_n_eval, chi_m_eval): (&str, &str),
+        column_evals: &[String],
+        candidate_evals: &[String],
+    ) -> String {
+        let c_fold = self.fold(alpha, beta, column_evals);
+        let d_fold = self.fold(alpha, beta, candidate_evals);
+        self.bind(
+            "multiplicity_eval",
+            &format!(
+                "verify_membership_check(builder_ptr, {c_fold}, {d_fold}, {chi_n_eval}, {chi_m_eval})"
+            ),
+        )
+    }
+
+    /// Mirrors `verify_monotonic` for an ascending column.
+    fn verify_monotonic(
+        &mut self,
+        (alpha, beta): (&str, &str),
+        column_eval: &str,
+        chi_eval: &str,
+        strict: bool,
+    ) {
+        self.lines.push(format!(
+            "verify_monotonic(builder_ptr, {alpha}, {beta}, {column_eval}, {chi_eval}, {})",
+            u8::from(strict)
+        ));
+    }
+
+    /// Mirrors `verify_union`, where each part is its column evaluations and its chi evaluation.
+    fn verify_union(
+        &mut self,
+        (gamma, beta): (&str, &str),
+        parts: &[(&[String], &str)],
+        output_evals: &[String],
+        chi_m_eval: &str,
+    ) {
+        let c_star_sum_eval = parts
+            .iter()
+            .fold(None, |sum: Option<String>, &(input_evals, chi_n_eval)| {
+                let c_fold = self.fold(gamma, beta, input_evals);
+                let c_star_eval = self.bind(
+                    "c_star_eval",
+                    &format!("verify_union_input(builder_ptr, {c_fold}, {chi_n_eval})"),
+                );
+                Some(match sum {
+                    None => c_star_eval,
+                    Some(sum) => self.bind("c_star_sum_eval", &add(&sum, &c_star_eval)),
+                })
+            })
+            .unwrap_or_else(|| "0".to_string());
+        let d_fold = self.fold(gamma, beta, output_evals);
+        self.lines.push(format!(
+            "verify_union_output(builder_ptr, {d_fold}, {chi_m_eval}, {c_star_sum_eval})"
+        ));
+    }
+
+    /// Mirrors `verify_rows_by_match`, returning the selected rows of `\hat{L}` or `\hat{R}` and their chi evaluation.
+    ///
+    /// The multiplicities are those of `U` in the input and in the other input.
+    fn verify_rows_by_match(
+        &mut self,
+        challenges: (&str, &str),
+        (chi_hat_eval, u_chi_eval): (&str, &str),
+        (hat_column_evals, join_column_indexes): (&[String], &[usize]),
+        u_column_evals: &[String],
+        (w_eval, w_other_eval): (&str, &str),
+        matched: bool,
+    ) -> (Vec<String>, String) {
+        let chi_selected_eval = self.consume_chi_evaluation("chi_selected_eval");
+        let z_eval = self.consume_final_round_mle("z_eval");
+        let w_other_inv_eval = self.consume_final_round_mle("w_other_inv_eval");
+        // z * w_other = 0
+        self.produce_identity_constraint(&mul(&z_eval, w_other_eval), 2);
+        // w_other * w_other_inv + z - chi_u = 0
+        self.produce_identity_constraint(
+            &sub(
+                &add(&mul(w_other_eval, &w_other_inv_eval), &z_eval),
+                u_chi_eval,
+            ),
+            2,
+        );
+        let selected_u_eval = if matched {
+            self.bind("selected_u_eval", &sub(u_chi_eval, &z_eval))
+        } else {
+            z_eval
+        };
+        let selected_hat_column_evals =
+            self.consume_final_round_mles("selected_hat_column_eval", hat_column_evals.len());
+        self.verify_membership_check(
+            challenges,
+            (chi_hat_eval, &chi_selected_eval),
+            hat_column_evals,
+            &selected_hat_column_evals,
+        );
+        let v_eval = self.verify_membership_check(
+            challenges,
+            (u_chi_eval, &chi_selected_eval),
+            u_column_evals,
+            &select(&selected_hat_column_evals, join_column_indexes),
+        );
+        // v * (chi_u - selected_u) = 0
+        self.produce_identity_constraint(&mul(&v_eval, &sub(u_chi_eval, &selected_u_eval)), 2);
+        let rho_eval = &selected_hat_column_evals[selected_hat_column_evals.len() - 1];
+        self.verify_monotonic(challenges, rho_eval, &chi_selected_eval, true);
+        // sum w * selected_u - chi_selected = 0
+        self.produce_zerosum_constraint(
+            &sub(&mul(w_eval, &selected_u_eval), &chi_selected_eval),
+            2,
+        );
+        (selected_hat_column_evals, chi_selected_eval)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn verify_expr(
+        &mut self,
+        expr: &DynProofExpr,
+        accessor: &IndexMap<ColumnRef, String>,
+        chi_eval: &str,
+    ) -> Result<String, SolidityVerifierError> {
+        match expr {
+            DynProofExpr::Column(column_expr) => accessor
+                .get(&column_expr.column_ref)
+                .cloned()
+                .ok_or(SolidityVerifierError::ColumnNotFound),
+            DynProofExpr::Literal(literal_expr) => Ok(self.bind(
+                "literal_eval",
+                &mul(
+                    &scalar_literal(literal_expr.value.to_scalar::<BNScalar>()),
+                    chi_eval,
+                ),
+            )),
+            DynProofExpr::Equals(equals_expr) => {
+                let lhs_eval = self.verify_expr(&equals_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&equals_expr.rhs, accessor, chi_eval)?;
+                let diff_eval = self.bind(
+                    "diff_eval",
+                    &scale_and_add_subtract(
+                        (&lhs_eval, scale(&equals_expr.lhs)),
+                        (&rhs_eval, scale(&equals_expr.rhs)),
+                        true,
+                    ),
+                );
+                Ok(self.verify_equals_zero(&diff_eval, chi_eval))
+            }
+            DynProofExpr::Inequality(inequality_expr) => {
+                let lhs = (
+                    self.verify_expr(&inequality_expr.lhs, accessor, chi_eval)?,
+                    scale(&inequality_expr.lhs),
+                );
+                let rhs = (
+                    self.verify_expr(&inequality_expr.rhs, accessor, chi_eval)?,
+                    scale(&inequality_expr.rhs),
+                );
+                let (minuend, subtrahend) = if inequality_expr.is_lt {
+                    (lhs, rhs)
+                } else {
+                    (rhs, lhs)
+                };
+                let diff_eval = self.bind(
+                    "diff_eval",
+                    &scale_and_add_subtract(
+                        (&minuend.0, minuend.1),
+                        (&subtrahend.0, subtrahend.1),
+                        true,
+                    ),
+                );
+                Ok(self.verify_sign(&diff_eval, chi_eval))
+            }
+            DynProofExpr::And(and_expr) => {
+                let lhs_eval = self.verify_expr(&and_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&and_expr.rhs, accessor, chi_eval)?;
+                Ok(self.verify_product(&lhs_eval, &rhs_eval))
+            }
+            DynProofExpr::Or(or_expr) => {
+                let lhs_eval = self.verify_expr(&or_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&or_expr.rhs, accessor, chi_eval)?;
+                Ok(self.verify_or(&lhs_eval, &rhs_eval))
+            }
+            DynProofExpr::Not(not_expr) => {
+                let eval = self.verify_expr(&not_expr.expr, accessor, chi_eval)?;
+                Ok(self.bind("not_eval", &sub(chi_eval, &eval)))
+            }
+            DynProofExpr::AddSubtract(add_subtract_expr) => {
+                let lhs_eval = self.verify_expr(&add_subtract_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&add_subtract_expr.rhs, accessor, chi_eval)?;
+                let name = if add_subtract_expr.is_subtract {
+                    "difference_eval"
+                } else {
+                    "sum_eval"
+                };
+                Ok(self.bind(
+                    name,
+                    &scale_and_add_subtract(
+                        (&lhs_eval, scale(&add_subtract_expr.lhs)),
+                        (&rhs_eval, scale(&add_subtract_expr.rhs)),
+                        add_subtract_expr.is_subtract,
+                    ),
+                ))
+            }
+            DynProofExpr::Multiply(multiply_expr) => {
+                let lhs_eval = self.verify_expr(&multiply_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&multiply_expr.rhs, accessor, chi_eval)?;
+                Ok(self.verify_product(&lhs_eval, &rhs_eval))
+            }
+            DynProofExpr::InList(in_list_expr) => {
+                let eval = self.verify_expr(&in_list_expr.expr, accessor, chi_eval)?;
+                let eval = match in_list_expr.expr_scaling_exponent().unsigned_abs() {
+                    0 => eval,
+                    exponent => self.bind("scaled_eval", &scale_by(&eval, exponent)),
+                };
+                // product - previous * (x - l), starting from chi
+                let mut product_eval = chi_eval.to_string();
+                for value in in_list_expr.scaled_values::<BNScalar>() {
+                    let previous_eval = product_eval;
+                    product_eval = self.consume_final_round_mle("product_eval");
+                    let factor_eval = sub(&eval, &scalar_literal(value));
+                    self.produce_identity_constraint(
+                        &sub(&product_eval, &mul(&previous_eval, &factor_eval)),
+                        2,
+                    );
+                }
+                Ok(self.verify_equals_zero(&product_eval, chi_eval))
+            }
+            DynProofExpr::Between(between_expr) => {
+                let eval = self.verify_expr(&between_expr.expr, accessor, chi_eval)?;
+                let low_eval = self.verify_expr(&between_expr.low, accessor, chi_eval)?;
+                let high_eval = self.verify_expr(&between_expr.high, accessor, chi_eval)?;
+                let (scale, low_scale, high_scale) = between_expr.scales();
+                let below_low_eval = self.bind(
+                    "below_low_eval",
+                    &scale_and_add_subtract((&eval, scale), (&low_eval, low_scale), true),
+                );
+                let is_below_low_eval = self.verify_sign(&below_low_eval, chi_eval);
+                let above_high_eval = self.bind(
+                    "above_high_eval",
+                    &scale_and_add_subtract((&high_eval, high_scale), (&eval, scale), true),
+                );
+                let is_above_high_eval = self.verify_sign(&above_high_eval, chi_eval);
+                let is_outside_eval = self.verify_or(&is_below_low_eval, &is_above_high_eval);
+                Ok(self.bind("between_eval", &sub(chi_eval, &is_outside_eval)))
+            }
+            DynProofExpr::Divide(divide_expr) => {
+                let lhs_eval = self.verify_expr(&divide_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&divide_expr.rhs, accessor, chi_eval)?;
+                let (lhs_exponent, rhs_exponent) = divide_expr.scaling_exponents();
+                let (quotient_eval, _) = self.verify_divide_and_modulo(
+                    &scale_by(&lhs_eval, lhs_exponent.unsigned_abs()),
+                    &scale_by(&rhs_eval, rhs_exponent.unsigned_abs()),
+                    chi_eval,
+                );
+                self.verify_range(
+                    &quotient_eval,
+                    divide_expr.range_check_bounds::<BNScalar>(),
+                    chi_eval,
+                );
+                Ok(quotient_eval)
+            }
+            DynProofExpr::Modulo(modulo_expr) => {
+                let lhs_eval = self.verify_expr(&modulo_expr.lhs, accessor, chi_eval)?;
+                let rhs_eval = self.verify_expr(&modulo_expr.rhs, accessor, chi_eval)?;
+                let (lhs_exponent, rhs_exponent) = modulo_expr.scaling_exponents();
+                let (_, remainder_eval) = self.verify_divide_and_modulo(
+                    &scale_by(&lhs_eval, lhs_exponent.unsigned_abs()),
+                    &scale_by(&rhs_eval, rhs_exponent.unsigned_abs()),
+                    chi_eval,
+                );
+                Ok(remainder_eval)
+            }
+            DynProofExpr::Cast(cast_expr) => {
+                let eval = self.verify_expr(&cast_expr.from_expr, accessor, chi_eval)?;
+                let exponent = cast_expr.scaling_exponent();
+                let eval = match exponent.unsigned_abs() {
+                    0 => eval,
+                    exponent_abs if exponent > 0 => {
+                        self.bind("cast_eval", &scale_by(&eval, exponent_abs))
+                    }
+                    _ => cast_expr
+                        .division_exponents()
+                        .fold(eval, |eval, division_exponent| {
+                            self.verify_divide_and_modulo(
+                                &eval,
+                                &scale_by(chi_eval, division_exponent),
+                                chi_eval,
+                            )
+                            .0
+                        }),
+                };
+                self.verify_range(&eval, cast_expr.range_check_bounds::<BNScalar>(), chi_eval);
+                Ok(eval)
+            }
+            DynProofExpr::Case(case_expr) => {
+                let mut condition_evals = Vec::with_capacity(case_expr.when_then.len());
+                let mut result_evals = Vec::with_capacity(case_expr.when_then.len());
+                for (condition, result) in &case_expr.when_then {
+                    condition_evals.push(self.verify_expr(condition, accessor, chi_eval)?);
+                    result_evals.push(self.verify_expr(result, accessor, chi_eval)?);
+                }
+                let else_eval = self.verify_expr(&case_expr.else_expr, accessor, chi_eval)?;
+                let mut selector_evals = Vec::with_capacity(condition_evals.len());
+                let mut previous_selectors_eval = "0".to_string();
+                for condition_eval in &condition_evals {
+                    let selector_eval = self.consume_final_round_mle("selector_eval");
+                    // s_i - c_i + c_i * (s_1 + ... + s_{i-1}) = 0
+                    self.produce_identity_constraint(
+                        &add(
+                            &sub(&selector_eval, condition_eval),
+                            &mul(condition_eval, &previous_selectors_eval),
+                        ),
+                        2,
+                    );
+                    previous_selectors_eval = self.bind(
+                        "previous_selectors_eval",
+                        &add(&previous_selectors_eval, &selector_eval),
+                    );
+                    selector_evals.push(selector_eval);
+                }
+                let res_eval = self.consume_final_round_mle("case_eval");
+                let selected_results_eval = selector_evals.iter().zip(&result_evals).fold(
+                    "0".to_string(),
+                    |sum, (selector_eval, result_eval)| {
+                        self.bind(
+                            "selected_results_eval",
+                            &add(&sum, &mul(selector_eval, &sub(result_eval, &else_eval))),
+                        )
+                    },
+                );
+                // res - else - sum s_i * (v_i - else) = 0
+                self.produce_identity_constraint(
+                    &sub(&sub(&res_eval, &else_eval), &selected_results_eval),
+                    2,
+                );
+                Ok(res_eval)
+            }
+            DynProofExpr::DateTrunc(date_trunc_expr) => {
+                let eval = self.verify_expr(&date_trunc_expr.expr, accessor, chi_eval)?;
+                let Some(division) =
+                    timestamp_field_division(date_trunc_expr.field, date_trunc_expr.data_type())
+                else {
+                    return Ok(eval);
+                };
+                let (quotient_eval, _) = self.verify_floor_division(division, &eval, chi_eval);
+                Ok(self.bind(
+                    "truncated_eval",
+                    &add(
+                        &mul(
+                            &quotient_eval,
+                            &scalar_literal(BNScalar::from(division.length())),
+                        ),
+                        &mul(chi_eval, &scalar_literal(BNScalar::from(division.shift()))),
+                    ),
+                ))
+            }
+            DynProofExpr::Extract(extract_expr) => {
+                let eval = self.verify_expr(&extract_expr.expr, accessor, chi_eval)?;
+                let (division, period_division) = extract_expr.divisions();
+                let (quotient_eval, _) = self.verify_floor_division(division, &eval, chi_eval);
+                Ok(match period_division {
+                    Some(period_division) => {
+                        self.verify_floor_division(period_division, &quotient_eval, chi_eval)
+                            .1
+                    }
+                    None => quotient_eval,
+                })
+            }
+            DynProofExpr::Aggregate(aggregate_expr) => {
+                self.verify_expr(&aggregate_expr.expr, accessor, chi_eval)
+            }
+        }
+    }
+
+    fn verify_product(&mut self, lhs_eval: &str, rhs_eval: &str) -> String {
+        self.bind(
+            "product_eval",
+            &format!("verify_product(builder_ptr, {lhs_eval}, {rhs_eval})"),
+        )
+    }
+
+    /// Mirrors `verifier_evaluate_or`.
+    fn verify_or(&mut self, lhs_eval: &str, rhs_eval: &str) -> String {
+        let lhs_and_rhs_eval = self.verify_product(lhs_eval, rhs_eval);
+        self.bind("or_eval", &sub(&add(lhs_eval, rhs_eval), &lhs_and_rhs_eval))
+    }
+
+    /// Mirrors `verifier_evaluate_equals_zero`.
+    fn verify_equals_zero(&mut self, eval: &str, chi_eval: &str) -> String {
+        self.bind(
+            "equals_zero_eval",
+            &format!("verify_equals_zero(builder_ptr, {eval}, {chi_eval})"),
+        )
+    }
+
+    /// Mirrors `verifier_evaluate_sign`.
+    fn verify_sign(&mut self, eval: &str, chi_eval: &str) -> String {
+        self.bind(
+            "sign_eval",
+            &format!("verify_sign(builder_ptr, {eval}, {chi_eval})"),
+        )
+    }
+
+    /// Mirrors `verifier_evaluate_divide_and_modulo`, returning the quotient and the remainder.
+    fn verify_divide_and_modulo(
+        &mut self,
+        lhs_eval: &str,
+        rhs_eval: &str,
+        chi_eval: &str,
+    ) -> (String, String) {
+        self.bind_pair(
+            ("quotient_eval", "remainder_eval"),
+            &format!("verify_divide_and_modulo(builder_ptr, {lhs_eval}, {rhs_eval}, {chi_eval})"),
+        )
+    }
+
+    /// Mirrors `FloorDivision::verifier_evaluate`.
+    fn verify_floor_division(
+        &mut self,
+        division: FloorDivision,
+        eval: &str,
+        chi_eval: &str,
+    ) -> (String, String) {
+        let (offset, quotient_offset) = division.offset_and_quotient_offset();
+        let offset_eval = self.bind(
+            "offset_eval",
+            &add(
+                eval,
+                &mul(chi_eval, &scalar_literal(BNScalar::from(offset))),
+            ),
+        );
+        let (quotient_eval, remainder_eval) = self.verify_divide_and_modulo(
+            &offset_eval,
+            &mul(chi_eval, &scalar_literal(BNScalar::from(division.length()))),
+            chi_eval,
+        );
+        let quotient_eval = self.bind(
+            "floor_quotient_eval",
+            &sub(
+                &quotient_eval,
+                &mul(chi_eval, &scalar_literal(BNScalar::from(quotient_offset))),
+            ),
+        );
+        (quotient_eval, remainder_eval)
+    }
+
+    /// Checks the bounds of `range_check_bounds`, lower bound first.
+    fn verify_range(
+        &mut self,
+        eval: &str,
+        (lower, upper): (Option<BNScalar>, Option<BNScalar>),
+        chi_eval: &str,
+    ) {
+        if let Some(lower) = lower {
+            self.require_nonnegative(
+                &sub(eval, &mul(chi_eval, &scalar_literal(lower))),
+                chi_eval,
+                "ERR_OUT_OF_RANGE",
+            );
+        }
+        if let Some(upper) = upper {
+            self.require_nonnegative(
+                &sub(&mul(chi_eval, &scalar_literal(upper)), eval),
+                chi_eval,
+                "ERR_OUT_OF_RANGE",
+            );
+        }
+    }
+
+    /// Stores the result evaluations in a new memory array, prefixed with its length.
+    fn write_output(&mut self, evaluation: &YulTableEvaluation) {
+        self.comment("output");
+        self.lines
+            .push("evaluations_ptr := mload(FREE_PTR)".to_string());
+        self.lines.push(format!(
+            "mstore(evaluations_ptr, {})",
+            evaluation.column_evals.len()
+        ));
+        for (i, eval) in evaluation.column_evals.iter().enumerate() {
+            self.lines.push(format!(
+                "mstore(add(evaluations_ptr, {:#x}), {eval})",
+                32 * (i + 1)
+            ));
+        }
+        self.lines.push(format!(
+            "mstore(FREE_PTR, add(evaluations_ptr, {:#x}))",
+            32 * (evaluation.column_evals.len() + 1)
+        ));
+    }
+}
+
+/// The body of `verify_plan` for a plan, along with what the rest of the verifier needs to know about it.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct VerifyPlan {
+    /// The lines of the body
+    pub lines: Vec<String>,
+    /// The number of memory words that the body keeps its intermediate evaluations in
+    pub num_variables: usize,
+    /// The number of leading result columns that must be strictly increasing, if the order of the result is checked
+    pub num_ordered_result_columns: Option<usize>,
+}
+
+/// Generates the body of `verify_plan` for `plan`.
+///
+/// The column evaluations and the table chi evaluations of the builder are in the order of
+/// [`ProofPlan::get_column_references`] and [`ProofPlan::get_table_references`].
+///
+/// # Errors
+/// Returns an error if the verifier of the plan rejects it regardless of the proof,
+/// or if the plan references a column or a table that it does not read.
+pub(super) fn generate_verify_plan(
+    plan: &DynProofPlan,
+) -> Result<VerifyPlan, SolidityVerifierError> {
+    let accessor: IndexMap<ColumnRef, String> = plan
+        .get_column_references()
+        .into_iter()
+        .enumerate()
+        .map(|(i, column_ref)| {
+            (
+                column_ref,
+                format!("builder_get_column_evaluation(builder_ptr, {i})"),
+            )
+        })
+        .collect();
+    let chi_eval_map: IndexMap<TableRef, String> = plan
+        .get_table_references()
+        .into_iter()
+        .enumerate()
+        .map(|(i, table_ref)| {
+            (
+                table_ref,
+                format!("builder_get_table_chi_evaluation(builder_ptr, {i})"),
+            )
+        })
+        .collect();
+    let mut codegen = PlanCodegen::default();
+    let evaluation = codegen.verify_plan(plan, &accessor, &chi_eval_map, true)?;
+    codegen.write_output(&evaluation);
+    // Only a group by at the root checks the order of the result, which must be strictly increasing in its groups
+    let num_ordered_result_columns = match plan {
+        DynProofPlan::GroupBy(group_by_exec) => Some(group_by_exec.group_by_exprs.len()),
+        _ => None,
+    };
+    Ok(VerifyPlan {
+        lines: codegen.lines,
+        num_variables: codegen.variable_count,
+        num_ordered_result_columns,
+    })
+}
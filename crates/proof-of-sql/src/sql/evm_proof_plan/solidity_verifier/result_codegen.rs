@@ -0,0 +1,128 @@
+//! Generation of the body of `read_result`, which reads the result columns with the types of the plan.
+//!
+//! Each column is encoded the way that `OwnedTable` serializes it: the name, the column type and the values.
+//! As with `OwnedTable::try_coerce_with_fields`, a column either has exactly the type of its field,
+//! or it is a scalar column whose values are coerced to the type of its field.
+//! So `read_result_column` is given the hashes of both encodings of the name and the type,
+//! along with how the values are read in each case.
+use super::{generator::word_literal, SolidityVerifierError};
+use crate::{
+    base::database::{ColumnField, ColumnType, OwnedColumn},
+    proof_primitive::hyperkzg::BNScalar,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+use tiny_keccak::{Hasher, Keccak};
+
+/// A boolean, encoded as a byte.
+const BOOLEAN_KIND: u64 = 0x00;
+/// An unsigned byte.
+const UINT8_KIND: u64 = 0x01;
+/// A string or a byte array, encoded as its length followed by its bytes.
+const VARBINARY_KIND: u64 = 0x02;
+/// A scalar, encoded as a big-endian word.
+const WORD_KIND: u64 = 0x03;
+/// A scalar that must fit into an unsigned byte.
+const WORD_UINT8_KIND: u64 = 0x04;
+
+/// A signed integer of `width` bytes, encoded as a big-endian integer of that width.
+const fn integer_kind(width: u64) -> u64 {
+    0x20 | width
+}
+
+/// A scalar that must fit into a signed integer of `width` bytes.
+const fn word_integer_kind(width: u64) -> u64 {
+    0x40 | width
+}
+
+/// The kind of the values of a column of `column_type`, and the kind of those of a scalar column coerced to it.
+fn kinds(column_type: ColumnType) -> (u64, Option<u64>) {
+    match column_type {
+        ColumnType::Boolean => (BOOLEAN_KIND, None),
+        ColumnType::Uint8 => (UINT8_KIND, Some(WORD_UINT8_KIND)),
+        ColumnType::TinyInt => (integer_kind(1), Some(word_integer_kind(1))),
+        ColumnType::SmallInt => (integer_kind(2), Some(word_integer_kind(2))),
+        ColumnType::Int => (integer_kind(4), Some(word_integer_kind(4))),
+        ColumnType::BigInt | ColumnType::TimestampTZ(_, _) => {
+            (integer_kind(8), Some(word_integer_kind(8)))
+        }
+        ColumnType::Int128 => (integer_kind(16), Some(word_integer_kind(16))),
+        ColumnType::VarChar | ColumnType::VarBinary => (VARBINARY_KIND, None),
+        ColumnType::Decimal75(_, _) | ColumnType::Scalar => (WORD_KIND, Some(WORD_KIND)),
+    }
+}
+
+fn empty_column(column_type: ColumnType) -> OwnedColumn<BNScalar> {
+    match column_type {
+        ColumnType::Boolean => OwnedColumn::Boolean(vec![]),
+        ColumnType::Uint8 => OwnedColumn::Uint8(vec![]),
+        ColumnType::TinyInt => OwnedColumn::TinyInt(vec![]),
+        ColumnType::SmallInt => OwnedColumn::SmallInt(vec![]),
+        ColumnType::Int => OwnedColumn::Int(vec![]),
+        ColumnType::BigInt => OwnedColumn::BigInt(vec![]),
+        ColumnType::Int128 => OwnedColumn::Int128(vec![]),
+        ColumnType::VarChar => OwnedColumn::VarChar(vec![]),
+        ColumnType::VarBinary => OwnedColumn::VarBinary(vec![]),
+        ColumnType::Decimal75(precision, scale) => OwnedColumn::Decimal75(precision, scale, vec![]),
+        ColumnType::Scalar => OwnedColumn::Scalar(vec![]),
+        ColumnType::TimestampTZ(time_unit, time_zone) => {
+            OwnedColumn::TimestampTZ(time_unit, time_zone, vec![])
+        }
+    }
+}
+
+/// The encoding of the name and the type of a column of the result, along with its keccak hash.
+fn header(
+    field: &ColumnField,
+    column: &OwnedColumn<BNScalar>,
+) -> Result<(u64, [u8; 32]), SolidityVerifierError> {
+    let mut bytes = bincode::serde::encode_to_vec(
+        (field.name(), column),
+        bincode::config::legacy()
+            .with_fixed_int_encoding()
+            .with_big_endian(),
+    )
+    .map_err(|_| SolidityVerifierError::PlanEncoding)?;
+    // Drop the number of values
+    bytes.truncate(bytes.len() - 8);
+    let length = u64::try_from(bytes.len())
+        .ok()
+        .filter(|&length| length <= 0xffff)
+        .ok_or(SolidityVerifierError::PlanEncoding)?;
+    let mut hash = [0; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(&bytes);
+    hasher.finalize(&mut hash);
+    Ok((length, hash))
+}
+
+/// Generates the body of `read_result` for the result columns `fields`.
+///
+/// # Errors
+/// Returns an error if the name of a column is too long to be read.
+pub(super) fn generate_read_result(
+    fields: &[ColumnField],
+) -> Result<Vec<String>, SolidityVerifierError> {
+    let mut lines = Vec::with_capacity(2 * fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let (exact_kind, scalar_kind) = kinds(field.data_type());
+        let (exact_length, exact_hash) = header(field, &empty_column(field.data_type()))?;
+        let (scalar_length, scalar_hash, scalar_kind) = match scalar_kind {
+            Some(scalar_kind) => {
+                let (length, hash) = header(field, &OwnedColumn::Scalar(vec![]))?;
+                (length, hash, scalar_kind)
+            }
+            None => (0, [0; 32], 0),
+        };
+        let spec = exact_length | (exact_kind << 16) | (scalar_length << 32) | (scalar_kind << 48);
+        lines.push(format!(
+            "column_ptr, ptr := read_result_column(ptr, end, {}, {}, {spec:#x})",
+            word_literal(&exact_hash),
+            word_literal(&scalar_hash),
+        ));
+        lines.push(format!(
+            "mstore(add(columns_ptr, {:#x}), column_ptr)",
+            32 * (i + 1)
+        ));
+    }
+    Ok(lines)
+}
@@ -0,0 +1,177 @@
+//! Extraction of the building blocks of the generated verifier from the `solidity/src` tree.
+//!
+//! This mirrors `solidity/scripts/preprocess_yul_imports.sh`, which resolves the `IMPORT-YUL` markers of that tree.
+use super::SolidityVerifierError;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+/// The Yul functions that the verifier calls without defining them, along with the source they are imported from.
+pub(super) const IMPORTS: [(&str, &str); 32] = [
+    ("base/Errors.sol", "err"),
+    ("base/Queue.pre.sol", "dequeue"),
+    ("base/Array.pre.sol", "get_array_element"),
+    ("base/MathUtil.sol", "log2_up"),
+    ("base/Transcript.sol", "append_calldata"),
+    ("base/Transcript.sol", "draw_challenge"),
+    ("base/Transcript.sol", "draw_challenges"),
+    (
+        "base/LagrangeBasisEvaluation.sol",
+        "compute_truncated_lagrange_basis_sum",
+    ),
+    (
+        "base/LagrangeBasisEvaluation.sol",
+        "compute_truncated_lagrange_basis_inner_product",
+    ),
+    ("base/ECPrecompiles.pre.sol", "ec_add"),
+    ("base/ECPrecompiles.pre.sol", "ec_mul"),
+    ("base/ECPrecompiles.pre.sol", "ec_mul_assign"),
+    ("base/ECPrecompiles.pre.sol", "calldata_ec_add_assign"),
+    ("base/ECPrecompiles.pre.sol", "calldata_ec_mul_add_assign"),
+    ("base/ECPrecompiles.pre.sol", "constant_ec_mul_add_assign"),
+    ("base/ECPrecompiles.pre.sol", "ec_add_assign"),
+    ("base/ECPrecompiles.pre.sol", "ec_pairing_x2"),
+    ("proof/Sumcheck.pre.sol", "verify_sumcheck_proof"),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_consume_challenge",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_consume_first_round_mle",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_consume_final_round_mle",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_consume_chi_evaluation",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_consume_rho_evaluation",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_produce_zerosum_constraint",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_produce_identity_constraint",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_get_column_evaluation",
+    ),
+    (
+        "proof/VerificationBuilder.pre.sol",
+        "builder_get_table_chi_evaluation",
+    ),
+    ("hyperkzg/HyperKZGHelpers.pre.sol", "run_transcript"),
+    ("hyperkzg/HyperKZGHelpers.pre.sol", "bivariate_evaluation"),
+    ("hyperkzg/HyperKZGHelpers.pre.sol", "check_v_consistency"),
+    (
+        "hyperkzg/HyperKZGHelpers.pre.sol",
+        "univariate_group_evaluation",
+    ),
+    ("hyperkzg/HyperKZGHelpers.pre.sol", "compute_gl_msm"),
+];
+
+/// Returns the block that starts at the first line satisfying `is_start`, up to and including the line that closes it.
+fn find_block<'a>(source: &'a str, is_start: impl Fn(&str) -> bool) -> Option<&'a str> {
+    let start = source
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let line_offset = *offset;
+            *offset += line.len();
+            Some((line_offset, line))
+        })
+        .find(|(_, line)| is_start(line))?
+        .0;
+    let mut depth = 0;
+    let mut has_opened = false;
+    let mut end = start;
+    for line in source[start..].split_inclusive('\n') {
+        end += line.len();
+        for c in line.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    has_opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if has_opened && depth == 0 {
+            return Some(source[start..end].trim_end_matches('\n'));
+        }
+    }
+    None
+}
+
+/// Finds the definition of the Yul function `name`.
+///
+/// As with the `IMPORT-YUL` markers, the first definition in the source is the one that is imported.
+pub(super) fn find_yul_function<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    find_block(source, |line| {
+        line.trim_start()
+            .strip_prefix("function")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .and_then(|rest| rest.trim_start().strip_prefix(name))
+            .is_some_and(|rest| rest.trim_start().starts_with('(') || rest.trim().is_empty())
+    })
+}
+
+/// Strips the license, the pragma and the imports from the start of a Solidity source.
+pub(super) fn strip_preamble(source: &str) -> &str {
+    let mut rest = source;
+    while let Some((line, remainder)) = rest.split_once('\n') {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with("// SPDX-License-Identifier")
+            || line.starts_with("// This is licensed")
+            || line.starts_with("pragma ")
+            || line.starts_with("import ")
+        {
+            rest = remainder;
+        } else {
+            break;
+        }
+    }
+    rest.trim_end()
+}
+
+/// Reads a source of `solidity/src`.
+pub(super) fn read_source(
+    read: &impl Fn(&str) -> Option<String>,
+    path: &str,
+) -> Result<String, SolidityVerifierError> {
+    read(path).ok_or_else(|| SolidityVerifierError::MissingSource {
+        path: path.to_string(),
+    })
+}
+
+/// Imports Yul functions, each preceded by a marker naming where it was imported from.
+pub(super) fn import_yul_functions(
+    read: &impl Fn(&str) -> Option<String>,
+    imports: &[(&str, &str)],
+) -> Result<String, SolidityVerifierError> {
+    imports
+        .iter()
+        .map(|&(path, name)| {
+            let source = read_source(read, path)?;
+            let function = find_yul_function(&source, name).ok_or_else(|| {
+                SolidityVerifierError::MissingDefinition {
+                    name: name.to_string(),
+                    path: path.to_string(),
+                }
+            })?;
+            Ok(format!(
+                "            // IMPORTED-YUL {path}::{name}\n{function}\n"
+            ))
+        })
+        .collect()
+}
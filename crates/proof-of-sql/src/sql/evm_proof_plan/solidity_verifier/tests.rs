@@ -0,0 +1,872 @@
+use super::{
+    generate_solidity_verifier,
+    generator::word_literal,
+    plan_codegen::generate_verify_plan,
+    sources::{find_yul_function, strip_preamble},
+    SolidityVerifierError,
+};
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnType, CommitmentAccessor, LiteralValue, MetadataAccessor,
+            OwnedTableTestAccessor, SchemaAccessor, TableRef, TestSchemaAccessor,
+        },
+        map::indexmap,
+        math::decimal::Precision,
+    },
+    proof_primitive::hyperkzg::{
+        HyperKZGCommitmentEvaluationProof, HyperKZGEngine, HyperKZGPublicSetup,
+    },
+    sql::{
+        evm_proof_plan::EVMProofPlan,
+        proof::{encode_evm_calldata, verify_evm_calldata, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan, JoinType},
+    },
+};
+use ark_bn254::G2Affine;
+use ark_ec::AffineRepr;
+use nova_snark::provider::hyperkzg::CommitmentKey;
+use proof_of_sql_parser::posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone};
+use rand::{rngs::StdRng, SeedableRng};
+use sqlparser::ast::Ident;
+use std::{
+    fs, iter,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Set this environment variable to rewrite the golden files instead of comparing against them.
+const UPDATE_GOLDEN_FILES: &str = "UPDATE_GOLDEN_FILES";
+
+fn read_solidity_source(path: &str) -> Option<String> {
+    let solidity_src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../solidity/src");
+    fs::read_to_string(solidity_src.join(path)).ok()
+}
+
+fn assert_matches_golden_file(plan: &DynProofPlan, name: &str) {
+    let generated = generate_verify_plan(plan).unwrap().lines.join("\n") + "\n";
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/sql/evm_proof_plan/solidity_verifier/golden")
+        .join(name);
+    if std::env::var_os(UPDATE_GOLDEN_FILES).is_some() {
+        fs::write(&path, generated).unwrap();
+    } else {
+        assert_eq!(generated, fs::read_to_string(&path).unwrap());
+    }
+}
+
+fn table_a_b() -> (TableRef, TestSchemaAccessor, DynProofPlan) {
+    let t = TableRef::new("sxt", "t");
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        t.clone() => indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+        },
+    });
+    let input = table_exec(
+        t.clone(),
+        vec![
+            column_field("a", ColumnType::BigInt),
+            column_field("b", ColumnType::BigInt),
+        ],
+    );
+    (t, accessor, input)
+}
+
+fn filter_plan() -> DynProofPlan {
+    let (t, accessor, input) = table_a_b();
+    filter_with_input(
+        vec![
+            aliased_plan(column(&t, "a", &accessor), "a"),
+            aliased_plan(
+                add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "c",
+            ),
+        ],
+        input,
+        or(
+            equal(column(&t, "b", &accessor), const_bigint(1)),
+            not(equal(column(&t, "a", &accessor), const_bigint(2))),
+        ),
+    )
+}
+
+fn projection_plan() -> DynProofPlan {
+    let (t, accessor, input) = table_a_b();
+    projection(
+        vec![
+            aliased_plan(
+                multiply(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "ab",
+            ),
+            aliased_plan(subtract(column(&t, "a", &accessor), const_bigint(3)), "d"),
+        ],
+        input,
+    )
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_filter() {
+    assert_matches_golden_file(&filter_plan(), "filter.yul");
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_projection() {
+    assert_matches_golden_file(&projection_plan(), "projection.yul");
+}
+
+fn tables_t_u() -> (TableRef, TableRef, TestSchemaAccessor) {
+    let t = TableRef::new("sxt", "t");
+    let u = TableRef::new("sxt", "u");
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        t.clone() => indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::VarChar,
+            "d".into() => ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            "e".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        },
+        u.clone() => indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::VarChar,
+        },
+    });
+    (t, u, accessor)
+}
+
+fn table_exec_of(table_ref: &TableRef, accessor: &TestSchemaAccessor) -> DynProofPlan {
+    let schema = accessor
+        .lookup_schema(table_ref.clone())
+        .into_iter()
+        .map(|(name, column_type)| column_field(&name.value, column_type))
+        .collect();
+    table_exec(table_ref.clone(), schema)
+}
+
+fn verify_plan_of(plan: &DynProofPlan) -> String {
+    generate_verify_plan(plan).unwrap().lines.join("\n")
+}
+
+fn join_of(join_type: JoinType) -> DynProofPlan {
+    let (t, u, accessor) = tables_t_u();
+    outer_join(
+        table_exec_of(&t, &accessor),
+        table_exec_of(&u, &accessor),
+        vec![0],
+        vec![0],
+        ["a", "b", "c", "d", "e", "f"].map(Ident::new).to_vec(),
+        join_type,
+    )
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_slice() {
+    let (_, _, input) = table_a_b();
+    let verify_plan = verify_plan_of(&slice_exec(input, 1, Some(2)));
+    assert!(verify_plan.contains("// slice\n"));
+    assert!(verify_plan.contains("// offset_chi_eval\n"));
+    assert!(verify_plan.contains("verify_filter(builder_ptr, "));
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_sort() {
+    let (_, _, input) = table_a_b();
+    let verify_plan = verify_plan_of(&sort_exec(input, vec![(1, false), (0, true)]));
+    assert!(verify_plan.contains("// sort\n"));
+    assert!(verify_plan.contains("verify_permutation_check(builder_ptr, "));
+    assert!(verify_plan.contains("// sort_key\n"));
+    assert!(verify_plan.contains("verify_monotonic(builder_ptr, "));
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_distinct() {
+    let (_, _, input) = table_a_b();
+    let verify_plan = verify_plan_of(&distinct_exec(input));
+    assert!(verify_plan.contains("// distinct\n"));
+    assert!(verify_plan.contains("verify_group_by(builder_ptr, "));
+    assert!(verify_plan.contains("{ err(ERR_DISTINCT_CHECK_FAILED) }"));
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_union() {
+    let (t, u, _) = tables_t_u();
+    let schema = vec![column_field("a", ColumnType::BigInt)];
+    let verify_plan = verify_plan_of(&union_exec(
+        vec![table_exec(t, schema.clone()), table_exec(u, schema.clone())],
+        schema,
+    ));
+    assert!(verify_plan.contains("// union\n"));
+    assert_eq!(
+        verify_plan
+            .matches("verify_union_input(builder_ptr, ")
+            .count(),
+        2
+    );
+    assert!(verify_plan.contains("verify_union_output(builder_ptr, "));
+}
+
+fn group_by_with_extrema_plan() -> DynProofPlan {
+    let (t, _, accessor) = tables_t_u();
+    group_by_with_max_min(
+        vec![aliased_plan(column(&t, "c", &accessor), "c")],
+        vec![sum_expr(column(&t, "a", &accessor), "sum_a")],
+        vec![max_expr(column(&t, "b", &accessor), "max_b")],
+        vec![min_expr(column(&t, "d", &accessor), "min_d")],
+        "count",
+        tab(&t),
+        gte(column(&t, "a", &accessor), const_bigint(0)),
+    )
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_group_by_with_extrema() {
+    let verify_plan = generate_verify_plan(&group_by_with_extrema_plan()).unwrap();
+    // The groups of a varchar column are only unique if the result is ordered by them
+    assert_eq!(verify_plan.num_ordered_result_columns, Some(1));
+    let verify_plan = verify_plan.lines.join("\n");
+    assert!(verify_plan.contains("// group by\n"));
+    assert!(verify_plan.contains("verify_group_by(builder_ptr, "));
+    assert_eq!(
+        verify_plan.matches("verify_extremum(builder_ptr, ").count(),
+        2
+    );
+    assert!(!verify_plan.contains("verify_monotonic(builder_ptr, "));
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_a_group_by_with_unique_groups_below_the_root() {
+    let (t, _, accessor) = tables_t_u();
+    let plan = slice_exec(
+        group_by(
+            vec![aliased_plan(column(&t, "a", &accessor), "a")],
+            vec![sum_expr(column(&t, "b", &accessor), "sum_b")],
+            "count",
+            tab(&t),
+            const_bool(true),
+        ),
+        0,
+        Some(1),
+    );
+    let verify_plan = generate_verify_plan(&plan).unwrap();
+    assert_eq!(verify_plan.num_ordered_result_columns, None);
+    let verify_plan = verify_plan.lines.join("\n");
+    assert!(verify_plan.contains("// group by\n"));
+    assert!(verify_plan.contains("verify_monotonic(builder_ptr, "));
+    assert!(verify_plan.contains("// slice\n"));
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_an_inner_join() {
+    let verify_plan = verify_plan_of(&join_of(JoinType::Inner));
+    assert!(verify_plan.contains("// sort merge join\n"));
+    assert_eq!(verify_plan.matches("// multiplicity_eval\n").count(), 4);
+    assert!(verify_plan.contains("builder_produce_zerosum_constraint(builder_ptr, "));
+    assert!(!verify_plan.contains("verify_union_output(builder_ptr, "));
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_outer_joins() {
+    for (join_type, num_parts) in [
+        (JoinType::Left, 2),
+        (JoinType::Right, 2),
+        (JoinType::Full, 3),
+    ] {
+        let verify_plan = verify_plan_of(&join_of(join_type));
+        assert!(verify_plan.contains("// sort merge join\n"));
+        assert_eq!(
+            verify_plan.matches("// chi_selected_eval\n").count(),
+            num_parts - 1
+        );
+        assert_eq!(
+            verify_plan
+                .matches("verify_union_input(builder_ptr, ")
+                .count(),
+            num_parts
+        );
+        assert!(verify_plan.contains("verify_union_output(builder_ptr, "));
+    }
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_semi_and_anti_joins() {
+    let (t, u, accessor) = tables_t_u();
+    let semi_join_plan = verify_plan_of(&semi_join(
+        table_exec_of(&t, &accessor),
+        table_exec_of(&u, &accessor),
+        vec![0],
+        vec![0],
+    ));
+    assert!(semi_join_plan.contains("// semi join\n"));
+    assert!(semi_join_plan.contains("// selected_u_eval\n"));
+    let anti_join_plan = verify_plan_of(&anti_join(
+        table_exec_of(&t, &accessor),
+        table_exec_of(&u, &accessor),
+        vec![0],
+        vec![0],
+    ));
+    assert!(anti_join_plan.contains("// anti join\n"));
+    assert!(!anti_join_plan.contains("// selected_u_eval\n"));
+}
+
+/// A filter that uses every expression the generated verifier supports.
+fn every_expression_plan() -> DynProofPlan {
+    let (t, _, accessor) = tables_t_u();
+    let a = || column(&t, "a", &accessor);
+    let b = || column(&t, "b", &accessor);
+    let d = || column(&t, "d", &accessor);
+    let e = || column(&t, "e", &accessor);
+    filter(
+        vec![
+            aliased_plan(divide(a(), b()), "quotient"),
+            aliased_plan(modulo(a(), b()), "remainder"),
+            aliased_plan(
+                cast(a(), ColumnType::Decimal75(Precision::new(20).unwrap(), 2)),
+                "upcast",
+            ),
+            aliased_plan(cast(d(), ColumnType::BigInt), "downcast"),
+            aliased_plan(case_when(vec![(lte(a(), b()), a())], b()), "smaller"),
+            aliased_plan(
+                in_list(a(), vec![LiteralValue::BigInt(1), LiteralValue::BigInt(2)]),
+                "is_listed",
+            ),
+            aliased_plan(between(d(), const_bigint(1), a()), "is_between"),
+            aliased_plan(date_trunc(e(), PoSQLTimeField::Day), "day"),
+            aliased_plan(extract(e(), PoSQLTimeField::Hour), "hour"),
+            aliased_plan(multiply(a(), subtract(b(), d())), "product"),
+        ],
+        tab(&t),
+        and(
+            not(equal(a(), const_bigint(3))),
+            or(
+                gte(a(), b()),
+                equal(column(&t, "c", &accessor), const_varchar("x")),
+            ),
+        ),
+    )
+}
+
+#[test]
+fn we_can_generate_verify_plan_for_every_expression() {
+    let verify_plan = verify_plan_of(&every_expression_plan());
+    for name in [
+        "equals_zero_eval",
+        "not_eval",
+        "sign_eval",
+        "or_eval",
+        "product_eval",
+        "quotient_eval, remainder_eval",
+        "cast_eval",
+        "selector_eval",
+        "case_eval",
+        "between_eval",
+        "floor_quotient_eval",
+        "truncated_eval",
+        "difference_eval",
+    ] {
+        assert!(
+            verify_plan.contains(&format!(" // {name}\n")),
+            "missing {name}"
+        );
+    }
+    assert!(verify_plan.contains("{ err(ERR_OUT_OF_RANGE) }"));
+}
+
+#[test]
+fn we_cannot_generate_verify_plan_for_a_group_by_without_unique_groups_below_the_root() {
+    let (t, _, accessor) = tables_t_u();
+    let plan = slice_exec(
+        group_by(
+            vec![aliased_plan(column(&t, "c", &accessor), "c")],
+            vec![sum_expr(column(&t, "b", &accessor), "sum_b")],
+            "count",
+            tab(&t),
+            const_bool(true),
+        ),
+        0,
+        Some(1),
+    );
+    assert!(matches!(
+        generate_verify_plan(&plan),
+        Err(SolidityVerifierError::UnverifiablePlan { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_generate_verify_plan_for_a_sort_by_a_varchar() {
+    let (t, _, accessor) = tables_t_u();
+    assert_eq!(
+        generate_verify_plan(&sort_exec(table_exec_of(&t, &accessor), vec![(2, true)])),
+        Err(SolidityVerifierError::UnverifiablePlan {
+            error: "Sort columns can not be folded into a sort key"
+        })
+    );
+}
+
+#[test]
+fn we_cannot_generate_verify_plan_for_a_join_on_columns_that_can_not_be_folded() {
+    let (t, u, accessor) = tables_t_u();
+    let plan = semi_join(
+        table_exec_of(&t, &accessor),
+        table_exec_of(&u, &accessor),
+        vec![0, 2],
+        vec![0, 1],
+    );
+    assert_eq!(
+        generate_verify_plan(&plan),
+        Err(SolidityVerifierError::UnverifiablePlan {
+            error: "Join on multiple columns requires join columns of at most 64 bits"
+        })
+    );
+}
+
+#[test]
+fn we_can_generate_a_self_contained_solidity_verifier() {
+    let plan = EVMProofPlan::new(filter_plan());
+    let verifier =
+        generate_solidity_verifier(&plan, &G2Affine::generator(), read_solidity_source).unwrap();
+    assert!(!verifier.contains("{{"));
+    assert!(!verifier.contains("import "));
+    assert!(!verifier.contains("IMPORT-YUL"));
+    assert!(verifier.contains("uint256 constant MODULUS = "));
+    assert!(verifier.contains("uint32 constant ERR_EMPTY_QUEUE = "));
+    assert!(verifier.contains("uint256 constant NUM_PLAN_VARIABLES = 19;"));
+    assert!(verifier.contains("uint256 constant CHECK_RESULT_ORDER = 0;"));
+    assert!(verifier.contains("            function builder_consume_final_round_mle("));
+    assert!(verifier.contains("            function ec_pairing_x2("));
+    assert!(verifier.contains("            function builder_consume_rho_evaluation("));
+    assert!(verifier.contains("            function verify_plan("));
+    assert!(verifier.contains(
+        "                mstore(add(vars_ptr, 0x180), builder_consume_challenge(builder_ptr)) // alpha\n"
+    ));
+    assert!(verifier.contains("                column_ptr, ptr := read_result_column(ptr, end, "));
+    // With the generator as τ·H, the verifier key constants are those of the generator.
+    assert!(verifier.contains(
+        "uint256 constant VK_TAU_H_X_REAL = 0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed;"
+    ));
+    assert!(verifier.contains(
+        "uint256 constant VK_TAU_H_X_IMAG = 0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2;"
+    ));
+    assert!(verifier.contains(
+        "uint256 constant VK_TAU_H_Y_REAL = 0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa;"
+    ));
+    assert!(verifier.contains(
+        "uint256 constant VK_TAU_H_Y_IMAG = 0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b;"
+    ));
+}
+
+#[test]
+fn we_can_pin_the_plan_hash_in_the_generated_verifier() {
+    let tau_h = G2Affine::generator();
+    let filter_verifier = generate_solidity_verifier(
+        &EVMProofPlan::new(filter_plan()),
+        &tau_h,
+        read_solidity_source,
+    )
+    .unwrap();
+    let projection_verifier = generate_solidity_verifier(
+        &EVMProofPlan::new(projection_plan()),
+        &tau_h,
+        read_solidity_source,
+    )
+    .unwrap();
+    let plan_hash = |verifier: &str| {
+        verifier
+            .lines()
+            .find_map(|line| {
+                line.strip_prefix("uint256 constant PLAN_HASH = ")?
+                    .strip_suffix(';')
+            })
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(plan_hash(&filter_verifier).len(), 2 + 64);
+    assert_ne!(plan_hash(&filter_verifier), plan_hash(&projection_verifier));
+}
+
+#[test]
+fn we_cannot_generate_a_solidity_verifier_with_a_missing_source() {
+    let plan = EVMProofPlan::new(filter_plan());
+    let read = |path: &str| match path {
+        "base/Queue.pre.sol" => None,
+        _ => read_solidity_source(path),
+    };
+    assert_eq!(
+        generate_solidity_verifier(&plan, &G2Affine::generator(), read),
+        Err(SolidityVerifierError::MissingSource {
+            path: "base/Queue.pre.sol".to_string()
+        })
+    );
+}
+
+#[test]
+fn we_cannot_generate_a_solidity_verifier_with_a_missing_definition() {
+    let plan = EVMProofPlan::new(filter_plan());
+    let read = |path: &str| match path {
+        "base/Array.pre.sol" => Some(String::new()),
+        _ => read_solidity_source(path),
+    };
+    assert_eq!(
+        generate_solidity_verifier(&plan, &G2Affine::generator(), read),
+        Err(SolidityVerifierError::MissingDefinition {
+            name: "get_array_element".to_string(),
+            path: "base/Array.pre.sol".to_string()
+        })
+    );
+}
+
+#[test]
+fn we_can_find_the_first_definition_of_a_yul_function() {
+    let source = "\
+        function f_inner(a) -> b {
+            b := a
+        }
+        function f(a) -> b {
+            if a { b := 1 }
+        }
+        function f(a) -> b {
+            b := 2
+        }
+";
+    assert_eq!(
+        find_yul_function(source, "f"),
+        Some("        function f(a) -> b {\n            if a { b := 1 }\n        }")
+    );
+    assert_eq!(find_yul_function(source, "g"), None);
+}
+
+#[test]
+fn we_can_strip_the_preamble_of_a_solidity_source() {
+    let source = "// SPDX-License-Identifier: UNLICENSED\n// This is licensed under the Cryptographic Open Software License 1.0\npragma solidity ^0.8.28;\n\nimport \"./A.sol\";\n\n/// @dev A\nuint256 constant A = 1;\n";
+    assert_eq!(
+        strip_preamble(source),
+        "/// @dev A\nuint256 constant A = 1;"
+    );
+}
+
+type CP = HyperKZGCommitmentEvaluationProof;
+
+fn sol_test_accessor(
+    ck: &CommitmentKey<HyperKZGEngine>,
+) -> (TableRef, OwnedTableTestAccessor<'_, CP>) {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<CP>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 2, 5]),
+            bigint("b", [1_i64, 0, 1, 1, 0]),
+            varchar("c", ["x", "y", "z", "y", "x"]),
+        ]),
+        0,
+        ck,
+    );
+    (t, accessor)
+}
+
+/// The statements that fill `array` with `values`, which `forge` tests can not write as a literal.
+fn solidity_array(array: &str, values: &[String]) -> String {
+    iter::once(format!(
+        "        {array} = new uint256[]({});\n",
+        values.len()
+    ))
+    .chain(
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| format!("        {array}[{i}] = {value};\n")),
+    )
+    .collect()
+}
+
+fn solidity_bytes(bytes: &[u8]) -> String {
+    bytes.iter().fold("hex\"".to_string(), |literal, byte| {
+        format!("{literal}{byte:02x}")
+    }) + "\""
+}
+
+/// Proves `plan` over the table of [`sol_test_accessor`], and checks with `forge` that the verifier generated for it
+/// returns the verification hash of [`verify_evm_calldata`], and rejects the proof once it is tampered with.
+fn assert_generated_verifier_accepts_the_proof(
+    plan: impl FnOnce(&TableRef, &OwnedTableTestAccessor<CP>) -> DynProofPlan,
+    name: &str,
+) {
+    let setup = HyperKZGPublicSetup::test_rand(32, &mut StdRng::seed_from_u64(0));
+    let ck = setup.prover_setup();
+    let vk = setup.verifier_setup();
+    let (t, accessor) = sol_test_accessor(&ck);
+    let plan = EVMProofPlan::new(plan(&t, &accessor));
+    let verifiable_result = VerifiableQueryResult::<CP>::new(&plan, &accessor, &&ck);
+    let calldata = encode_evm_calldata(&plan, &verifiable_result).unwrap();
+    let verification_hash = verify_evm_calldata(&calldata, &accessor, &vk)
+        .unwrap()
+        .verification_hash;
+    let mut tampered_calldata = calldata.clone();
+    *tampered_calldata.last_mut().unwrap() ^= 1;
+
+    let table_lengths: Vec<_> = plan
+        .inner()
+        .get_table_references()
+        .iter()
+        .map(|table_ref| accessor.get_length(table_ref).to_string())
+        .collect();
+    let commitments: Vec<_> = plan
+        .inner()
+        .get_column_references()
+        .into_iter()
+        .flat_map(|column_ref| {
+            let bytes = bincode::serde::encode_to_vec(
+                accessor.get_commitment(column_ref),
+                bincode::config::legacy()
+                    .with_fixed_int_encoding()
+                    .with_big_endian(),
+            )
+            .unwrap();
+            bytes
+                .chunks(32)
+                .map(|word| word_literal(word.try_into().unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let verifier = generate_solidity_verifier(&plan, setup.tau_h(), read_solidity_source).unwrap();
+
+    let inputs = solidity_array("tableLengths", &table_lengths)
+        + &solidity_array("commitments", &commitments);
+    let test = format!(
+        r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.28;
+
+import {{QueryVerifier}} from "../src/QueryVerifier.sol";
+
+contract QueryVerifierWrapper {{
+    function verify(bytes calldata proof, uint256[] memory tableLengths, uint256[] memory commitments)
+        external
+        view
+        returns (uint256)
+    {{
+        return QueryVerifier.verify(proof, tableLengths, commitments);
+    }}
+}}
+
+contract QueryVerifierTest {{
+    function inputs() internal pure returns (uint256[] memory tableLengths, uint256[] memory commitments) {{
+{inputs}    }}
+
+    function testVerifiesTheProof() public {{
+        (uint256[] memory tableLengths, uint256[] memory commitments) = inputs();
+        uint256 verificationHash = new QueryVerifierWrapper().verify({proof}, tableLengths, commitments);
+        require(verificationHash == {verification_hash}, "unexpected verification hash");
+    }}
+
+    function testRejectsATamperedProof() public {{
+        (uint256[] memory tableLengths, uint256[] memory commitments) = inputs();
+        QueryVerifierWrapper wrapper = new QueryVerifierWrapper();
+        try wrapper.verify({tampered_proof}, tableLengths, commitments) returns (uint256) {{
+            revert("accepted a tampered proof");
+        }} catch {{}}
+    }}
+}}
+"#,
+        proof = solidity_bytes(&calldata),
+        tampered_proof = solidity_bytes(&tampered_calldata),
+        verification_hash = word_literal(&verification_hash),
+    );
+
+    let root = forge_project(name, &verifier);
+    fs::create_dir_all(root.join("test")).unwrap();
+    fs::write(root.join("test/QueryVerifier.t.sol"), test).unwrap();
+    assert_forge_succeeds("test", &root);
+}
+
+/// Writes a `forge` project named `name` whose only source is `verifier`, and returns its root.
+fn forge_project(name: &str, verifier: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("proof-of-sql-sol-test-{name}"));
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(
+        root.join("foundry.toml"),
+        "[profile.default]\nsrc = \"src\"\ntest = \"test\"\nout = \"out\"\nsolc = \"0.8.28\"\n",
+    )
+    .unwrap();
+    fs::write(root.join("src/QueryVerifier.sol"), verifier).unwrap();
+    root
+}
+
+fn assert_forge_succeeds(command: &str, root: &Path) {
+    let output = Command::new("forge")
+        .arg(command)
+        .arg("--root")
+        .arg(root)
+        .output()
+        .expect("forge should be installed");
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+#[ignore = "requires forge"]
+fn sol_test_generated_verifier_for_a_filter() {
+    assert_generated_verifier_accepts_the_proof(
+        |t, accessor| {
+            filter(
+                cols_expr_plan(t, &["a", "c"], accessor),
+                tab(t),
+                equal(column(t, "b", accessor), const_bigint(1)),
+            )
+        },
+        "filter",
+    );
+}
+
+#[test]
+#[ignore = "requires forge"]
+fn sol_test_generated_verifier_for_a_group_by() {
+    assert_generated_verifier_accepts_the_proof(
+        |t, accessor| {
+            group_by_with_max_min(
+                vec![aliased_plan(column(t, "c", accessor), "c")],
+                vec![sum_expr(column(t, "a", accessor), "sum_a")],
+                vec![max_expr(column(t, "b", accessor), "max_b")],
+                vec![],
+                "count",
+                tab(t),
+                gte(column(t, "a", accessor), const_bigint(2)),
+            )
+        },
+        "group_by",
+    );
+}
+
+#[test]
+#[ignore = "requires forge"]
+fn sol_test_generated_verifier_for_a_left_join() {
+    assert_generated_verifier_accepts_the_proof(
+        |t, _| {
+            let scan = |columns: &[&str]| {
+                table_exec(
+                    t.clone(),
+                    columns
+                        .iter()
+                        .map(|&name| {
+                            column_field(
+                                name,
+                                if name == "c" {
+                                    ColumnType::VarChar
+                                } else {
+                                    ColumnType::BigInt
+                                },
+                            )
+                        })
+                        .collect(),
+                )
+            };
+            outer_join(
+                scan(&["a", "b"]),
+                scan(&["b", "c"]),
+                vec![0],
+                vec![0],
+                ["a", "b", "c"].map(Ident::new).to_vec(),
+                JoinType::Left,
+            )
+        },
+        "left_join",
+    );
+}
+
+#[test]
+#[ignore = "requires forge"]
+fn sol_test_generated_verifier_for_expressions() {
+    assert_generated_verifier_accepts_the_proof(
+        |t, accessor| {
+            let a = || column(t, "a", accessor);
+            let b = || column(t, "b", accessor);
+            projection(
+                vec![
+                    aliased_plan(divide(a(), const_bigint(2)), "quotient"),
+                    aliased_plan(modulo(a(), const_bigint(2)), "remainder"),
+                    aliased_plan(
+                        cast(a(), ColumnType::Decimal75(Precision::new(20).unwrap(), 2)),
+                        "upcast",
+                    ),
+                    aliased_plan(case_when(vec![(lte(a(), b()), a())], b()), "smaller"),
+                    aliased_plan(
+                        in_list(a(), vec![LiteralValue::BigInt(1), LiteralValue::BigInt(2)]),
+                        "is_listed",
+                    ),
+                    aliased_plan(between(a(), const_bigint(2), const_bigint(3)), "is_between"),
+                ],
+                table_exec(
+                    t.clone(),
+                    vec![
+                        column_field("a", ColumnType::BigInt),
+                        column_field("b", ColumnType::BigInt),
+                    ],
+                ),
+            )
+        },
+        "expressions",
+    );
+}
+
+#[test]
+#[ignore = "requires forge"]
+fn sol_test_generated_verifier_compiles_for_every_plan_and_expression() {
+    let (t, u, accessor) = tables_t_u();
+    let (_, _, input) = table_a_b();
+    let union_schema = vec![column_field("a", ColumnType::BigInt)];
+    let plans = [
+        ("compile-filter", filter_plan()),
+        ("compile-projection", projection_plan()),
+        ("compile-slice", slice_exec(input.clone(), 1, Some(2))),
+        (
+            "compile-sort",
+            sort_exec(input.clone(), vec![(1, false), (0, true)]),
+        ),
+        ("compile-distinct", distinct_exec(input)),
+        (
+            "compile-union",
+            union_exec(
+                vec![
+                    table_exec(t.clone(), union_schema.clone()),
+                    table_exec(u.clone(), union_schema.clone()),
+                ],
+                union_schema,
+            ),
+        ),
+        ("compile-group-by", group_by_with_extrema_plan()),
+        ("compile-inner-join", join_of(JoinType::Inner)),
+        ("compile-left-join", join_of(JoinType::Left)),
+        ("compile-right-join", join_of(JoinType::Right)),
+        ("compile-full-join", join_of(JoinType::Full)),
+        (
+            "compile-semi-join",
+            semi_join(
+                table_exec_of(&t, &accessor),
+                table_exec_of(&u, &accessor),
+                vec![0],
+                vec![0],
+            ),
+        ),
+        (
+            "compile-anti-join",
+            anti_join(
+                table_exec_of(&t, &accessor),
+                table_exec_of(&u, &accessor),
+                vec![0],
+                vec![0],
+            ),
+        ),
+        ("compile-expressions", every_expression_plan()),
+    ];
+    for (name, plan) in plans {
+        let verifier = generate_solidity_verifier(
+            &EVMProofPlan::new(plan),
+            &G2Affine::generator(),
+            read_solidity_source,
+        )
+        .unwrap();
+        assert_forge_succeeds("build", &forge_project(name, &verifier));
+    }
+}
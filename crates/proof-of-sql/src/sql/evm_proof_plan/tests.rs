@@ -1,26 +1,62 @@
 use crate::{
-    base::database::{ColumnRef, ColumnType, LiteralValue, TableRef},
+    base::{
+        database::{ColumnRef, ColumnType, LiteralValue, TableRef, TestSchemaAccessor},
+        map::indexmap,
+        math::{decimal::Precision, i256::I256},
+    },
     sql::{
         evm_proof_plan::EVMProofPlan,
         proof_exprs::{
-            AliasedDynProofExpr, ColumnExpr, DynProofExpr, EqualsExpr, LiteralExpr, TableExpr,
+            test_utility::*, AliasedDynProofExpr, ColumnExpr, DynProofExpr, EqualsExpr,
+            LiteralExpr, TableExpr,
         },
-        proof_plans::{DynProofPlan, EmptyExec, FilterExec},
+        proof_plans::{test_utility::*, DynProofPlan, FilterExec, JoinType},
     },
 };
+use bincode::config::{BigEndian, Configuration, Fixint, NoLimit};
 use core::iter;
+use proof_of_sql_parser::{
+    intermediate_ast::AggregationOperator,
+    posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone},
+};
 
-#[test]
-fn we_cannot_generate_serialized_proof_plan_for_unsupported_plan() {
-    let plan = DynProofPlan::Empty(EmptyExec::new());
-
-    bincode::serde::encode_to_vec(
-        EVMProofPlan::new(plan),
-        bincode::config::legacy()
-            .with_fixed_int_encoding()
-            .with_big_endian(),
-    )
-    .unwrap_err();
+fn config() -> Configuration<BigEndian, Fixint, NoLimit> {
+    bincode::config::legacy()
+        .with_fixed_int_encoding()
+        .with_big_endian()
+}
+
+fn serialize(plan: DynProofPlan) -> Vec<u8> {
+    bincode::serde::encode_to_vec(EVMProofPlan::new(plan), config()).unwrap()
+}
+
+fn deserialize(bytes: &[u8]) -> Result<DynProofPlan, bincode::error::DecodeError> {
+    let (plan, num_bytes): (EVMProofPlan, _) = bincode::serde::decode_from_slice(bytes, config())?;
+    assert_eq!(num_bytes, bytes.len());
+    Ok(plan.into_inner())
+}
+
+fn assert_round_trip(plan: DynProofPlan) {
+    assert_eq!(deserialize(&serialize(plan.clone())).unwrap(), plan);
+}
+
+fn accessor() -> (TableRef, TableRef, TestSchemaAccessor) {
+    let t = TableRef::new("sxt", "t");
+    let u = TableRef::new("sxt", "u");
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        t.clone() => indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+            "c".into() => ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            "d".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            "e".into() => ColumnType::Boolean,
+        },
+        u.clone() => indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "f".into() => ColumnType::Int,
+        },
+    });
+    (t, u, accessor)
 }
 
 #[test]
@@ -47,13 +83,7 @@ fn we_can_generate_serialized_proof_plan_for_simple_filter() {
         )),
     ));
 
-    let bytes = bincode::serde::encode_to_vec(
-        EVMProofPlan::new(plan),
-        bincode::config::legacy()
-            .with_fixed_int_encoding()
-            .with_big_endian(),
-    )
-    .unwrap();
+    let bytes = serialize(plan.clone());
 
     let expected_bytes: Vec<_> = iter::empty()
         .chain(&1_usize.to_be_bytes())
@@ -63,12 +93,17 @@ fn we_can_generate_serialized_proof_plan_for_simple_filter() {
         .chain(&0_usize.to_be_bytes())
         .chain(&1_usize.to_be_bytes())
         .chain("b".as_bytes())
+        .chain(&5_u32.to_be_bytes())
         .chain(&0_usize.to_be_bytes())
         .chain(&1_usize.to_be_bytes())
         .chain("a".as_bytes())
-        .chain([])
+        .chain(&5_u32.to_be_bytes())
         .chain(&0_u32.to_be_bytes()) //   FilterExec
-        .chain(&0_usize.to_be_bytes()) //   table_number
+        .chain(&2_u32.to_be_bytes()) //     input - TableExec
+        .chain(&0_usize.to_be_bytes()) //     table_number
+        .chain(&2_usize.to_be_bytes()) //     column_numbers.len()
+        .chain(&0_usize.to_be_bytes()) //     column_numbers[0]
+        .chain(&1_usize.to_be_bytes()) //     column_numbers[1]
         .chain(&1_u32.to_be_bytes()) //     where_clause - EqualsExpr
         .chain(&0_u32.to_be_bytes()) //       lhs - ColumnExpr
         .chain(&1_usize.to_be_bytes()) //       column_number
@@ -78,7 +113,257 @@ fn we_can_generate_serialized_proof_plan_for_simple_filter() {
         .chain(&1_usize.to_be_bytes()) //   results.len()
         .chain(&0_u32.to_be_bytes()) //     results[0] - ColumnExpr
         .chain(&0_usize.to_be_bytes()) //     column_number
+        .chain(&5_usize.to_be_bytes()) //     alias
+        .chain("alias".as_bytes())
         .copied()
         .collect();
     assert_eq!(bytes, expected_bytes);
+    assert_eq!(deserialize(&bytes).unwrap(), plan);
+}
+
+#[test]
+fn we_can_round_trip_filters_and_group_bys() {
+    let (t, _, accessor) = accessor();
+    assert_round_trip(filter(
+        vec![
+            col_expr_plan(&t, "b", &accessor),
+            aliased_plan(
+                add(column(&t, "a", &accessor), const_bigint(1)),
+                "a_plus_one",
+            ),
+            aliased_plan(
+                subtract(column(&t, "c", &accessor), const_decimal75(10, 2, 150)),
+                "c_minus",
+            ),
+        ],
+        tab(&t),
+        and(
+            or(
+                equal(column(&t, "b", &accessor), const_varchar("x")),
+                not(column(&t, "e", &accessor)),
+            ),
+            lte(column(&t, "a", &accessor), const_bigint(3)),
+        ),
+    ));
+    assert_round_trip(group_by_with_max_min(
+        vec![col_expr_plan(&t, "b", &accessor)],
+        vec![aliased_plan(
+            multiply(column(&t, "a", &accessor), const_int(2)),
+            "sum_a",
+        )],
+        vec![aliased_plan(column(&t, "c", &accessor), "max_c")],
+        vec![aliased_plan(column(&t, "d", &accessor), "min_d")],
+        "count",
+        tab(&t),
+        gte(column(&t, "a", &accessor), const_smallint(0)),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_all_other_expressions() {
+    let (t, _, accessor) = accessor();
+    assert_round_trip(filter(
+        vec![
+            aliased_plan(
+                divide(column(&t, "a", &accessor), const_bigint(7)),
+                "quotient",
+            ),
+            aliased_plan(
+                modulo(column(&t, "a", &accessor), const_bigint(7)),
+                "remainder",
+            ),
+            aliased_plan(cast(column(&t, "a", &accessor), ColumnType::Int128), "cast"),
+            aliased_plan(
+                case_when(
+                    vec![(column(&t, "e", &accessor), const_bigint(1))],
+                    column(&t, "a", &accessor),
+                ),
+                "case",
+            ),
+            aliased_plan(
+                date_trunc(column(&t, "d", &accessor), PoSQLTimeField::Day),
+                "day",
+            ),
+            aliased_plan(
+                extract(column(&t, "d", &accessor), PoSQLTimeField::Hour),
+                "hour",
+            ),
+            aliased_plan(
+                DynProofExpr::new_aggregate(AggregationOperator::Sum, column(&t, "a", &accessor)),
+                "sum",
+            ),
+        ],
+        tab(&t),
+        and(
+            in_list(
+                column(&t, "a", &accessor),
+                vec![LiteralValue::BigInt(1), LiteralValue::BigInt(2)],
+            ),
+            between(
+                column(&t, "c", &accessor),
+                const_decimal75(10, 2, 0),
+                const_decimal75(10, 2, 1000),
+            ),
+        ),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_literals_of_every_type() {
+    let literals = [
+        LiteralValue::Boolean(true),
+        LiteralValue::Uint8(200),
+        LiteralValue::TinyInt(-3),
+        LiteralValue::SmallInt(-300),
+        LiteralValue::Int(70_000),
+        LiteralValue::BigInt(-5_000_000_000),
+        LiteralValue::VarChar("proof".to_string()),
+        LiteralValue::Int128(i128::MIN),
+        LiteralValue::Decimal75(Precision::new(75).unwrap(), -12, I256::from(-123_i32)),
+        LiteralValue::Scalar([1, 2, 3, 4]),
+        LiteralValue::TimeStampTZ(PoSQLTimeUnit::Nanosecond, PoSQLTimeZone::new(3_600), -1),
+    ];
+    assert_round_trip(projection(
+        literals
+            .into_iter()
+            .enumerate()
+            .map(|(i, literal)| {
+                aliased_plan(DynProofExpr::new_literal(literal), &format!("literal_{i}"))
+            })
+            .collect(),
+        empty_exec(),
+    ));
+}
+
+#[test]
+fn we_can_round_trip_plans_over_other_plans() {
+    let (t, u, accessor) = accessor();
+    let t_exec = || {
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("b", ColumnType::VarChar),
+            ],
+        )
+    };
+    let u_exec = || {
+        table_exec(
+            u.clone(),
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("f", ColumnType::Int),
+            ],
+        )
+    };
+    assert_round_trip(slice_exec(
+        sort_exec(distinct_exec(t_exec()), vec![(0, false), (1, true)]),
+        1,
+        Some(2),
+    ));
+    assert_round_trip(union_exec(
+        vec![
+            t_exec(),
+            projection(
+                vec![
+                    col_expr_plan(&u, "a", &accessor),
+                    aliased_plan(const_varchar("u"), "b"),
+                ],
+                u_exec(),
+            ),
+        ],
+        vec![
+            column_field("a", ColumnType::BigInt),
+            column_field("b", ColumnType::VarChar),
+        ],
+    ));
+    // The join output columns `b` and `f` are not columns of the tables
+    assert_round_trip(filter_with_input(
+        vec![aliased_plan(
+            DynProofExpr::new_column(ColumnRef::new(t.clone(), "f".into(), ColumnType::Int)),
+            "f",
+        )],
+        sort_merge_join(
+            t_exec(),
+            u_exec(),
+            vec![0],
+            vec![0],
+            vec!["a".into(), "b".into(), "f".into()],
+        ),
+        equal(
+            DynProofExpr::new_column(ColumnRef::new(t.clone(), "b".into(), ColumnType::VarChar)),
+            const_varchar("x"),
+        ),
+    ));
+    assert_round_trip(outer_join(
+        t_exec(),
+        u_exec(),
+        vec![0],
+        vec![0],
+        vec!["a".into(), "b".into(), "f".into()],
+        JoinType::Full,
+    ));
+    assert_round_trip(semi_join(t_exec(), u_exec(), vec![0], vec![0]));
+    assert_round_trip(anti_join(t_exec(), u_exec(), vec![0], vec![0]));
+}
+
+#[test]
+fn we_cannot_deserialize_malformed_plans() {
+    let (t, _, _) = accessor();
+    let table_and_column: Vec<u8> = iter::empty()
+        .chain(&1_usize.to_be_bytes())
+        .chain(&5_usize.to_be_bytes())
+        .chain("sxt.t".as_bytes())
+        .chain(&1_usize.to_be_bytes())
+        .chain(&0_usize.to_be_bytes())
+        .chain(&1_usize.to_be_bytes())
+        .chain("a".as_bytes())
+        .chain(&5_u32.to_be_bytes())
+        .copied()
+        .collect();
+    let table_exec_bytes: Vec<u8> = iter::empty()
+        .chain(&2_u32.to_be_bytes())
+        .chain(&0_usize.to_be_bytes())
+        .chain(&1_usize.to_be_bytes())
+        .chain(&0_usize.to_be_bytes())
+        .copied()
+        .collect();
+
+    let valid: Vec<u8> = table_and_column
+        .iter()
+        .chain(&table_exec_bytes)
+        .copied()
+        .collect();
+    assert_eq!(
+        deserialize(&valid).unwrap(),
+        table_exec(t, vec![column_field("a", ColumnType::BigInt)])
+    );
+
+    // The sort column is out of bounds
+    let sort: Vec<u8> = table_and_column
+        .iter()
+        .chain(&6_u32.to_be_bytes())
+        .chain(&table_exec_bytes)
+        .chain(&1_usize.to_be_bytes())
+        .chain(&1_usize.to_be_bytes())
+        .chain(&[1])
+        .copied()
+        .collect();
+    assert!(deserialize(&sort).is_err());
+
+    // The column number is out of bounds
+    let table_exec_with_missing_column: Vec<u8> = table_and_column
+        .iter()
+        .chain(&2_u32.to_be_bytes())
+        .chain(&0_usize.to_be_bytes())
+        .chain(&1_usize.to_be_bytes())
+        .chain(&1_usize.to_be_bytes())
+        .copied()
+        .collect();
+    assert!(deserialize(&table_exec_with_missing_column).is_err());
+
+    // The table number of the column is out of bounds
+    let mut column_with_missing_table = valid.clone();
+    column_with_missing_table[29..37].copy_from_slice(&1_usize.to_be_bytes());
+    assert!(deserialize(&column_with_missing_table).is_err());
 }
@@ -1,6 +1,6 @@
 //! This module contains the main logic for Proof of SQL.
 
-/// This module holds the [`EVMProofPlan`] struct and its implementation, which allows for EVM compatible serialization.
+/// This module holds the [`EVMProofPlan`] struct and its implementation, which allows for EVM compatible serialization and deserialization.
 pub mod evm_proof_plan;
 pub mod parse;
 pub mod postprocessing;
@@ -1,8 +1,8 @@
-use super::ConversionError;
+use super::{try_cast_to_data_type, type_check_binary_operation, ConversionError};
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue},
-        map::IndexMap,
+        database::{presence_column_ident, ColumnRef, ColumnType, LiteralValue},
+        map::{IndexMap, IndexSet},
         math::{
             decimal::{DecimalError, Precision},
             i256::I256,
@@ -50,6 +50,17 @@ impl<'a> DynProofExprBuilder<'a> {
     pub fn build(&self, expr: &Expression) -> Result<DynProofExpr, ConversionError> {
         self.visit_expr(expr)
     }
+    /// Returns the columns in the column mapping whose presence column is also in the column mapping.
+    pub(crate) fn nullable_columns(&self) -> IndexSet<ColumnRef> {
+        self.column_mapping
+            .iter()
+            .filter(|(ident, _)| {
+                self.column_mapping
+                    .contains_key(&presence_column_ident(ident))
+            })
+            .map(|(_, column_ref)| column_ref.clone())
+            .collect()
+    }
 }
 
 #[allow(clippy::match_wildcard_for_single_variants)]
@@ -64,6 +75,90 @@ impl DynProofExprBuilder<'_> {
             }
             Expression::Unary { op, expr } => self.visit_unary_expr((*op).into(), expr),
             Expression::Aggregation { op, expr } => self.visit_aggregate_expr(*op, expr),
+            Expression::IsNull(expr) => Ok(DynProofExpr::new_is_null(
+                &self.visit_expr(expr)?,
+                &self.nullable_columns(),
+            )),
+            Expression::IsNotNull(expr) => Ok(DynProofExpr::new_is_not_null(
+                &self.visit_expr(expr)?,
+                &self.nullable_columns(),
+            )),
+            Expression::Cast { expr, data_type } => {
+                let expr = self.visit_expr(expr)?;
+                let to_type = try_cast_to_data_type(expr.data_type(), *data_type)?;
+                DynProofExpr::try_new_cast(expr, to_type)
+            }
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let in_list = DynProofExpr::try_new_in_list(
+                    self.visit_expr(expr)?,
+                    list.iter()
+                        .map(|value| self.visit_in_list_value(value))
+                        .collect::<Result<_, ConversionError>>()?,
+                )?;
+                if *negated {
+                    DynProofExpr::try_new_not(in_list)
+                } else {
+                    Ok(in_list)
+                }
+            }
+            Expression::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => {
+                let between = DynProofExpr::try_new_between(
+                    self.visit_expr(expr)?,
+                    self.visit_expr(low)?,
+                    self.visit_expr(high)?,
+                )?;
+                if *negated {
+                    DynProofExpr::try_new_not(between)
+                } else {
+                    Ok(between)
+                }
+            }
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => {
+                let like = DynProofExpr::try_new_like(self.visit_expr(expr)?, pattern)?;
+                if *negated {
+                    DynProofExpr::try_new_not(like)
+                } else {
+                    Ok(like)
+                }
+            }
+            Expression::Length(expr) => DynProofExpr::try_new_length(self.visit_expr(expr)?),
+            Expression::StartsWith { expr, prefix } => {
+                DynProofExpr::try_new_starts_with(self.visit_expr(expr)?, prefix)
+            }
+            Expression::AddInterval { expr, interval } => {
+                DynProofExpr::try_new_add_interval(self.visit_expr(expr)?, *interval)
+            }
+            Expression::Extract { field, expr } => {
+                DynProofExpr::try_new_extract(self.visit_expr(expr)?, *field)
+            }
+            Expression::DateTrunc { field, expr } => {
+                DynProofExpr::try_new_date_trunc(self.visit_expr(expr)?, *field)
+            }
+            Expression::Case {
+                when_then,
+                else_expr: Some(else_expr),
+            } => DynProofExpr::try_new_case(
+                when_then
+                    .iter()
+                    .map(|(condition, result)| {
+                        Ok((self.visit_expr(condition)?, self.visit_expr(result)?))
+                    })
+                    .collect::<Result<_, ConversionError>>()?,
+                self.visit_expr(else_expr)?,
+            ),
             _ => Err(ConversionError::Unprovable {
                 error: format!("Expression {expr:?} is not supported yet"),
             }),
@@ -83,10 +178,25 @@ impl DynProofExprBuilder<'_> {
 
     #[allow(clippy::unused_self)]
     fn visit_literal(&self, lit: &Literal) -> Result<DynProofExpr, ConversionError> {
+        Ok(DynProofExpr::new_literal(self.visit_literal_value(lit)?))
+    }
+
+    /// Visits a value of an `IN` list, which has to be a literal.
+    fn visit_in_list_value(&self, expr: &Expression) -> Result<LiteralValue, ConversionError> {
+        match expr {
+            Expression::Literal(lit) => self.visit_literal_value(lit),
+            _ => Err(ConversionError::Unprovable {
+                error: format!("IN list value {expr:?} is not a literal"),
+            }),
+        }
+    }
+
+    #[allow(clippy::unused_self)]
+    fn visit_literal_value(&self, lit: &Literal) -> Result<LiteralValue, ConversionError> {
         match lit {
-            Literal::Boolean(b) => Ok(DynProofExpr::new_literal(LiteralValue::Boolean(*b))),
-            Literal::BigInt(i) => Ok(DynProofExpr::new_literal(LiteralValue::BigInt(*i))),
-            Literal::Int128(i) => Ok(DynProofExpr::new_literal(LiteralValue::Int128(*i))),
+            Literal::Boolean(b) => Ok(LiteralValue::Boolean(*b)),
+            Literal::BigInt(i) => Ok(LiteralValue::BigInt(*i)),
+            Literal::Int128(i) => Ok(LiteralValue::Int128(*i)),
             Literal::Decimal(d) => {
                 let raw_scale = d.scale();
                 let scale = raw_scale.try_into().map_err(|_| InvalidScale {
@@ -98,15 +208,15 @@ impl DynProofExprBuilder<'_> {
                             error: d.precision().to_string(),
                         },
                     })?;
-                Ok(DynProofExpr::new_literal(LiteralValue::Decimal75(
+                Ok(LiteralValue::Decimal75(
                     precision,
                     scale,
                     I256::from_num_bigint(
                         &d.try_into_bigint_with_precision_and_scale(precision.value(), scale)?,
                     ),
-                )))
+                ))
             }
-            Literal::VarChar(s) => Ok(DynProofExpr::new_literal(LiteralValue::VarChar(s.clone()))),
+            Literal::VarChar(s) => Ok(LiteralValue::VarChar(s.clone())),
             Literal::Timestamp(its) => {
                 let timestamp = match its.timeunit() {
                     PoSQLTimeUnit::Nanosecond => {
@@ -122,11 +232,11 @@ impl DynProofExprBuilder<'_> {
                     PoSQLTimeUnit::Second => its.timestamp().timestamp(),
                 };
 
-                Ok(DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                Ok(LiteralValue::TimeStampTZ(
                     its.timeunit(),
                     its.timezone(),
                     timestamp,
-                )))
+                ))
             }
         }
     }
@@ -193,9 +303,16 @@ impl DynProofExprBuilder<'_> {
                 let right = self.visit_expr(right);
                 DynProofExpr::try_new_multiply(left?, right?)
             }
-            BinaryOperator::Divide => Err(ConversionError::Unprovable {
-                error: format!("Binary operator {op:?} is not supported at this location"),
-            }),
+            BinaryOperator::Divide => {
+                let left = self.visit_expr(left);
+                let right = self.visit_expr(right);
+                DynProofExpr::try_new_divide(left?, right?)
+            }
+            BinaryOperator::Modulo => {
+                let left = self.visit_expr(left);
+                let right = self.visit_expr(right);
+                DynProofExpr::try_new_modulo(left?, right?)
+            }
             _ => {
                 // Handle unsupported binary operations
                 Err(ConversionError::UnsupportedOperation {
@@ -220,6 +337,16 @@ impl DynProofExprBuilder<'_> {
             (AggregationOperator::Count, _) | (AggregationOperator::Sum, true) => {
                 Ok(DynProofExpr::new_aggregate(op, expr))
             }
+            (AggregationOperator::Max | AggregationOperator::Min, _)
+                if expr.data_type() != ColumnType::VarChar
+                    && type_check_binary_operation(
+                        expr.data_type(),
+                        expr.data_type(),
+                        &BinaryOperator::Lt,
+                    ) =>
+            {
+                Ok(DynProofExpr::new_aggregate(op, expr))
+            }
             (AggregationOperator::Sum, false) => Err(ConversionError::InvalidExpression {
                 expression: format!(
                     "Aggregation operator {op:?} doesn't work with non-numeric types"
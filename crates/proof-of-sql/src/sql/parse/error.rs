@@ -38,6 +38,20 @@ pub enum ConversionError {
         identifier: Box<Ident>,
     },
 
+    #[snafu(display("Column reference '{identifier}' is ambiguous"))]
+    /// The column exists in more than one of the queried tables
+    AmbiguousColumn {
+        /// The ambiguous column identifier
+        identifier: Box<Ident>,
+    },
+
+    #[snafu(display("Table '{qualifier}' was not found in the FROM clause"))]
+    /// A column is qualified with a table that is not queried
+    MissingTable {
+        /// The name or alias of the missing table
+        qualifier: Box<Ident>,
+    },
+
     #[snafu(display("Expected '{expected}' but found '{actual}'"))]
     /// Invalid data type received
     InvalidDataType {
@@ -81,6 +95,15 @@ pub enum ConversionError {
         datatype: ColumnType,
     },
 
+    #[snafu(display(
+        "A HAVING clause must have boolean type. It is currently of type '{datatype}'."
+    ))]
+    /// HAVING clause is not boolean
+    NonbooleanHavingClause {
+        /// The actual datatype of the HAVING clause
+        datatype: ColumnType,
+    },
+
     #[snafu(display(
         "Invalid order by: alias '{alias}' does not appear in the result expressions."
     ))]
@@ -1,11 +1,13 @@
-use super::{where_expr_builder::WhereExprBuilder, ConversionError, EnrichedExpr};
+use super::{
+    where_expr_builder::WhereExprBuilder, ConversionError, DynProofExprBuilder, EnrichedExpr,
+};
 use crate::{
     base::{
-        database::{ColumnRef, LiteralValue, TableRef},
+        database::{presence_column_ident, ColumnRef, LiteralValue, TableRef},
         map::IndexMap,
     },
     sql::{
-        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
         proof_plans::FilterExec,
     },
 };
@@ -52,12 +54,22 @@ impl FilterExecBuilder {
         // If a column is provable, add it to the filter result expression list
         // If at least one column is non-provable, add all columns from the column mapping to the filter result expression list
         let mut has_nonprovable_column = false;
+        let nullable_columns = DynProofExprBuilder::new(&self.column_mapping).nullable_columns();
         for enriched_expr in columns {
             if let Some(plan) = &enriched_expr.dyn_proof_expr {
+                let alias: Ident = enriched_expr.residue_expression.alias.into();
+                // Nullable results are followed by their presence
+                let presence = plan.presence_expr(&nullable_columns);
                 self.filter_result_expr_list.push(AliasedDynProofExpr {
                     expr: plan.clone(),
-                    alias: enriched_expr.residue_expression.alias.into(),
+                    alias: alias.clone(),
                 });
+                if let Some(presence) = presence {
+                    self.filter_result_expr_list.push(AliasedDynProofExpr {
+                        expr: presence,
+                        alias: presence_column_ident(&alias),
+                    });
+                }
             } else {
                 has_nonprovable_column = true;
             }
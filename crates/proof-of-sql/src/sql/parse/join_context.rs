@@ -0,0 +1,222 @@
+use super::{ConversionError, ConversionResult, WhereExprBuilder};
+use crate::{
+    base::{
+        database::{ColumnRef, LiteralValue, TableRef},
+        map::IndexMap,
+    },
+    sql::{
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
+        proof_plans::{DynProofPlan, JoinType},
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, Expression};
+use sqlparser::ast::Ident;
+
+/// The two sides of a join
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JoinSide {
+    Left,
+    Right,
+}
+
+impl JoinSide {
+    /// Returns the opposite side
+    pub fn other(self) -> Self {
+        match self {
+            JoinSide::Left => JoinSide::Right,
+            JoinSide::Right => JoinSide::Left,
+        }
+    }
+}
+
+/// A table joined in the `FROM` clause of a query
+#[derive(Debug, Clone)]
+pub(crate) struct JoinTable {
+    /// The identifier columns of the table are qualified with, i.e. its alias or its name
+    qualifier: Ident,
+    table_ref: TableRef,
+    /// The columns of the table needed by the query keyed by their identifiers in the join result.
+    ///
+    /// The join columns always come first.
+    column_mapping: IndexMap<Ident, ColumnRef>,
+    /// The conjuncts of the WHERE clause that only reference this table
+    where_exprs: Vec<Expression>,
+}
+
+impl JoinTable {
+    pub fn new(qualifier: Ident, table_ref: TableRef) -> Self {
+        Self {
+            qualifier,
+            table_ref,
+            column_mapping: IndexMap::default(),
+            where_exprs: Vec::new(),
+        }
+    }
+
+    pub fn qualifier(&self) -> &Ident {
+        &self.qualifier
+    }
+
+    pub fn table_ref(&self) -> &TableRef {
+        &self.table_ref
+    }
+
+    pub fn push_column_ref(&mut self, column: Ident, column_ref: ColumnRef) {
+        self.column_mapping.insert(column, column_ref);
+    }
+
+    pub fn push_where_expr(&mut self, where_expr: Expression) {
+        self.where_exprs.push(where_expr);
+    }
+
+    /// The plan filtering the table and selecting the columns needed by the join
+    fn to_proof_plan(&self) -> ConversionResult<DynProofPlan> {
+        let where_expr =
+            self.where_exprs
+                .iter()
+                .cloned()
+                .reduce(|left, right| Expression::Binary {
+                    op: BinaryOperator::And,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                });
+        let where_clause = WhereExprBuilder::new(&self.column_mapping)
+            .build(where_expr.map(Box::new))?
+            .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
+        let aliased_results = self
+            .column_mapping
+            .iter()
+            .map(|(alias, column_ref)| AliasedDynProofExpr {
+                expr: DynProofExpr::new_column(column_ref.clone()),
+                alias: alias.clone(),
+            })
+            .collect();
+        Ok(DynProofPlan::new_filter(
+            aliased_results,
+            TableExpr {
+                table_ref: self.table_ref.clone(),
+            },
+            where_clause,
+        ))
+    }
+}
+
+/// A join of two tables in the `FROM` clause of a query
+#[derive(Debug, Clone)]
+pub(crate) struct JoinContext {
+    join_type: JoinType,
+    left: JoinTable,
+    right: JoinTable,
+    /// The names of the pairs of columns the tables are joined on
+    join_columns: Vec<(Ident, Ident)>,
+}
+
+impl JoinContext {
+    pub fn new(join_type: JoinType, left: JoinTable, right: JoinTable) -> Self {
+        Self {
+            join_type,
+            left,
+            right,
+            join_columns: Vec::new(),
+        }
+    }
+
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    /// Whether the rows of the result can have NULLs in place of the columns of the given side,
+    /// i.e. whether the other side is preserved by an outer join.
+    pub fn is_padded(&self, side: JoinSide) -> bool {
+        match side {
+            JoinSide::Left => self.join_type.preserves_right(),
+            JoinSide::Right => self.join_type.preserves_left(),
+        }
+    }
+
+    pub fn table(&self, side: JoinSide) -> &JoinTable {
+        match side {
+            JoinSide::Left => &self.left,
+            JoinSide::Right => &self.right,
+        }
+    }
+
+    pub fn table_mut(&mut self, side: JoinSide) -> &mut JoinTable {
+        match side {
+            JoinSide::Left => &mut self.left,
+            JoinSide::Right => &mut self.right,
+        }
+    }
+
+    /// Adds a pair of columns to join on given their names and their identifiers in the join inputs.
+    ///
+    /// Must be called before any other column is added to the tables.
+    pub fn push_join_columns(
+        &mut self,
+        (left_column, left_ident, left_column_ref): (Ident, Ident, ColumnRef),
+        (right_column, right_ident, right_column_ref): (Ident, Ident, ColumnRef),
+    ) {
+        self.left.push_column_ref(left_ident, left_column_ref);
+        self.right.push_column_ref(right_ident, right_column_ref);
+        self.join_columns.push((left_column, right_column));
+    }
+
+    /// Returns the names of the pairs of columns the tables are joined on.
+    pub fn join_columns(&self) -> &[(Ident, Ident)] {
+        &self.join_columns
+    }
+
+    /// Returns the position of `column` among the join columns of the given side, if it is one.
+    pub fn join_column_index(&self, side: JoinSide, column: &Ident) -> Option<usize> {
+        self.join_columns
+            .iter()
+            .position(|(left_column, right_column)| match side {
+                JoinSide::Left => left_column == column,
+                JoinSide::Right => right_column == column,
+            })
+    }
+
+    /// Returns the side a column of the join result comes from
+    pub fn side_of(&self, column: &Ident) -> Option<JoinSide> {
+        if self.left.column_mapping.contains_key(column) {
+            Some(JoinSide::Left)
+        } else if self.right.column_mapping.contains_key(column) {
+            Some(JoinSide::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the column a column of the join result comes from
+    pub fn get_column_ref(&self, column: &Ident) -> Option<&ColumnRef> {
+        self.left
+            .column_mapping
+            .get(column)
+            .or_else(|| self.right.column_mapping.get(column))
+    }
+}
+
+/// Converts a `JoinContext` into a `SortMergeJoinExec` over filters of the two tables.
+impl TryFrom<&JoinContext> for DynProofPlan {
+    type Error = ConversionError;
+
+    fn try_from(value: &JoinContext) -> ConversionResult<DynProofPlan> {
+        let num_join_columns = value.join_columns.len();
+        let result_idents = value
+            .left
+            .column_mapping
+            .keys()
+            .chain(value.right.column_mapping.keys().skip(num_join_columns))
+            .cloned()
+            .collect();
+        Ok(DynProofPlan::new_sort_merge_join_with_join_type(
+            value.left.to_proof_plan()?,
+            value.right.to_proof_plan()?,
+            (0..num_join_columns).collect(),
+            (0..num_join_columns).collect(),
+            result_idents,
+            value.join_type,
+        ))
+    }
+}
@@ -18,10 +18,15 @@ pub(crate) use filter_exec_builder::FilterExecBuilder;
 
 /// TODO: add docs
 pub(crate) mod query_context;
-pub(crate) use query_context::QueryContext;
+pub(crate) use query_context::{InSubquery, QueryContext};
+
+mod join_context;
+pub(crate) use join_context::{JoinContext, JoinSide, JoinTable};
 
 mod query_context_builder;
-pub(crate) use query_context_builder::{type_check_binary_operation, QueryContextBuilder};
+pub(crate) use query_context_builder::{
+    try_cast_to_data_type, type_check_binary_operation, QueryContextBuilder,
+};
 
 mod dyn_proof_expr_builder;
 pub(crate) use dyn_proof_expr_builder::DynProofExprBuilder;
@@ -1,20 +1,37 @@
 use crate::{
     base::{
-        database::{order_by_util::OrderIndexDirectionPairs, ColumnRef, LiteralValue, TableRef},
+        database::{
+            order_by_util::OrderIndexDirectionPairs, ColumnRef, ColumnType, LiteralValue, TableRef,
+        },
         map::{IndexMap, IndexSet},
+        math::i256::I256,
     },
     sql::{
-        parse::{ConversionError, ConversionResult, DynProofExprBuilder, WhereExprBuilder},
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
-        proof_plans::GroupByExec,
+        parse::{
+            ConversionError, ConversionResult, DynProofExprBuilder, JoinContext, JoinSide,
+            WhereExprBuilder,
+        },
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_plans::{DynProofPlan, GroupByExec, JoinType},
     },
 };
-use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
+use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec, vec::Vec};
 use proof_of_sql_parser::intermediate_ast::{
     AggregationOperator, AliasedResultExpr, Expression, Slice,
 };
 use sqlparser::ast::Ident;
 
+/// A conjunct `column [NOT] IN (subquery)` of the `WHERE` clause of a query
+#[derive(Debug)]
+pub(crate) struct InSubquery {
+    /// The column that is looked up in the result of the subquery
+    pub column: Ident,
+    /// The plan of the subquery, which returns a single column
+    pub plan: DynProofPlan,
+    /// Whether this is `NOT IN`
+    pub negated: bool,
+}
+
 #[derive(Default, Debug)]
 pub struct QueryContext {
     in_agg_scope: bool,
@@ -22,21 +39,28 @@ pub struct QueryContext {
     slice_expr: Option<Slice>,
     col_ref_counter: usize,
     table: Option<TableRef>,
+    table_qualifier: Option<Ident>,
+    join: Option<JoinContext>,
+    derived_table: Option<DynProofPlan>,
+    in_subqueries: Vec<InSubquery>,
     in_result_scope: bool,
     has_visited_group_by: bool,
     order_by_exprs: OrderIndexDirectionPairs,
     group_by_exprs: Vec<Ident>,
+    group_by_key_exprs: IndexMap<Ident, (Expression, ColumnType)>,
     where_expr: Option<Box<Expression>>,
+    having_expr: Option<Box<Expression>>,
     result_column_set: IndexSet<Ident>,
     res_aliased_exprs: Vec<AliasedResultExpr>,
     column_mapping: IndexMap<Ident, ColumnRef>,
     first_result_col_out_agg_scope: Option<Ident>,
+    is_distinct: bool,
 }
 
 impl QueryContext {
     #[allow(clippy::missing_panics_doc)]
     pub fn set_table_ref(&mut self, table: TableRef) {
-        assert!(self.table.is_none());
+        assert!(self.table.is_none() && self.join.is_none());
         self.table = Some(table);
     }
 
@@ -47,6 +71,49 @@ impl QueryContext {
             .expect("Table should already have been set")
     }
 
+    /// Sets the identifier columns of the table can be qualified with, i.e. its alias or its name.
+    pub fn set_table_qualifier(&mut self, qualifier: Ident) {
+        self.table_qualifier = Some(qualifier);
+    }
+
+    pub fn get_table_qualifier(&self) -> Option<&Ident> {
+        self.table_qualifier.as_ref()
+    }
+
+    #[allow(clippy::missing_panics_doc)]
+    pub fn set_join(&mut self, join: JoinContext) {
+        assert!(self.table.is_none() && self.join.is_none());
+        self.join = Some(join);
+    }
+
+    /// Returns the join in the `FROM` clause, if the query is over a join of tables.
+    pub fn get_join(&self) -> Option<&JoinContext> {
+        self.join.as_ref()
+    }
+
+    pub fn get_join_mut(&mut self) -> Option<&mut JoinContext> {
+        self.join.as_mut()
+    }
+
+    /// Sets the plan of the subquery in the `FROM` clause, whose result is the table of the query.
+    pub fn set_derived_table(&mut self, plan: DynProofPlan) {
+        self.derived_table = Some(plan);
+    }
+
+    /// Returns the plan of the subquery in the `FROM` clause, if the query is over a derived table.
+    pub fn get_derived_table(&self) -> Option<&DynProofPlan> {
+        self.derived_table.as_ref()
+    }
+
+    pub(crate) fn push_in_subquery(&mut self, in_subquery: InSubquery) {
+        self.in_subqueries.push(in_subquery);
+    }
+
+    /// Returns the `[NOT] IN (subquery)` conjuncts of the `WHERE` clause.
+    pub(crate) fn get_in_subqueries(&self) -> &[InSubquery] {
+        &self.in_subqueries
+    }
+
     pub fn set_where_expr(&mut self, where_expr: Option<Box<Expression>>) {
         self.where_expr = where_expr;
     }
@@ -56,6 +123,24 @@ impl QueryContext {
         &self.where_expr
     }
 
+    pub fn set_having_expr(&mut self, having_expr: Option<Box<Expression>>) {
+        self.having_expr = having_expr;
+    }
+
+    #[allow(clippy::ref_option)]
+    pub fn get_having_expr(&self) -> &Option<Box<Expression>> {
+        &self.having_expr
+    }
+
+    pub fn set_distinct(&mut self, is_distinct: bool) {
+        self.is_distinct = is_distinct;
+    }
+
+    /// Whether the duplicate rows of the result are removed, i.e. whether this is a `SELECT DISTINCT` query
+    pub fn is_distinct(&self) -> bool {
+        self.is_distinct
+    }
+
     pub fn set_slice_expr(&mut self, slice_expr: Option<Slice>) {
         self.slice_expr = slice_expr;
     }
@@ -107,6 +192,16 @@ impl QueryContext {
         self.column_mapping.insert(column, column_ref);
     }
 
+    /// Adds the presence column of a nullable column to the column mapping.
+    pub fn push_presence_column_ref(&mut self, column: Ident, column_ref: ColumnRef) {
+        self.column_mapping.insert(column, column_ref);
+    }
+
+    /// Adds an encoding column of a `VarChar` column to the column mapping.
+    pub fn push_varchar_encoding_column_ref(&mut self, column: Ident, column_ref: ColumnRef) {
+        self.column_mapping.insert(column, column_ref);
+    }
+
     fn push_result_column_ref(&mut self, column: Ident) {
         if self.is_in_result_scope() {
             self.result_column_set.insert(column.clone());
@@ -137,6 +232,16 @@ impl QueryContext {
         self.has_visited_group_by = true;
     }
 
+    /// Adds a `GROUP BY` expression that is not a column, which is known as `ident` in the query.
+    pub fn push_group_by_key_expr(&mut self, ident: Ident, expr: Expression, dtype: ColumnType) {
+        self.group_by_key_exprs.insert(ident, (expr, dtype));
+    }
+
+    /// Returns the `GROUP BY` expressions that are not columns, keyed by the identifiers they are known as.
+    pub fn get_group_by_key_exprs(&self) -> &IndexMap<Ident, (Expression, ColumnType)> {
+        &self.group_by_key_exprs
+    }
+
     pub fn set_order_by_exprs(&mut self, order_by_exprs: OrderIndexDirectionPairs) {
         self.order_by_exprs = order_by_exprs;
     }
@@ -216,56 +321,94 @@ impl QueryContext {
     pub fn get_column_mapping(&self) -> IndexMap<Ident, ColumnRef> {
         self.column_mapping.clone()
     }
+
+    /// Returns the columns of the result of the join of the query, which are named after their
+    /// identifiers, or `None` if the query is not over an inner join.
+    ///
+    /// The presence columns of nullable columns are included.
+    pub fn get_inner_join_column_mapping(&self) -> Option<IndexMap<Ident, ColumnRef>> {
+        if self.join.as_ref()?.join_type() != JoinType::Inner {
+            return None;
+        }
+        Some(
+            self.column_mapping
+                .iter()
+                .map(|(ident, column_ref)| {
+                    (
+                        ident.clone(),
+                        ColumnRef::new(
+                            column_ref.table_ref(),
+                            ident.clone(),
+                            *column_ref.column_type(),
+                        ),
+                    )
+                })
+                .collect(),
+        )
+    }
 }
 
+/// The alias of the `COUNT(*)` column of a `GroupByExec` whose last result column is a null-aware count
+///
+/// `$` can not appear in an identifier parsed from SQL, so the alias can not clash with the result aliases.
+const HIDDEN_COUNT_IDENT: &str = "$count";
+
 /// Converts a `QueryContext` into an `Option<GroupByExec>`.
 ///
 /// We use Some if the query is provable and None if it is not
 /// We error out if the query is wrong
+///
+/// A query over an inner join aggregates the result of the join, whose filters are applied
+/// to the joined tables.
+///
+/// Nulls are ignored by the aggregations: `SUM(expr)` of a nullable `expr` is the sum of
+/// `expr * presence`, and `COUNT(expr)` is the sum of the presence of `expr`. The latter
+/// is a sum column, so if it is the last result column the plan has a hidden `COUNT(*)`
+/// column named `$count` instead.
 impl TryFrom<&QueryContext> for Option<GroupByExec> {
     type Error = ConversionError;
 
+    #[allow(clippy::too_many_lines)]
     fn try_from(value: &QueryContext) -> Result<Option<GroupByExec>, Self::Error> {
-        let where_clause = WhereExprBuilder::new(&value.column_mapping)
+        let (column_mapping, table_ref) = match &value.join {
+            Some(join) => match value.get_inner_join_column_mapping() {
+                Some(column_mapping) => (
+                    column_mapping,
+                    join.table(JoinSide::Left).table_ref().clone(),
+                ),
+                None => return Ok(None),
+            },
+            None => (
+                value.column_mapping.clone(),
+                value
+                    .table
+                    .clone()
+                    .ok_or(ConversionError::InvalidExpression {
+                        expression: "QueryContext has no table_ref".to_owned(),
+                    })?,
+            ),
+        };
+        let where_clause = WhereExprBuilder::new(&column_mapping)
             .build(value.where_expr.clone())?
             .unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true)));
-        let table = value
-            .table
-            .as_ref()
-            .map(|table_ref| TableExpr {
-                table_ref: table_ref.clone(),
-            })
-            .ok_or(ConversionError::InvalidExpression {
-                expression: "QueryContext has no table_ref".to_owned(),
-            })?;
+        let nullable_columns = DynProofExprBuilder::new(&column_mapping).nullable_columns();
 
-        let group_by_exprs = value
-            .group_by_exprs
-            .iter()
-            .map(|expr| -> Result<ColumnExpr, ConversionError> {
-                value
-                    .column_mapping
-                    .get(expr)
-                    .ok_or_else(|| ConversionError::MissingColumn {
-                        identifier: Box::new(expr.clone()),
-                        table_ref: table.table_ref.clone(),
-                    })
-                    .map(|column_ref| ColumnExpr::new(column_ref.clone()))
-            })
-            .collect::<Result<Vec<ColumnExpr>, ConversionError>>()?;
         // For a query to be provable the result columns must be of one of three kinds below:
         // 1. Group by columns (it is mandatory to have all of them in the correct order)
-        // 2. Sum(expr) expressions (it is optional to have any)
+        // 2. Sum(expr), then max(expr), then min(expr) expressions (it is optional to have any)
         // 3. count(*) with an alias (it is mandatory to have one and only one)
-        let num_group_by_columns = group_by_exprs.len();
+        // A null-aware count is a sum, which may also be the last column.
+        let num_group_by_columns = value.group_by_exprs.len();
         let num_result_columns = value.res_aliased_exprs.len();
         if num_result_columns < num_group_by_columns + 1 {
             return Ok(None);
         }
         let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns].to_vec();
-        let sum_expr_columns =
+        let aggregate_expr_columns =
             &value.res_aliased_exprs[num_group_by_columns..num_result_columns - 1].to_vec();
-        // Check group by columns
+        let count_column = &value.res_aliased_exprs[num_result_columns - 1];
+        let null_aware_count = build_null_aware_count(count_column, &column_mapping);
+        // Check group by columns and expressions
         let group_by_compliance = value
             .group_by_exprs
             .iter()
@@ -277,32 +420,101 @@ impl TryFrom<&QueryContext> for Option<GroupByExec> {
                     false
                 }
             });
+        if !group_by_compliance {
+            return Ok(None);
+        }
+        let group_by_exprs = value
+            .group_by_exprs
+            .iter()
+            .zip(res_group_by_columns.iter())
+            .map(
+                |(ident, res)| -> Result<AliasedDynProofExpr, ConversionError> {
+                    let expr = match value.group_by_key_exprs.get(ident) {
+                        Some((key_expr, _)) => {
+                            DynProofExprBuilder::new(&column_mapping).build(key_expr)?
+                        }
+                        None => column_mapping
+                            .get(ident)
+                            .ok_or_else(|| ConversionError::MissingColumn {
+                                identifier: Box::new(ident.clone()),
+                                table_ref: table_ref.clone(),
+                            })
+                            .map(|column_ref| DynProofExpr::new_column(column_ref.clone()))?,
+                    };
+                    Ok(AliasedDynProofExpr {
+                        alias: res.alias.into(),
+                        expr,
+                    })
+                },
+            )
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        // Null keys are not told apart from the default value of the key
+        if group_by_exprs
+            .iter()
+            .any(|aliased_expr| aliased_expr.expr.presence_expr(&nullable_columns).is_some())
+        {
+            return Ok(None);
+        }
 
-        // Check sums
-        let sum_expr = sum_expr_columns
+        // Check sums, maxes and mins
+        let aggregate_exprs = aggregate_expr_columns
             .iter()
             .map(|res| {
                 if let Expression::Aggregation {
-                    op: AggregationOperator::Sum,
+                    op: AggregationOperator::Count,
                     ..
+                } = *res.expr
+                {
+                    return build_null_aware_count(res, &column_mapping)
+                        .map(|count| (AggregationOperator::Sum, count));
+                }
+                if let Expression::Aggregation {
+                    op:
+                        op @ (AggregationOperator::Sum
+                        | AggregationOperator::Max
+                        | AggregationOperator::Min),
+                    expr,
                 } = (*res.expr).clone()
                 {
-                    let res_dyn_proof_expr =
-                        DynProofExprBuilder::new(&value.column_mapping).build(&res.expr);
-                    res_dyn_proof_expr
-                        .ok()
-                        .map(|dyn_proof_expr| AliasedDynProofExpr {
+                    let dyn_proof_expr = DynProofExprBuilder::new(&column_mapping)
+                        .build(&res.expr)
+                        .ok()?;
+                    let dyn_proof_expr = match (op, dyn_proof_expr.presence_expr(&nullable_columns))
+                    {
+                        (_, None) => dyn_proof_expr,
+                        // Nulls are ignored by SUM, so the sum of `expr * presence` is proven
+                        (AggregationOperator::Sum, Some(presence)) => {
+                            let expr = DynProofExprBuilder::new(&column_mapping)
+                                .build(&expr)
+                                .ok()?;
+                            let zero = DynProofExpr::new_literal(zero_literal(expr.data_type())?);
+                            DynProofExpr::new_aggregate(
+                                AggregationOperator::Sum,
+                                DynProofExpr::try_new_case(vec![(presence, expr)], zero).ok()?,
+                            )
+                        }
+                        // The maximum and minimum of nullable expressions are not proven yet
+                        (_, Some(_)) => return None,
+                    };
+                    Some((
+                        op,
+                        AliasedDynProofExpr {
                             alias: res.alias.into(),
                             expr: dyn_proof_expr,
-                        })
+                        },
+                    ))
                 } else {
                     None
                 }
             })
-            .collect::<Option<Vec<AliasedDynProofExpr>>>();
+            .collect::<Option<Vec<_>>>();
+        let aggregate_order_compliance = aggregate_exprs.as_ref().is_some_and(|exprs| {
+            exprs
+                .windows(2)
+                .all(|pair| aggregate_rank(pair[0].0) <= aggregate_rank(pair[1].0))
+        });
 
         // Check count(*)
-        let count_column = &value.res_aliased_exprs[num_result_columns - 1];
         let count_column_compliant = matches!(
             *count_column.expr,
             Expression::Aggregation {
@@ -311,15 +523,97 @@ impl TryFrom<&QueryContext> for Option<GroupByExec> {
             }
         );
 
-        if !group_by_compliance || sum_expr.is_none() || !count_column_compliant {
+        if !aggregate_order_compliance || !count_column_compliant {
             return Ok(None);
         }
-        Ok(Some(GroupByExec::new(
-            group_by_exprs,
-            sum_expr.expect("the none case was just checked"),
-            count_column.alias.into(),
-            table,
-            where_clause,
-        )))
+        let (mut sum_expr, mut max_expr, mut min_expr) = (vec![], vec![], vec![]);
+        for (op, aliased_expr) in aggregate_exprs.expect("the none case was just checked") {
+            match op {
+                AggregationOperator::Max => max_expr.push(aliased_expr),
+                AggregationOperator::Min => min_expr.push(aliased_expr),
+                _ => sum_expr.push(aliased_expr),
+            }
+        }
+        let count_alias = match null_aware_count {
+            Some(count) => {
+                sum_expr.push(count);
+                Ident::new(HIDDEN_COUNT_IDENT)
+            }
+            None => count_column.alias.into(),
+        };
+        Ok(Some(match &value.join {
+            Some(join) => GroupByExec::new_with_input(
+                group_by_exprs,
+                sum_expr,
+                max_expr,
+                min_expr,
+                count_alias,
+                Box::new(DynProofPlan::try_from(join)?),
+                where_clause,
+            ),
+            None => GroupByExec::new(
+                group_by_exprs,
+                sum_expr,
+                max_expr,
+                min_expr,
+                count_alias,
+                TableExpr { table_ref },
+                where_clause,
+            ),
+        }))
+    }
+}
+
+/// Builds the sum column proving `COUNT(expr)` of a nullable `expr`, which is the sum of the presence of `expr`.
+///
+/// Returns `None` if `res` is not the count of a nullable expression.
+fn build_null_aware_count(
+    res: &AliasedResultExpr,
+    column_mapping: &IndexMap<Ident, ColumnRef>,
+) -> Option<AliasedDynProofExpr> {
+    let Expression::Aggregation {
+        op: AggregationOperator::Count,
+        expr,
+    } = res.expr.as_ref()
+    else {
+        return None;
+    };
+    let builder = DynProofExprBuilder::new(column_mapping);
+    let presence = builder
+        .build(expr)
+        .ok()?
+        .presence_expr(&builder.nullable_columns())?;
+    Some(AliasedDynProofExpr {
+        alias: res.alias.into(),
+        expr: DynProofExpr::new_aggregate(
+            AggregationOperator::Sum,
+            DynProofExpr::try_new_cast(presence, ColumnType::BigInt).ok()?,
+        ),
+    })
+}
+
+/// The zero of a numeric column type
+fn zero_literal(column_type: ColumnType) -> Option<LiteralValue> {
+    match column_type {
+        ColumnType::Uint8 => Some(LiteralValue::Uint8(0)),
+        ColumnType::TinyInt => Some(LiteralValue::TinyInt(0)),
+        ColumnType::SmallInt => Some(LiteralValue::SmallInt(0)),
+        ColumnType::Int => Some(LiteralValue::Int(0)),
+        ColumnType::BigInt => Some(LiteralValue::BigInt(0)),
+        ColumnType::Int128 => Some(LiteralValue::Int128(0)),
+        ColumnType::Decimal75(precision, scale) => {
+            Some(LiteralValue::Decimal75(precision, scale, I256::from(0)))
+        }
+        ColumnType::Scalar => Some(LiteralValue::Scalar([0; 4])),
+        _ => None,
+    }
+}
+
+/// The position of an aggregate among the result columns of a `GroupByExec`
+fn aggregate_rank(op: AggregationOperator) -> u8 {
+    match op {
+        AggregationOperator::Max => 1,
+        AggregationOperator::Min => 2,
+        _ => 0,
     }
 }
@@ -1,27 +1,64 @@
-use super::{ConversionError, ConversionResult, QueryContext};
-use crate::base::{
-    database::{
-        try_add_subtract_column_types, try_multiply_column_types, ColumnRef, ColumnType,
-        SchemaAccessor, TableRef,
-    },
-    map::IndexSet,
-    math::{
-        decimal::{DecimalError, Precision},
-        BigDecimalExt,
+use super::{
+    ConversionError, ConversionResult, InSubquery, JoinContext, JoinSide, JoinTable, QueryContext,
+    QueryExpr,
+};
+use crate::{
+    base::{
+        database::{
+            is_presence_column_ident, is_varchar_encoding_column_ident, presence_column_ident,
+            presence_column_ref, try_add_interval_column_type, try_add_subtract_column_types,
+            try_case_column_types, try_cast_column_types, try_date_trunc_column_type,
+            try_divide_column_types, try_extract_column_type, try_modulo_column_types,
+            try_multiply_column_types, varchar_key_column_ident, varchar_length_column_ident,
+            ColumnRef, ColumnType, SchemaAccessor, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        math::{
+            decimal::{DecimalError, Precision},
+            BigDecimalExt,
+        },
     },
+    sql::proof_plans::{DynProofPlan, JoinType},
 };
-use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use core::iter;
 use proof_of_sql_parser::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, Expression, Literal, SelectResultExpr, Slice,
-        TableExpression,
+        AggregationOperator, AliasedResultExpr, BinaryOperator as PoSqlBinaryOperator, DataType,
+        Expression, JoinType as PoSqlJoinType, Literal, SelectResultExpr, Slice, TableExpression,
     },
-    Identifier,
+    posql_time::PoSQLTimeZone,
+    Identifier, SelectStatement,
 };
 use sqlparser::ast::{BinaryOperator, Expr, Ident, OrderByExpr, UnaryOperator};
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
+    schema_accessor: DerivedTableSchemaAccessor<'a>,
+}
+
+/// A [`SchemaAccessor`] that also knows the schemas of the subqueries in the `FROM` clause of a query
+struct DerivedTableSchemaAccessor<'a> {
     schema_accessor: &'a dyn SchemaAccessor,
+    derived_tables: IndexMap<TableRef, Vec<(Ident, ColumnType)>>,
+}
+
+impl SchemaAccessor for DerivedTableSchemaAccessor<'_> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Ident) -> Option<ColumnType> {
+        match self.derived_tables.get(&table_ref) {
+            Some(schema) => schema
+                .iter()
+                .find(|(column, _)| *column == column_id)
+                .map(|(_, column_type)| *column_type),
+            None => self.schema_accessor.lookup_column(table_ref, column_id),
+        }
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Ident, ColumnType)> {
+        match self.derived_tables.get(&table_ref) {
+            Some(schema) => schema.clone(),
+            None => self.schema_accessor.lookup_schema(table_ref),
+        }
+    }
 }
 
 // Public interface
@@ -29,7 +66,10 @@ impl<'a> QueryContextBuilder<'a> {
     pub fn new(schema_accessor: &'a dyn SchemaAccessor) -> Self {
         Self {
             context: QueryContext::default(),
-            schema_accessor,
+            schema_accessor: DerivedTableSchemaAccessor {
+                schema_accessor,
+                derived_tables: IndexMap::default(),
+            },
         }
     }
 
@@ -38,28 +78,77 @@ impl<'a> QueryContextBuilder<'a> {
         mut self,
         table_expr: &[Box<TableExpression>],
         default_schema: Ident,
-    ) -> Self {
+    ) -> ConversionResult<Self> {
         assert_eq!(table_expr.len(), 1);
 
-        match *table_expr[0] {
-            TableExpression::Named { table, schema } => {
-                let actual_schema = schema.map_or(default_schema, Ident::from);
-                let table_ref = TableRef::from_idents(Some(actual_schema), Ident::from(table));
+        match table_expr[0].as_ref() {
+            TableExpression::Named {
+                table,
+                schema,
+                alias,
+            } => {
+                self.context
+                    .set_table_ref(named_table_ref(*table, *schema, default_schema));
+                self.context
+                    .set_table_qualifier(alias.unwrap_or(*table).into());
+            }
+            TableExpression::Join {
+                left,
+                right,
+                join_type,
+                on,
+            } => self.visit_join(left, right, *join_type, on, &default_schema)?,
+            TableExpression::Subquery { query, alias } => {
+                let plan = self.plan_subquery(query, default_schema)?;
+                let table_ref = TableRef::from_idents(None, (*alias).into());
+                let schema = plan
+                    .get_column_result_fields()
+                    .iter()
+                    .map(|field| (field.name(), field.data_type()))
+                    .collect();
+                self.schema_accessor
+                    .derived_tables
+                    .insert(table_ref.clone(), schema);
                 self.context.set_table_ref(table_ref);
+                self.context.set_table_qualifier((*alias).into());
+                self.context.set_derived_table(plan);
             }
         }
 
-        self
+        Ok(self)
     }
 
+    /// Visits the `WHERE` clause.
+    ///
+    /// Its `[NOT] IN (subquery)` conjuncts are planned separately, with `default_schema`
+    /// as the default schema of the tables of the subqueries.
     pub fn visit_where_expr(
         mut self,
-        mut where_expr: Option<Box<Expression>>,
+        where_expr: Option<Box<Expression>>,
+        default_schema: Ident,
     ) -> ConversionResult<Self> {
-        if let Some(expr) = where_expr.as_deref_mut() {
+        let where_expr = where_expr
+            .map(|expr| self.visit_in_subqueries(*expr, &default_schema))
+            .transpose()?
+            .flatten();
+        let where_expr = where_expr
+            .map(|expr| self.resolve_columns(&expr).map(Box::new))
+            .transpose()?;
+        if where_expr.is_some() && self.context.get_derived_table().is_some() {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "Filters on subqueries in FROM are not supported yet".to_string(),
+            });
+        }
+        if let Some(expr) = where_expr.as_deref() {
             self.visit_expr(expr)?;
         }
-        self.context.set_where_expr(where_expr);
+        if self.context.get_join().is_some() {
+            if let Some(expr) = where_expr {
+                self.push_join_where_expr(*expr)?;
+            }
+        } else {
+            self.context.set_where_expr(where_expr);
+        }
         Ok(self)
     }
 
@@ -132,11 +221,73 @@ impl<'a> QueryContextBuilder<'a> {
         self
     }
 
-    pub fn visit_group_by_exprs(mut self, group_by_exprs: Vec<Ident>) -> ConversionResult<Self> {
-        for id in &group_by_exprs {
-            self.visit_column_identifier(id)?;
+    pub fn visit_distinct(mut self, distinct: bool) -> Self {
+        self.context.set_distinct(distinct);
+        self
+    }
+
+    /// Visits the `GROUP BY` expressions.
+    ///
+    /// Expressions other than columns are known as hidden identifiers in the rest of the query,
+    /// and the result expressions refer to them wherever they contain a `GROUP BY` expression.
+    pub fn visit_group_by_exprs(
+        mut self,
+        group_by_exprs: Vec<Box<Expression>>,
+    ) -> ConversionResult<Self> {
+        let mut resolved_group_by_exprs = Vec::with_capacity(group_by_exprs.len());
+        for (index, expr) in group_by_exprs.into_iter().enumerate() {
+            let id = match *expr {
+                Expression::Column(column) => self.resolve_column(None, &column.into())?,
+                Expression::QualifiedColumn { table, column } => {
+                    self.resolve_column(Some(&table.into()), &column.into())?
+                }
+                expr => {
+                    let expr = self.resolve_columns(&expr)?;
+                    let dtype = self.visit_expr(&expr)?;
+                    // No GROUP BY expression has been set yet, so only aggregations count
+                    if self.context.has_agg() {
+                        return Err(ConversionError::InvalidExpression {
+                            expression: "aggregations are not allowed in the GROUP BY clause"
+                                .to_string(),
+                        });
+                    }
+                    let id = group_by_key_ident(index);
+                    self.context.push_group_by_key_expr(id.clone(), expr, dtype);
+                    resolved_group_by_exprs.push(id);
+                    continue;
+                }
+            };
+            self.visit_column_identifier(&id)?;
+            resolved_group_by_exprs.push(id);
         }
-        self.context.set_group_by_exprs(group_by_exprs);
+        self.context.set_group_by_exprs(resolved_group_by_exprs);
+        Ok(self)
+    }
+
+    /// Visits the `HAVING` clause.
+    ///
+    /// Like the result expressions, it refers to the `GROUP BY` expressions by their identifiers.
+    pub fn visit_having_expr(
+        mut self,
+        having_expr: Option<Box<Expression>>,
+    ) -> ConversionResult<Self> {
+        let having_expr = having_expr
+            .map(|expr| -> ConversionResult<_> {
+                let expr = replace_group_by_key_exprs(
+                    &self.resolve_columns(&expr)?,
+                    self.context.get_group_by_key_exprs(),
+                )?;
+                // Columns outside aggregations are checked in the same way as those of the result
+                self.context.toggle_result_scope();
+                let dtype = self.visit_expr(&expr);
+                self.context.toggle_result_scope();
+                match dtype? {
+                    ColumnType::Boolean => Ok(Box::new(expr)),
+                    datatype => Err(ConversionError::NonbooleanHavingClause { datatype }),
+                }
+            })
+            .transpose()?;
+        self.context.set_having_expr(having_expr);
         Ok(self)
     }
 
@@ -159,36 +310,698 @@ impl QueryContextBuilder<'_> {
         columns
     }
 
+    /// Plans a subquery, which has to be provable without postprocessing.
+    fn plan_subquery(
+        &self,
+        query: &SelectStatement,
+        default_schema: Ident,
+    ) -> ConversionResult<DynProofPlan> {
+        let query_expr = QueryExpr::try_new(
+            query.clone(),
+            default_schema,
+            self.schema_accessor.schema_accessor,
+        )?;
+        if !query_expr.postprocessing().is_empty() {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "Subqueries whose result is not fully provable are not supported yet"
+                    .to_string(),
+            });
+        }
+        Ok(query_expr.proof_expr().clone())
+    }
+
+    /// Visits the `[NOT] IN (subquery)` conjuncts of the `WHERE` clause
+    /// and returns the remaining conjuncts, if any.
+    fn visit_in_subqueries(
+        &mut self,
+        where_expr: Expression,
+        default_schema: &Ident,
+    ) -> ConversionResult<Option<Expression>> {
+        match where_expr {
+            Expression::Binary {
+                op: PoSqlBinaryOperator::And,
+                left,
+                right,
+            } => Ok(
+                match (
+                    self.visit_in_subqueries(*left, default_schema)?,
+                    self.visit_in_subqueries(*right, default_schema)?,
+                ) {
+                    (Some(left), Some(right)) => Some(Expression::Binary {
+                        op: PoSqlBinaryOperator::And,
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    }),
+                    (conjunct, None) | (None, conjunct) => conjunct,
+                },
+            ),
+            Expression::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                self.visit_in_subquery(&expr, &subquery, negated, default_schema)?;
+                Ok(None)
+            }
+            conjunct => Ok(Some(conjunct)),
+        }
+    }
+
+    /// Visits a conjunct `column [NOT] IN (subquery)` of the `WHERE` clause.
+    ///
+    /// The subquery has to return a single column of the same type as `column`.
+    fn visit_in_subquery(
+        &mut self,
+        expr: &Expression,
+        subquery: &SelectStatement,
+        negated: bool,
+        default_schema: &Ident,
+    ) -> ConversionResult<()> {
+        if self.context.get_join().is_some() {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "IN (subquery) over joins is not supported yet".to_string(),
+            });
+        }
+        let Expression::Column(column) = self.resolve_columns(expr)? else {
+            return Err(ConversionError::UnsupportedOperation {
+                message: format!("IN (subquery) of {expr:?} is not supported yet"),
+            });
+        };
+        let column = Ident::from(column);
+        let column_type = self.visit_column_identifier(&column)?;
+        if self.is_nullable(self.context.get_table_ref(), &column) {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "IN (subquery) of nullable columns is not supported yet".to_string(),
+            });
+        }
+        let plan = self.plan_subquery(subquery, default_schema.clone())?;
+        let subquery_type = match plan.get_column_result_fields().as_slice() {
+            [field] => field.data_type(),
+            _ => {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "subqueries of IN must return exactly one column".to_string(),
+                })
+            }
+        };
+        if column_type != subquery_type {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: column_type.to_string(),
+                right_type: subquery_type.to_string(),
+            });
+        }
+        self.context.push_in_subquery(InSubquery {
+            column,
+            plan,
+            negated,
+        });
+        Ok(())
+    }
+
     fn visit_select_all_expr(&mut self) -> ConversionResult<()> {
-        for (column_name, _) in self.lookup_schema() {
-            let column_identifier = Identifier::try_from(column_name).map_err(|e| {
-                ConversionError::IdentifierConversionError {
-                    error: format!("Failed to convert Ident to Identifier: {e}"),
-                }
-            })?;
+        if self.context.get_join().is_some() {
+            return self.visit_join_select_all_expr();
+        }
+        for (column_name, _) in self.lookup_schema().into_iter().filter(|(column_name, _)| {
+            !is_presence_column_ident(column_name) && !is_varchar_encoding_column_ident(column_name)
+        }) {
+            let column_identifier = try_into_identifier(column_name)?;
             let col_expr = Expression::Column(column_identifier);
             self.visit_aliased_expr(AliasedResultExpr::new(col_expr, column_identifier))?;
         }
         Ok(())
     }
 
+    /// Selects all columns of both tables of a join.
+    ///
+    /// The join columns are only selected from one table since they equal those of the other table.
+    /// They are taken from the right table of a RIGHT JOIN and from the left table otherwise.
+    /// The result columns are named after their identifiers in the join result to keep them unique.
+    fn visit_join_select_all_expr(&mut self) -> ConversionResult<()> {
+        let join = self
+            .context
+            .get_join()
+            .expect("The query must be over a join");
+        let join_column_side = if join.join_type() == JoinType::Right {
+            JoinSide::Right
+        } else {
+            JoinSide::Left
+        };
+        let columns = [JoinSide::Left, JoinSide::Right]
+            .into_iter()
+            .flat_map(|side| {
+                let table = join.table(side);
+                self.schema_accessor
+                    .lookup_schema(table.table_ref().clone())
+                    .into_iter()
+                    .filter(move |(column, _)| {
+                        !is_presence_column_ident(column)
+                            && !is_varchar_encoding_column_ident(column)
+                            && (side == join_column_side
+                                || join.join_column_index(side, column).is_none())
+                    })
+                    .map(|(column, _)| (table.qualifier().clone(), column))
+            })
+            .collect::<Vec<_>>();
+        for (qualifier, column) in columns {
+            let alias = try_into_identifier(self.resolve_column(Some(&qualifier), &column)?)?;
+            let col_expr = Expression::QualifiedColumn {
+                table: try_into_identifier(qualifier)?,
+                column: try_into_identifier(column)?,
+            };
+            self.visit_aliased_expr(AliasedResultExpr::new(col_expr, alias))?;
+        }
+        Ok(())
+    }
+
     fn visit_aliased_expr(&mut self, aliased_expr: AliasedResultExpr) -> ConversionResult<()> {
-        self.visit_expr(&aliased_expr.expr)?;
-        self.context.push_aliased_result_expr(aliased_expr)?;
+        let expr = replace_group_by_key_exprs(
+            &self.resolve_columns(&aliased_expr.expr)?,
+            self.context.get_group_by_key_exprs(),
+        )?;
+        self.visit_expr(&expr)?;
+        self.context
+            .push_aliased_result_expr(AliasedResultExpr::new(expr, aliased_expr.alias))?;
         Ok(())
     }
 
+    /// Visits a join of two tables and its join condition.
+    fn visit_join(
+        &mut self,
+        left: &TableExpression,
+        right: &TableExpression,
+        join_type: PoSqlJoinType,
+        on: &Expression,
+        default_schema: &Ident,
+    ) -> ConversionResult<()> {
+        let [left, right] = [left, right].map(|table_expr| match table_expr {
+            TableExpression::Named {
+                table,
+                schema,
+                alias,
+            } => Ok(JoinTable::new(
+                alias.unwrap_or(*table).into(),
+                named_table_ref(*table, *schema, default_schema.clone()),
+            )),
+            TableExpression::Join { .. } => Err(ConversionError::UnsupportedOperation {
+                message: "Joins of more than two tables are not supported yet".to_string(),
+            }),
+            TableExpression::Subquery { .. } => Err(ConversionError::UnsupportedOperation {
+                message: "Joins of subqueries are not supported yet".to_string(),
+            }),
+        });
+        let (left, right) = (left?, right?);
+        if left.qualifier() == right.qualifier() {
+            return Err(ConversionError::InvalidExpression {
+                expression: format!(
+                    "table '{}' is joined with itself and needs an alias",
+                    left.qualifier()
+                ),
+            });
+        }
+        let join_type = match join_type {
+            PoSqlJoinType::Inner => JoinType::Inner,
+            PoSqlJoinType::Left => JoinType::Left,
+            PoSqlJoinType::Right => JoinType::Right,
+            PoSqlJoinType::Full => JoinType::Full,
+        };
+        self.context
+            .set_join(JoinContext::new(join_type, left, right));
+        self.visit_join_condition(on)
+    }
+
+    /// Visits the condition of a join, which has to be a conjunction of equalities
+    /// between a column of each of the two tables.
+    fn visit_join_condition(&mut self, on: &Expression) -> ConversionResult<()> {
+        match on {
+            Expression::Binary {
+                op: PoSqlBinaryOperator::And,
+                left,
+                right,
+            } => {
+                self.visit_join_condition(left)?;
+                self.visit_join_condition(right)
+            }
+            Expression::Binary {
+                op: PoSqlBinaryOperator::Equal,
+                left,
+                right,
+            } => match (
+                self.visit_join_column(left)?,
+                self.visit_join_column(right)?,
+            ) {
+                (
+                    (JoinSide::Left, left_column, left_type),
+                    (JoinSide::Right, right_column, right_type),
+                )
+                | (
+                    (JoinSide::Right, right_column, right_type),
+                    (JoinSide::Left, left_column, left_type),
+                ) => self.push_join_columns((left_column, left_type), (right_column, right_type)),
+                _ => Err(ConversionError::UnsupportedOperation {
+                    message: "Join conditions must compare a column of each table".to_string(),
+                }),
+            },
+            _ => Err(ConversionError::UnsupportedOperation {
+                message: format!("Join condition {on:?} is not supported yet"),
+            }),
+        }
+    }
+
+    /// Finds the table of a column compared in a join condition.
+    fn visit_join_column(
+        &self,
+        expr: &Expression,
+    ) -> ConversionResult<(JoinSide, Ident, ColumnType)> {
+        let (qualifier, column) = match expr {
+            Expression::Column(column) => (None, Ident::from(*column)),
+            Expression::QualifiedColumn { table, column } => {
+                (Some(Ident::from(*table)), Ident::from(*column))
+            }
+            _ => {
+                return Err(ConversionError::UnsupportedOperation {
+                    message: format!("Join condition on {expr:?} is not supported yet"),
+                })
+            }
+        };
+        let join = self
+            .context
+            .get_join()
+            .expect("The query must be over a join");
+        let (side, column_type) = self.lookup_join_column(join, qualifier.as_ref(), &column)?;
+        Ok((side, column, column_type))
+    }
+
+    fn push_join_columns(
+        &mut self,
+        (left_column, left_type): (Ident, ColumnType),
+        (right_column, right_type): (Ident, ColumnType),
+    ) -> ConversionResult<()> {
+        if left_type != right_type {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: left_type.to_string(),
+                right_type: right_type.to_string(),
+            });
+        }
+        let join = self
+            .context
+            .get_join()
+            .expect("The query must be over a join");
+        if join
+            .join_column_index(JoinSide::Left, &left_column)
+            .is_some()
+            || join
+                .join_column_index(JoinSide::Right, &right_column)
+                .is_some()
+        {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "Joining on a column more than once is not supported".to_string(),
+            });
+        }
+        let [left, right] = [
+            (JoinSide::Left, left_column, left_type),
+            (JoinSide::Right, right_column, right_type),
+        ]
+        .map(|(side, column, column_type)| {
+            let table_ref = join.table(side).table_ref().clone();
+            if self.is_nullable(&table_ref, &column) {
+                return Err(ConversionError::UnsupportedOperation {
+                    message: "Joining on nullable columns is not supported yet".to_string(),
+                });
+            }
+            let ident = self.join_result_ident(join, side, &column)?;
+            let column_ref = ColumnRef::new(table_ref, column.clone(), column_type);
+            Ok((column, ident, column_ref))
+        });
+        let (left, right) = (left?, right?);
+        self.context
+            .get_join_mut()
+            .expect("The query must be over a join")
+            .push_join_columns(left, right);
+        Ok(())
+    }
+
+    /// Pushes the conjuncts of the WHERE clause of a join down to the tables they reference.
+    fn push_join_where_expr(&mut self, where_expr: Expression) -> ConversionResult<()> {
+        match where_expr {
+            Expression::Binary {
+                op: PoSqlBinaryOperator::And,
+                left,
+                right,
+            } => {
+                self.push_join_where_expr(*left)?;
+                self.push_join_where_expr(*right)
+            }
+            conjunct => {
+                let join = self
+                    .context
+                    .get_join_mut()
+                    .expect("The query must be over a join");
+                let sides = get_column_identifiers(&conjunct)
+                    .iter()
+                    .filter_map(|column| join.side_of(column))
+                    .collect::<Vec<_>>();
+                let side = sides.first().copied().unwrap_or(JoinSide::Left);
+                if sides.iter().any(|other_side| *other_side != side) {
+                    return Err(ConversionError::UnsupportedOperation {
+                        message: "Filters comparing columns of both tables of a join are not supported yet"
+                            .to_string(),
+                    });
+                }
+                if join.is_padded(side) {
+                    return Err(ConversionError::UnsupportedOperation {
+                        message: "Filters on a table padded with NULLs by an outer join are not supported yet"
+                            .to_string(),
+                    });
+                }
+                join.table_mut(side).push_where_expr(conjunct);
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces all columns in an expression, qualified or not, with the columns they
+    /// are known as after the FROM clause.
+    fn resolve_columns(&mut self, expr: &Expression) -> ConversionResult<Expression> {
+        Ok(match expr {
+            Expression::Column(column) => Expression::Column(try_into_identifier(
+                self.resolve_column(None, &(*column).into())?,
+            )?),
+            Expression::QualifiedColumn { table, column } => {
+                Expression::Column(try_into_identifier(
+                    self.resolve_column(Some(&(*table).into()), &(*column).into())?,
+                )?)
+            }
+            Expression::Literal(_) | Expression::Wildcard => expr.clone(),
+            Expression::Unary { op, expr } => Expression::Unary {
+                op: *op,
+                expr: Box::new(self.resolve_columns(expr)?),
+            },
+            Expression::Binary { op, left, right } => Expression::Binary {
+                op: *op,
+                left: Box::new(self.resolve_columns(left)?),
+                right: Box::new(self.resolve_columns(right)?),
+            },
+            Expression::IsNull(expr) => Expression::IsNull(Box::new(self.resolve_columns(expr)?)),
+            Expression::IsNotNull(expr) => {
+                Expression::IsNotNull(Box::new(self.resolve_columns(expr)?))
+            }
+            Expression::Aggregation { op, expr } => Expression::Aggregation {
+                op: *op,
+                expr: Box::new(self.resolve_columns(expr)?),
+            },
+            Expression::Cast { expr, data_type } => Expression::Cast {
+                expr: Box::new(self.resolve_columns(expr)?),
+                data_type: *data_type,
+            },
+            Expression::Like {
+                expr,
+                pattern,
+                negated,
+            } => Expression::Like {
+                expr: Box::new(self.resolve_columns(expr)?),
+                pattern: pattern.clone(),
+                negated: *negated,
+            },
+            Expression::Length(expr) => Expression::Length(Box::new(self.resolve_columns(expr)?)),
+            Expression::StartsWith { expr, prefix } => Expression::StartsWith {
+                expr: Box::new(self.resolve_columns(expr)?),
+                prefix: prefix.clone(),
+            },
+            Expression::AddInterval { expr, interval } => Expression::AddInterval {
+                expr: Box::new(self.resolve_columns(expr)?),
+                interval: *interval,
+            },
+            Expression::Extract { field, expr } => Expression::Extract {
+                field: *field,
+                expr: Box::new(self.resolve_columns(expr)?),
+            },
+            Expression::DateTrunc { field, expr } => Expression::DateTrunc {
+                field: *field,
+                expr: Box::new(self.resolve_columns(expr)?),
+            },
+            Expression::InList {
+                expr,
+                list,
+                negated,
+            } => Expression::InList {
+                expr: Box::new(self.resolve_columns(expr)?),
+                list: list
+                    .iter()
+                    .map(|expr| self.resolve_columns(expr))
+                    .collect::<ConversionResult<_>>()?,
+                negated: *negated,
+            },
+            Expression::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => Expression::Between {
+                expr: Box::new(self.resolve_columns(expr)?),
+                low: Box::new(self.resolve_columns(low)?),
+                high: Box::new(self.resolve_columns(high)?),
+                negated: *negated,
+            },
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => Expression::Case {
+                when_then: when_then
+                    .iter()
+                    .map(|(condition, result)| {
+                        Ok((
+                            self.resolve_columns(condition)?,
+                            self.resolve_columns(result)?,
+                        ))
+                    })
+                    .collect::<ConversionResult<_>>()?,
+                else_expr: else_expr
+                    .as_ref()
+                    .map(|else_expr| self.resolve_columns(else_expr).map(Box::new))
+                    .transpose()?,
+            },
+            Expression::InSubquery { .. } => {
+                return Err(ConversionError::UnsupportedOperation {
+                    message: "IN (subquery) is only supported as a conjunct of the WHERE clause"
+                        .to_string(),
+                })
+            }
+        })
+    }
+
+    /// Returns the identifier a possibly qualified column is known as after the FROM clause.
+    ///
+    /// For joins this is its identifier in the join result, and the column is added
+    /// to the columns needed from its table.
+    fn resolve_column(
+        &mut self,
+        qualifier: Option<&Ident>,
+        column: &Ident,
+    ) -> ConversionResult<Ident> {
+        let Some(join) = self.context.get_join() else {
+            return match qualifier {
+                Some(qualifier) if Some(qualifier) != self.context.get_table_qualifier() => {
+                    Err(ConversionError::MissingTable {
+                        qualifier: Box::new(qualifier.clone()),
+                    })
+                }
+                _ => Ok(column.clone()),
+            };
+        };
+        let (side, column_type) = self.lookup_join_column(join, qualifier, column)?;
+        // The join columns of the right table equal those of the left one in the join result
+        let (side, column) = match join.join_column_index(side, column) {
+            Some(_) if join.is_padded(side) => {
+                return Err(ConversionError::UnsupportedOperation {
+                    message: "The join columns of a table padded with NULLs by an outer join can not be referenced"
+                        .to_string(),
+                });
+            }
+            Some(index) if side == JoinSide::Right => {
+                (JoinSide::Left, join.join_columns()[index].0.clone())
+            }
+            _ => (side, column.clone()),
+        };
+        let ident = self.join_result_ident(join, side, &column)?;
+        let table_ref = join.table(side).table_ref().clone();
+        let is_nullable = self.is_nullable(&table_ref, &column);
+        if is_nullable && join.is_padded(side) {
+            return Err(ConversionError::UnsupportedOperation {
+                message:
+                    "Nullable columns of a table padded with NULLs by an outer join are not supported yet"
+                        .to_string(),
+            });
+        }
+        let column_ref = ColumnRef::new(table_ref, column, column_type);
+        let table = self
+            .context
+            .get_join_mut()
+            .expect("The query must be over a join")
+            .table_mut(side);
+        table.push_column_ref(ident.clone(), column_ref.clone());
+        if is_nullable {
+            table.push_column_ref(
+                presence_column_ident(&ident),
+                presence_column_ref(&column_ref),
+            );
+        }
+        Ok(ident)
+    }
+
+    /// Finds the table of a joined column and its type.
+    fn lookup_join_column(
+        &self,
+        join: &JoinContext,
+        qualifier: Option<&Ident>,
+        column: &Ident,
+    ) -> ConversionResult<(JoinSide, ColumnType)> {
+        let sides = [JoinSide::Left, JoinSide::Right]
+            .into_iter()
+            .filter(|side| {
+                qualifier.is_none_or(|qualifier| join.table(*side).qualifier() == qualifier)
+            })
+            .collect::<Vec<_>>();
+        let matches = sides
+            .iter()
+            .filter_map(|side| {
+                self.schema_accessor
+                    .lookup_column(join.table(*side).table_ref().clone(), column.clone())
+                    .map(|column_type| (*side, column_type))
+            })
+            .collect::<Vec<_>>();
+        match (matches.as_slice(), qualifier, sides.as_slice()) {
+            ([found], _, _) => Ok(*found),
+            ([], Some(qualifier), []) => Err(ConversionError::MissingTable {
+                qualifier: Box::new(qualifier.clone()),
+            }),
+            ([], Some(_), [side]) => Err(ConversionError::MissingColumn {
+                identifier: Box::new(column.clone()),
+                table_ref: join.table(*side).table_ref().clone(),
+            }),
+            ([], _, _) => Err(ConversionError::MissingColumnWithoutTable {
+                identifier: Box::new(column.clone()),
+            }),
+            _ => Err(ConversionError::AmbiguousColumn {
+                identifier: Box::new(column.clone()),
+            }),
+        }
+    }
+
+    /// Returns the identifier of a column of a joined table in the join result.
+    ///
+    /// Columns whose name also appears in the other table are prefixed with the qualifier of their table.
+    fn join_result_ident(
+        &self,
+        join: &JoinContext,
+        side: JoinSide,
+        column: &Ident,
+    ) -> ConversionResult<Ident> {
+        let table_has_column = |side: JoinSide, column: &Ident| {
+            self.schema_accessor
+                .lookup_column(join.table(side).table_ref().clone(), column.clone())
+                .is_some()
+        };
+        if !table_has_column(side.other(), column) {
+            return Ok(column.clone());
+        }
+        let ident = Ident::new(format!(
+            "{}_{}",
+            join.table(side).qualifier().value,
+            column.value
+        ));
+        if table_has_column(JoinSide::Left, &ident) || table_has_column(JoinSide::Right, &ident) {
+            return Err(ConversionError::AmbiguousColumn {
+                identifier: Box::new(ident),
+            });
+        }
+        Ok(ident)
+    }
+
+    fn is_nullable(&self, table_ref: &TableRef, column_name: &Ident) -> bool {
+        self.schema_accessor
+            .lookup_column(table_ref.clone(), presence_column_ident(column_name))
+            == Some(ColumnType::Boolean)
+    }
+
     /// Visits the expression and returns its data type.
     fn visit_expr(&mut self, expr: &Expression) -> ConversionResult<ColumnType> {
         match expr {
             Expression::Wildcard => Ok(ColumnType::BigInt), // Since COUNT(*) = COUNT(1)
             Expression::Literal(literal) => self.visit_literal(literal),
             Expression::Column(_) => self.visit_column_expr(expr),
+            Expression::QualifiedColumn { .. } => Err(ConversionError::InvalidExpression {
+                expression: format!("Column {expr:?} has not been resolved"),
+            }),
             Expression::Unary { op, expr } => self.visit_unary_expr((*op).into(), expr),
             Expression::Binary { op, left, right } => {
                 self.visit_binary_expr(&(*op).into(), left, right)
             }
             Expression::Aggregation { op, expr } => self.visit_agg_expr(*op, expr),
+            Expression::IsNull(expr) | Expression::IsNotNull(expr) => {
+                self.visit_expr(expr)?;
+                Ok(ColumnType::Boolean)
+            }
+            Expression::Cast { expr, data_type } => {
+                let from_dtype = self.visit_expr(expr)?;
+                try_cast_to_data_type(from_dtype, *data_type)
+            }
+            Expression::InList { expr, list, .. } => {
+                let dtype = self.visit_expr(expr)?;
+                for value in list {
+                    check_dtypes(dtype, self.visit_expr(value)?, &BinaryOperator::Eq)?;
+                }
+                Ok(ColumnType::Boolean)
+            }
+            Expression::Between {
+                expr, low, high, ..
+            } => {
+                let dtype = self.visit_expr(expr)?;
+                check_dtypes(dtype, self.visit_expr(low)?, &BinaryOperator::Lt)?;
+                check_dtypes(dtype, self.visit_expr(high)?, &BinaryOperator::Lt)?;
+                if dtype == ColumnType::VarChar {
+                    for operand in [expr, low, high] {
+                        self.visit_varchar_key(operand)?;
+                    }
+                }
+                Ok(ColumnType::Boolean)
+            }
+            Expression::Like { expr, pattern, .. } => {
+                self.visit_varchar_operand(expr)?;
+                if pattern.contains(['%', '_']) {
+                    self.visit_varchar_key(expr)?;
+                }
+                Ok(ColumnType::Boolean)
+            }
+            Expression::StartsWith { expr, .. } => {
+                self.visit_varchar_operand(expr)?;
+                self.visit_varchar_key(expr)?;
+                Ok(ColumnType::Boolean)
+            }
+            Expression::Length(expr) => {
+                self.visit_varchar_operand(expr)?;
+                self.visit_varchar_encoding(expr, varchar_length_column_ident, ColumnType::BigInt)?;
+                Ok(ColumnType::BigInt)
+            }
+            Expression::AddInterval { expr, interval } => {
+                let dtype = self.visit_expr(expr)?;
+                Ok(try_add_interval_column_type(dtype, *interval)?)
+            }
+            Expression::Extract { field, expr } => {
+                let dtype = self.visit_expr(expr)?;
+                Ok(try_extract_column_type(*field, dtype)?)
+            }
+            Expression::DateTrunc { field, expr } => {
+                let dtype = self.visit_expr(expr)?;
+                Ok(try_date_trunc_column_type(*field, dtype)?)
+            }
+            Expression::Case {
+                when_then,
+                else_expr,
+            } => self.visit_case_expr(when_then, else_expr.as_deref()),
+            Expression::InSubquery { .. } => Err(ConversionError::UnsupportedOperation {
+                message: "IN (subquery) is only supported as a conjunct of the WHERE clause"
+                    .to_string(),
+            }),
         }
     }
 
@@ -212,6 +1025,12 @@ impl QueryContextBuilder<'_> {
         let left_dtype = self.visit_expr(left)?;
         let right_dtype = self.visit_expr(right)?;
         check_dtypes(left_dtype, right_dtype, op)?;
+        if matches!(op, BinaryOperator::Gt | BinaryOperator::Lt)
+            && left_dtype == ColumnType::VarChar
+        {
+            self.visit_varchar_key(left)?;
+            self.visit_varchar_key(right)?;
+        }
         match op {
             BinaryOperator::And
             | BinaryOperator::Or
@@ -220,6 +1039,7 @@ impl QueryContextBuilder<'_> {
             | BinaryOperator::Lt => Ok(ColumnType::Boolean),
             BinaryOperator::Multiply
             | BinaryOperator::Divide
+            | BinaryOperator::Modulo
             | BinaryOperator::Minus
             | BinaryOperator::Plus => Ok(left_dtype),
             _ => {
@@ -231,6 +1051,72 @@ impl QueryContextBuilder<'_> {
         }
     }
 
+    /// Visits the operand of a string operation, which has to be a `VarChar`.
+    fn visit_varchar_operand(&mut self, expr: &Expression) -> ConversionResult<()> {
+        match self.visit_expr(expr)? {
+            ColumnType::VarChar => Ok(()),
+            dtype => Err(ConversionError::InvalidDataType {
+                expected: ColumnType::VarChar,
+                actual: dtype,
+            }),
+        }
+    }
+
+    /// Adds the key column of a `VarChar` column that is compared lexicographically or by prefix.
+    fn visit_varchar_key(&mut self, expr: &Expression) -> ConversionResult<()> {
+        self.visit_varchar_encoding(expr, varchar_key_column_ident, ColumnType::Scalar)
+    }
+
+    /// Adds an encoding column of a `VarChar` column to the context,
+    /// see [`crate::base::database::varchar_key`].
+    ///
+    /// Literals need no encoding, and other expressions are rejected when the proof expression is built.
+    fn visit_varchar_encoding(
+        &mut self,
+        expr: &Expression,
+        encoding_column_ident: fn(&Ident) -> Ident,
+        encoding_column_type: ColumnType,
+    ) -> ConversionResult<()> {
+        let (Expression::Column(identifier)
+        | Expression::QualifiedColumn {
+            column: identifier, ..
+        }) = expr
+        else {
+            return Ok(());
+        };
+        if self.context.get_join().is_some() {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "String operations beyond equality over joins are not supported yet"
+                    .to_string(),
+            });
+        }
+        let column_name = Ident::from(*identifier);
+        let table_ref = self.context.get_table_ref().clone();
+        if self.is_nullable(&table_ref, &column_name) {
+            return Err(ConversionError::UnsupportedOperation {
+                message:
+                    "String operations beyond equality on nullable columns are not supported yet"
+                        .to_string(),
+            });
+        }
+        let encoding = encoding_column_ident(&column_name);
+        if self
+            .schema_accessor
+            .lookup_column(table_ref.clone(), encoding.clone())
+            != Some(encoding_column_type)
+        {
+            return Err(ConversionError::MissingColumn {
+                identifier: Box::new(encoding),
+                table_ref,
+            });
+        }
+        self.context.push_varchar_encoding_column_ref(
+            encoding.clone(),
+            ColumnRef::new(table_ref, encoding, encoding_column_type),
+        );
+        Ok(())
+    }
+
     fn visit_unary_expr(
         &mut self,
         op: UnaryOperator,
@@ -254,6 +1140,28 @@ impl QueryContextBuilder<'_> {
         }
     }
 
+    fn visit_case_expr(
+        &mut self,
+        when_then: &[(Expression, Expression)],
+        else_expr: Option<&Expression>,
+    ) -> ConversionResult<ColumnType> {
+        let else_expr = else_expr.ok_or_else(|| ConversionError::UnsupportedOperation {
+            message: "CASE without ELSE".to_string(),
+        })?;
+        let mut dtype = self.visit_expr(else_expr)?;
+        for (condition, result) in when_then {
+            let condition_dtype = self.visit_expr(condition)?;
+            if condition_dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: condition_dtype,
+                });
+            }
+            dtype = try_case_column_types(dtype, self.visit_expr(result)?)?;
+        }
+        Ok(dtype)
+    }
+
     fn visit_agg_expr(
         &mut self,
         op: AggregationOperator,
@@ -263,8 +1171,12 @@ impl QueryContextBuilder<'_> {
 
         let expr_dtype = self.visit_expr(expr)?;
 
+        let is_count = matches!(
+            op,
+            AggregationOperator::Count | AggregationOperator::CountDistinct
+        );
         // We only support sum/max/min aggregations on numeric columns.
-        if op != AggregationOperator::Count && expr_dtype == ColumnType::VarChar {
+        if !is_count && expr_dtype == ColumnType::VarChar {
             return Err(ConversionError::non_numeric_expr_in_agg(
                 expr_dtype.to_string(),
                 op.to_string(),
@@ -274,7 +1186,7 @@ impl QueryContextBuilder<'_> {
         self.context.set_in_agg_scope(false)?;
 
         // Count aggregation always results in an integer type
-        if op == AggregationOperator::Count {
+        if is_count {
             Ok(ColumnType::BigInt)
         } else {
             Ok(expr_dtype)
@@ -303,6 +1215,26 @@ impl QueryContextBuilder<'_> {
     }
 
     fn visit_column_identifier(&mut self, column_name: &Ident) -> ConversionResult<ColumnType> {
+        if let Some((_, dtype)) = self.context.get_group_by_key_exprs().get(column_name) {
+            return Ok(*dtype);
+        }
+        if let Some(join) = self.context.get_join() {
+            let column = join
+                .get_column_ref(column_name)
+                .ok_or_else(|| ConversionError::MissingColumnWithoutTable {
+                    identifier: Box::new(column_name.clone()),
+                })?
+                .clone();
+            let presence_ident = presence_column_ident(column_name);
+            if let Some(presence) = join.get_column_ref(&presence_ident).cloned() {
+                self.context
+                    .push_presence_column_ref(presence_ident, presence);
+            }
+            let column_type = *column.column_type();
+            self.context.push_column_ref(column_name.clone(), column);
+            return Ok(column_type);
+        }
+
         let table_ref = self.context.get_table_ref();
         let column_type = self
             .schema_accessor
@@ -314,6 +1246,12 @@ impl QueryContextBuilder<'_> {
         })?;
 
         let column = ColumnRef::new(table_ref.clone(), column_name.clone(), column_type);
+        if self.is_nullable(table_ref, column_name) {
+            self.context.push_presence_column_ref(
+                presence_column_ident(column_name),
+                presence_column_ref(&column),
+            );
+        }
 
         self.context.push_column_ref(column_name.clone(), column);
 
@@ -321,6 +1259,207 @@ impl QueryContextBuilder<'_> {
     }
 }
 
+fn named_table_ref(
+    table: Identifier,
+    schema: Option<Identifier>,
+    default_schema: Ident,
+) -> TableRef {
+    let actual_schema = schema.map_or(default_schema, Ident::from);
+    TableRef::from_idents(Some(actual_schema), Ident::from(table))
+}
+
+pub(super) fn try_into_identifier(ident: Ident) -> ConversionResult<Identifier> {
+    Identifier::try_from(ident).map_err(|e| ConversionError::IdentifierConversionError {
+        error: format!("Failed to convert Ident to Identifier: {e}"),
+    })
+}
+
+/// Returns the column type that an expression of type `from` is cast to by `CAST(... AS data_type)`.
+///
+/// Timestamps keep their time zone; other types cast to timestamps are in UTC.
+pub(crate) fn try_cast_to_data_type(
+    from: ColumnType,
+    data_type: DataType,
+) -> ConversionResult<ColumnType> {
+    let to = match data_type {
+        DataType::Boolean => ColumnType::Boolean,
+        DataType::TinyInt => ColumnType::TinyInt,
+        DataType::SmallInt => ColumnType::SmallInt,
+        DataType::Int => ColumnType::Int,
+        DataType::BigInt => ColumnType::BigInt,
+        DataType::Decimal { precision, scale } => ColumnType::Decimal75(
+            Precision::new(precision)?,
+            scale.try_into().map_err(|_| DecimalError::InvalidScale {
+                scale: scale.to_string(),
+            })?,
+        ),
+        DataType::Timestamp(time_unit) => {
+            let time_zone = match from {
+                ColumnType::TimestampTZ(_, time_zone) => time_zone,
+                _ => PoSQLTimeZone::utc(),
+            };
+            ColumnType::TimestampTZ(time_unit, time_zone)
+        }
+    };
+    Ok(try_cast_column_types(from, to)?)
+}
+
+/// The hidden identifier the `GROUP BY` expression at `index` is known as,
+/// if it is not a column.
+fn group_by_key_ident(index: usize) -> Ident {
+    Ident::new(format!("__group_by_{index}__"))
+}
+
+/// Replaces the subexpressions of `expr` outside aggregations that equal a `GROUP BY` expression
+/// with the identifier it is known as.
+fn replace_group_by_key_exprs(
+    expr: &Expression,
+    key_exprs: &IndexMap<Ident, (Expression, ColumnType)>,
+) -> ConversionResult<Expression> {
+    if let Some(ident) = key_exprs
+        .iter()
+        .find_map(|(ident, (key_expr, _))| (key_expr == expr).then_some(ident))
+    {
+        return Ok(Expression::Column(try_into_identifier(ident.clone())?));
+    }
+    let replace = |expr: &Expression| replace_group_by_key_exprs(expr, key_exprs).map(Box::new);
+    Ok(match expr {
+        Expression::Unary { op, expr } => Expression::Unary {
+            op: *op,
+            expr: replace(expr)?,
+        },
+        Expression::Binary { op, left, right } => Expression::Binary {
+            op: *op,
+            left: replace(left)?,
+            right: replace(right)?,
+        },
+        Expression::IsNull(expr) => Expression::IsNull(replace(expr)?),
+        Expression::IsNotNull(expr) => Expression::IsNotNull(replace(expr)?),
+        Expression::Cast { expr, data_type } => Expression::Cast {
+            expr: replace(expr)?,
+            data_type: *data_type,
+        },
+        Expression::Like {
+            expr,
+            pattern,
+            negated,
+        } => Expression::Like {
+            expr: replace(expr)?,
+            pattern: pattern.clone(),
+            negated: *negated,
+        },
+        Expression::Length(expr) => Expression::Length(replace(expr)?),
+        Expression::StartsWith { expr, prefix } => Expression::StartsWith {
+            expr: replace(expr)?,
+            prefix: prefix.clone(),
+        },
+        Expression::AddInterval { expr, interval } => Expression::AddInterval {
+            expr: replace(expr)?,
+            interval: *interval,
+        },
+        Expression::Extract { field, expr } => Expression::Extract {
+            field: *field,
+            expr: replace(expr)?,
+        },
+        Expression::DateTrunc { field, expr } => Expression::DateTrunc {
+            field: *field,
+            expr: replace(expr)?,
+        },
+        Expression::InList {
+            expr,
+            list,
+            negated,
+        } => Expression::InList {
+            expr: replace(expr)?,
+            list: list
+                .iter()
+                .map(|expr| replace_group_by_key_exprs(expr, key_exprs))
+                .collect::<ConversionResult<_>>()?,
+            negated: *negated,
+        },
+        Expression::Between {
+            expr,
+            low,
+            high,
+            negated,
+        } => Expression::Between {
+            expr: replace(expr)?,
+            low: replace(low)?,
+            high: replace(high)?,
+            negated: *negated,
+        },
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => Expression::Case {
+            when_then: when_then
+                .iter()
+                .map(|(condition, result)| {
+                    Ok((
+                        replace_group_by_key_exprs(condition, key_exprs)?,
+                        replace_group_by_key_exprs(result, key_exprs)?,
+                    ))
+                })
+                .collect::<ConversionResult<_>>()?,
+            else_expr: else_expr.as_deref().map(replace).transpose()?,
+        },
+        // Aggregations are computed over the rows of a group, not over the group keys
+        Expression::Aggregation { .. }
+        | Expression::InSubquery { .. }
+        | Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard => expr.clone(),
+    })
+}
+
+/// Returns the identifiers of all columns in an expression.
+fn get_column_identifiers(expr: &Expression) -> Vec<Ident> {
+    match expr {
+        Expression::Column(identifier)
+        | Expression::QualifiedColumn {
+            column: identifier, ..
+        } => vec![(*identifier).into()],
+        Expression::Literal(_) | Expression::Wildcard => vec![],
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::Aggregation { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::InSubquery { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Length(expr)
+        | Expression::StartsWith { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. } => get_column_identifiers(expr),
+        Expression::Binary { left, right, .. } => {
+            let mut identifiers = get_column_identifiers(left);
+            identifiers.extend(get_column_identifiers(right));
+            identifiers
+        }
+        Expression::InList { expr, list, .. } => iter::once(expr.as_ref())
+            .chain(list)
+            .flat_map(get_column_identifiers)
+            .collect(),
+        Expression::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .flat_map(|expr| get_column_identifiers(expr))
+            .collect(),
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => when_then
+            .iter()
+            .flat_map(|(condition, result)| [condition, result])
+            .chain(else_expr.as_deref())
+            .flat_map(get_column_identifiers)
+            .collect(),
+    }
+}
+
 /// Checks if the binary operation between the left and right data types is valid.
 ///
 /// # Arguments
@@ -356,8 +1495,9 @@ pub(crate) fn type_check_binary_operation(
             ) || (left_dtype.is_numeric() && right_dtype.is_numeric())
         }
         BinaryOperator::Gt | BinaryOperator::Lt => {
+            // Strings are compared by their keys, see `crate::base::database::varchar_key`
             if left_dtype == ColumnType::VarChar || right_dtype == ColumnType::VarChar {
-                return false;
+                return left_dtype == right_dtype;
             }
             // Due to constraints in bitwise_verification we limit the precision of decimal types to 38
             if let ColumnType::Decimal75(precision, _) = left_dtype {
@@ -381,7 +1521,8 @@ pub(crate) fn type_check_binary_operation(
             try_add_subtract_column_types(left_dtype, right_dtype).is_ok()
         }
         BinaryOperator::Multiply => try_multiply_column_types(left_dtype, right_dtype).is_ok(),
-        BinaryOperator::Divide => left_dtype.is_numeric() && right_dtype.is_numeric(),
+        BinaryOperator::Divide => try_divide_column_types(left_dtype, right_dtype).is_ok(),
+        BinaryOperator::Modulo => try_modulo_column_types(left_dtype, right_dtype).is_ok(),
         _ => {
             // Handle unsupported binary operations
             false
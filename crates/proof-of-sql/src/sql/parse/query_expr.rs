@@ -1,17 +1,32 @@
-use super::{EnrichedExpr, FilterExecBuilder, QueryContextBuilder};
+use super::{
+    query_context_builder::try_into_identifier, DynProofExprBuilder, EnrichedExpr,
+    FilterExecBuilder, JoinSide, QueryContext, QueryContextBuilder, WhereExprBuilder,
+};
 use crate::{
-    base::database::SchemaAccessor,
+    base::{
+        database::{
+            is_presence_column_ident, is_varchar_encoding_column_ident, presence_column_ident,
+            ColumnField, ColumnRef, SchemaAccessor, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+    },
     sql::{
-        parse::ConversionResult,
+        parse::{ConversionError, ConversionResult},
         postprocessing::{
-            GroupByPostprocessing, OrderByPostprocessing, OwnedTablePostprocessing,
-            SelectPostprocessing, SlicePostprocessing,
+            DistinctPostprocessing, GroupByPostprocessing, OrderByPostprocessing,
+            OwnedTablePostprocessing, SelectPostprocessing, SlicePostprocessing,
         },
-        proof_plans::{DynProofPlan, GroupByExec},
+        proof::ProofPlan,
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr},
+        proof_plans::{DistinctExec, DynProofPlan, GroupByExec, SortExec},
     },
 };
-use alloc::{fmt, vec, vec::Vec};
-use proof_of_sql_parser::{intermediate_ast::SetExpression, SelectStatement};
+use alloc::{boxed::Box, fmt, string::ToString, vec, vec::Vec};
+use core::iter;
+use proof_of_sql_parser::{
+    intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression, SetExpression, Slice},
+    Identifier, SelectStatement,
+};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
@@ -57,36 +72,155 @@ impl QueryExpr {
                 from,
                 where_expr,
                 group_by,
+                having,
+                distinct,
             } => QueryContextBuilder::new(schema_accessor)
-                .visit_table_expr(&from, default_schema)
-                .visit_group_by_exprs(group_by.into_iter().map(Ident::from).collect())?
+                .visit_table_expr(&from, default_schema.clone())?
+                .visit_group_by_exprs(group_by)?
                 .visit_result_exprs(result_exprs)?
-                .visit_where_expr(where_expr)?
+                .visit_where_expr(where_expr, default_schema)?
+                .visit_having_expr(having)?
                 .visit_order_by_exprs(ast.order_by.into_iter().map(Into::into).collect())?
                 .visit_slice_expr(ast.slice)
+                .visit_distinct(distinct)
                 .build()?,
         };
         let result_aliased_exprs = context.get_aliased_result_exprs()?.to_vec();
         let group_by = context.get_group_by_exprs();
-        // Figure out the basic postprocessing steps.
+        let having = context.get_having_expr().as_deref();
         let mut postprocessing = vec![];
-        let order_bys = context.get_order_by_exprs();
-        if !order_bys.is_empty() {
-            postprocessing.push(OwnedTablePostprocessing::new_order_by(
-                OrderByPostprocessing::new(order_bys.to_vec()),
-            ));
+        let count_distinct_column = get_count_distinct_column(&result_aliased_exprs)?;
+        let group_by_key_exprs = context.get_group_by_key_exprs();
+        if !group_by_key_exprs.is_empty()
+            && (context.get_join().is_some()
+                || context.get_derived_table().is_some()
+                || !context.get_in_subqueries().is_empty()
+                || count_distinct_column.is_some())
+        {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "GROUP BY expressions other than columns are only supported over a single table without COUNT(DISTINCT ...)".to_string(),
+            });
         }
-        if let Some(slice) = context.get_slice_expr() {
-            postprocessing.push(OwnedTablePostprocessing::new_slice(
-                SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
-            ));
-        }
-        if context.has_agg() {
-            if let Some(group_by_expr) = Option::<GroupByExec>::try_from(&context)? {
-                Ok(Self {
-                    proof_expr: DynProofPlan::GroupBy(group_by_expr),
-                    postprocessing,
+        let proof_expr = if let Some(join) = context.get_join() {
+            if count_distinct_column.is_some() {
+                return Err(ConversionError::UnsupportedOperation {
+                    message: "COUNT(DISTINCT ...) over joins is not supported yet".to_string(),
+                });
+            }
+            // The join provides the referenced columns. Aggregations are proven by a group by
+            // of the join and other results by a projection of the join if possible,
+            // otherwise they are computed in postprocessing.
+            let join_plan = DynProofPlan::try_from(join)?;
+            let group_by_plan = if context.has_agg() {
+                Option::<GroupByExec>::try_from(&context)?
+                    .map(|group_by_exec| {
+                        try_filter_groups(
+                            group_by_exec,
+                            having,
+                            group_by,
+                            &result_aliased_exprs,
+                            join.table(JoinSide::Left).table_ref(),
+                            &context.get_column_mapping(),
+                        )
+                    })
+                    .transpose()?
+                    .flatten()
+            } else {
+                None
+            };
+            if let Some(group_by_plan) = group_by_plan {
+                group_by_plan
+            } else if context.has_agg() {
+                push_group_by_postprocessing(
+                    group_by,
+                    result_aliased_exprs,
+                    having,
+                    &mut postprocessing,
+                )?;
+                join_plan
+            } else if let Some(aliased_results) = try_project_join(&context, &result_aliased_exprs)
+            {
+                DynProofPlan::new_projection(aliased_results, join_plan)
+            } else {
+                postprocessing.push(OwnedTablePostprocessing::new_select(
+                    SelectPostprocessing::new(result_aliased_exprs),
+                ));
+                join_plan
+            }
+        } else if context.get_derived_table().is_some() || !context.get_in_subqueries().is_empty() {
+            if count_distinct_column.is_some() {
+                return Err(ConversionError::UnsupportedOperation {
+                    message: "COUNT(DISTINCT ...) over subqueries is not supported yet".to_string(),
+                });
+            }
+            // The subqueries provide the referenced columns, the result is computed in postprocessing.
+            let proof_expr = plan_subqueries(&context)?;
+            if context.has_agg() {
+                push_group_by_postprocessing(
+                    group_by,
+                    result_aliased_exprs,
+                    having,
+                    &mut postprocessing,
+                )?;
+            } else if !is_selection_of_columns(&proof_expr, &result_aliased_exprs) {
+                postprocessing.push(OwnedTablePostprocessing::new_select(
+                    SelectPostprocessing::new(result_aliased_exprs),
+                ));
+            }
+            proof_expr
+        } else if let Some(column) = count_distinct_column {
+            // COUNT(DISTINCT column) is the COUNT(column) over the distinct rows
+            // of the group by columns and the column.
+            let column_mapping = context.get_column_mapping();
+            let distinct_exprs = group_by
+                .iter()
+                .cloned()
+                .map(try_into_identifier)
+                .chain(iter::once(Ok(column)))
+                .collect::<ConversionResult<IndexSet<_>>>()?
+                .into_iter()
+                .map(|identifier| {
+                    EnrichedExpr::new(
+                        AliasedResultExpr::new(Expression::Column(identifier), identifier),
+                        &column_mapping,
+                    )
+                })
+                .collect::<Vec<_>>();
+            let filter = FilterExecBuilder::new(context.get_column_mapping())
+                .add_table_expr(context.get_table_ref().clone())
+                .add_where_expr(context.get_where_expr().clone())?
+                .add_result_columns(&distinct_exprs)
+                .build();
+            let proof_expr = deduplicate(DynProofPlan::Filter(filter), &mut postprocessing);
+            push_group_by_postprocessing(
+                group_by,
+                result_aliased_exprs
+                    .iter()
+                    .map(|aliased_expr| AliasedResultExpr {
+                        expr: Box::new(replace_count_distinct(&aliased_expr.expr)),
+                        alias: aliased_expr.alias,
+                    })
+                    .collect(),
+                having.map(replace_count_distinct).as_ref(),
+                &mut postprocessing,
+            )?;
+            proof_expr
+        } else if context.has_agg() {
+            let group_by_plan = Option::<GroupByExec>::try_from(&context)?
+                .map(|group_by_exec| {
+                    try_filter_groups(
+                        group_by_exec,
+                        having,
+                        group_by,
+                        &result_aliased_exprs,
+                        context.get_table_ref(),
+                        &context.get_column_mapping(),
+                    )
                 })
+                .transpose()?
+                .flatten();
+            if let Some(group_by_plan) = group_by_plan {
+                group_by_plan
             } else {
                 let raw_enriched_exprs = result_aliased_exprs
                     .iter()
@@ -95,36 +229,37 @@ impl QueryExpr {
                         dyn_proof_expr: None,
                     })
                     .collect::<Vec<_>>();
-                let filter = FilterExecBuilder::new(context.get_column_mapping())
+                // The GROUP BY expressions other than columns are computed by the filter
+                let column_mapping = context.get_column_mapping();
+                let key_enriched_exprs = group_by_key_exprs
+                    .iter()
+                    .map(|(ident, (key_expr, _))| {
+                        let identifier = try_into_identifier(ident.clone())?;
+                        Ok(EnrichedExpr {
+                            residue_expression: AliasedResultExpr::new(
+                                Expression::Column(identifier),
+                                identifier,
+                            ),
+                            dyn_proof_expr: Some(
+                                DynProofExprBuilder::new(&column_mapping).build(key_expr)?,
+                            ),
+                        })
+                    })
+                    .collect::<ConversionResult<Vec<_>>>()?;
+                let filter = FilterExecBuilder::new(column_mapping)
                     .add_table_expr(context.get_table_ref().clone())
                     .add_where_expr(context.get_where_expr().clone())?
                     .add_result_columns(&raw_enriched_exprs)
+                    .add_result_columns(&key_enriched_exprs)
                     .build();
 
-                let group_by_postprocessing =
-                    GroupByPostprocessing::try_new(group_by.to_vec(), result_aliased_exprs)?;
-                postprocessing.insert(
-                    0,
-                    OwnedTablePostprocessing::new_group_by(group_by_postprocessing.clone()),
-                );
-                let remainder_exprs = group_by_postprocessing.remainder_exprs();
-                // Check whether we need to do select postprocessing.
-                // That is, if any of them is not simply a column reference.
-                if remainder_exprs
-                    .iter()
-                    .any(|expr| expr.try_as_identifier().is_none())
-                {
-                    postprocessing.insert(
-                        1,
-                        OwnedTablePostprocessing::new_select(SelectPostprocessing::new(
-                            remainder_exprs.to_vec(),
-                        )),
-                    );
-                }
-                Ok(Self {
-                    proof_expr: DynProofPlan::Filter(filter),
-                    postprocessing,
-                })
+                push_group_by_postprocessing(
+                    group_by,
+                    result_aliased_exprs,
+                    having,
+                    &mut postprocessing,
+                )?;
+                DynProofPlan::Filter(filter)
             }
         } else {
             // No group by, so we need to do a filter.
@@ -147,16 +282,71 @@ impl QueryExpr {
                 .iter()
                 .any(|expr| expr.try_as_identifier().is_none())
             {
-                postprocessing.insert(
-                    0,
-                    OwnedTablePostprocessing::new_select(SelectPostprocessing::new(select_exprs)),
-                );
+                postprocessing.push(OwnedTablePostprocessing::new_select(
+                    SelectPostprocessing::new(select_exprs),
+                ));
             }
-            Ok(Self {
-                proof_expr: DynProofPlan::Filter(filter),
-                postprocessing,
-            })
+            DynProofPlan::Filter(filter)
+        };
+        let proof_expr = if context.is_distinct() {
+            deduplicate(proof_expr, &mut postprocessing)
+        } else {
+            proof_expr
+        };
+        Ok(Self::new_with_order_by_and_slice(
+            proof_expr,
+            postprocessing,
+            context.get_order_by_exprs(),
+            context.get_slice_expr().as_ref(),
+        ))
+    }
+
+    /// Creates a new `QueryExpr` that additionally sorts and slices the result of `proof_expr`
+    /// and `postprocessing`.
+    ///
+    /// The sort, and the slice following it, are proven if there is no other postprocessing
+    /// and the result can be provably sorted by the ORDER BY columns, see [`SortExec::can_sort_by`].
+    /// Otherwise they are applied in postprocessing.
+    fn new_with_order_by_and_slice(
+        proof_expr: DynProofPlan,
+        mut postprocessing: Vec<OwnedTablePostprocessing>,
+        order_bys: &[(usize, bool)],
+        slice: Option<&Slice>,
+    ) -> Self {
+        let fields = proof_expr.get_column_result_fields();
+        let sort_column_types = order_bys
+            .iter()
+            .map(|&(index, _)| fields.get(index).map(ColumnField::data_type))
+            .collect::<Option<Vec<_>>>();
+        let is_provable = postprocessing.is_empty()
+            && sort_column_types.is_some_and(|types| SortExec::can_sort_by(&types));
+        let skip = slice.map_or(Some(0), |slice| usize::try_from(slice.offset_value).ok());
+        if let Some(skip) = skip.filter(|_| is_provable) {
+            let sort = DynProofPlan::new_sort(proof_expr, order_bys.to_vec());
+            let proof_expr = match slice {
+                // A fetch that can not be reached is the same as fetching all rows
+                Some(slice) => DynProofPlan::new_slice(
+                    sort,
+                    skip,
+                    usize::try_from(slice.number_rows)
+                        .ok()
+                        .filter(|fetch| fetch.checked_add(skip).is_some()),
+                ),
+                None => sort,
+            };
+            return Self::new(proof_expr, postprocessing);
+        }
+        if !order_bys.is_empty() {
+            postprocessing.push(OwnedTablePostprocessing::new_order_by(
+                OrderByPostprocessing::new(order_bys.to_vec()),
+            ));
+        }
+        if let Some(slice) = slice {
+            postprocessing.push(OwnedTablePostprocessing::new_slice(
+                SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
+            ));
         }
+        Self::new(proof_expr, postprocessing)
     }
 
     /// Immutable access to this query's provable filter expression.
@@ -171,3 +361,483 @@ impl QueryExpr {
         &self.postprocessing
     }
 }
+
+/// Returns the plan of the table of a query with subqueries, restricted by its `[NOT] IN (subquery)` conjuncts.
+///
+/// The table is either the subquery in the `FROM` clause or a filter of the named table
+/// that selects all referenced columns. It is restricted by a semi join, or an anti join for `NOT IN`,
+/// with the plan of each subquery of the `WHERE` clause.
+fn plan_subqueries(context: &QueryContext) -> ConversionResult<DynProofPlan> {
+    let table = if let Some(derived_table) = context.get_derived_table() {
+        derived_table.clone()
+    } else {
+        let column_mapping = context.get_column_mapping();
+        if column_mapping.keys().any(is_presence_column_ident) {
+            return Err(ConversionError::UnsupportedOperation {
+                message: "Nullable columns in queries with IN (subquery) are not supported yet"
+                    .to_string(),
+            });
+        }
+        if column_mapping.keys().any(is_varchar_encoding_column_ident) {
+            return Err(ConversionError::UnsupportedOperation {
+                message:
+                    "String operations beyond equality in queries with IN (subquery) are not supported yet"
+                        .to_string(),
+            });
+        }
+        let column_exprs = column_mapping
+            .keys()
+            .cloned()
+            .map(|column| {
+                let identifier = try_into_identifier(column)?;
+                Ok(EnrichedExpr::new(
+                    AliasedResultExpr::new(Expression::Column(identifier), identifier),
+                    &column_mapping,
+                ))
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        DynProofPlan::Filter(
+            FilterExecBuilder::new(context.get_column_mapping())
+                .add_table_expr(context.get_table_ref().clone())
+                .add_where_expr(context.get_where_expr().clone())?
+                .add_result_columns(&column_exprs)
+                .build(),
+        )
+    };
+    Ok(context
+        .get_in_subqueries()
+        .iter()
+        .fold(table, |left, in_subquery| {
+            let left_index = left
+                .get_column_result_fields()
+                .iter()
+                .position(|field| field.name() == in_subquery.column)
+                .expect("The column of IN (subquery) should be selected");
+            DynProofPlan::new_semi_join(
+                left,
+                in_subquery.plan.clone(),
+                vec![left_index],
+                vec![0],
+                in_subquery.negated,
+            )
+        }))
+}
+
+/// Returns the result expressions of a query over a join as expressions over the result of the join,
+/// or `None` if one of them can not be proven.
+///
+/// Nullable results are followed by their presence as in a `FilterExec`. The padded columns
+/// of outer joins are not supported, see [`QueryContext::get_inner_join_column_mapping`].
+fn try_project_join(
+    context: &QueryContext,
+    result_aliased_exprs: &[AliasedResultExpr],
+) -> Option<Vec<AliasedDynProofExpr>> {
+    let join_column_mapping = context.get_inner_join_column_mapping()?;
+    let nullable_columns = DynProofExprBuilder::new(&join_column_mapping).nullable_columns();
+    result_aliased_exprs
+        .iter()
+        .map(|aliased_expr| {
+            let expr =
+                EnrichedExpr::new(aliased_expr.clone(), &join_column_mapping).dyn_proof_expr?;
+            let alias: Ident = aliased_expr.alias.into();
+            let presence =
+                expr.presence_expr(&nullable_columns)
+                    .map(|presence| AliasedDynProofExpr {
+                        expr: presence,
+                        alias: presence_column_ident(&alias),
+                    });
+            Some(iter::once(AliasedDynProofExpr { expr, alias }).chain(presence))
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|aliased_results| aliased_results.into_iter().flatten().collect())
+}
+
+/// Whether the result expressions select exactly the columns of `proof_expr`, in the same order
+fn is_selection_of_columns(
+    proof_expr: &DynProofPlan,
+    result_aliased_exprs: &[AliasedResultExpr],
+) -> bool {
+    let fields = proof_expr.get_column_result_fields();
+    fields.len() == result_aliased_exprs.len()
+        && fields
+            .iter()
+            .zip(result_aliased_exprs)
+            .all(|(field, aliased_expr)| {
+                aliased_expr.try_as_identifier().is_some_and(|identifier| {
+                    *identifier == aliased_expr.alias && Ident::from(*identifier) == field.name()
+                })
+            })
+}
+
+/// Removes the duplicate rows from the result of `proof_expr` and `postprocessing`.
+///
+/// This is proven by a `DistinctExec` if there is no postprocessing and the result can be
+/// provably deduplicated, see [`DistinctExec::can_deduplicate`]. Otherwise it is done in postprocessing.
+fn deduplicate(
+    proof_expr: DynProofPlan,
+    postprocessing: &mut Vec<OwnedTablePostprocessing>,
+) -> DynProofPlan {
+    if postprocessing.is_empty() {
+        // The groups of a `GroupByExec` are distinct already
+        if matches!(proof_expr, DynProofPlan::GroupBy(_)) {
+            return proof_expr;
+        }
+        let column_types = proof_expr
+            .get_column_result_fields()
+            .iter()
+            .map(ColumnField::data_type)
+            .collect::<Vec<_>>();
+        if DistinctExec::can_deduplicate(&column_types) {
+            return DynProofPlan::new_distinct(proof_expr);
+        }
+    }
+    postprocessing.push(OwnedTablePostprocessing::new_distinct(
+        DistinctPostprocessing::new(),
+    ));
+    proof_expr
+}
+
+/// Returns the column that is counted by all `COUNT(DISTINCT ...)` aggregations of the result,
+/// or `None` if there are none.
+///
+/// `COUNT(DISTINCT ...)` can not be combined with other aggregations yet.
+fn get_count_distinct_column(
+    result_aliased_exprs: &[AliasedResultExpr],
+) -> ConversionResult<Option<Identifier>> {
+    let mut aggregations = Vec::new();
+    for aliased_expr in result_aliased_exprs {
+        push_aggregations(&aliased_expr.expr, &mut aggregations);
+    }
+    if !aggregations
+        .iter()
+        .any(|(op, _)| *op == AggregationOperator::CountDistinct)
+    {
+        return Ok(None);
+    }
+    match aggregations.first() {
+        Some((_, Expression::Column(column)))
+            if aggregations.iter().all(|(op, expr)| {
+                *op == AggregationOperator::CountDistinct && *expr == Expression::Column(*column)
+            }) =>
+        {
+            Ok(Some(*column))
+        }
+        _ => Err(ConversionError::UnsupportedOperation {
+            message: "COUNT(DISTINCT ...) can only be combined with COUNT(DISTINCT ...) of the same column"
+                .to_string(),
+        }),
+    }
+}
+
+/// Adds the aggregations in `expr` to `aggregations`.
+fn push_aggregations<'a>(
+    expr: &'a Expression,
+    aggregations: &mut Vec<(AggregationOperator, &'a Expression)>,
+) {
+    match expr {
+        Expression::Aggregation { op, expr } => aggregations.push((*op, expr)),
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::InSubquery { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Length(expr)
+        | Expression::StartsWith { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. } => {
+            push_aggregations(expr, aggregations);
+        }
+        Expression::Binary { left, right, .. } => {
+            push_aggregations(left, aggregations);
+            push_aggregations(right, aggregations);
+        }
+        Expression::InList { expr, list, .. } => {
+            push_aggregations(expr, aggregations);
+            for expr in list {
+                push_aggregations(expr, aggregations);
+            }
+        }
+        Expression::Between {
+            expr, low, high, ..
+        } => {
+            push_aggregations(expr, aggregations);
+            push_aggregations(low, aggregations);
+            push_aggregations(high, aggregations);
+        }
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            for (condition, result) in when_then {
+                push_aggregations(condition, aggregations);
+                push_aggregations(result, aggregations);
+            }
+            if let Some(else_expr) = else_expr {
+                push_aggregations(else_expr, aggregations);
+            }
+        }
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard => {}
+    }
+}
+
+/// Replaces `COUNT(DISTINCT ...)` with `COUNT(...)` in `expr`.
+fn replace_count_distinct(expr: &Expression) -> Expression {
+    match expr {
+        Expression::Aggregation { op, expr } => Expression::Aggregation {
+            op: match op {
+                AggregationOperator::CountDistinct => AggregationOperator::Count,
+                _ => *op,
+            },
+            expr: expr.clone(),
+        },
+        Expression::Unary { op, expr } => Expression::Unary {
+            op: *op,
+            expr: Box::new(replace_count_distinct(expr)),
+        },
+        Expression::Binary { op, left, right } => Expression::Binary {
+            op: *op,
+            left: Box::new(replace_count_distinct(left)),
+            right: Box::new(replace_count_distinct(right)),
+        },
+        Expression::IsNull(expr) => Expression::IsNull(Box::new(replace_count_distinct(expr))),
+        Expression::IsNotNull(expr) => {
+            Expression::IsNotNull(Box::new(replace_count_distinct(expr)))
+        }
+        Expression::InSubquery {
+            expr,
+            subquery,
+            negated,
+        } => Expression::InSubquery {
+            expr: Box::new(replace_count_distinct(expr)),
+            subquery: subquery.clone(),
+            negated: *negated,
+        },
+        Expression::Cast { expr, data_type } => Expression::Cast {
+            expr: Box::new(replace_count_distinct(expr)),
+            data_type: *data_type,
+        },
+        Expression::Like {
+            expr,
+            pattern,
+            negated,
+        } => Expression::Like {
+            expr: Box::new(replace_count_distinct(expr)),
+            pattern: pattern.clone(),
+            negated: *negated,
+        },
+        Expression::Length(expr) => Expression::Length(Box::new(replace_count_distinct(expr))),
+        Expression::StartsWith { expr, prefix } => Expression::StartsWith {
+            expr: Box::new(replace_count_distinct(expr)),
+            prefix: prefix.clone(),
+        },
+        Expression::AddInterval { expr, interval } => Expression::AddInterval {
+            expr: Box::new(replace_count_distinct(expr)),
+            interval: *interval,
+        },
+        Expression::Extract { field, expr } => Expression::Extract {
+            field: *field,
+            expr: Box::new(replace_count_distinct(expr)),
+        },
+        Expression::DateTrunc { field, expr } => Expression::DateTrunc {
+            field: *field,
+            expr: Box::new(replace_count_distinct(expr)),
+        },
+        Expression::InList {
+            expr,
+            list,
+            negated,
+        } => Expression::InList {
+            expr: Box::new(replace_count_distinct(expr)),
+            list: list.iter().map(replace_count_distinct).collect(),
+            negated: *negated,
+        },
+        Expression::Between {
+            expr,
+            low,
+            high,
+            negated,
+        } => Expression::Between {
+            expr: Box::new(replace_count_distinct(expr)),
+            low: Box::new(replace_count_distinct(low)),
+            high: Box::new(replace_count_distinct(high)),
+            negated: *negated,
+        },
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => Expression::Case {
+            when_then: when_then
+                .iter()
+                .map(|(condition, result)| {
+                    (
+                        replace_count_distinct(condition),
+                        replace_count_distinct(result),
+                    )
+                })
+                .collect(),
+            else_expr: else_expr
+                .as_ref()
+                .map(|else_expr| Box::new(replace_count_distinct(else_expr))),
+        },
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard => expr.clone(),
+    }
+}
+
+/// Returns the plan of the groups of `group_by_exec` that satisfy the `HAVING` clause,
+/// or `None` if the `HAVING` clause can not be proven.
+///
+/// The groups are filtered by a `FilterExec` over the `GroupByExec`, which requires the uniqueness
+/// of the groups to be proven by the `GroupByExec`. Outside aggregations the `HAVING` clause refers to the group by
+/// columns of the result, and each of its aggregations has to be one of the aggregations of the result
+/// or a count of rows. `column_mapping` is the column mapping of the query, which tells which counts are null-aware.
+///
+/// If the columns of `group_by_exec` are not the result columns, e.g. because it has a hidden count column,
+/// the result columns are selected by a `ProjectionExec`.
+fn try_filter_groups(
+    group_by_exec: GroupByExec,
+    having: Option<&Expression>,
+    group_by: &[Ident],
+    result_aliased_exprs: &[AliasedResultExpr],
+    table_ref: &TableRef,
+    column_mapping: &IndexMap<Ident, ColumnRef>,
+) -> ConversionResult<Option<DynProofPlan>> {
+    let fields = group_by_exec.get_column_result_fields();
+    let column_ref =
+        |field: &ColumnField| ColumnRef::new(table_ref.clone(), field.name(), field.data_type());
+    let plan = if let Some(having) = having {
+        if !group_by_exec.proves_unique_groups() {
+            return Ok(None);
+        }
+        // The aggregations of the HAVING clause are replaced with identifiers as in postprocessing
+        let group_by_postprocessing = GroupByPostprocessing::try_new_with_having(
+            group_by.to_vec(),
+            result_aliased_exprs.to_vec(),
+            Some(having.clone()),
+        )?;
+        let aggregation_columns = group_by_postprocessing
+            .aggregation_exprs()
+            .iter()
+            .map(|(op, expr, id)| {
+                let field = result_aliased_exprs
+                    .iter()
+                    .find(|aliased_expr| {
+                        matches!(
+                            aliased_expr.expr.as_ref(),
+                            Expression::Aggregation { op: result_op, expr: result_expr }
+                                if result_op == op && result_expr.as_ref() == expr
+                        )
+                    })
+                    .and_then(|aliased_expr| {
+                        fields
+                            .iter()
+                            .find(|field| field.name() == Ident::from(aliased_expr.alias))
+                    })
+                    .or_else(|| {
+                        // Counts of expressions that are never null are the count column,
+                        // which is the last column of the result
+                        (*op == AggregationOperator::Count && is_never_null(expr, column_mapping))
+                            .then(|| &fields[fields.len() - 1])
+                    });
+                field.map(|field| (id.clone(), column_ref(field)))
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(aggregation_columns) = aggregation_columns else {
+            return Ok(None);
+        };
+        let having_column_mapping = group_by
+            .iter()
+            .cloned()
+            .zip(fields.iter().map(column_ref))
+            .chain(aggregation_columns)
+            .collect::<IndexMap<_, _>>();
+        let Ok(Some(where_clause)) = WhereExprBuilder::new(&having_column_mapping)
+            .build(group_by_postprocessing.having_expr().cloned().map(Box::new))
+        else {
+            return Ok(None);
+        };
+        let aliased_results = fields
+            .iter()
+            .map(|field| AliasedDynProofExpr {
+                expr: DynProofExpr::new_column(column_ref(field)),
+                alias: field.name(),
+            })
+            .collect();
+        DynProofPlan::new_filter_with_input(
+            aliased_results,
+            DynProofPlan::GroupBy(group_by_exec),
+            where_clause,
+        )
+    } else {
+        DynProofPlan::GroupBy(group_by_exec)
+    };
+    let is_result = fields.len() == result_aliased_exprs.len()
+        && fields
+            .iter()
+            .zip(result_aliased_exprs)
+            .all(|(field, aliased_expr)| field.name() == Ident::from(aliased_expr.alias));
+    if is_result {
+        return Ok(Some(plan));
+    }
+    let aliased_results = result_aliased_exprs
+        .iter()
+        .map(|aliased_expr| {
+            let alias: Ident = aliased_expr.alias.into();
+            fields
+                .iter()
+                .find(|field| field.name() == alias)
+                .map(|field| AliasedDynProofExpr {
+                    expr: DynProofExpr::new_column(column_ref(field)),
+                    alias,
+                })
+        })
+        .collect::<Option<Vec<_>>>();
+    Ok(aliased_results.map(|aliased_results| DynProofPlan::new_projection(aliased_results, plan)))
+}
+
+/// Whether `expr` is `*` or an expression over `column_mapping` that is never null
+fn is_never_null(expr: &Expression, column_mapping: &IndexMap<Ident, ColumnRef>) -> bool {
+    if *expr == Expression::Wildcard {
+        return true;
+    }
+    let builder = DynProofExprBuilder::new(column_mapping);
+    builder
+        .build(expr)
+        .is_ok_and(|expr| expr.presence_expr(&builder.nullable_columns()).is_none())
+}
+
+/// Adds the steps grouping and aggregating the result of the provable part of a query to `postprocessing`.
+fn push_group_by_postprocessing(
+    group_by: &[Ident],
+    result_aliased_exprs: Vec<AliasedResultExpr>,
+    having: Option<&Expression>,
+    postprocessing: &mut Vec<OwnedTablePostprocessing>,
+) -> ConversionResult<()> {
+    let group_by_postprocessing = GroupByPostprocessing::try_new_with_having(
+        group_by.to_vec(),
+        result_aliased_exprs,
+        having.cloned(),
+    )?;
+    postprocessing.push(OwnedTablePostprocessing::new_group_by(
+        group_by_postprocessing.clone(),
+    ));
+    let remainder_exprs = group_by_postprocessing.remainder_exprs();
+    // Check whether we need to do select postprocessing.
+    // That is, if any of them is not simply a column reference.
+    if remainder_exprs
+        .iter()
+        .any(|expr| expr.try_as_identifier().is_none())
+    {
+        postprocessing.push(OwnedTablePostprocessing::new_select(
+            SelectPostprocessing::new(remainder_exprs.to_vec()),
+        ));
+    }
+    Ok(())
+}
@@ -1,22 +1,24 @@
 use super::ConversionError;
 use crate::{
     base::{
-        database::{ColumnType, TableRef, TestSchemaAccessor},
+        database::{ColumnRef, ColumnType, LiteralValue, TableRef, TestSchemaAccessor},
         map::{indexmap, IndexMap, IndexSet},
+        math::decimal::Precision,
     },
     sql::{
         parse::QueryExpr,
         postprocessing::{test_utility::*, PostprocessingError},
-        proof_exprs::test_utility::*,
-        proof_plans::{test_utility::*, DynProofPlan},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan, JoinType},
     },
 };
 use itertools::Itertools;
 use proof_of_sql_parser::{
+    posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone},
     sql::SelectStatementParser,
     utility::{
-        add as padd, aliased_expr, col, count, count_all, lit, max, min, mul as pmul, sub as psub,
-        sum,
+        add as padd, aliased_expr, col, count, count_all, gt, lit, max, min, mul as pmul,
+        sub as psub, sum,
     },
 };
 use sqlparser::ast::Ident;
@@ -632,12 +634,15 @@ fn we_can_parse_order_by_with_a_single_column() {
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["b", "a"], &accessor),
-            tab(&t),
-            equal(column(&t, "a", &accessor), const_bigint(3)),
+        sort_exec(
+            filter(
+                cols_expr_plan(&t, &["b", "a"], &accessor),
+                tab(&t),
+                equal(column(&t, "a", &accessor), const_bigint(3)),
+            ),
+            vec![(0, true)],
         ),
-        vec![orders(&[0_usize], &[true])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -658,15 +663,18 @@ fn we_can_parse_order_by_with_multiple_columns() {
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(&t, &["a", "b"], &accessor),
-            tab(&t),
-            equal(
-                column(&t, "a", &accessor),
-                add(column(&t, "b", &accessor), const_bigint(3)),
+        sort_exec(
+            filter(
+                cols_expr_plan(&t, &["a", "b"], &accessor),
+                tab(&t),
+                equal(
+                    column(&t, "a", &accessor),
+                    add(column(&t, "b", &accessor), const_bigint(3)),
+                ),
             ),
+            vec![(1, false), (0, true)],
         ),
-        vec![orders(&[1_usize, 0], &[false, true])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -790,16 +798,19 @@ fn we_can_parse_order_by_queries_with_the_same_column_name_appearing_more_than_o
             &accessor,
         );
         let expected_ast = QueryExpr::new(
-            filter(
-                vec![
-                    aliased_col_expr_plan(&t, "salary", "s", &accessor),
-                    col_expr_plan(&t, "name", &accessor),
-                    aliased_col_expr_plan(&t, "salary", "d", &accessor),
-                ],
-                tab(&t),
-                const_bool(true),
+            sort_exec(
+                filter(
+                    vec![
+                        aliased_col_expr_plan(&t, "salary", "s", &accessor),
+                        col_expr_plan(&t, "name", &accessor),
+                        aliased_col_expr_plan(&t, "salary", "d", &accessor),
+                    ],
+                    tab(&t),
+                    const_bool(true),
+                ),
+                vec![(index, true)],
             ),
-            vec![orders(&[index], &[true])],
+            vec![],
         );
         assert_eq!(ast, expected_ast);
     }
@@ -934,21 +945,86 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_wher
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            vec![
-                col_expr_plan(&t, "a", &accessor),
-                aliased_plan(
-                    and(
-                        column(&t, "boolean", &accessor),
-                        gte(column(&t, "a", &accessor), const_bigint(4)),
-                    ),
-                    "res",
+        slice_exec(
+            sort_exec(
+                filter(
+                    vec![
+                        col_expr_plan(&t, "a", &accessor),
+                        aliased_plan(
+                            and(
+                                column(&t, "boolean", &accessor),
+                                gte(column(&t, "a", &accessor), const_bigint(4)),
+                            ),
+                            "res",
+                        ),
+                    ],
+                    tab(&t),
+                    equal(column(&t, "a", &accessor), const_bigint(-3)),
                 ),
-            ],
+                vec![(0, false)],
+            ),
+            3,
+            Some(55),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_an_order_by_followed_by_a_limit_without_an_offset() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "revenue".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select name, revenue from sxt_tab order by revenue desc limit 10",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                filter(
+                    cols_expr_plan(&t, &["name", "revenue"], &accessor),
+                    tab(&t),
+                    const_bool(true),
+                ),
+                vec![(1, false)],
+            ),
+            0,
+            Some(10),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_prove_an_order_by_followed_by_a_negative_offset() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select a from sxt_tab order by a limit 2 offset -1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
             tab(&t),
-            equal(column(&t, "a", &accessor), const_bigint(-3)),
+            const_bool(true),
         ),
-        vec![orders(&[0_usize], &[false]), slice(Some(55), Some(3))],
+        vec![orders(&[0_usize], &[true]), slice(Some(2), Some(-1))],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -973,7 +1049,7 @@ fn we_can_do_provable_group_by() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(&t, &["department"], &accessor),
+            cols_expr_plan(&t, &["department"], &accessor),
             vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
             "num_employee",
             tab(&t),
@@ -1001,7 +1077,7 @@ fn we_can_do_provable_group_by_without_sum() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(&t, &["department"], &accessor),
+            cols_expr_plan(&t, &["department"], &accessor),
             vec![],
             "num_employee",
             tab(&t),
@@ -1012,6 +1088,36 @@ fn we_can_do_provable_group_by_without_sum() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_do_provable_group_by_with_max_and_min() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, sum(salary) as total_salary, max(salary) as max_salary, min(salary) as min_salary, count(*) as num_employee from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_max_min(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
+            vec![max_expr(column(&t, "salary", &accessor), "max_salary")],
+            vec![min_expr(column(&t, "salary", &accessor), "min_salary")],
+            "num_employee",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_do_provable_group_by_with_two_group_by_columns() {
     let t = TableRef::new("sxt", "employees");
@@ -1030,7 +1136,7 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(&t, &["state", "department"], &accessor),
+            cols_expr_plan(&t, &["state", "department"], &accessor),
             vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
             "num_employee",
             tab(&t),
@@ -1059,7 +1165,7 @@ fn we_can_do_provable_group_by_with_two_sums_and_filter() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(&t, &["department"], &accessor),
+            cols_expr_plan(&t, &["department"], &accessor),
             vec![
                 sum_expr(column(&t, "salary", &accessor), "total_salary"),
                 sum_expr(column(&t, "tax", &accessor), "total_tax"),
@@ -1073,132 +1179,183 @@ fn we_can_do_provable_group_by_with_two_sums_and_filter() {
     assert_eq!(ast, expected_ast);
 }
 
-///////////////////////////
-// Group By Expressions - Postprocessing
-///////////////////////////
 #[test]
-fn we_can_group_by_without_using_aggregate_functions() {
+fn we_can_do_provable_group_by_an_expression() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
-            "department".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         &t,
-        "select department, true as is_remote from employees group by department",
+        "select salary + tax as total, sum(salary) as total_salary, count(*) as num_employee from employees group by salary + tax",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            vec![col_expr_plan(&t, "department", &accessor)],
+        group_by(
+            vec![aliased_plan(
+                add(
+                    column(&t, "salary", &accessor),
+                    column(&t, "tax", &accessor),
+                ),
+                "total",
+            )],
+            vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
+            "num_employee",
             tab(&t),
             const_bool(true),
         ),
-        vec![
-            group_by_postprocessing(
-                &["department"],
-                &[
-                    aliased_expr(col("department"), "department"),
-                    aliased_expr(lit(true), "is_remote"),
-                ],
-            ),
-            select_expr(&[
-                aliased_expr(col("department"), "department"),
-                aliased_expr(lit(true), "is_remote"),
-            ]),
-        ],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_expressions_are_parsed_before_an_order_by_referencing_an_aggregate_alias_result() {
-    let query_text =
-        "select max(salary) max_sal, department_budget d, count(department_budget) from sxt.employees group by department_budget, tax order by max_sal";
-
+fn we_can_do_provable_group_by_with_having() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "department_budget".into() => ColumnType::BigInt,
             "salary".into() => ColumnType::BigInt,
-            "tax".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
         },
     );
-
-    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
-    let query =
-        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
-
-    let expected_query = QueryExpr::new(
-        filter(
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department having sum(salary) >= 100 and count(*) >= 2 and department = 3",
+        &accessor,
+    );
+    let output_column = |name: &str| {
+        DynProofExpr::new_column(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt))
+    };
+    let expected_ast = QueryExpr::new(
+        filter_with_input(
             vec![
-                col_expr_plan(&t, "department_budget", &accessor),
-                col_expr_plan(&t, "salary", &accessor),
-                col_expr_plan(&t, "tax", &accessor),
+                aliased_plan(output_column("department"), "department"),
+                aliased_plan(output_column("total_salary"), "total_salary"),
+                aliased_plan(output_column("num_employee"), "num_employee"),
             ],
-            tab(&t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &["department_budget", "tax"],
-                &[
-                    aliased_expr(max(col("salary")), "max_sal"),
-                    aliased_expr(col("department_budget"), "d"),
-                    aliased_expr(count(col("department_budget")), "__count__"),
-                ],
+            group_by(
+                cols_expr_plan(&t, &["department"], &accessor),
+                vec![sum_expr(column(&t, "salary", &accessor), "total_salary")],
+                "num_employee",
+                tab(&t),
+                const_bool(true),
             ),
-            orders(&[0_usize], &[true]),
-        ],
+            and(
+                and(
+                    gte(output_column("total_salary"), const_bigint(100)),
+                    gte(output_column("num_employee"), const_bigint(2)),
+                ),
+                equal(output_column("department"), const_bigint(3)),
+            ),
+        ),
+        vec![],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_cannot_parse_non_aggregated_or_non_group_by_columns_in_the_select_clause() {
+fn we_can_do_provable_group_by_with_null_aware_aggregations() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
+            "salary$presence".into() => ColumnType::Boolean,
             "department".into() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         &t,
-        "select department, salary from sxt.employees group by department",
+        "select department, sum(salary) as total_salary, count(salary) as num_salaries, count(*) as num_employee from employees group by department",
         &accessor,
     );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(&t, &["department"], &accessor),
+            vec![
+                sum_expr(
+                    case_when(
+                        vec![(
+                            column(&t, "salary$presence", &accessor),
+                            column(&t, "salary", &accessor),
+                        )],
+                        const_bigint(0),
+                    ),
+                    "total_salary",
+                ),
+                sum_expr(
+                    cast(column(&t, "salary$presence", &accessor), ColumnType::BigInt),
+                    "num_salaries",
+                ),
+            ],
+            "num_employee",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn alias_references_are_not_allowed_in_the_group_by() {
+fn we_can_do_provable_group_by_with_a_null_aware_count_as_the_last_column() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
             "salary".into() => ColumnType::BigInt,
+            "salary$presence".into() => ColumnType::Boolean,
             "department".into() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         &t,
-        "select department, min(salary) as min_salary from employees group by min_salary",
+        "select department, count(salary) as num_salaries from employees group by department having count(*) >= 2",
         &accessor,
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select salary as min_salary from employees group by min_salary",
-        &accessor,
+    let output_column = |name: &str| {
+        DynProofExpr::new_column(ColumnRef::new(t.clone(), name.into(), ColumnType::BigInt))
+    };
+    let expected_ast = QueryExpr::new(
+        projection(
+            vec![
+                aliased_plan(output_column("department"), "department"),
+                aliased_plan(output_column("num_salaries"), "num_salaries"),
+            ],
+            filter_with_input(
+                vec![
+                    aliased_plan(output_column("department"), "department"),
+                    aliased_plan(output_column("num_salaries"), "num_salaries"),
+                    aliased_plan(output_column("$count"), "$count"),
+                ],
+                group_by(
+                    cols_expr_plan(&t, &["department"], &accessor),
+                    vec![sum_expr(
+                        cast(column(&t, "salary$presence", &accessor), ColumnType::BigInt),
+                        "num_salaries",
+                    )],
+                    "$count",
+                    tab(&t),
+                    const_bool(true),
+                ),
+                gte(output_column("$count"), const_bigint(2)),
+            ),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
 }
 
+///////////////////////////
+// Group By Expressions - Postprocessing
+///////////////////////////
 #[test]
-fn order_by_cannot_reference_an_invalid_group_by_column() {
+fn we_can_filter_groups_by_a_having_clause_in_postprocessing() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
@@ -1207,20 +1364,33 @@ fn order_by_cannot_reference_an_invalid_group_by_column() {
             "department".into() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    // MAX(salary) is not a result column, so the HAVING clause can not be proven
+    let ast = query_to_provable_ast(
         &t,
-        "select department as d from sxt.employees group by department order by department",
+        "select department, sum(salary) as total_salary, count(*) as num_employee from employees group by department having max(salary) > 10",
         &accessor,
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select department, min(salary) from sxt.employees group by department order by salary",
-        &accessor,
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["department", "salary"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing_with_having(
+            &["department"],
+            &[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(sum(col("salary")), "total_salary"),
+                aliased_expr(count_all(), "num_employee"),
+            ],
+            gt(max(col("salary")), lit(10)),
+        )],
     );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_column_cannot_be_a_column_result_alias() {
+fn we_cannot_use_invalid_having_clauses() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
@@ -1229,22 +1399,198 @@ fn group_by_column_cannot_be_a_column_result_alias() {
             "department".into() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
-        &t,
-        "select min(salary) as min_sal from sxt.employees group by min_sal",
-        &accessor,
-    );
+    let try_query = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            t.schema_id().cloned().unwrap(),
+            &accessor,
+        )
+    };
+    assert!(matches!(
+        try_query("select department, count(*) as n from sxt.employees group by department having salary > 10"),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::IdentNotInAggregationOperatorOrGroupByClause { .. }
+        })
+    ));
+    assert!(matches!(
+        try_query("select sum(salary) as s from sxt.employees having department > 10"),
+        Err(ConversionError::InvalidGroupByColumnRef { .. })
+    ));
+    assert!(matches!(
+        try_query("select department, count(*) as n from sxt.employees group by department having sum(salary)"),
+        Err(ConversionError::NonbooleanHavingClause { .. })
+    ));
 }
 
 #[test]
-fn we_can_have_aggregate_functions_without_a_group_by_clause() {
-    let query_text = "select count(name) from sxt.employees";
+fn we_can_group_by_without_using_aggregate_functions() {
     let t = TableRef::new("sxt", "employees");
     let accessor = schema_accessor_from_table_ref_with_schema(
         &t,
         indexmap! {
-            "name".into() => ColumnType::VarChar,
-        },
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select department, true as is_remote from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![col_expr_plan(&t, "department", &accessor)],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &["department"],
+                &[
+                    aliased_expr(col("department"), "department"),
+                    aliased_expr(lit(true), "is_remote"),
+                ],
+            ),
+            select_expr(&[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(lit(true), "is_remote"),
+            ]),
+        ],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn group_by_expressions_are_parsed_before_an_order_by_referencing_an_aggregate_alias_result() {
+    let query_text =
+        "select max(salary) max_sal, department_budget d, count(department_budget) from sxt.employees group by department_budget, tax order by max_sal";
+
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "department_budget".into() => ColumnType::BigInt,
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+        },
+    );
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    let expected_query = QueryExpr::new(
+        filter(
+            vec![
+                col_expr_plan(&t, "department_budget", &accessor),
+                col_expr_plan(&t, "salary", &accessor),
+                col_expr_plan(&t, "tax", &accessor),
+            ],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &["department_budget", "tax"],
+                &[
+                    aliased_expr(max(col("salary")), "max_sal"),
+                    aliased_expr(col("department_budget"), "d"),
+                    aliased_expr(count(col("department_budget")), "__count__"),
+                ],
+            ),
+            orders(&[0_usize], &[true]),
+        ],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_cannot_parse_non_aggregated_or_non_group_by_columns_in_the_select_clause() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, salary from sxt.employees group by department",
+        &accessor,
+    );
+}
+
+#[test]
+fn alias_references_are_not_allowed_in_the_group_by() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, min(salary) as min_salary from employees group by min_salary",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select salary as min_salary from employees group by min_salary",
+        &accessor,
+    );
+}
+
+#[test]
+fn order_by_cannot_reference_an_invalid_group_by_column() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department as d from sxt.employees group by department order by department",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select department, min(salary) from sxt.employees group by department order by salary",
+        &accessor,
+    );
+}
+
+#[test]
+fn group_by_column_cannot_be_a_column_result_alias() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "department".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select min(salary) as min_sal from sxt.employees group by min_sal",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_have_aggregate_functions_without_a_group_by_clause() {
+    let query_text = "select count(name) from sxt.employees";
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
     );
 
     let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
@@ -1485,6 +1831,44 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_division_and_modulo_in_the_result_expr_and_the_where_clause() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select a / b as q, a % b as r from employees where a % 2 = 0",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "q",
+                ),
+                aliased_plan(
+                    modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                    "r",
+                ),
+            ],
+            tab(&t),
+            equal(
+                modulo(column(&t, "a", &accessor), const_bigint(2)),
+                const_bigint(0),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
 ) {
@@ -1876,6 +2260,90 @@ fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
     assert_eq!(query, expected_query);
 }
 
+#[test]
+fn we_can_use_group_by_expressions_inside_result_expressions() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+        },
+    );
+    let query_text =
+        "select 2 * (salary + tax) as s, count(*) as n from sxt.employees group by salary + tax";
+
+    let intermediate_ast = SelectStatementParser::new().parse(query_text).unwrap();
+    let query =
+        QueryExpr::try_new(intermediate_ast, t.schema_id().cloned().unwrap(), &accessor).unwrap();
+
+    // The GROUP BY expression is computed by the filter as a hidden column
+    let expected_query = QueryExpr::new(
+        filter(
+            vec![
+                col_expr_plan(&t, "salary", &accessor),
+                col_expr_plan(&t, "tax", &accessor),
+                aliased_plan(
+                    add(
+                        column(&t, "salary", &accessor),
+                        column(&t, "tax", &accessor),
+                    ),
+                    "__group_by_0__",
+                ),
+            ],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &["__group_by_0__"],
+                &[
+                    aliased_expr(pmul(lit(2), col("__group_by_0__")), "s"),
+                    aliased_expr(count_all(), "n"),
+                ],
+            ),
+            select_expr(&[
+                aliased_expr(pmul(lit(2), col("__group_by_0__")), "s"),
+                aliased_expr(col("__col_agg_0"), "n"),
+            ]),
+        ],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_cannot_use_invalid_group_by_expressions() {
+    let t = TableRef::new("sxt", "employees");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "salary".into() => ColumnType::BigInt,
+            "tax".into() => ColumnType::BigInt,
+        },
+    );
+    let try_query = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            t.schema_id().cloned().unwrap(),
+            &accessor,
+        )
+    };
+    assert!(matches!(
+        try_query("select count(*) as n from sxt.employees group by sum(salary)"),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        try_query("select salary, count(*) as n from sxt.employees group by salary + tax"),
+        Err(ConversionError::PostprocessingError {
+            source: PostprocessingError::IdentNotInAggregationOperatorOrGroupByClause { .. }
+        })
+    ));
+    assert!(matches!(
+        try_query("select count(distinct tax) as n from sxt.employees group by salary + tax"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+}
+
 #[test]
 fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
     let t = TableRef::new("sxt", "employees");
@@ -2125,6 +2593,1099 @@ fn select_group_and_order_by_preserve_the_column_order_reference() {
     }
 }
 
+// Joins
+fn orders_and_customers_accessor() -> (TableRef, TableRef, TestSchemaAccessor) {
+    let orders = TableRef::new("sxt", "orders");
+    let customers = TableRef::new("sxt", "customers");
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        orders.clone() => indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "customer_id".into() => ColumnType::BigInt,
+            "amount".into() => ColumnType::BigInt,
+        },
+        customers.clone() => indexmap! {
+            "id".into() => ColumnType::BigInt,
+            "name".into() => ColumnType::VarChar,
+        },
+    });
+    (orders, customers, accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_inner_join_of_aliased_tables() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select o.amount, c.name from orders o join customers c on o.customer_id = c.id where c.name = 'abc'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        projection(
+            vec![
+                col_expr_plan(&orders, "amount", &accessor),
+                col_expr_plan(&customers, "name", &accessor),
+            ],
+            sort_merge_join(
+                filter(
+                    cols_expr_plan(&orders, &["customer_id", "amount"], &accessor),
+                    tab(&orders),
+                    const_bool(true),
+                ),
+                filter(
+                    aliased_cols_expr_plan(
+                        &customers,
+                        &[("id", "c_id"), ("name", "name")],
+                        &accessor,
+                    ),
+                    tab(&customers),
+                    equal(column(&customers, "name", &accessor), const_varchar("abc")),
+                ),
+                vec![0],
+                vec![0],
+                vec!["customer_id".into(), "amount".into(), "name".into()],
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_left_join_and_select_star() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select * from orders left join customers on customer_id = customers.id where amount = 5",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        outer_join(
+            filter(
+                aliased_cols_expr_plan(
+                    &orders,
+                    &[
+                        ("customer_id", "customer_id"),
+                        ("id", "orders_id"),
+                        ("amount", "amount"),
+                    ],
+                    &accessor,
+                ),
+                tab(&orders),
+                equal(column(&orders, "amount", &accessor), const_bigint(5)),
+            ),
+            filter(
+                aliased_cols_expr_plan(
+                    &customers,
+                    &[("id", "customers_id"), ("name", "name")],
+                    &accessor,
+                ),
+                tab(&customers),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![0],
+            vec![
+                "customer_id".into(),
+                "orders_id".into(),
+                "amount".into(),
+                "name".into(),
+            ],
+            JoinType::Left,
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("orders_id"), "orders_id"),
+            aliased_expr(col("customer_id"), "customer_id"),
+            aliased_expr(col("amount"), "amount"),
+            aliased_expr(col("name"), "name"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_right_join_and_select_star() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select * from orders right join customers on customer_id = customers.id where name = 'abc'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        outer_join(
+            filter(
+                aliased_cols_expr_plan(
+                    &orders,
+                    &[
+                        ("customer_id", "customer_id"),
+                        ("id", "orders_id"),
+                        ("amount", "amount"),
+                    ],
+                    &accessor,
+                ),
+                tab(&orders),
+                const_bool(true),
+            ),
+            filter(
+                aliased_cols_expr_plan(
+                    &customers,
+                    &[("id", "customers_id"), ("name", "name")],
+                    &accessor,
+                ),
+                tab(&customers),
+                equal(column(&customers, "name", &accessor), const_varchar("abc")),
+            ),
+            vec![0],
+            vec![0],
+            vec![
+                "customer_id".into(),
+                "orders_id".into(),
+                "amount".into(),
+                "name".into(),
+            ],
+            JoinType::Right,
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("orders_id"), "orders_id"),
+            aliased_expr(col("amount"), "amount"),
+            aliased_expr(col("customer_id"), "customer_id"),
+            aliased_expr(col("name"), "name"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_full_join() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select amount, name from orders full outer join customers on customer_id = customers.id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        outer_join(
+            filter(
+                cols_expr_plan(&orders, &["customer_id", "amount"], &accessor),
+                tab(&orders),
+                const_bool(true),
+            ),
+            filter(
+                aliased_cols_expr_plan(
+                    &customers,
+                    &[("id", "customers_id"), ("name", "name")],
+                    &accessor,
+                ),
+                tab(&customers),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![0],
+            vec!["customer_id".into(), "amount".into(), "name".into()],
+            JoinType::Full,
+        ),
+        vec![select_expr(&[
+            aliased_expr(col("amount"), "amount"),
+            aliased_expr(col("name"), "name"),
+        ])],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_group_by_a_column_of_a_join() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select name, sum(amount) as total from orders join customers on customer_id = customers.id group by name",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        sort_merge_join(
+            filter(
+                cols_expr_plan(&orders, &["customer_id", "amount"], &accessor),
+                tab(&orders),
+                const_bool(true),
+            ),
+            filter(
+                aliased_cols_expr_plan(
+                    &customers,
+                    &[("id", "customers_id"), ("name", "name")],
+                    &accessor,
+                ),
+                tab(&customers),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![0],
+            vec!["customer_id".into(), "amount".into(), "name".into()],
+        ),
+        vec![group_by_postprocessing(
+            &["name"],
+            &[
+                aliased_expr(col("name"), "name"),
+                aliased_expr(sum(col("amount")), "total"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_a_group_by_of_a_join() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select customer_id, sum(amount) as total, count(*) as num_orders from orders join customers on customer_id = customers.id where name = 'abc' group by customer_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_input(
+            cols_expr_plan(&orders, &["customer_id"], &accessor),
+            vec![sum_expr(column(&orders, "amount", &accessor), "total")],
+            "num_orders",
+            sort_merge_join(
+                filter(
+                    cols_expr_plan(&orders, &["customer_id", "amount"], &accessor),
+                    tab(&orders),
+                    const_bool(true),
+                ),
+                filter(
+                    aliased_cols_expr_plan(
+                        &customers,
+                        &[("id", "customers_id"), ("name", "name")],
+                        &accessor,
+                    ),
+                    tab(&customers),
+                    equal(column(&customers, "name", &accessor), const_varchar("abc")),
+                ),
+                vec![0],
+                vec![0],
+                vec!["customer_id".into(), "amount".into(), "name".into()],
+            ),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_prove_a_group_by_of_a_join_with_nullable_columns() {
+    let orders = TableRef::new("sxt", "orders");
+    let customers = TableRef::new("sxt", "customers");
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        orders.clone() => indexmap! {
+            "customer_id".into() => ColumnType::BigInt,
+            "amount".into() => ColumnType::BigInt,
+            "amount$presence".into() => ColumnType::Boolean,
+        },
+        customers.clone() => indexmap! {
+            "id".into() => ColumnType::BigInt,
+        },
+    });
+    let ast = query_to_provable_ast(
+        &orders,
+        "select customer_id, sum(amount) as total, count(*) as num_orders from orders join customers on customer_id = customers.id group by customer_id",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_input(
+            cols_expr_plan(&orders, &["customer_id"], &accessor),
+            vec![sum_expr(
+                case_when(
+                    vec![(
+                        column(&orders, "amount$presence", &accessor),
+                        column(&orders, "amount", &accessor),
+                    )],
+                    const_bigint(0),
+                ),
+                "total",
+            )],
+            "num_orders",
+            sort_merge_join(
+                filter(
+                    cols_expr_plan(
+                        &orders,
+                        &["customer_id", "amount", "amount$presence"],
+                        &accessor,
+                    ),
+                    tab(&orders),
+                    const_bool(true),
+                ),
+                filter(
+                    aliased_cols_expr_plan(&customers, &[("id", "customers_id")], &accessor),
+                    tab(&customers),
+                    const_bool(true),
+                ),
+                vec![0],
+                vec![0],
+                vec![
+                    "customer_id".into(),
+                    "amount".into(),
+                    "amount$presence".into(),
+                ],
+            ),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_join() {
+    let (_, _, accessor) = orders_and_customers_accessor();
+    let try_query = |query: &str| {
+        let intermediate_ast = SelectStatementParser::new().parse(query).unwrap();
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor)
+    };
+    assert!(matches!(
+        try_query("select id from orders join customers on customer_id = customers.id"),
+        Err(ConversionError::AmbiguousColumn { .. })
+    ));
+    assert!(matches!(
+        try_query("select x.amount from orders o join customers c on customer_id = c.id"),
+        Err(ConversionError::MissingTable { .. })
+    ));
+    assert!(matches!(
+        try_query("select o.name from orders o join customers c on customer_id = c.id"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+    assert!(matches!(
+        try_query("select amount from orders join customers on customer_id = name"),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        try_query("select amount from orders join customers on customer_id < customers.id"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query(
+            "select amount from orders left join customers on customer_id = customers.id where name = 'abc'"
+        ),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query(
+            "select name from orders right join customers on customer_id = customers.id where amount = 5"
+        ),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query(
+            "select customer_id from orders right join customers on customer_id = customers.id"
+        ),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query("select * from orders full join customers on customer_id = customers.id"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query("select amount from orders join orders on customer_id = id"),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+// Subqueries
+#[test]
+fn we_can_convert_an_ast_with_a_subquery_in_from() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::BigInt,
+        },
+    );
+    let subquery_plan = || {
+        filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            equal(column(&t, "c", &accessor), const_bigint(1)),
+        )
+    };
+    let ast = query_to_provable_ast(
+        &t,
+        "select a, b from (select a, b from sxt_tab where c = 1) as s",
+        &accessor,
+    );
+    assert_eq!(ast, QueryExpr::new(subquery_plan(), vec![]));
+    let ast = query_to_provable_ast(
+        &t,
+        "select b, sum(a) as total from (select a, b from sxt_tab where c = 1) s group by b",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        subquery_plan(),
+        vec![group_by_postprocessing(
+            &["b"],
+            &[
+                aliased_expr(col("b"), "b"),
+                aliased_expr(sum(col("a")), "total"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_in_and_not_in_subqueries() {
+    let (orders, customers, accessor) = orders_and_customers_accessor();
+    let ast = query_to_provable_ast(
+        &orders,
+        "select amount from orders where customer_id in (select id from customers where name = 'abc') and amount >= 5",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        semi_join(
+            filter(
+                cols_expr_plan(&orders, &["amount", "customer_id"], &accessor),
+                tab(&orders),
+                gte(column(&orders, "amount", &accessor), const_bigint(5)),
+            ),
+            filter(
+                cols_expr_plan(&customers, &["id"], &accessor),
+                tab(&customers),
+                equal(column(&customers, "name", &accessor), const_varchar("abc")),
+            ),
+            vec![1],
+            vec![0],
+        ),
+        vec![select_expr(&[aliased_expr(col("amount"), "amount")])],
+    );
+    assert_eq!(ast, expected_ast);
+    let ast = query_to_provable_ast(
+        &orders,
+        "select id, customer_id from orders where id not in (select id from customers)",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        anti_join(
+            filter(
+                cols_expr_plan(&orders, &["id", "customer_id"], &accessor),
+                tab(&orders),
+                const_bool(true),
+            ),
+            filter(
+                cols_expr_plan(&customers, &["id"], &accessor),
+                tab(&customers),
+                const_bool(true),
+            ),
+            vec![0],
+            vec![0],
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_subquery() {
+    let (_, _, accessor) = orders_and_customers_accessor();
+    let try_query = |query: &str| {
+        let intermediate_ast = SelectStatementParser::new().parse(query).unwrap();
+        QueryExpr::try_new(intermediate_ast, "sxt".into(), &accessor)
+    };
+    assert!(matches!(
+        try_query(
+            "select amount from orders where customer_id in (select id from customers) or amount = 5"
+        ),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query(
+            "select amount from orders where customer_id in (select id, name from customers)"
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        try_query("select amount from orders where customer_id in (select name from customers)"),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        try_query("select amount from (select id, amount from orders) o where amount = 5"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query("select id from (select id, name from customers order by name) c"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        try_query(
+            "select amount from orders join customers on customer_id = customers.id where amount in (select id from orders)"
+        ),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_casts() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::Int,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select cast(a as smallint) as a, cast(b as decimal(12, 2)) as b from sxt_tab where cast(b as bigint) = a",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(cast(column(&t, "a", &accessor), ColumnType::SmallInt), "a"),
+                aliased_plan(
+                    cast(
+                        column(&t, "b", &accessor),
+                        ColumnType::Decimal75(Precision::new(12).unwrap(), 2),
+                    ),
+                    "b",
+                ),
+            ],
+            tab(&t),
+            equal(
+                cast(column(&t, "b", &accessor), ColumnType::BigInt),
+                column(&t, "a", &accessor),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_cast() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::VarChar,
+        },
+    );
+    assert!(matches!(
+        QueryExpr::try_new(
+            SelectStatementParser::new()
+                .parse("select cast(a as bigint) as a from sxt_tab")
+                .unwrap(),
+            "sxt".into(),
+            &accessor,
+        ),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_in_lists_and_between() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select a from sxt_tab where b in ('x', 'y') and a not in (1, 2) and a between 0 and 10 and a not between 3 and 4",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            and(
+                and(
+                    and(
+                        in_list(
+                            column(&t, "b", &accessor),
+                            vec![
+                                LiteralValue::VarChar("x".to_string()),
+                                LiteralValue::VarChar("y".to_string()),
+                            ],
+                        ),
+                        not(in_list(
+                            column(&t, "a", &accessor),
+                            vec![LiteralValue::BigInt(1), LiteralValue::BigInt(2)],
+                        )),
+                    ),
+                    between(
+                        column(&t, "a", &accessor),
+                        const_bigint(0),
+                        const_bigint(10),
+                    ),
+                ),
+                not(between(
+                    column(&t, "a", &accessor),
+                    const_bigint(3),
+                    const_bigint(4),
+                )),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_in_list_or_between() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+        },
+    );
+    let try_query = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    assert!(matches!(
+        try_query("select a from sxt_tab where a in (1, 'x')"),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        try_query("select a from sxt_tab where a in (1, a + 1)"),
+        Err(ConversionError::Unprovable { .. })
+    ));
+    assert!(matches!(
+        try_query("select a from sxt_tab where a between 1 and b"),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_string_operations() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::VarChar,
+            "a$key".into() => ColumnType::Scalar,
+            "a$length".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select length(a) as l from sxt_tab where a like 'ab%' and a not like 'x' and a < 'y'",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(length(column(&t, "a", &accessor)), "l")],
+            tab(&t),
+            and(
+                and(
+                    starts_with(column(&t, "a", &accessor), "ab"),
+                    not(equal(column(&t, "a", &accessor), const_varchar("x"))),
+                ),
+                DynProofExpr::try_new_inequality(
+                    column(&t, "a", &accessor),
+                    const_varchar("y"),
+                    true,
+                )
+                .unwrap(),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Encoding columns are not selected by `*`
+    let ast = query_to_provable_ast(&t, "select * from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["a"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_string_operations_without_encoding_columns() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::VarChar,
+            "a$key".into() => ColumnType::Scalar,
+            "b".into() => ColumnType::VarChar,
+            "c".into() => ColumnType::BigInt,
+        },
+    );
+    let try_query = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    assert!(try_query("select a from sxt_tab where a >= 'b' and b = 'x' and b like 'y'").is_ok());
+    assert!(matches!(
+        try_query("select a from sxt_tab where a < b"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+    assert!(matches!(
+        try_query("select length(a) as l from sxt_tab"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+    assert!(matches!(
+        try_query("select a from sxt_tab where b like 'x%'"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+    assert!(matches!(
+        try_query("select a from sxt_tab where c like 'x%'"),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    assert!(matches!(
+        try_query("select a from sxt_tab where a like '%x'"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_timestamp_operations() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select date_trunc('day', a) as d, a - interval '1 hour' as b from sxt_tab where extract(dow from a) = 1",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Day),
+                    "d",
+                ),
+                aliased_plan(add_interval(column(&t, "a", &accessor), "-1 hour"), "b"),
+            ],
+            tab(&t),
+            equal(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::DayOfWeek),
+                const_bigint(1),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Grouping by a truncated timestamp is provable
+    let ast = query_to_provable_ast(
+        &t,
+        "select date_trunc('week', a) as w, count(*) as n from sxt_tab group by date_trunc('week', a)",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![aliased_plan(
+                date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Week),
+                "w",
+            )],
+            vec![],
+            "n",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // So is grouping by a truncated timestamp in a subquery, with the grouping in postprocessing
+    let ast = query_to_provable_ast(
+        &t,
+        "select w, count(*) as n from (select date_trunc('week', a) as w from sxt_tab) s group by w",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(
+                date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Week),
+                "w",
+            )],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["w"],
+            &[aliased_expr(col("w"), "w"), aliased_expr(count_all(), "n")],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_invalid_timestamp_operations() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+            "b".into() => ColumnType::BigInt,
+        },
+    );
+    let try_query = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    for query in [
+        "select a + interval '1 millisecond' as c from sxt_tab",
+        "select b + interval '1 day' as c from sxt_tab",
+        "select extract(day from a) as c from sxt_tab",
+        "select date_trunc('dow', a) as c from sxt_tab",
+        "select date_trunc('day', b) as c from sxt_tab",
+    ] {
+        assert!(
+            matches!(
+                try_query(query),
+                Err(ConversionError::ColumnOperationError { .. })
+            ),
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn we_can_convert_an_ast_with_case_expressions() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select case when a >= 10 then 'large' else 'small' end as size from sxt_tab where case b when 'x' then a = 1 else true end",
+        &accessor,
+    );
+    let size = || {
+        case_when(
+            vec![(
+                gte(column(&t, "a", &accessor), const_bigint(10)),
+                const_varchar("large"),
+            )],
+            const_varchar("small"),
+        )
+    };
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(size(), "size")],
+            tab(&t),
+            case_when(
+                vec![(
+                    equal(column(&t, "b", &accessor), const_varchar("x")),
+                    equal(column(&t, "a", &accessor), const_bigint(1)),
+                )],
+                const_bool(true),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Grouping by a CASE expression is provable
+    let ast = query_to_provable_ast(
+        &t,
+        "select case when a >= 10 then 'large' else 'small' end as size, count(*) as n from sxt_tab group by case when a >= 10 then 'large' else 'small' end",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by(
+            vec![aliased_plan(size(), "size")],
+            vec![],
+            "n",
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // So is grouping by a CASE expression in a subquery, with the grouping in postprocessing
+    let ast = query_to_provable_ast(
+        &t,
+        "select size, count(*) as n from (select case when a >= 10 then 'large' else 'small' end as size from sxt_tab) s group by size",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(size(), "size")],
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["size"],
+            &[
+                aliased_expr(col("size"), "size"),
+                aliased_expr(count_all(), "n"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_case_expression() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::VarChar,
+        },
+    );
+    let try_query = |query: &str| {
+        QueryExpr::try_new(
+            SelectStatementParser::new().parse(query).unwrap(),
+            "sxt".into(),
+            &accessor,
+        )
+    };
+    assert!(matches!(
+        try_query("select case when a = 1 then b else a end as c from sxt_tab"),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        try_query("select case when a then b else b end as c from sxt_tab"),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    assert!(matches!(
+        try_query("select case when a = 1 then b end as c from sxt_tab"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_select_distinct() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::Int,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select distinct a, b from sxt_tab where a = 3 order by b desc limit 2",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort_exec(
+                distinct_exec(filter(
+                    cols_expr_plan(&t, &["a", "b"], &accessor),
+                    tab(&t),
+                    equal(column(&t, "a", &accessor), const_bigint(3)),
+                )),
+                vec![(1, false)],
+            ),
+            0,
+            Some(2),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_select_distinct_of_strings_using_postprocessing() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "name".into() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(&t, "select distinct name from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(&t, &["name"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![distinct()],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_count_distinct() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+            "c".into() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        &t,
+        "select a, count(distinct b) as n from sxt_tab where c = 1 group by a",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        distinct_exec(filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            equal(column(&t, "c", &accessor), const_bigint(1)),
+        )),
+        vec![group_by_postprocessing(
+            &["a"],
+            &[
+                aliased_expr(col("a"), "a"),
+                aliased_expr(count(col("b")), "n"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_count_distinct_and_other_aggregations() {
+    let t = TableRef::new("sxt", "sxt_tab");
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        &t,
+        indexmap! {
+            "a".into() => ColumnType::BigInt,
+            "b".into() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select count(distinct a) as n, sum(b) as s from sxt_tab",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        &t,
+        "select count(distinct a) as n, count(distinct b) as m from sxt_tab",
+        &accessor,
+    );
+}
+
 /// Creates a new [`QueryExpr`], with the given select statement and a sample schema accessor.
 fn query_expr_for_test_table(sql_text: &str) -> QueryExpr {
     let schema_accessor = schema_accessor_from_table_ref_with_schema(
@@ -33,7 +33,13 @@ impl<'a> WhereExprBuilder<'a> {
                 let expr_plan = self.builder.build(&where_expr)?;
                 // Ensure that the expression is a boolean expression
                 match expr_plan.data_type() {
-                    ColumnType::Boolean => Ok(expr_plan),
+                    // Rows where the where clause is null are not selected
+                    ColumnType::Boolean => Ok(
+                        match expr_plan.presence_expr(&self.builder.nullable_columns()) {
+                            Some(presence) => DynProofExpr::try_new_and(expr_plan, presence)?,
+                            None => expr_plan,
+                        },
+                    ),
                     _ => Err(ConversionError::NonbooleanWhereClause {
                         datatype: expr_plan.data_type(),
                     }),
@@ -0,0 +1,44 @@
+use super::{PostprocessingResult, PostprocessingStep};
+use crate::base::{
+    database::{group_by_util::aggregate_columns, Column, OwnedColumn, OwnedTable},
+    scalar::Scalar,
+};
+use alloc::{vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// A `DistinctPostprocessing` removes the duplicate rows of an `OwnedTable`.
+///
+/// The distinct rows are returned in ascending order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DistinctPostprocessing;
+
+impl DistinctPostprocessing {
+    /// Create a new `DistinctPostprocessing`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S: Scalar> PostprocessingStep<S> for DistinctPostprocessing {
+    /// Apply the distinct transformation to the given `OwnedTable`.
+    fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
+        let alloc = Bump::new();
+        let columns = owned_table
+            .inner_table()
+            .values()
+            .map(|column| Column::<S>::from_owned_column(column, &alloc))
+            .collect::<Vec<_>>();
+        let selection = vec![true; owned_table.num_rows()];
+        let aggregation_results = aggregate_columns(&alloc, &columns, &[], &[], &[], &selection)?;
+        Ok(OwnedTable::try_from_iter(
+            owned_table.inner_table().keys().cloned().zip(
+                aggregation_results
+                    .group_by_columns
+                    .iter()
+                    .map(OwnedColumn::from),
+            ),
+        )?)
+    }
+}
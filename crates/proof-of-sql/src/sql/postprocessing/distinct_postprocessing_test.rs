@@ -0,0 +1,29 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable},
+        scalar::Curve25519Scalar,
+    },
+    sql::postprocessing::{apply_postprocessing_steps, test_utility::*},
+};
+
+#[test]
+fn we_can_remove_the_duplicate_rows_of_an_owned_table() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [3_i64, 1, 3, 2, 1, 3]),
+        varchar("d", ["f", "abc", "f", "kl", "abc", "alfa"]),
+    ]);
+    let expected_table = owned_table([
+        bigint("a", [1_i64, 2, 3, 3]),
+        varchar("d", ["abc", "kl", "alfa", "f"]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &[distinct()]).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_remove_the_duplicate_rows_of_an_empty_owned_table() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0_i64; 0]), boolean("b", [true; 0])]);
+    let actual_table = apply_postprocessing_steps(table.clone(), &[distinct()]).unwrap();
+    assert_eq!(actual_table, table);
+}
@@ -1,3 +1,4 @@
+use crate::base::database::ColumnType;
 use alloc::string::String;
 use snafu::Snafu;
 use sqlparser::ast::Ident;
@@ -59,6 +60,20 @@ pub enum PostprocessingError {
         /// The underlying source error
         source: crate::base::database::OwnedColumnError,
     },
+    /// Aggregation of a nullable expression that does not support nulls yet
+    #[snafu(display("{operator} of nullable expressions is not supported yet"))]
+    NullableAggregationNotSupported {
+        /// The aggregation operator
+        operator: String,
+    },
+    /// `HAVING` clause that is not boolean
+    #[snafu(display(
+        "A HAVING clause must have boolean type. It is currently of type '{datatype}'."
+    ))]
+    NonbooleanHavingClause {
+        /// The actual datatype of the `HAVING` clause
+        datatype: ColumnType,
+    },
     /// Nested aggregation in `GROUP BY` clause
     #[snafu(display("Nested aggregation in `GROUP BY` clause: {error}"))]
     NestedAggregationInGroupByClause {
@@ -1,11 +1,15 @@
 use super::{PostprocessingError, PostprocessingResult, PostprocessingStep};
 use crate::base::{
-    database::{group_by_util::aggregate_columns, Column, OwnedColumn, OwnedTable},
+    database::{
+        filter_util::filter_columns, group_by_util::aggregate_columns, presence_column_ident,
+        Column, ColumnType, NullableOwnedColumn, OwnedColumn, OwnedTable,
+    },
     map::{indexmap, IndexMap, IndexSet},
     scalar::Scalar,
 };
 use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
 use bumpalo::Bump;
+use core::iter;
 use itertools::{izip, Itertools};
 use proof_of_sql_parser::{
     intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression},
@@ -25,6 +29,10 @@ pub struct GroupByPostprocessing {
 
     /// A list of aggregation expressions
     aggregation_exprs: Vec<(AggregationOperator, Expression, Ident)>,
+
+    /// The `HAVING` clause, which exclusively uses identifiers in the group by clause or results of aggregation expressions
+    #[serde(default)]
+    having_expr: Option<Expression>,
 }
 
 /// Check whether multiple layers of aggregation exist within the same GROUP BY clause
@@ -34,19 +42,54 @@ pub struct GroupByPostprocessing {
 /// Otherwise we need two layers of aggregation functions to be nested.
 fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => false,
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard => false,
         Expression::Aggregation { expr, .. } => is_agg || contains_nested_aggregation(expr, true),
         Expression::Binary { left, right, .. } => {
             contains_nested_aggregation(left, is_agg) || contains_nested_aggregation(right, is_agg)
         }
-        Expression::Unary { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::InSubquery { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Length(expr)
+        | Expression::StartsWith { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::InList { expr, list, .. } => iter::once(expr.as_ref())
+            .chain(list)
+            .any(|expr| contains_nested_aggregation(expr, is_agg)),
+        Expression::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .any(|expr| contains_nested_aggregation(expr, is_agg)),
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => {
+            when_then.iter().any(|(condition, result)| {
+                contains_nested_aggregation(condition, is_agg)
+                    || contains_nested_aggregation(result, is_agg)
+            }) || else_expr
+                .as_ref()
+                .is_some_and(|else_expr| contains_nested_aggregation(else_expr, is_agg))
+        }
     }
 }
 
 /// Get identifiers NOT in aggregate functions
 fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Ident> {
     match expr {
-        Expression::Column(identifier) => IndexSet::from_iter([(*identifier).into()]),
+        Expression::Column(identifier)
+        | Expression::QualifiedColumn {
+            column: identifier, ..
+        } => IndexSet::from_iter([(*identifier).into()]),
         Expression::Literal(_) | Expression::Aggregation { .. } | Expression::Wildcard => {
             IndexSet::default()
         }
@@ -56,10 +99,62 @@ fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Ident> {
             left_identifiers.extend(right_identifiers);
             left_identifiers
         }
-        Expression::Unary { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::Unary { expr, .. }
+        | Expression::IsNull(expr)
+        | Expression::IsNotNull(expr)
+        | Expression::InSubquery { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Like { expr, .. }
+        | Expression::Length(expr)
+        | Expression::StartsWith { expr, .. }
+        | Expression::AddInterval { expr, .. }
+        | Expression::Extract { expr, .. }
+        | Expression::DateTrunc { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::InList { expr, list, .. } => iter::once(expr.as_ref())
+            .chain(list)
+            .flat_map(get_free_identifiers_from_expr)
+            .collect(),
+        Expression::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .flat_map(|expr| get_free_identifiers_from_expr(expr))
+            .collect(),
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => when_then
+            .iter()
+            .flat_map(|(condition, result)| [condition, result])
+            .chain(else_expr.as_deref())
+            .flat_map(get_free_identifiers_from_expr)
+            .collect(),
     }
 }
 
+/// Returns the rows where `expr` is not null, or `None` if `expr` is never null
+///
+/// An expression is null wherever any of the columns it references is null.
+fn evaluate_presence<S: Scalar>(
+    owned_table: &OwnedTable<S>,
+    expr: &Expression,
+) -> Option<Vec<bool>> {
+    get_free_identifiers_from_expr(expr)
+        .iter()
+        .filter_map(
+            |id| match owned_table.inner_table().get(&presence_column_ident(id))? {
+                OwnedColumn::Boolean(presence) => Some(presence),
+                _ => None,
+            },
+        )
+        .fold(None, |acc: Option<Vec<bool>>, presence| {
+            Some(match acc {
+                Some(acc) => acc.iter().zip(presence).map(|(a, b)| *a && *b).collect(),
+                None => presence.clone(),
+            })
+        })
+}
+
 /// Get aggregate expressions from an expression as well as the remainder
 ///
 /// The idea here is to recursively traverse the expression tree and collect all the aggregation expressions
@@ -74,7 +169,10 @@ fn get_aggregate_and_remainder_expressions(
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Ident>,
 ) -> Result<Expression, PostprocessingError> {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => Ok(expr),
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard => Ok(expr),
         Expression::Aggregation { op, expr } => {
             let key = (op, (*expr));
             if let Some(ident) = aggregation_expr_map.get(&key) {
@@ -118,34 +216,153 @@ fn get_aggregate_and_remainder_expressions(
                 expr: Box::new(remainder?),
             })
         }
+        Expression::IsNull(expr) => Ok(Expression::IsNull(Box::new(
+            get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map)?,
+        ))),
+        Expression::IsNotNull(expr) => Ok(Expression::IsNotNull(Box::new(
+            get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map)?,
+        ))),
+        Expression::InSubquery {
+            expr,
+            subquery,
+            negated,
+        } => Ok(Expression::InSubquery {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            subquery,
+            negated,
+        }),
+        Expression::Cast { expr, data_type } => Ok(Expression::Cast {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            data_type,
+        }),
+        Expression::Like {
+            expr,
+            pattern,
+            negated,
+        } => Ok(Expression::Like {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            pattern,
+            negated,
+        }),
+        Expression::Length(expr) => Ok(Expression::Length(Box::new(
+            get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map)?,
+        ))),
+        Expression::StartsWith { expr, prefix } => Ok(Expression::StartsWith {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            prefix,
+        }),
+        Expression::AddInterval { expr, interval } => Ok(Expression::AddInterval {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            interval,
+        }),
+        Expression::Extract { field, expr } => Ok(Expression::Extract {
+            field,
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+        }),
+        Expression::DateTrunc { field, expr } => Ok(Expression::DateTrunc {
+            field,
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+        }),
+        Expression::InList {
+            expr,
+            list,
+            negated,
+        } => Ok(Expression::InList {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            list: list
+                .into_iter()
+                .map(|expr| get_aggregate_and_remainder_expressions(expr, aggregation_expr_map))
+                .collect::<Result<_, PostprocessingError>>()?,
+            negated,
+        }),
+        Expression::Between {
+            expr,
+            low,
+            high,
+            negated,
+        } => Ok(Expression::Between {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                aggregation_expr_map,
+            )?),
+            low: Box::new(get_aggregate_and_remainder_expressions(
+                *low,
+                aggregation_expr_map,
+            )?),
+            high: Box::new(get_aggregate_and_remainder_expressions(
+                *high,
+                aggregation_expr_map,
+            )?),
+            negated,
+        }),
+        Expression::Case {
+            when_then,
+            else_expr,
+        } => Ok(Expression::Case {
+            when_then: when_then
+                .into_iter()
+                .map(|(condition, result)| {
+                    Ok((
+                        get_aggregate_and_remainder_expressions(condition, aggregation_expr_map)?,
+                        get_aggregate_and_remainder_expressions(result, aggregation_expr_map)?,
+                    ))
+                })
+                .collect::<Result<_, PostprocessingError>>()?,
+            else_expr: else_expr
+                .map(|else_expr| {
+                    get_aggregate_and_remainder_expressions(*else_expr, aggregation_expr_map)
+                        .map(Box::new)
+                })
+                .transpose()?,
+        }),
     }
 }
 
-/// Given an `AliasedResultExpr`, check if it is legitimate and if so grab the relevant aggregation expression
+/// Given an expression, check if it is legitimate and if so grab the relevant aggregation expression
 /// # Panics
 ///
 /// Will panic if there is an issue retrieving the first element from the difference of free identifiers and group-by identifiers, indicating a logical inconsistency in the identifiers.
 fn check_and_get_aggregation_and_remainder(
-    expr: AliasedResultExpr,
+    expr: Expression,
     group_by_identifiers: &[Ident],
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Ident>,
-) -> PostprocessingResult<AliasedResultExpr> {
-    let free_identifiers = get_free_identifiers_from_expr(&expr.expr);
+) -> PostprocessingResult<Expression> {
+    let free_identifiers = get_free_identifiers_from_expr(&expr);
     let group_by_identifier_set = group_by_identifiers
         .iter()
         .cloned()
         .collect::<IndexSet<_>>();
-    if contains_nested_aggregation(&expr.expr, false) {
+    if contains_nested_aggregation(&expr, false) {
         return Err(PostprocessingError::NestedAggregationInGroupByClause {
-            error: format!("Nested aggregations found {:?}", expr.expr),
+            error: format!("Nested aggregations found {expr:?}"),
         });
     }
     if free_identifiers.is_subset(&group_by_identifier_set) {
-        let remainder = get_aggregate_and_remainder_expressions(*expr.expr, aggregation_expr_map);
-        Ok(AliasedResultExpr {
-            alias: expr.alias,
-            expr: Box::new(remainder?),
-        })
+        get_aggregate_and_remainder_expressions(expr, aggregation_expr_map)
     } else {
         let diff = free_identifiers
             .difference(&group_by_identifier_set)
@@ -164,6 +381,16 @@ impl GroupByPostprocessing {
     pub fn try_new(
         by_ids: Vec<Ident>,
         aliased_exprs: Vec<AliasedResultExpr>,
+    ) -> PostprocessingResult<Self> {
+        Self::try_new_with_having(by_ids, aliased_exprs, None)
+    }
+
+    /// Create a new group by expression containing the group by and aggregation expressions
+    /// whose groups are filtered by a `HAVING` clause
+    pub fn try_new_with_having(
+        by_ids: Vec<Ident>,
+        aliased_exprs: Vec<AliasedResultExpr>,
+        having_expr: Option<Expression>,
     ) -> PostprocessingResult<Self> {
         let mut aggregation_expr_map: IndexMap<(AggregationOperator, Expression), Ident> =
             IndexMap::default();
@@ -171,13 +398,21 @@ impl GroupByPostprocessing {
         let remainder_exprs: Vec<AliasedResultExpr> = aliased_exprs
             .into_iter()
             .map(|aliased_expr| -> PostprocessingResult<_> {
-                check_and_get_aggregation_and_remainder(
-                    aliased_expr,
-                    &by_ids,
-                    &mut aggregation_expr_map,
-                )
+                Ok(AliasedResultExpr {
+                    alias: aliased_expr.alias,
+                    expr: Box::new(check_and_get_aggregation_and_remainder(
+                        *aliased_expr.expr,
+                        &by_ids,
+                        &mut aggregation_expr_map,
+                    )?),
+                })
             })
             .collect::<PostprocessingResult<Vec<AliasedResultExpr>>>()?;
+        let having_expr = having_expr
+            .map(|expr| {
+                check_and_get_aggregation_and_remainder(expr, &by_ids, &mut aggregation_expr_map)
+            })
+            .transpose()?;
         let group_by_identifiers = Vec::from_iter(IndexSet::from_iter(by_ids));
         Ok(Self {
             remainder_exprs,
@@ -186,6 +421,7 @@ impl GroupByPostprocessing {
                 .into_iter()
                 .map(|((op, expr), id)| (op, expr, id))
                 .collect(),
+            having_expr,
         })
     }
 
@@ -206,6 +442,12 @@ impl GroupByPostprocessing {
     pub fn aggregation_exprs(&self) -> &[(AggregationOperator, Expression, Ident)] {
         &self.aggregation_exprs
     }
+
+    /// Get the `HAVING` clause, if any
+    #[must_use]
+    pub fn having_expr(&self) -> Option<&Expression> {
+        self.having_expr.as_ref()
+    }
 }
 
 impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
@@ -218,7 +460,23 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
             .aggregation_exprs
             .iter()
             .map(|(agg_op, expr, id)| -> PostprocessingResult<_> {
-                let evaluated_owned_column = owned_table.evaluate(expr)?;
+                let evaluated_owned_column = match (agg_op, evaluate_presence(&owned_table, expr)) {
+                    (_, None) => owned_table.evaluate(expr)?,
+                    // Nulls are ignored by SUM and COUNT, so they are replaced with zeros
+                    (AggregationOperator::Sum, Some(presence)) => {
+                        NullableOwnedColumn::try_new(owned_table.evaluate(expr)?, Some(presence))?
+                            .into_parts()
+                            .0
+                    }
+                    (AggregationOperator::Count, Some(presence)) => {
+                        OwnedColumn::BigInt(presence.into_iter().map(i64::from).collect())
+                    }
+                    (_, Some(_)) => {
+                        return Err(PostprocessingError::NullableAggregationNotSupported {
+                            operator: agg_op.to_string(),
+                        })
+                    }
+                };
                 Ok((*agg_op, (id.clone(), evaluated_owned_column)))
             })
             .process_results(|iter| {
@@ -243,7 +501,7 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
                 Ok(Column::<S>::from_owned_column(column, &alloc))
             })
             .collect::<PostprocessingResult<Vec<_>>>()?;
-        // TODO: Allow a filter
+        // The WHERE clause is applied before the rows are grouped
         let selection_in = vec![true; owned_table.num_rows()];
         let (sum_identifiers, sum_columns): (Vec<_>, Vec<_>) = evaluated_columns
             .get(&AggregationOperator::Sum)
@@ -253,6 +511,22 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
                     .map(|(id, c)| (id.clone(), Column::<S>::from_owned_column(c, &alloc)))
                     .unzip()
             });
+        // COUNT(expr) of a nullable `expr` is the sum of its presence
+        let (null_aware_count_identifiers, null_aware_count_columns): (Vec<_>, Vec<_>) = self
+            .aggregation_exprs
+            .iter()
+            .filter(|(agg_op, expr, _)| {
+                *agg_op == AggregationOperator::Count
+                    && evaluate_presence(&owned_table, expr).is_some()
+            })
+            .map(|(_, _, id)| {
+                let column = evaluated_columns[&AggregationOperator::Count]
+                    .iter()
+                    .find_map(|(count_id, c)| (count_id == id).then_some(c))
+                    .expect("every COUNT expression is evaluated");
+                (id.clone(), Column::<S>::from_owned_column(column, &alloc))
+            })
+            .unzip();
         let (max_identifiers, max_columns): (Vec<_>, Vec<_>) = evaluated_columns
             .get(&AggregationOperator::Max)
             .map_or((vec![], vec![]), |tuple| {
@@ -272,7 +546,7 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
         let aggregation_results = aggregate_columns(
             &alloc,
             &group_by_ins,
-            &sum_columns,
+            &[sum_columns.as_slice(), null_aware_count_columns.as_slice()].concat(),
             &max_columns,
             &min_columns,
             &selection_in,
@@ -284,17 +558,16 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
             .iter()
             .zip(self.group_by_identifiers.iter())
             .map(|(column, id)| Ok((id.clone(), OwnedColumn::from(column))));
-        let sum_outs = izip!(
-            aggregation_results.sum_columns,
-            sum_identifiers,
-            sum_columns,
-        )
-        .map(|(c_out, id, c_in)| {
-            Ok((
-                id,
-                OwnedColumn::try_from_scalars(c_out, c_in.column_type())?,
-            ))
-        });
+        let (sum_results, null_aware_count_results) =
+            aggregation_results.sum_columns.split_at(sum_columns.len());
+        let sum_outs = izip!(sum_results.iter().copied(), sum_identifiers, sum_columns).map(
+            |(c_out, id, c_in)| {
+                Ok((
+                    id,
+                    OwnedColumn::try_from_scalars(c_out, c_in.column_type())?,
+                ))
+            },
+        );
         let max_outs = izip!(
             aggregation_results.max_columns,
             max_identifiers,
@@ -317,13 +590,26 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
                 OwnedColumn::try_from_option_scalars(c_out, c_in.column_type())?,
             ))
         });
-        //TODO: When we have NULLs we need to differentiate between count(1) and count(expression)
         let count_column = OwnedColumn::BigInt(aggregation_results.count_column.to_vec());
         let count_outs = evaluated_columns
             .get(&AggregationOperator::Count)
             .into_iter()
             .flatten()
-            .map(|(id, _)| -> PostprocessingResult<_> { Ok((id.clone(), count_column.clone())) });
+            .map(|(id, _)| -> PostprocessingResult<_> {
+                match null_aware_count_identifiers
+                    .iter()
+                    .position(|count_id| count_id == id)
+                {
+                    Some(index) => Ok((
+                        id.clone(),
+                        OwnedColumn::try_from_scalars(
+                            null_aware_count_results[index],
+                            ColumnType::BigInt,
+                        )?,
+                    )),
+                    None => Ok((id.clone(), count_column.clone())),
+                }
+            });
         let new_owned_table: OwnedTable<S> = group_by_outs
             .into_iter()
             .chain(sum_outs)
@@ -338,6 +624,33 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
         } else {
             new_owned_table
         };
+        // Drop the groups that do not satisfy the HAVING clause
+        let target_table = match &self.having_expr {
+            Some(having_expr) => {
+                let selection = match target_table.evaluate(having_expr)? {
+                    OwnedColumn::Boolean(selection) => selection,
+                    column => {
+                        return Err(PostprocessingError::NonbooleanHavingClause {
+                            datatype: column.column_type(),
+                        })
+                    }
+                };
+                let columns = target_table
+                    .inner_table()
+                    .values()
+                    .map(|column| Column::<S>::from_owned_column(column, &alloc))
+                    .collect::<Vec<_>>();
+                let (filtered_columns, _) = filter_columns(&alloc, &columns, &selection);
+                OwnedTable::try_from_iter(
+                    target_table
+                        .inner_table()
+                        .keys()
+                        .cloned()
+                        .zip(filtered_columns.iter().map(OwnedColumn::from)),
+                )?
+            }
+            None => target_table,
+        };
         let result = self
             .remainder_exprs
             .iter()
@@ -61,6 +61,114 @@ fn we_can_make_group_by_postprocessing() {
     );
 }
 
+#[test]
+fn we_cannot_have_invalid_having_clauses() {
+    // Column in HAVING but not in group by or aggregation
+    let res = GroupByPostprocessing::try_new_with_having(
+        vec!["a".into()],
+        vec![aliased_expr(sum(col("a")), "res")],
+        Some(*gt(col("b"), lit(1))),
+    );
+    assert!(matches!(
+        res,
+        Err(PostprocessingError::IdentNotInAggregationOperatorOrGroupByClause { .. })
+    ));
+
+    // Nested aggregation
+    let res = GroupByPostprocessing::try_new_with_having(
+        vec!["a".into()],
+        vec![aliased_expr(col("a"), "a")],
+        Some(*gt(sum(max(col("b"))), lit(1))),
+    );
+    assert!(matches!(
+        res,
+        Err(PostprocessingError::NestedAggregationInGroupByClause { .. })
+    ));
+
+    // HAVING clause that is not boolean
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [1_i64, 2]), bigint("b", [3_i64, 4])]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing_with_having(
+        &["a"],
+        &[aliased_expr(col("a"), "a")],
+        sum(col("b")),
+    )];
+    assert!(matches!(
+        apply_postprocessing_steps(table, &postprocessing),
+        Err(PostprocessingError::NonbooleanHavingClause { .. })
+    ));
+}
+
+#[test]
+fn we_can_make_group_by_postprocessing_with_having() {
+    // SELECT SUM(a) as c0 FROM tab GROUP BY b HAVING SUM(a) > 2 AND MAX(c) < b
+    let res = GroupByPostprocessing::try_new_with_having(
+        vec!["b".into()],
+        vec![aliased_expr(sum(col("a")), "c0")],
+        Some(*and(gt(sum(col("a")), lit(2)), lt(max(col("c")), col("b")))),
+    )
+    .unwrap();
+    assert_eq!(
+        res.remainder_exprs(),
+        &[aliased_expr(col("__col_agg_0"), "c0")]
+    );
+    assert_eq!(
+        res.having_expr(),
+        Some(&*and(
+            gt(col("__col_agg_0"), lit(2)),
+            lt(col("__col_agg_1"), col("b"))
+        ))
+    );
+    assert_eq!(
+        res.aggregation_exprs(),
+        &[
+            (AggregationOperator::Sum, *col("a"), "__col_agg_0".into()),
+            (AggregationOperator::Max, *col("c"), "__col_agg_1".into()),
+        ]
+    );
+}
+
+#[test]
+fn we_can_do_group_bys_with_having() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 2, 3, 3, 3]),
+        bigint("b", [5_i64, 6, 7, 8, -9, 10]),
+    ]);
+
+    // SELECT a, SUM(b) AS s FROM tab GROUP BY a HAVING SUM(b) > 6
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing_with_having(
+        &["a"],
+        &[
+            aliased_expr(col("a"), "a"),
+            aliased_expr(sum(col("b")), "s"),
+        ],
+        gt(sum(col("b")), lit(6)),
+    )];
+    let expected_table = owned_table([bigint("a", [2_i64, 3]), bigint("s", [13_i64, 9])]);
+    let actual_table = apply_postprocessing_steps(table.clone(), &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+
+    // SELECT a FROM tab GROUP BY a HAVING COUNT(*) >= 2 AND MIN(b) > 0
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing_with_having(
+        &["a"],
+        &[aliased_expr(col("a"), "a")],
+        and(ge(count_all(), lit(2)), gt(min(col("b")), lit(0))),
+    )];
+    let expected_table = owned_table([bigint("a", [2_i64])]);
+    let actual_table = apply_postprocessing_steps(table.clone(), &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+
+    // SELECT SUM(b) AS s FROM tab HAVING SUM(b) < 0
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing_with_having(
+        &[],
+        &[aliased_expr(sum(col("b")), "s")],
+        lt(sum(col("b")), lit(0)),
+    )];
+    let expected_table = owned_table([bigint("s", [0_i64; 0])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
 #[allow(clippy::too_many_lines)]
 #[test]
 fn we_can_do_simple_group_bys() {
@@ -11,6 +11,11 @@ pub use postprocessing_step::PostprocessingStep;
 /// Utility functions for testing postprocessing steps.
 pub mod test_utility;
 
+mod distinct_postprocessing;
+pub use distinct_postprocessing::DistinctPostprocessing;
+#[cfg(test)]
+mod distinct_postprocessing_test;
+
 mod group_by_postprocessing;
 pub use group_by_postprocessing::GroupByPostprocessing;
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use super::{
-    GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult, PostprocessingStep,
-    SelectPostprocessing, SlicePostprocessing,
+    DistinctPostprocessing, GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult,
+    PostprocessingStep, SelectPostprocessing, SlicePostprocessing,
 };
 use crate::base::{database::OwnedTable, scalar::Scalar};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,8 @@ pub enum OwnedTablePostprocessing {
     Select(SelectPostprocessing),
     /// Aggregate the `OwnedTable` with the given `GroupByPostprocessing`.
     GroupBy(GroupByPostprocessing),
+    /// Remove the duplicate rows of the `OwnedTable` with the given `DistinctPostprocessing`.
+    Distinct(DistinctPostprocessing),
 }
 
 impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
@@ -26,6 +28,7 @@ impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
             OwnedTablePostprocessing::OrderBy(order_by_expr) => order_by_expr.apply(owned_table),
             OwnedTablePostprocessing::Select(select_expr) => select_expr.apply(owned_table),
             OwnedTablePostprocessing::GroupBy(group_by_expr) => group_by_expr.apply(owned_table),
+            OwnedTablePostprocessing::Distinct(distinct_expr) => distinct_expr.apply(owned_table),
         }
     }
 }
@@ -51,6 +54,11 @@ impl OwnedTablePostprocessing {
     pub fn new_group_by(group_by_postprocessing: GroupByPostprocessing) -> Self {
         Self::GroupBy(group_by_postprocessing)
     }
+    /// Create a new `OwnedTablePostprocessing` with the given `DistinctPostprocessing`.
+    #[must_use]
+    pub fn new_distinct(distinct_postprocessing: DistinctPostprocessing) -> Self {
+        Self::Distinct(distinct_postprocessing)
+    }
 }
 
 /// Apply a list of postprocessing steps to an `OwnedTable`.
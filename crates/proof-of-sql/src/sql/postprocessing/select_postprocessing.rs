@@ -1,6 +1,6 @@
 use super::{PostprocessingResult, PostprocessingStep};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable},
+    database::{presence_column_ident, OwnedColumn, OwnedTable},
     map::IndexMap,
     scalar::Scalar,
 };
@@ -29,16 +29,24 @@ impl SelectPostprocessing {
 impl<S: Scalar> PostprocessingStep<S> for SelectPostprocessing {
     /// Apply the select transformation to the given `OwnedTable`.
     fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
-        let cols: IndexMap<Ident, OwnedColumn<S>> = self
-            .aliased_result_exprs
-            .iter()
-            .map(
-                |aliased_result_expr| -> PostprocessingResult<(Ident, OwnedColumn<S>)> {
-                    let result_column = owned_table.evaluate(&aliased_result_expr.expr)?;
-                    Ok((aliased_result_expr.alias.into(), result_column))
-                },
-            )
-            .collect::<PostprocessingResult<_>>()?;
+        let mut cols: IndexMap<Ident, OwnedColumn<S>> = IndexMap::default();
+        for aliased_result_expr in &self.aliased_result_exprs {
+            let alias: Ident = aliased_result_expr.alias.into();
+            let result_column = owned_table.evaluate(&aliased_result_expr.expr)?;
+            // Selected nullable columns keep their presence
+            let presence = aliased_result_expr
+                .try_as_identifier()
+                .and_then(|identifier| {
+                    owned_table
+                        .inner_table()
+                        .get(&presence_column_ident(&(*identifier).into()))
+                });
+            let presence_alias = presence_column_ident(&alias);
+            cols.insert(alias, result_column);
+            if let Some(presence) = presence {
+                cols.insert(presence_alias, presence.clone());
+            }
+        }
         Ok(OwnedTable::try_new(cols)?)
     }
 }
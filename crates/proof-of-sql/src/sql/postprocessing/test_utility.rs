@@ -1,5 +1,5 @@
 use super::*;
-use proof_of_sql_parser::intermediate_ast::AliasedResultExpr;
+use proof_of_sql_parser::intermediate_ast::{AliasedResultExpr, Expression};
 use sqlparser::ast::Ident;
 
 #[must_use]
@@ -14,6 +14,20 @@ pub fn group_by_postprocessing(
     )
 }
 
+#[must_use]
+/// Producing a postprocessing object that represents a group by operation with a `HAVING` clause.
+pub fn group_by_postprocessing_with_having(
+    cols: &[&str],
+    result_exprs: &[AliasedResultExpr],
+    having_expr: Box<Expression>,
+) -> OwnedTablePostprocessing {
+    let ids: Vec<Ident> = cols.iter().map(|col| (*col).into()).collect();
+    OwnedTablePostprocessing::new_group_by(
+        GroupByPostprocessing::try_new_with_having(ids, result_exprs.to_vec(), Some(*having_expr))
+            .unwrap(),
+    )
+}
+
 /// Producing a postprocessing object that represents a select operation.
 /// # Panics
 ///
@@ -39,3 +53,9 @@ pub fn orders(indexes: &[usize], directions: &[bool]) -> OwnedTablePostprocessin
         .collect();
     OwnedTablePostprocessing::new_order_by(OrderByPostprocessing::new(index_direction_pairs))
 }
+
+/// Producing a postprocessing object that represents a distinct operation.
+#[must_use]
+pub fn distinct() -> OwnedTablePostprocessing {
+    OwnedTablePostprocessing::new_distinct(DistinctPostprocessing::new())
+}
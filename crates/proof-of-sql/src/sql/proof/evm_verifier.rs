@@ -0,0 +1,485 @@
+//! A Rust reference implementation of the byte protocol that the EVM verifier consumes.
+//!
+//! The calldata is laid out as follows, where all integers and words are big-endian:
+//!
+//! | section                  | layout                                                                                   |
+//! |--------------------------|------------------------------------------------------------------------------------------|
+//! | plan                     | `u64` length, followed by the [`EVMProofPlan`] encoding                                  |
+//! | result                   | `u64` length, followed by the encoding of the result table                               |
+//! | first round message      | range length, post result challenge count, chi lengths, rho lengths and commitments      |
+//! | final round message      | constraint count, commitments and bit distributions                                      |
+//! | sumcheck proof           | `u64` count, followed by the coefficients of each round, leading coefficient first       |
+//! | pcs proof evaluations    | the first round, column and final round evaluations, each a `u64` count and the words    |
+//! | `HyperKZG` proof `com`   | `u64` count, followed by `(x, y)` for each point                                         |
+//! | `HyperKZG` proof `v`     | `u64` count, followed by `(v_{i,0}, v_{i,1}, v_{i,2})` for each round                    |
+//! | `HyperKZG` proof `w`     | `(x, y)` for each of the three points                                                    |
+//!
+//! Everything but the `HyperKZG` proof is exactly what the prover appends to its transcript,
+//! so the verifier can append each section straight from calldata.
+use super::{
+    query_proof::{
+        get_index_range, FinalRoundMessage, FirstRoundMessage, QueryProofPCSProofEvaluations,
+    },
+    ProofPlan, QueryData, QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars,
+    VerifiableQueryResult, VerificationBuilderImpl,
+};
+use crate::{
+    base::{
+        database::{CommitmentAccessor, OwnedTable, TableRef},
+        map::IndexMap,
+        math::log2_up,
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    proof_primitive::hyperkzg::{
+        verify_batched_proof_with_transcript_state, BNScalar, EVMHyperKZGProof, HyperKZGCommitment,
+        HyperKZGCommitmentEvaluationProof, HyperKZGEngine,
+    },
+    sql::evm_proof_plan::EVMProofPlan,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use bincode::config::{BigEndian, Configuration, Fixint, NoLimit};
+use bnum::types::U256;
+use core::cmp;
+use nova_snark::provider::hyperkzg::VerifierKey;
+use num_traits::Zero;
+use serde::{de::DeserializeOwned, Serialize};
+use snafu::Snafu;
+use tiny_keccak::{Hasher, Keccak};
+
+const MALFORMED_CALLDATA: ProofError = ProofError::VerificationError {
+    error: "calldata is malformed",
+};
+
+/// Errors that can occur when encoding calldata for the EVM verifier.
+#[derive(Snafu, Debug)]
+pub enum EVMCalldataError {
+    /// The result has no proof, which happens when every table the plan references is empty.
+    #[snafu(display("the result has no proof"))]
+    MissingProof,
+    /// The evaluation proof does not have the shape of a `HyperKZG` proof.
+    #[snafu(display("the evaluation proof is malformed"))]
+    MalformedEvaluationProof,
+}
+
+fn config() -> Configuration<BigEndian, Fixint, NoLimit> {
+    bincode::config::legacy()
+        .with_fixed_int_encoding()
+        .with_big_endian()
+}
+
+fn encode(message: &impl Serialize) -> Vec<u8> {
+    bincode::serde::encode_to_vec(message, config()).unwrap()
+}
+
+fn word_to_u256(word: &[u8]) -> U256 {
+    let limbs: [u64; 4] = core::array::from_fn(|i| {
+        u64::from_be_bytes(word[24 - 8 * i..32 - 8 * i].try_into().unwrap())
+    });
+    U256::from(limbs)
+}
+
+fn word_to_scalar(word: &[u8]) -> BNScalar {
+    BNScalar::from_wrapping(word_to_u256(word))
+}
+
+fn keccak(data: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for bytes in data {
+        hasher.update(bytes);
+    }
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Encodes a proven result, along with the plan it was proven for, into the calldata of the EVM verifier.
+///
+/// The result must have been proven for `plan` itself, so that the prover's transcript starts with the encoding of `plan`.
+///
+/// # Panics
+/// Panics if the plan or the proof cannot be encoded, which should never happen.
+pub fn encode_evm_calldata(
+    plan: &EVMProofPlan,
+    verifiable_result: &VerifiableQueryResult<HyperKZGCommitmentEvaluationProof>,
+) -> Result<Vec<u8>, EVMCalldataError> {
+    let (Some(result), Some(proof)) = (&verifiable_result.result, &verifiable_result.proof) else {
+        return Err(EVMCalldataError::MissingProof);
+    };
+    let evaluation_proof = EVMHyperKZGProof::try_from_proof(&proof.evaluation_proof)
+        .ok_or(EVMCalldataError::MalformedEvaluationProof)?;
+    let mut calldata = Vec::new();
+    for section in [encode(plan), encode(result)] {
+        calldata.extend((section.len() as u64).to_be_bytes());
+        calldata.extend(section);
+    }
+    calldata.extend(encode(&proof.first_round_message));
+    calldata.extend(encode(&proof.final_round_message));
+    calldata.extend(encode(&proof.sumcheck_proof));
+    calldata.extend(encode(&proof.pcs_proof_evaluations));
+    calldata.extend((evaluation_proof.com.len() as u64).to_be_bytes());
+    calldata.extend(evaluation_proof.com.iter().flatten().flatten());
+    calldata.extend((evaluation_proof.v.len() as u64).to_be_bytes());
+    calldata.extend(evaluation_proof.v.iter().flatten().flatten());
+    calldata.extend(evaluation_proof.w.iter().flatten().flatten());
+    Ok(calldata)
+}
+
+/// Reads calldata front to back.
+struct CalldataReader<'a>(&'a [u8]);
+
+impl<'a> CalldataReader<'a> {
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8], ProofError> {
+        if length > self.0.len() {
+            return Err(MALFORMED_CALLDATA);
+        }
+        let (bytes, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(bytes)
+    }
+    fn read_length(&mut self) -> Result<usize, ProofError> {
+        let bytes = self.read_bytes(8)?;
+        usize::try_from(u64::from_be_bytes(bytes.try_into().unwrap()))
+            .map_err(|_| MALFORMED_CALLDATA)
+    }
+    fn read_words<const N: usize>(&mut self) -> Result<[[u8; 32]; N], ProofError> {
+        let mut words = [[0; 32]; N];
+        for word in &mut words {
+            word.copy_from_slice(self.read_bytes(32)?);
+        }
+        Ok(words)
+    }
+    /// Reads a message, returning it along with the bytes it was read from.
+    fn read_message<T: DeserializeOwned>(&mut self) -> Result<(T, &'a [u8]), ProofError> {
+        let (message, length) =
+            bincode::serde::decode_from_slice(self.0, config()).map_err(|_| MALFORMED_CALLDATA)?;
+        Ok((message, self.read_bytes(length)?))
+    }
+    /// Reads a message that is prefixed with its length, returning it along with the bytes it was read from.
+    fn read_sized_message<T: DeserializeOwned>(&mut self) -> Result<(T, &'a [u8]), ProofError> {
+        let length = self.read_length()?;
+        let bytes = self.read_bytes(length)?;
+        match bincode::serde::decode_from_slice(bytes, config()) {
+            Ok((message, read_length)) if read_length == length => Ok((message, bytes)),
+            _ => Err(MALFORMED_CALLDATA),
+        }
+    }
+}
+
+/// The calldata of the EVM verifier, decoded.
+///
+/// The raw bytes of each section that is appended to the transcript are kept alongside the decoded values.
+struct Calldata<'a> {
+    plan: EVMProofPlan,
+    plan_bytes: &'a [u8],
+    result: OwnedTable<BNScalar>,
+    result_bytes: &'a [u8],
+    first_round_message: FirstRoundMessage<HyperKZGCommitment>,
+    first_round_bytes: &'a [u8],
+    final_round_message: FinalRoundMessage<HyperKZGCommitment>,
+    final_round_bytes: &'a [u8],
+    sumcheck_coefficients: &'a [u8],
+    pcs_proof_evaluations: QueryProofPCSProofEvaluations<BNScalar>,
+    pcs_proof_evaluations_bytes: &'a [u8],
+    evaluation_proof: EVMHyperKZGProof,
+}
+
+impl<'a> Calldata<'a> {
+    fn try_decode(calldata: &'a [u8]) -> Result<Self, ProofError> {
+        let mut reader = CalldataReader(calldata);
+        let (plan, plan_bytes) = reader.read_sized_message()?;
+        let (result, result_bytes) = reader.read_sized_message()?;
+        let (first_round_message, first_round_bytes) = reader.read_message()?;
+        let (final_round_message, final_round_bytes) = reader.read_message()?;
+        let sumcheck_length = reader.read_length()?;
+        let sumcheck_coefficients =
+            reader.read_bytes(sumcheck_length.checked_mul(32).ok_or(MALFORMED_CALLDATA)?)?;
+        let (pcs_proof_evaluations, pcs_proof_evaluations_bytes) = reader.read_message()?;
+        let com_length = reader.read_length()?;
+        let com = (0..com_length)
+            .map(|_| reader.read_words())
+            .collect::<Result<_, _>>()?;
+        let v_length = reader.read_length()?;
+        let v = (0..v_length)
+            .map(|_| reader.read_words())
+            .collect::<Result<_, _>>()?;
+        let w = [
+            reader.read_words()?,
+            reader.read_words()?,
+            reader.read_words()?,
+        ];
+        if !reader.0.is_empty() {
+            return Err(MALFORMED_CALLDATA);
+        }
+        Ok(Self {
+            plan,
+            plan_bytes,
+            result,
+            result_bytes,
+            first_round_message,
+            first_round_bytes,
+            final_round_message,
+            final_round_bytes,
+            sumcheck_coefficients,
+            pcs_proof_evaluations,
+            pcs_proof_evaluations_bytes,
+            evaluation_proof: EVMHyperKZGProof { com, v, w },
+        })
+    }
+}
+
+/// The transcript as the EVM verifier keeps it, which is a single word. This mirrors `Transcript.sol`.
+struct EVMTranscript([u8; 32]);
+
+impl EVMTranscript {
+    fn append_calldata(&mut self, data: &[u8]) {
+        self.0 = keccak(&[&self.0, data]);
+    }
+    /// The current state as a challenge. This does not advance the transcript.
+    fn challenge(&self) -> BNScalar {
+        BNScalar::from_wrapping(word_to_u256(&self.0) & BNScalar::CHALLENGE_MASK)
+    }
+    fn draw_challenges(&mut self, count: usize) -> Vec<BNScalar> {
+        core::iter::repeat_with(|| {
+            let challenge = self.challenge();
+            self.0 = keccak(&[&self.0]);
+            challenge
+        })
+        .take(count)
+        .collect()
+    }
+}
+
+/// The output of the sumcheck verification, short of the final evaluation check.
+struct SumcheckSubclaim {
+    evaluation_point: Vec<BNScalar>,
+    expected_evaluation: BNScalar,
+    degree: usize,
+}
+
+/// Verifies that the sumcheck proof sums to zero. This mirrors `verify_sumcheck_proof` in `Sumcheck.pre.sol`.
+fn verify_sumcheck_proof(
+    transcript: &mut EVMTranscript,
+    coefficients: &[u8],
+    num_vars: usize,
+) -> Result<SumcheckSubclaim, ProofError> {
+    let num_coefficients = coefficients.len() / 32;
+    if num_coefficients == 0 || num_coefficients % num_vars != 0 {
+        return Err(ProofError::VerificationError {
+            error: "invalid proof size",
+        });
+    }
+    let degree = num_coefficients / num_vars - 1;
+    transcript.append_calldata(
+        &[
+            (degree as u64).to_be_bytes(),
+            (num_vars as u64).to_be_bytes(),
+        ]
+        .concat(),
+    );
+    let mut evaluation_point = Vec::with_capacity(num_vars);
+    let mut expected_evaluation = BNScalar::zero();
+    for round in coefficients.chunks(32 * (degree + 1)) {
+        transcript.append_calldata(round);
+        let challenge = transcript.challenge();
+        let round_coefficients: Vec<_> = round.chunks(32).map(word_to_scalar).collect();
+        // The round polynomial evaluated at 0 is its constant coefficient, which is the last one.
+        let mut actual_sum = round_coefficients[degree];
+        let mut round_evaluation = BNScalar::zero();
+        for &coefficient in &round_coefficients {
+            round_evaluation = round_evaluation * challenge + coefficient;
+            actual_sum += coefficient;
+        }
+        if actual_sum != expected_evaluation {
+            return Err(ProofError::VerificationError {
+                error: "round evaluation does not match claimed sum",
+            });
+        }
+        evaluation_point.push(challenge);
+        expected_evaluation = round_evaluation;
+    }
+    Ok(SumcheckSubclaim {
+        evaluation_point,
+        expected_evaluation,
+        degree,
+    })
+}
+
+/// Decodes and verifies the calldata of the EVM verifier.
+///
+/// This checks the calldata the same way the EVM verifier does, and the same way [`VerifiableQueryResult::verify`]
+/// checks the proof that the calldata was encoded from, so both produce the same verification hash.
+/// The table lengths and the column commitments come from `accessor`.
+#[tracing::instrument(name = "evm_verifier::verify_evm_calldata", level = "info", skip_all)]
+pub fn verify_evm_calldata(
+    calldata: &[u8],
+    accessor: &impl CommitmentAccessor<HyperKZGCommitment>,
+    setup: &VerifierKey<HyperKZGEngine>,
+) -> QueryResult<BNScalar> {
+    let Calldata {
+        plan,
+        plan_bytes,
+        result,
+        result_bytes,
+        first_round_message,
+        first_round_bytes,
+        final_round_message,
+        final_round_bytes,
+        sumcheck_coefficients,
+        pcs_proof_evaluations,
+        pcs_proof_evaluations_bytes,
+        evaluation_proof,
+    } = Calldata::try_decode(calldata)?;
+
+    let table_refs = plan.get_table_references();
+    let (min_row_num, _) = get_index_range(accessor, &table_refs);
+    if min_row_num != 0 {
+        Err(ProofError::VerificationError {
+            error: "tables with a non-zero offset are not supported by the EVM verifier",
+        })?;
+    }
+    let num_sumcheck_variables = cmp::max(log2_up(first_round_message.range_length), 1);
+
+    for dist in &final_round_message.bit_distributions {
+        if !dist.is_valid() {
+            Err(ProofError::VerificationError {
+                error: "invalid bit distributions",
+            })?;
+        } else if !dist.is_within_acceptable_range() {
+            Err(ProofError::VerificationError {
+                error: "bit distribution outside of acceptable range",
+            })?;
+        }
+    }
+
+    let mut transcript = EVMTranscript(keccak(&[
+        plan_bytes,
+        result_bytes,
+        &(min_row_num as u64).to_be_bytes(),
+    ]));
+
+    transcript.append_calldata(first_round_bytes);
+    let post_result_challenges: VecDeque<_> = transcript
+        .draw_challenges(first_round_message.post_result_challenge_count)
+        .into();
+
+    transcript.append_calldata(final_round_bytes);
+    let random_scalars = transcript.draw_challenges(
+        num_sumcheck_variables + final_round_message.subpolynomial_constraint_count,
+    );
+    let sumcheck_random_scalars = SumcheckRandomScalars::new(
+        &random_scalars,
+        first_round_message.range_length,
+        num_sumcheck_variables,
+    );
+
+    let subclaim = verify_sumcheck_proof(
+        &mut transcript,
+        sumcheck_coefficients,
+        num_sumcheck_variables,
+    )?;
+
+    transcript.append_calldata(pcs_proof_evaluations_bytes);
+    let evaluation_random_scalars = transcript.draw_challenges(
+        pcs_proof_evaluations.first_round.len()
+            + pcs_proof_evaluations.column_ref.len()
+            + pcs_proof_evaluations.final_round.len(),
+    );
+
+    let table_length_map = table_refs
+        .into_iter()
+        .map(|table_ref| {
+            let len = accessor.get_length(&table_ref);
+            (table_ref, len)
+        })
+        .collect::<IndexMap<TableRef, usize>>();
+    let chi_evaluation_lengths = table_length_map
+        .values()
+        .chain(first_round_message.chi_evaluation_lengths.iter())
+        .copied();
+    let sumcheck_evaluations = SumcheckMleEvaluations::new(
+        first_round_message.range_length,
+        chi_evaluation_lengths,
+        first_round_message.rho_evaluation_lengths.clone(),
+        &subclaim.evaluation_point,
+        &sumcheck_random_scalars,
+        &pcs_proof_evaluations.first_round,
+        &pcs_proof_evaluations.final_round,
+    );
+    let chi_eval_map: IndexMap<TableRef, BNScalar> = table_length_map
+        .into_iter()
+        .map(|(table_ref, length)| (table_ref, sumcheck_evaluations.chi_evaluations[&length]))
+        .collect();
+    let mut builder = VerificationBuilderImpl::new(
+        sumcheck_evaluations,
+        &final_round_message.bit_distributions,
+        sumcheck_random_scalars.subpolynomial_multipliers,
+        post_result_challenges,
+        first_round_message.chi_evaluation_lengths.clone(),
+        first_round_message.rho_evaluation_lengths.clone(),
+        subclaim.degree,
+    );
+
+    let column_references = plan.get_column_references();
+    let pcs_proof_commitments: Vec<_> = first_round_message
+        .round_commitments
+        .iter()
+        .copied()
+        .chain(
+            column_references
+                .iter()
+                .map(|col| accessor.get_commitment(col.clone())),
+        )
+        .chain(final_round_message.round_commitments.iter().copied())
+        .collect();
+    let evaluation_accessor: IndexMap<_, _> = column_references
+        .into_iter()
+        .zip(pcs_proof_evaluations.column_ref.iter().copied())
+        .collect();
+
+    let verifier_evaluations = plan.verifier_evaluate(
+        &mut builder,
+        &evaluation_accessor,
+        Some(&result),
+        &chi_eval_map,
+    )?;
+    if verifier_evaluations.column_evals() != result.mle_evaluations(&subclaim.evaluation_point) {
+        Err(ProofError::VerificationError {
+            error: "result evaluation check failed",
+        })?;
+    }
+    if builder.sumcheck_evaluation() != subclaim.expected_evaluation {
+        Err(ProofError::VerificationError {
+            error: "sumcheck evaluation check failed",
+        })?;
+    }
+
+    let pcs_proof_evaluations: Vec<_> = pcs_proof_evaluations
+        .first_round
+        .iter()
+        .chain(pcs_proof_evaluations.column_ref.iter())
+        .chain(pcs_proof_evaluations.final_round.iter())
+        .copied()
+        .collect();
+    let evaluation_proof = evaluation_proof
+        .try_into_proof()
+        .ok_or(MALFORMED_CALLDATA)?;
+    let evaluation_proof_challenge = verify_batched_proof_with_transcript_state(
+        &evaluation_proof,
+        transcript.0,
+        &pcs_proof_commitments,
+        &evaluation_random_scalars,
+        &pcs_proof_evaluations,
+        &subclaim.evaluation_point,
+        setup,
+    )
+    .map_err(|_e| ProofError::VerificationError {
+        error: "Inner product proof of MLE evaluations failed",
+    })?;
+    transcript.append_calldata(&evaluation_proof_challenge);
+
+    Ok(QueryData {
+        table: result.try_coerce_with_fields(plan.get_column_result_fields())?,
+        verification_hash: transcript.0,
+    })
+}
@@ -0,0 +1,206 @@
+use super::{
+    encode_evm_calldata, verify_evm_calldata, EVMCalldataError, QueryError, VerifiableQueryResult,
+};
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef},
+        proof::ProofError,
+    },
+    proof_primitive::hyperkzg::{HyperKZGCommitmentEvaluationProof, HyperKZGEngine},
+    sql::{
+        evm_proof_plan::EVMProofPlan,
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use nova_snark::{
+    provider::hyperkzg::{CommitmentEngine, CommitmentKey, EvaluationEngine, VerifierKey},
+    traits::{commitment::CommitmentEngineTrait, evaluation::EvaluationEngineTrait},
+};
+
+type CP = HyperKZGCommitmentEvaluationProof;
+
+fn setup() -> (CommitmentKey<HyperKZGEngine>, VerifierKey<HyperKZGEngine>) {
+    let ck: CommitmentKey<HyperKZGEngine> = CommitmentEngine::setup(b"test", 32);
+    let (_, vk) = EvaluationEngine::setup(&ck);
+    (ck, vk)
+}
+
+fn accessor(ck: &CommitmentKey<HyperKZGEngine>) -> (TableRef, OwnedTableTestAccessor<'_, CP>) {
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<CP>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 2, 5]),
+            bigint("b", [1_i64, 0, 1, 1, 0]),
+            varchar("c", ["x", "y", "z", "y", "x"]),
+        ]),
+        0,
+        ck,
+    );
+    (t, accessor)
+}
+
+fn filter_plan(t: &TableRef, accessor: &OwnedTableTestAccessor<CP>) -> DynProofPlan {
+    filter(
+        cols_expr_plan(t, &["a", "c"], accessor),
+        tab(t),
+        equal(column(t, "b", accessor), const_bigint(1)),
+    )
+}
+
+fn calldata_of(
+    plan: &EVMProofPlan,
+    accessor: &OwnedTableTestAccessor<CP>,
+    ck: &CommitmentKey<HyperKZGEngine>,
+) -> Vec<u8> {
+    let verifiable_result = VerifiableQueryResult::<CP>::new(plan, accessor, &ck);
+    encode_evm_calldata(plan, &verifiable_result).unwrap()
+}
+
+fn read_length(bytes: &[u8]) -> usize {
+    usize::try_from(u64::from_be_bytes(bytes[..8].try_into().unwrap())).unwrap()
+}
+
+/// Returns the start and the length of the encoded result.
+fn result_section(calldata: &[u8]) -> (usize, usize) {
+    let result_length_start = 8 + read_length(calldata);
+    (
+        result_length_start + 8,
+        read_length(&calldata[result_length_start..]),
+    )
+}
+
+fn assert_calldata_verifies_like_the_proof(plan: DynProofPlan) {
+    let (ck, vk) = setup();
+    let (_, accessor) = accessor(&ck);
+    let plan = EVMProofPlan::new(plan);
+    let verifiable_result = VerifiableQueryResult::<CP>::new(&plan, &accessor, &&ck);
+    let calldata = encode_evm_calldata(&plan, &verifiable_result).unwrap();
+    let evm_data = verify_evm_calldata(&calldata, &accessor, &vk).unwrap();
+    let data = verifiable_result.verify(&plan, &accessor, &&vk).unwrap();
+    assert_eq!(evm_data.table, data.table);
+    assert_eq!(evm_data.verification_hash, data.verification_hash);
+}
+
+#[test]
+fn we_can_verify_the_calldata_of_a_filter() {
+    let (ck, _) = setup();
+    let (t, accessor) = accessor(&ck);
+    assert_calldata_verifies_like_the_proof(filter_plan(&t, &accessor));
+}
+
+#[test]
+fn we_can_verify_the_calldata_of_a_group_by() {
+    let (ck, _) = setup();
+    let (t, accessor) = accessor(&ck);
+    assert_calldata_verifies_like_the_proof(group_by(
+        cols_expr_plan(&t, &["c"], &accessor),
+        vec![sum_expr(column(&t, "a", &accessor), "sum_a")],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_bigint(1)),
+    ));
+}
+
+#[test]
+fn we_can_verify_the_calldata_of_a_plan_over_another_plan() {
+    let (ck, _) = setup();
+    let (t, accessor) = accessor(&ck);
+    assert_calldata_verifies_like_the_proof(slice_exec(filter_plan(&t, &accessor), 1, Some(1)));
+}
+
+#[test]
+fn we_cannot_encode_a_result_without_a_proof() {
+    let (ck, _) = setup();
+    let t = TableRef::new("sxt", "t");
+    let accessor = OwnedTableTestAccessor::<CP>::new_from_table(
+        t.clone(),
+        owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]),
+        0,
+        &ck,
+    );
+    let plan = EVMProofPlan::new(filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_bigint(1)),
+    ));
+    let verifiable_result = VerifiableQueryResult::<CP>::new(&plan, &accessor, &&ck);
+    assert!(matches!(
+        encode_evm_calldata(&plan, &verifiable_result),
+        Err(EVMCalldataError::MissingProof)
+    ));
+}
+
+#[test]
+fn we_cannot_verify_truncated_or_extended_calldata() {
+    let (ck, vk) = setup();
+    let (t, accessor) = accessor(&ck);
+    let plan = EVMProofPlan::new(filter_plan(&t, &accessor));
+    let calldata = calldata_of(&plan, &accessor, &ck);
+    assert!(verify_evm_calldata(&calldata[..calldata.len() - 1], &accessor, &vk).is_err());
+    let mut extended_calldata = calldata;
+    extended_calldata.push(0);
+    assert!(verify_evm_calldata(&extended_calldata, &accessor, &vk).is_err());
+    assert!(verify_evm_calldata(&[], &accessor, &vk).is_err());
+}
+
+#[test]
+fn we_cannot_verify_calldata_with_any_part_of_the_proof_changed() {
+    let (ck, vk) = setup();
+    let (t, accessor) = accessor(&ck);
+    let plan = EVMProofPlan::new(filter_plan(&t, &accessor));
+    let calldata = calldata_of(&plan, &accessor, &ck);
+    let (result_start, result_length) = result_section(&calldata);
+    let plan_and_result_length = result_start + result_length;
+    // Every byte of the proof is either appended to a transcript or read as a length or a point.
+    for i in (plan_and_result_length..calldata.len()).step_by(31) {
+        let mut tampered_calldata = calldata.clone();
+        tampered_calldata[i] ^= 1;
+        assert!(
+            verify_evm_calldata(&tampered_calldata, &accessor, &vk).is_err(),
+            "byte {i}"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_verify_calldata_with_a_tampered_result() {
+    let (ck, vk) = setup();
+    let (t, accessor) = accessor(&ck);
+    let plan = EVMProofPlan::new(filter_plan(&t, &accessor));
+    let calldata = calldata_of(&plan, &accessor, &ck);
+    let mut tampered_calldata = calldata.clone();
+    // The last byte of the result is the last byte of the last string, "y".
+    let (result_start, result_length) = result_section(&calldata);
+    let last_result_byte = result_start + result_length - 1;
+    assert_eq!(tampered_calldata[last_result_byte], b'y');
+    tampered_calldata[last_result_byte] = b'z';
+    assert!(verify_evm_calldata(&tampered_calldata, &accessor, &vk).is_err());
+}
+
+#[test]
+fn we_cannot_verify_calldata_against_a_table_with_a_non_zero_offset() {
+    let (ck, vk) = setup();
+    let (t, accessor) = accessor(&ck);
+    let plan = EVMProofPlan::new(filter_plan(&t, &accessor));
+    let calldata = calldata_of(&plan, &accessor, &ck);
+    let offset_accessor = OwnedTableTestAccessor::<CP>::new_from_table(
+        t.clone(),
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 2, 5]),
+            bigint("b", [1_i64, 0, 1, 1, 0]),
+            varchar("c", ["x", "y", "z", "y", "x"]),
+        ]),
+        1,
+        &ck,
+    );
+    assert!(matches!(
+        verify_evm_calldata(&calldata, &offset_accessor, &vk),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "tables with a non-zero offset are not supported by the EVM verifier"
+            }
+        })
+    ));
+}
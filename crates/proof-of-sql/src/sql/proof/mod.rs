@@ -58,6 +58,13 @@ mod verifiable_query_result_test_utility;
 #[cfg(all(test, feature = "blitzar"))]
 pub(crate) use verifiable_query_result_test_utility::exercise_verification;
 
+#[cfg(feature = "hyperkzg")]
+mod evm_verifier;
+#[cfg(feature = "hyperkzg")]
+pub use evm_verifier::{encode_evm_calldata, verify_evm_calldata, EVMCalldataError};
+#[cfg(all(test, feature = "hyperkzg"))]
+mod evm_verifier_test;
+
 mod result_element_serialization;
 pub(crate) use result_element_serialization::{
     decode_and_convert, decode_multiple_elements, ProvableResultElement,
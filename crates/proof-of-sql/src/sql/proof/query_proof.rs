@@ -29,7 +29,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Basically we are looking for the smallest offset and the largest offset + length
 /// so that we have an index range of the table rows that the query is referencing.
-fn get_index_range<'a>(
+pub(super) fn get_index_range<'a>(
     accessor: &dyn MetadataAccessor,
     table_refs: impl IntoIterator<Item = &'a TableRef>,
 ) -> (usize, usize) {
@@ -1,4 +1,7 @@
-use super::{add_subtract_columns, scale_and_add_subtract_eval, DynProofExpr, ProofExpr};
+use super::{
+    add_subtract_columns, presence_util::all_present, scale_and_add_subtract_eval, DynProofExpr,
+    ProofExpr,
+};
 use crate::{
     base::{
         database::{try_add_subtract_column_types, Column, ColumnRef, ColumnType, Table},
@@ -16,9 +19,9 @@ use serde::{Deserialize, Serialize};
 /// Provable numerical `+` / `-` expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AddSubtractExpr {
-    lhs: Box<DynProofExpr>,
-    rhs: Box<DynProofExpr>,
-    is_subtract: bool,
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+    pub(crate) is_subtract: bool,
 }
 
 impl AddSubtractExpr {
@@ -103,4 +106,11 @@ impl ProofExpr for AddSubtractExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.lhs.presence_expr(nullable_columns),
+            self.rhs.presence_expr(nullable_columns),
+        ])
+    }
 }
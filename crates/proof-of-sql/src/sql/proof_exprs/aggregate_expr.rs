@@ -19,8 +19,8 @@ use serde::{Deserialize, Serialize};
 /// Currently it doesn't do much since aggregation logic is implemented elsewhere
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AggregateExpr {
-    op: AggregationOperator,
-    expr: Box<DynProofExpr>,
+    pub(crate) op: AggregationOperator,
+    pub(crate) expr: Box<DynProofExpr>,
 }
 
 impl AggregateExpr {
@@ -34,9 +34,11 @@ impl ProofExpr for AggregateExpr {
     // Remove the count method
     fn data_type(&self) -> ColumnType {
         match self.op {
-            AggregationOperator::Count => ColumnType::BigInt,
-            AggregationOperator::Sum => self.expr.data_type(),
-            _ => todo!("Aggregation operator not supported here yet"),
+            AggregationOperator::Count | AggregationOperator::CountDistinct => ColumnType::BigInt,
+            AggregationOperator::Sum | AggregationOperator::Max | AggregationOperator::Min => {
+                self.expr.data_type()
+            }
+            AggregationOperator::First => todo!("Aggregation operator not supported here yet"),
         }
     }
 
@@ -83,4 +85,8 @@ impl ProofExpr for AggregateExpr {
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.expr.get_column_references(columns);
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        self.expr.presence_expr(nullable_columns)
+    }
 }
@@ -5,6 +5,8 @@ use sqlparser::ast::Ident;
 /// A `DynProofExpr` with an alias.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AliasedDynProofExpr {
+    /// The expression
     pub expr: DynProofExpr,
+    /// The alias of the expression
     pub alias: Ident,
 }
@@ -1,4 +1,7 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{
+    presence_util::{all_present, any_present, not},
+    DynProofExpr, ProofExpr,
+};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -16,8 +19,8 @@ use serde::{Deserialize, Serialize};
 /// Provable logical AND expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AndExpr {
-    lhs: Box<DynProofExpr>,
-    rhs: Box<DynProofExpr>,
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
 }
 
 impl AndExpr {
@@ -114,4 +117,20 @@ impl ProofExpr for AndExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    /// `a AND b` is not null where both operands are not null or where either operand is `false`.
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        let lhs_presence = self.lhs.presence_expr(nullable_columns);
+        let rhs_presence = self.rhs.presence_expr(nullable_columns);
+        if lhs_presence.is_none() && rhs_presence.is_none() {
+            return None;
+        }
+        let lhs_is_false = all_present([lhs_presence.clone(), Some(not(*self.lhs.clone()))]);
+        let rhs_is_false = all_present([rhs_presence.clone(), Some(not(*self.rhs.clone()))]);
+        any_present([
+            all_present([lhs_presence, rhs_presence]),
+            lhs_is_false,
+            rhs_is_false,
+        ])
+    }
 }
@@ -5,7 +5,7 @@ use crate::{
             owned_table_utility::*, table_utility::*, Column, ColumnRef, ColumnType,
             OwnedTableTestAccessor, Table, TableRef, TableTestAccessor,
         },
-        map::indexmap,
+        map::{indexmap, indexset},
         polynomial::MultilinearExtension,
         scalar::{test_scalar::TestScalar, Scalar},
     },
@@ -58,6 +58,54 @@ fn we_can_prove_a_simple_and_query() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_prove_an_and_query_with_nulls() {
+    let data = nullable_owned_table(
+        [
+            boolean("a", [true, true, false, false, false]),
+            boolean("b", [true, false, false, false, false]),
+            bigint("c", [0, 1, 2, 3, 4]),
+        ],
+        [
+            ("a".into(), vec![true, true, true, false, false]),
+            ("b".into(), vec![true, false, false, true, false]),
+        ],
+    );
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let nullable_columns = indexset! {
+        col_ref(&t, "a", &accessor),
+        col_ref(&t, "b", &accessor),
+    };
+    let a_and_b = and(column(&t, "a", &accessor), column(&t, "b", &accessor));
+
+    // NOT (a AND b) is true where one side is false, even if the other side is null
+    let where_clause = not(a_and_b.clone());
+    let presence = where_clause.presence_expr(&nullable_columns).unwrap();
+    let ast = filter(
+        cols_expr_plan(&t, &["c"], &accessor),
+        tab(&t),
+        and(where_clause, presence),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [2, 3])]);
+    assert_eq!(res, expected_res);
+
+    let ast = filter(
+        cols_expr_plan(&t, &["c"], &accessor),
+        tab(&t),
+        DynProofExpr::new_is_null(&a_and_b, &nullable_columns),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [1, 4])]);
+    assert_eq!(res, expected_res);
+}
+
 #[test]
 fn we_can_prove_a_simple_and_query_with_128_bits() {
     let data = owned_table([
@@ -0,0 +1,152 @@
+use super::{
+    presence_util::all_present, prover_evaluate_or, result_evaluate_or,
+    scale_and_add_subtract_eval, scale_and_subtract, verifier_evaluate_or, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof_gadgets::{prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign},
+    },
+    utils::log,
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable `BETWEEN` expression
+///
+/// `expr BETWEEN low AND high` is `NOT (expr < low OR high < expr)`.
+/// Unlike the equivalent pair of inequalities, `expr` is only evaluated once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BetweenExpr {
+    pub(crate) expr: Box<DynProofExpr>,
+    pub(crate) low: Box<DynProofExpr>,
+    pub(crate) high: Box<DynProofExpr>,
+}
+
+impl BetweenExpr {
+    /// Create a new `BETWEEN` expression
+    pub fn new(expr: Box<DynProofExpr>, low: Box<DynProofExpr>, high: Box<DynProofExpr>) -> Self {
+        Self { expr, low, high }
+    }
+
+    pub(crate) fn scales(&self) -> (i8, i8, i8) {
+        (
+            self.expr.data_type().scale().unwrap_or(0),
+            self.low.data_type().scale().unwrap_or(0),
+            self.high.data_type().scale().unwrap_or(0),
+        )
+    }
+}
+
+impl ProofExpr for BetweenExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.result_evaluate(alloc, table);
+        let low_column = self.low.result_evaluate(alloc, table);
+        let high_column = self.high.result_evaluate(alloc, table);
+        let (scale, low_scale, high_scale) = self.scales();
+        let table_length = table.num_rows();
+
+        // sign(expr - low) == -1
+        let below_low = scale_and_subtract(alloc, column, low_column, scale, low_scale, false)
+            .expect("Failed to scale and subtract");
+        let is_below_low = result_evaluate_sign(table_length, alloc, below_low);
+        // sign(high - expr) == -1
+        let above_high = scale_and_subtract(alloc, high_column, column, high_scale, scale, false)
+            .expect("Failed to scale and subtract");
+        let is_above_high = result_evaluate_sign(table_length, alloc, above_high);
+
+        let is_outside = result_evaluate_or(table_length, alloc, is_below_low, is_above_high);
+        let res = Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| !is_outside[i]));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column = self.expr.prover_evaluate(builder, alloc, table);
+        let low_column = self.low.prover_evaluate(builder, alloc, table);
+        let high_column = self.high.prover_evaluate(builder, alloc, table);
+        let (scale, low_scale, high_scale) = self.scales();
+
+        // sign(expr - low) == -1
+        let below_low = scale_and_subtract(alloc, column, low_column, scale, low_scale, false)
+            .expect("Failed to scale and subtract");
+        let is_below_low = prover_evaluate_sign(builder, alloc, below_low);
+        // sign(high - expr) == -1
+        let above_high = scale_and_subtract(alloc, high_column, column, high_scale, scale, false)
+            .expect("Failed to scale and subtract");
+        let is_above_high = prover_evaluate_sign(builder, alloc, above_high);
+
+        let is_outside = prover_evaluate_or(builder, alloc, is_below_low, is_above_high);
+        let res =
+            Column::Boolean(alloc.alloc_slice_fill_with(table.num_rows(), |i| !is_outside[i]));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        let low_eval = self.low.verifier_evaluate(builder, accessor, chi_eval)?;
+        let high_eval = self.high.verifier_evaluate(builder, accessor, chi_eval)?;
+        let (scale, low_scale, high_scale) = self.scales();
+
+        // sign(expr - low) == -1
+        let below_low_eval = scale_and_add_subtract_eval(eval, low_eval, scale, low_scale, true);
+        let is_below_low_eval = verifier_evaluate_sign(builder, below_low_eval, chi_eval, None)?;
+        // sign(high - expr) == -1
+        let above_high_eval = scale_and_add_subtract_eval(high_eval, eval, high_scale, scale, true);
+        let is_above_high_eval = verifier_evaluate_sign(builder, above_high_eval, chi_eval, None)?;
+
+        let is_outside_eval =
+            verifier_evaluate_or(builder, &is_below_low_eval, &is_above_high_eval)?;
+        Ok(chi_eval - is_outside_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+        self.low.get_column_references(columns);
+        self.high.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.expr.presence_expr(nullable_columns),
+            self.low.presence_expr(nullable_columns),
+            self.high.presence_expr(nullable_columns),
+        ])
+    }
+}
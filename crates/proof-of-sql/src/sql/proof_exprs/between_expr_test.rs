@@ -0,0 +1,111 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor,
+        },
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+
+// select a, b from sxt.t where a between -2 and 5
+#[test]
+fn we_can_prove_a_between_query_with_literal_bounds() {
+    let data = owned_table([
+        bigint("a", [-3_i64, -2, 0, 5, 6, i64::MIN, i64::MAX]),
+        varchar("b", ["a", "b", "c", "d", "e", "f", "g"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        between(
+            column(&t, "a", &accessor),
+            const_bigint(-2),
+            const_bigint(5),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [-2_i64, 0, 5]), varchar("b", ["b", "c", "d"])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where not a between b and c
+#[test]
+fn we_can_prove_a_not_between_query_with_column_bounds_of_different_scales() {
+    let data = owned_table([
+        decimal75("a", 10, 2, [150_i64, 100, 300, 99, 301, 0]),
+        int("b", [1_i32, 1, 1, 1, 1, 1]),
+        decimal75("c", 10, 1, [30_i64, 30, 30, 30, 30, -30]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        not(between(
+            column(&t, "a", &accessor),
+            column(&t, "b", &accessor),
+            column(&t, "c", &accessor),
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("a", 10, 2, [99_i64, 301, 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_a_between_expression_with_incompatible_bounds() {
+    let data = owned_table([bigint("a", [1_i64]), varchar("b", ["ab"])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_between(
+            column(&t, "a", &accessor),
+            const_bigint(0),
+            column(&t, "b", &accessor),
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_between(
+            column(&t, "b", &accessor),
+            const_bigint(0),
+            const_varchar("b"),
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+// a between 0 and 10
+#[test]
+fn we_can_compute_the_correct_output_of_a_between_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_smallint("a", [-1_i16, 0, 7, 10, 11], &alloc)]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let between_expr: DynProofExpr = between(
+        column(&t, "a", &accessor),
+        const_bigint(0),
+        const_bigint(10),
+    );
+    let res = between_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[false, true, true, true, false]);
+    assert_eq!(res, expected_res);
+}
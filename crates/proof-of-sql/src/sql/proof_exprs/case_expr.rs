@@ -0,0 +1,267 @@
+use super::{presence_util::all_present, scale_column, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{
+            apply_column_to_indexes, union_util::column_union, Column, ColumnRef, ColumnType, Table,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        FinalRoundBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::iter;
+use serde::{Deserialize, Serialize};
+
+/// Provable `CASE WHEN ... THEN ... ELSE ... END` expression
+///
+/// Every branch has the data type of the expression. The result of a row is the result
+/// of the first branch whose condition is true, or the `ELSE` result if there is none.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseExpr {
+    pub(crate) when_then: Vec<(DynProofExpr, DynProofExpr)>,
+    pub(crate) else_expr: Box<DynProofExpr>,
+}
+
+impl CaseExpr {
+    /// Create a new `CASE` expression
+    pub fn new(when_then: Vec<(DynProofExpr, DynProofExpr)>, else_expr: Box<DynProofExpr>) -> Self {
+        Self {
+            when_then,
+            else_expr,
+        }
+    }
+
+    /// The result expressions of the branches, with the `ELSE` branch last
+    fn results(&self) -> impl Iterator<Item = &DynProofExpr> {
+        self.when_then
+            .iter()
+            .map(|(_, result)| result)
+            .chain(iter::once(self.else_expr.as_ref()))
+    }
+}
+
+/// For every row, the index of the first condition that is true,
+/// or the number of conditions if there is none.
+fn first_true_conditions(conditions: &[&[bool]], num_rows: usize) -> Vec<usize> {
+    (0..num_rows)
+        .map(|row| {
+            conditions
+                .iter()
+                .position(|condition| condition[row])
+                .unwrap_or(conditions.len())
+        })
+        .collect()
+}
+
+/// Select the result of every row from the result of its branch.
+///
+/// The result keeps the column type of the branches if they all have the same one.
+fn select_results<'a, S: Scalar>(
+    alloc: &'a Bump,
+    results: &[Column<'a, S>],
+    branches: &[usize],
+) -> Column<'a, S> {
+    let num_rows = branches.len();
+    let column_type = results[0].column_type();
+    if results
+        .iter()
+        .all(|result| result.column_type() == column_type)
+    {
+        let indexes = branches
+            .iter()
+            .enumerate()
+            .map(|(row, branch)| branch * num_rows + row)
+            .collect::<Vec<_>>();
+        let union = column_union(&results.iter().collect::<Vec<_>>(), alloc, column_type)
+            .expect("Branches should have the same column type");
+        apply_column_to_indexes(&union, alloc, &indexes).expect("Indexes should be in bounds")
+    } else {
+        let results = results
+            .iter()
+            .map(|result| scale_column(alloc, *result, 0))
+            .collect::<Vec<_>>();
+        Column::Scalar(alloc.alloc_slice_fill_with(num_rows, |row| results[branches[row]][row]))
+    }
+}
+
+impl ProofExpr for CaseExpr {
+    fn data_type(&self) -> ColumnType {
+        self.else_expr.data_type()
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let conditions = self
+            .when_then
+            .iter()
+            .map(|(condition, _)| {
+                condition
+                    .result_evaluate(alloc, table)
+                    .as_boolean()
+                    .expect("Condition is not boolean")
+            })
+            .collect::<Vec<_>>();
+        let results = self
+            .results()
+            .map(|result| result.result_evaluate(alloc, table))
+            .collect::<Vec<_>>();
+        let branches = first_true_conditions(&conditions, table.num_rows());
+        select_results(alloc, &results, &branches)
+    }
+
+    /// Let `c_i` be the conditions, `v_i` the results of the branches and `e` the `ELSE` result.
+    /// The argument consists of
+    /// 1. `s_i - c_i * (1 - s_1 - ... - s_{i-1}) = 0` for every branch, so that the selector `s_i`
+    ///    is exactly where `c_i` is the first true condition and the selectors are mutually exclusive
+    /// 2. `result - s_1 * v_1 - ... - s_n * v_n - (1 - s_1 - ... - s_n) * e = 0`
+    #[tracing::instrument(name = "CaseExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let num_rows = table.num_rows();
+        let mut conditions = Vec::with_capacity(self.when_then.len());
+        let mut results = Vec::with_capacity(self.when_then.len() + 1);
+        for (condition, result) in &self.when_then {
+            conditions.push(
+                condition
+                    .prover_evaluate(builder, alloc, table)
+                    .as_boolean()
+                    .expect("Condition is not boolean"),
+            );
+            results.push(result.prover_evaluate(builder, alloc, table));
+        }
+        results.push(self.else_expr.prover_evaluate(builder, alloc, table));
+        let branches = first_true_conditions(&conditions, num_rows);
+
+        // selectors
+        let mut selectors: Vec<&'a [bool]> = Vec::with_capacity(conditions.len());
+        for (i, &condition) in conditions.iter().enumerate() {
+            let selector: &'a [bool] =
+                alloc.alloc_slice_fill_with(num_rows, |row| branches[row] == i);
+            builder.produce_intermediate_mle(selector);
+
+            // subpolynomial: s_i - c_i + c_i * (s_1 + ... + s_{i-1})
+            let mut terms: Vec<SumcheckSubpolynomialTerm<'a, S>> = vec![
+                (S::one(), vec![Box::new(selector)]),
+                (-S::one(), vec![Box::new(condition)]),
+            ];
+            for &previous_selector in &selectors {
+                terms.push((
+                    S::one(),
+                    vec![Box::new(condition), Box::new(previous_selector)],
+                ));
+            }
+            builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+            selectors.push(selector);
+        }
+
+        // result
+        let result_values = results
+            .iter()
+            .map(|result| scale_column(alloc, *result, 0))
+            .collect::<Vec<_>>();
+        let else_values = result_values[conditions.len()];
+        let res: &'a [S] =
+            alloc.alloc_slice_fill_with(num_rows, |row| result_values[branches[row]][row]);
+        builder.produce_intermediate_mle(res);
+
+        // subpolynomial: result - e - sum(s_i * v_i) + sum(s_i * e)
+        let mut terms: Vec<SumcheckSubpolynomialTerm<'a, S>> = vec![
+            (S::one(), vec![Box::new(res)]),
+            (-S::one(), vec![Box::new(else_values)]),
+        ];
+        for (&selector, &values) in selectors.iter().zip(&result_values) {
+            terms.push((-S::one(), vec![Box::new(selector), Box::new(values)]));
+            terms.push((S::one(), vec![Box::new(selector), Box::new(else_values)]));
+        }
+        builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+        let res = select_results(alloc, &results, &branches);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let mut condition_evals = Vec::with_capacity(self.when_then.len());
+        let mut result_evals = Vec::with_capacity(self.when_then.len());
+        for (condition, result) in &self.when_then {
+            condition_evals.push(condition.verifier_evaluate(builder, accessor, chi_eval)?);
+            result_evals.push(result.verifier_evaluate(builder, accessor, chi_eval)?);
+        }
+        let else_eval = self
+            .else_expr
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+
+        // selectors
+        let mut selector_evals = Vec::with_capacity(condition_evals.len());
+        let mut previous_selectors_eval = S::ZERO;
+        for condition_eval in condition_evals {
+            let selector_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+            // subpolynomial: s_i - c_i + c_i * (s_1 + ... + s_{i-1})
+            builder.try_produce_sumcheck_subpolynomial_evaluation(
+                SumcheckSubpolynomialType::Identity,
+                selector_eval - condition_eval + condition_eval * previous_selectors_eval,
+                2,
+            )?;
+            previous_selectors_eval += selector_eval;
+            selector_evals.push(selector_eval);
+        }
+
+        // result
+        let res_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+        // subpolynomial: result - e - sum(s_i * v_i) + sum(s_i * e)
+        let selected_results_eval: S = selector_evals
+            .iter()
+            .zip(&result_evals)
+            .map(|(selector_eval, result_eval)| *selector_eval * (*result_eval - else_eval))
+            .sum();
+        builder.try_produce_sumcheck_subpolynomial_evaluation(
+            SumcheckSubpolynomialType::Identity,
+            res_eval - else_eval - selected_results_eval,
+            2,
+        )?;
+
+        Ok(res_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        for (condition, result) in &self.when_then {
+            condition.get_column_references(columns);
+            result.get_column_references(columns);
+        }
+        self.else_expr.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present(
+            self.when_then
+                .iter()
+                .flat_map(|(condition, result)| [condition, result])
+                .chain(iter::once(self.else_expr.as_ref()))
+                .map(|expr| expr.presence_expr(nullable_columns)),
+        )
+    }
+}
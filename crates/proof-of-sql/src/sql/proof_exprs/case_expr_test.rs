@@ -0,0 +1,191 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnType, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor,
+        },
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+
+// select case when a >= 10 then 'large' else 'small' end as size, b from sxt.t
+#[test]
+fn we_can_prove_a_case_expression_with_varchar_results() {
+    let data = owned_table([
+        bigint("a", [1_i64, 10, 25, -3, 9]),
+        bigint("b", [1_i64, 2, 3, 4, 5]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                case_when(
+                    vec![(
+                        gte(column(&t, "a", &accessor), const_bigint(10)),
+                        const_varchar("large"),
+                    )],
+                    const_varchar("small"),
+                ),
+                "size",
+            ),
+            col_expr_plan(&t, "b", &accessor),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        varchar("size", ["small", "large", "large", "small", "small"]),
+        bigint("b", [1_i64, 2, 3, 4, 5]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select case when a <= 0 then 0 when a <= 5 then b when a <= 10 then a else 100 end as c from sxt.t
+#[test]
+fn we_can_prove_a_case_expression_with_multiple_branches_of_different_numeric_types() {
+    let data = owned_table([
+        int("a", [-1_i32, 3, 5, 8, 11, 0]),
+        smallint("b", [7_i16, 8, 9, 10, 11, 12]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            case_when(
+                vec![
+                    (
+                        lte(column(&t, "a", &accessor), const_int(0)),
+                        const_smallint(0),
+                    ),
+                    (
+                        lte(column(&t, "a", &accessor), const_int(5)),
+                        column(&t, "b", &accessor),
+                    ),
+                    (
+                        lte(column(&t, "a", &accessor), const_int(10)),
+                        column(&t, "a", &accessor),
+                    ),
+                ],
+                const_bigint(100),
+            ),
+            "c",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [0_i64, 8, 9, 8, 100, 0])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where case when a <= 0 then false when b = 1 then true else a >= 10 end
+#[test]
+fn we_can_prove_a_case_expression_in_a_where_clause() {
+    let data = owned_table([
+        bigint("a", [-5_i64, 1, 2, 10, 20, 0]),
+        bigint("b", [1_i64, 1, 0, 0, 1, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        case_when(
+            vec![
+                (
+                    lte(column(&t, "a", &accessor), const_bigint(0)),
+                    const_bool(false),
+                ),
+                (
+                    equal(column(&t, "b", &accessor), const_bigint(1)),
+                    const_bool(true),
+                ),
+            ],
+            gte(column(&t, "a", &accessor), const_bigint(10)),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 10, 20])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_a_case_expression_with_incompatible_results() {
+    let data = owned_table([
+        varchar("a", ["ab"]),
+        bigint("b", [1_i64]),
+        boolean("c", [true]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_case(
+            vec![(column(&t, "c", &accessor), column(&t, "a", &accessor))],
+            column(&t, "b", &accessor),
+        ),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_case(
+            vec![(column(&t, "b", &accessor), column(&t, "b", &accessor))],
+            column(&t, "b", &accessor),
+        ),
+        Err(ConversionError::InvalidDataType {
+            expected: ColumnType::Boolean,
+            actual: ColumnType::BigInt,
+        })
+    ));
+    assert_eq!(
+        DynProofExpr::try_new_case(vec![], column(&t, "b", &accessor)).unwrap(),
+        column(&t, "b", &accessor)
+    );
+}
+
+// case when a = 1 then b when a = 2 then c else a end
+#[test]
+fn we_can_compute_the_correct_output_of_a_case_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [1_i64, 2, 3, 1], &alloc),
+        borrowed_bigint("b", [10_i64, 20, 30, 40], &alloc),
+        borrowed_bigint("c", [-10_i64, -20, -30, -40], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let case_expr: DynProofExpr = case_when(
+        vec![
+            (
+                equal(column(&t, "a", &accessor), const_bigint(1)),
+                column(&t, "b", &accessor),
+            ),
+            (
+                equal(column(&t, "a", &accessor), const_bigint(2)),
+                column(&t, "c", &accessor),
+            ),
+        ],
+        column(&t, "a", &accessor),
+    );
+    let res = case_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::BigInt(&[10, -20, 3, 40]);
+    assert_eq!(res, expected_res);
+}
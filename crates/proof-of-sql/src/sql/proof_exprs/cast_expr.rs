@@ -0,0 +1,245 @@
+use super::{
+    divide_and_modulo_scalars, prover_evaluate_divide_and_modulo, scale_column,
+    verifier_evaluate_divide_and_modulo, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{FinalRoundBuilder, VerificationBuilder},
+        proof_gadgets::{prover_evaluate_sign, verifier_evaluate_sign},
+    },
+    utils::log,
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+
+/// The largest power of ten that a downscaling cast divides by in a single step.
+///
+/// `10^37` is the largest power of ten below `2^125`, which bounds the divisors of the division gadget.
+const MAX_DIVISION_EXPONENT: u8 = 37;
+
+/// Provable `CAST` expression
+///
+/// Values are rescaled to the scale or time unit of the target type, truncating towards zero.
+/// Casts that can overflow the target type are range checked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CastExpr {
+    pub(crate) from_expr: Box<DynProofExpr>,
+    pub(crate) to_type: ColumnType,
+}
+
+impl CastExpr {
+    /// Create a new `CAST` expression
+    pub fn new(from_expr: Box<DynProofExpr>, to_type: ColumnType) -> Self {
+        Self { from_expr, to_type }
+    }
+
+    /// The power of ten that the values are scaled by. A negative exponent means truncating division.
+    ///
+    /// # Panics
+    /// Panics if the scaling exponent does not fit into an `i8`, which can not happen for valid scales.
+    pub(crate) fn scaling_exponent(&self) -> i8 {
+        let from_type = self.from_expr.data_type();
+        if matches!(
+            (from_type, self.to_type),
+            (ColumnType::TimestampTZ(_, _), ColumnType::BigInt)
+                | (ColumnType::BigInt, ColumnType::TimestampTZ(_, _))
+        ) {
+            return 0;
+        }
+        let from_scale = i16::from(from_type.scale().unwrap_or(0));
+        let to_scale = i16::from(self.to_type.scale().unwrap_or(0));
+        i8::try_from(to_scale - from_scale).expect("Scaling exponent should fit into i8")
+    }
+
+    /// The powers of ten that a downscaling cast successively divides by.
+    ///
+    /// Truncating division by `10^a` and then by `10^b` is the same as truncating division by `10^(a + b)`.
+    pub(crate) fn division_exponents(&self) -> impl Iterator<Item = u8> {
+        let exponent = self.scaling_exponent();
+        let total = if exponent < 0 {
+            exponent.unsigned_abs()
+        } else {
+            0
+        };
+        (0..total)
+            .step_by(MAX_DIVISION_EXPONENT.into())
+            .map(move |start| (total - start).min(MAX_DIVISION_EXPONENT))
+    }
+
+    /// The bounds of the target type that the rescaled values can exceed.
+    ///
+    /// # Panics
+    /// Panics if a bound does not fit into a scalar, which can not happen for supported types.
+    pub(crate) fn range_check_bounds<S: Scalar>(&self) -> (Option<S>, Option<S>) {
+        let (Some((from_min, from_max)), Some((to_min, to_max))) = (
+            type_bounds(self.from_expr.data_type()),
+            type_bounds(self.to_type),
+        ) else {
+            return (None, None);
+        };
+        let exponent = self.scaling_exponent();
+        let factor = BigInt::from(10).pow(u32::from(exponent.unsigned_abs()));
+        let rescale = |bound: BigInt| {
+            if exponent >= 0 {
+                bound * &factor
+            } else {
+                bound / &factor
+            }
+        };
+        let to_scalar =
+            |bound: BigInt| S::try_from(bound).expect("Type bounds should fit into a scalar");
+        let lower = (rescale(from_min) < to_min).then(|| to_scalar(to_min));
+        let upper = (rescale(from_max) > to_max).then(|| to_scalar(to_max));
+        (lower, upper)
+    }
+}
+
+impl ProofExpr for CastExpr {
+    fn data_type(&self) -> ColumnType {
+        self.to_type
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column: Column<'a, S> = self.from_expr.result_evaluate(alloc, table);
+        let exponent = self.scaling_exponent();
+        let values = if exponent >= 0 {
+            scale_column(alloc, column, exponent)
+        } else {
+            let values = scale_column(alloc, column, 0);
+            let divisor =
+                alloc.alloc_slice_fill_copy(values.len(), S::pow10(exponent.unsigned_abs()));
+            divide_and_modulo_scalars(alloc, values, divisor).0
+        };
+        Column::Scalar(values)
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.cast_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.from_expr.prover_evaluate(builder, alloc, table);
+        let exponent = self.scaling_exponent();
+        let values = if exponent >= 0 {
+            scale_column(alloc, column, exponent)
+        } else {
+            self.division_exponents().fold(
+                scale_column(alloc, column, 0),
+                |values, division_exponent| {
+                    let divisor =
+                        alloc.alloc_slice_fill_copy(values.len(), S::pow10(division_exponent));
+                    prover_evaluate_divide_and_modulo(builder, alloc, values, divisor).0
+                },
+            )
+        };
+
+        let (lower, upper) = self.range_check_bounds::<S>();
+        // values - lower >= 0
+        if let Some(lower) = lower {
+            let slack = alloc.alloc_slice_fill_with(values.len(), |i| values[i] - lower);
+            prover_evaluate_sign(builder, alloc, slack);
+        }
+        // upper - values >= 0
+        if let Some(upper) = upper {
+            let slack = alloc.alloc_slice_fill_with(values.len(), |i| upper - values[i]);
+            prover_evaluate_sign(builder, alloc, slack);
+        }
+        let res = Column::Scalar(values);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let eval = self
+            .from_expr
+            .verifier_evaluate(builder, accessor, chi_eval)?;
+        let exponent = self.scaling_exponent();
+        let eval = if exponent >= 0 {
+            eval * S::pow10(exponent.unsigned_abs())
+        } else {
+            self.division_exponents()
+                .try_fold(eval, |eval, division_exponent| {
+                    verifier_evaluate_divide_and_modulo(
+                        builder,
+                        eval,
+                        chi_eval * S::pow10(division_exponent),
+                        chi_eval,
+                    )
+                    .map(|(quotient_eval, _)| quotient_eval)
+                })?
+        };
+
+        let (lower, upper) = self.range_check_bounds::<S>();
+        // values - lower >= 0
+        let lower_slack_is_negative_eval = lower
+            .map(|lower| verifier_evaluate_sign(builder, eval - chi_eval * lower, chi_eval, None))
+            .transpose()?;
+        // upper - values >= 0
+        let upper_slack_is_negative_eval = upper
+            .map(|upper| verifier_evaluate_sign(builder, chi_eval * upper - eval, chi_eval, None))
+            .transpose()?;
+        if lower_slack_is_negative_eval.is_some_and(|e| e != S::ZERO)
+            || upper_slack_is_negative_eval.is_some_and(|e| e != S::ZERO)
+        {
+            return Err(ProofError::VerificationError {
+                error: "cast value is out of range",
+            });
+        }
+        Ok(eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.from_expr.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        self.from_expr.presence_expr(nullable_columns)
+    }
+}
+
+/// The smallest and largest values of a column type, in units of its scale or time unit.
+pub(super) fn type_bounds(column_type: ColumnType) -> Option<(BigInt, BigInt)> {
+    match column_type {
+        ColumnType::Boolean => Some((0.into(), 1.into())),
+        ColumnType::Uint8 => Some((u8::MIN.into(), u8::MAX.into())),
+        ColumnType::TinyInt => Some((i8::MIN.into(), i8::MAX.into())),
+        ColumnType::SmallInt => Some((i16::MIN.into(), i16::MAX.into())),
+        ColumnType::Int => Some((i32::MIN.into(), i32::MAX.into())),
+        ColumnType::BigInt | ColumnType::TimestampTZ(_, _) => {
+            Some((i64::MIN.into(), i64::MAX.into()))
+        }
+        ColumnType::Int128 => Some((i128::MIN.into(), i128::MAX.into())),
+        ColumnType::Decimal75(precision, _) => {
+            let max = BigInt::from(10).pow(u32::from(precision.value())) - 1;
+            Some((-max.clone(), max))
+        }
+        ColumnType::VarChar | ColumnType::VarBinary | ColumnType::Scalar => None,
+    }
+}
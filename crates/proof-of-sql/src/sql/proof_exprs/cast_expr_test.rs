@@ -0,0 +1,322 @@
+use super::divide_expr::prove_quotient_and_remainder;
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnRef, ColumnType,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor,
+        },
+        map::indexmap,
+        math::decimal::Precision,
+        polynomial::MultilinearExtension,
+        proof::ProofError,
+        scalar::{test_scalar::TestScalar, Curve25519Scalar, Scalar},
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{
+            exercise_verification, mock_verification_builder::run_verify_for_each_row,
+            FinalRoundBuilder, QueryError, VerifiableQueryResult,
+        },
+        proof_exprs::{test_utility::*, CastExpr, ColumnExpr, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use core::cell::RefCell;
+use num_traits::Inv;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+use sqlparser::ast::Ident;
+use std::collections::VecDeque;
+
+// select cast(a as bigint) as a, cast(b as int) as b, cast(c as decimal(10, 2)) as c from sxt.t
+#[test]
+fn we_can_prove_widening_casts() {
+    let data = owned_table([
+        smallint("a", [7_i16, -7, i16::MIN, i16::MAX]),
+        boolean("b", [true, false, true, false]),
+        int("c", [1_i32, -2, 30, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(cast(column(&t, "a", &accessor), ColumnType::BigInt), "a"),
+            aliased_plan(cast(column(&t, "b", &accessor), ColumnType::Int), "b"),
+            aliased_plan(
+                cast(
+                    column(&t, "c", &accessor),
+                    ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+                ),
+                "c",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [7_i64, -7, i16::MIN.into(), i16::MAX.into()]),
+        int("b", [1_i32, 0, 1, 0]),
+        decimal75("c", 10, 2, [100_i64, -200, 3000, 0]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as smallint) as a, cast(b as int) as b from sxt.t where cast(a as smallint) >= 0
+#[test]
+fn we_can_prove_narrowing_casts_that_truncate_towards_zero() {
+    let data = owned_table([
+        bigint("a", [7_i64, -7, i16::MAX.into(), 0]),
+        decimal75("b", 10, 2, [12_345_i64, -12_399, 99, -100]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(cast(column(&t, "a", &accessor), ColumnType::SmallInt), "a"),
+            aliased_plan(cast(column(&t, "b", &accessor), ColumnType::Int), "b"),
+        ],
+        tab(&t),
+        gte(
+            cast(column(&t, "a", &accessor), ColumnType::SmallInt),
+            const_smallint(0),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        smallint("a", [7_i16, i16::MAX, 0]),
+        int("b", [123_i32, 0, -1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as decimal(75, 0)) as a from sxt.t
+#[test]
+fn we_can_prove_a_narrowing_cast_that_divides_in_several_steps() {
+    let data = owned_table([decimal75(
+        "a",
+        75,
+        38,
+        [
+            15 * 10_i128.pow(37),
+            -12 * 10_i128.pow(37),
+            10_i128.pow(37),
+            0,
+        ],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = projection(
+        vec![aliased_plan(
+            cast(
+                column(&t, "a", &accessor),
+                ColumnType::Decimal75(Precision::new(75).unwrap(), 0),
+            ),
+            "a",
+        )],
+        tab(&t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("a", 75, 0, [1_i64, -1, 0, 0])]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as timestamp(0)) as a, cast(b as timestamp(3)) as b, cast(a as bigint) as c from sxt.t
+#[test]
+fn we_can_prove_casts_between_timestamps_and_bigints() {
+    let data = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::utc(),
+            [1_500_i64, -1_500, 0],
+        ),
+        bigint("b", [1_i64, -2, 3]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                cast(
+                    column(&t, "a", &accessor),
+                    ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::utc()),
+                ),
+                "a",
+            ),
+            aliased_plan(
+                cast(
+                    column(&t, "b", &accessor),
+                    ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::utc()),
+                ),
+                "b",
+            ),
+            aliased_plan(cast(column(&t, "a", &accessor), ColumnType::BigInt), "c"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [1_i64, -1, 0],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::utc(),
+            [1_i64, -2, 3],
+        ),
+        bigint("c", [1_500_i64, -1_500, 0]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select cast(a as tinyint) as a from sxt.t
+#[test]
+fn we_cannot_verify_a_cast_that_overflows_the_target_type() {
+    for values in [[1_i64, 128], [-129_i64, 0]] {
+        let data = owned_table([bigint("a", values)]);
+        let t = TableRef::new("sxt", "t");
+        let accessor =
+            OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+        let ast = filter(
+            vec![aliased_plan(
+                cast(column(&t, "a", &accessor), ColumnType::TinyInt),
+                "a",
+            )],
+            tab(&t),
+            const_bool(true),
+        );
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &());
+        assert!(matches!(
+            verifiable_res.verify(&ast, &accessor, &()),
+            Err(QueryError::ProofError {
+                source: ProofError::VerificationError { .. }
+            })
+        ));
+    }
+}
+
+#[test]
+fn we_cannot_cast_unsupported_types() {
+    let data = owned_table([
+        varchar("a", ["ab"]),
+        bigint("b", [1_i64]),
+        scalar("c", [1_i64]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_cast(column(&t, "a", &accessor), ColumnType::BigInt),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_cast(column(&t, "b", &accessor), ColumnType::VarChar),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_cast(column(&t, "c", &accessor), ColumnType::BigInt),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert_eq!(
+        DynProofExpr::try_new_cast(column(&t, "a", &accessor), ColumnType::VarChar).unwrap(),
+        column(&t, "a", &accessor)
+    );
+}
+
+// cast(a as decimal(5, 1))
+#[test]
+fn we_can_compute_the_correct_output_of_a_cast_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_decimal75(
+        "a",
+        10,
+        3,
+        [1_239_i64, -1_239, 5, 0],
+        &alloc,
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let cast_expr: DynProofExpr = cast(
+        column(&t, "a", &accessor),
+        ColumnType::Decimal75(Precision::new(5).unwrap(), 1),
+    );
+    let res = cast_expr.result_evaluate(&alloc, &data);
+    let expected_res_scalar = [12, -12, 0, 0]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Scalar(&expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_narrowing_cast_with_a_tampered_quotient() {
+    let alloc = Bump::new();
+    let t = TableRef::new("sxt", "t");
+    let a = ColumnRef::new(
+        t,
+        Ident::from("a"),
+        ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+    );
+    let cast_expr = CastExpr::new(
+        Box::new(DynProofExpr::Column(ColumnExpr::new(a.clone()))),
+        ColumnType::Decimal75(Precision::new(10).unwrap(), 0),
+    );
+    let values = [799, -799].map(TestScalar::from);
+    let divisors = [100, 100].map(TestScalar::from);
+    // 7.99 and -7.99 are 100 times a field element that is not an integer, with a remainder of 0
+    let quotient = values.map(|value| value * TestScalar::from(100).inv().unwrap());
+    let remainder = [TestScalar::ZERO; 2];
+
+    let mut final_round_builder: FinalRoundBuilder<'_, TestScalar> =
+        FinalRoundBuilder::new(2, VecDeque::new());
+    prove_quotient_and_remainder(
+        &mut final_round_builder,
+        &alloc,
+        alloc.alloc_slice_copy(&values),
+        alloc.alloc_slice_copy(&divisors),
+        alloc.alloc_slice_copy(&quotient),
+        alloc.alloc_slice_copy(&remainder),
+    );
+    let results = RefCell::new(Vec::new());
+    run_verify_for_each_row(
+        2,
+        &final_round_builder,
+        3,
+        |verification_builder, chi_eval, evaluation_point| {
+            let accessor = indexmap! {
+                a.clone() => values.inner_product(evaluation_point),
+            };
+            results.borrow_mut().push(cast_expr.verifier_evaluate(
+                verification_builder,
+                &accessor,
+                chi_eval,
+            ));
+        },
+    );
+    assert!(results
+        .into_inner()
+        .iter()
+        .all(|result| matches!(result, Err(ProofError::VerificationError { .. }))));
+}
@@ -1,7 +1,7 @@
-use super::ProofExpr;
+use super::{DynProofExpr, ProofExpr};
 use crate::{
     base::{
-        database::{Column, ColumnField, ColumnRef, ColumnType, Table},
+        database::{presence_column_ref, Column, ColumnField, ColumnRef, ColumnType, Table},
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
@@ -21,21 +21,25 @@ pub struct ColumnExpr {
 
 impl ColumnExpr {
     /// Create a new column expression
+    #[must_use]
     pub fn new(column_ref: ColumnRef) -> Self {
         Self { column_ref }
     }
 
     /// Return the column referenced by this [`ColumnExpr`]
+    #[must_use]
     pub fn get_column_reference(&self) -> ColumnRef {
         self.column_ref.clone()
     }
 
     /// Wrap the column output name and its type within the [`ColumnField`]
+    #[must_use]
     pub fn get_column_field(&self) -> ColumnField {
         ColumnField::new(self.column_ref.column_id(), *self.column_ref.column_type())
     }
 
     /// Get the column identifier
+    #[must_use]
     pub fn column_id(&self) -> Ident {
         self.column_ref.column_id()
     }
@@ -101,4 +105,10 @@ impl ProofExpr for ColumnExpr {
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         columns.insert(self.column_ref.clone());
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        nullable_columns
+            .contains(&self.column_ref)
+            .then(|| DynProofExpr::new_column(presence_column_ref(&self.column_ref)))
+    }
 }
@@ -0,0 +1,106 @@
+use super::{scale_column, timestamp_util::timestamp_field_division, DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    utils::log,
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::PoSQLTimeField;
+use serde::{Deserialize, Serialize};
+
+/// Provable `DATE_TRUNC` expression
+///
+/// Timestamps are rounded down to the start of the field in their timezone.
+/// The floor division by the length of the field is proven, and the quotient is scaled back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateTruncExpr {
+    pub(crate) expr: Box<DynProofExpr>,
+    pub(crate) field: PoSQLTimeField,
+}
+
+impl DateTruncExpr {
+    /// Create a new `DATE_TRUNC` expression
+    pub fn new(expr: Box<DynProofExpr>, field: PoSQLTimeField) -> Self {
+        Self { expr, field }
+    }
+}
+
+impl ProofExpr for DateTruncExpr {
+    fn data_type(&self) -> ColumnType {
+        self.expr.data_type()
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
+        let values = scale_column(alloc, column, 0);
+        let Some(division) = timestamp_field_division(self.field, self.data_type()) else {
+            return Column::Scalar(values);
+        };
+        let (quotient, _) = division.result_evaluate(alloc, values);
+        Column::Scalar(alloc.alloc_slice_fill_with(quotient.len(), |i| {
+            quotient[i] * S::from(division.length()) + S::from(division.shift())
+        }))
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.date_trunc_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
+        let values = scale_column(alloc, column, 0);
+        let res = match timestamp_field_division(self.field, self.data_type()) {
+            Some(division) => {
+                let (quotient, _) = division.prover_evaluate(builder, alloc, values);
+                Column::Scalar(alloc.alloc_slice_fill_with(quotient.len(), |i| {
+                    quotient[i] * S::from(division.length()) + S::from(division.shift())
+                }))
+            }
+            None => Column::Scalar(values),
+        };
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        let Some(division) = timestamp_field_division(self.field, self.data_type()) else {
+            return Ok(eval);
+        };
+        let (quotient_eval, _) = division.verifier_evaluate(builder, eval, chi_eval)?;
+        Ok(quotient_eval * S::from(division.length()) + chi_eval * S::from(division.shift()))
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        self.expr.presence_expr(nullable_columns)
+    }
+}
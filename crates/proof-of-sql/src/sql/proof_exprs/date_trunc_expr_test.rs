@@ -0,0 +1,256 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, LiteralValue, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone};
+
+// select date_trunc('day', a) as d, date_trunc('week', a) as w from sxt.t
+#[test]
+fn we_can_prove_date_trunc_to_days_and_weeks() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [1_700_000_000_i64, -1, 0, 86_399],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Day),
+                "d",
+            ),
+            aliased_plan(
+                date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Week),
+                "w",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [1_699_920_000_i64, -86_400, 0, 0],
+        ),
+        // Weeks start on Mondays, e.g. 1969-12-29 and 2023-11-13
+        timestamptz(
+            "w",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [1_699_833_600_i64, -259_200, -259_200, -259_200],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select date_trunc('day', a) as d, date_trunc('hour', b) as h from sxt.t
+#[test]
+fn we_can_prove_date_trunc_in_a_timezone_and_a_finer_time_unit() {
+    let data = owned_table([
+        timestamptz(
+            "a",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::new(3_600),
+            [82_800_i64, 82_799, -3_600],
+        ),
+        timestamptz(
+            "b",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::utc(),
+            [5_400_123_i64, -1, 3_600_000],
+        ),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Day),
+                "d",
+            ),
+            aliased_plan(
+                date_trunc(column(&t, "b", &accessor), PoSQLTimeField::Hour),
+                "h",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    // Days start at 23:00 UTC in UTC+1
+    let expected_res = owned_table([
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::new(3_600),
+            [82_800_i64, -3_600, -3_600],
+        ),
+        timestamptz(
+            "h",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::utc(),
+            [3_600_000_i64, -3_600_000, 3_600_000],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where date_trunc('day', a + interval '1 hour') = timestamp '1970-01-02 00:00:00'
+#[test]
+fn we_can_prove_a_filter_on_the_date_trunc_of_a_timestamp_plus_an_interval() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [82_800_i64, 82_799, 86_400, 169_200],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        equal(
+            date_trunc(
+                add_interval(column(&t, "a", &accessor), "1 hour"),
+                PoSQLTimeField::Day,
+            ),
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Second,
+                PoSQLTimeZone::utc(),
+                86_400,
+            )),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [82_800_i64, 86_400],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_evaluate_date_trunc_of_literals_and_fields_shorter_than_the_time_unit() {
+    let timestamp = |time| {
+        DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::new(-3_600),
+            time,
+        ))
+    };
+    assert_eq!(
+        date_trunc(timestamp(3_599), PoSQLTimeField::Day),
+        timestamp(-82_800)
+    );
+    assert_eq!(
+        add_interval(timestamp(1), "-1 day 1 second"),
+        timestamp(-86_398)
+    );
+
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [1_i64],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    for field in [PoSQLTimeField::Millisecond, PoSQLTimeField::Second] {
+        assert_eq!(
+            date_trunc(column(&t, "a", &accessor), field),
+            column(&t, "a", &accessor)
+        );
+    }
+}
+
+#[test]
+fn we_cannot_create_unsupported_date_trunc_or_interval_expressions() {
+    let data = owned_table([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1_i64]),
+        bigint("b", [1_i64]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_date_trunc(column(&t, "b", &accessor), PoSQLTimeField::Day),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_date_trunc(column(&t, "a", &accessor), PoSQLTimeField::DayOfWeek),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_add_interval(
+            column(&t, "a", &accessor),
+            "1 millisecond".try_into().unwrap()
+        ),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_add_interval(
+            DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+                PoSQLTimeUnit::Nanosecond,
+                PoSQLTimeZone::utc(),
+                i64::MAX,
+            )),
+            "1 nanosecond".try_into().unwrap()
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+// date_trunc('minute', a)
+#[test]
+fn we_can_compute_the_correct_output_of_a_date_trunc_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [61_i64, -61, 0],
+        &alloc,
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let date_trunc_expr: DynProofExpr =
+        date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Minute);
+    let res = date_trunc_expr.result_evaluate(&alloc, &data);
+    let expected_res_scalar = [60, -120, 0]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Scalar(&expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
@@ -0,0 +1,496 @@
+use super::{
+    cast_expr::type_bounds, presence_util::all_present, prover_evaluate_equals_zero,
+    verifier_evaluate_equals_zero, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{try_divide_column_types, Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::{
+        proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_gadgets::{prover_evaluate_sign, verifier_evaluate_sign},
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use itertools::izip;
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
+
+/// Provable numerical `/` expression
+///
+/// The quotient is truncated towards zero. Division by zero results in zero.
+/// Quotients that can overflow the result type, such as `i64::MIN / -1`, are range checked.
+/// Divisors and quotients must be less than `2^125` in absolute value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DivideExpr {
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+}
+
+impl DivideExpr {
+    /// Create numerical `/` expression
+    pub fn new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>) -> Self {
+        Self { lhs, rhs }
+    }
+
+    /// The powers of ten that the numerator and denominator are scaled by
+    /// so that the truncated quotient has the scale of the result type.
+    ///
+    /// # Panics
+    /// Panics if the scaling exponents do not fit into an `i8`, which can not happen for valid result types.
+    pub(crate) fn scaling_exponents(&self) -> (i8, i8) {
+        let lhs_scale = i16::from(self.lhs.data_type().scale().unwrap_or(0));
+        let rhs_scale = i16::from(self.rhs.data_type().scale().unwrap_or(0));
+        let result_scale = i16::from(self.data_type().scale().unwrap_or(0));
+        let applied_scale = rhs_scale - lhs_scale + result_scale;
+        let (lhs_exponent, rhs_exponent) = if applied_scale >= 0 {
+            (applied_scale, 0)
+        } else {
+            (0, -applied_scale)
+        };
+        (
+            i8::try_from(lhs_exponent).expect("Scaling exponent should fit into i8"),
+            i8::try_from(rhs_exponent).expect("Scaling exponent should fit into i8"),
+        )
+    }
+
+    /// The bounds of the result type that the quotient can exceed.
+    ///
+    /// The absolute value of the quotient is at most the absolute value of the scaled numerator.
+    ///
+    /// # Panics
+    /// Panics if a bound does not fit into a scalar, which can not happen for supported types.
+    pub(crate) fn range_check_bounds<S: Scalar>(&self) -> (Option<S>, Option<S>) {
+        let (Some((lhs_min, lhs_max)), Some((result_min, result_max))) = (
+            type_bounds(self.lhs.data_type()),
+            type_bounds(self.data_type()),
+        ) else {
+            return (None, None);
+        };
+        let (lhs_exponent, _) = self.scaling_exponents();
+        let factor = BigInt::from(10).pow(u32::from(lhs_exponent.unsigned_abs()));
+        let max_abs_quotient = (-lhs_min).max(lhs_max) * factor;
+        let to_scalar =
+            |bound: BigInt| S::try_from(bound).expect("Type bounds should fit into a scalar");
+        let lower = (-&max_abs_quotient < result_min).then(|| to_scalar(result_min));
+        let upper = (max_abs_quotient > result_max).then(|| to_scalar(result_max));
+        (lower, upper)
+    }
+}
+
+impl ProofExpr for DivideExpr {
+    fn data_type(&self) -> ColumnType {
+        try_divide_column_types(self.lhs.data_type(), self.rhs.data_type())
+            .expect("Failed to divide column types")
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.result_evaluate(alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.result_evaluate(alloc, table);
+        let (lhs_exponent, rhs_exponent) = self.scaling_exponents();
+        let lhs = scale_column(alloc, lhs_column, lhs_exponent);
+        let rhs = scale_column(alloc, rhs_column, rhs_exponent);
+        let (quotient, _) = divide_and_modulo_scalars(alloc, lhs, rhs);
+        Column::Scalar(quotient)
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.divide_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column: Column<'a, S> = self.lhs.prover_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.prover_evaluate(builder, alloc, table);
+        let (lhs_exponent, rhs_exponent) = self.scaling_exponents();
+        let lhs = scale_column(alloc, lhs_column, lhs_exponent);
+        let rhs = scale_column(alloc, rhs_column, rhs_exponent);
+        let (quotient, _) = prover_evaluate_divide_and_modulo(builder, alloc, lhs, rhs);
+
+        let (lower, upper) = self.range_check_bounds::<S>();
+        // quotient - lower >= 0
+        if let Some(lower) = lower {
+            let slack = alloc.alloc_slice_fill_with(quotient.len(), |i| quotient[i] - lower);
+            prover_evaluate_sign(builder, alloc, slack);
+        }
+        // upper - quotient >= 0
+        if let Some(upper) = upper {
+            let slack = alloc.alloc_slice_fill_with(quotient.len(), |i| upper - quotient[i]);
+            prover_evaluate_sign(builder, alloc, slack);
+        }
+        let res = Column::Scalar(quotient);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let lhs_eval = self.lhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        let rhs_eval = self.rhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        let (lhs_exponent, rhs_exponent) = self.scaling_exponents();
+        let (quotient_eval, _) = verifier_evaluate_divide_and_modulo(
+            builder,
+            lhs_eval * S::pow10(lhs_exponent.unsigned_abs()),
+            rhs_eval * S::pow10(rhs_exponent.unsigned_abs()),
+            chi_eval,
+        )?;
+
+        let (lower, upper) = self.range_check_bounds::<S>();
+        // quotient - lower >= 0
+        let lower_slack_is_negative_eval = lower
+            .map(|lower| {
+                verifier_evaluate_sign(builder, quotient_eval - chi_eval * lower, chi_eval, None)
+            })
+            .transpose()?;
+        // upper - quotient >= 0
+        let upper_slack_is_negative_eval = upper
+            .map(|upper| {
+                verifier_evaluate_sign(builder, chi_eval * upper - quotient_eval, chi_eval, None)
+            })
+            .transpose()?;
+        if lower_slack_is_negative_eval.is_some_and(|e| e != S::ZERO)
+            || upper_slack_is_negative_eval.is_some_and(|e| e != S::ZERO)
+        {
+            return Err(ProofError::VerificationError {
+                error: "quotient is out of range",
+            });
+        }
+        Ok(quotient_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.lhs.presence_expr(nullable_columns),
+            self.rhs.presence_expr(nullable_columns),
+        ])
+    }
+}
+
+/// Convert a column to scalars, multiplying each value by `10^exponent`.
+pub(crate) fn scale_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: Column<'a, S>,
+    exponent: i8,
+) -> &'a [S] {
+    alloc.alloc_slice_copy(&column.to_scalar_with_scaling(exponent))
+}
+
+/// Compute the quotient and remainder of two columns of scalars, truncating the quotient towards zero.
+///
+/// Division by zero results in a quotient of zero and a remainder equal to the numerator,
+/// so that `quotient * rhs + remainder = lhs` always holds.
+///
+/// # Panics
+/// Panics if `lhs` and `rhs` are not of the same length.
+pub(crate) fn divide_and_modulo_scalars<'a, S: Scalar>(
+    alloc: &'a Bump,
+    lhs: &[S],
+    rhs: &[S],
+) -> (&'a [S], &'a [S]) {
+    assert_eq!(
+        lhs.len(),
+        rhs.len(),
+        "lhs and rhs should have the same length"
+    );
+    let quotient = alloc.alloc_slice_fill_copy(lhs.len(), S::ZERO);
+    let remainder = alloc.alloc_slice_fill_copy(lhs.len(), S::ZERO);
+    for (q, r, &l, &d) in izip!(&mut *quotient, &mut *remainder, lhs, rhs) {
+        if d.is_zero() {
+            *r = l;
+        } else {
+            let l_bigint: BigInt = l.into();
+            let d_bigint: BigInt = d.into();
+            *q = S::try_from(l_bigint / d_bigint).expect("Quotient should fit into scalar");
+            *r = l - *q * d;
+        }
+    }
+    (quotient, remainder)
+}
+
+/// Prove the quotient and remainder of `lhs` divided by `rhs`.
+///
+/// Let `z` indicate that `rhs` is zero, and let `s_lhs` and `s_rhs` be the sign bits of `lhs` and `rhs`.
+/// The argument consists of
+/// 1. `quotient * rhs + remainder - lhs = 0`
+/// 2. `z * quotient = 0`, so that dividing by zero results in a remainder of `lhs`
+/// 3. `|remainder| = remainder * (1 - 2 * s_lhs) >= 0`, so that the remainder has the sign of `lhs`
+/// 4. `|rhs| - |remainder| - 1 + z * (|remainder| + 1) >= 0`, so that `|remainder| < |rhs|` when `rhs` is nonzero
+/// 5. `2^125 - 1 - |quotient| >= 0` and `2^125 - 1 - |rhs| >= 0`
+///
+/// The inequalities are proven with the sign gadget, which uses only final round MLEs
+/// and bounds every value it is applied to by `2^128` in absolute value.
+/// By 5, `|quotient * rhs + remainder - lhs| < 2^251`, which is less than the modulus of either scalar field,
+/// so 1 holds over the integers and not just modulo the field.
+/// Together with 2-4 this uniquely determines the truncated quotient and the remainder.
+/// As a consequence, divisions whose divisor or quotient is at least `2^125` in absolute value can not be proven.
+pub(crate) fn prover_evaluate_divide_and_modulo<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    lhs: &'a [S],
+    rhs: &'a [S],
+) -> (&'a [S], &'a [S]) {
+    let (quotient, remainder) = divide_and_modulo_scalars(alloc, lhs, rhs);
+    prove_quotient_and_remainder(builder, alloc, lhs, rhs, quotient, remainder);
+    (quotient, remainder)
+}
+
+/// Prove that `quotient` and `remainder` are the quotient and remainder of `lhs` divided by `rhs`.
+///
+/// See [`prover_evaluate_divide_and_modulo`].
+pub(super) fn prove_quotient_and_remainder<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    lhs: &'a [S],
+    rhs: &'a [S],
+    quotient: &'a [S],
+    remainder: &'a [S],
+) {
+    let table_length = lhs.len();
+    builder.produce_intermediate_mle(quotient);
+    builder.produce_intermediate_mle(remainder);
+
+    // subpolynomial: quotient * rhs + remainder - lhs
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(quotient), Box::new(rhs)]),
+            (S::one(), vec![Box::new(remainder)]),
+            (-S::one(), vec![Box::new(lhs)]),
+        ],
+    );
+
+    // rhs_is_zero
+    let rhs_is_zero = prover_evaluate_equals_zero(table_length, builder, alloc, rhs);
+
+    // subpolynomial: rhs_is_zero * quotient
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![(S::one(), vec![Box::new(rhs_is_zero), Box::new(quotient)])],
+    );
+
+    // abs_remainder
+    let lhs_sign = prover_evaluate_sign(builder, alloc, lhs);
+    let abs_remainder = prover_evaluate_signed_abs(builder, alloc, lhs_sign, remainder);
+
+    // abs_rhs
+    let rhs_sign = prover_evaluate_sign(builder, alloc, rhs);
+    let abs_rhs = prover_evaluate_signed_abs(builder, alloc, rhs_sign, rhs);
+
+    // rhs_is_zero_times_abs_remainder
+    let rhs_is_zero_times_abs_remainder: &'a [S] = alloc.alloc_slice_fill_with(table_length, |i| {
+        if rhs_is_zero[i] {
+            abs_remainder[i]
+        } else {
+            S::ZERO
+        }
+    });
+    builder.produce_intermediate_mle(rhs_is_zero_times_abs_remainder);
+
+    // subpolynomial: rhs_is_zero_times_abs_remainder - rhs_is_zero * abs_remainder
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(rhs_is_zero_times_abs_remainder)]),
+            (
+                -S::one(),
+                vec![Box::new(rhs_is_zero), Box::new(abs_remainder)],
+            ),
+        ],
+    );
+
+    // |remainder| >= 0
+    prover_evaluate_sign(builder, alloc, abs_remainder);
+
+    // |rhs| - |remainder| - 1 + z * (|remainder| + 1) >= 0
+    let slack: &'a [S] = alloc.alloc_slice_fill_with(table_length, |i| {
+        abs_rhs[i] - abs_remainder[i] - S::ONE
+            + rhs_is_zero_times_abs_remainder[i]
+            + S::from(rhs_is_zero[i])
+    });
+    prover_evaluate_sign(builder, alloc, slack);
+
+    // abs_quotient
+    let quotient_sign = prover_evaluate_sign(builder, alloc, quotient);
+    let abs_quotient = prover_evaluate_signed_abs(builder, alloc, quotient_sign, quotient);
+
+    // 2^125 - 1 - |quotient| >= 0 and 2^125 - 1 - |rhs| >= 0
+    prover_evaluate_operand_bound(builder, alloc, abs_quotient);
+    prover_evaluate_operand_bound(builder, alloc, abs_rhs);
+}
+
+/// Verify the quotient and remainder of `lhs` divided by `rhs`.
+///
+/// Returns the evaluations of the quotient and the remainder.
+///
+/// See [`prover_evaluate_divide_and_modulo`].
+pub(crate) fn verifier_evaluate_divide_and_modulo<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    lhs_eval: S,
+    rhs_eval: S,
+    chi_eval: S,
+) -> Result<(S, S), ProofError> {
+    let quotient_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let remainder_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // subpolynomial: quotient * rhs + remainder - lhs
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        quotient_eval * rhs_eval + remainder_eval - lhs_eval,
+        2,
+    )?;
+
+    // rhs_is_zero
+    let rhs_is_zero_eval = verifier_evaluate_equals_zero(builder, rhs_eval, chi_eval)?;
+
+    // subpolynomial: rhs_is_zero * quotient
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        rhs_is_zero_eval * quotient_eval,
+        2,
+    )?;
+
+    // abs_remainder
+    let lhs_sign_eval = verifier_evaluate_sign(builder, lhs_eval, chi_eval, None)?;
+    let abs_remainder_eval = verifier_evaluate_signed_abs(builder, lhs_sign_eval, remainder_eval)?;
+
+    // abs_rhs
+    let rhs_sign_eval = verifier_evaluate_sign(builder, rhs_eval, chi_eval, None)?;
+    let abs_rhs_eval = verifier_evaluate_signed_abs(builder, rhs_sign_eval, rhs_eval)?;
+
+    // rhs_is_zero_times_abs_remainder
+    let rhs_is_zero_times_abs_remainder_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // subpolynomial: rhs_is_zero_times_abs_remainder - rhs_is_zero * abs_remainder
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        rhs_is_zero_times_abs_remainder_eval - rhs_is_zero_eval * abs_remainder_eval,
+        2,
+    )?;
+
+    // |remainder| >= 0
+    let abs_remainder_is_negative_eval =
+        verifier_evaluate_sign(builder, abs_remainder_eval, chi_eval, None)?;
+
+    // |rhs| - |remainder| - 1 + z * (|remainder| + 1) >= 0
+    let slack_eval = abs_rhs_eval - abs_remainder_eval - chi_eval
+        + rhs_is_zero_times_abs_remainder_eval
+        + rhs_is_zero_eval;
+    let slack_is_negative_eval = verifier_evaluate_sign(builder, slack_eval, chi_eval, None)?;
+
+    if abs_remainder_is_negative_eval != S::ZERO || slack_is_negative_eval != S::ZERO {
+        return Err(ProofError::VerificationError {
+            error: "remainder is out of range",
+        });
+    }
+
+    // abs_quotient
+    let quotient_sign_eval = verifier_evaluate_sign(builder, quotient_eval, chi_eval, None)?;
+    let abs_quotient_eval =
+        verifier_evaluate_signed_abs(builder, quotient_sign_eval, quotient_eval)?;
+
+    // 2^125 - 1 - |quotient| >= 0 and 2^125 - 1 - |rhs| >= 0
+    verifier_evaluate_operand_bound(builder, abs_quotient_eval, chi_eval)?;
+    verifier_evaluate_operand_bound(builder, abs_rhs_eval, chi_eval)?;
+
+    Ok((quotient_eval, remainder_eval))
+}
+
+/// Prove `abs = values * (1 - 2 * sign)`, where `sign` is the sign bit of some column.
+fn prover_evaluate_signed_abs<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    sign: &'a [bool],
+    values: &'a [S],
+) -> &'a [S] {
+    let abs: &'a [S] =
+        alloc.alloc_slice_fill_with(
+            values.len(),
+            |i| if sign[i] { -values[i] } else { values[i] },
+        );
+    builder.produce_intermediate_mle(abs);
+
+    // subpolynomial: abs - values + 2 * sign * values
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(abs)]),
+            (-S::one(), vec![Box::new(values)]),
+            (S::TWO, vec![Box::new(sign), Box::new(values)]),
+        ],
+    );
+    abs
+}
+
+/// Verify `abs = values * (1 - 2 * sign)`. See [`prover_evaluate_signed_abs`].
+fn verifier_evaluate_signed_abs<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    sign_eval: S,
+    values_eval: S,
+) -> Result<S, ProofError> {
+    let abs_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // subpolynomial: abs - values + 2 * sign * values
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        abs_eval - values_eval + S::TWO * sign_eval * values_eval,
+        2,
+    )?;
+    Ok(abs_eval)
+}
+
+/// The largest absolute value of the quotient and of the divisor in [`prover_evaluate_divide_and_modulo`].
+fn max_operand<S: Scalar>() -> S {
+    S::from((1i128 << 125) - 1)
+}
+
+/// Prove `2^125 - 1 - abs >= 0`.
+fn prover_evaluate_operand_bound<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    abs: &'a [S],
+) {
+    let slack: &'a [S] = alloc.alloc_slice_fill_with(abs.len(), |i| max_operand::<S>() - abs[i]);
+    prover_evaluate_sign(builder, alloc, slack);
+}
+
+/// Verify `2^125 - 1 - abs >= 0`. See [`prover_evaluate_operand_bound`].
+fn verifier_evaluate_operand_bound<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    abs_eval: S,
+    chi_eval: S,
+) -> Result<(), ProofError> {
+    let slack_eval = max_operand::<S>() * chi_eval - abs_eval;
+    if verifier_evaluate_sign(builder, slack_eval, chi_eval, None)? != S::ZERO {
+        return Err(ProofError::VerificationError {
+            error: "quotient or divisor is out of range",
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,313 @@
+use super::{
+    divide_and_modulo_scalars, divide_expr::prove_quotient_and_remainder,
+    verifier_evaluate_divide_and_modulo,
+};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor,
+        },
+        polynomial::MultilinearExtension,
+        proof::ProofError,
+        scalar::{test_scalar::TestScalar, Curve25519Scalar, Scalar},
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{
+            exercise_verification, mock_verification_builder::run_verify_for_each_row,
+            FinalRoundBuilder, QueryError, VerifiableQueryResult,
+        },
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use bumpalo::Bump;
+use core::cell::RefCell;
+use num_traits::Inv;
+use std::collections::VecDeque;
+
+// select a, b, a / b as q from sxt.t where a / b >= 1
+#[test]
+fn we_can_prove_a_typical_divide_query() {
+    let data = owned_table([
+        smallint("a", [7_i16, -7, 7, -7, 0, 5]),
+        int("b", [2_i32, 2, -2, -2, 3, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            col_expr_plan(&t, "a", &accessor),
+            col_expr_plan(&t, "b", &accessor),
+            aliased_plan(
+                divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "q",
+            ),
+        ],
+        tab(&t),
+        gte(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            const_int(1),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        smallint("a", [7_i16, -7]),
+        int("b", [2_i32, -2]),
+        int("q", [3_i32, 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q from sxt.t
+#[test]
+fn we_can_prove_truncated_division_including_division_by_zero() {
+    let data = owned_table([
+        bigint("a", [7_i64, -7, 7, -7, 0, 5, i64::MIN, i64::MAX]),
+        bigint("b", [2_i64, 2, -2, -2, 3, 0, 1, -1]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "q",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("q", [3_i64, -3, -3, 3, 0, 0, i64::MIN, -i64::MAX])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q from sxt.t
+#[test]
+fn we_can_prove_decimal_division_with_the_result_scale() {
+    let data = owned_table([decimal75("a", 5, 2, [1000_i64, -250]), int("b", [3_i32, 2])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "q",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75(
+        "q",
+        16,
+        13,
+        [33_333_333_333_333_i64, -12_500_000_000_000],
+    )]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q from sxt.t
+#[test]
+fn we_cannot_verify_a_quotient_that_overflows_the_result_type() {
+    let data = owned_table([smallint("a", [i16::MIN]), smallint("b", [-1_i16])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast: DynProofPlan = filter(
+        vec![aliased_plan(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "q",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "quotient is out of range"
+            }
+        })
+    ));
+}
+
+// select a from sxt.t where a / b >= 1
+#[test]
+fn we_cannot_verify_a_quotient_that_overflows_the_result_type_in_a_where_clause() {
+    let data = owned_table([bigint("a", [i64::MIN, 6]), bigint("b", [-1_i64, 3])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast: DynProofPlan = filter(
+        vec![col_expr_plan(&t, "a", &accessor)],
+        tab(&t),
+        gte(
+            divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            const_bigint(1),
+        ),
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "quotient is out of range"
+            }
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_divide_non_numeric_or_scalar_types() {
+    let data = owned_table([
+        varchar("a", ["ab"]),
+        bigint("b", [1_i64]),
+        scalar("c", [1_i64]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_divide(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_divide(column(&t, "b", &accessor), column(&t, "c", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+// a / (b - 1)
+#[test]
+fn we_can_compute_the_correct_output_of_a_divide_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [9_i64, -9, 4, 0], &alloc),
+        borrowed_int("b", [3_i32, 5, 1, -1], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let arithmetic_expr: DynProofExpr = divide(
+        column(&t, "a", &accessor),
+        subtract(column(&t, "b", &accessor), const_int(1)),
+    );
+    let res = arithmetic_expr.result_evaluate(&alloc, &data);
+    let expected_res_scalar = [4, -2, 0, 0]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Scalar(&expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
+
+/// Prove `quotient` and `remainder` for `lhs / rhs` and verify them row by row.
+fn verify_quotient_and_remainder(
+    lhs: &[TestScalar],
+    rhs: &[TestScalar],
+    quotient: &[TestScalar],
+    remainder: &[TestScalar],
+) -> Vec<Result<(TestScalar, TestScalar), ProofError>> {
+    let alloc = Bump::new();
+    let table_length = lhs.len();
+    let mut final_round_builder: FinalRoundBuilder<'_, TestScalar> =
+        FinalRoundBuilder::new(table_length, VecDeque::new());
+    prove_quotient_and_remainder(
+        &mut final_round_builder,
+        &alloc,
+        alloc.alloc_slice_copy(lhs),
+        alloc.alloc_slice_copy(rhs),
+        alloc.alloc_slice_copy(quotient),
+        alloc.alloc_slice_copy(remainder),
+    );
+    let results = RefCell::new(Vec::new());
+    let verification_builder = run_verify_for_each_row(
+        table_length,
+        &final_round_builder,
+        3,
+        |verification_builder, chi_eval, evaluation_point| {
+            results
+                .borrow_mut()
+                .push(verifier_evaluate_divide_and_modulo(
+                    verification_builder,
+                    lhs.inner_product(evaluation_point),
+                    rhs.inner_product(evaluation_point),
+                    chi_eval,
+                ));
+        },
+    );
+    assert!(verification_builder
+        .get_identity_results()
+        .iter()
+        .all(|row| row.iter().all(|constraint| *constraint)));
+    results.into_inner()
+}
+
+#[test]
+fn we_can_verify_an_honest_quotient_and_remainder() {
+    let alloc = Bump::new();
+    let lhs = [7, -7, 7, -7, 0, 5].map(TestScalar::from);
+    let rhs = [2, 2, -2, -2, 3, 0].map(TestScalar::from);
+    let (quotient, remainder) = divide_and_modulo_scalars(&alloc, &lhs, &rhs);
+    let results = verify_quotient_and_remainder(&lhs, &rhs, quotient, remainder);
+    assert!(results.iter().all(Result::is_ok));
+}
+
+#[test]
+fn we_cannot_verify_a_tampered_quotient_whose_remainder_is_in_range() {
+    // 7 = q * 2 + 0 holds in the field for q = 7 / 2, which is not an integer
+    let lhs = [TestScalar::from(7)];
+    let rhs = [TestScalar::TWO];
+    let quotient = [TestScalar::from(7) * TestScalar::TWO.inv().unwrap()];
+    let remainder = [TestScalar::ZERO];
+    let results = verify_quotient_and_remainder(&lhs, &rhs, &quotient, &remainder);
+    assert!(matches!(
+        results[0],
+        Err(ProofError::VerificationError { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_tampered_remainder_that_is_too_large() {
+    // 7 = 2 * 2 + 3, but 3 is not less than 2
+    let lhs = [TestScalar::from(7)];
+    let rhs = [TestScalar::TWO];
+    let quotient = [TestScalar::TWO];
+    let remainder = [TestScalar::from(3)];
+    let results = verify_quotient_and_remainder(&lhs, &rhs, &quotient, &remainder);
+    assert!(matches!(
+        results[0],
+        Err(ProofError::VerificationError {
+            error: "remainder is out of range"
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_tampered_remainder_with_the_wrong_sign() {
+    // -7 = -4 * 2 + 1, but the remainder must have the sign of -7
+    let lhs = [TestScalar::from(-7)];
+    let rhs = [TestScalar::TWO];
+    let quotient = [TestScalar::from(-4)];
+    let remainder = [TestScalar::ONE];
+    let results = verify_quotient_and_remainder(&lhs, &rhs, &quotient, &remainder);
+    assert!(matches!(
+        results[0],
+        Err(ProofError::VerificationError {
+            error: "remainder is out of range"
+        })
+    ));
+}
@@ -1,10 +1,18 @@
 use super::{
-    AddSubtractExpr, AggregateExpr, AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr,
-    MultiplyExpr, NotExpr, OrExpr, ProofExpr,
+    timestamp_util::{field_period, timestamp_field_division, FloorDivision},
+    AddSubtractExpr, AggregateExpr, AndExpr, BetweenExpr, CaseExpr, CastExpr, ColumnExpr,
+    DateTruncExpr, DivideExpr, EqualsExpr, ExtractExpr, InListExpr, InequalityExpr, LiteralExpr,
+    ModuloExpr, MultiplyExpr, NotExpr, OrExpr, ProofExpr,
 };
 use crate::{
     base::{
-        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        database::{
+            try_add_interval_column_type, try_case_column_types, try_cast_column_types,
+            try_date_trunc_column_type, try_extract_column_type, varchar_key_column_ref,
+            varchar_key_limbs, varchar_length, varchar_length_column_ref,
+            varchar_prefix_key_limb_bounds, Column, ColumnRef, ColumnType, LiteralValue, Table,
+            VARCHAR_KEY_PREFIX_BYTES,
+        },
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
@@ -14,10 +22,13 @@ use crate::{
         proof::{FinalRoundBuilder, VerificationBuilder},
     },
 };
-use alloc::{boxed::Box, string::ToString};
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
 use bumpalo::Bump;
 use core::fmt::Debug;
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use proof_of_sql_parser::{
+    intermediate_ast::AggregationOperator,
+    posql_time::{PoSQLInterval, PoSQLTimeField},
+};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::BinaryOperator;
 
@@ -39,10 +50,26 @@ pub enum DynProofExpr {
     Equals(EqualsExpr),
     /// Provable AST expression for an inequality expression
     Inequality(InequalityExpr),
+    /// Provable `IN` expression over a list of literals
+    InList(InListExpr),
+    /// Provable `BETWEEN` expression
+    Between(BetweenExpr),
     /// Provable numeric `+` / `-` expression
     AddSubtract(AddSubtractExpr),
     /// Provable numeric `*` expression
     Multiply(MultiplyExpr),
+    /// Provable numeric `/` expression
+    Divide(DivideExpr),
+    /// Provable numeric `%` expression
+    Modulo(ModuloExpr),
+    /// Provable `CAST` expression
+    Cast(CastExpr),
+    /// Provable `CASE` expression
+    Case(CaseExpr),
+    /// Provable `DATE_TRUNC` expression
+    DateTrunc(DateTruncExpr),
+    /// Provable `EXTRACT` expression
+    Extract(ExtractExpr),
     /// Provable aggregate expression
     Aggregate(AggregateExpr),
 }
@@ -96,9 +123,10 @@ impl DynProofExpr {
         let lhs_datatype = lhs.data_type();
         let rhs_datatype = rhs.data_type();
         if type_check_binary_operation(lhs_datatype, rhs_datatype, &BinaryOperator::Lt) {
+            check_varchar_comparison(&lhs, &rhs)?;
             Ok(Self::Inequality(InequalityExpr::new(
-                Box::new(lhs),
-                Box::new(rhs),
+                Box::new(lhs.try_into_varchar_key()?),
+                Box::new(rhs.try_into_varchar_key()?),
                 is_lt,
             )))
         } else {
@@ -109,6 +137,112 @@ impl DynProofExpr {
         }
     }
 
+    /// Create a new `IN` expression over a list of literals
+    pub fn try_new_in_list(expr: DynProofExpr, list: Vec<LiteralValue>) -> ConversionResult<Self> {
+        if list.is_empty() {
+            return Err(ConversionError::InvalidExpression {
+                expression: "IN list must not be empty".to_string(),
+            });
+        }
+        let datatype = expr.data_type();
+        for value in &list {
+            let value_datatype = value.column_type();
+            if !type_check_binary_operation(datatype, value_datatype, &BinaryOperator::Eq) {
+                return Err(ConversionError::DataTypeMismatch {
+                    left_type: datatype.to_string(),
+                    right_type: value_datatype.to_string(),
+                });
+            }
+        }
+        Ok(Self::InList(InListExpr::new(Box::new(expr), list)))
+    }
+
+    /// Create a new `BETWEEN` expression
+    pub fn try_new_between(
+        expr: DynProofExpr,
+        low: DynProofExpr,
+        high: DynProofExpr,
+    ) -> ConversionResult<Self> {
+        let datatype = expr.data_type();
+        for bound_datatype in [low.data_type(), high.data_type()] {
+            if !type_check_binary_operation(datatype, bound_datatype, &BinaryOperator::Lt) {
+                return Err(ConversionError::DataTypeMismatch {
+                    left_type: datatype.to_string(),
+                    right_type: bound_datatype.to_string(),
+                });
+            }
+        }
+        check_varchar_comparison(&expr, &low)?;
+        check_varchar_comparison(&expr, &high)?;
+        Ok(Self::Between(BetweenExpr::new(
+            Box::new(expr.try_into_varchar_key()?),
+            Box::new(low.try_into_varchar_key()?),
+            Box::new(high.try_into_varchar_key()?),
+        )))
+    }
+
+    /// Create a new `LENGTH` expression, which is the number of characters of a string
+    pub fn try_new_length(expr: DynProofExpr) -> ConversionResult<Self> {
+        expr.check_data_type(ColumnType::VarChar)?;
+        match expr {
+            Self::Column(column) => Ok(Self::new_column(varchar_length_column_ref(
+                &column.column_ref,
+            ))),
+            Self::Literal(LiteralExpr {
+                value: LiteralValue::VarChar(value),
+            }) => Ok(Self::new_literal(LiteralValue::BigInt(varchar_length(
+                &value,
+            )))),
+            _ => Err(unsupported_string_operation()),
+        }
+    }
+
+    /// Create a new `starts_with` expression
+    ///
+    /// The prefix may have at most [`VARCHAR_KEY_PREFIX_BYTES`] bytes.
+    pub fn try_new_starts_with(expr: DynProofExpr, prefix: &str) -> ConversionResult<Self> {
+        expr.check_data_type(ColumnType::VarChar)?;
+        let (low, high) = varchar_prefix_key_limb_bounds(prefix).ok_or_else(|| {
+            ConversionError::InvalidExpression {
+                expression: format!(
+                    "prefixes of more than {VARCHAR_KEY_PREFIX_BYTES} bytes are not supported"
+                ),
+            }
+        })?;
+        if let Self::Literal(LiteralExpr {
+            value: LiteralValue::VarChar(value),
+        }) = &expr
+        {
+            return Ok(Self::new_literal(LiteralValue::Boolean(
+                value.starts_with(prefix),
+            )));
+        }
+        Ok(Self::Between(BetweenExpr::new(
+            Box::new(expr.try_into_varchar_key()?),
+            Box::new(Self::new_literal(LiteralValue::Scalar(low))),
+            Box::new(Self::new_literal(LiteralValue::Scalar(high))),
+        )))
+    }
+
+    /// Create a new `LIKE` expression
+    ///
+    /// Only patterns without wildcards and patterns of the form `prefix%` are supported.
+    pub fn try_new_like(expr: DynProofExpr, pattern: &str) -> ConversionResult<Self> {
+        let is_literal = |text: &str| !text.contains(['%', '_', '\\']);
+        if is_literal(pattern) {
+            return Self::try_new_equals(
+                expr,
+                Self::new_literal(LiteralValue::VarChar(pattern.to_string())),
+            );
+        }
+        match pattern.strip_suffix('%') {
+            Some(prefix) if is_literal(prefix) => Self::try_new_starts_with(expr, prefix),
+            _ => Err(ConversionError::UnsupportedOperation {
+                message: format!("LIKE pattern '{pattern}' is not of the form 'prefix%'"),
+            }),
+        }
+    }
+
     /// Create a new add expression
     pub fn try_new_add(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
         let lhs_datatype = lhs.data_type();
@@ -162,12 +296,213 @@ impl DynProofExpr {
         }
     }
 
+    /// Create a new divide expression
+    pub fn try_new_divide(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        if type_check_binary_operation(lhs_datatype, rhs_datatype, &BinaryOperator::Divide) {
+            Ok(Self::Divide(DivideExpr::new(Box::new(lhs), Box::new(rhs))))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: lhs_datatype.to_string(),
+                right_type: rhs_datatype.to_string(),
+            })
+        }
+    }
+
+    /// Create a new modulo expression
+    pub fn try_new_modulo(lhs: DynProofExpr, rhs: DynProofExpr) -> ConversionResult<Self> {
+        let lhs_datatype = lhs.data_type();
+        let rhs_datatype = rhs.data_type();
+        if type_check_binary_operation(lhs_datatype, rhs_datatype, &BinaryOperator::Modulo) {
+            Ok(Self::Modulo(ModuloExpr::new(Box::new(lhs), Box::new(rhs))))
+        } else {
+            Err(ConversionError::DataTypeMismatch {
+                left_type: lhs_datatype.to_string(),
+                right_type: rhs_datatype.to_string(),
+            })
+        }
+    }
+
+    /// Create a new `CAST` expression
+    ///
+    /// Casting an expression to its own type returns the expression unchanged.
+    pub fn try_new_cast(expr: DynProofExpr, to_type: ColumnType) -> ConversionResult<Self> {
+        try_cast_column_types(expr.data_type(), to_type)?;
+        if expr.data_type() == to_type {
+            Ok(expr)
+        } else {
+            Ok(Self::Cast(CastExpr::new(Box::new(expr), to_type)))
+        }
+    }
+
+    /// Create a new `CASE` expression
+    ///
+    /// The results of all branches are cast to their common data type.
+    pub fn try_new_case(
+        when_then: Vec<(DynProofExpr, DynProofExpr)>,
+        else_expr: DynProofExpr,
+    ) -> ConversionResult<Self> {
+        if when_then.is_empty() {
+            return Ok(else_expr);
+        }
+        let data_type = when_then
+            .iter()
+            .try_fold(else_expr.data_type(), |data_type, (_, result)| {
+                try_case_column_types(data_type, result.data_type())
+            })?;
+        let when_then = when_then
+            .into_iter()
+            .map(|(condition, result)| {
+                condition.check_data_type(ColumnType::Boolean)?;
+                Ok((condition, Self::try_new_cast(result, data_type)?))
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        let else_expr = Self::try_new_cast(else_expr, data_type)?;
+        Ok(Self::Case(CaseExpr::new(when_then, Box::new(else_expr))))
+    }
+
+    /// Create a new expression adding an interval to a timestamp
+    ///
+    /// The timestamp is cast to its count of time units, which the interval is added to.
+    pub fn try_new_add_interval(
+        expr: DynProofExpr,
+        interval: PoSQLInterval,
+    ) -> ConversionResult<Self> {
+        let data_type = try_add_interval_column_type(expr.data_type(), interval)?;
+        let ColumnType::TimestampTZ(timeunit, timezone) = data_type else {
+            unreachable!("Intervals can only be added to timestamps")
+        };
+        let count = interval
+            .count_of(timeunit)
+            .expect("The interval is a whole number of time units");
+        if let Self::Literal(LiteralExpr {
+            value: LiteralValue::TimeStampTZ(_, _, time),
+        }) = &expr
+        {
+            let time =
+                time.checked_add(count)
+                    .ok_or_else(|| ConversionError::InvalidExpression {
+                        expression: format!(
+                            "timestamp overflows when adding INTERVAL '{interval}'"
+                        ),
+                    })?;
+            return Ok(Self::new_literal(LiteralValue::TimeStampTZ(
+                timeunit, timezone, time,
+            )));
+        }
+        Self::try_new_cast(
+            Self::try_new_add(
+                Self::try_new_cast(expr, ColumnType::BigInt)?,
+                Self::new_literal(LiteralValue::BigInt(count)),
+            )?,
+            data_type,
+        )
+    }
+
+    /// Create a new `DATE_TRUNC` expression
+    ///
+    /// Truncating timestamps to a field no longer than their time unit returns them unchanged.
+    pub fn try_new_date_trunc(expr: DynProofExpr, field: PoSQLTimeField) -> ConversionResult<Self> {
+        let data_type = try_date_trunc_column_type(field, expr.data_type())?;
+        let Some(division) =
+            timestamp_field_division(field, data_type).filter(|division| division.length() > 1)
+        else {
+            return Ok(expr);
+        };
+        if let Self::Literal(LiteralExpr {
+            value: LiteralValue::TimeStampTZ(timeunit, timezone, time),
+        }) = &expr
+        {
+            let (quotient, _) = division.divide((*time).into());
+            let time = quotient * i128::from(division.length()) + i128::from(division.shift());
+            return Ok(Self::new_literal(LiteralValue::TimeStampTZ(
+                *timeunit,
+                *timezone,
+                i64::try_from(time).map_err(|_| ConversionError::InvalidExpression {
+                    expression: format!("timestamp overflows when truncated to {field}"),
+                })?,
+            )));
+        }
+        Ok(Self::DateTrunc(DateTruncExpr::new(Box::new(expr), field)))
+    }
+
+    /// Create a new `EXTRACT` expression
+    pub fn try_new_extract(expr: DynProofExpr, field: PoSQLTimeField) -> ConversionResult<Self> {
+        try_extract_column_type(field, expr.data_type())?;
+        if let Self::Literal(LiteralExpr {
+            value: LiteralValue::TimeStampTZ(_, _, time),
+        }) = &expr
+        {
+            let division = timestamp_field_division(field, expr.data_type())
+                .expect("Extracted fields should be at least a second long");
+            let (quotient, _) = division.divide((*time).into());
+            let value = match field_period(field) {
+                Some(period) => FloorDivision::new(period, 0).divide(quotient).1,
+                None => quotient,
+            };
+            return Ok(Self::new_literal(LiteralValue::BigInt(
+                i64::try_from(value).expect("Extracted fields fit into a bigint"),
+            )));
+        }
+        Ok(Self::Extract(ExtractExpr::new(Box::new(expr), field)))
+    }
+
     /// Create a new aggregate expression
     #[must_use]
     pub fn new_aggregate(op: AggregationOperator, expr: DynProofExpr) -> Self {
         Self::Aggregate(AggregateExpr::new(op, Box::new(expr)))
     }
 
+    /// Create an `IS NULL` expression
+    ///
+    /// `nullable_columns` are the columns that have a presence column.
+    #[must_use]
+    pub fn new_is_null(expr: &DynProofExpr, nullable_columns: &IndexSet<ColumnRef>) -> Self {
+        expr.presence_expr(nullable_columns).map_or_else(
+            || Self::new_literal(LiteralValue::Boolean(false)),
+            |presence| Self::Not(NotExpr::new(Box::new(presence))),
+        )
+    }
+
+    /// Create an `IS NOT NULL` expression
+    ///
+    /// `nullable_columns` are the columns that have a presence column.
+    #[must_use]
+    pub fn new_is_not_null(expr: &DynProofExpr, nullable_columns: &IndexSet<ColumnRef>) -> Self {
+        expr.presence_expr(nullable_columns)
+            .unwrap_or_else(|| Self::new_literal(LiteralValue::Boolean(true)))
+    }
+
+    /// Replaces a `VarChar` column or literal with its key, which preserves the order of strings.
+    ///
+    /// Keys only preserve the order of strings when one of them has at most
+    /// [`VARCHAR_KEY_PREFIX_BYTES`] bytes, so longer literals are rejected.
+    /// Expressions of other types are returned unchanged.
+    fn try_into_varchar_key(self) -> ConversionResult<Self> {
+        match self {
+            Self::Column(column) if column.data_type() == ColumnType::VarChar => {
+                Ok(Self::new_column(varchar_key_column_ref(&column.column_ref)))
+            }
+            Self::Literal(LiteralExpr {
+                value: LiteralValue::VarChar(value),
+            }) if value.len() > VARCHAR_KEY_PREFIX_BYTES => {
+                Err(ConversionError::InvalidExpression {
+                    expression: format!(
+                        "string literals over {VARCHAR_KEY_PREFIX_BYTES} bytes can not be compared"
+                    ),
+                })
+            }
+            Self::Literal(LiteralExpr {
+                value: LiteralValue::VarChar(value),
+            }) => Ok(Self::new_literal(LiteralValue::Scalar(varchar_key_limbs(
+                &value,
+            )))),
+            _ if self.data_type() == ColumnType::VarChar => Err(unsupported_string_operation()),
+            _ => Ok(self),
+        }
+    }
+
     /// Check that the plan has the correct data type
     fn check_data_type(&self, data_type: ColumnType) -> ConversionResult<()> {
         if self.data_type() == data_type {
@@ -180,3 +515,35 @@ impl DynProofExpr {
         }
     }
 }
+
+/// Check that a comparison of two `VarChar` expressions has a literal on at least one side.
+///
+/// Two strings that are both longer than [`VARCHAR_KEY_PREFIX_BYTES`] bytes can not be compared
+/// by their keys, which is only ruled out when one side is a literal.
+fn check_varchar_comparison(lhs: &DynProofExpr, rhs: &DynProofExpr) -> ConversionResult<()> {
+    let is_varchar_literal = |expr: &DynProofExpr| {
+        matches!(
+            expr,
+            DynProofExpr::Literal(LiteralExpr {
+                value: LiteralValue::VarChar(_)
+            })
+        )
+    };
+    if lhs.data_type() == ColumnType::VarChar
+        && rhs.data_type() == ColumnType::VarChar
+        && !is_varchar_literal(lhs)
+        && !is_varchar_literal(rhs)
+    {
+        return Err(ConversionError::InvalidExpression {
+            expression: "comparisons of two string columns are not supported".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn unsupported_string_operation() -> ConversionError {
+    ConversionError::UnsupportedOperation {
+        message: "string operations beyond equality are only supported on columns and literals"
+            .to_string(),
+    }
+}
@@ -1,4 +1,7 @@
-use super::{scale_and_add_subtract_eval, scale_and_subtract, DynProofExpr, ProofExpr};
+use super::{
+    presence_util::all_present, scale_and_add_subtract_eval, scale_and_subtract, DynProofExpr,
+    ProofExpr,
+};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -100,6 +103,13 @@ impl ProofExpr for EqualsExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.lhs.presence_expr(nullable_columns),
+            self.rhs.presence_expr(nullable_columns),
+        ])
+    }
 }
 
 #[allow(
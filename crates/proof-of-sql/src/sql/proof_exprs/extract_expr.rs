@@ -0,0 +1,124 @@
+use super::{
+    scale_column,
+    timestamp_util::{field_period, timestamp_field_division, FloorDivision},
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    utils::log,
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::PoSQLTimeField;
+use serde::{Deserialize, Serialize};
+
+/// Provable `EXTRACT` expression
+///
+/// The timestamps are floor divided by the length of the field in their timezone.
+/// For fields that repeat, e.g. hours, the quotient is then taken modulo the number of values
+/// of the field, e.g. 24.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractExpr {
+    pub(crate) expr: Box<DynProofExpr>,
+    pub(crate) field: PoSQLTimeField,
+}
+
+impl ExtractExpr {
+    /// Create a new `EXTRACT` expression
+    pub fn new(expr: Box<DynProofExpr>, field: PoSQLTimeField) -> Self {
+        Self { expr, field }
+    }
+
+    /// The division by the length of the field and the division by its period, if any
+    ///
+    /// # Panics
+    /// Panics if the field is shorter than the time unit, which can not happen for valid fields.
+    pub(crate) fn divisions(&self) -> (FloorDivision, Option<FloorDivision>) {
+        (
+            timestamp_field_division(self.field, self.expr.data_type())
+                .expect("Extracted fields should be at least a second long"),
+            field_period(self.field).map(|period| FloorDivision::new(period, 0)),
+        )
+    }
+}
+
+impl ProofExpr for ExtractExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::BigInt
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column: Column<'a, S> = self.expr.result_evaluate(alloc, table);
+        let values = scale_column(alloc, column, 0);
+        let (division, period_division) = self.divisions();
+        let (quotient, _) = division.result_evaluate(alloc, values);
+        let values = match period_division {
+            Some(period_division) => period_division.result_evaluate(alloc, quotient).1,
+            None => quotient,
+        };
+        Column::Scalar(values)
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.extract_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let column: Column<'a, S> = self.expr.prover_evaluate(builder, alloc, table);
+        let values = scale_column(alloc, column, 0);
+        let (division, period_division) = self.divisions();
+        let (quotient, _) = division.prover_evaluate(builder, alloc, values);
+        let values = match period_division {
+            Some(period_division) => period_division.prover_evaluate(builder, alloc, quotient).1,
+            None => quotient,
+        };
+        let res = Column::Scalar(values);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?;
+        let (division, period_division) = self.divisions();
+        let (quotient_eval, _) = division.verifier_evaluate(builder, eval, chi_eval)?;
+        match period_division {
+            Some(period_division) => Ok(period_division
+                .verifier_evaluate(builder, quotient_eval, chi_eval)?
+                .1),
+            None => Ok(quotient_eval),
+        }
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        self.expr.presence_expr(nullable_columns)
+    }
+}
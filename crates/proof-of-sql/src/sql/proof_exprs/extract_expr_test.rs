@@ -0,0 +1,176 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, LiteralValue, OwnedTableTestAccessor,
+            TableRef, TableTestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone};
+
+// select extract(hour from a) as h, extract(minute from a) as m, extract(second from a) as s,
+// extract(dow from a) as dow from sxt.t
+#[test]
+fn we_can_prove_extracting_fields_from_timestamps() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [1_700_000_000_i64, -1, 0],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::Hour),
+                "h",
+            ),
+            aliased_plan(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::Minute),
+                "m",
+            ),
+            aliased_plan(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::Second),
+                "s",
+            ),
+            aliased_plan(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::DayOfWeek),
+                "dow",
+            ),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    // 2023-11-14 22:13:20 is a Tuesday, 1969-12-31 23:59:59 a Wednesday and 1970-01-01 a Thursday
+    let expected_res = owned_table([
+        bigint("h", [22_i64, 23, 0]),
+        bigint("m", [13_i64, 59, 0]),
+        bigint("s", [20_i64, 59, 0]),
+        bigint("dow", [2_i64, 3, 4]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select extract(epoch from a) as e, extract(hour from a) as h from sxt.t
+// where extract(epoch from a) <= 0
+#[test]
+fn we_can_prove_extracting_the_epoch_and_fields_in_a_timezone() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Millisecond,
+        PoSQLTimeZone::new(3_600),
+        [1_500_i64, -1_500, 0, 999],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::Epoch),
+                "e",
+            ),
+            aliased_plan(
+                extract(column(&t, "a", &accessor), PoSQLTimeField::Hour),
+                "h",
+            ),
+        ],
+        tab(&t),
+        lte(
+            extract(column(&t, "a", &accessor), PoSQLTimeField::Epoch),
+            const_bigint(0),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    // The epoch is rounded down to whole seconds and does not depend on the timezone
+    let expected_res = owned_table([bigint("e", [-2_i64, 0, 0]), bigint("h", [0_i64, 1, 1])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_evaluate_extract_of_literals() {
+    let timestamp = |time| {
+        DynProofExpr::new_literal(LiteralValue::TimeStampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::new(-3_600),
+            time,
+        ))
+    };
+    assert_eq!(
+        extract(timestamp(3_599), PoSQLTimeField::Hour),
+        const_bigint(23)
+    );
+    assert_eq!(
+        extract(timestamp(3_599), PoSQLTimeField::DayOfWeek),
+        const_bigint(3)
+    );
+    assert_eq!(
+        extract(timestamp(-61), PoSQLTimeField::Epoch),
+        const_bigint(-61)
+    );
+}
+
+#[test]
+fn we_cannot_extract_unsupported_fields() {
+    let data = owned_table([
+        timestamptz("a", PoSQLTimeUnit::Second, PoSQLTimeZone::utc(), [1_i64]),
+        bigint("b", [1_i64]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_extract(column(&t, "b", &accessor), PoSQLTimeField::Hour),
+        Err(ConversionError::ColumnOperationError { .. })
+    ));
+    for field in [
+        PoSQLTimeField::Microsecond,
+        PoSQLTimeField::Day,
+        PoSQLTimeField::Week,
+    ] {
+        assert!(matches!(
+            DynProofExpr::try_new_extract(column(&t, "a", &accessor), field),
+            Err(ConversionError::ColumnOperationError { .. })
+        ));
+    }
+}
+
+// extract(minute from a)
+#[test]
+fn we_can_compute_the_correct_output_of_an_extract_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [61_i64, -61, 3_600],
+        &alloc,
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let extract_expr: DynProofExpr = extract(column(&t, "a", &accessor), PoSQLTimeField::Minute);
+    let res = extract_expr.result_evaluate(&alloc, &data);
+    let expected_res_scalar = [1, 58, 0]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Scalar(&expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
@@ -0,0 +1,160 @@
+use super::{
+    prover_evaluate_equals_zero, scale_column, verifier_evaluate_equals_zero, DynProofExpr,
+    ProofExpr,
+};
+use crate::{
+    base::{
+        database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::proof::{FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    utils::log,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable `IN` expression over a list of literals
+///
+/// A row is in the list exactly when the product of its differences to the values of the
+/// list is zero. The product is built up one value at a time, so that every constraint
+/// has degree 2 regardless of the length of the list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InListExpr {
+    pub(crate) expr: Box<DynProofExpr>,
+    pub(crate) list: Vec<LiteralValue>,
+}
+
+impl InListExpr {
+    /// Create a new `IN` list expression
+    pub fn new(expr: Box<DynProofExpr>, list: Vec<LiteralValue>) -> Self {
+        Self { expr, list }
+    }
+
+    /// The scale that the expression and the values of the list are compared at
+    fn scale(&self) -> i8 {
+        self.list
+            .iter()
+            .map(|value| value.column_type().scale().unwrap_or(0))
+            .fold(self.expr.data_type().scale().unwrap_or(0), i8::max)
+    }
+
+    /// The power of ten that the expression is scaled by, which is never negative
+    pub(crate) fn expr_scaling_exponent(&self) -> i8 {
+        self.scale() - self.expr.data_type().scale().unwrap_or(0)
+    }
+
+    /// The values of the list, scaled to the common scale
+    pub(crate) fn scaled_values<S: Scalar>(&self) -> Vec<S> {
+        let scale = self.scale();
+        self.list
+            .iter()
+            .map(|value| {
+                let exponent = scale - value.column_type().scale().unwrap_or(0);
+                value.to_scalar::<S>() * S::pow10(exponent.unsigned_abs())
+            })
+            .collect()
+    }
+}
+
+impl ProofExpr for InListExpr {
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let column = self.expr.result_evaluate(alloc, table);
+        let values = scale_column(alloc, column, self.expr_scaling_exponent());
+        let list = self.scaled_values::<S>();
+        Column::Boolean(
+            alloc.alloc_slice_fill_with(table.num_rows(), |row| list.contains(&values[row])),
+        )
+    }
+
+    /// Let `x` be the expression, `l_1, ..., l_n` the values of the list and `p_0 = chi`.
+    /// The argument consists of
+    /// 1. `p_k - p_{k-1} * (x - l_k) = 0` for `k = 1, ..., n`, so that `p_n = (x - l_1) * ... * (x - l_n)`
+    /// 2. the equals-zero argument for `p_n`, whose selection is the result
+    #[tracing::instrument(name = "InListExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let num_rows = table.num_rows();
+        let column = self.expr.prover_evaluate(builder, alloc, table);
+        let values = scale_column(alloc, column, self.expr_scaling_exponent());
+        let list = self.scaled_values::<S>();
+
+        let chi: &'a [S] = alloc.alloc_slice_fill_copy(num_rows, S::ONE);
+        let product = list.iter().fold(chi, |previous: &'a [S], &value| {
+            // product
+            let product: &'a [S] =
+                alloc.alloc_slice_fill_with(num_rows, |row| previous[row] * (values[row] - value));
+            builder.produce_intermediate_mle(product);
+
+            // subpolynomial: product - previous * x + l * previous
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(product)]),
+                    (-S::one(), vec![Box::new(previous), Box::new(values)]),
+                    (value, vec![Box::new(previous)]),
+                ],
+            );
+            product
+        });
+
+        let res = Column::Boolean(prover_evaluate_equals_zero(
+            num_rows, builder, alloc, product,
+        ));
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let eval = self.expr.verifier_evaluate(builder, accessor, chi_eval)?
+            * S::pow10(self.expr_scaling_exponent().unsigned_abs());
+        let list = self.scaled_values::<S>();
+
+        let product_eval = list.iter().try_fold(chi_eval, |previous_eval, &value| {
+            // product
+            let product_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+            // subpolynomial: product - previous * x + l * previous
+            builder
+                .try_produce_sumcheck_subpolynomial_evaluation(
+                    SumcheckSubpolynomialType::Identity,
+                    product_eval - previous_eval * eval + value * previous_eval,
+                    2,
+                )
+                .map(|()| product_eval)
+        })?;
+
+        verifier_evaluate_equals_zero(builder, product_eval, chi_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        self.expr.presence_expr(nullable_columns)
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, ColumnRef, ColumnType, LiteralValue,
+            OwnedTableTestAccessor, TableRef, TableTestAccessor,
+        },
+        map::indexmap,
+        math::{decimal::Precision, i256::I256},
+        polynomial::MultilinearExtension,
+        scalar::test_scalar::TestScalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{
+            exercise_verification, mock_verification_builder::run_verify_for_each_row,
+            FinalRoundBuilder, VerifiableQueryResult,
+        },
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+use core::cell::RefCell;
+use std::collections::VecDeque;
+
+fn varchars(values: &[&str]) -> Vec<LiteralValue> {
+    values
+        .iter()
+        .map(|value| LiteralValue::VarChar((*value).to_string()))
+        .collect()
+}
+
+// select a, b from sxt.t where b in ('a', 'b', 'c', 'd')
+#[test]
+fn we_can_prove_an_in_list_query_over_varchars() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6]),
+        varchar("b", ["a", "x", "d", "", "c", "ab"]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        in_list(column(&t, "b", &accessor), varchars(&["a", "b", "c", "d"])),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 3, 5]), varchar("b", ["a", "d", "c"])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where not a in (1, 2.50)
+#[test]
+fn we_can_prove_a_not_in_list_query_with_values_of_different_scales() {
+    let data = owned_table([decimal75("a", 10, 1, [10_i64, 25, 26, -10, 0])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a"], &accessor),
+        tab(&t),
+        not(in_list(
+            column(&t, "a", &accessor),
+            vec![
+                LiteralValue::BigInt(1),
+                LiteralValue::Decimal75(Precision::new(5).unwrap(), 2, I256::from(250)),
+            ],
+        )),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("a", 10, 1, [26_i64, -10, 0])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a in (1, 3) as c from sxt.t
+#[test]
+fn we_can_prove_an_in_list_expression_in_the_result() {
+    let data = owned_table([int("a", [1_i32, 2, 3, -1])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            in_list(
+                column(&t, "a", &accessor),
+                vec![LiteralValue::BigInt(1), LiteralValue::BigInt(3)],
+            ),
+            "c",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([boolean("c", [true, false, true, false])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_create_an_invalid_in_list_expression() {
+    let data = owned_table([varchar("a", ["ab"])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_in_list(
+            column(&t, "a", &accessor),
+            vec![
+                LiteralValue::VarChar("ab".to_string()),
+                LiteralValue::BigInt(1)
+            ],
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_in_list(column(&t, "a", &accessor), vec![]),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+}
+
+// a in (-1, 7)
+#[test]
+fn we_can_compute_the_correct_output_of_an_in_list_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([borrowed_bigint("a", [-1_i64, 0, 7, 8], &alloc)]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let in_list_expr: DynProofExpr = in_list(
+        column(&t, "a", &accessor),
+        vec![LiteralValue::BigInt(-1), LiteralValue::BigInt(7)],
+    );
+    let res = in_list_expr.result_evaluate(&alloc, &data);
+    let expected_res = Column::Boolean(&[true, false, true, false]);
+    assert_eq!(res, expected_res);
+}
+
+// a in (0, 1, ..., 19)
+#[test]
+fn we_can_verify_a_long_in_list_with_constraints_of_degree_two() {
+    let alloc = Bump::new();
+    let values = &[-1_i64, 0, 7, 19, 20];
+    let data = table([borrowed_bigint("a", *values, &alloc)]);
+    let a = ColumnRef::new(TableRef::new("sxt", "t"), "a".into(), ColumnType::BigInt);
+    let in_list_expr = DynProofExpr::try_new_in_list(
+        DynProofExpr::new_column(a.clone()),
+        (0..20).map(LiteralValue::BigInt).collect(),
+    )
+    .unwrap();
+
+    let mut final_round_builder: FinalRoundBuilder<'_, TestScalar> =
+        FinalRoundBuilder::new(5, VecDeque::new());
+    let res = in_list_expr.prover_evaluate(&mut final_round_builder, &alloc, &data);
+    assert_eq!(res, Column::Boolean(&[false, true, true, true, false]));
+
+    let results = RefCell::new(Vec::new());
+    let verification_builder = run_verify_for_each_row(
+        5,
+        &final_round_builder,
+        3,
+        |verification_builder, chi_eval, evaluation_point| {
+            let accessor = indexmap! {
+                a.clone() => values.inner_product(evaluation_point)
+            };
+            results.borrow_mut().push(
+                in_list_expr
+                    .verifier_evaluate(verification_builder, &accessor, chi_eval)
+                    .unwrap(),
+            );
+        },
+    );
+    assert!(verification_builder
+        .get_identity_results()
+        .iter()
+        .flatten()
+        .all(|result| *result));
+    assert_eq!(
+        results.into_inner(),
+        [0, 1, 1, 1, 0].map(TestScalar::from).to_vec()
+    );
+}
@@ -1,4 +1,7 @@
-use super::{scale_and_add_subtract_eval, scale_and_subtract, DynProofExpr, ProofExpr};
+use super::{
+    presence_util::all_present, scale_and_add_subtract_eval, scale_and_subtract, DynProofExpr,
+    ProofExpr,
+};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -19,9 +22,9 @@ use serde::{Deserialize, Serialize};
 /// Provable AST expression for an inequality expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct InequalityExpr {
-    lhs: Box<DynProofExpr>,
-    rhs: Box<DynProofExpr>,
-    is_lt: bool,
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+    pub(crate) is_lt: bool,
 }
 
 impl InequalityExpr {
@@ -118,4 +121,11 @@ impl ProofExpr for InequalityExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.lhs.presence_expr(nullable_columns),
+            self.rhs.presence_expr(nullable_columns),
+        ])
+    }
 }
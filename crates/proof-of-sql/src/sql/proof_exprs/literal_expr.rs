@@ -1,4 +1,4 @@
-use super::ProofExpr;
+use super::{DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, LiteralValue, Table},
@@ -82,4 +82,8 @@ impl ProofExpr for LiteralExpr {
     }
 
     fn get_column_references(&self, _columns: &mut IndexSet<ColumnRef>) {}
+
+    fn presence_expr(&self, _nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        None
+    }
 }
@@ -5,7 +5,7 @@ pub(crate) use proof_expr::ProofExpr;
 mod proof_expr_test;
 
 mod aliased_dyn_proof_expr;
-pub(crate) use aliased_dyn_proof_expr::AliasedDynProofExpr;
+pub use aliased_dyn_proof_expr::AliasedDynProofExpr;
 
 mod add_subtract_expr;
 pub(crate) use add_subtract_expr::AddSubtractExpr;
@@ -16,10 +16,58 @@ mod aggregate_expr;
 pub(crate) use aggregate_expr::AggregateExpr;
 
 mod multiply_expr;
-use multiply_expr::MultiplyExpr;
+pub(crate) use multiply_expr::MultiplyExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod multiply_expr_test;
 
+mod divide_expr;
+pub(crate) use divide_expr::{
+    divide_and_modulo_scalars, prover_evaluate_divide_and_modulo, scale_column,
+    verifier_evaluate_divide_and_modulo, DivideExpr,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod divide_expr_test;
+
+mod cast_expr;
+pub(crate) use cast_expr::CastExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod cast_expr_test;
+
+mod timestamp_util;
+pub(crate) use timestamp_util::{timestamp_field_division, FloorDivision};
+
+mod date_trunc_expr;
+pub(crate) use date_trunc_expr::DateTruncExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod date_trunc_expr_test;
+
+mod extract_expr;
+pub(crate) use extract_expr::ExtractExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod extract_expr_test;
+
+mod case_expr;
+pub(crate) use case_expr::CaseExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod case_expr_test;
+
+mod in_list_expr;
+pub(crate) use in_list_expr::InListExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod in_list_expr_test;
+
+mod between_expr;
+pub(crate) use between_expr::BetweenExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod between_expr_test;
+#[cfg(all(test, feature = "blitzar"))]
+mod string_operations_test;
+
+mod modulo_expr;
+pub(crate) use modulo_expr::ModuloExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod modulo_expr_test;
+
 mod dyn_proof_expr;
 pub use dyn_proof_expr::DynProofExpr;
 
@@ -34,20 +82,23 @@ pub(crate) use and_expr::AndExpr;
 mod and_expr_test;
 
 mod inequality_expr;
-use inequality_expr::InequalityExpr;
+pub(crate) use inequality_expr::InequalityExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod inequality_expr_test;
 
 mod or_expr;
-use or_expr::OrExpr;
+pub(crate) use or_expr::OrExpr;
+use or_expr::{prover_evaluate_or, result_evaluate_or, verifier_evaluate_or};
 #[cfg(all(test, feature = "blitzar"))]
 mod or_expr_test;
 
 mod not_expr;
-use not_expr::NotExpr;
+pub(crate) use not_expr::NotExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod not_expr_test;
 
+mod presence_util;
+
 mod comparison_util;
 pub(crate) use comparison_util::scale_and_subtract;
 
@@ -57,17 +108,19 @@ pub(crate) use numerical_util::{
 };
 
 mod equals_expr;
-pub(crate) use equals_expr::EqualsExpr;
+pub(crate) use equals_expr::{
+    prover_evaluate_equals_zero, verifier_evaluate_equals_zero, EqualsExpr,
+};
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
 
 mod table_expr;
-pub(crate) use table_expr::TableExpr;
+pub use table_expr::TableExpr;
 
 #[cfg(test)]
 pub(crate) mod test_utility;
 
 mod column_expr;
-pub(crate) use column_expr::ColumnExpr;
+pub use column_expr::ColumnExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod column_expr_test;
@@ -0,0 +1,120 @@
+use super::{
+    divide_and_modulo_scalars, presence_util::all_present, prover_evaluate_divide_and_modulo,
+    scale_column, verifier_evaluate_divide_and_modulo, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        database::{try_modulo_column_types, Column, ColumnRef, ColumnType, Table},
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::{Scalar, ScalarExt},
+    },
+    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+    utils::log,
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable numerical `%` expression
+///
+/// The remainder has the sign of the numerator. Modulo by zero results in the numerator.
+/// The divisor and the truncated quotient must be less than `2^125` in absolute value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuloExpr {
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
+}
+
+impl ModuloExpr {
+    /// Create numerical `%` expression
+    pub fn new(lhs: Box<DynProofExpr>, rhs: Box<DynProofExpr>) -> Self {
+        Self { lhs, rhs }
+    }
+
+    /// The powers of ten that the numerator and denominator are scaled by
+    /// so that both have the scale of the result type.
+    pub(crate) fn scaling_exponents(&self) -> (i8, i8) {
+        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
+        let result_scale = self.data_type().scale().unwrap_or(0);
+        (result_scale - lhs_scale, result_scale - rhs_scale)
+    }
+}
+
+impl ProofExpr for ModuloExpr {
+    fn data_type(&self) -> ColumnType {
+        try_modulo_column_types(self.lhs.data_type(), self.rhs.data_type())
+            .expect("Failed to modulo column types")
+    }
+
+    fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        let lhs_column: Column<'a, S> = self.lhs.result_evaluate(alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.result_evaluate(alloc, table);
+        let (lhs_exponent, rhs_exponent) = self.scaling_exponents();
+        let lhs = scale_column(alloc, lhs_column, lhs_exponent);
+        let rhs = scale_column(alloc, rhs_column, rhs_exponent);
+        let (_, remainder) = divide_and_modulo_scalars(alloc, lhs, rhs);
+        Column::Scalar(remainder)
+    }
+
+    #[tracing::instrument(
+        name = "proofs.sql.ast.modulo_expr.prover_evaluate",
+        level = "info",
+        skip_all
+    )]
+    fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table: &Table<'a, S>,
+    ) -> Column<'a, S> {
+        log::log_memory_usage("Start");
+
+        let lhs_column: Column<'a, S> = self.lhs.prover_evaluate(builder, alloc, table);
+        let rhs_column: Column<'a, S> = self.rhs.prover_evaluate(builder, alloc, table);
+        let (lhs_exponent, rhs_exponent) = self.scaling_exponents();
+        let lhs = scale_column(alloc, lhs_column, lhs_exponent);
+        let rhs = scale_column(alloc, rhs_column, rhs_exponent);
+        let (_, remainder) = prover_evaluate_divide_and_modulo(builder, alloc, lhs, rhs);
+        let res = Column::Scalar(remainder);
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        chi_eval: S,
+    ) -> Result<S, ProofError> {
+        let lhs_eval = self.lhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        let rhs_eval = self.rhs.verifier_evaluate(builder, accessor, chi_eval)?;
+        let (lhs_exponent, rhs_exponent) = self.scaling_exponents();
+        let (_, remainder_eval) = verifier_evaluate_divide_and_modulo(
+            builder,
+            lhs_eval * S::pow10(lhs_exponent.unsigned_abs()),
+            rhs_eval * S::pow10(rhs_exponent.unsigned_abs()),
+            chi_eval,
+        )?;
+        Ok(remainder_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.lhs.presence_expr(nullable_columns),
+            self.rhs.presence_expr(nullable_columns),
+        ])
+    }
+}
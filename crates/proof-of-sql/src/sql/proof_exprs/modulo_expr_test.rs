@@ -0,0 +1,144 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{
+            owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor,
+        },
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use bumpalo::Bump;
+
+// select a, b, a % b as r from sxt.t where a % b = 1
+#[test]
+fn we_can_prove_a_typical_modulo_query() {
+    let data = owned_table([
+        smallint("a", [7_i16, -7, 7, -7, 0, 5]),
+        int("b", [2_i32, 2, -2, -2, 3, 0]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            col_expr_plan(&t, "a", &accessor),
+            col_expr_plan(&t, "b", &accessor),
+            aliased_plan(
+                modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+                "r",
+            ),
+        ],
+        tab(&t),
+        equal(
+            modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            const_int(1),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        smallint("a", [7_i16, 7]),
+        int("b", [2_i32, -2]),
+        int("r", [1_i32, 1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a % b as r from sxt.t
+#[test]
+fn we_can_prove_modulo_with_the_sign_of_the_numerator_including_modulo_by_zero() {
+    let data = owned_table([
+        bigint("a", [7_i64, -7, 7, -7, 0, 5, i64::MIN, i64::MAX]),
+        bigint("b", [2_i64, 2, -2, -2, 3, 0, -1, i64::MIN]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "r",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("r", [1_i64, -1, 1, -1, 0, 5, 0, i64::MAX])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a % b as r from sxt.t
+#[test]
+fn we_can_prove_decimal_modulo_with_the_result_scale() {
+    let data = owned_table([decimal75("a", 5, 2, [1050_i64, -725]), int("b", [3_i32, 2])]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![aliased_plan(
+            modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "r",
+        )],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("r", 5, 2, [150_i64, -125])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_modulo_non_numeric_or_scalar_types() {
+    let data = owned_table([
+        varchar("a", ["ab"]),
+        bigint("b", [1_i64]),
+        scalar("c", [1_i64]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_modulo(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_modulo(column(&t, "c", &accessor), column(&t, "b", &accessor)),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+// a % (b + 1)
+#[test]
+fn we_can_compute_the_correct_output_of_a_modulo_expr_using_result_evaluate() {
+    let alloc = Bump::new();
+    let data = table([
+        borrowed_bigint("a", [9_i64, -9, 4, 3], &alloc),
+        borrowed_int("b", [3_i32, 4, 0, -1], &alloc),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        TableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data.clone(), 0, ());
+    let arithmetic_expr: DynProofExpr = modulo(
+        column(&t, "a", &accessor),
+        add(column(&t, "b", &accessor), const_int(1)),
+    );
+    let res = arithmetic_expr.result_evaluate(&alloc, &data);
+    let expected_res_scalar = [1, -4, 0, 3]
+        .iter()
+        .map(|v| Curve25519Scalar::from(*v))
+        .collect::<Vec<_>>();
+    let expected_res = Column::Scalar(&expected_res_scalar);
+    assert_eq!(res, expected_res);
+}
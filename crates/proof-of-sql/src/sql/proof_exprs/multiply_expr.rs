@@ -1,4 +1,4 @@
-use super::{DynProofExpr, ProofExpr};
+use super::{presence_util::all_present, DynProofExpr, ProofExpr};
 use crate::{
     base::{
         database::{try_multiply_column_types, Column, ColumnRef, ColumnType, Table},
@@ -19,8 +19,8 @@ use serde::{Deserialize, Serialize};
 /// Provable numerical * expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MultiplyExpr {
-    lhs: Box<DynProofExpr>,
-    rhs: Box<DynProofExpr>,
+    pub(crate) lhs: Box<DynProofExpr>,
+    pub(crate) rhs: Box<DynProofExpr>,
 }
 
 impl MultiplyExpr {
@@ -109,4 +109,11 @@ impl ProofExpr for MultiplyExpr {
         self.lhs.get_column_references(columns);
         self.rhs.get_column_references(columns);
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        all_present([
+            self.lhs.presence_expr(nullable_columns),
+            self.rhs.presence_expr(nullable_columns),
+        ])
+    }
 }
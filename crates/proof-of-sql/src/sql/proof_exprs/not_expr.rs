@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 /// Provable logical NOT expression
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NotExpr {
-    expr: Box<DynProofExpr>,
+    pub(crate) expr: Box<DynProofExpr>,
 }
 
 impl NotExpr {
@@ -79,4 +79,8 @@ impl ProofExpr for NotExpr {
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.expr.get_column_references(columns);
     }
+
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr> {
+        self.expr.presence_expr(nullable_columns)
+    }
 }
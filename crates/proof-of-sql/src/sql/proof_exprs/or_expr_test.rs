@@ -5,6 +5,7 @@ use crate::{
             owned_table_utility::*, table_utility::*, Column, OwnedTableTestAccessor, TableRef,
             TableTestAccessor, TestAccessor,
         },
+        map::indexset,
     },
     sql::{
         proof::{exercise_verification, VerifiableQueryResult},
@@ -45,6 +46,53 @@ fn we_can_prove_a_simple_or_query() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_prove_an_or_query_with_nulls() {
+    let data = nullable_owned_table(
+        [
+            boolean("a", [true, false, false, false, false]),
+            boolean("b", [false, false, true, false, false]),
+            bigint("c", [0, 1, 2, 3, 4]),
+        ],
+        [
+            ("a".into(), vec![true, true, false, false, true]),
+            ("b".into(), vec![false, false, true, true, true]),
+        ],
+    );
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let nullable_columns = indexset! {
+        col_ref(&t, "a", &accessor),
+        col_ref(&t, "b", &accessor),
+    };
+    let a_or_b = or(column(&t, "a", &accessor), column(&t, "b", &accessor));
+
+    // a OR b is true where one side is true, even if the other side is null
+    let presence = a_or_b.presence_expr(&nullable_columns).unwrap();
+    let ast = filter(
+        cols_expr_plan(&t, &["c"], &accessor),
+        tab(&t),
+        and(a_or_b.clone(), presence),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [0, 2])]);
+    assert_eq!(res, expected_res);
+
+    let ast = filter(
+        cols_expr_plan(&t, &["c"], &accessor),
+        tab(&t),
+        DynProofExpr::new_is_not_null(&a_or_b, &nullable_columns),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [0, 2, 4])]);
+    assert_eq!(res, expected_res);
+}
+
 #[test]
 fn we_can_prove_a_simple_or_query_with_variable_integer_types() {
     let data = owned_table([
@@ -0,0 +1,32 @@
+//! Utilities for building presence expressions, i.e. boolean expressions that are `true`
+//! exactly where another expression is not null.
+//!
+//! A presence of `None` means that the expression is never null.
+use super::{AndExpr, DynProofExpr, NotExpr, OrExpr};
+use alloc::{boxed::Box, vec::Vec};
+
+/// Returns the presence of an expression that is not null exactly where all of `presences` are not null.
+pub(crate) fn all_present(
+    presences: impl IntoIterator<Item = Option<DynProofExpr>>,
+) -> Option<DynProofExpr> {
+    presences
+        .into_iter()
+        .flatten()
+        .reduce(|lhs, rhs| DynProofExpr::And(AndExpr::new(Box::new(lhs), Box::new(rhs))))
+}
+
+/// Returns the presence of an expression that is not null exactly where any of `presences` is not null.
+pub(crate) fn any_present(
+    presences: impl IntoIterator<Item = Option<DynProofExpr>>,
+) -> Option<DynProofExpr> {
+    presences
+        .into_iter()
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .reduce(|lhs, rhs| DynProofExpr::Or(OrExpr::new(Box::new(lhs), Box::new(rhs))))
+}
+
+/// Returns the negation of the boolean expression `expr`.
+pub(crate) fn not(expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::Not(NotExpr::new(Box::new(expr)))
+}
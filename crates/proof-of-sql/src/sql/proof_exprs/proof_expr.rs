@@ -1,3 +1,4 @@
+use super::DynProofExpr;
 use crate::{
     base::{
         database::{Column, ColumnRef, ColumnType, Table},
@@ -48,4 +49,10 @@ pub trait ProofExpr: Debug + Send + Sync {
     /// references in the `BoolExpr` or forwards the call to some
     /// subsequent `bool_expr`
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>);
+
+    /// Returns a boolean expression that is `true` exactly where this expression is not null,
+    /// or `None` if this expression is never null.
+    ///
+    /// `nullable_columns` are the columns that have a presence column.
+    fn presence_expr(&self, nullable_columns: &IndexSet<ColumnRef>) -> Option<DynProofExpr>;
 }
@@ -0,0 +1,190 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef},
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+
+// select a, b from sxt.t where b < 'b'
+#[test]
+fn we_can_prove_a_lexicographic_comparison_of_varchars() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6]),
+        varchar("b", ["a", "b", "ab", "", "ba", "B"]),
+    ])
+    .with_varchar_encodings();
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        DynProofExpr::try_new_inequality(column(&t, "b", &accessor), const_varchar("b"), true)
+            .unwrap(),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 3, 4, 6]),
+        varchar("b", ["a", "ab", "", "B"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where b >= c
+#[test]
+fn we_cannot_prove_a_comparison_of_varchar_columns() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4]),
+        varchar("b", ["abc", "ab", "x", "é"]),
+        varchar("c", ["abc", "abc", "xy", "z"]),
+    ])
+    .with_varchar_encodings();
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let result = DynProofExpr::try_new_inequality(
+        column(&t, "b", &accessor),
+        column(&t, "c", &accessor),
+        true,
+    );
+    assert_eq!(
+        result,
+        Err(ConversionError::InvalidExpression {
+            expression: "comparisons of two string columns are not supported".to_string(),
+        })
+    );
+}
+
+// select a, b from sxt.t where b like 'ab%'
+#[test]
+fn we_can_prove_a_like_query_with_a_prefix_pattern() {
+    let long = "ab".repeat(20);
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6]),
+        varchar("b", ["ab", "a", "abc", "b", long.as_str(), "aab"]),
+    ])
+    .with_varchar_encodings();
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        cols_expr_plan(&t, &["a", "b"], &accessor),
+        tab(&t),
+        like(column(&t, "b", &accessor), "ab%"),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 3, 5]),
+        varchar("b", ["ab", "abc", long.as_str()]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select length(b) as l, not starts_with(b, 'x') as s from sxt.t
+#[test]
+fn we_can_prove_length_and_starts_with_expressions_in_the_result() {
+    let data = owned_table([varchar("b", ["x", "héllo", "", "xyz"])]).with_varchar_encodings();
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = filter(
+        vec![
+            aliased_plan(length(column(&t, "b", &accessor)), "l"),
+            aliased_plan(not(starts_with(column(&t, "b", &accessor), "x")), "s"),
+        ],
+        tab(&t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("l", [1_i64, 5, 0, 3]),
+        boolean("s", [false, true, true, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_evaluate_string_operations_on_literals() {
+    assert_eq!(length(const_varchar("héllo")), const_bigint(5));
+    assert_eq!(starts_with(const_varchar("abc"), "ab"), const_bool(true));
+    assert_eq!(like(const_varchar("abc"), "b%"), const_bool(false));
+}
+
+#[test]
+fn we_cannot_create_unsupported_string_operations() {
+    let data = owned_table([bigint("a", [1_i64]), varchar("b", ["ab"])]).with_varchar_encodings();
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    assert!(matches!(
+        DynProofExpr::try_new_length(column(&t, "a", &accessor)),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_like(column(&t, "b", &accessor), "%b"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_like(column(&t, "b", &accessor), "a_%"),
+        Err(ConversionError::UnsupportedOperation { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_starts_with(column(&t, "b", &accessor), &"a".repeat(28)),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_inequality(column(&t, "b", &accessor), const_bigint(1), true),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_compare_strings_by_keys_that_do_not_preserve_their_order() {
+    let data = owned_table([varchar("b", ["ab"]), varchar("c", ["abc"])]).with_varchar_encodings();
+    let t = TableRef::new("sxt", "t");
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let long = "a".repeat(28);
+    assert!(matches!(
+        DynProofExpr::try_new_inequality(
+            column(&t, "b", &accessor),
+            const_varchar(long.as_str()),
+            true
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_between(
+            column(&t, "b", &accessor),
+            const_varchar("a"),
+            const_varchar(long.as_str())
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(matches!(
+        DynProofExpr::try_new_between(
+            column(&t, "b", &accessor),
+            column(&t, "c", &accessor),
+            const_varchar("b")
+        ),
+        Err(ConversionError::InvalidExpression { .. })
+    ));
+    assert!(DynProofExpr::try_new_between(
+        const_varchar("b"),
+        column(&t, "b", &accessor),
+        const_varchar(&"b".repeat(27))
+    )
+    .is_ok());
+}
@@ -4,5 +4,6 @@ use serde::{Deserialize, Serialize};
 /// Expression for an SQL table
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 pub struct TableExpr {
+    /// The table reference
     pub table_ref: TableRef,
 }
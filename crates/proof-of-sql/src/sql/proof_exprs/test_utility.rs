@@ -1,10 +1,13 @@
 use super::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr};
 use crate::base::{
-    database::{ColumnRef, LiteralValue, SchemaAccessor, TableRef},
+    database::{ColumnRef, ColumnType, LiteralValue, SchemaAccessor, TableRef},
     math::{decimal::Precision, i256::I256},
     scalar::Scalar,
 };
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use proof_of_sql_parser::{
+    intermediate_ast::AggregationOperator,
+    posql_time::{PoSQLInterval, PoSQLTimeField},
+};
 use sqlparser::ast::Ident;
 
 pub fn col_ref(tab: &TableRef, name: &str, accessor: &impl SchemaAccessor) -> ColumnRef {
@@ -85,6 +88,93 @@ pub fn multiply(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
     DynProofExpr::try_new_multiply(left, right).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_divide()` returns an error.
+pub fn divide(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_divide(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_modulo()` returns an error.
+pub fn modulo(left: DynProofExpr, right: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_modulo(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_in_list()` returns an error.
+pub fn in_list(expr: DynProofExpr, list: Vec<LiteralValue>) -> DynProofExpr {
+    DynProofExpr::try_new_in_list(expr, list).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_between()` returns an error.
+pub fn between(expr: DynProofExpr, low: DynProofExpr, high: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_between(expr, low, high).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_like()` returns an error.
+pub fn like(expr: DynProofExpr, pattern: &str) -> DynProofExpr {
+    DynProofExpr::try_new_like(expr, pattern).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_starts_with()` returns an error.
+pub fn starts_with(expr: DynProofExpr, prefix: &str) -> DynProofExpr {
+    DynProofExpr::try_new_starts_with(expr, prefix).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_length()` returns an error.
+pub fn length(expr: DynProofExpr) -> DynProofExpr {
+    DynProofExpr::try_new_length(expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_cast()` returns an error.
+pub fn cast(expr: DynProofExpr, to_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::try_new_cast(expr, to_type).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_add_interval()` returns an error.
+pub fn add_interval(expr: DynProofExpr, interval: &str) -> DynProofExpr {
+    DynProofExpr::try_new_add_interval(expr, PoSQLInterval::try_from(interval).unwrap()).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_date_trunc()` returns an error.
+pub fn date_trunc(expr: DynProofExpr, field: PoSQLTimeField) -> DynProofExpr {
+    DynProofExpr::try_new_date_trunc(expr, field).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_extract()` returns an error.
+pub fn extract(expr: DynProofExpr, field: PoSQLTimeField) -> DynProofExpr {
+    DynProofExpr::try_new_extract(expr, field).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_case()` returns an error.
+pub fn case_when(
+    when_then: Vec<(DynProofExpr, DynProofExpr)>,
+    else_expr: DynProofExpr,
+) -> DynProofExpr {
+    DynProofExpr::try_new_case(when_then, else_expr).unwrap()
+}
+
 pub fn const_bool(val: bool) -> DynProofExpr {
     DynProofExpr::new_literal(LiteralValue::Boolean(val))
 }
@@ -218,3 +308,17 @@ pub fn sum_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
         alias: alias.into(),
     }
 }
+
+pub fn max_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Max, expr),
+        alias: alias.into(),
+    }
+}
+
+pub fn min_expr(expr: DynProofExpr, alias: &str) -> AliasedDynProofExpr {
+    AliasedDynProofExpr {
+        expr: DynProofExpr::new_aggregate(AggregationOperator::Min, expr),
+        alias: alias.into(),
+    }
+}
@@ -0,0 +1,342 @@
+use super::{
+    divide_and_modulo_scalars, prover_evaluate_divide_and_modulo,
+    verifier_evaluate_divide_and_modulo,
+};
+use crate::{
+    base::{database::ColumnType, proof::ProofError, scalar::Scalar},
+    sql::proof::{FinalRoundBuilder, VerificationBuilder},
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone};
+
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+const NANOSECONDS_PER_DAY: i64 = 86_400 * NANOSECONDS_PER_SECOND;
+
+/// Floor division of values by a positive constant, `floor((x - shift) / length)`,
+/// together with the remainder, which is in `[0, length)`.
+///
+/// Timestamps are divided by the length of a field, shifted so that the field starts at zero.
+/// The quotient and the remainder are proven with the truncating division argument,
+/// after adding a multiple of `length` that makes every value of at most 64 bits nonnegative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FloorDivision {
+    length: i64,
+    shift: i64,
+}
+
+impl FloorDivision {
+    /// Create a new floor division by the positive `length`
+    pub(crate) fn new(length: i64, shift: i64) -> Self {
+        assert!(length > 0, "Length should be positive");
+        Self { length, shift }
+    }
+
+    /// The length that values are divided by
+    pub(crate) fn length(&self) -> i64 {
+        self.length
+    }
+
+    /// The shift that is subtracted from values before dividing them
+    pub(crate) fn shift(&self) -> i64 {
+        self.shift
+    }
+
+    /// The multiple of `length` that is added to values, so that they are nonnegative,
+    /// and the quotient that is subtracted from the truncated quotient because of it
+    pub(crate) fn offset_and_quotient_offset(&self) -> (i128, i128) {
+        let quotient_offset = (1_i128 << 65) / i128::from(self.length) + 1;
+        (
+            quotient_offset * i128::from(self.length) - i128::from(self.shift),
+            quotient_offset,
+        )
+    }
+
+    /// Divide a single value
+    pub(crate) fn divide(&self, value: i128) -> (i128, i128) {
+        let value = value - i128::from(self.shift);
+        (
+            value.div_euclid(i128::from(self.length)),
+            value.rem_euclid(i128::from(self.length)),
+        )
+    }
+
+    /// Divide a column of values
+    pub(crate) fn result_evaluate<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        values: &[S],
+    ) -> (&'a [S], &'a [S]) {
+        let (offset, quotient_offset) = self.offset_and_quotient_offset();
+        let offset_values =
+            alloc.alloc_slice_fill_with(values.len(), |i| values[i] + S::from(offset));
+        let lengths = alloc.alloc_slice_fill_copy(values.len(), S::from(self.length));
+        let (quotient, remainder) = divide_and_modulo_scalars(alloc, offset_values, lengths);
+        let quotient =
+            alloc.alloc_slice_fill_with(values.len(), |i| quotient[i] - S::from(quotient_offset));
+        (quotient, remainder)
+    }
+
+    /// Prove the quotient and the remainder of a column of values
+    pub(crate) fn prover_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        values: &[S],
+    ) -> (&'a [S], &'a [S]) {
+        let (offset, quotient_offset) = self.offset_and_quotient_offset();
+        let offset_values: &'a [S] =
+            alloc.alloc_slice_fill_with(values.len(), |i| values[i] + S::from(offset));
+        let lengths: &'a [S] = alloc.alloc_slice_fill_copy(values.len(), S::from(self.length));
+        let (quotient, remainder) =
+            prover_evaluate_divide_and_modulo(builder, alloc, offset_values, lengths);
+        let quotient =
+            alloc.alloc_slice_fill_with(values.len(), |i| quotient[i] - S::from(quotient_offset));
+        (quotient, remainder)
+    }
+
+    /// Verify the quotient and the remainder, returning their evaluations
+    pub(crate) fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        eval: S,
+        chi_eval: S,
+    ) -> Result<(S, S), ProofError> {
+        let (offset, quotient_offset) = self.offset_and_quotient_offset();
+        let (quotient_eval, remainder_eval) = verifier_evaluate_divide_and_modulo(
+            builder,
+            eval + chi_eval * S::from(offset),
+            chi_eval * S::from(self.length),
+            chi_eval,
+        )?;
+        Ok((
+            quotient_eval - chi_eval * S::from(quotient_offset),
+            remainder_eval,
+        ))
+    }
+}
+
+/// The number of nanoseconds in one `timeunit`
+fn nanoseconds_per_unit(timeunit: PoSQLTimeUnit) -> i64 {
+    match timeunit {
+        PoSQLTimeUnit::Second => NANOSECONDS_PER_SECOND,
+        PoSQLTimeUnit::Millisecond => 1_000_000,
+        PoSQLTimeUnit::Microsecond => 1_000,
+        PoSQLTimeUnit::Nanosecond => 1,
+    }
+}
+
+/// The length of `field` and the time of its first boundary after the Unix epoch, in nanoseconds,
+/// and the number of values the field takes when it is extracted.
+fn field_length_origin_and_period(field: PoSQLTimeField) -> (i64, i64, Option<i64>) {
+    match field {
+        PoSQLTimeField::Microsecond => (1_000, 0, None),
+        PoSQLTimeField::Millisecond => (1_000_000, 0, None),
+        PoSQLTimeField::Second => (NANOSECONDS_PER_SECOND, 0, Some(60)),
+        PoSQLTimeField::Minute => (60 * NANOSECONDS_PER_SECOND, 0, Some(60)),
+        PoSQLTimeField::Hour => (3_600 * NANOSECONDS_PER_SECOND, 0, Some(24)),
+        PoSQLTimeField::Day => (NANOSECONDS_PER_DAY, 0, None),
+        // 1970-01-05 is the first Monday after the Unix epoch
+        PoSQLTimeField::Week => (7 * NANOSECONDS_PER_DAY, 4 * NANOSECONDS_PER_DAY, None),
+        // 1970-01-04 is the first Sunday after the Unix epoch
+        PoSQLTimeField::DayOfWeek => (NANOSECONDS_PER_DAY, 3 * NANOSECONDS_PER_DAY, Some(7)),
+        PoSQLTimeField::Epoch => (NANOSECONDS_PER_SECOND, 0, None),
+    }
+}
+
+/// The floor division of timestamps by the length of `field` in their local time,
+/// or `None` if the field is shorter than the time unit.
+///
+/// The epoch is the same in every timezone.
+fn field_division(
+    field: PoSQLTimeField,
+    timeunit: PoSQLTimeUnit,
+    timezone: PoSQLTimeZone,
+) -> Option<FloorDivision> {
+    let (length, origin, _) = field_length_origin_and_period(field);
+    let nanoseconds_per_unit = nanoseconds_per_unit(timeunit);
+    let offset = if field == PoSQLTimeField::Epoch {
+        0
+    } else {
+        i64::from(timezone.offset()) * (NANOSECONDS_PER_SECOND / nanoseconds_per_unit)
+    };
+    (length >= nanoseconds_per_unit).then(|| {
+        FloorDivision::new(
+            length / nanoseconds_per_unit,
+            origin / nanoseconds_per_unit - offset,
+        )
+    })
+}
+
+/// The floor division of values of `data_type` by the length of `field`,
+/// or `None` if they are not timestamps or the field is shorter than their time unit.
+pub(crate) fn timestamp_field_division(
+    field: PoSQLTimeField,
+    data_type: ColumnType,
+) -> Option<FloorDivision> {
+    match data_type {
+        ColumnType::TimestampTZ(timeunit, timezone) => field_division(field, timeunit, timezone),
+        _ => None,
+    }
+}
+
+/// The number of values `field` takes when it is extracted, e.g. 24 for hours,
+/// or `None` if it is not taken modulo anything.
+pub(crate) fn field_period(field: PoSQLTimeField) -> Option<i64> {
+    field_length_origin_and_period(field).2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        base::{polynomial::MultilinearExtension, scalar::test_scalar::TestScalar},
+        sql::{
+            proof::mock_verification_builder::run_verify_for_each_row,
+            proof_exprs::divide_expr::prove_quotient_and_remainder,
+        },
+    };
+    use core::cell::RefCell;
+    use num_traits::Inv;
+    use std::collections::VecDeque;
+
+    /// Prove a tampered `quotient` and `remainder` of the offset `values` and verify them row by row.
+    fn verify_tampered_floor_division(
+        division: FloorDivision,
+        values: &[TestScalar],
+        quotient: &[TestScalar],
+        remainder: &[TestScalar],
+    ) -> Vec<Result<(TestScalar, TestScalar), ProofError>> {
+        let alloc = Bump::new();
+        let (offset, _) = division.offset_and_quotient_offset();
+        let offset_values =
+            alloc.alloc_slice_fill_with(values.len(), |i| values[i] + TestScalar::from(offset));
+        let lengths =
+            alloc.alloc_slice_fill_copy(values.len(), TestScalar::from(division.length()));
+        let mut final_round_builder: FinalRoundBuilder<'_, TestScalar> =
+            FinalRoundBuilder::new(values.len(), VecDeque::new());
+        prove_quotient_and_remainder(
+            &mut final_round_builder,
+            &alloc,
+            offset_values,
+            lengths,
+            alloc.alloc_slice_copy(quotient),
+            alloc.alloc_slice_copy(remainder),
+        );
+        let results = RefCell::new(Vec::new());
+        run_verify_for_each_row(
+            values.len(),
+            &final_round_builder,
+            3,
+            |verification_builder, chi_eval, evaluation_point| {
+                results.borrow_mut().push(division.verifier_evaluate(
+                    verification_builder,
+                    values.inner_product(evaluation_point),
+                    chi_eval,
+                ));
+            },
+        );
+        results.into_inner()
+    }
+
+    #[test]
+    fn we_can_floor_divide_values() {
+        let division = FloorDivision::new(10, 3);
+        assert_eq!(division.divide(3), (0, 0));
+        assert_eq!(division.divide(25), (2, 2));
+        assert_eq!(division.divide(2), (-1, 9));
+        assert_eq!(division.divide(-17), (-2, 0));
+
+        let alloc = Bump::new();
+        let values = [3, 25, 2, -17, i64::MIN, i64::MAX].map(TestScalar::from);
+        let (quotient, remainder) = division.result_evaluate(&alloc, &values);
+        for (i, value) in [3, 25, 2, -17, i64::MIN, i64::MAX].into_iter().enumerate() {
+            let (expected_quotient, expected_remainder) = division.divide(value.into());
+            assert_eq!(quotient[i], TestScalar::from(expected_quotient));
+            assert_eq!(remainder[i], TestScalar::from(expected_remainder));
+        }
+    }
+
+    #[test]
+    fn we_cannot_verify_a_floor_division_with_a_tampered_quotient() {
+        let division = FloorDivision::new(10, 3);
+        let (offset, _) = division.offset_and_quotient_offset();
+        let values = [TestScalar::from(25)];
+        // The offset value is 10 times a field element that is not an integer, with a remainder of 0
+        let quotient = [(values[0] + TestScalar::from(offset)) * TestScalar::TEN.inv().unwrap()];
+        let remainder = [TestScalar::ZERO];
+        let results = verify_tampered_floor_division(division, &values, &quotient, &remainder);
+        assert!(matches!(
+            results[0],
+            Err(ProofError::VerificationError { .. })
+        ));
+    }
+
+    #[test]
+    fn we_cannot_verify_a_floor_division_with_a_tampered_remainder() {
+        let division = FloorDivision::new(10, 3);
+        let (offset, _) = division.offset_and_quotient_offset();
+        let values = [TestScalar::from(25)];
+        let alloc = Bump::new();
+        let (quotient, remainder) = divide_and_modulo_scalars(
+            &alloc,
+            &[values[0] + TestScalar::from(offset)],
+            &[TestScalar::TEN],
+        );
+        // Borrowing one from the quotient keeps the offset value, but the remainder is too large
+        let quotient = [quotient[0] - TestScalar::ONE];
+        let remainder = [remainder[0] + TestScalar::TEN];
+        let results = verify_tampered_floor_division(division, &values, &quotient, &remainder);
+        assert!(matches!(
+            results[0],
+            Err(ProofError::VerificationError {
+                error: "remainder is out of range"
+            })
+        ));
+    }
+
+    #[test]
+    fn we_can_get_the_division_of_a_field() {
+        let utc = PoSQLTimeZone::utc();
+        assert_eq!(
+            field_division(PoSQLTimeField::Day, PoSQLTimeUnit::Second, utc),
+            Some(FloorDivision::new(86_400, 0))
+        );
+        assert_eq!(
+            field_division(
+                PoSQLTimeField::Hour,
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::new(-3_600)
+            ),
+            Some(FloorDivision::new(3_600_000, 3_600_000))
+        );
+        assert_eq!(
+            field_division(PoSQLTimeField::Week, PoSQLTimeUnit::Second, utc),
+            Some(FloorDivision::new(604_800, 345_600))
+        );
+        assert_eq!(
+            field_division(
+                PoSQLTimeField::Epoch,
+                PoSQLTimeUnit::Microsecond,
+                PoSQLTimeZone::new(3_600)
+            ),
+            Some(FloorDivision::new(1_000_000, 0))
+        );
+        assert_eq!(
+            field_division(PoSQLTimeField::Millisecond, PoSQLTimeUnit::Second, utc),
+            None
+        );
+        assert_eq!(
+            timestamp_field_division(
+                PoSQLTimeField::Minute,
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, utc)
+            ),
+            Some(FloorDivision::new(60, 0))
+        );
+        assert_eq!(
+            timestamp_field_division(PoSQLTimeField::Minute, ColumnType::BigInt),
+            None
+        );
+        assert_eq!(field_period(PoSQLTimeField::Hour), Some(24));
+        assert_eq!(field_period(PoSQLTimeField::Day), None);
+    }
+}
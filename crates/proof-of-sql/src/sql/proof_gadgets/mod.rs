@@ -9,8 +9,9 @@ pub(crate) use membership_check::{
 };
 #[cfg(test)]
 mod membership_check_test;
-#[allow(unused_imports, dead_code)]
-use permutation_check::{final_round_evaluate_permutation_check, verify_permutation_check};
+pub(crate) use permutation_check::{
+    final_round_evaluate_permutation_check, verify_permutation_check,
+};
 #[cfg(test)]
 mod permutation_check_test;
 use shift::{final_round_evaluate_shift, first_round_evaluate_shift, verify_shift};
@@ -0,0 +1,250 @@
+use super::{
+    group_by_exec::{prove_group_by, verify_group_by},
+    sort_exec::{sort_key, sort_key_eval},
+    DynProofPlan, SortExec,
+};
+use crate::{
+    base::{
+        database::{
+            group_by_util::{aggregate_columns, AggregatedColumns},
+            Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, prover_evaluate_sign,
+            verifier_evaluate_sign, verify_monotonic,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     SELECT DISTINCT * FROM <ProofPlan>
+/// ```
+///
+/// The distinct rows are returned in ascending order. They are proven to be the groups of the input
+/// in the same way as the groups of a [`GroupByExec`](super::GroupByExec), with multiplicities
+/// that are at least one. Their uniqueness follows from the folded key of all columns,
+/// see [`SortExec::can_sort_by`], being strictly increasing.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct DistinctExec {
+    pub(crate) input: Box<DynProofPlan>,
+}
+
+impl DistinctExec {
+    /// Creates a new distinct execution plan.
+    ///
+    /// # Panics
+    /// Panics if the input has no columns.
+    pub fn new(input: Box<DynProofPlan>) -> Self {
+        assert!(
+            !input.get_column_result_fields().is_empty(),
+            "Distinct requires at least one column"
+        );
+        Self { input }
+    }
+
+    /// Whether results with columns of the given types can be provably deduplicated
+    ///
+    /// This is the case if they can be provably sorted by all of their columns.
+    #[must_use]
+    pub fn can_deduplicate(column_types: &[ColumnType]) -> bool {
+        SortExec::can_sort_by(column_types)
+    }
+
+    /// All columns in ascending order
+    fn order_by(&self) -> Vec<(usize, bool)> {
+        (0..self.input.get_column_result_fields().len())
+            .map(|index| (index, true))
+            .collect()
+    }
+}
+
+impl ProofPlan for DistinctExec
+where
+    DistinctExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        let column_types = self
+            .input
+            .get_column_result_fields()
+            .iter()
+            .map(ColumnField::data_type)
+            .collect::<Vec<_>>();
+        if !Self::can_deduplicate(&column_types) {
+            return Err(ProofError::VerificationError {
+                error: "Distinct columns can not be folded into a sort key",
+            });
+        }
+        // 1. columns
+        let input_table_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let input_chi_eval = input_table_eval.chi_eval();
+        let column_evals = input_table_eval.column_evals();
+        // 2. distinct columns and their multiplicities
+        let distinct_column_evals =
+            builder.try_consume_final_round_mle_evaluations(column_evals.len())?;
+        let count_eval = builder.try_consume_final_round_mle_evaluation()?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+        // 3. The distinct columns are the groups of the columns
+        verify_group_by(
+            builder,
+            alpha,
+            beta,
+            input_chi_eval,
+            output_chi_eval,
+            (column_evals.to_vec(), Vec::new(), input_chi_eval),
+            (distinct_column_evals.clone(), Vec::new(), count_eval),
+        )?;
+        // 4. The distinct columns are unique
+        let key_eval = sort_key_eval(&self.order_by(), &distinct_column_evals);
+        verify_monotonic::<S, true, true>(builder, alpha, beta, key_eval, output_chi_eval)?;
+        // 5. Every distinct row occurs in the columns
+        let count_is_nonpositive_eval =
+            verifier_evaluate_sign(builder, count_eval - output_chi_eval, output_chi_eval, None)?;
+        if count_is_nonpositive_eval != S::ZERO {
+            return Err(ProofError::VerificationError {
+                error: "distinct row does not occur in the input",
+            });
+        }
+        Ok(TableEvaluation::new(distinct_column_evals, output_chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for DistinctExec {
+    #[tracing::instrument(name = "DistinctExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let chi = alloc.alloc_slice_fill_copy(input.num_rows(), true);
+        // 2. distinct columns
+        let AggregatedColumns {
+            group_by_columns: distinct_columns,
+            count_column,
+            ..
+        } = aggregate_columns(alloc, &columns, &[], &[], &[], chi)
+            .expect("columns should be aggregatable");
+        let num_distinct_rows = count_column.len();
+        builder.request_post_result_challenges(2);
+        builder.produce_chi_evaluation_length(num_distinct_rows);
+        // 3. uniqueness of the distinct columns
+        first_round_evaluate_monotonic(builder, num_distinct_rows);
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(distinct_columns),
+            TableOptions::new(Some(num_distinct_rows)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "DistinctExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        let chi = alloc.alloc_slice_fill_copy(num_rows, true);
+        // 2. distinct columns and their multiplicities
+        let AggregatedColumns {
+            group_by_columns: distinct_columns,
+            count_column,
+            ..
+        } = aggregate_columns(alloc, &columns, &[], &[], &[], chi)
+            .expect("columns should be aggregatable");
+        let num_distinct_rows = count_column.len();
+        for column in &distinct_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.produce_intermediate_mle(Column::BigInt(count_column));
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. The distinct columns are the groups of the columns
+        prove_group_by(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            (&columns, &[], chi),
+            (&distinct_columns, &[], count_column),
+            num_rows,
+        );
+        // 4. The distinct columns are unique
+        let key = sort_key(
+            alloc,
+            &self.order_by(),
+            &distinct_columns,
+            num_distinct_rows,
+        );
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, key);
+        // 5. Every distinct row occurs in the columns
+        let count_minus_one =
+            alloc.alloc_slice_fill_with(num_distinct_rows, |i| S::from(count_column[i] - 1));
+        prover_evaluate_sign(builder, alloc, count_minus_one);
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(distinct_columns),
+            TableOptions::new(Some(num_distinct_rows)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
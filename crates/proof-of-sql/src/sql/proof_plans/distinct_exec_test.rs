@@ -0,0 +1,132 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        proof::ProofError,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_distinct_exec() {
+    let data = owned_table([bigint("a", [3_i64, -1, 3, 5, -1, 3])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = distinct_exec(table_exec(
+        t.clone(),
+        vec![column_field("a", ColumnType::BigInt)],
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [-1_i64, 3, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_distinct_exec_on_multiple_columns() {
+    let data = owned_table([
+        boolean("a", [true, false, true, false, true, true]),
+        int("b", [1, 2, 1, 2, -4, 1]),
+        tinyint("c", [0_i8, 1, 0, 1, 4, 2]),
+        varchar("d", ["x", "y", "z", "y", "x", "x"]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = distinct_exec(filter(
+        cols_expr_plan(&t, &["a", "b", "c"], &accessor),
+        tab(&t),
+        not(equal(column(&t, "d", &accessor), const_varchar("z"))),
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("a", [false, true, true, true]),
+        int("b", [2, -4, 1, 1]),
+        tinyint("c", [1_i8, 4, 0, 2]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_over_a_distinct_exec() {
+    let data = owned_table([int("a", [1, 2, 2, 3, 1, 2])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = slice_exec(
+        sort_exec(
+            distinct_exec(table_exec(
+                t.clone(),
+                vec![column_field("a", ColumnType::Int)],
+            )),
+            vec![(0, false)],
+        ),
+        0,
+        Some(2),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([int("a", [3, 2])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_distinct_exec() {
+    let data = owned_table([bigint("a", [1_i64, 1, 3]), int128("b", [4_i128, 4, 6])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = distinct_exec(filter(
+        cols_expr_plan(&t, &["b"], &accessor),
+        tab(&t),
+        const_bool(false),
+    ));
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([int128("b", [0_i128; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_distinct_exec_on_columns_that_can_not_be_folded() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let data = table([
+        borrowed_int128("a", [2_i128, 2], &alloc),
+        borrowed_bigint("b", [1_i64, 1], &alloc),
+        borrowed_varchar("c", ["a", "a"], &alloc),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    accessor.add_table(t.clone(), data, 0);
+    for schema in [
+        vec![
+            column_field("a", ColumnType::Int128),
+            column_field("b", ColumnType::BigInt),
+        ],
+        vec![column_field("c", ColumnType::VarChar)],
+    ] {
+        let ast = distinct_exec(table_exec(t.clone(), schema));
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &());
+        assert!(matches!(
+            verifiable_res.verify(&ast, &accessor, &()),
+            Err(QueryError::ProofError {
+                source: ProofError::VerificationError { .. }
+            })
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use super::{
-    EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortMergeJoinExec, TableExec,
-    UnionExec,
+    DistinctExec, EmptyExec, FilterExec, GroupByExec, JoinType, ProjectionExec, SemiJoinExec,
+    SliceExec, SortExec, SortMergeJoinExec, TableExec, UnionExec,
 };
 use crate::{
     base::{
@@ -9,13 +9,17 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{
-        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
     },
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
+use sqlparser::ast::Ident;
 
 /// The query plan for proving a query
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
@@ -32,17 +36,17 @@ pub enum DynProofPlan {
     Projection(ProjectionExec),
     /// Provable expressions for queries of the form
     /// ```ignore
-    ///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
+    ///     SELECT <group_by_expr1>.0 as <group_by_expr1>.1, ..., <group_by_exprM>.0 as <group_by_exprM>.1,
     ///         SUM(<sum_expr1>.0) as <sum_expr1>.1, ..., SUM(<sum_exprN>.0) as <sum_exprN>.1,
     ///         COUNT(*) as count_alias
-    ///     FROM <table>
+    ///     FROM <ProofPlan>
     ///     WHERE <where_clause>
-    ///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
+    ///     GROUP BY <group_by_expr1>.0, ..., <group_by_exprM>.0
     /// ```
     GroupBy(GroupByExec),
     /// Provable expressions for queries of the form, where the result is sent in a dense form
     /// ```ignore
-    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <ProofPlan> WHERE <where_clause>
     /// ```
     Filter(FilterExec),
     /// `ProofPlan` for queries of the form
@@ -52,6 +56,16 @@ pub enum DynProofPlan {
     Slice(SliceExec),
     /// `ProofPlan` for queries of the form
     /// ```ignore
+    ///     <ProofPlan> ORDER BY <column1> [ASC|DESC], ..., <columnN> [ASC|DESC]
+    /// ```
+    Sort(SortExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     SELECT DISTINCT * FROM <ProofPlan>
+    /// ```
+    Distinct(DistinctExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
     ///     <ProofPlan>
     ///     UNION ALL
     ///     <ProofPlan>
@@ -66,4 +80,201 @@ pub enum DynProofPlan {
     ///     ON col1 = col2
     /// ```
     SortMergeJoin(SortMergeJoinExec),
+    /// `ProofPlan` for queries of the form
+    /// ```ignore
+    ///     <ProofPlan> WHERE col1 [NOT] IN (SELECT col2 FROM <ProofPlan>)
+    /// ```
+    SemiJoin(SemiJoinExec),
+}
+
+impl DynProofPlan {
+    /// Creates a new empty plan.
+    #[must_use]
+    pub fn new_empty() -> Self {
+        Self::Empty(EmptyExec::new())
+    }
+
+    /// Creates a new table plan.
+    #[must_use]
+    pub fn new_table(table_ref: TableRef, schema: Vec<ColumnField>) -> Self {
+        Self::Table(TableExec::new(table_ref, schema))
+    }
+
+    /// Creates a new projection plan.
+    #[must_use]
+    pub fn new_projection(aliased_results: Vec<AliasedDynProofExpr>, input: DynProofPlan) -> Self {
+        Self::Projection(ProjectionExec::new(aliased_results, Box::new(input)))
+    }
+
+    /// Creates a new filter plan.
+    #[must_use]
+    pub fn new_filter(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        Self::Filter(FilterExec::new(aliased_results, table, where_clause))
+    }
+
+    /// Creates a new filter plan over the result of another plan.
+    #[must_use]
+    pub fn new_filter_with_input(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        input: DynProofPlan,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        Self::Filter(FilterExec::new_with_input(
+            aliased_results,
+            Box::new(input),
+            where_clause,
+        ))
+    }
+
+    /// Creates a new group by plan.
+    #[must_use]
+    pub fn new_group_by(
+        group_by_exprs: Vec<AliasedDynProofExpr>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        max_expr: Vec<AliasedDynProofExpr>,
+        min_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        table: TableExpr,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        Self::GroupBy(GroupByExec::new(
+            group_by_exprs,
+            sum_expr,
+            max_expr,
+            min_expr,
+            count_alias,
+            table,
+            where_clause,
+        ))
+    }
+
+    /// Creates a new group by plan over the result of another plan.
+    #[must_use]
+    pub fn new_group_by_with_input(
+        group_by_exprs: Vec<AliasedDynProofExpr>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        max_expr: Vec<AliasedDynProofExpr>,
+        min_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        input: DynProofPlan,
+        where_clause: DynProofExpr,
+    ) -> Self {
+        Self::GroupBy(GroupByExec::new_with_input(
+            group_by_exprs,
+            sum_expr,
+            max_expr,
+            min_expr,
+            count_alias,
+            Box::new(input),
+            where_clause,
+        ))
+    }
+
+    /// Creates a new slice plan.
+    #[must_use]
+    pub fn new_slice(input: DynProofPlan, skip: usize, fetch: Option<usize>) -> Self {
+        Self::Slice(SliceExec::new(Box::new(input), skip, fetch))
+    }
+
+    /// Creates a new sort plan.
+    ///
+    /// `order_by` are pairs of the indexes of the sort columns and whether they are sorted ascending.
+    ///
+    /// # Panics
+    /// Panics if there are no sort columns or if a sort column index is out of bounds.
+    #[must_use]
+    pub fn new_sort(input: DynProofPlan, order_by: Vec<(usize, bool)>) -> Self {
+        Self::Sort(SortExec::new(Box::new(input), order_by))
+    }
+
+    /// Creates a new distinct plan.
+    ///
+    /// # Panics
+    /// Panics if the input has no columns.
+    #[must_use]
+    pub fn new_distinct(input: DynProofPlan) -> Self {
+        Self::Distinct(DistinctExec::new(Box::new(input)))
+    }
+
+    /// Creates a new union plan.
+    #[must_use]
+    pub fn new_union(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> Self {
+        Self::Union(UnionExec::new(inputs, schema))
+    }
+
+    /// Creates a new sort merge join plan.
+    ///
+    /// # Panics
+    /// Panics if the join column indexes are out of bounds, if the number of join columns
+    /// differs between the two sides or if the number of result idents is not the expected one.
+    #[must_use]
+    pub fn new_sort_merge_join(
+        left: DynProofPlan,
+        right: DynProofPlan,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        result_idents: Vec<Ident>,
+    ) -> Self {
+        Self::SortMergeJoin(SortMergeJoinExec::new(
+            Box::new(left),
+            Box::new(right),
+            left_join_column_indexes,
+            right_join_column_indexes,
+            result_idents,
+        ))
+    }
+
+    /// Creates a new sort merge join plan of the given type.
+    ///
+    /// For outer joins the result has additional presence columns, see [`JoinType`].
+    ///
+    /// # Panics
+    /// Panics if the join column indexes are out of bounds, if the number of join columns
+    /// differs between the two sides or if the number of result idents is not the expected one.
+    #[must_use]
+    pub fn new_sort_merge_join_with_join_type(
+        left: DynProofPlan,
+        right: DynProofPlan,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        result_idents: Vec<Ident>,
+        join_type: JoinType,
+    ) -> Self {
+        Self::SortMergeJoin(SortMergeJoinExec::new_with_join_type(
+            Box::new(left),
+            Box::new(right),
+            left_join_column_indexes,
+            right_join_column_indexes,
+            result_idents,
+            join_type,
+        ))
+    }
+
+    /// Creates a new semi join plan, or an anti join plan if `is_anti_join` is true.
+    ///
+    /// The result consists of the rows of `left` whose join columns do, or do not, match a row of `right`.
+    ///
+    /// # Panics
+    /// Panics if there are no join columns, if the join column indexes are out of bounds
+    /// or if the number of join columns differs between the two sides.
+    #[must_use]
+    pub fn new_semi_join(
+        left: DynProofPlan,
+        right: DynProofPlan,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        is_anti_join: bool,
+    ) -> Self {
+        Self::SemiJoin(SemiJoinExec::new(
+            Box::new(left),
+            Box::new(right),
+            left_join_column_indexes,
+            right_join_column_indexes,
+            is_anti_join,
+        ))
+    }
 }
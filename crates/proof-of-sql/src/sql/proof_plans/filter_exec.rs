@@ -1,4 +1,4 @@
-use super::{fold_columns, fold_vals};
+use super::{fold_columns, fold_vals, projection_exec::input_accessor, DynProofPlan, TableExec};
 use crate::{
     base::{
         database::{
@@ -21,35 +21,62 @@ use crate::{
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
-use core::marker::PhantomData;
+use core::{iter, marker::PhantomData};
 use num_traits::{One, Zero};
 use serde::{Deserialize, Serialize};
 
 /// Provable expressions for queries of the form
 /// ```ignore
-///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
+///     SELECT <result_expr1>, ..., <result_exprN> FROM <input> WHERE <where_clause>
 /// ```
 ///
+/// The input is the result of another [`ProofPlan`], e.g. a table, a join, a union
+/// or the groups of a [`GroupByExec`](super::GroupByExec) filtered by a `HAVING` clause.
+/// The expressions refer to the columns of the input by their names, qualified with any
+/// of the tables of the input.
+///
 /// This differs from the [`FilterExec`] in that the result is not a sparse table.
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct OstensibleFilterExec<H: ProverHonestyMarker> {
     pub(crate) aliased_results: Vec<AliasedDynProofExpr>,
-    pub(crate) table: TableExpr,
-    /// TODO: add docs
+    pub(crate) input: Box<DynProofPlan>,
+    /// The selection, which is evaluated on the input
     pub(crate) where_clause: DynProofExpr,
     phantom: PhantomData<H>,
 }
 
 impl<H: ProverHonestyMarker> OstensibleFilterExec<H> {
-    /// Creates a new filter expression.
+    /// Creates a new filter expression over a table.
+    ///
+    /// Only the columns of the table referenced by the expressions are loaded.
     pub fn new(
         aliased_results: Vec<AliasedDynProofExpr>,
         table: TableExpr,
         where_clause: DynProofExpr,
+    ) -> Self {
+        let input = TableExec::from_referenced_columns(
+            table.table_ref,
+            aliased_results
+                .iter()
+                .map(|aliased_expr| &aliased_expr.expr)
+                .chain(iter::once(&where_clause)),
+        );
+        Self::new_with_input(
+            aliased_results,
+            Box::new(DynProofPlan::Table(input)),
+            where_clause,
+        )
+    }
+
+    /// Creates a new filter expression over the result of another plan.
+    pub fn new_with_input(
+        aliased_results: Vec<AliasedDynProofExpr>,
+        input: Box<DynProofPlan>,
+        where_clause: DynProofExpr,
     ) -> Self {
         Self {
             aliased_results,
-            table,
+            input,
             where_clause,
             phantom: PhantomData,
         }
@@ -60,7 +87,6 @@ impl<H: ProverHonestyMarker> ProofPlan for OstensibleFilterExec<H>
 where
     OstensibleFilterExec<H>: ProverEvaluate,
 {
-    #[allow(unused_variables)]
     fn verifier_evaluate<S: Scalar>(
         &self,
         builder: &mut impl VerificationBuilder<S>,
@@ -68,25 +94,27 @@ where
         _result: Option<&OwnedTable<S>>,
         chi_eval_map: &IndexMap<TableRef, S>,
     ) -> Result<TableEvaluation<S>, ProofError> {
-        let input_chi_eval = *chi_eval_map
-            .get(&self.table.table_ref)
-            .expect("Chi eval not found");
-        // 1. selection
+        // 1. input
+        let input_eval = self
+            .input
+            .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let input_chi_eval = input_eval.chi_eval();
+        let current_accessor = input_accessor(&self.input, &input_eval)?;
+        // 2. selection
         let selection_eval =
             self.where_clause
-                .verifier_evaluate(builder, accessor, input_chi_eval)?;
-        // 2. columns
-        let columns_evals = Vec::from_iter(
-            self.aliased_results
-                .iter()
-                .map(|aliased_expr| {
-                    aliased_expr
-                        .expr
-                        .verifier_evaluate(builder, accessor, input_chi_eval)
-                })
-                .collect::<Result<Vec<_>, _>>()?,
-        );
-        // 3. filtered_columns
+                .verifier_evaluate(builder, &current_accessor, input_chi_eval)?;
+        // 3. columns
+        let columns_evals = self
+            .aliased_results
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, &current_accessor, input_chi_eval)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // 4. filtered_columns
         let filtered_columns_evals =
             builder.try_consume_final_round_mle_evaluations(self.aliased_results.len())?;
         assert!(filtered_columns_evals.len() == self.aliased_results.len());
@@ -122,19 +150,12 @@ where
     }
 
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
-        let mut columns = IndexSet::default();
-
-        for aliased_expr in &self.aliased_results {
-            aliased_expr.expr.get_column_references(&mut columns);
-        }
-
-        self.where_clause.get_column_references(&mut columns);
-
-        columns
+        // Any column reference of the expressions is a reference to a column of the input
+        self.input.get_column_references()
     }
 
     fn get_table_references(&self) -> IndexSet<TableRef> {
-        IndexSet::from_iter([self.table.table_ref.clone()])
+        self.input.get_table_references()
     }
 }
 
@@ -151,21 +172,20 @@ impl ProverEvaluate for FilterExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
-        // 1. selection
-        let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
+        // 1. input
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        // 2. selection
+        let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, &input);
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
         let output_length = selection.iter().filter(|b| **b).count();
 
-        // 2. columns
+        // 3. columns
         let columns: Vec<_> = self
             .aliased_results
             .iter()
-            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, &input))
             .collect();
 
         // Compute filtered_columns and indexes
@@ -187,7 +207,6 @@ impl ProverEvaluate for FilterExec {
     }
 
     #[tracing::instrument(name = "FilterExec::final_round_evaluate", level = "debug", skip_all)]
-    #[allow(unused_variables)]
     fn final_round_evaluate<'a, S: Scalar>(
         &self,
         builder: &mut FinalRoundBuilder<'a, S>,
@@ -196,26 +215,25 @@ impl ProverEvaluate for FilterExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
-        // 1. selection
+        // 1. input
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        // 2. selection
         let selection_column: Column<'a, S> =
-            self.where_clause.prover_evaluate(builder, alloc, table);
+            self.where_clause.prover_evaluate(builder, alloc, &input);
         let selection = selection_column
             .as_boolean()
             .expect("selection is not boolean");
         let output_length = selection.iter().filter(|b| **b).count();
 
-        // 2. columns
+        // 3. columns
         let columns: Vec<_> = self
             .aliased_results
             .iter()
-            .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
+            .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, &input))
             .collect();
         // Compute filtered_columns
         let (filtered_columns, result_len) = filter_columns(alloc, &columns, selection);
-        // 3. Produce MLEs
+        // 4. Produce MLEs
         filtered_columns.iter().copied().for_each(|column| {
             builder.produce_intermediate_mle(column);
         });
@@ -231,7 +249,7 @@ impl ProverEvaluate for FilterExec {
             &columns,
             selection,
             &filtered_columns,
-            table.num_rows(),
+            input.num_rows(),
             result_len,
         );
         let res = Table::<'a, S>::try_from_iter_with_options(
@@ -8,12 +8,13 @@ use crate::{
         },
         map::{indexmap, IndexMap, IndexSet},
         math::decimal::Precision,
+        proof::ProofError,
         scalar::Curve25519Scalar,
     },
     sql::{
         proof::{
             exercise_verification, FirstRoundBuilder, ProofPlan, ProvableQueryResult,
-            ProverEvaluate, VerifiableQueryResult,
+            ProverEvaluate, QueryError, VerifiableQueryResult,
         },
         proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr, LiteralExpr, TableExpr},
     },
@@ -449,3 +450,280 @@ fn we_can_prove_a_filter() {
     ]);
     assert_eq!(res, expected);
 }
+
+fn output_column(t: &TableRef, name: &str, column_type: ColumnType) -> DynProofExpr {
+    DynProofExpr::new_column(ColumnRef::new(t.clone(), name.into(), column_type))
+}
+
+/// `select a, sum_c from (select a, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a)
+/// where sum_c > 150`
+#[test]
+fn we_can_prove_a_filter_on_the_groups_of_a_group_by() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 3, -1]),
+        bigint("c", [101, 102, 103, 104, 105, 50]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = filter_with_input(
+        vec![
+            aliased_plan(output_column(&t, "a", ColumnType::BigInt), "a"),
+            aliased_plan(output_column(&t, "sum_c", ColumnType::BigInt), "sum_c"),
+        ],
+        group_by(
+            cols_expr_plan(&t, &["a"], &accessor),
+            vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+            "__count__",
+            tab(&t),
+            const_bool(true),
+        ),
+        not(lte(
+            output_column(&t, "sum_c", ColumnType::BigInt),
+            const_bigint(150),
+        )),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("a", [1, 2]), bigint("sum_c", [205, 205])]);
+    assert_eq!(res, expected);
+}
+
+/// `select __count__ from (select sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 0)
+/// where sum_c >= 0`
+#[test]
+fn we_can_prove_a_filter_on_an_aggregation_without_group_by() {
+    let data = owned_table([bigint("b", [0, 1, 0, 0]), bigint("c", [-5, 100, 2, 1])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let group_by_plan = |where_value: i64| {
+        group_by(
+            vec![],
+            vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+            "__count__",
+            tab(&t),
+            equal(column(&t, "b", &accessor), const_bigint(where_value)),
+        )
+    };
+    let expr = filter_with_input(
+        vec![aliased_plan(
+            output_column(&t, "__count__", ColumnType::BigInt),
+            "__count__",
+        )],
+        group_by_plan(0),
+        gte(
+            output_column(&t, "sum_c", ColumnType::BigInt),
+            const_bigint(0),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("__count__", [0; 0])]));
+
+    let expr = filter_with_input(
+        vec![aliased_plan(
+            output_column(&t, "__count__", ColumnType::BigInt),
+            "__count__",
+        )],
+        group_by_plan(1),
+        gte(
+            output_column(&t, "sum_c", ColumnType::BigInt),
+            const_bigint(0),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("__count__", [1])]));
+}
+
+/// `select b * 2 as d from (select * from sxt.t) where a = 2`
+#[test]
+fn we_can_prove_a_filter_on_a_table_exec() {
+    let data = owned_table([bigint("a", [1, 2, 2, 3]), bigint("b", [4, 5, 6, 7])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = filter_with_input(
+        vec![aliased_plan(
+            multiply(column(&t, "b", &accessor), const_bigint(2)),
+            "d",
+        )],
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("b", ColumnType::BigInt),
+            ],
+        ),
+        equal(column(&t, "a", &accessor), const_bigint(2)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("d", [10, 12])]));
+}
+
+/// `select name, human from sxt.cats join sxt.cat_details on cats.id = cat_details.id
+/// where human = 'Ian'`
+#[test]
+fn we_can_prove_a_filter_on_a_sort_merge_join() {
+    let cats = TableRef::new("sxt", "cats");
+    let cat_details = TableRef::new("sxt", "cat_details");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        cats.clone(),
+        owned_table([
+            bigint("id", [1, 2, 3]),
+            varchar("name", ["Chloe", "Margaret", "Prudence"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        cat_details.clone(),
+        owned_table([
+            bigint("id", [1, 2, 1, 3]),
+            varchar("human", ["Cassia", "Ian", "Ian", "Erik"]),
+        ]),
+        0,
+    );
+    let expr = filter_with_input(
+        vec![
+            aliased_plan(output_column(&cats, "name", ColumnType::VarChar), "name"),
+            aliased_plan(
+                output_column(&cat_details, "human", ColumnType::VarChar),
+                "human",
+            ),
+        ],
+        sort_merge_join(
+            table_exec(
+                cats.clone(),
+                vec![
+                    column_field("id", ColumnType::BigInt),
+                    column_field("name", ColumnType::VarChar),
+                ],
+            ),
+            table_exec(
+                cat_details.clone(),
+                vec![
+                    column_field("id", ColumnType::BigInt),
+                    column_field("human", ColumnType::VarChar),
+                ],
+            ),
+            vec![0],
+            vec![0],
+            vec!["id".into(), "name".into(), "human".into()],
+        ),
+        equal(
+            output_column(&cat_details, "human", ColumnType::VarChar),
+            const_varchar("Ian"),
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &cats);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        varchar("name", ["Chloe", "Margaret"]),
+        varchar("human", ["Ian", "Ian"]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_prove_a_filter_on_a_group_by_a_varchar() {
+    let data = owned_table([varchar("a", ["x", "y", "x"]), bigint("c", [101, 102, 103])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = filter_with_input(
+        vec![aliased_plan(
+            output_column(&t, "a", ColumnType::VarChar),
+            "a",
+        )],
+        group_by(
+            cols_expr_plan(&t, &["a"], &accessor),
+            vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+            "__count__",
+            tab(&t),
+            const_bool(true),
+        ),
+        gte(
+            output_column(&t, "sum_c", ColumnType::BigInt),
+            const_bigint(150),
+        ),
+    );
+    let res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&expr, &accessor, &());
+    assert!(matches!(
+        res.verify(&expr, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::UnsupportedQueryPlan { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_can_get_the_result_fields_and_references_of_a_filter_with_input() {
+    let t = TableRef::new("sxt", "t");
+    let a = ColumnRef::new(t.clone(), "a".into(), ColumnType::BigInt);
+    let b = ColumnRef::new(t.clone(), "b".into(), ColumnType::Int);
+    let expr = filter_with_input(
+        vec![aliased_plan(DynProofExpr::new_column(b.clone()), "c")],
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("b", ColumnType::Int),
+            ],
+        ),
+        equal(DynProofExpr::new_column(a.clone()), const_bigint(1)),
+    );
+    assert_eq!(
+        expr.get_column_result_fields(),
+        vec![ColumnField::new("c".into(), ColumnType::Int)]
+    );
+    assert_eq!(expr.get_column_references(), IndexSet::from_iter([a, b]));
+    assert_eq!(expr.get_table_references(), IndexSet::from_iter([t]));
+}
+
+/// `select x from (select a as x, b as x from sxt.t) where x = 1`
+#[test]
+fn we_cannot_verify_a_filter_over_an_input_whose_columns_have_the_same_name() {
+    let data = owned_table([bigint("a", [1, 2, 1]), bigint("b", [4, 5, 6])]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = filter_with_input(
+        vec![aliased_plan(
+            output_column(&t, "x", ColumnType::BigInt),
+            "x",
+        )],
+        projection(
+            vec![
+                aliased_plan(column(&t, "a", &accessor), "x"),
+                aliased_plan(column(&t, "b", &accessor), "x"),
+            ],
+            table_exec(
+                t.clone(),
+                vec![
+                    column_field("a", ColumnType::BigInt),
+                    column_field("b", ColumnType::BigInt),
+                ],
+            ),
+        ),
+        equal(output_column(&t, "x", ColumnType::BigInt), const_bigint(1)),
+    );
+    let res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&expr, &accessor, &());
+    assert!(matches!(
+        res.verify(&expr, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "input columns do not have unique names"
+            }
+        })
+    ));
+}
@@ -43,9 +43,7 @@ impl ProverEvaluate for DishonestFilterExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.input.first_round_evaluate(builder, alloc, table_map);
         // 1. selection
         let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
         let selection = selection_column
@@ -91,9 +89,7 @@ impl ProverEvaluate for DishonestFilterExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        let table = &self.input.final_round_evaluate(builder, alloc, table_map);
         // 1. selection
         let selection_column: Column<'a, S> =
             self.where_clause.prover_evaluate(builder, alloc, table);
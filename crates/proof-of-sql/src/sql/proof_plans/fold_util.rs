@@ -1,4 +1,12 @@
-use crate::base::{polynomial::MultilinearExtension, scalar::Scalar};
+use crate::base::{database::ColumnType, polynomial::MultilinearExtension, scalar::Scalar};
+use bumpalo::Bump;
+
+/// The maximum number of columns that [`fold_64_bit_columns`] can fold into a single column
+///
+/// Rows of columns `c_0, ..., c_{n - 1}` that each fit into 64 bits are folded as
+/// `±c_0 * 2^(64 * (n - 1)) + ... ± c_{n - 1}`, which preserves the lexicographic order of the rows.
+/// With at most three columns, the folded values stay well within the range of the sign gadget.
+pub(crate) const MAX_NUM_64_BIT_FOLD_COLUMNS: usize = 3;
 
 /// This function takes a set of columns and fold it into a slice of scalars.
 ///
@@ -27,6 +35,37 @@ pub fn fold_vals<S: Scalar>(beta: S, vals: &[S]) -> S {
     vals.iter().fold(S::zero(), |acc, &v| acc * beta + v)
 }
 
+/// Whether columns of the given types can be folded by [`fold_64_bit_columns`]
+pub(crate) fn can_fold_64_bit_columns(column_types: &[ColumnType]) -> bool {
+    column_types.len() <= MAX_NUM_64_BIT_FOLD_COLUMNS
+        && column_types
+            .iter()
+            .all(|column_type| column_type.bit_size() <= 64)
+}
+
+/// Folds columns that each fit into 64 bits into a single column with the same lexicographic order
+///
+/// Each column comes with whether it is ascending. Descending columns are negated, which reverses their order.
+/// See [`MAX_NUM_64_BIT_FOLD_COLUMNS`] for the folding.
+pub(crate) fn fold_64_bit_columns<'a, S: Scalar, C: MultilinearExtension<S>>(
+    alloc: &'a Bump,
+    columns: impl DoubleEndedIterator<Item = (C, bool)>,
+    num_rows: usize,
+) -> &'a [S] {
+    let res = alloc.alloc_slice_fill_copy(num_rows, S::ZERO);
+    for (multiplier, (column, is_asc)) in powers(S::ONE, S::TWO_POW_64).zip(columns.rev()) {
+        column.mul_add(res, &if is_asc { multiplier } else { -multiplier });
+    }
+    res
+}
+
+/// The evaluation of the column that [`fold_64_bit_columns`] folds columns with the given evaluations into
+pub(crate) fn fold_64_bit_column_evals<S: Scalar>(evals: impl Iterator<Item = (S, bool)>) -> S {
+    evals.fold(S::zero(), |acc, (eval, is_asc)| {
+        acc * S::TWO_POW_64 + if is_asc { eval } else { -eval }
+    })
+}
+
 /// Returns an iterator for the lazily evaluated sequence `init, init * base, init * base^2, ...`
 fn powers<S: Scalar>(init: S, base: S) -> impl Iterator<Item = S> {
     core::iter::successors(Some(init), move |&m| Some(m * base))
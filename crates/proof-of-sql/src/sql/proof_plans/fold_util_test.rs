@@ -1,5 +1,11 @@
-use super::{fold_columns, fold_vals};
-use crate::base::{database::Column, math::decimal::Precision, scalar::Curve25519Scalar};
+use super::{
+    can_fold_64_bit_columns, fold_64_bit_column_evals, fold_64_bit_columns, fold_columns, fold_vals,
+};
+use crate::base::{
+    database::{Column, ColumnType},
+    math::decimal::Precision,
+    scalar::{Curve25519Scalar, Scalar},
+};
 use bumpalo::Bump;
 use num_traits::Zero;
 
@@ -119,3 +125,40 @@ fn we_can_fold_vals() {
         (12345).into()
     );
 }
+
+#[test]
+fn we_can_fold_64_bit_columns_and_their_evaluations() {
+    let a_values = [1_i64, -2, 3];
+    let b_values = [4_i32, 5, -6];
+    let a = Column::<Curve25519Scalar>::BigInt(&a_values);
+    let b = Column::<Curve25519Scalar>::Int(&b_values);
+    let alloc = Bump::new();
+    let folded = fold_64_bit_columns(&alloc, [(&a, true), (&b, false)].into_iter(), 3);
+    for ((&value, a_value), b_value) in folded.iter().zip(a_values).zip(b_values) {
+        let (a_eval, b_eval) = (
+            Curve25519Scalar::from(a_value),
+            Curve25519Scalar::from(b_value),
+        );
+        assert_eq!(value, Curve25519Scalar::TWO_POW_64 * a_eval - b_eval);
+        assert_eq!(
+            fold_64_bit_column_evals([(a_eval, true), (b_eval, false)].into_iter()),
+            value
+        );
+    }
+}
+
+#[test]
+fn we_can_only_fold_up_to_three_64_bit_columns() {
+    assert!(can_fold_64_bit_columns(&[]));
+    assert!(can_fold_64_bit_columns(&[
+        ColumnType::BigInt,
+        ColumnType::Boolean,
+        ColumnType::Int
+    ]));
+    assert!(!can_fold_64_bit_columns(&[ColumnType::BigInt; 4]));
+    assert!(!can_fold_64_bit_columns(&[
+        ColumnType::BigInt,
+        ColumnType::Int128
+    ]));
+    assert!(!can_fold_64_bit_columns(&[ColumnType::VarChar]));
+}
@@ -1,15 +1,21 @@
-use super::{fold_columns, fold_vals};
+use super::{
+    fold_columns, fold_vals,
+    projection_exec::input_accessor,
+    sort_exec::{sort_key, sort_key_eval},
+    DynProofPlan, SortExec, TableExec,
+};
 use crate::{
     base::{
         database::{
-            group_by_util::{aggregate_columns, AggregatedColumns},
+            filter_util::filter_column_by_index,
+            group_by_util::{aggregate_columns, group_indexes_by_columns, AggregatedColumns},
             order_by_util::compare_indexes_by_owned_columns,
             Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
             TableRef,
         },
         map::{IndexMap, IndexSet},
         proof::ProofError,
-        scalar::Scalar,
+        scalar::{Scalar, ScalarExt},
         slice_ops,
     },
     sql::{
@@ -17,58 +23,157 @@ use crate::{
             FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
             SumcheckSubpolynomialType, VerificationBuilder,
         },
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_gadgets::{
+            final_round_evaluate_monotonic, first_round_evaluate_monotonic, prover_evaluate_sign,
+            verifier_evaluate_sign, verify_monotonic,
+        },
     },
     utils::log,
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
-use core::iter;
+use core::{cmp::Ordering, iter};
 use num_traits::{One, Zero};
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
 /// Provable expressions for queries of the form
 /// ```ignore
-///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
+///     SELECT <group_by_expr1>.expr as <group_by_expr1>.alias, ...,
+///         <group_by_exprM>.expr as <group_by_exprM>.alias,
 ///         SUM(<sum_expr1>.expr) as <sum_expr1>.alias, ..., SUM(<sum_exprN>.expr) as <sum_exprN>.alias,
+///         MAX(<max_expr1>.expr) as <max_expr1>.alias, ..., MAX(<max_exprK>.expr) as <max_exprK>.alias,
+///         MIN(<min_expr1>.expr) as <min_expr1>.alias, ..., MIN(<min_exprL>.expr) as <min_exprL>.alias,
 ///         COUNT(*) as count_alias
-///     FROM <table>
+///     FROM <input>
 ///     WHERE <where_clause>
-///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
+///     GROUP BY <group_by_expr1>.expr, ..., <group_by_exprM>.expr
 /// ```
 ///
+/// The input is the result of another [`ProofPlan`], e.g. a table, a join or a union,
+/// whose columns the expressions refer to by their names, qualified with any of its tables.
+///
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+/// The groups are ordered by the values of the group by expressions.
+///
+/// If the group by expressions can be folded into a sort key, see [`SortExec::can_sort_by`],
+/// the groups are proven to be unique by the key being strictly increasing,
+/// so that the result can be the input of other plans.
+/// Otherwise their uniqueness is checked on the result, and the plan has to be at the top level.
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct GroupByExec {
-    pub(super) group_by_exprs: Vec<ColumnExpr>,
-    pub(super) sum_expr: Vec<AliasedDynProofExpr>,
-    pub(super) count_alias: Ident,
-    pub(super) table: TableExpr,
-    pub(super) where_clause: DynProofExpr,
+    pub(crate) group_by_exprs: Vec<AliasedDynProofExpr>,
+    pub(crate) sum_expr: Vec<AliasedDynProofExpr>,
+    pub(crate) max_expr: Vec<AliasedDynProofExpr>,
+    pub(crate) min_expr: Vec<AliasedDynProofExpr>,
+    pub(crate) count_alias: Ident,
+    pub(crate) input: Box<DynProofPlan>,
+    pub(crate) where_clause: DynProofExpr,
 }
 
 impl GroupByExec {
-    /// Creates a new `group_by` expression.
+    /// Creates a new `group_by` expression over a table.
+    ///
+    /// Only the columns of the table referenced by the expressions are loaded.
     pub fn new(
-        group_by_exprs: Vec<ColumnExpr>,
+        group_by_exprs: Vec<AliasedDynProofExpr>,
         sum_expr: Vec<AliasedDynProofExpr>,
+        max_expr: Vec<AliasedDynProofExpr>,
+        min_expr: Vec<AliasedDynProofExpr>,
         count_alias: Ident,
         table: TableExpr,
         where_clause: DynProofExpr,
+    ) -> Self {
+        let input = TableExec::from_referenced_columns(
+            table.table_ref,
+            group_by_exprs
+                .iter()
+                .chain(&sum_expr)
+                .chain(&max_expr)
+                .chain(&min_expr)
+                .map(|aliased_expr| &aliased_expr.expr)
+                .chain(iter::once(&where_clause)),
+        );
+        Self::new_with_input(
+            group_by_exprs,
+            sum_expr,
+            max_expr,
+            min_expr,
+            count_alias,
+            Box::new(DynProofPlan::Table(input)),
+            where_clause,
+        )
+    }
+
+    /// Creates a new `group_by` expression over the result of another plan.
+    pub fn new_with_input(
+        group_by_exprs: Vec<AliasedDynProofExpr>,
+        sum_expr: Vec<AliasedDynProofExpr>,
+        max_expr: Vec<AliasedDynProofExpr>,
+        min_expr: Vec<AliasedDynProofExpr>,
+        count_alias: Ident,
+        input: Box<DynProofPlan>,
+        where_clause: DynProofExpr,
     ) -> Self {
         Self {
             group_by_exprs,
             sum_expr,
+            max_expr,
+            min_expr,
             count_alias,
-            table,
+            input,
             where_clause,
         }
     }
+
+    /// Whether the uniqueness of the groups is proven, see [`SortExec::can_sort_by`]
+    ///
+    /// Without group by expressions there is at most one group, whose sort key is zero.
+    pub(crate) fn proves_unique_groups(&self) -> bool {
+        self.group_by_exprs.is_empty()
+            || SortExec::can_sort_by(
+                &self
+                    .group_by_exprs
+                    .iter()
+                    .map(|aliased_expr| aliased_expr.expr.data_type())
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    /// All group by columns in ascending order
+    fn order_by(&self) -> Vec<(usize, bool)> {
+        (0..self.group_by_exprs.len())
+            .map(|index| (index, true))
+            .collect()
+    }
+
+    /// Computes the witnesses for the `MAX` and `MIN` aggregates, in that order.
+    fn compute_extrema<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        group_by_columns: &[Column<'a, S>],
+        selection: &[bool],
+        extremum_columns: &[Column<'a, S>],
+        num_groups: usize,
+    ) -> Vec<Extremum<'a, S>> {
+        let group_indexes = group_indexes_by_columns(group_by_columns, selection);
+        extremum_columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let replace_ordering = if i < self.max_expr.len() {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
+                Extremum::new(alloc, column, &group_indexes, num_groups, replace_ordering)
+            })
+            .collect()
+    }
 }
 
 impl ProofPlan for GroupByExec {
-    #[allow(unused_variables)]
     fn verifier_evaluate<S: Scalar>(
         &self,
         builder: &mut impl VerificationBuilder<S>,
@@ -76,18 +181,25 @@ impl ProofPlan for GroupByExec {
         result: Option<&OwnedTable<S>>,
         chi_eval_map: &IndexMap<TableRef, S>,
     ) -> Result<TableEvaluation<S>, ProofError> {
-        let input_chi_eval = *chi_eval_map
-            .get(&self.table.table_ref)
-            .expect("Chi eval not found");
+        // 0. input
+        let input_eval = self
+            .input
+            .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let input_chi_eval = input_eval.chi_eval();
+        let current_accessor = input_accessor(&self.input, &input_eval)?;
         // 1. selection
-        let where_eval = self
-            .where_clause
-            .verifier_evaluate(builder, accessor, input_chi_eval)?;
+        let where_eval =
+            self.where_clause
+                .verifier_evaluate(builder, &current_accessor, input_chi_eval)?;
         // 2. columns
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, accessor, input_chi_eval))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, &current_accessor, input_chi_eval)
+            })
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
             .sum_expr
@@ -95,7 +207,17 @@ impl ProofPlan for GroupByExec {
             .map(|aliased_expr| {
                 aliased_expr
                     .expr
-                    .verifier_evaluate(builder, accessor, input_chi_eval)
+                    .verifier_evaluate(builder, &current_accessor, input_chi_eval)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let extremum_evals = self
+            .max_expr
+            .iter()
+            .chain(&self.min_expr)
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .verifier_evaluate(builder, &current_accessor, input_chi_eval)
             })
             .collect::<Result<Vec<_>, _>>()?;
         // 3. filtered_columns
@@ -103,31 +225,85 @@ impl ProofPlan for GroupByExec {
             builder.try_consume_final_round_mle_evaluations(self.group_by_exprs.len())?;
         let sum_result_columns_evals =
             builder.try_consume_final_round_mle_evaluations(self.sum_expr.len())?;
+        let extremum_result_columns_evals =
+            builder.try_consume_final_round_mle_evaluations(extremum_evals.len())?;
         let count_column_eval = builder.try_consume_final_round_mle_evaluation()?;
 
         let alpha = builder.try_consume_post_result_challenge()?;
         let beta = builder.try_consume_post_result_challenge()?;
         let output_chi_eval = builder.try_consume_chi_evaluation()?;
 
+        // 4. MAX and MIN witnesses
+        let (expanded_extremum_evals, is_extremum_evals): (Vec<_>, Vec<_>) = extremum_evals
+            .iter()
+            .map(|_| -> Result<_, ProofError> {
+                Ok((
+                    builder.try_consume_final_round_mle_evaluation()?,
+                    builder.try_consume_final_round_mle_evaluation()?,
+                ))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        // Each extremum is an additional group by key, and each group has exactly one row attaining it.
         verify_group_by(
             builder,
             alpha,
             beta,
             input_chi_eval,
             output_chi_eval,
-            (group_by_evals, aggregate_evals, where_eval),
             (
-                group_by_result_columns_evals.clone(),
-                sum_result_columns_evals.clone(),
+                group_by_evals
+                    .into_iter()
+                    .chain(expanded_extremum_evals.iter().copied())
+                    .collect(),
+                aggregate_evals
+                    .into_iter()
+                    .chain(is_extremum_evals.iter().copied())
+                    .collect(),
+                where_eval,
+            ),
+            (
+                group_by_result_columns_evals
+                    .iter()
+                    .chain(&extremum_result_columns_evals)
+                    .copied()
+                    .collect(),
+                sum_result_columns_evals
+                    .iter()
+                    .copied()
+                    .chain(iter::repeat(output_chi_eval).take(extremum_evals.len()))
+                    .collect(),
                 count_column_eval,
             ),
         )?;
+        for (i, value_eval) in extremum_evals.into_iter().enumerate() {
+            let replace_ordering = if i < self.max_expr.len() {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+            verify_extremum(
+                builder,
+                input_chi_eval,
+                where_eval,
+                (value_eval, expanded_extremum_evals[i], is_extremum_evals[i]),
+                replace_ordering,
+            )?;
+        }
+        // 5. The groups are unique
+        let proves_unique_groups = self.proves_unique_groups();
+        if proves_unique_groups {
+            let key_eval = sort_key_eval(&self.order_by(), &group_by_result_columns_evals);
+            verify_monotonic::<S, true, true>(builder, alpha, beta, key_eval, output_chi_eval)?;
+        }
         match result {
             Some(table) => {
                 let cols = self
                     .group_by_exprs
                     .iter()
-                    .map(|col| table.inner_table().get(&col.column_id()))
+                    .map(|aliased_expr| table.inner_table().get(&aliased_expr.alias))
                     .collect::<Option<Vec<_>>>()
                     .ok_or(ProofError::VerificationError {
                         error: "Result does not all correct group by columns.",
@@ -140,9 +316,10 @@ impl ProofPlan for GroupByExec {
                     })?;
                 }
             }
+            None if proves_unique_groups => {}
             None => {
                 Err(ProofError::UnsupportedQueryPlan {
-                    error: "GroupByExec currently only supported at top level of query plan.",
+                    error: "GroupByExec with group by columns that can not be folded into a sort key is only supported at top level of query plan.",
                 })?;
             }
         }
@@ -150,19 +327,21 @@ impl ProofPlan for GroupByExec {
         let column_evals = group_by_result_columns_evals
             .into_iter()
             .chain(sum_result_columns_evals)
+            .chain(extremum_result_columns_evals)
             .chain(iter::once(count_column_eval))
             .collect::<Vec<_>>();
         Ok(TableEvaluation::new(column_evals, output_chi_eval))
     }
 
-    #[allow(clippy::redundant_closure_for_method_calls)]
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.group_by_exprs
             .iter()
-            .map(|col| col.get_column_field())
-            .chain(self.sum_expr.iter().map(|aliased_expr| {
+            .chain(&self.sum_expr)
+            .chain(&self.max_expr)
+            .chain(&self.min_expr)
+            .map(|aliased_expr| {
                 ColumnField::new(aliased_expr.alias.clone(), aliased_expr.expr.data_type())
-            }))
+            })
             .chain(iter::once(ColumnField::new(
                 self.count_alias.clone(),
                 ColumnType::BigInt,
@@ -171,22 +350,12 @@ impl ProofPlan for GroupByExec {
     }
 
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
-        let mut columns = IndexSet::default();
-
-        for col in &self.group_by_exprs {
-            columns.insert(col.get_column_reference());
-        }
-        for aliased_expr in &self.sum_expr {
-            aliased_expr.expr.get_column_references(&mut columns);
-        }
-
-        self.where_clause.get_column_references(&mut columns);
-
-        columns
+        // Any column reference of the expressions is a reference to a column of the input
+        self.input.get_column_references()
     }
 
     fn get_table_references(&self) -> IndexSet<TableRef> {
-        IndexSet::from_iter([self.table.table_ref.clone()])
+        self.input.get_table_references()
     }
 }
 
@@ -200,9 +369,8 @@ impl ProverEvaluate for GroupByExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        // 0. input
+        let table = &self.input.first_round_evaluate(builder, alloc, table_map);
         // 1. selection
         let selection_column: Column<'a, S> = self.where_clause.result_evaluate(alloc, table);
 
@@ -214,13 +382,25 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.result_evaluate(alloc, table))
+            .map(|aliased_expr| {
+                typed_group_by_column(
+                    alloc,
+                    aliased_expr.expr.result_evaluate(alloc, table),
+                    aliased_expr.expr.data_type(),
+                )
+            })
             .collect::<Vec<_>>();
         let sum_columns = self
             .sum_expr
             .iter()
             .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
             .collect::<Vec<_>>();
+        let extremum_columns = self
+            .max_expr
+            .iter()
+            .chain(&self.min_expr)
+            .map(|aliased_expr| aliased_expr.expr.result_evaluate(alloc, table))
+            .collect::<Vec<_>>();
         // Compute filtered_columns
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
@@ -229,6 +409,13 @@ impl ProverEvaluate for GroupByExec {
             ..
         } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
             .expect("columns should be aggregatable");
+        let extrema = self.compute_extrema(
+            alloc,
+            &group_by_columns,
+            selection,
+            &extremum_columns,
+            count_column.len(),
+        );
         let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
         let res = Table::<'a, S>::try_from_iter(
             self.get_column_result_fields()
@@ -238,12 +425,16 @@ impl ProverEvaluate for GroupByExec {
                     group_by_result_columns
                         .into_iter()
                         .chain(sum_result_columns_iter)
+                        .chain(extrema.iter().map(|extremum| extremum.result))
                         .chain(iter::once(Column::BigInt(count_column))),
                 ),
         )
         .expect("Failed to create table from column references");
         builder.request_post_result_challenges(2);
         builder.produce_chi_evaluation_length(count_column.len());
+        if self.proves_unique_groups() {
+            first_round_evaluate_monotonic(builder, count_column.len());
+        }
 
         log::log_memory_usage("End");
 
@@ -251,7 +442,6 @@ impl ProverEvaluate for GroupByExec {
     }
 
     #[tracing::instrument(name = "GroupByExec::final_round_evaluate", level = "debug", skip_all)]
-    #[allow(unused_variables)]
     fn final_round_evaluate<'a, S: Scalar>(
         &self,
         builder: &mut FinalRoundBuilder<'a, S>,
@@ -260,9 +450,8 @@ impl ProverEvaluate for GroupByExec {
     ) -> Table<'a, S> {
         log::log_memory_usage("Start");
 
-        let table = table_map
-            .get(&self.table.table_ref)
-            .expect("Table not found");
+        // 0. input
+        let table = &self.input.final_round_evaluate(builder, alloc, table_map);
         // 1. selection
         let selection_column: Column<'a, S> =
             self.where_clause.prover_evaluate(builder, alloc, table);
@@ -274,13 +463,25 @@ impl ProverEvaluate for GroupByExec {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.prover_evaluate(builder, alloc, table))
+            .map(|aliased_expr| {
+                typed_group_by_column(
+                    alloc,
+                    aliased_expr.expr.prover_evaluate(builder, alloc, table),
+                    aliased_expr.expr.data_type(),
+                )
+            })
             .collect::<Vec<_>>();
         let sum_columns = self
             .sum_expr
             .iter()
             .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
             .collect::<Vec<_>>();
+        let extremum_columns = self
+            .max_expr
+            .iter()
+            .chain(&self.min_expr)
+            .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, table))
+            .collect::<Vec<_>>();
         // 3. Compute filtered_columns
         let AggregatedColumns {
             group_by_columns: group_by_result_columns,
@@ -289,6 +490,13 @@ impl ProverEvaluate for GroupByExec {
             ..
         } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
             .expect("columns should be aggregatable");
+        let extrema = self.compute_extrema(
+            alloc,
+            &group_by_columns,
+            selection,
+            &extremum_columns,
+            count_column.len(),
+        );
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
@@ -299,6 +507,7 @@ impl ProverEvaluate for GroupByExec {
             .clone()
             .into_iter()
             .chain(sum_result_columns_iter)
+            .chain(extrema.iter().map(|extremum| extremum.result))
             .chain(iter::once(Column::BigInt(count_column)));
         let res = Table::<'a, S>::try_from_iter(
             self.get_column_result_fields()
@@ -311,16 +520,68 @@ impl ProverEvaluate for GroupByExec {
         for column in columns {
             builder.produce_intermediate_mle(column);
         }
-        // 6. Prove group by
+        for extremum in &extrema {
+            builder.produce_intermediate_mle(extremum.expanded);
+            builder.produce_intermediate_mle(extremum.is_extremum);
+        }
+        // 6. Prove group by, where each extremum is an additional group by key
+        // and each group has exactly one row attaining it.
+        let chi_m = alloc.alloc_slice_fill_copy(count_column.len(), S::one());
         prove_group_by(
             builder,
             alloc,
             alpha,
             beta,
-            (&group_by_columns, &sum_columns, selection),
-            (&group_by_result_columns, &sum_result_columns, count_column),
+            (
+                &group_by_columns
+                    .iter()
+                    .copied()
+                    .chain(
+                        extrema
+                            .iter()
+                            .map(|extremum| Column::Scalar(extremum.expanded)),
+                    )
+                    .collect::<Vec<_>>(),
+                &sum_columns
+                    .iter()
+                    .copied()
+                    .chain(
+                        extrema
+                            .iter()
+                            .map(|extremum| Column::Boolean(extremum.is_extremum)),
+                    )
+                    .collect::<Vec<_>>(),
+                selection,
+            ),
+            (
+                &group_by_result_columns
+                    .iter()
+                    .copied()
+                    .chain(extrema.iter().map(|extremum| extremum.result))
+                    .collect::<Vec<_>>(),
+                &sum_result_columns
+                    .iter()
+                    .copied()
+                    .chain(iter::repeat(chi_m as &[_]).take(extrema.len()))
+                    .collect::<Vec<_>>(),
+                count_column,
+            ),
             table.num_rows(),
         );
+        // 7. Prove that the extrema bound every selected row of their group
+        for extremum in &extrema {
+            prove_extremum(builder, alloc, selection, extremum);
+        }
+        // 8. Prove that the groups are unique
+        if self.proves_unique_groups() {
+            let key = sort_key(
+                alloc,
+                &self.order_by(),
+                &group_by_result_columns,
+                count_column.len(),
+            );
+            final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, key);
+        }
 
         log::log_memory_usage("End");
 
@@ -328,8 +589,25 @@ impl ProverEvaluate for GroupByExec {
     }
 }
 
+/// Converts a group by column that is evaluated as scalars to its data type,
+/// so that the groups are ordered the same way as the columns of the verified result.
+///
+/// Values that do not fit into the data type are kept as scalars, and fail verification.
+fn typed_group_by_column<'a, S: Scalar>(
+    alloc: &'a Bump,
+    column: Column<'a, S>,
+    data_type: ColumnType,
+) -> Column<'a, S> {
+    match column {
+        Column::Scalar(scalars) => {
+            Column::try_from_scalars(alloc, scalars, data_type).unwrap_or(column)
+        }
+        _ => column,
+    }
+}
+
 #[allow(clippy::unnecessary_wraps)]
-fn verify_group_by<S: Scalar>(
+pub(super) fn verify_group_by<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     alpha: S,
     beta: S,
@@ -466,3 +744,157 @@ pub fn prove_group_by<'a, S: Scalar>(
         ],
     );
 }
+
+/// The witness for a `MAX` or `MIN` aggregate.
+struct Extremum<'a, S: Scalar> {
+    /// The aggregated column as scalars
+    values: &'a [S],
+    /// The extremum of each group
+    result: Column<'a, S>,
+    /// The extremum of the group of each selected row, and zero for rows that are not selected
+    expanded: &'a [S],
+    /// Whether the row is the first selected row of its group attaining the extremum
+    is_extremum: &'a [bool],
+    /// The ordering that a value must have compared to the current extremum in order to replace it,
+    /// i.e. `Greater` for `MAX` and `Less` for `MIN`
+    replace_ordering: Ordering,
+}
+
+impl<'a, S: Scalar> Extremum<'a, S> {
+    fn new(
+        alloc: &'a Bump,
+        column: &Column<'a, S>,
+        group_indexes: &[Option<usize>],
+        num_groups: usize,
+        replace_ordering: Ordering,
+    ) -> Self {
+        let values: &'a [S] = alloc.alloc_slice_copy(&column.to_scalar_with_scaling(0));
+        let mut extremum_indexes: Vec<Option<usize>> = vec![None; num_groups];
+        for (i, group_index) in group_indexes.iter().enumerate() {
+            if let Some(g) = *group_index {
+                let is_new_extremum = match extremum_indexes[g] {
+                    None => true,
+                    Some(j) => values[i].signed_cmp(&values[j]) == replace_ordering,
+                };
+                if is_new_extremum {
+                    extremum_indexes[g] = Some(i);
+                }
+            }
+        }
+        let extremum_indexes: Vec<usize> = extremum_indexes
+            .into_iter()
+            .map(|i| i.expect("every group has at least one selected row"))
+            .collect();
+        let expanded = alloc.alloc_slice_fill_with(values.len(), |i| {
+            group_indexes[i].map_or(S::zero(), |g| values[extremum_indexes[g]])
+        });
+        let is_extremum = alloc.alloc_slice_fill_with(values.len(), |i| {
+            group_indexes[i].is_some_and(|g| extremum_indexes[g] == i)
+        });
+        Self {
+            values,
+            result: filter_column_by_index(alloc, column, &extremum_indexes),
+            expanded,
+            is_extremum,
+            replace_ordering,
+        }
+    }
+}
+
+/// `1` for `MAX` and `-1` for `MIN`
+fn extremum_sign<S: Scalar>(replace_ordering: Ordering) -> S {
+    match replace_ordering {
+        Ordering::Less => -S::one(),
+        _ => S::one(),
+    }
+}
+
+/// Prove that the extremum of each group is attained by a selected row of the group
+/// (together with the group by argument) and that it bounds every selected row of the group.
+///
+/// With `sign = 1` for `MAX` and `sign = -1` for `MIN`, the argument consists of
+/// 1. `is_extremum * (value - expanded) = 0`
+/// 2. `diff = sign * (expanded - sel * value) >= 0`
+fn prove_extremum<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    selection: &'a [bool],
+    extremum: &Extremum<'a, S>,
+) {
+    let sign = extremum_sign::<S>(extremum.replace_ordering);
+
+    // subpolynomial: is_extremum * value - is_extremum * expanded
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(extremum.is_extremum), Box::new(extremum.values)],
+            ),
+            (
+                -S::one(),
+                vec![Box::new(extremum.is_extremum), Box::new(extremum.expanded)],
+            ),
+        ],
+    );
+
+    // diff
+    let diff: &'a [S] = alloc.alloc_slice_fill_with(extremum.values.len(), |i| {
+        if selection[i] {
+            sign * (extremum.expanded[i] - extremum.values[i])
+        } else {
+            sign * extremum.expanded[i]
+        }
+    });
+    builder.produce_intermediate_mle(diff);
+
+    // subpolynomial: diff - sign * expanded + sign * sel * value
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff)]),
+            (-sign, vec![Box::new(extremum.expanded)]),
+            (sign, vec![Box::new(selection), Box::new(extremum.values)]),
+        ],
+    );
+
+    // diff >= 0
+    prover_evaluate_sign(builder, alloc, diff);
+}
+
+/// Verify the extremum of each group. See [`prove_extremum`].
+fn verify_extremum<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    input_chi_eval: S,
+    sel_in_eval: S,
+    (value_eval, expanded_eval, is_extremum_eval): (S, S, S),
+    replace_ordering: Ordering,
+) -> Result<(), ProofError> {
+    let sign = extremum_sign::<S>(replace_ordering);
+
+    // subpolynomial: is_extremum * value - is_extremum * expanded
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        is_extremum_eval * value_eval - is_extremum_eval * expanded_eval,
+        2,
+    )?;
+
+    // diff
+    let diff_eval = builder.try_consume_final_round_mle_evaluation()?;
+
+    // subpolynomial: diff - sign * expanded + sign * sel * value
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        diff_eval - sign * expanded_eval + sign * sel_in_eval * value_eval,
+        2,
+    )?;
+
+    // diff >= 0
+    let diff_is_negative_eval = verifier_evaluate_sign(builder, diff_eval, input_chi_eval, None)?;
+    if diff_is_negative_eval != S::ZERO {
+        return Err(ProofError::VerificationError {
+            error: "extremum does not bound its group",
+        });
+    }
+    Ok(())
+}
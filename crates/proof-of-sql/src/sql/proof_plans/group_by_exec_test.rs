@@ -2,14 +2,19 @@ use super::test_utility::*;
 use crate::{
     base::{
         commitment::InnerProductProof,
-        database::{owned_table_utility::*, OwnedTableTestAccessor, TableRef, TestAccessor},
+        database::{
+            owned_table_utility::*, ColumnRef, ColumnType, OwnedTableTestAccessor, TableRef,
+            TestAccessor,
+        },
+        proof::ProofError,
         scalar::Curve25519Scalar,
     },
     sql::{
-        proof::{exercise_verification, VerifiableQueryResult},
-        proof_exprs::test_utility::*,
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
     },
 };
+use proof_of_sql_parser::posql_time::{PoSQLTimeField, PoSQLTimeUnit, PoSQLTimeZone};
 
 /// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a`
 #[test]
@@ -23,7 +28,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t.clone(), data, 0);
     let expr = group_by(
-        cols_expr(&t, &["a"], &accessor),
+        cols_expr_plan(&t, &["a"], &accessor),
         vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
         "__count__",
         tab(&t),
@@ -52,7 +57,7 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t.clone(), data, 0);
     let expr = group_by(
-        cols_expr(&t, &["a"], &accessor),
+        cols_expr_plan(&t, &["a"], &accessor),
         vec![sum_expr(
             add(
                 multiply(column(&t, "c", &accessor), const_bigint(2)),
@@ -75,6 +80,75 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+/// `select a + b as s, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a + b`
+#[test]
+fn we_can_prove_a_group_by_an_expression_with_negative_keys() {
+    let data = owned_table([
+        bigint("a", [1, -2, 3, -4, 0]),
+        bigint("b", [-1, 2, -5, 4, -2]),
+        bigint("c", [1, 2, 3, 4, 5]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        vec![aliased_plan(
+            add(column(&t, "a", &accessor), column(&t, "b", &accessor)),
+            "s",
+        )],
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("s", [-2, 0]),
+        bigint("sum_c", [3 + 5, 1 + 2 + 4]),
+        bigint("__count__", [2, 3]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select date_trunc('day', a) as d, count(*) as __count__ from sxt.t group by date_trunc('day', a)`
+#[test]
+fn we_can_prove_a_group_by_a_truncated_timestamp() {
+    let data = owned_table([timestamptz(
+        "a",
+        PoSQLTimeUnit::Second,
+        PoSQLTimeZone::utc(),
+        [-1_i64, 1, 86_401, -86_399],
+    )]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by(
+        vec![aliased_plan(
+            date_trunc(column(&t, "a", &accessor), PoSQLTimeField::Day),
+            "d",
+        )],
+        vec![],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::utc(),
+            [-86_400_i64, 0, 86_400],
+        ),
+        bigint("__count__", [2, 1, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
 #[allow(clippy::too_many_lines)]
 #[test]
 fn we_can_prove_a_complex_group_by_query_with_many_columns() {
@@ -160,7 +234,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     //  FROM sxt.t WHERE int128_filter = 1020 AND varchar_filter = 'f2'
     //  GROUP BY scalar_group, int128_group, bigint_group
     let expr = group_by(
-        cols_expr(
+        cols_expr_plan(
             &t,
             &["scalar_group", "int128_group", "bigint_group"],
             &accessor,
@@ -230,3 +304,259 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     ]);
     assert_eq!(res, expected);
 }
+
+/// `select a, sum(c) as sum_c, max(c) as max_c, min(c) as min_c, count(*) as __count__ from sxt.t where b = 99 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_max_and_min() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3, 1]),
+        bigint("b", [99, 99, 99, 99, 0, 99, 99]),
+        bigint("c", [-101, 102, -103, 104, 105, 0, 104]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_max_min(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![sum_expr(column(&t, "c", &accessor), "sum_c")],
+        vec![max_expr(column(&t, "c", &accessor), "max_c")],
+        vec![min_expr(column(&t, "c", &accessor), "min_c")],
+        "__count__",
+        tab(&t),
+        equal(column(&t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("sum_c", [-101 + 104 + 104, 102 - 103, 0]),
+        bigint("max_c", [104, 102, 0]),
+        bigint("min_c", [-101, -103, 0]),
+        bigint("__count__", [3, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select max(c * 2 - b) as max_c, min(d) as min_d, max(d) as max_d, count(*) as __count__ from sxt.t`
+#[test]
+fn we_can_prove_max_and_min_of_expressions_and_booleans_without_group_by() {
+    let data = owned_table([
+        bigint("b", [1, 2, 3, 4]),
+        int128("c", [5, -7, 9, 3]),
+        boolean("d", [true, false, true, true]),
+    ]);
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t.clone(), data, 0);
+    let expr = group_by_with_max_min(
+        vec![],
+        vec![],
+        vec![
+            max_expr(
+                subtract(
+                    multiply(column(&t, "c", &accessor), const_bigint(2)),
+                    column(&t, "b", &accessor),
+                ),
+                "max_c",
+            ),
+            max_expr(column(&t, "d", &accessor), "max_d"),
+        ],
+        vec![min_expr(column(&t, "d", &accessor), "min_d")],
+        "__count__",
+        tab(&t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        int128("max_c", [15]),
+        boolean("max_d", [true]),
+        boolean("min_d", [false]),
+        bigint("__count__", [4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select owner_id, sum(weight) as sum_weight, count(*) as __count__
+/// from sxt.cats join sxt.cat_details on cats.id = cat_details.id where weight > 3 group by owner_id`
+#[test]
+fn we_can_prove_a_group_by_on_a_sort_merge_join() {
+    let cats = TableRef::new("sxt", "cats");
+    let cat_details = TableRef::new("sxt", "cat_details");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        cats.clone(),
+        owned_table([bigint("id", [1, 2, 3, 4]), bigint("weight", [4, 5, 3, 6])]),
+        0,
+    );
+    accessor.add_table(
+        cat_details.clone(),
+        owned_table([
+            bigint("id", [1, 2, 3, 4, 5]),
+            bigint("owner_id", [10, 20, 10, 10, 20]),
+        ]),
+        0,
+    );
+    let expr = group_by_with_input(
+        vec![col_expr_plan(&cat_details, "owner_id", &accessor)],
+        vec![sum_expr(column(&cats, "weight", &accessor), "sum_weight")],
+        "__count__",
+        sort_merge_join(
+            table_exec(
+                cats.clone(),
+                vec![
+                    column_field("id", ColumnType::BigInt),
+                    column_field("weight", ColumnType::BigInt),
+                ],
+            ),
+            table_exec(
+                cat_details.clone(),
+                vec![
+                    column_field("id", ColumnType::BigInt),
+                    column_field("owner_id", ColumnType::BigInt),
+                ],
+            ),
+            vec![0],
+            vec![0],
+            vec!["id".into(), "weight".into(), "owner_id".into()],
+        ),
+        not(lte(column(&cats, "weight", &accessor), const_bigint(3))),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &cats);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("owner_id", [10, 20]),
+        bigint("sum_weight", [4 + 6, 5]),
+        bigint("__count__", [2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, sum(b) as sum_b, count(*) as __count__
+/// from (select a, b from sxt.t union all select a, b from sxt.u) group by a`
+#[test]
+fn we_can_prove_a_group_by_on_a_union() {
+    let t = TableRef::new("sxt", "t");
+    let u = TableRef::new("sxt", "u");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 1]), bigint("b", [1, 2, 3])]),
+        0,
+    );
+    accessor.add_table(
+        u.clone(),
+        owned_table([bigint("a", [3, 1]), bigint("b", [4, 5])]),
+        0,
+    );
+    let schema = vec![
+        column_field("a", ColumnType::BigInt),
+        column_field("b", ColumnType::BigInt),
+    ];
+    let expr = group_by_with_input(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![sum_expr(column(&t, "b", &accessor), "sum_b")],
+        "__count__",
+        union_exec(
+            vec![
+                table_exec(t.clone(), schema.clone()),
+                table_exec(u.clone(), schema.clone()),
+            ],
+            schema,
+        ),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("sum_b", [1 + 3 + 5, 2, 4]),
+        bigint("__count__", [3, 1, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, sum(b) as sum_b, count(*) as __count__
+/// from (select a, b from sxt.t limit 3 offset 1) group by a`
+#[test]
+fn we_can_prove_a_group_by_on_a_slice() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 1, 2, 1]), bigint("b", [1, 2, 3, 4, 5])]),
+        0,
+    );
+    let expr = group_by_with_input(
+        cols_expr_plan(&t, &["a"], &accessor),
+        vec![sum_expr(column(&t, "b", &accessor), "sum_b")],
+        "__count__",
+        slice_exec(
+            table_exec(
+                t.clone(),
+                vec![
+                    column_field("a", ColumnType::BigInt),
+                    column_field("b", ColumnType::BigInt),
+                ],
+            ),
+            1,
+            Some(3),
+        ),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2]),
+        bigint("sum_b", [3, 2 + 4]),
+        bigint("__count__", [1, 2]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select x, sum(x) as sum_x, count(*) as __count__
+/// from (select a as x, b as x from sxt.t) group by x`
+#[test]
+fn we_cannot_verify_a_group_by_over_an_input_whose_columns_have_the_same_name() {
+    let t = TableRef::new("sxt", "t");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        t.clone(),
+        owned_table([bigint("a", [1, 2, 1]), bigint("b", [4, 5, 6])]),
+        0,
+    );
+    let x = DynProofExpr::new_column(ColumnRef::new(t.clone(), "x".into(), ColumnType::BigInt));
+    let expr = group_by_with_input(
+        vec![aliased_plan(x.clone(), "x")],
+        vec![sum_expr(x, "sum_x")],
+        "__count__",
+        projection(
+            vec![
+                aliased_plan(column(&t, "a", &accessor), "x"),
+                aliased_plan(column(&t, "b", &accessor), "x"),
+            ],
+            table_exec(
+                t.clone(),
+                vec![
+                    column_field("a", ColumnType::BigInt),
+                    column_field("b", ColumnType::BigInt),
+                ],
+            ),
+        ),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    assert!(matches!(
+        res.verify(&expr, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError {
+                error: "input columns do not have unique names"
+            }
+        })
+    ));
+}
@@ -24,8 +24,16 @@ mod filter_exec_test;
 #[cfg(all(test, feature = "blitzar"))]
 mod filter_exec_test_dishonest_prover;
 
+mod distinct_exec;
+pub use distinct_exec::DistinctExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod distinct_exec_test;
+
 mod fold_util;
-pub(crate) use fold_util::{fold_columns, fold_vals};
+pub(crate) use fold_util::{
+    can_fold_64_bit_columns, fold_64_bit_column_evals, fold_64_bit_columns, fold_columns,
+    fold_vals, MAX_NUM_64_BIT_FOLD_COLUMNS,
+};
 #[cfg(test)]
 mod fold_util_test;
 
@@ -40,16 +48,27 @@ pub(crate) use slice_exec::SliceExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod slice_exec_test;
 
+mod sort_exec;
+pub use sort_exec::SortExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod sort_exec_test;
+
 mod union_exec;
 pub(crate) use union_exec::UnionExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod union_exec_test;
 
 mod sort_merge_join_exec;
-pub(crate) use sort_merge_join_exec::SortMergeJoinExec;
+pub use sort_merge_join_exec::JoinType;
+pub(crate) use sort_merge_join_exec::{verify_join_column_types, SortMergeJoinExec};
 #[cfg(all(test, feature = "blitzar"))]
 mod sort_merge_join_exec_test;
 
+mod semi_join_exec;
+pub use semi_join_exec::SemiJoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod semi_join_exec_test;
+
 mod dyn_proof_plan;
 pub use dyn_proof_plan::DynProofPlan;
 
@@ -24,10 +24,12 @@ use serde::{Deserialize, Serialize};
 /// ```ignore
 ///     SELECT <result_expr1>, ..., <result_exprN> FROM <input>
 /// ```
+///
+/// The input may be the result of any plan, e.g. a join or a union of several tables.
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ProjectionExec {
-    pub(super) aliased_results: Vec<AliasedDynProofExpr>,
-    pub(super) input: Box<DynProofPlan>,
+    pub(crate) aliased_results: Vec<AliasedDynProofExpr>,
+    pub(crate) input: Box<DynProofPlan>,
 }
 
 impl ProjectionExec {
@@ -41,7 +43,6 @@ impl ProjectionExec {
 }
 
 impl ProofPlan for ProjectionExec {
-    #[allow(unused_variables)]
     fn verifier_evaluate<S: Scalar>(
         &self,
         builder: &mut impl VerificationBuilder<S>,
@@ -54,37 +55,7 @@ impl ProofPlan for ProjectionExec {
             .input
             .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
         let chi_eval = input_eval.chi_eval();
-        // Build new accessors
-        // TODO: Make this work with inputs with multiple tables such as join
-        // and union results
-        let input_schema = self.input.get_column_result_fields();
-        let input_table_refs = self.input.get_table_references();
-        if input_table_refs.len() > 1 {
-            return Err(ProofError::UnsupportedQueryPlan {
-                error: "Projections with multiple tables are not supported yet",
-            });
-        }
-        // Covers the case of tablelessness
-        let input_table_ref = if let Some(table_ref) = input_table_refs.first() {
-            table_ref.clone()
-        } else {
-            TableRef::from_names(None, "empty")
-        };
-        let current_accessor = input_schema
-            .iter()
-            .zip(input_eval.column_evals())
-            .map(|(field, eval)| {
-                (
-                    ColumnRef::new(
-                        input_table_ref.clone(),
-                        field.name().clone(),
-                        field.data_type(),
-                    ),
-                    *eval,
-                )
-            })
-            .collect::<IndexMap<_, _>>();
-
+        let current_accessor = input_accessor(&self.input, &input_eval)?;
         let output_column_evals = self
             .aliased_results
             .iter()
@@ -116,6 +87,47 @@ impl ProofPlan for ProjectionExec {
     }
 }
 
+/// Builds the accessor that the expressions over the result of `input` are evaluated with.
+///
+/// The columns of the result must have unique names, so that the expressions may qualify them
+/// with any of the tables referenced by `input`, or with a placeholder table if there are none.
+///
+/// # Errors
+/// Returns an error if two columns of the result of `input` have the same name.
+pub(super) fn input_accessor<S: Scalar>(
+    input: &DynProofPlan,
+    input_eval: &TableEvaluation<S>,
+) -> Result<IndexMap<ColumnRef, S>, ProofError> {
+    let input_schema = input.get_column_result_fields();
+    if input_schema
+        .iter()
+        .enumerate()
+        .any(|(i, field)| input_schema[..i].iter().any(|f| f.name() == field.name()))
+    {
+        return Err(ProofError::VerificationError {
+            error: "input columns do not have unique names",
+        });
+    }
+    let mut input_table_refs = input.get_table_references();
+    if input_table_refs.is_empty() {
+        input_table_refs.insert(TableRef::from_names(None, "empty"));
+    }
+    Ok(input_table_refs
+        .iter()
+        .flat_map(|table_ref| {
+            input_schema
+                .iter()
+                .zip(input_eval.column_evals())
+                .map(move |(field, eval)| {
+                    (
+                        ColumnRef::new(table_ref.clone(), field.name(), field.data_type()),
+                        *eval,
+                    )
+                })
+        })
+        .collect())
+}
+
 impl ProverEvaluate for ProjectionExec {
     #[tracing::instrument(
         name = "ProjectionExec::first_round_evaluate",
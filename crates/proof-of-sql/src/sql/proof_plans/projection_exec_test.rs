@@ -1,13 +1,15 @@
-use super::{test_utility::*, DynProofPlan, ProjectionExec};
+use super::{projection_exec::input_accessor, test_utility::*, DynProofPlan, ProjectionExec};
 use crate::{
     base::{
         database::{
             owned_table_utility::*, table_utility::*, ColumnField, ColumnRef, ColumnType,
-            OwnedTable, OwnedTableTestAccessor, TableRef, TableTestAccessor, TestAccessor,
+            OwnedTable, OwnedTableTestAccessor, TableEvaluation, TableRef, TableTestAccessor,
+            TestAccessor,
         },
         map::{indexmap, IndexMap, IndexSet},
         math::decimal::Precision,
-        scalar::Curve25519Scalar,
+        proof::ProofError,
+        scalar::{Curve25519Scalar, Scalar},
     },
     sql::{
         proof::{
@@ -467,3 +469,144 @@ fn we_can_prove_a_projection() {
     ]);
     assert_eq!(res, expected);
 }
+
+#[test]
+fn we_can_prove_a_projection_over_a_sort_merge_join() {
+    let left = owned_table([
+        bigint("id", [1_i64, 2, 3, 4, 5]),
+        varchar("name", ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper"]),
+    ]);
+    let table_left = TableRef::new("sxt", "cats");
+    let right = owned_table([
+        bigint("id", [1_i64, 2, 98, 4, 1, 2, 7]),
+        varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Gretta", "Ian", "Ian", "Erik"],
+        ),
+    ]);
+    let table_right = TableRef::new("sxt", "cat_details");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let join_column = |table: &TableRef, name: &str, column_type| {
+        DynProofExpr::new_column(ColumnRef::new(table.clone(), name.into(), column_type))
+    };
+    let expr = projection(
+        vec![
+            aliased_plan(
+                join_column(&table_left, "name", ColumnType::VarChar),
+                "name",
+            ),
+            aliased_plan(
+                equal(
+                    join_column(&table_right, "human", ColumnType::VarChar),
+                    const_varchar("Ian"),
+                ),
+                "is_ian",
+            ),
+            aliased_plan(
+                add(
+                    join_column(&table_left, "id", ColumnType::BigInt),
+                    const_bigint(1),
+                ),
+                "next_id",
+            ),
+        ],
+        sort_merge_join(
+            table_exec(
+                table_left.clone(),
+                vec![
+                    ColumnField::new("id".into(), ColumnType::BigInt),
+                    ColumnField::new("name".into(), ColumnType::VarChar),
+                ],
+            ),
+            table_exec(
+                table_right.clone(),
+                vec![
+                    ColumnField::new("id".into(), ColumnType::BigInt),
+                    ColumnField::new("human".into(), ColumnType::VarChar),
+                ],
+            ),
+            vec![0],
+            vec![0],
+            vec![Ident::new("id"), Ident::new("name"), Ident::new("human")],
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &table_left);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        varchar("name", ["Chloe", "Chloe", "Margaret", "Margaret", "Lucy"]),
+        boolean("is_ian", [false, true, false, true, false]),
+        bigint("next_id", [2_i64, 2, 3, 3, 5]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_can_prove_a_projection_over_a_union() {
+    let t0 = TableRef::new("sxt", "t0");
+    let t1 = TableRef::new("sxt", "t1");
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t0.clone(), owned_table([bigint("a0", [1_i64, 2])]), 0);
+    accessor.add_table(t1.clone(), owned_table([bigint("a1", [3_i64])]), 0);
+    let expr = projection(
+        vec![aliased_plan(
+            multiply(
+                DynProofExpr::new_column(ColumnRef::new(
+                    t1.clone(),
+                    "a".into(),
+                    ColumnType::BigInt,
+                )),
+                const_bigint(2),
+            ),
+            "double_a",
+        )],
+        union_exec(
+            vec![
+                table_exec(t0.clone(), vec![column_field("a0", ColumnType::BigInt)]),
+                table_exec(t1, vec![column_field("a1", ColumnType::BigInt)]),
+            ],
+            vec![column_field("a", ColumnType::BigInt)],
+        ),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, &t0);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([bigint("double_a", [2_i64, 4, 6])]);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn we_cannot_evaluate_expressions_over_an_input_whose_columns_have_the_same_name() {
+    let t = TableRef::new("sxt", "t");
+    let input = projection(
+        vec![
+            aliased_plan(
+                DynProofExpr::new_column(ColumnRef::new(t.clone(), "a".into(), ColumnType::BigInt)),
+                "x",
+            ),
+            aliased_plan(
+                DynProofExpr::new_column(ColumnRef::new(t.clone(), "b".into(), ColumnType::BigInt)),
+                "x",
+            ),
+        ],
+        table_exec(
+            t,
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("b", ColumnType::BigInt),
+            ],
+        ),
+    );
+    let input_eval = TableEvaluation::new(
+        vec![Curve25519Scalar::ONE, Curve25519Scalar::TWO],
+        Curve25519Scalar::ONE,
+    );
+    assert!(matches!(
+        input_accessor(&input, &input_eval),
+        Err(ProofError::VerificationError {
+            error: "input columns do not have unique names"
+        })
+    ));
+}
@@ -0,0 +1,364 @@
+use super::{
+    fold_64_bit_column_evals,
+    sort_merge_join_exec::{
+        final_round_evaluate_rows_by_match, first_round_evaluate_rows_by_match, fold_join_key,
+        verify_join_column_types, verify_rows_by_match,
+    },
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        database::{
+            join_util::{
+                get_columns_of_table, get_matched_rows_of_table, get_sort_merge_join_indexes,
+                get_unmatched_rows_of_table, ordered_set_union,
+            },
+            slice_operation::apply_slice_to_indexes,
+            Column, ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions,
+            TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_membership_check, final_round_evaluate_monotonic,
+            first_round_evaluate_membership_check, first_round_evaluate_monotonic,
+            verify_membership_check, verify_monotonic,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> WHERE (col1, ..., colN) [NOT] IN (SELECT col1, ..., colN FROM <ProofPlan>)
+/// ```
+///
+/// The result consists of the rows of the left input, in their original order, whose join columns
+/// match a row of the right input, or, for an anti join, match no row of the right input.
+/// Like the unmatched rows of an outer [`SortMergeJoinExec`](super::SortMergeJoinExec) they are
+/// proven with membership checks against the ordered set union `U` of the join columns of both inputs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SemiJoinExec {
+    pub(crate) left: Box<DynProofPlan>,
+    pub(crate) right: Box<DynProofPlan>,
+    pub(crate) left_join_column_indexes: Vec<usize>,
+    pub(crate) right_join_column_indexes: Vec<usize>,
+    /// Whether the rows of the left input without a match are returned instead
+    #[serde(default)]
+    pub(crate) is_anti_join: bool,
+}
+
+impl SemiJoinExec {
+    /// Create a new `SemiJoinExec` with the given left and right plans
+    ///
+    /// # Panics
+    /// Panics if one of the following conditions is met:
+    /// - There are no join columns
+    /// - The join column index is out of bounds
+    /// - The number of join columns is different
+    pub fn new(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        is_anti_join: bool,
+    ) -> Self {
+        let num_columns_left = left.get_column_result_fields().len();
+        let num_columns_right = right.get_column_result_fields().len();
+        assert!(
+            !left_join_column_indexes.is_empty(),
+            "Join requires at least one join column"
+        );
+        assert!(
+            left_join_column_indexes
+                .iter()
+                .all(|&index| index < num_columns_left)
+                && right_join_column_indexes
+                    .iter()
+                    .all(|&index| index < num_columns_right),
+            "Join column index out of bounds"
+        );
+        assert_eq!(
+            left_join_column_indexes.len(),
+            right_join_column_indexes.len(),
+            "Join columns should have the same number of columns"
+        );
+        Self {
+            left,
+            right,
+            left_join_column_indexes,
+            right_join_column_indexes,
+            is_anti_join,
+        }
+    }
+
+    /// Whether the rows of the left input without a match are returned instead
+    #[must_use]
+    pub fn is_anti_join(&self) -> bool {
+        self.is_anti_join
+    }
+
+    /// The rows of `\hat{L}` that are part of the result, in their original order
+    fn selected_rows<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        left_hat: &Table<'a, S>,
+        c_l: &[Column<'a, S>],
+        c_r: &[Column<'a, S>],
+        num_rows_right: usize,
+    ) -> Vec<Column<'a, S>> {
+        let matched_row_indexes =
+            get_sort_merge_join_indexes(c_l, c_r, left_hat.num_rows(), num_rows_right)
+                .into_iter()
+                .map(|(left_index, _)| left_index)
+                .collect::<Vec<_>>();
+        if self.is_anti_join {
+            get_unmatched_rows_of_table(left_hat, &matched_row_indexes, alloc)
+        } else {
+            get_matched_rows_of_table(left_hat, &matched_row_indexes, alloc)
+        }
+        .expect("Indexes can not be out of bounds")
+    }
+}
+
+impl ProofPlan for SemiJoinExec
+where
+    SemiJoinExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        verify_join_column_types(
+            &self.left.get_column_result_fields(),
+            &self.right.get_column_result_fields(),
+            &self.left_join_column_indexes,
+            &self.right_join_column_indexes,
+        )?;
+        // 1. columns
+        let left_eval = self
+            .left
+            .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let right_eval = self
+            .right
+            .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        // 2. Chi eval and rho eval
+        let left_chi_eval = left_eval.chi_eval();
+        let right_chi_eval = right_eval.chi_eval();
+        let u_chi_eval = builder.try_consume_chi_evaluation()?;
+        let left_rho_eval = builder.try_consume_rho_evaluation()?;
+        // 3. alpha, beta
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 4. `U` contains the join columns of both inputs and is strictly increasing
+        let u_column_evals = (0..self.left_join_column_indexes.len())
+            .map(|_| builder.try_consume_first_round_mle_evaluation())
+            .collect::<Result<Vec<_>, _>>()?;
+        let u_fold_eval = fold_64_bit_column_evals(u_column_evals.iter().map(|&eval| (eval, true)));
+        let left_join_column_evals =
+            apply_slice_to_indexes(left_eval.column_evals(), &self.left_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let right_join_column_evals =
+            apply_slice_to_indexes(right_eval.column_evals(), &self.right_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+        let w_l_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            left_chi_eval,
+            &u_column_evals,
+            &left_join_column_evals,
+        )?;
+        let w_r_eval = verify_membership_check(
+            builder,
+            alpha,
+            beta,
+            u_chi_eval,
+            right_chi_eval,
+            &u_column_evals,
+            &right_join_column_evals,
+        )?;
+        verify_monotonic::<S, true, true>(builder, alpha, beta, u_fold_eval, u_chi_eval)?;
+        // 5. The selected rows of the left input
+        let left_hat_column_evals = left_eval
+            .column_evals()
+            .iter()
+            .copied()
+            .chain(core::iter::once(left_rho_eval))
+            .collect::<Vec<_>>();
+        let (res_hat_column_evals, res_chi_eval) = verify_rows_by_match(
+            builder,
+            alpha,
+            beta,
+            left_chi_eval,
+            u_chi_eval,
+            &left_hat_column_evals,
+            &self.left_join_column_indexes,
+            &u_column_evals,
+            w_l_eval,
+            w_r_eval,
+            !self.is_anti_join,
+        )?;
+        // Drop the rho column
+        let num_columns_left = left_eval.column_evals().len();
+        Ok(TableEvaluation::new(
+            res_hat_column_evals[..num_columns_left].to_vec(),
+            res_chi_eval,
+        ))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.left.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.left
+            .get_column_references()
+            .into_iter()
+            .chain(self.right.get_column_references())
+            .collect()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.left
+            .get_table_references()
+            .into_iter()
+            .chain(self.right.get_table_references())
+            .collect()
+    }
+}
+
+impl ProverEvaluate for SemiJoinExec {
+    #[tracing::instrument(name = "SemiJoinExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        // 1. columns
+        let left = self.left.first_round_evaluate(builder, alloc, table_map);
+        let right = self.right.first_round_evaluate(builder, alloc, table_map);
+        let num_rows_left = left.num_rows();
+        let num_rows_right = right.num_rows();
+        let left_hat = left.add_rho_column(alloc);
+        let c_l = get_columns_of_table(&left_hat, &self.left_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let c_r = get_columns_of_table(&right, &self.right_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        // 2. Get and commit the strictly increasing columns, `U`
+        let u = ordered_set_union(&c_l, &c_r, alloc).expect("Join columns should be comparable");
+        let num_rows_u = u[0].len();
+        for column in &u {
+            let scalars = column.to_scalar_with_scaling(0);
+            let alloc_scalars = alloc.alloc_slice_copy(scalars.as_slice());
+            builder.produce_intermediate_mle(alloc_scalars as &[_]);
+        }
+        // 3. Chi eval and rho eval
+        builder.produce_chi_evaluation_length(num_rows_u);
+        builder.produce_rho_evaluation_length(num_rows_left);
+        // 4. Membership checks and monotonicity of `U`
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_l);
+        first_round_evaluate_membership_check(builder, alloc, &u, &c_r);
+        first_round_evaluate_monotonic(builder, num_rows_u);
+        // 5. The selected rows of the left input
+        let left_hat_columns = left_hat.columns().copied().collect::<Vec<_>>();
+        let res_hat = self.selected_rows(alloc, &left_hat, &c_l, &c_r, num_rows_right);
+        first_round_evaluate_rows_by_match(
+            builder,
+            alloc,
+            &left_hat_columns,
+            &res_hat,
+            &self.left_join_column_indexes,
+            &u,
+        );
+        builder.request_post_result_challenges(2);
+        // 6. Return the result, dropping the rho column
+        let num_rows_res = res_hat[0].len();
+        Table::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(res_hat),
+            TableOptions::new(Some(num_rows_res)),
+        )
+        .expect("Can not create table")
+    }
+
+    #[tracing::instrument(name = "SemiJoinExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        // 1. columns
+        let left = self.left.final_round_evaluate(builder, alloc, table_map);
+        let right = self.right.final_round_evaluate(builder, alloc, table_map);
+        let num_rows_left = left.num_rows();
+        let num_rows_right = right.num_rows();
+        let chi_m_l = alloc.alloc_slice_fill_copy(num_rows_left, true) as &[_];
+        let chi_m_r = alloc.alloc_slice_fill_copy(num_rows_right, true) as &[_];
+        let left_hat = left.add_rho_column(alloc);
+        let c_l = get_columns_of_table(&left_hat, &self.left_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        let c_r = get_columns_of_table(&right, &self.right_join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+        // 2. ordered set union `U`
+        let u = ordered_set_union(&c_l, &c_r, alloc).expect("Join columns should be comparable");
+        let num_rows_u = u[0].len();
+        let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true) as &[_];
+        let u_fold = fold_join_key(alloc, &u, num_rows_u);
+        // 3. Get post-result challenges
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 4. Membership checks and monotonicity of `U`
+        let w_l = final_round_evaluate_membership_check(
+            builder, alloc, alpha, beta, chi_u, chi_m_l, &u, &c_l,
+        );
+        let w_r = final_round_evaluate_membership_check(
+            builder, alloc, alpha, beta, chi_u, chi_m_r, &u, &c_r,
+        );
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, u_fold);
+        // 5. The selected rows of the left input
+        let left_hat_columns = left_hat.columns().copied().collect::<Vec<_>>();
+        let res_hat = self.selected_rows(alloc, &left_hat, &c_l, &c_r, num_rows_right);
+        final_round_evaluate_rows_by_match(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi_m_l,
+            chi_u,
+            &left_hat_columns,
+            &res_hat,
+            &self.left_join_column_indexes,
+            &u,
+            w_l,
+            w_r,
+            !self.is_anti_join,
+        );
+        // 6. Return the result, dropping the rho column
+        let num_rows_res = res_hat[0].len();
+        Table::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(res_hat),
+            TableOptions::new(Some(num_rows_res)),
+        )
+        .expect("Can not create table")
+    }
+}
@@ -0,0 +1,271 @@
+use super::{test_utility::*, DynProofPlan};
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, table_utility::*, ColumnType, TableRef, TableTestAccessor,
+            TestAccessor,
+        },
+        proof::ProofError,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+
+fn cats_and_cat_details(
+    alloc: &Bump,
+) -> (TableTestAccessor<InnerProductProof>, TableRef, TableRef) {
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let cats = table([
+        borrowed_bigint("id", [3_i64, 1, 2, 5, 4, 1], alloc),
+        borrowed_varchar(
+            "name",
+            ["Prudence", "Chloe", "Margaret", "Pepper", "Lucy", "Chloe"],
+            alloc,
+        ),
+    ]);
+    let table_cats: TableRef = "sxt.cats".parse().unwrap();
+    let cat_details = table([
+        borrowed_bigint("id", [1_i64, 2, 98, 4, 1, 2, 7], alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Gretta", "Ian", "Ian", "Erik"],
+            alloc,
+        ),
+    ]);
+    let table_cat_details: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_cats.clone(), cats, 0);
+    accessor.add_table(table_cat_details.clone(), cat_details, 0);
+    (accessor, table_cats, table_cat_details)
+}
+
+fn cats_exec(table_cats: &TableRef) -> DynProofPlan {
+    table_exec(
+        table_cats.clone(),
+        vec![
+            column_field("id", ColumnType::BigInt),
+            column_field("name", ColumnType::VarChar),
+        ],
+    )
+}
+
+fn cat_details_exec(table_cat_details: &TableRef) -> DynProofPlan {
+    table_exec(
+        table_cat_details.clone(),
+        vec![
+            column_field("id", ColumnType::BigInt),
+            column_field("human", ColumnType::VarChar),
+        ],
+    )
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_semi_join() {
+    let alloc = Bump::new();
+    let (accessor, table_cats, table_cat_details) = cats_and_cat_details(&alloc);
+    let ast = semi_join(
+        cats_exec(&table_cats),
+        cat_details_exec(&table_cat_details),
+        vec![0],
+        vec![0],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_cats);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 2, 4, 1]),
+        varchar("name", ["Chloe", "Margaret", "Lucy", "Chloe"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_an_anti_join() {
+    let alloc = Bump::new();
+    let (accessor, table_cats, table_cat_details) = cats_and_cat_details(&alloc);
+    let ast = anti_join(
+        cats_exec(&table_cats),
+        cat_details_exec(&table_cat_details),
+        vec![0],
+        vec![0],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_cats);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [3_i64, 5]),
+        varchar("name", ["Prudence", "Pepper"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_semi_join_over_filters() {
+    let alloc = Bump::new();
+    let (accessor, table_cats, table_cat_details) = cats_and_cat_details(&alloc);
+    let ast = semi_join(
+        filter(
+            cols_expr_plan(&table_cats, &["name", "id"], &accessor),
+            tab(&table_cats),
+            lte(column(&table_cats, "id", &accessor), const_bigint(3)),
+        ),
+        filter(
+            cols_expr_plan(&table_cat_details, &["id"], &accessor),
+            tab(&table_cat_details),
+            equal(
+                column(&table_cat_details, "human", &accessor),
+                const_varchar("Ian"),
+            ),
+        ),
+        vec![1],
+        vec![0],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_cats);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        varchar("name", ["Chloe", "Margaret", "Chloe"]),
+        bigint("id", [1_i64, 2, 1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_semi_join_and_an_anti_join_on_multiple_columns() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_int("a", [1, 1, 2, 2, 3], &alloc),
+        borrowed_bigint("b", [1_i64, 2, 1, 2, 1], &alloc),
+        borrowed_varchar("c", ["v", "w", "x", "y", "z"], &alloc),
+    ]);
+    let table_left: TableRef = "sxt.left".parse().unwrap();
+    let right = table([
+        borrowed_bigint("b", [2_i64, 1, 2], &alloc),
+        borrowed_int("a", [1, 2, 1], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.right".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let left_exec = || {
+        table_exec(
+            table_left.clone(),
+            vec![
+                column_field("a", ColumnType::Int),
+                column_field("b", ColumnType::BigInt),
+                column_field("c", ColumnType::VarChar),
+            ],
+        )
+    };
+    let right_exec = || {
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("b", ColumnType::BigInt),
+                column_field("a", ColumnType::Int),
+            ],
+        )
+    };
+    for (ast, expected_res) in [
+        (
+            semi_join(left_exec(), right_exec(), vec![0, 1], vec![1, 0]),
+            owned_table([
+                int("a", [1, 2]),
+                bigint("b", [2_i64, 1]),
+                varchar("c", ["w", "x"]),
+            ]),
+        ),
+        (
+            anti_join(left_exec(), right_exec(), vec![0, 1], vec![1, 0]),
+            owned_table([
+                int("a", [1, 2, 3]),
+                bigint("b", [1_i64, 2, 1]),
+                varchar("c", ["v", "y", "z"]),
+            ]),
+        ),
+    ] {
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_semi_join_and_an_anti_join_if_the_right_input_has_no_rows(
+) {
+    let alloc = Bump::new();
+    let (accessor, table_cats, table_cat_details) = cats_and_cat_details(&alloc);
+    let right_exec = || {
+        filter(
+            cols_expr_plan(&table_cat_details, &["id"], &accessor),
+            tab(&table_cat_details),
+            const_bool(false),
+        )
+    };
+    for (ast, expected_res) in [
+        (
+            semi_join(cats_exec(&table_cats), right_exec(), vec![0], vec![0]),
+            owned_table([bigint("id", [0_i64; 0]), varchar("name", [""; 0])]),
+        ),
+        (
+            anti_join(cats_exec(&table_cats), right_exec(), vec![0], vec![0]),
+            owned_table([
+                bigint("id", [3_i64, 1, 2, 5, 4, 1]),
+                varchar(
+                    "name",
+                    ["Prudence", "Chloe", "Margaret", "Pepper", "Lucy", "Chloe"],
+                ),
+            ]),
+        ),
+    ] {
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, &table_cats);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_semi_join_on_multiple_columns_wider_than_64_bits() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_int128("a", [1_i128, 2], &alloc),
+        borrowed_bigint("b", [1_i64, 2], &alloc),
+    ]);
+    let table_left: TableRef = "sxt.left".parse().unwrap();
+    let right = table([
+        borrowed_int128("a", [1_i128, 2], &alloc),
+        borrowed_bigint("b", [1_i64, 3], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.right".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let schema = vec![
+        column_field("a", ColumnType::Int128),
+        column_field("b", ColumnType::BigInt),
+    ];
+    let ast = semi_join(
+        table_exec(table_left.clone(), schema.clone()),
+        table_exec(table_right.clone(), schema),
+        vec![0, 1],
+        vec![0, 1],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError { .. }
+        })
+    ));
+}
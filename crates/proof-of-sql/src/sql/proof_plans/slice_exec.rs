@@ -29,9 +29,9 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct SliceExec {
-    pub(super) input: Box<DynProofPlan>,
-    pub(super) skip: usize,
-    pub(super) fetch: Option<usize>,
+    pub(crate) input: Box<DynProofPlan>,
+    pub(crate) skip: usize,
+    pub(crate) fetch: Option<usize>,
 }
 
 /// Get the boolean slice selection from the number of rows, skip and fetch
@@ -45,7 +45,6 @@ fn get_slice_select(num_rows: usize, skip: usize, fetch: Option<usize>) -> Vec<b
 
 impl SliceExec {
     /// Creates a new slice execution plan.
-    #[allow(dead_code)]
     pub fn new(input: Box<DynProofPlan>, skip: usize, fetch: Option<usize>) -> Self {
         Self { input, skip, fetch }
     }
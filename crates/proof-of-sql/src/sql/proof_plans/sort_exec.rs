@@ -0,0 +1,249 @@
+use super::{can_fold_64_bit_columns, fold_64_bit_column_evals, fold_64_bit_columns, DynProofPlan};
+use crate::{
+    base::{
+        database::{
+            order_by_util::sort_columns, Column, ColumnField, ColumnRef, ColumnType, OwnedTable,
+            Table, TableEvaluation, TableOptions, TableRef,
+        },
+        map::{IndexMap, IndexSet},
+        polynomial::MultilinearExtension,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_gadgets::{
+            final_round_evaluate_monotonic, final_round_evaluate_permutation_check,
+            first_round_evaluate_monotonic, verify_monotonic, verify_permutation_check,
+        },
+    },
+    utils::log,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// `ProofPlan` for queries of the form
+/// ```ignore
+///     <ProofPlan> ORDER BY <column1> [ASC|DESC], ..., <columnN> [ASC|DESC]
+/// ```
+///
+/// The result is proven to be a permutation of the input whose sort key is nondecreasing,
+/// see [`SortExec::can_sort_by`]. Rows with equal sort keys may be returned in any order.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SortExec {
+    pub(crate) input: Box<DynProofPlan>,
+    /// Pairs of the indexes of the sort columns in the input and whether they are sorted ascending
+    pub(crate) order_by: Vec<(usize, bool)>,
+}
+
+impl SortExec {
+    /// Creates a new sort execution plan.
+    ///
+    /// # Panics
+    /// Panics if there are no sort columns or if a sort column index is out of bounds.
+    pub fn new(input: Box<DynProofPlan>, order_by: Vec<(usize, bool)>) -> Self {
+        let num_columns = input.get_column_result_fields().len();
+        assert!(
+            !order_by.is_empty(),
+            "Sort requires at least one sort column"
+        );
+        assert!(
+            order_by.iter().all(|&(index, _)| index < num_columns),
+            "Sort column indexes should be in bounds"
+        );
+        Self { input, order_by }
+    }
+
+    /// Whether results can be provably sorted by columns of the given types
+    ///
+    /// Multiple sort columns are folded into the sort key with [`fold_64_bit_columns`],
+    /// where descending columns are negated.
+    /// A single sort column is never folded and only needs values whose order matches
+    /// the order of their scalars, which is not the case for strings, binaries and scalars.
+    #[must_use]
+    pub fn can_sort_by(column_types: &[ColumnType]) -> bool {
+        match column_types {
+            [] => false,
+            [column_type] => !matches!(
+                column_type,
+                ColumnType::VarChar | ColumnType::VarBinary | ColumnType::Scalar
+            ),
+            _ => can_fold_64_bit_columns(column_types),
+        }
+    }
+
+    fn sort_column_types(&self) -> Vec<ColumnType> {
+        let fields = self.input.get_column_result_fields();
+        self.order_by
+            .iter()
+            .map(|&(index, _)| fields[index].data_type())
+            .collect()
+    }
+}
+
+impl ProofPlan for SortExec
+where
+    SortExec: ProverEvaluate,
+{
+    fn verifier_evaluate<S: Scalar>(
+        &self,
+        builder: &mut impl VerificationBuilder<S>,
+        accessor: &IndexMap<ColumnRef, S>,
+        _result: Option<&OwnedTable<S>>,
+        chi_eval_map: &IndexMap<TableRef, S>,
+    ) -> Result<TableEvaluation<S>, ProofError> {
+        if !Self::can_sort_by(&self.sort_column_types()) {
+            return Err(ProofError::VerificationError {
+                error: "Sort columns can not be folded into a sort key",
+            });
+        }
+        // 1. columns
+        let input_table_eval =
+            self.input
+                .verifier_evaluate(builder, accessor, None, chi_eval_map)?;
+        let chi_eval = input_table_eval.chi_eval();
+        let column_evals = input_table_eval.column_evals();
+        // 2. sorted columns
+        let sorted_column_evals =
+            builder.try_consume_final_round_mle_evaluations(column_evals.len())?;
+        let alpha = builder.try_consume_post_result_challenge()?;
+        let beta = builder.try_consume_post_result_challenge()?;
+        // 3. The sorted columns are a permutation of the columns
+        verify_permutation_check(
+            builder,
+            alpha,
+            beta,
+            chi_eval,
+            column_evals,
+            &sorted_column_evals,
+        )?;
+        // 4. The sort key of the sorted columns is nondecreasing
+        let sort_key_eval = sort_key_eval(&self.order_by, &sorted_column_evals);
+        verify_monotonic::<S, false, true>(builder, alpha, beta, sort_key_eval, chi_eval)?;
+        Ok(TableEvaluation::new(sorted_column_evals, chi_eval))
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+
+    fn get_table_references(&self) -> IndexSet<TableRef> {
+        self.input.get_table_references()
+    }
+}
+
+impl ProverEvaluate for SortExec {
+    #[tracing::instrument(name = "SortExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.first_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        // 2. sorted columns
+        let sorted_columns = sort_columns(alloc, &columns, &self.order_by);
+        builder.request_post_result_challenges(2);
+        // 3. monotonicity of the sort key
+        first_round_evaluate_monotonic(builder, num_rows);
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(sorted_columns),
+            TableOptions::new(Some(num_rows)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+
+    #[tracing::instrument(name = "SortExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a, S: Scalar>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        table_map: &IndexMap<TableRef, Table<'a, S>>,
+    ) -> Table<'a, S> {
+        log::log_memory_usage("Start");
+
+        // 1. columns
+        let input = self.input.final_round_evaluate(builder, alloc, table_map);
+        let num_rows = input.num_rows();
+        let columns = input.columns().copied().collect::<Vec<_>>();
+        // 2. sorted columns
+        let sorted_columns = sort_columns(alloc, &columns, &self.order_by);
+        for column in &sorted_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        // 3. The sorted columns are a permutation of the columns
+        let chi = alloc.alloc_slice_fill_copy(num_rows, true);
+        final_round_evaluate_permutation_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            chi,
+            &columns,
+            &sorted_columns,
+        );
+        // 4. The sort key of the sorted columns is nondecreasing
+        let sort_key = sort_key(alloc, &self.order_by, &sorted_columns, num_rows);
+        final_round_evaluate_monotonic::<S, false, true>(builder, alloc, alpha, beta, sort_key);
+        let res = Table::<'a, S>::try_from_iter_with_options(
+            self.get_column_result_fields()
+                .into_iter()
+                .map(|field| field.name())
+                .zip(sorted_columns),
+            TableOptions::new(Some(num_rows)),
+        )
+        .expect("Failed to create table from iterator");
+
+        log::log_memory_usage("End");
+
+        res
+    }
+}
+
+/// Fold the evaluations of the sort columns among `column_evals` into the evaluation of the sort key
+pub(super) fn sort_key_eval<S: Scalar>(order_by: &[(usize, bool)], column_evals: &[S]) -> S {
+    fold_64_bit_column_evals(
+        order_by
+            .iter()
+            .map(|&(index, is_asc)| (column_evals[index], is_asc)),
+    )
+}
+
+/// Fold the sort columns among `columns` into the sort key
+///
+/// See [`SortExec::can_sort_by`] for why the sort key has the same order as the sort columns.
+pub(super) fn sort_key<'a, S: Scalar>(
+    alloc: &'a Bump,
+    order_by: &[(usize, bool)],
+    columns: &[Column<'a, S>],
+    num_rows: usize,
+) -> &'a [S] {
+    fold_64_bit_columns(
+        alloc,
+        order_by
+            .iter()
+            .map(|&(index, is_asc)| (&columns[index], is_asc)),
+        num_rows,
+    )
+}
@@ -0,0 +1,159 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, table_utility::*, ColumnType, OwnedTableTestAccessor, TableRef,
+            TableTestAccessor, TestAccessor,
+        },
+        proof::ProofError,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use bumpalo::Bump;
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_exec() {
+    let data = owned_table([
+        bigint("a", [3_i64, -1, 2, 5, -7]),
+        varchar("b", ["3", "-1", "2", "5", "-7"]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = sort_exec(
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("a", ColumnType::BigInt),
+                column_field("b", ColumnType::VarChar),
+            ],
+        ),
+        vec![(0, true)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [-7_i64, -1, 2, 3, 5]),
+        varchar("b", ["-7", "-1", "2", "3", "5"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_exec_on_multiple_columns() {
+    let data = owned_table([
+        boolean("a", [true, false, true, false, true, false]),
+        int("b", [1, 2, 3, 2, 1, -4]),
+        tinyint("c", [0_i8, 1, 2, 3, 4, 5]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = sort_exec(
+        filter(
+            cols_expr_plan(&t, &["a", "b", "c"], &accessor),
+            tab(&t),
+            const_bool(true),
+        ),
+        vec![(0, false), (1, true), (2, false)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("a", [true, true, true, false, false, false]),
+        int("b", [1, 1, 3, -4, 2, 2]),
+        tinyint("c", [4_i8, 0, 2, 5, 3, 1]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_slice_of_a_sort_exec() {
+    let data = owned_table([
+        bigint("revenue", [30_i64, 10, 50, 20, 40]),
+        varchar("name", ["c", "a", "e", "b", "d"]),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = slice_exec(
+        sort_exec(
+            filter(
+                cols_expr_plan(&t, &["name", "revenue"], &accessor),
+                tab(&t),
+                const_bool(true),
+            ),
+            vec![(1, false)],
+        ),
+        0,
+        Some(2),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("name", ["e", "d"]), bigint("revenue", [50_i64, 40])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_exec() {
+    let data = owned_table([bigint("a", [1_i64, 2, 3]), int128("b", [4_i128, 5, 6])]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor =
+        OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t.clone(), data, 0, ());
+    let ast = sort_exec(
+        filter(
+            cols_expr_plan(&t, &["a", "b"], &accessor),
+            tab(&t),
+            const_bool(false),
+        ),
+        vec![(1, false)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), int128("b", [0_i128; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_sort_exec_on_columns_that_can_not_be_folded() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let data = table([
+        borrowed_int128("a", [2_i128, 1], &alloc),
+        borrowed_bigint("b", [1_i64, 2], &alloc),
+        borrowed_varchar("c", ["b", "a"], &alloc),
+    ]);
+    let t: TableRef = "sxt.t".parse().unwrap();
+    accessor.add_table(t.clone(), data, 0);
+    let exec = || {
+        table_exec(
+            t.clone(),
+            vec![
+                column_field("a", ColumnType::Int128),
+                column_field("b", ColumnType::BigInt),
+                column_field("c", ColumnType::VarChar),
+            ],
+        )
+    };
+    for ast in [
+        sort_exec(exec(), vec![(0, true), (1, true)]),
+        sort_exec(exec(), vec![(2, true)]),
+    ] {
+        let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+            VerifiableQueryResult::new(&ast, &accessor, &());
+        assert!(matches!(
+            verifiable_res.verify(&ast, &accessor, &()),
+            Err(QueryError::ProofError {
+                source: ProofError::VerificationError { .. }
+            })
+        ));
+    }
+}
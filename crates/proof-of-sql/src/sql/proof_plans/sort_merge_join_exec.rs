@@ -1,17 +1,25 @@
-use super::DynProofPlan;
+use super::{
+    can_fold_64_bit_columns, fold_64_bit_column_evals, fold_64_bit_columns,
+    union_exec::{prove_union, verify_union},
+    DynProofPlan, MAX_NUM_64_BIT_FOLD_COLUMNS,
+};
 use crate::{
     base::{
         database::{
             join_util::{
                 apply_sort_merge_join_indexes, get_columns_of_table, get_sort_merge_join_indexes,
-                ordered_set_union,
+                get_unmatched_rows_of_table, ordered_set_union,
             },
+            presence_column_ident,
             slice_operation::apply_slice_to_indexes,
-            ColumnField, ColumnRef, OwnedTable, Table, TableEvaluation, TableOptions, TableRef,
+            union_util::column_union,
+            Column, ColumnField, ColumnRef, ColumnType, OwnedTable, Table, TableEvaluation,
+            TableOptions, TableRef,
         },
         map::{IndexMap, IndexSet},
         proof::ProofError,
         scalar::Scalar,
+        slice_ops,
     },
     sql::{
         proof::{
@@ -34,36 +42,95 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use sqlparser::ast::Ident;
 
+/// The type of a [`SortMergeJoinExec`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JoinType {
+    /// Only rows that match on both sides are returned
+    #[default]
+    Inner,
+    /// Rows of the left input without a match are also returned, padded with NULLs
+    Left,
+    /// Rows of the right input without a match are also returned, padded with NULLs
+    Right,
+    /// Rows of either input without a match are also returned, padded with NULLs
+    Full,
+}
+
+impl JoinType {
+    /// Whether the rows of the left input without a match are part of the result
+    #[must_use]
+    pub fn preserves_left(self) -> bool {
+        matches!(self, Self::Left | Self::Full)
+    }
+
+    /// Whether the rows of the right input without a match are part of the result
+    #[must_use]
+    pub fn preserves_right(self) -> bool {
+        matches!(self, Self::Right | Self::Full)
+    }
+}
+
 /// `ProofPlan` for queries of the form
 /// ```ignore
-///     <ProofPlan> INNER JOIN <ProofPlan>
-///     ON col1 = col2
+///     <ProofPlan> [INNER | LEFT | RIGHT | FULL] JOIN <ProofPlan>
+///     ON col1 = col2 [AND col3 = col4 ...]
 /// ```
+///
+/// The result consists of the join columns, the other columns of the left input and the other
+/// columns of the right input. For outer joins the columns that can be padded are followed
+/// by their presence columns, see [`crate::base::database::NullableOwnedColumn`].
+/// The join columns are never padded since their value is taken from whichever side has the row.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SortMergeJoinExec {
-    pub(super) left: Box<DynProofPlan>,
-    pub(super) right: Box<DynProofPlan>,
+    pub(crate) left: Box<DynProofPlan>,
+    pub(crate) right: Box<DynProofPlan>,
     // `j_l` in the protocol
-    pub(super) left_join_column_indexes: Vec<usize>,
+    pub(crate) left_join_column_indexes: Vec<usize>,
     // `j_r` in the protocol
-    pub(super) right_join_column_indexes: Vec<usize>,
-    pub(super) result_idents: Vec<Ident>,
+    pub(crate) right_join_column_indexes: Vec<usize>,
+    pub(crate) result_idents: Vec<Ident>,
+    #[serde(default)]
+    pub(crate) join_type: JoinType,
 }
 
 impl SortMergeJoinExec {
-    /// Create a new `SortMergeJoinExec` with the given left and right plans
+    /// Create a new inner `SortMergeJoinExec` with the given left and right plans
+    ///
+    /// # Panics
+    /// See [`SortMergeJoinExec::new_with_join_type`]
+    pub fn new(
+        left: Box<DynProofPlan>,
+        right: Box<DynProofPlan>,
+        left_join_column_indexes: Vec<usize>,
+        right_join_column_indexes: Vec<usize>,
+        result_idents: Vec<Ident>,
+    ) -> Self {
+        Self::new_with_join_type(
+            left,
+            right,
+            left_join_column_indexes,
+            right_join_column_indexes,
+            result_idents,
+            JoinType::Inner,
+        )
+    }
+
+    /// Create a new `SortMergeJoinExec` of the given type with the given left and right plans
+    ///
+    /// `result_idents` does not include the presence columns of an outer join.
     ///
     /// # Panics
     /// Panics if one of the following conditions is met:
     /// - The join column index is out of bounds
     /// - The number of join columns is different
     /// - The number of result idents is different from the expected number of columns
-    pub fn new(
+    pub fn new_with_join_type(
         left: Box<DynProofPlan>,
         right: Box<DynProofPlan>,
         left_join_column_indexes: Vec<usize>,
         right_join_column_indexes: Vec<usize>,
         result_idents: Vec<Ident>,
+        join_type: JoinType,
     ) -> Self {
         let num_columns_left = left.get_column_result_fields().len();
         let num_columns_right = right.get_column_result_fields().len();
@@ -89,8 +156,495 @@ impl SortMergeJoinExec {
             left_join_column_indexes,
             right_join_column_indexes,
             result_idents,
+            join_type,
+        }
+    }
+
+    /// The type of the join
+    #[must_use]
+    pub fn join_type(&self) -> JoinType {
+        self.join_type
+    }
+
+    /// The idents of the presence columns of the result
+    ///
+    /// These are the presence columns of the other columns of the left input if unmatched rows
+    /// of the right input are preserved, followed by the presence columns of the other columns
+    /// of the right input if unmatched rows of the left input are preserved.
+    fn presence_idents(&self) -> Vec<Ident> {
+        let num_join_columns = self.left_join_column_indexes.len();
+        let num_left_other_columns = self.left.get_column_result_fields().len() - num_join_columns;
+        let (left_other_idents, right_other_idents) =
+            self.result_idents[num_join_columns..].split_at(num_left_other_columns);
+        self.join_type
+            .preserves_right()
+            .then_some(left_other_idents)
+            .into_iter()
+            .chain(
+                self.join_type
+                    .preserves_left()
+                    .then_some(right_other_idents),
+            )
+            .flatten()
+            .map(presence_column_ident)
+            .collect()
+    }
+
+    /// The evaluations of one part of the result of an outer join
+    ///
+    /// The parts are the matched rows and the unmatched rows of each preserved input.
+    /// Other columns of the side that is `None` are padded with default values,
+    /// whose scalars are all zero, and are absent in the presence columns.
+    fn outer_join_part_evals<S: Scalar>(
+        &self,
+        join_evals: &[S],
+        left_other_evals: Option<&[S]>,
+        right_other_evals: Option<&[S]>,
+        num_left_other_columns: usize,
+        num_right_other_columns: usize,
+        chi_eval: S,
+    ) -> Vec<S> {
+        let pad = |evals: Option<&[S]>, num_columns: usize| {
+            evals.map_or_else(|| vec![S::ZERO; num_columns], <[S]>::to_vec)
+        };
+        let presence = |evals: Option<&[S]>, num_columns: usize| {
+            vec![if evals.is_some() { chi_eval } else { S::ZERO }; num_columns]
+        };
+        join_evals
+            .iter()
+            .copied()
+            .chain(pad(left_other_evals, num_left_other_columns))
+            .chain(pad(right_other_evals, num_right_other_columns))
+            .chain(
+                self.join_type
+                    .preserves_right()
+                    .then(|| presence(left_other_evals, num_left_other_columns))
+                    .into_iter()
+                    .flatten(),
+            )
+            .chain(
+                self.join_type
+                    .preserves_left()
+                    .then(|| presence(right_other_evals, num_right_other_columns))
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect()
+    }
+
+    /// The columns of one part of the result of an outer join
+    ///
+    /// See [`SortMergeJoinExec::outer_join_part_evals`].
+    #[allow(clippy::too_many_arguments)]
+    fn outer_join_part_columns<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        join_columns: &[Column<'a, S>],
+        left_other_columns: Option<&[Column<'a, S>]>,
+        right_other_columns: Option<&[Column<'a, S>]>,
+        left_other_column_types: &[ColumnType],
+        right_other_column_types: &[ColumnType],
+        num_rows: usize,
+    ) -> Vec<Column<'a, S>> {
+        let pad = |columns: Option<&[Column<'a, S>]>, column_types: &[ColumnType]| {
+            columns.map_or_else(
+                || {
+                    column_types
+                        .iter()
+                        .map(|&column_type| {
+                            Column::default_with_length(column_type, num_rows, alloc)
+                        })
+                        .collect()
+                },
+                <[Column<'a, S>]>::to_vec,
+            )
+        };
+        let presence = |columns: Option<&[Column<'a, S>]>, num_columns: usize| {
+            let present = alloc.alloc_slice_fill_copy(num_rows, columns.is_some()) as &[_];
+            vec![Column::Boolean(present); num_columns]
+        };
+        join_columns
+            .iter()
+            .copied()
+            .chain(pad(left_other_columns, left_other_column_types))
+            .chain(pad(right_other_columns, right_other_column_types))
+            .chain(
+                self.join_type
+                    .preserves_right()
+                    .then(|| presence(left_other_columns, left_other_column_types.len()))
+                    .into_iter()
+                    .flatten(),
+            )
+            .chain(
+                self.join_type
+                    .preserves_left()
+                    .then(|| presence(right_other_columns, right_other_column_types.len()))
+                    .into_iter()
+                    .flatten(),
+            )
+            .collect()
+    }
+
+    /// The parts of the result of an outer join and their union
+    ///
+    /// The parts are `J` followed by the padded unmatched rows of `\hat{L}` and `\hat{R}`
+    /// if the respective input is preserved.
+    fn outer_join_result<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        res_columns: &[Column<'a, S>],
+        num_rows_res: usize,
+        unmatched_left: Option<&[Column<'a, S>]>,
+        unmatched_right: Option<&[Column<'a, S>]>,
+    ) -> (Vec<Vec<Column<'a, S>>>, Vec<Column<'a, S>>) {
+        let left_fields = self.left.get_column_result_fields();
+        let right_fields = self.right.get_column_result_fields();
+        let num_columns_u = self.left_join_column_indexes.len();
+        let num_columns_left = left_fields.len();
+        let left_other_column_indexes =
+            other_column_indexes(num_columns_left, &self.left_join_column_indexes);
+        let right_other_column_indexes =
+            other_column_indexes(right_fields.len(), &self.right_join_column_indexes);
+        let left_other_column_types = left_other_column_indexes
+            .iter()
+            .map(|&i| left_fields[i].data_type())
+            .collect::<Vec<_>>();
+        let right_other_column_types = right_other_column_indexes
+            .iter()
+            .map(|&i| right_fields[i].data_type())
+            .collect::<Vec<_>>();
+        let mut parts = vec![self.outer_join_part_columns(
+            alloc,
+            &res_columns[..num_columns_u],
+            Some(&res_columns[num_columns_u..num_columns_left]),
+            Some(&res_columns[num_columns_left..]),
+            &left_other_column_types,
+            &right_other_column_types,
+            num_rows_res,
+        )];
+        if let Some(unmatched) = unmatched_left {
+            let join_columns = apply_slice_to_indexes(unmatched, &self.left_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+            let other_columns = apply_slice_to_indexes(unmatched, &left_other_column_indexes)
+                .expect("Indexes can not be out of bounds");
+            parts.push(self.outer_join_part_columns(
+                alloc,
+                &join_columns,
+                Some(&other_columns),
+                None,
+                &left_other_column_types,
+                &right_other_column_types,
+                unmatched[0].len(),
+            ));
+        }
+        if let Some(unmatched) = unmatched_right {
+            let join_columns = apply_slice_to_indexes(unmatched, &self.right_join_column_indexes)
+                .expect("Indexes can not be out of bounds");
+            let other_columns = apply_slice_to_indexes(unmatched, &right_other_column_indexes)
+                .expect("Indexes can not be out of bounds");
+            parts.push(self.outer_join_part_columns(
+                alloc,
+                &join_columns,
+                None,
+                Some(&other_columns),
+                &left_other_column_types,
+                &right_other_column_types,
+                unmatched[0].len(),
+            ));
         }
+        let output = self
+            .get_column_result_fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                column_union(
+                    &parts.iter().map(|part| &part[i]).collect::<Vec<_>>(),
+                    alloc,
+                    field.data_type(),
+                )
+                .expect("Join columns of both inputs should have the same types")
+            })
+            .collect();
+        (parts, output)
+    }
+}
+
+/// Check that the join columns can be folded into a single column in an order preserving way
+///
+/// Multiple join columns are folded with [`fold_64_bit_columns`].
+/// A join on a single column is never folded and hence supports every column type.
+pub(crate) fn verify_join_column_types(
+    left_fields: &[ColumnField],
+    right_fields: &[ColumnField],
+    left_join_column_indexes: &[usize],
+    right_join_column_indexes: &[usize],
+) -> Result<(), ProofError> {
+    let num_join_columns = left_join_column_indexes.len();
+    if num_join_columns == 0 {
+        return Err(ProofError::VerificationError {
+            error: "Join requires at least one join column",
+        });
+    }
+    if num_join_columns > MAX_NUM_64_BIT_FOLD_COLUMNS {
+        return Err(ProofError::VerificationError {
+            error: "Join on too many columns",
+        });
+    }
+    if num_join_columns == 1 {
+        return Ok(());
+    }
+    let all_foldable = [
+        (left_fields, left_join_column_indexes),
+        (right_fields, right_join_column_indexes),
+    ]
+    .into_iter()
+    .all(|(fields, indexes)| {
+        can_fold_64_bit_columns(
+            &indexes
+                .iter()
+                .map(|&i| fields[i].data_type())
+                .collect::<Vec<_>>(),
+        )
+    });
+    if all_foldable {
+        Ok(())
+    } else {
+        Err(ProofError::VerificationError {
+            error: "Join on multiple columns requires join columns of at most 64 bits",
+        })
+    }
+}
+
+/// Fold the columns of the composite key `U` into a single column
+///
+/// See [`verify_join_column_types`] for why the result has the same order as `U`.
+pub(super) fn fold_join_key<'a, S: Scalar>(
+    alloc: &'a Bump,
+    u: &[Column<'a, S>],
+    num_rows: usize,
+) -> &'a [S] {
+    fold_64_bit_columns(alloc, u.iter().map(|column| (column, true)), num_rows)
+}
+
+/// The indexes of the columns of an input with `num_columns` columns that are not join columns
+fn other_column_indexes(num_columns: usize, join_column_indexes: &[usize]) -> Vec<usize> {
+    (0..num_columns)
+        .filter(|i| !join_column_indexes.contains(i))
+        .collect()
+}
+
+/// Perform first round evaluation of the rows of one input of a join whose key does or does not
+/// occur in the other input
+///
+/// These are the unmatched rows of an outer join and the result of a semi or anti join.
+/// `hat_columns` are the columns of the input followed by its `rho` column and
+/// `selected_hat_columns` are the selected rows of `hat_columns` in their original order.
+pub(super) fn first_round_evaluate_rows_by_match<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    hat_columns: &[Column<'a, S>],
+    selected_hat_columns: &[Column<'a, S>],
+    join_column_indexes: &[usize],
+    u: &[Column<'a, S>],
+) {
+    let num_rows_selected = selected_hat_columns[0].len();
+    builder.produce_chi_evaluation_length(num_rows_selected);
+    first_round_evaluate_membership_check(builder, alloc, hat_columns, selected_hat_columns);
+    let selected_join_columns = apply_slice_to_indexes(selected_hat_columns, join_column_indexes)
+        .expect("Indexes can not be out of bounds");
+    first_round_evaluate_membership_check(builder, alloc, u, &selected_join_columns);
+    first_round_evaluate_monotonic(builder, num_rows_selected);
+}
+
+/// Perform final round evaluation of the rows of one input of a join whose key does or does not
+/// occur in the other input
+///
+/// `w` is the multiplicity of the rows of `U` in the join columns of this input and
+/// `w_other` the one of the other input. The rows whose key occurs in the other input
+/// are selected if `matched` is true and the others otherwise.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn final_round_evaluate_rows_by_match<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    chi_hat: &'a [bool],
+    chi_u: &'a [bool],
+    hat_columns: &[Column<'a, S>],
+    selected_hat_columns: &[Column<'a, S>],
+    join_column_indexes: &[usize],
+    u: &[Column<'a, S>],
+    w: &'a [i128],
+    w_other: &'a [i128],
+    matched: bool,
+) {
+    // 1. `z` indicates the rows of `U` that do not occur in the other input
+    let z = alloc.alloc_slice_fill_with(chi_u.len(), |i| w_other[i] == 0) as &[_];
+    let w_other_inv = alloc.alloc_slice_fill_with(chi_u.len(), |i| S::from(w_other[i]));
+    slice_ops::batch_inversion(w_other_inv);
+    let w_other_inv = w_other_inv as &[_];
+    builder.produce_intermediate_mle(z);
+    builder.produce_intermediate_mle(w_other_inv);
+    // z * w_other = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![(S::one(), vec![Box::new(z), Box::new(w_other)])],
+    );
+    // w_other * w_other_inv + z - chi_u = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(w_other), Box::new(w_other_inv)]),
+            (S::one(), vec![Box::new(z)]),
+            (-S::one(), vec![Box::new(chi_u)]),
+        ],
+    );
+    // 2. The selected rows are rows of the input
+    for column in selected_hat_columns {
+        builder.produce_intermediate_mle(*column);
+    }
+    let num_rows_selected = selected_hat_columns[0].len();
+    let chi_selected = alloc.alloc_slice_fill_copy(num_rows_selected, true) as &[_];
+    final_round_evaluate_membership_check(
+        builder,
+        alloc,
+        alpha,
+        beta,
+        chi_hat,
+        chi_selected,
+        hat_columns,
+        selected_hat_columns,
+    );
+    // 3. The selected rows have keys that do or do not occur in the other input
+    let selected_join_columns = apply_slice_to_indexes(selected_hat_columns, join_column_indexes)
+        .expect("Indexes can not be out of bounds");
+    let v = final_round_evaluate_membership_check(
+        builder,
+        alloc,
+        alpha,
+        beta,
+        chi_u,
+        chi_selected,
+        u,
+        &selected_join_columns,
+    );
+    if matched {
+        // v * z = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(S::one(), vec![Box::new(v), Box::new(z)])],
+        );
+    } else {
+        // v * (chi_u - z) = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(v), Box::new(chi_u)]),
+                (-S::one(), vec![Box::new(v), Box::new(z)]),
+            ],
+        );
     }
+    // 4. The selected rows are distinct since their `rho` is strictly increasing
+    let rho = selected_hat_columns[selected_hat_columns.len() - 1].to_scalar_with_scaling(0);
+    let alloc_rho = alloc.alloc_slice_copy(rho.as_slice());
+    final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_rho);
+    // 5. There are as many selected rows as rows of the input whose key does or does not occur in the other input
+    if matched {
+        // sum w * (chi_u - z) - chi_selected = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(w), Box::new(chi_u)]),
+                (-S::one(), vec![Box::new(w), Box::new(z)]),
+                (-S::one(), vec![Box::new(chi_selected)]),
+            ],
+        );
+    } else {
+        // sum w * z - chi_selected = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::ZeroSum,
+            vec![
+                (S::one(), vec![Box::new(w), Box::new(z)]),
+                (-S::one(), vec![Box::new(chi_selected)]),
+            ],
+        );
+    }
+}
+
+/// Verify the rows of one input of a join whose key does or does not occur in the other input
+///
+/// Returns the evaluations of the selected rows of `\hat{L}` or `\hat{R}` and their chi evaluation.
+/// See [`final_round_evaluate_rows_by_match`] for the arguments.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn verify_rows_by_match<S: Scalar>(
+    builder: &mut impl VerificationBuilder<S>,
+    alpha: S,
+    beta: S,
+    chi_hat_eval: S,
+    u_chi_eval: S,
+    hat_column_evals: &[S],
+    join_column_indexes: &[usize],
+    u_column_evals: &[S],
+    w_eval: S,
+    w_other_eval: S,
+    matched: bool,
+) -> Result<(Vec<S>, S), ProofError> {
+    let chi_selected_eval = builder.try_consume_chi_evaluation()?;
+    // 1. `z` indicates the rows of `U` that do not occur in the other input
+    let z_eval = builder.try_consume_final_round_mle_evaluation()?;
+    let w_other_inv_eval = builder.try_consume_final_round_mle_evaluation()?;
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        z_eval * w_other_eval,
+        2,
+    )?;
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        w_other_eval * w_other_inv_eval + z_eval - u_chi_eval,
+        2,
+    )?;
+    // `z` or `chi_u - z` for the rows of `U` whose rows of the input are selected
+    let selected_u_eval = if matched { u_chi_eval - z_eval } else { z_eval };
+    // 2. The selected rows are rows of the input
+    let selected_hat_column_evals =
+        builder.try_consume_final_round_mle_evaluations(hat_column_evals.len())?;
+    verify_membership_check(
+        builder,
+        alpha,
+        beta,
+        chi_hat_eval,
+        chi_selected_eval,
+        hat_column_evals,
+        &selected_hat_column_evals,
+    )?;
+    // 3. The selected rows have keys that do or do not occur in the other input
+    let selected_join_column_evals =
+        apply_slice_to_indexes(&selected_hat_column_evals, join_column_indexes)
+            .expect("Indexes can not be out of bounds");
+    let v_eval = verify_membership_check(
+        builder,
+        alpha,
+        beta,
+        u_chi_eval,
+        chi_selected_eval,
+        u_column_evals,
+        &selected_join_column_evals,
+    )?;
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::Identity,
+        v_eval * (u_chi_eval - selected_u_eval),
+        2,
+    )?;
+    // 4. The selected rows are distinct since their `rho` is strictly increasing
+    let rho_eval = selected_hat_column_evals[selected_hat_column_evals.len() - 1];
+    verify_monotonic::<S, true, true>(builder, alpha, beta, rho_eval, chi_selected_eval)?;
+    // 5. There are as many selected rows as rows of the input whose key does or does not occur in the other input
+    builder.try_produce_sumcheck_subpolynomial_evaluation(
+        SumcheckSubpolynomialType::ZeroSum,
+        w_eval * selected_u_eval - chi_selected_eval,
+        2,
+    )?;
+    Ok((selected_hat_column_evals, chi_selected_eval))
 }
 
 impl ProofPlan for SortMergeJoinExec
@@ -139,22 +693,26 @@ where
             .copied()
             .collect::<Vec<_>>();
         let num_columns_u = self.left_join_column_indexes.len();
-        if num_columns_u != 1 {
-            return Err(ProofError::VerificationError {
-                error: "Join on multiple columns not supported yet",
-            });
-        }
+        verify_join_column_types(
+            &self.left.get_column_result_fields(),
+            &self.right.get_column_result_fields(),
+            &self.left_join_column_indexes,
+            &self.right_join_column_indexes,
+        )?;
         let num_columns_res_hat = num_columns_left + num_columns_right - num_columns_u + 2;
         // `\hat{J}` in the protocol
         let res_hat_column_evals =
             builder.try_consume_final_round_mle_evaluations(num_columns_res_hat)?;
         // 5. First round MLE evaluations: `i` and `U`
-        //TODO: Make it possible for `U` to have multiple columns
         let rho_bar_left_eval = res_hat_column_evals[num_columns_left];
         let rho_bar_right_eval = res_hat_column_evals[num_columns_res_hat - 1];
         let i_eval: S = itertools::repeat_n(S::TWO, 64_usize).product::<S>() * rho_bar_left_eval
             + rho_bar_right_eval;
-        let u_column_eval = builder.try_consume_first_round_mle_evaluation()?;
+        let u_column_evals = (0..num_columns_u)
+            .map(|_| builder.try_consume_first_round_mle_evaluation())
+            .collect::<Result<Vec<_>, _>>()?;
+        // `U` folded into a single column that preserves the lexicographic order of its rows
+        let u_fold_eval = fold_64_bit_column_evals(u_column_evals.iter().map(|&eval| (eval, true)));
         // 6. Membership checks
         let hat_left_column_indexes = self
             .left_join_column_indexes
@@ -208,19 +766,13 @@ where
         let right_join_column_evals =
             apply_slice_to_indexes(&right_hat_column_evals, &self.right_join_column_indexes)
                 .expect("Indexes can not be out of bounds");
-        //TODO: Relax to allow multiple columns
-        if left_join_column_evals.len() != 1 || right_join_column_evals.len() != 1 {
-            return Err(ProofError::VerificationError {
-                error: "Left and right join columns should have exactly one column",
-            });
-        }
         let w_l_eval = verify_membership_check(
             builder,
             alpha,
             beta,
             u_chi_eval,
             left_chi_eval,
-            &[u_column_eval],
+            &u_column_evals,
             &left_join_column_evals,
         )?;
         let w_r_eval = verify_membership_check(
@@ -229,12 +781,12 @@ where
             beta,
             u_chi_eval,
             right_chi_eval,
-            &[u_column_eval],
+            &u_column_evals,
             &right_join_column_evals,
         )?;
         // 7. Monotonicity checks
         verify_monotonic::<S, true, true>(builder, alpha, beta, i_eval, res_chi_eval)?;
-        verify_monotonic::<S, true, true>(builder, alpha, beta, u_column_eval, u_chi_eval)?;
+        verify_monotonic::<S, true, true>(builder, alpha, beta, u_fold_eval, u_chi_eval)?;
         // 8. Prove that sum w_l * w_r = chi_m
         // sum w_l * w_r - chi_m = 0
         builder.try_produce_sumcheck_subpolynomial_evaluation(
@@ -249,7 +801,99 @@ where
             .collect::<Vec<_>>();
         let res_column_evals = apply_slice_to_indexes(&res_hat_column_evals, &res_column_indexes)
             .expect("Indexes can not be out of bounds");
-        Ok(TableEvaluation::new(res_column_evals, res_chi_eval))
+        if self.join_type == JoinType::Inner {
+            return Ok(TableEvaluation::new(res_column_evals, res_chi_eval));
+        }
+        // 10. Unmatched rows of the preserved inputs of an outer join
+        let num_left_other_columns = num_columns_left - num_columns_u;
+        let num_right_other_columns = num_columns_right - num_columns_u;
+        let mut part_evals = vec![self.outer_join_part_evals(
+            &res_column_evals[..num_columns_u],
+            Some(&res_column_evals[num_columns_u..num_columns_left]),
+            Some(&res_column_evals[num_columns_left..]),
+            num_left_other_columns,
+            num_right_other_columns,
+            res_chi_eval,
+        )];
+        let mut part_chi_evals = vec![res_chi_eval];
+        if self.join_type.preserves_left() {
+            let (unmatched_evals, unmatched_chi_eval) = verify_rows_by_match(
+                builder,
+                alpha,
+                beta,
+                left_chi_eval,
+                u_chi_eval,
+                &left_hat_column_evals,
+                &self.left_join_column_indexes,
+                &u_column_evals,
+                w_l_eval,
+                w_r_eval,
+                false,
+            )?;
+            let join_evals =
+                apply_slice_to_indexes(&unmatched_evals, &self.left_join_column_indexes)
+                    .expect("Indexes can not be out of bounds");
+            let other_evals = apply_slice_to_indexes(
+                &unmatched_evals,
+                &other_column_indexes(num_columns_left, &self.left_join_column_indexes),
+            )
+            .expect("Indexes can not be out of bounds");
+            part_evals.push(self.outer_join_part_evals(
+                &join_evals,
+                Some(&other_evals),
+                None,
+                num_left_other_columns,
+                num_right_other_columns,
+                unmatched_chi_eval,
+            ));
+            part_chi_evals.push(unmatched_chi_eval);
+        }
+        if self.join_type.preserves_right() {
+            let (unmatched_evals, unmatched_chi_eval) = verify_rows_by_match(
+                builder,
+                alpha,
+                beta,
+                right_chi_eval,
+                u_chi_eval,
+                &right_hat_column_evals,
+                &self.right_join_column_indexes,
+                &u_column_evals,
+                w_r_eval,
+                w_l_eval,
+                false,
+            )?;
+            let join_evals =
+                apply_slice_to_indexes(&unmatched_evals, &self.right_join_column_indexes)
+                    .expect("Indexes can not be out of bounds");
+            let other_evals = apply_slice_to_indexes(
+                &unmatched_evals,
+                &other_column_indexes(num_columns_right, &self.right_join_column_indexes),
+            )
+            .expect("Indexes can not be out of bounds");
+            part_evals.push(self.outer_join_part_evals(
+                &join_evals,
+                None,
+                Some(&other_evals),
+                num_left_other_columns,
+                num_right_other_columns,
+                unmatched_chi_eval,
+            ));
+            part_chi_evals.push(unmatched_chi_eval);
+        }
+        // 11. The result is the union of the matched rows and the padded unmatched rows
+        let output_column_evals =
+            builder.try_consume_final_round_mle_evaluations(part_evals[0].len())?;
+        let output_chi_eval = builder.try_consume_chi_evaluation()?;
+        verify_union(
+            builder,
+            alpha,
+            beta,
+            &part_evals.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &output_column_evals,
+            &part_chi_evals,
+            output_chi_eval,
+        )?;
+        Ok(TableEvaluation::new(output_column_evals, output_chi_eval))
     }
 
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
@@ -284,6 +928,11 @@ where
             .iter()
             .zip_eq(column_types)
             .map(|(ident, column_type)| ColumnField::new(ident.clone(), column_type))
+            .chain(
+                self.presence_idents()
+                    .into_iter()
+                    .map(|ident| ColumnField::new(ident, ColumnType::Boolean)),
+            )
             .collect()
     }
 
@@ -350,14 +999,12 @@ impl ProverEvaluate for SortMergeJoinExec {
         // ordered set union `U`
         let u = ordered_set_union(&c_l, &c_r, alloc).unwrap();
         let num_columns_u = u.len();
-        assert!(
-            (num_columns_u == 1),
-            "Join on multiple columns not supported yet"
-        );
-        let u_0 = u[0].to_scalar_with_scaling(0);
         let num_rows_u = u[0].len();
-        let alloc_u_0 = alloc.alloc_slice_copy(u_0.as_slice());
-        builder.produce_intermediate_mle(alloc_u_0 as &[_]);
+        for column in &u {
+            let scalars = column.to_scalar_with_scaling(0);
+            let alloc_scalars = alloc.alloc_slice_copy(scalars.as_slice());
+            builder.produce_intermediate_mle(alloc_scalars as &[_]);
+        }
         // 3. Chi eval and rho eval
         builder.produce_chi_evaluation_length(num_rows_res);
         builder.produce_chi_evaluation_length(num_rows_u);
@@ -400,21 +1047,69 @@ impl ProverEvaluate for SortMergeJoinExec {
         // 5. Monotonicity checks
         first_round_evaluate_monotonic(builder, num_rows_res);
         first_round_evaluate_monotonic(builder, num_rows_u);
-        // 6. Request post-result challenges
+        // 6. Unmatched rows of the preserved inputs of an outer join
+        let unmatched_left = self.join_type.preserves_left().then(|| {
+            let left_hat_columns = left_hat.columns().copied().collect::<Vec<_>>();
+            let unmatched = get_unmatched_rows_of_table(&left_hat, &left_row_indexes, alloc)
+                .expect("Indexes can not be out of bounds");
+            first_round_evaluate_rows_by_match(
+                builder,
+                alloc,
+                &left_hat_columns,
+                &unmatched,
+                &self.left_join_column_indexes,
+                &u,
+            );
+            unmatched
+        });
+        let unmatched_right = self.join_type.preserves_right().then(|| {
+            let right_hat_columns = right_hat.columns().copied().collect::<Vec<_>>();
+            let unmatched = get_unmatched_rows_of_table(&right_hat, &right_row_indexes, alloc)
+                .expect("Indexes can not be out of bounds");
+            first_round_evaluate_rows_by_match(
+                builder,
+                alloc,
+                &right_hat_columns,
+                &unmatched,
+                &self.right_join_column_indexes,
+                &u,
+            );
+            unmatched
+        });
+        // 7. Request post-result challenges
         builder.request_post_result_challenges(2);
-        // 7. Return join result
+        // 8. Return join result
         // Drop the two rho columns of `\hat{J}` to get `J`
         let res_column_indexes = (0..num_columns_left)
             .chain(num_columns_left + 1..num_columns_left + 1 + num_columns_right - num_columns_u)
             .collect::<Vec<_>>();
         let res_columns = apply_slice_to_indexes(&res_hat, &res_column_indexes)
             .expect("Indexes can not be out of bounds");
-        let tab = Table::try_from_iter_with_options(
-            self.result_idents.iter().cloned().zip_eq(res_columns),
-            TableOptions::new(Some(num_rows_res)),
+        if self.join_type == JoinType::Inner {
+            return Table::try_from_iter_with_options(
+                self.result_idents.iter().cloned().zip_eq(res_columns),
+                TableOptions::new(Some(num_rows_res)),
+            )
+            .expect("Can not create table");
+        }
+        let (_, output_columns) = self.outer_join_result(
+            alloc,
+            &res_columns,
+            num_rows_res,
+            unmatched_left.as_deref(),
+            unmatched_right.as_deref(),
+        );
+        let num_rows_output = output_columns[0].len();
+        builder.produce_chi_evaluation_length(num_rows_output);
+        Table::try_from_iter_with_options(
+            self.result_idents
+                .iter()
+                .cloned()
+                .chain(self.presence_idents())
+                .zip_eq(output_columns),
+            TableOptions::new(Some(num_rows_output)),
         )
-        .expect("Can not create table");
-        tab
+        .expect("Can not create table")
     }
 
     #[tracing::instrument(
@@ -436,8 +1131,8 @@ impl ProverEvaluate for SortMergeJoinExec {
         let num_columns_left = left.num_columns();
         let num_columns_right = right.num_columns();
 
-        let chi_m_l = alloc.alloc_slice_fill_copy(num_rows_left, true);
-        let chi_m_r = alloc.alloc_slice_fill_copy(num_rows_right, true);
+        let chi_m_l = alloc.alloc_slice_fill_copy(num_rows_left, true) as &[_];
+        let chi_m_r = alloc.alloc_slice_fill_copy(num_rows_right, true) as &[_];
 
         let left_hat = left.add_rho_column(alloc);
         let right_hat = right.add_rho_column(alloc);
@@ -485,15 +1180,10 @@ impl ProverEvaluate for SortMergeJoinExec {
         // ordered set union `U`
         let u = ordered_set_union(&c_l, &c_r, alloc).unwrap();
         let num_columns_u = u.len();
-        assert!(
-            (num_columns_u == 1),
-            "Join on multiple columns not supported yet"
-        );
-        let u_0 = u[0].to_scalar_with_scaling(0);
         let num_rows_u = u[0].len();
-        let alloc_u_0 = alloc.alloc_slice_copy(u_0.as_slice());
-        let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true);
-        let alloc_u_0 = alloc.alloc_slice_copy(u_0.as_slice());
+        let chi_u = alloc.alloc_slice_fill_copy(num_rows_u, true) as &[_];
+        // `U` folded into a single column that preserves the lexicographic order of its rows
+        let u_fold = fold_join_key(alloc, &u, num_rows_u);
 
         // 3. Get post-result challenges
         let alpha = builder.consume_post_result_challenge();
@@ -561,7 +1251,7 @@ impl ProverEvaluate for SortMergeJoinExec {
 
         // 6. Monotonicity checks
         final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_i);
-        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, alloc_u_0);
+        final_round_evaluate_monotonic::<S, true, true>(builder, alloc, alpha, beta, u_fold);
 
         // 7. Prove that sum w_l * w_r = chi_m
         // sum w_l * w_r - chi_m = 0
@@ -573,17 +1263,98 @@ impl ProverEvaluate for SortMergeJoinExec {
             ],
         );
 
-        // 8. Return join result
+        // 8. Unmatched rows of the preserved inputs of an outer join
+        let unmatched_left = self.join_type.preserves_left().then(|| {
+            let left_hat_columns = left_hat.columns().copied().collect::<Vec<_>>();
+            let unmatched = get_unmatched_rows_of_table(&left_hat, &left_row_indexes, alloc)
+                .expect("Indexes can not be out of bounds");
+            final_round_evaluate_rows_by_match(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                chi_m_l,
+                chi_u,
+                &left_hat_columns,
+                &unmatched,
+                &self.left_join_column_indexes,
+                &u,
+                w_l,
+                w_r,
+                false,
+            );
+            unmatched
+        });
+        let unmatched_right = self.join_type.preserves_right().then(|| {
+            let right_hat_columns = right_hat.columns().copied().collect::<Vec<_>>();
+            let unmatched = get_unmatched_rows_of_table(&right_hat, &right_row_indexes, alloc)
+                .expect("Indexes can not be out of bounds");
+            final_round_evaluate_rows_by_match(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                chi_m_r,
+                chi_u,
+                &right_hat_columns,
+                &unmatched,
+                &self.right_join_column_indexes,
+                &u,
+                w_r,
+                w_l,
+                false,
+            );
+            unmatched
+        });
+
+        // 9. Return join result
         // Drop the two rho columns of `\hat{J}` to get `J`
         let res_column_indexes = (0..num_columns_left)
             .chain(num_columns_left + 1..num_columns_left + 1 + num_columns_right - num_columns_u)
             .collect::<Vec<_>>();
         let res_columns = apply_slice_to_indexes(res_hat, &res_column_indexes)
             .expect("Indexes can not be out of bounds");
+        if self.join_type == JoinType::Inner {
+            return Table::try_from_iter_with_options(
+                self.result_idents.iter().cloned().zip_eq(res_columns),
+                TableOptions::new(Some(num_rows_res)),
+            )
+            .expect("Can not create table");
+        }
 
+        // 10. The result of an outer join is the union of the matched rows and the padded unmatched rows
+        let (part_columns, output_columns) = self.outer_join_result(
+            alloc,
+            &res_columns,
+            num_rows_res,
+            unmatched_left.as_deref(),
+            unmatched_right.as_deref(),
+        );
+        for column in &output_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        let part_lengths = part_columns
+            .iter()
+            .map(|part| part[0].len())
+            .collect::<Vec<_>>();
+        let num_rows_output = output_columns[0].len();
+        prove_union(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &part_columns,
+            &output_columns,
+            &part_lengths,
+            num_rows_output,
+        );
         Table::try_from_iter_with_options(
-            self.result_idents.iter().cloned().zip_eq(res_columns),
-            TableOptions::new(Some(num_rows_res)),
+            self.result_idents
+                .iter()
+                .cloned()
+                .chain(self.presence_idents())
+                .zip_eq(output_columns),
+            TableOptions::new(Some(num_rows_output)),
         )
         .expect("Can not create table")
     }
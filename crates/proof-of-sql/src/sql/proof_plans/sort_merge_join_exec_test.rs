@@ -1,11 +1,14 @@
-use super::test_utility::*;
+use super::{test_utility::*, DynProofPlan, JoinType};
 use crate::{
-    base::database::{
-        owned_table_utility::*, table_utility::*, ColumnType, TableRef, TableTestAccessor,
-        TestAccessor,
+    base::{
+        database::{
+            owned_table_utility::*, table_utility::*, ColumnType, TableRef, TableTestAccessor,
+            TestAccessor,
+        },
+        proof::ProofError,
     },
     sql::{
-        proof::{exercise_verification, VerifiableQueryResult},
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
         proof_exprs::test_utility::*,
     },
 };
@@ -445,3 +448,278 @@ fn we_can_prove_and_get_the_correct_empty_result_from_a_sort_merge_join_if_one_o
     ]);
     assert_eq!(res, expected_res);
 }
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_sort_merge_join_on_multiple_columns() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let orders = table([
+        borrowed_int("tenant_id", [1_i32, 1, 2, 2, -3], &alloc),
+        borrowed_bigint("order_id", [10_i64, -20, 10, 20, 10], &alloc),
+        borrowed_bigint("amount", [5_i64, 6, 7, 8, 9], &alloc),
+    ]);
+    let table_orders: TableRef = "sxt.orders".parse().unwrap();
+    let shipments = table([
+        borrowed_bigint("order_id", [10_i64, -20, -20, 30, 20, 10], &alloc),
+        borrowed_int("tenant_id", [2_i32, 1, 1, 3, 2, 1], &alloc),
+        borrowed_varchar(
+            "carrier",
+            ["UPS", "DHL", "FedEx", "UPS", "DHL", "USPS"],
+            &alloc,
+        ),
+    ]);
+    let table_shipments: TableRef = "sxt.shipments".parse().unwrap();
+    accessor.add_table(table_orders.clone(), orders, 0);
+    accessor.add_table(table_shipments.clone(), shipments, 0);
+    let ast = sort_merge_join(
+        table_exec(
+            table_orders.clone(),
+            vec![
+                column_field("tenant_id", ColumnType::Int),
+                column_field("order_id", ColumnType::BigInt),
+                column_field("amount", ColumnType::BigInt),
+            ],
+        ),
+        table_exec(
+            table_shipments.clone(),
+            vec![
+                column_field("order_id", ColumnType::BigInt),
+                column_field("tenant_id", ColumnType::Int),
+                column_field("carrier", ColumnType::VarChar),
+            ],
+        ),
+        vec![0, 1],
+        vec![1, 0],
+        vec![
+            Ident::new("tenant_id"),
+            Ident::new("order_id"),
+            Ident::new("amount"),
+            Ident::new("carrier"),
+        ],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_orders);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("tenant_id", [1_i32, 1, 1, 2, 2]),
+        bigint("order_id", [10_i64, -20, -20, 10, 20]),
+        bigint("amount", [5_i64, 6, 6, 7, 8]),
+        varchar("carrier", ["USPS", "DHL", "FedEx", "UPS", "DHL"]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_sort_merge_join_on_multiple_columns_wider_than_64_bits() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_int128("a", [1_i128, 2], &alloc),
+        borrowed_bigint("b", [1_i64, 2], &alloc),
+    ]);
+    let table_left: TableRef = "sxt.left".parse().unwrap();
+    let right = table([
+        borrowed_int128("a", [1_i128, 2], &alloc),
+        borrowed_bigint("b", [1_i64, 3], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.right".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let ast = sort_merge_join(
+        table_exec(
+            table_left.clone(),
+            vec![
+                column_field("a", ColumnType::Int128),
+                column_field("b", ColumnType::BigInt),
+            ],
+        ),
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("a", ColumnType::Int128),
+                column_field("b", ColumnType::BigInt),
+            ],
+        ),
+        vec![0, 1],
+        vec![0, 1],
+        vec![Ident::new("a"), Ident::new("b")],
+    );
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError { .. }
+        })
+    ));
+}
+
+fn cats_and_cat_details(
+    alloc: &Bump,
+) -> (TableTestAccessor<InnerProductProof>, TableRef, TableRef) {
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 2, 3, 4, 5], alloc),
+        borrowed_varchar(
+            "name",
+            ["Chloe", "Margaret", "Prudence", "Lucy", "Pepper"],
+            alloc,
+        ),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_bigint("id", [1_i64, 2, 98, 4, 1, 2, 7], alloc),
+        borrowed_varchar(
+            "human",
+            ["Cassia", "Cassia", "Gretta", "Gretta", "Ian", "Ian", "Erik"],
+            alloc,
+        ),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    (accessor, table_left, table_right)
+}
+
+fn cats_outer_join(
+    table_left: &TableRef,
+    table_right: &TableRef,
+    join_type: JoinType,
+) -> DynProofPlan {
+    outer_join(
+        table_exec(
+            table_left.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("name", ColumnType::VarChar),
+            ],
+        ),
+        table_exec(
+            table_right.clone(),
+            vec![
+                column_field("id", ColumnType::BigInt),
+                column_field("human", ColumnType::VarChar),
+            ],
+        ),
+        vec![0],
+        vec![0],
+        vec![Ident::new("id"), Ident::new("name"), Ident::new("human")],
+        join_type,
+    )
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_left_join() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) = cats_and_cat_details(&alloc);
+    let ast = cats_outer_join(&table_left, &table_right, JoinType::Left);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 1, 2, 2, 4, 3, 5]),
+        varchar(
+            "name",
+            [
+                "Chloe", "Chloe", "Margaret", "Margaret", "Lucy", "Prudence", "Pepper",
+            ],
+        ),
+        varchar(
+            "human",
+            ["Cassia", "Ian", "Cassia", "Ian", "Gretta", "", ""],
+        ),
+        presence("human", [true, true, true, true, true, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_right_join() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) = cats_and_cat_details(&alloc);
+    let ast = cats_outer_join(&table_left, &table_right, JoinType::Right);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 1, 2, 2, 4, 98, 7]),
+        varchar(
+            "name",
+            ["Chloe", "Chloe", "Margaret", "Margaret", "Lucy", "", ""],
+        ),
+        varchar(
+            "human",
+            ["Cassia", "Ian", "Cassia", "Ian", "Gretta", "Gretta", "Erik"],
+        ),
+        presence("name", [true, true, true, true, true, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_full_join() {
+    let alloc = Bump::new();
+    let (accessor, table_left, table_right) = cats_and_cat_details(&alloc);
+    let ast = cats_outer_join(&table_left, &table_right, JoinType::Full);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 1, 2, 2, 4, 3, 5, 98, 7]),
+        varchar(
+            "name",
+            [
+                "Chloe", "Chloe", "Margaret", "Margaret", "Lucy", "Prudence", "Pepper", "", "",
+            ],
+        ),
+        varchar(
+            "human",
+            [
+                "Cassia", "Ian", "Cassia", "Ian", "Gretta", "", "", "Gretta", "Erik",
+            ],
+        ),
+        presence(
+            "name",
+            [true, true, true, true, true, true, true, false, false],
+        ),
+        presence(
+            "human",
+            [true, true, true, true, true, false, false, true, true],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_and_get_the_correct_result_from_a_left_join_if_the_right_table_has_no_rows() {
+    let alloc = Bump::new();
+    let mut accessor = TableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let left = table([
+        borrowed_bigint("id", [1_i64, 2, 2], &alloc),
+        borrowed_varchar("name", ["Chloe", "Margaret", "Prudence"], &alloc),
+    ]);
+    let table_left: TableRef = "sxt.cats".parse().unwrap();
+    let right = table([
+        borrowed_bigint("id", [0_i64; 0], &alloc),
+        borrowed_varchar("human", [""; 0], &alloc),
+    ]);
+    let table_right: TableRef = "sxt.cat_details".parse().unwrap();
+    accessor.add_table(table_left.clone(), left, 0);
+    accessor.add_table(table_right.clone(), right, 0);
+    let ast = cats_outer_join(&table_left, &table_right, JoinType::Left);
+    let verifiable_res: VerifiableQueryResult<InnerProductProof> =
+        VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, &table_left);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("id", [1_i64, 2, 2]),
+        varchar("name", ["Chloe", "Margaret", "Prudence"]),
+        varchar("human", ["", "", ""]),
+        presence("human", [false, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
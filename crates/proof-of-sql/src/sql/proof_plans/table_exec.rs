@@ -5,8 +5,11 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{
-        FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+    sql::{
+        proof::{
+            FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder,
+        },
+        proof_exprs::{DynProofExpr, ProofExpr},
     },
     utils::log,
 };
@@ -31,6 +34,22 @@ impl TableExec {
     pub fn new(table_ref: TableRef, schema: Vec<ColumnField>) -> Self {
         Self { table_ref, schema }
     }
+
+    /// Creates a new [`TableExec`] loading exactly the columns referenced by the expressions.
+    pub(super) fn from_referenced_columns<'a>(
+        table_ref: TableRef,
+        exprs: impl IntoIterator<Item = &'a DynProofExpr>,
+    ) -> Self {
+        let mut columns = IndexSet::default();
+        for expr in exprs {
+            expr.get_column_references(&mut columns);
+        }
+        let schema = columns
+            .into_iter()
+            .map(|column_ref| ColumnField::new(column_ref.column_id(), *column_ref.column_type()))
+            .collect();
+        Self::new(table_ref, schema)
+    }
 }
 
 impl ProofPlan for TableExec {
@@ -1,10 +1,10 @@
 use super::{
-    DynProofPlan, EmptyExec, FilterExec, GroupByExec, ProjectionExec, SliceExec, SortMergeJoinExec,
-    TableExec, UnionExec,
+    DistinctExec, DynProofPlan, EmptyExec, FilterExec, GroupByExec, JoinType, ProjectionExec,
+    SemiJoinExec, SliceExec, SortExec, SortMergeJoinExec, TableExec, UnionExec,
 };
 use crate::{
     base::database::{ColumnField, ColumnType, TableRef},
-    sql::proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
+    sql::proof_exprs::{AliasedDynProofExpr, DynProofExpr, TableExpr},
 };
 use sqlparser::ast::Ident;
 
@@ -32,12 +32,47 @@ pub fn filter(
     DynProofPlan::Filter(FilterExec::new(results, table, where_clause))
 }
 
+pub fn filter_with_input(
+    results: Vec<AliasedDynProofExpr>,
+    input: DynProofPlan,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::Filter(FilterExec::new_with_input(
+        results,
+        Box::new(input),
+        where_clause,
+    ))
+}
+
 /// # Panics
 ///
 /// Will panic if `count_alias` cannot be parsed as a valid identifier.
 pub fn group_by(
-    group_by_exprs: Vec<ColumnExpr>,
+    group_by_exprs: Vec<AliasedDynProofExpr>,
+    sum_expr: Vec<AliasedDynProofExpr>,
+    count_alias: &str,
+    table: TableExpr,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::GroupBy(GroupByExec::new(
+        group_by_exprs,
+        sum_expr,
+        vec![],
+        vec![],
+        count_alias.into(),
+        table,
+        where_clause,
+    ))
+}
+
+/// # Panics
+///
+/// Will panic if `count_alias` cannot be parsed as a valid identifier.
+pub fn group_by_with_max_min(
+    group_by_exprs: Vec<AliasedDynProofExpr>,
     sum_expr: Vec<AliasedDynProofExpr>,
+    max_expr: Vec<AliasedDynProofExpr>,
+    min_expr: Vec<AliasedDynProofExpr>,
     count_alias: &str,
     table: TableExpr,
     where_clause: DynProofExpr,
@@ -45,16 +80,47 @@ pub fn group_by(
     DynProofPlan::GroupBy(GroupByExec::new(
         group_by_exprs,
         sum_expr,
+        max_expr,
+        min_expr,
         count_alias.into(),
         table,
         where_clause,
     ))
 }
 
+/// # Panics
+///
+/// Will panic if `count_alias` cannot be parsed as a valid identifier.
+pub fn group_by_with_input(
+    group_by_exprs: Vec<AliasedDynProofExpr>,
+    sum_expr: Vec<AliasedDynProofExpr>,
+    count_alias: &str,
+    input: DynProofPlan,
+    where_clause: DynProofExpr,
+) -> DynProofPlan {
+    DynProofPlan::GroupBy(GroupByExec::new_with_input(
+        group_by_exprs,
+        sum_expr,
+        vec![],
+        vec![],
+        count_alias.into(),
+        Box::new(input),
+        where_clause,
+    ))
+}
+
 pub fn slice_exec(input: DynProofPlan, skip: usize, fetch: Option<usize>) -> DynProofPlan {
     DynProofPlan::Slice(SliceExec::new(Box::new(input), skip, fetch))
 }
 
+pub fn sort_exec(input: DynProofPlan, order_by: Vec<(usize, bool)>) -> DynProofPlan {
+    DynProofPlan::Sort(SortExec::new(Box::new(input), order_by))
+}
+
+pub fn distinct_exec(input: DynProofPlan) -> DynProofPlan {
+    DynProofPlan::Distinct(DistinctExec::new(Box::new(input)))
+}
+
 pub fn union_exec(inputs: Vec<DynProofPlan>, schema: Vec<ColumnField>) -> DynProofPlan {
     DynProofPlan::Union(UnionExec::new(inputs, schema))
 }
@@ -74,3 +140,51 @@ pub fn sort_merge_join(
         result_idents,
     ))
 }
+
+pub fn outer_join(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+    result_idents: Vec<Ident>,
+    join_type: JoinType,
+) -> DynProofPlan {
+    DynProofPlan::SortMergeJoin(SortMergeJoinExec::new_with_join_type(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_indexes,
+        right_join_column_indexes,
+        result_idents,
+        join_type,
+    ))
+}
+
+pub fn semi_join(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+) -> DynProofPlan {
+    DynProofPlan::SemiJoin(SemiJoinExec::new(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_indexes,
+        right_join_column_indexes,
+        false,
+    ))
+}
+
+pub fn anti_join(
+    left: DynProofPlan,
+    right: DynProofPlan,
+    left_join_column_indexes: Vec<usize>,
+    right_join_column_indexes: Vec<usize>,
+) -> DynProofPlan {
+    DynProofPlan::SemiJoin(SemiJoinExec::new(
+        Box::new(left),
+        Box::new(right),
+        left_join_column_indexes,
+        right_join_column_indexes,
+        true,
+    ))
+}
@@ -32,8 +32,8 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct UnionExec {
-    pub(super) inputs: Vec<DynProofPlan>,
-    pub(super) schema: Vec<ColumnField>,
+    pub(crate) inputs: Vec<DynProofPlan>,
+    pub(crate) schema: Vec<ColumnField>,
 }
 
 impl UnionExec {
@@ -170,7 +170,7 @@ impl ProverEvaluate for UnionExec {
 /// # Panics
 /// Should never panic if the code is correct.
 #[allow(clippy::too_many_arguments)]
-fn verify_union<S: Scalar>(
+pub(super) fn verify_union<S: Scalar>(
     builder: &mut impl VerificationBuilder<S>,
     gamma: S,
     beta: S,
@@ -224,7 +224,7 @@ fn verify_union<S: Scalar>(
 /// # Panics
 /// Should never panic if the code is correct.
 #[allow(clippy::too_many_arguments)]
-fn prove_union<'a, S: Scalar + 'a>(
+pub(super) fn prove_union<'a, S: Scalar + 'a>(
     builder: &mut FinalRoundBuilder<'a, S>,
     alloc: &'a Bump,
     gamma: S,
@@ -548,6 +548,157 @@ fn we_can_prove_a_complex_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_filter_query_on_nullable_columns_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        nullable_owned_table(
+            [bigint("a", [1, 0, 3, 4, 0]), bigint("b", [1, 2, 3, 4, 5])],
+            [("a".into(), vec![true, false, true, true, false])],
+        ),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, b, a IS NULL as n FROM table WHERE a > 1 OR b = 2"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    let verifiable_result =
+        VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [0, 3, 4]),
+        presence("a", [false, true, true]),
+        bigint("b", [2, 3, 4]),
+        boolean("n", [true, false, false]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_group_by_query_on_nullable_columns_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        nullable_owned_table(
+            [bigint("a", [1, 0, 3, 0, 5]), bigint("b", [1, 1, 2, 2, 3])],
+            [("a".into(), vec![true, false, true, false, true])],
+        ),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT b, sum(a) as s, count(a) as ca, count(b) as cb FROM table group by b"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result =
+        VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result: OwnedTable<Curve25519Scalar> = verifiable_result
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<Curve25519Scalar> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("b", [1_i64, 2, 3]),
+        bigint("s", [1_i64, 3, 5]),
+        bigint("ca", [1_i64, 1, 1]),
+        bigint("cb", [2_i64, 2, 1]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_null_aware_aggregations_without_postprocessing_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        TableRef::new("sxt", "table"),
+        nullable_owned_table(
+            [bigint("a", [1, 0, 3, 0, 5]), bigint("b", [1, 1, 2, 2, 3])],
+            [("a".into(), vec![true, false, true, false, true])],
+        ),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT b, sum(a + 1) as s, count(a) as ca FROM table WHERE b < 3 group by b"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result =
+        VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("b", [1_i64, 2]),
+        bigint("s", [2_i64, 4]),
+        bigint("ca", [1_i64, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_group_by_of_a_join_with_nullable_columns_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        TableRef::new("sxt", "orders"),
+        nullable_owned_table(
+            [
+                bigint("customer_id", [1, 1, 2, 3]),
+                bigint("amount", [10, 0, 5, 7]),
+            ],
+            [("amount".into(), vec![true, false, true, true])],
+        ),
+        0,
+    );
+    accessor.add_table(
+        TableRef::new("sxt", "customers"),
+        owned_table([bigint("id", [1, 2])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT customer_id, sum(amount) as total, count(amount) as num_amounts FROM orders JOIN customers ON customer_id = customers.id GROUP BY customer_id"
+            .parse()
+            .unwrap(),
+        "sxt".into(),
+        &accessor,
+    )
+    .unwrap();
+    assert!(query.postprocessing().is_empty());
+    let verifiable_result =
+        VerifiableQueryResult::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = verifiable_result
+        .verify(query.proof_expr(), &accessor, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("customer_id", [1_i64, 2]),
+        bigint("total", [10_i64, 5]),
+        bigint("num_amounts", [1_i64, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 //TODO: This test uses postprocessing now. Check proof results once PROOF-765 is done.
 #[test]
 #[cfg(feature = "blitzar")]
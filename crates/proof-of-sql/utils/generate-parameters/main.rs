@@ -3,10 +3,14 @@
 #[cfg(test)]
 mod round_trip_test;
 
+#[cfg(feature = "hyperkzg")]
+use ark_serialize::CanonicalSerialize;
 use ark_std::rand::SeedableRng;
 use clap::{Parser, ValueEnum};
 use indicatif::{ProgressBar, ProgressStyle};
 use proof_of_sql::proof_primitive::dory::{ProverSetup, PublicParameters, VerifierSetup};
+#[cfg(feature = "hyperkzg")]
+use proof_of_sql::proof_primitive::hyperkzg::HyperKZGPublicSetup;
 use rand_chacha::ChaCha20Rng;
 use sha2::{Digest, Sha256};
 use std::{
@@ -41,6 +45,21 @@ struct Args {
     /// The directory to store generated files and archives
     #[arg(short, long, default_value = "./output")]
     target: String,
+
+    /// The commitment scheme to generate parameters for
+    #[arg(long, default_value = "dory")]
+    scheme: Scheme,
+}
+
+/// The commitment scheme that parameters are generated for.
+#[derive(Debug, Clone, ValueEnum)]
+enum Scheme {
+    /// Dory, which supports tables of up to `2^(2ν-1)` rows
+    Dory,
+    /// `HyperKZG`, which supports tables of up to `2^ν` rows.
+    /// This is a test setup, since whoever runs the generation learns `τ`.
+    #[value(name = "hyperkzg")]
+    HyperKZG,
 }
 
 // An enum representing possible modes of operation,
@@ -96,6 +115,11 @@ fn generate_parameters(args: &Args) {
         }
     }
 
+    if let Scheme::HyperKZG = args.scheme {
+        generate_hyperkzg_parameters(args);
+        return;
+    }
+
     let mut rng = rng_from_seed(args);
 
     let spinner = spinner(format!(
@@ -124,6 +148,57 @@ fn generate_parameters(args: &Args) {
     }
 }
 
+/// Generates and writes a `HyperKZG` test setup that supports tables of up to `2^nu` rows.
+///
+/// The prover uses the whole public setup, while the verifier only needs `τ·H`,
+/// which is written compressed so that it can be passed to `generate-solidity-verifier`.
+#[cfg(feature = "hyperkzg")]
+fn generate_hyperkzg_parameters(args: &Args) {
+    let (nu, target) = (args.nu, &args.target);
+    let spinner = spinner(format!(
+        "Generating a random HyperKZG test setup with seed {:?} please wait...",
+        args.seed
+    ));
+    let public_setup = HyperKZGPublicSetup::test_rand(1 << nu, &mut rng_from_seed(args));
+    spinner.finish_with_message("HyperKZG public setup complete");
+
+    let mut digests = Vec::new();
+    if let Mode::All | Mode::Prover = args.mode {
+        let public_setup_path = format!("{target}/hyperkzg_public_setup_nu_{nu}.bin");
+        if let Err(e) = public_setup.save_to_file(Path::new(&public_setup_path)) {
+            eprintln!("Failed to save HyperKZG public setup: {e}.");
+            std::process::exit(-1)
+        }
+        println!("HyperKZG public setup saved successfully.");
+        if let Some(digest) = compute_sha256(&public_setup_path) {
+            digests.push((public_setup_path, digest));
+        }
+    }
+    if let Mode::All | Mode::Verifier = args.mode {
+        let tau_h_path = format!("{target}/hyperkzg_tau_h_nu_{nu}.bin");
+        let mut tau_h = Vec::new();
+        public_setup
+            .tau_h()
+            .serialize_compressed(&mut tau_h)
+            .expect("serializing to a vector does not fail");
+        if let Err(e) = fs::write(&tau_h_path, tau_h) {
+            eprintln!("Failed to save HyperKZG verifier key: {e}.");
+            std::process::exit(-1)
+        }
+        println!("HyperKZG verifier key saved successfully.");
+        if let Some(digest) = compute_sha256(&tau_h_path) {
+            digests.push((tau_h_path, digest));
+        }
+    }
+    save_digests(&digests, target, nu);
+}
+
+#[cfg(not(feature = "hyperkzg"))]
+fn generate_hyperkzg_parameters(_args: &Args) {
+    eprintln!("HyperKZG parameters require the `hyperkzg` feature.");
+    std::process::exit(-1)
+}
+
 /// # Panics
 /// expects that a [u8; 32] always contains 32 elements, guaranteed not to panic
 fn rng_from_seed(args: &Args) -> ChaCha20Rng {
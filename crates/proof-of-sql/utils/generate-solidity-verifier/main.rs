@@ -0,0 +1,100 @@
+//! Utility to generate a Solidity verifier that is specialized to a single plan and a single `HyperKZG` setup.
+use ark_bn254::G2Affine;
+use ark_serialize::CanonicalDeserialize;
+use clap::Parser;
+use proof_of_sql::sql::evm_proof_plan::{
+    generate_solidity_verifier, EVMProofPlan, SolidityVerifierError,
+};
+use snafu::Snafu;
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// The plan, encoded the way the EVM verifier consumes it
+    #[arg(short, long)]
+    plan: PathBuf,
+
+    /// `τ·H` of the `HyperKZG` verifier key, as a compressed `ark-bn254` G2 point
+    #[arg(short, long)]
+    verifier_key: PathBuf,
+
+    /// The `solidity/src` directory that the verifier is assembled from
+    #[arg(long, default_value = "solidity/src")]
+    solidity_src: PathBuf,
+
+    /// Output file (defaults to None which is stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Snafu)]
+enum GenerateSolidityVerifierError {
+    #[snafu(display("Failed to read from input file '{:?}'", filename))]
+    ReadInputFile { filename: PathBuf },
+
+    #[snafu(display("Failed to deserialize plan"))]
+    PlanDeserialization,
+
+    #[snafu(display("Failed to deserialize verifier key"))]
+    VerifierKeyDeserialization,
+
+    #[snafu(display("Failed to generate verifier: {source}"))]
+    Generation { source: SolidityVerifierError },
+
+    #[snafu(display("Failed to write to output file '{:?}'", filename))]
+    WriteOutputFile { filename: PathBuf },
+
+    #[snafu(display("Failed to write to stdout"))]
+    WriteStdout,
+}
+
+type GenerateSolidityVerifierResult<T, E = GenerateSolidityVerifierError> =
+    std::result::Result<T, E>;
+
+fn read_input_file(filename: &Path) -> GenerateSolidityVerifierResult<Vec<u8>> {
+    fs::read(filename).map_err(|_| GenerateSolidityVerifierError::ReadInputFile {
+        filename: filename.to_path_buf(),
+    })
+}
+
+fn main() -> GenerateSolidityVerifierResult<()> {
+    let cli = Cli::parse();
+
+    let plan_bytes = read_input_file(&cli.plan)?;
+    let (plan, num_bytes): (EVMProofPlan, _) = bincode::serde::decode_from_slice(
+        &plan_bytes,
+        bincode::config::legacy()
+            .with_fixed_int_encoding()
+            .with_big_endian(),
+    )
+    .map_err(|_| GenerateSolidityVerifierError::PlanDeserialization)?;
+    if num_bytes != plan_bytes.len() {
+        return Err(GenerateSolidityVerifierError::PlanDeserialization);
+    }
+
+    let tau_h = G2Affine::deserialize_compressed(&*read_input_file(&cli.verifier_key)?)
+        .map_err(|_| GenerateSolidityVerifierError::VerifierKeyDeserialization)?;
+
+    let verifier = generate_solidity_verifier(&plan, &tau_h, |path| {
+        fs::read_to_string(cli.solidity_src.join(path)).ok()
+    })
+    .map_err(|source| GenerateSolidityVerifierError::Generation { source })?;
+
+    match &cli.output {
+        Some(output_file) => fs::write(output_file, verifier).map_err(|_| {
+            GenerateSolidityVerifierError::WriteOutputFile {
+                filename: output_file.clone(),
+            }
+        })?,
+        None => io::stdout()
+            .write_all(verifier.as_bytes())
+            .map_err(|_| GenerateSolidityVerifierError::WriteStdout)?,
+    }
+
+    Ok(())
+}